@@ -0,0 +1,2779 @@
+//! Parses and validates the runtime configuration of spv
+
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use std::{env, fs, iter};
+
+use clap::Parser;
+use log::LevelFilter;
+use regex::Regex;
+use thiserror::Error;
+use tui::style::Color;
+
+use crate::core::command::CommandTemplate;
+use crate::core::external::ExternalProbeSpec;
+use crate::core::labels::LabelRule;
+use crate::core::watch::WatchExpression;
+
+const DEFAULT_REFRESH_PERIOD: Duration = Duration::from_secs(1);
+const DEFAULT_SPAN_DURATION: Duration = Duration::from_secs(60);
+const DEFAULT_SAMPLE_PERIOD: Duration = Duration::from_millis(200);
+const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::Debug;
+const DEFAULT_LOG_FILE: &str = "spv.log";
+const DEFAULT_PROC_ROOT: &str = "/proc";
+/// The highest amount of decimals with which metrics can be represented, as their column is narrow
+const MAX_PRECISION: usize = 4;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ConfigError {
+    #[error("Invalid value '{1}' for {0}: {2}")]
+    InvalidValue(&'static str, String, String),
+    #[error("{0} conflicts with {1}")]
+    ConflictingOptions(&'static str, &'static str),
+    #[error("Could not read configuration file {0}: {1}")]
+    UnreadableFile(PathBuf, String),
+    #[error("Invalid arguments: {0}")]
+    InvalidArguments(String),
+    #[error("{0} can not change while spv runs")]
+    NotReloadable(&'static str),
+}
+
+/// Command line arguments of spv
+#[derive(Parser, Debug, Default)]
+#[command(version, about, args_override_self = true)]
+pub struct Arguments {
+    /// Time between two collections of metrics (e.g. `500ms`, `2s`)
+    #[arg(short, long, value_name = "DURATION")]
+    pub refresh_period: Option<String>,
+
+    /// Comma-separated list of the probes to enable (cpu, child-count, children-cpu, cpu-pressure, diskio, disk-wait,
+    /// fd-limit, gpu (with the gpu feature), iops, io-pressure, lifetime-cpu, logical-io, major-faults, mapped-files,
+    /// memory, memory-growth, memory-pressure, memory-segments, memory-volatility, oom-score, power, protocol-io,
+    /// sched-latency, sockets, socket-states, thermal, yields)
+    #[arg(short, long, value_name = "PROBES", value_delimiter = ',')]
+    pub probes: Option<Vec<String>>,
+
+    /// Comma-separated list of enabled probes whose latest metrics are displayed in additional columns of the process
+    /// list, next to the metric of the current tab
+    #[arg(long, value_name = "PROBES", value_delimiter = ',')]
+    pub combine: Option<Vec<String>>,
+
+    /// Comma-separated list of the probes sampled several times per refresh period, whose displayed metrics are the
+    /// highest of their samples, to reveal short spikes
+    #[arg(long, value_name = "PROBES", value_delimiter = ',')]
+    pub subsample: Option<Vec<String>>,
+
+    /// How often the subsampled probes are sampled (e.g. 200ms), which must be shorter than the refresh period
+    #[arg(long, value_name = "DURATION")]
+    pub sample_period: Option<String>,
+
+    /// Comma-separated list of the amount of decimals with which the metrics of probes are displayed, overriding
+    /// their default precision (e.g. `cpu=2,memory=0`)
+    #[arg(long, value_name = "PROBE=DECIMALS", value_delimiter = ',')]
+    pub precision: Option<Vec<String>>,
+
+    /// Labels the processes whose command line matches a regular expression (e.g. `java .*kafka=kafka`). Can be
+    /// repeated, in which case the first matching label prevails
+    #[arg(long, value_name = "PATTERN=LABEL")]
+    pub label: Option<Vec<String>>,
+
+    /// Adds a tab displaying the values printed by a script on each iteration, as `PID VALUE` lines (e.g.
+    /// `queue:/usr/local/bin/queue-depth`). Can be repeated to add several tabs
+    #[arg(long, value_name = "NAME:PATH")]
+    pub external_probe: Option<Vec<String>>,
+
+    /// Maximum level of the logged messages (off, error, warn, info, debug)
+    #[arg(long, value_name = "LEVEL")]
+    pub log_level: Option<String>,
+
+    /// File in which logs are appended
+    #[arg(long, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// How long metrics are retained (e.g. `10m`, `1h`). By default, metrics are retained indefinitely
+    #[arg(long, value_name = "DURATION")]
+    pub history: Option<String>,
+
+    /// Merges the metrics older than this duration (e.g. `5m`) into coarser buckets, keeping the highest metric of each
+    /// bucket. By default, all the metrics are retained as collected
+    #[arg(long, value_name = "DURATION")]
+    pub compact_after: Option<String>,
+
+    /// Hides the processes which have been running for less than this duration (e.g. `5s`)
+    #[arg(long, value_name = "DURATION")]
+    pub min_uptime: Option<String>,
+
+    /// Keeps processes sorted by their metrics in their previous order until the metric of a process exceeds the one
+    /// of the process preceding it by more than this percentage (e.g. `10`), so that the list does not jitter
+    #[arg(long, value_name = "PERCENT")]
+    pub sort_hysteresis: Option<String>,
+
+    /// How spv lists its own process: show it as any other process, hide it, or highlight it to tell it apart
+    #[arg(long, value_name = "show|hide|highlight")]
+    pub own_process: Option<String>,
+
+    /// Highlights the processes whose latest metrics satisfy a condition on the enabled probes, regardless of their
+    /// position in the list (e.g. `cpu > 50 OR memory > 1G`)
+    #[arg(long, value_name = "EXPRESSION")]
+    pub watch: Option<String>,
+
+    /// Pins the processes whose latest metrics satisfy a condition on the enabled probes to the top of the list,
+    /// where they are sorted among themselves by the applied criteria (e.g. `cpu > 90`)
+    #[arg(long, value_name = "EXPRESSION")]
+    pub pin: Option<String>,
+
+    /// Command run on the selected process when pressing `e`, in which `{pid}` and `{command}` are replaced by the PID
+    /// and the command of the process (e.g. `strace -p {pid}`). The command is not run by a shell
+    #[arg(long, value_name = "COMMAND")]
+    pub exec: Option<String>,
+
+    /// Only monitors the processes whose command is this name when spv starts (e.g. `nginx`), along with the
+    /// processes they spawn afterwards
+    #[arg(long, value_name = "NAME")]
+    pub service: Option<String>,
+
+    /// Directory where the proc filesystem is mounted (e.g. the proc of another namespace)
+    #[arg(long, value_name = "PATH")]
+    pub proc_root: Option<PathBuf>,
+
+    /// Draws the chart with ASCII characters only, for terminals which can not render unicode
+    #[arg(long)]
+    pub ascii: bool,
+
+    /// Comma-separated list of the colors with which the components of metrics are drawn in the chart (black, red,
+    /// green, yellow, blue, magenta, cyan, gray, white), cycled through if a metric has more components
+    #[arg(long, value_name = "COLORS", value_delimiter = ',')]
+    pub chart_colors: Option<Vec<String>>,
+
+    /// Draws the newest metrics on the left of the chart, and the oldest on the right
+    #[arg(long)]
+    pub newest_left: bool,
+
+    /// Rounds the labels of the y-axis of the chart to steps of 1, 2 or 5 times a power of 10, extending the axis to
+    /// the nearest of these steps
+    #[arg(long)]
+    pub nice_y_axis: bool,
+
+    /// Adds the hugetlb memory of processes to the footprint reported by the memory probe
+    #[arg(long)]
+    pub hugepages: bool,
+
+    /// Shows the cumulative CPU time of processes in a TIME+ column, as top does
+    #[arg(long)]
+    pub cpu_time: bool,
+
+    /// Comma-separated list of the columns of the process list, in the order in which they are displayed (command,
+    /// pid, time, uptime, state, metric, or the name of an enabled probe). `metric` is the metric of the current tab.
+    /// Replaces the columns set by --combine and --cpu-time
+    #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+    pub columns: Option<Vec<String>>,
+
+    /// Replaces the names of processes with generic names such as process-1, e.g. to share screenshots
+    #[arg(long)]
+    pub anonymize: bool,
+
+    /// Lists the tmpfs filesystems, which are stored in memory, in the filesystem pane
+    #[arg(long)]
+    pub tmpfs: bool,
+
+    /// Asks for a confirmation before quitting when q is pressed, e.g. to keep spv running on a shared display
+    #[arg(long)]
+    pub confirm_quit: bool,
+
+    /// Collects metrics only once at startup, to examine a frozen snapshot of the processes with the navigation and
+    /// the sorting of spv
+    #[arg(long = "static")]
+    pub static_snapshot: bool,
+
+    /// File of options, one per line as on the command line (e.g. `--watch cpu > 50`), which the options of the command
+    /// line override. The file is read again when spv receives SIGHUP, to apply the options which can change while spv
+    /// runs (e.g. --watch, --pin, --label, --min-uptime), without losing the collected metrics
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Validates the configuration, prints it and exits without launching the UI
+    #[arg(long)]
+    pub check_config: bool,
+
+    /// Writes the tree of the running processes to a Graphviz DOT file and exits without launching the UI
+    #[arg(long, value_name = "PATH")]
+    pub export_tree: Option<PathBuf>,
+}
+
+/// Reads the configuration from the command line, and from the configuration file it refers to, if any
+///
+/// The configuration can be read again, so that the changes made to the configuration file are taken into account.
+#[derive(Debug, Clone)]
+pub struct ConfigSource {
+    cli_args: Vec<OsString>,
+}
+
+impl ConfigSource {
+    /// Creates a source reading the arguments with which spv was launched
+    pub fn from_env() -> Self {
+        Self::new(env::args_os().collect())
+    }
+
+    /// Creates a source reading the given command line arguments, starting with the name of the program
+    pub fn new(cli_args: Vec<OsString>) -> Self {
+        Self { cli_args }
+    }
+
+    /// Reads the arguments, merging the options of the configuration file with the options of the command line
+    ///
+    /// The options of the command line override the ones of the file, except for the options which can be repeated,
+    /// such as --label, which accumulate.
+    ///
+    /// When the command line asks for the help or the version, or is invalid, the help, the version or the usage is
+    /// printed and the process exits. As the command line does not change while spv runs, this only happens at startup.
+    pub fn read_args(&self) -> Result<Arguments, ConfigError> {
+        let args = Arguments::try_parse_from(&self.cli_args).unwrap_or_else(|e| e.exit());
+        let Some(path) = &args.config else {
+            return Ok(args);
+        };
+
+        let content = fs::read_to_string(path).map_err(|e| ConfigError::UnreadableFile(path.clone(), e.to_string()))?;
+        let file_args = parse_config_file(&content).into_iter().map(OsString::from);
+
+        let mut cli_args = self.cli_args.iter().cloned();
+        let merged_args: Vec<OsString> = iter::once(cli_args.next().unwrap_or_default())
+            .chain(file_args)
+            .chain(cli_args)
+            .collect();
+
+        Arguments::try_parse_from(merged_args).map_err(|e| ConfigError::InvalidArguments(e.to_string()))
+    }
+
+    /// Reads the arguments, then resolves and validates the configuration
+    pub fn load(&self) -> Result<Config, ConfigError> {
+        Config::from_args(&self.read_args()?)
+    }
+
+    /// Reads the configuration again while spv runs, and checks that it can replace the current configuration
+    ///
+    /// # Arguments
+    ///  * `current`: The configuration applied until now, which remains applied if an error is returned
+    pub fn reload(&self, current: &Config) -> Result<Config, ConfigError> {
+        let reloaded = self.load()?;
+        current.check_reloadable(&reloaded)?;
+
+        Ok(reloaded)
+    }
+}
+
+/// The probes that spv can use to collect metrics
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ProbeKind {
+    Cpu,
+    ChildCount,
+    ChildrenCpu,
+    CpuPressure,
+    DiskIO,
+    DiskWait,
+    FdLimit,
+    #[cfg(feature = "gpu")]
+    Gpu,
+    Iops,
+    IoPressure,
+    LifetimeCpu,
+    LogicalIO,
+    MajorFaults,
+    MappedFiles,
+    Memory,
+    MemoryGrowth,
+    MemoryPressure,
+    MemorySegments,
+    MemoryVolatility,
+    OomScore,
+    Power,
+    ProtocolIO,
+    SchedLatency,
+    Sockets,
+    SocketStates,
+    Thermal,
+    Yields,
+    /// The external probe at the given index of the configured external probes
+    External(usize),
+    #[cfg(feature = "netio")]
+    NetIO,
+}
+
+impl ProbeKind {
+    /// The name selecting the probe on the command line
+    ///
+    /// All the external probes share the `external` name, while they are displayed under the name of their spec (see
+    /// [`Config::probe_name`]).
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProbeKind::Cpu => "cpu",
+            ProbeKind::ChildCount => "child-count",
+            ProbeKind::ChildrenCpu => "children-cpu",
+            ProbeKind::CpuPressure => "cpu-pressure",
+            ProbeKind::DiskIO => "diskio",
+            ProbeKind::DiskWait => "disk-wait",
+            ProbeKind::FdLimit => "fd-limit",
+            #[cfg(feature = "gpu")]
+            ProbeKind::Gpu => "gpu",
+            ProbeKind::Iops => "iops",
+            ProbeKind::IoPressure => "io-pressure",
+            ProbeKind::LifetimeCpu => "lifetime-cpu",
+            ProbeKind::LogicalIO => "logical-io",
+            ProbeKind::MajorFaults => "major-faults",
+            ProbeKind::MappedFiles => "mapped-files",
+            ProbeKind::Memory => "memory",
+            ProbeKind::MemoryGrowth => "memory-growth",
+            ProbeKind::MemoryPressure => "memory-pressure",
+            ProbeKind::MemorySegments => "memory-segments",
+            ProbeKind::MemoryVolatility => "memory-volatility",
+            ProbeKind::OomScore => "oom-score",
+            ProbeKind::Power => "power",
+            ProbeKind::ProtocolIO => "protocol-io",
+            ProbeKind::SchedLatency => "sched-latency",
+            ProbeKind::Sockets => "sockets",
+            ProbeKind::SocketStates => "socket-states",
+            ProbeKind::Thermal => "thermal",
+            ProbeKind::Yields => "yields",
+            ProbeKind::External(_) => "external",
+            #[cfg(feature = "netio")]
+            ProbeKind::NetIO => "netio",
+        }
+    }
+
+    fn all() -> Vec<ProbeKind> {
+        let mut probes = Self::defaults();
+        probes.push(ProbeKind::ChildCount);
+        probes.push(ProbeKind::ChildrenCpu);
+        probes.push(ProbeKind::CpuPressure);
+        probes.push(ProbeKind::DiskWait);
+        probes.push(ProbeKind::FdLimit);
+        #[cfg(feature = "gpu")]
+        probes.push(ProbeKind::Gpu);
+        probes.push(ProbeKind::Iops);
+        probes.push(ProbeKind::IoPressure);
+        probes.push(ProbeKind::LifetimeCpu);
+        probes.push(ProbeKind::LogicalIO);
+        probes.push(ProbeKind::MajorFaults);
+        probes.push(ProbeKind::MappedFiles);
+        probes.push(ProbeKind::Memory);
+        probes.push(ProbeKind::MemoryGrowth);
+        probes.push(ProbeKind::MemoryPressure);
+        probes.push(ProbeKind::MemorySegments);
+        probes.push(ProbeKind::MemoryVolatility);
+        probes.push(ProbeKind::OomScore);
+        probes.push(ProbeKind::Power);
+        probes.push(ProbeKind::ProtocolIO);
+        probes.push(ProbeKind::SchedLatency);
+        probes.push(ProbeKind::Sockets);
+        probes.push(ProbeKind::SocketStates);
+        probes.push(ProbeKind::Thermal);
+        probes.push(ProbeKind::Yields);
+        probes
+    }
+
+    /// The probes enabled when none are explicitly selected
+    fn defaults() -> Vec<ProbeKind> {
+        vec![
+            ProbeKind::Cpu,
+            ProbeKind::DiskIO,
+            #[cfg(feature = "netio")]
+            ProbeKind::NetIO,
+        ]
+    }
+}
+
+impl FromStr for ProbeKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ProbeKind::all()
+            .into_iter()
+            .find(|kind| kind.name() == s.trim())
+            .ok_or_else(|| "unknown probe".to_string())
+    }
+}
+
+/// The columns which can be displayed in the process list
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ColumnKind {
+    Command,
+    /// The PID or the TGID of processes, as toggled by the user
+    Identifier,
+    /// The cumulative CPU time of processes
+    CpuTime,
+    Uptime,
+    State,
+    /// The latest metrics of the probe of the current tab
+    Metric,
+    /// The latest metrics of the given probe, whichever the current tab
+    Probe(ProbeKind),
+}
+
+impl ColumnKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColumnKind::Command => "command",
+            ColumnKind::Identifier => "pid",
+            ColumnKind::CpuTime => "time",
+            ColumnKind::Uptime => "uptime",
+            ColumnKind::State => "state",
+            ColumnKind::Metric => "metric",
+            ColumnKind::Probe(probe) => probe.name(),
+        }
+    }
+
+    /// The columns displayed when none are explicitly selected
+    ///
+    /// # Arguments
+    ///  * `show_cpu_time`: Whether the TIME+ column is displayed
+    ///  * `combined_probes`: The probes whose metrics are displayed next to the metric of the current tab
+    fn defaults(show_cpu_time: bool, combined_probes: &[ProbeKind]) -> Vec<ColumnKind> {
+        let mut columns = vec![ColumnKind::Command, ColumnKind::Identifier];
+        if show_cpu_time {
+            columns.push(ColumnKind::CpuTime);
+        }
+        columns.push(ColumnKind::Metric);
+        columns.extend(combined_probes.iter().map(|probe| ColumnKind::Probe(*probe)));
+        columns
+    }
+}
+
+impl FromStr for ColumnKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let columns = [
+            ColumnKind::Command,
+            ColumnKind::Identifier,
+            ColumnKind::CpuTime,
+            ColumnKind::Uptime,
+            ColumnKind::State,
+            ColumnKind::Metric,
+        ];
+
+        match columns.into_iter().find(|column| column.name() == s.trim()) {
+            Some(column) => Ok(column),
+            None => s
+                .parse::<ProbeKind>()
+                .map(ColumnKind::Probe)
+                .map_err(|_| "unknown column".to_string()),
+        }
+    }
+}
+
+/// How spv lists its own process
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum OwnProcess {
+    #[default]
+    Show,
+    Hide,
+    Highlight,
+}
+
+impl OwnProcess {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OwnProcess::Show => "show",
+            OwnProcess::Hide => "hide",
+            OwnProcess::Highlight => "highlight",
+        }
+    }
+}
+
+impl FromStr for OwnProcess {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [OwnProcess::Show, OwnProcess::Hide, OwnProcess::Highlight]
+            .into_iter()
+            .find(|own_process| own_process.name() == s.trim())
+            .ok_or_else(|| "expected show, hide or highlight".to_string())
+    }
+}
+
+/// The colors which can be selected with --chart-colors, by name
+const COLOR_NAMES: [(&str, Color); 9] = [
+    ("black", Color::Black),
+    ("red", Color::Red),
+    ("green", Color::Green),
+    ("yellow", Color::Yellow),
+    ("blue", Color::Blue),
+    ("magenta", Color::Magenta),
+    ("cyan", Color::Cyan),
+    ("gray", Color::Gray),
+    ("white", Color::White),
+];
+
+/// Colors used to render spv
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    chart_colors: Vec<Color>,
+}
+
+impl Theme {
+    /// Creates a theme drawing the components of metrics with the given palette, which must not be empty
+    pub fn new(chart_colors: Vec<Color>) -> Self {
+        assert!(
+            !chart_colors.is_empty(),
+            "The chart palette must contain at least one color"
+        );
+        Self { chart_colors }
+    }
+
+    /// The color of the dataset at the given index in the chart
+    ///
+    /// The palette is cycled through if the chart has more datasets than the palette has colors.
+    pub fn chart_color(&self, index: usize) -> Color {
+        self.chart_colors[index % self.chart_colors.len()]
+    }
+}
+
+impl Display for Theme {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<_> = self
+            .chart_colors
+            .iter()
+            .map(|color| {
+                COLOR_NAMES
+                    .iter()
+                    .find(|(_, named)| named == color)
+                    .map_or_else(|| format!("{:?}", color).to_lowercase(), |(name, _)| name.to_string())
+            })
+            .collect();
+
+        write!(f, "{}", names.join(", "))
+    }
+}
+
+/// Returns the color with the given name, among the colors which can be selected with --chart-colors
+fn parse_color(name: &str) -> Result<Color, String> {
+    COLOR_NAMES
+        .iter()
+        .find(|(known, _)| *known == name.trim())
+        .map(|(_, color)| *color)
+        .ok_or_else(|| "unknown color".to_string())
+}
+
+impl Default for Theme {
+    /// Red is left out of the default palette, as it marks the death of processes on the chart
+    fn default() -> Self {
+        Self::new(vec![
+            Color::Blue,
+            Color::Green,
+            Color::Yellow,
+            Color::Magenta,
+            Color::Cyan,
+        ])
+    }
+}
+
+/// The resolved configuration of spv
+///
+/// It is built once on startup and shared by all the components of the application.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    refresh_period: Duration,
+    span_duration: Duration,
+    probes: Vec<ProbeKind>,
+    combined_probes: Vec<ProbeKind>,
+    subsampled_probes: Vec<ProbeKind>,
+    sample_period: Duration,
+    precisions: Vec<(ProbeKind, usize)>,
+    labels: Vec<LabelRule>,
+    external_probes: Vec<ExternalProbeSpec>,
+    log_level: LevelFilter,
+    log_file: PathBuf,
+    theme: Theme,
+    ascii_chart: bool,
+    newest_left: bool,
+    nice_y_axis: bool,
+    include_hugepages: bool,
+    show_cpu_time: bool,
+    columns: Option<Vec<ColumnKind>>,
+    anonymize: bool,
+    include_tmpfs: bool,
+    confirm_quit: bool,
+    static_snapshot: bool,
+    history: Option<Duration>,
+    compact_after: Option<Duration>,
+    min_uptime: Option<Duration>,
+    sort_hysteresis: Option<f64>,
+    own_process: OwnProcess,
+    watch: Option<WatchExpression>,
+    pin: Option<WatchExpression>,
+    exec: Option<CommandTemplate>,
+    service: Option<String>,
+    proc_root: PathBuf,
+}
+
+impl Config {
+    /// Resolves and validates the configuration from the command line arguments
+    pub fn from_args(args: &Arguments) -> Result<Self, ConfigError> {
+        let mut builder = ConfigBuilder::default();
+
+        if let Some(period) = &args.refresh_period {
+            let period = parse_duration(period)
+                .map_err(|reason| ConfigError::InvalidValue("--refresh-period", period.clone(), reason))?;
+            builder = builder.refresh_period(period);
+        }
+
+        if let Some(probes) = &args.probes {
+            let probes = probes
+                .iter()
+                .map(|p| {
+                    p.parse::<ProbeKind>()
+                        .map_err(|reason| ConfigError::InvalidValue("--probes", p.clone(), reason))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            builder = builder.probes(probes);
+        }
+
+        if let Some(combined_probes) = &args.combine {
+            let combined_probes = combined_probes
+                .iter()
+                .map(|p| {
+                    p.parse::<ProbeKind>()
+                        .map_err(|reason| ConfigError::InvalidValue("--combine", p.clone(), reason))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            builder = builder.combined_probes(combined_probes);
+        }
+
+        if let Some(subsampled_probes) = &args.subsample {
+            let subsampled_probes = subsampled_probes
+                .iter()
+                .map(|p| {
+                    p.parse::<ProbeKind>()
+                        .map_err(|reason| ConfigError::InvalidValue("--subsample", p.clone(), reason))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            builder = builder.subsampled_probes(subsampled_probes);
+        }
+
+        if let Some(period) = &args.sample_period {
+            let period = parse_duration(period)
+                .map_err(|reason| ConfigError::InvalidValue("--sample-period", period.clone(), reason))?;
+            builder = builder.sample_period(period);
+        }
+
+        if let Some(precisions) = &args.precision {
+            let precisions = precisions
+                .iter()
+                .map(|p| {
+                    parse_precision(p).map_err(|reason| ConfigError::InvalidValue("--precision", p.clone(), reason))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            builder = builder.precisions(precisions);
+        }
+
+        if let Some(labels) = &args.label {
+            let labels = labels
+                .iter()
+                .map(|l| parse_label(l).map_err(|reason| ConfigError::InvalidValue("--label", l.clone(), reason)))
+                .collect::<Result<Vec<_>, _>>()?;
+            builder = builder.labels(labels);
+        }
+
+        if let Some(external_probes) = &args.external_probe {
+            let external_probes = external_probes
+                .iter()
+                .map(|p| {
+                    p.parse()
+                        .map_err(|reason| ConfigError::InvalidValue("--external-probe", p.clone(), reason))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            builder = builder.external_probes(external_probes);
+        }
+
+        if let Some(level) = &args.log_level {
+            let level = parse_log_level(level)
+                .map_err(|reason| ConfigError::InvalidValue("--log-level", level.clone(), reason))?;
+            builder = builder.log_level(level);
+        }
+
+        if let Some(log_file) = &args.log_file {
+            builder = builder.log_file(log_file.clone());
+        }
+
+        if args.ascii {
+            builder = builder.ascii_chart(true);
+        }
+
+        if let Some(chart_colors) = &args.chart_colors {
+            let chart_colors = chart_colors
+                .iter()
+                .map(|c| {
+                    parse_color(c).map_err(|reason| ConfigError::InvalidValue("--chart-colors", c.clone(), reason))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            builder = builder.theme(Theme::new(chart_colors));
+        }
+
+        if args.newest_left {
+            builder = builder.newest_left(true);
+        }
+
+        if args.nice_y_axis {
+            builder = builder.nice_y_axis(true);
+        }
+
+        if args.hugepages {
+            builder = builder.include_hugepages(true);
+        }
+
+        if args.cpu_time {
+            builder = builder.show_cpu_time(true);
+        }
+
+        if let Some(columns) = &args.columns {
+            let columns = columns
+                .iter()
+                .map(|c| {
+                    c.parse::<ColumnKind>()
+                        .map_err(|reason| ConfigError::InvalidValue("--columns", c.clone(), reason))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            builder = builder.columns(columns);
+        }
+
+        if args.anonymize {
+            builder = builder.anonymize(true);
+        }
+
+        if args.tmpfs {
+            builder = builder.include_tmpfs(true);
+        }
+
+        if args.confirm_quit {
+            builder = builder.confirm_quit(true);
+        }
+
+        if args.static_snapshot {
+            builder = builder.static_snapshot(true);
+        }
+
+        if let Some(history) = &args.history {
+            let history = parse_duration(history)
+                .map_err(|reason| ConfigError::InvalidValue("--history", history.clone(), reason))?;
+            builder = builder.history(history);
+        }
+
+        if let Some(compact_after) = &args.compact_after {
+            let compact_after = parse_duration(compact_after)
+                .map_err(|reason| ConfigError::InvalidValue("--compact-after", compact_after.clone(), reason))?;
+            builder = builder.compact_after(compact_after);
+        }
+
+        if let Some(min_uptime) = &args.min_uptime {
+            let min_uptime = parse_duration(min_uptime)
+                .map_err(|reason| ConfigError::InvalidValue("--min-uptime", min_uptime.clone(), reason))?;
+            builder = builder.min_uptime(min_uptime);
+        }
+
+        if let Some(hysteresis) = &args.sort_hysteresis {
+            let hysteresis = parse_percentage(hysteresis)
+                .map_err(|reason| ConfigError::InvalidValue("--sort-hysteresis", hysteresis.clone(), reason))?;
+            builder = builder.sort_hysteresis(hysteresis);
+        }
+
+        if let Some(own_process) = &args.own_process {
+            let own_process = own_process
+                .parse()
+                .map_err(|reason| ConfigError::InvalidValue("--own-process", own_process.clone(), reason))?;
+            builder = builder.own_process(own_process);
+        }
+
+        if let Some(watch) = &args.watch {
+            let expression = watch
+                .parse::<WatchExpression>()
+                .map_err(|reason| ConfigError::InvalidValue("--watch", watch.clone(), reason))?;
+            builder = builder.watch(expression);
+        }
+
+        if let Some(pin) = &args.pin {
+            let expression = pin
+                .parse::<WatchExpression>()
+                .map_err(|reason| ConfigError::InvalidValue("--pin", pin.clone(), reason))?;
+            builder = builder.pin(expression);
+        }
+
+        if let Some(exec) = &args.exec {
+            let template = exec
+                .parse::<CommandTemplate>()
+                .map_err(|reason| ConfigError::InvalidValue("--exec", exec.clone(), reason))?;
+            builder = builder.exec(template);
+        }
+
+        if let Some(service) = &args.service {
+            if service.is_empty() {
+                let reason = "the name of the service can not be empty".to_string();
+                return Err(ConfigError::InvalidValue("--service", service.clone(), reason));
+            }
+            builder = builder.service(service.clone());
+        }
+
+        if let Some(proc_root) = &args.proc_root {
+            builder = builder.proc_root(proc_root.clone());
+        }
+
+        builder.build()
+    }
+
+    /// Checks that the given configuration, read again while spv runs, only changes the options applied live
+    ///
+    /// The other options determine which metrics are collected and how they are displayed, so that spv has to be
+    /// restarted to change them.
+    pub fn check_reloadable(&self, reloaded: &Config) -> Result<(), ConfigError> {
+        let fixed_options = [
+            ("--refresh-period", self.refresh_period == reloaded.refresh_period),
+            ("--probes", self.probes == reloaded.probes),
+            ("--external-probe", self.external_probes == reloaded.external_probes),
+            ("--combine", self.combined_probes == reloaded.combined_probes),
+            ("--subsample", self.subsampled_probes == reloaded.subsampled_probes),
+            ("--sample-period", self.sample_period == reloaded.sample_period),
+            ("--precision", self.precisions == reloaded.precisions),
+            ("--log-level", self.log_level == reloaded.log_level),
+            ("--log-file", self.log_file == reloaded.log_file),
+            ("--history", self.history == reloaded.history),
+            ("--proc-root", self.proc_root == reloaded.proc_root),
+            ("--ascii", self.ascii_chart == reloaded.ascii_chart),
+            ("--chart-colors", self.theme == reloaded.theme),
+            ("--newest-left", self.newest_left == reloaded.newest_left),
+            ("--nice-y-axis", self.nice_y_axis == reloaded.nice_y_axis),
+            ("--hugepages", self.include_hugepages == reloaded.include_hugepages),
+            ("--cpu-time", self.show_cpu_time == reloaded.show_cpu_time),
+            ("--columns", self.columns == reloaded.columns),
+            ("--anonymize", self.anonymize == reloaded.anonymize),
+            ("--tmpfs", self.include_tmpfs == reloaded.include_tmpfs),
+            ("--confirm-quit", self.confirm_quit == reloaded.confirm_quit),
+            ("--static", self.static_snapshot == reloaded.static_snapshot),
+            ("--service", self.service == reloaded.service),
+        ];
+
+        match fixed_options.into_iter().find(|(_, unchanged)| !unchanged) {
+            Some((option, _)) => Err(ConfigError::NotReloadable(option)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn refresh_period(&self) -> Duration {
+        self.refresh_period
+    }
+
+    /// The duration represented by default in the chart
+    pub fn span_duration(&self) -> Duration {
+        self.span_duration
+    }
+
+    pub fn probes(&self) -> &[ProbeKind] {
+        &self.probes
+    }
+
+    /// The probes whose latest metrics are displayed in additional columns of the process list
+    pub fn combined_probes(&self) -> &[ProbeKind] {
+        &self.combined_probes
+    }
+
+    /// The probes sampled several times per refresh period, whose collected metrics are the highest of their samples
+    pub fn subsampled_probes(&self) -> &[ProbeKind] {
+        &self.subsampled_probes
+    }
+
+    /// How often the subsampled probes are sampled
+    pub fn sample_period(&self) -> Duration {
+        self.sample_period
+    }
+
+    /// How many times the subsampled probes are sampled per refresh period, including the collection of their metrics
+    ///
+    /// Without any subsampled probe, metrics are only collected once per refresh period.
+    pub fn samples_per_refresh(&self) -> u32 {
+        if self.subsampled_probes.is_empty() {
+            return 1;
+        }
+
+        (self.refresh_period.as_secs_f64() / self.sample_period.as_secs_f64()).round() as u32
+    }
+
+    /// The amount of decimals with which the metrics of the given probe are displayed, or `None` if the metrics are
+    /// displayed with their default precision
+    pub fn precision(&self, probe: ProbeKind) -> Option<usize> {
+        self.precisions
+            .iter()
+            .find(|(kind, _)| *kind == probe)
+            .map(|(_, decimals)| *decimals)
+    }
+
+    /// The rules labelling processes according to their command line
+    pub fn labels(&self) -> &[LabelRule] {
+        &self.labels
+    }
+
+    /// The external probes, whose metrics are printed by scripts
+    pub fn external_probes(&self) -> &[ExternalProbeSpec] {
+        &self.external_probes
+    }
+
+    /// The name of the given probe, which for an external probe is the name given to its script
+    pub fn probe_name(&self, probe: ProbeKind) -> &str {
+        match probe {
+            ProbeKind::External(index) => self.external_probes[index].name(),
+            _ => probe.name(),
+        }
+    }
+
+    pub fn log_level(&self) -> LevelFilter {
+        self.log_level
+    }
+
+    pub fn log_file(&self) -> &PathBuf {
+        &self.log_file
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Indicates if the chart must only be drawn with ASCII characters
+    pub fn ascii_chart(&self) -> bool {
+        self.ascii_chart
+    }
+
+    /// Indicates if the newest metrics are drawn on the left of the chart
+    pub fn newest_left(&self) -> bool {
+        self.newest_left
+    }
+
+    /// Indicates if the labels of the y-axis of the chart are rounded to human-friendly steps
+    pub fn nice_y_axis(&self) -> bool {
+        self.nice_y_axis
+    }
+
+    /// Indicates if the hugetlb memory of processes is included in their memory footprint
+    pub fn include_hugepages(&self) -> bool {
+        self.include_hugepages
+    }
+
+    /// Indicates if the cumulative CPU time of processes is displayed in the process list
+    pub fn show_cpu_time(&self) -> bool {
+        self.show_cpu_time
+    }
+
+    /// Indicates if the names of processes are replaced with generic names on the UI
+    pub fn anonymize(&self) -> bool {
+        self.anonymize
+    }
+
+    /// Indicates if the tmpfs filesystems are listed along with the filesystems stored on disks
+    pub fn include_tmpfs(&self) -> bool {
+        self.include_tmpfs
+    }
+
+    /// Indicates if pressing q asks for a confirmation before quitting
+    pub fn confirm_quit(&self) -> bool {
+        self.confirm_quit
+    }
+
+    /// Indicates if metrics are only collected once at startup, leaving a frozen snapshot on display
+    pub fn static_snapshot(&self) -> bool {
+        self.static_snapshot
+    }
+
+    /// How long metrics are retained, or `None` if they are retained indefinitely
+    pub fn history(&self) -> Option<Duration> {
+        self.history
+    }
+
+    /// The age from which metrics are compacted, or `None` if metrics are never compacted
+    pub fn compact_after(&self) -> Option<Duration> {
+        self.compact_after
+    }
+
+    /// The minimum time processes must have been running to be displayed, or `None` if all processes are displayed
+    pub fn min_uptime(&self) -> Option<Duration> {
+        self.min_uptime
+    }
+
+    /// By how many percent the metric of a process must exceed the one of its predecessor to reorder processes, or
+    /// `None` if processes are fully sorted on each refresh
+    pub fn sort_hysteresis(&self) -> Option<f64> {
+        self.sort_hysteresis
+    }
+
+    /// Whether spv shows, hides or highlights its own process
+    pub fn own_process(&self) -> OwnProcess {
+        self.own_process
+    }
+
+    /// The columns of the process list, in the order in which they are displayed
+    ///
+    /// Unless columns are explicitly selected, they are deduced from the combined probes and the display of the CPU
+    /// time.
+    pub fn columns(&self) -> Vec<ColumnKind> {
+        match &self.columns {
+            Some(columns) => columns.clone(),
+            None => ColumnKind::defaults(self.show_cpu_time, &self.combined_probes),
+        }
+    }
+
+    /// The condition on the metrics of the enabled probes which highlights the processes satisfying it, if any
+    ///
+    /// The expression refers to metrics by the name of the probe collecting them.
+    pub fn watch(&self) -> Option<&WatchExpression> {
+        self.watch.as_ref()
+    }
+
+    /// The condition on the metrics of the enabled probes which pins the processes satisfying it to the top of the
+    /// list, if any
+    pub fn pin(&self) -> Option<&WatchExpression> {
+        self.pin.as_ref()
+    }
+
+    /// The external command which the user can run on the selected process, if any
+    pub fn exec(&self) -> Option<&CommandTemplate> {
+        self.exec.as_ref()
+    }
+
+    /// The command of the processes which spv monitors along with their descendants, if spv only monitors a service
+    pub fn service(&self) -> Option<&str> {
+        self.service.as_deref()
+    }
+
+    /// The directory from which the proc filesystem is read
+    pub fn proc_root(&self) -> &PathBuf {
+        &self.proc_root
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            refresh_period: DEFAULT_REFRESH_PERIOD,
+            span_duration: DEFAULT_SPAN_DURATION,
+            probes: ProbeKind::defaults(),
+            combined_probes: vec![],
+            subsampled_probes: vec![],
+            sample_period: DEFAULT_SAMPLE_PERIOD,
+            precisions: vec![],
+            labels: vec![],
+            external_probes: vec![],
+            log_level: DEFAULT_LOG_LEVEL,
+            log_file: PathBuf::from(DEFAULT_LOG_FILE),
+            theme: Theme::default(),
+            ascii_chart: false,
+            newest_left: false,
+            nice_y_axis: false,
+            include_hugepages: false,
+            show_cpu_time: false,
+            columns: None,
+            anonymize: false,
+            include_tmpfs: false,
+            confirm_quit: false,
+            static_snapshot: false,
+            history: None,
+            compact_after: None,
+            min_uptime: None,
+            sort_hysteresis: None,
+            own_process: OwnProcess::Show,
+            watch: None,
+            pin: None,
+            exec: None,
+            service: None,
+            proc_root: PathBuf::from(DEFAULT_PROC_ROOT),
+        }
+    }
+}
+
+impl Display for Config {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let probes: Vec<_> = self.probes.iter().map(|p| p.name()).collect();
+
+        writeln!(f, "refresh period: {:?}", self.refresh_period)?;
+        writeln!(f, "span duration: {:?}", self.span_duration)?;
+        writeln!(f, "probes: {}", probes.join(", "))?;
+        let combined_probes: Vec<_> = self.combined_probes.iter().map(|p| p.name()).collect();
+        writeln!(f, "combined probes: {}", combined_probes.join(", "))?;
+        let subsampled_probes: Vec<_> = self.subsampled_probes.iter().map(|p| p.name()).collect();
+        writeln!(f, "subsampled probes: {}", subsampled_probes.join(", "))?;
+        writeln!(f, "sample period: {:?}", self.sample_period)?;
+        let precisions: Vec<_> = self
+            .precisions
+            .iter()
+            .map(|(probe, decimals)| format!("{}={}", probe.name(), decimals))
+            .collect();
+        writeln!(f, "precisions: {}", precisions.join(", "))?;
+        let labels: Vec<_> = self.labels.iter().map(|rule| rule.to_string()).collect();
+        writeln!(f, "labels: {}", labels.join(", "))?;
+        let external_probes: Vec<_> = self.external_probes.iter().map(|p| p.to_string()).collect();
+        writeln!(f, "external probes: {}", external_probes.join(", "))?;
+        writeln!(f, "log level: {}", self.log_level.as_str().to_lowercase())?;
+        writeln!(f, "log file: {}", self.log_file.display())?;
+        writeln!(f, "ascii chart: {}", self.ascii_chart)?;
+        writeln!(f, "chart colors: {}", self.theme)?;
+        writeln!(f, "newest left: {}", self.newest_left)?;
+        writeln!(f, "nice y axis: {}", self.nice_y_axis)?;
+        writeln!(f, "include hugepages: {}", self.include_hugepages)?;
+        writeln!(f, "show cpu time: {}", self.show_cpu_time)?;
+        let columns: Vec<_> = self.columns().iter().map(|c| c.name()).collect();
+        writeln!(f, "columns: {}", columns.join(", "))?;
+        writeln!(f, "anonymize: {}", self.anonymize)?;
+        writeln!(f, "include tmpfs: {}", self.include_tmpfs)?;
+        writeln!(f, "confirm quit: {}", self.confirm_quit)?;
+        writeln!(f, "static snapshot: {}", self.static_snapshot)?;
+        writeln!(f, "proc root: {}", self.proc_root.display())?;
+        match self.min_uptime {
+            Some(min_uptime) => writeln!(f, "min uptime: {:?}", min_uptime)?,
+            None => writeln!(f, "min uptime: none")?,
+        }
+        match self.sort_hysteresis {
+            Some(hysteresis) => writeln!(f, "sort hysteresis: {}%", hysteresis)?,
+            None => writeln!(f, "sort hysteresis: none")?,
+        }
+        writeln!(f, "own process: {}", self.own_process.name())?;
+        match &self.watch {
+            Some(watch) => writeln!(f, "watch: {}", watch)?,
+            None => writeln!(f, "watch: none")?,
+        }
+        match &self.pin {
+            Some(pin) => writeln!(f, "pin: {}", pin)?,
+            None => writeln!(f, "pin: none")?,
+        }
+        match &self.exec {
+            Some(exec) => writeln!(f, "exec: {}", exec)?,
+            None => writeln!(f, "exec: none")?,
+        }
+        match &self.service {
+            Some(service) => writeln!(f, "service: {}", service)?,
+            None => writeln!(f, "service: none")?,
+        }
+        match self.history {
+            Some(history) => writeln!(f, "history: {:?}", history)?,
+            None => writeln!(f, "history: unlimited")?,
+        }
+        match self.compact_after {
+            Some(compact_after) => write!(f, "compact after: {:?}", compact_after),
+            None => write!(f, "compact after: none"),
+        }
+    }
+}
+
+/// Builds a [`Config`](Config), validating the consistency of its options
+///
+/// Options which are not set keep their default value.
+#[derive(Default)]
+pub struct ConfigBuilder {
+    refresh_period: Option<Duration>,
+    span_duration: Option<Duration>,
+    probes: Option<Vec<ProbeKind>>,
+    combined_probes: Option<Vec<ProbeKind>>,
+    subsampled_probes: Option<Vec<ProbeKind>>,
+    sample_period: Option<Duration>,
+    precisions: Option<Vec<(ProbeKind, usize)>>,
+    labels: Option<Vec<LabelRule>>,
+    external_probes: Option<Vec<ExternalProbeSpec>>,
+    log_level: Option<LevelFilter>,
+    log_file: Option<PathBuf>,
+    theme: Option<Theme>,
+    ascii_chart: Option<bool>,
+    newest_left: Option<bool>,
+    nice_y_axis: Option<bool>,
+    include_hugepages: Option<bool>,
+    show_cpu_time: Option<bool>,
+    columns: Option<Vec<ColumnKind>>,
+    anonymize: Option<bool>,
+    include_tmpfs: Option<bool>,
+    confirm_quit: Option<bool>,
+    static_snapshot: Option<bool>,
+    history: Option<Duration>,
+    compact_after: Option<Duration>,
+    min_uptime: Option<Duration>,
+    sort_hysteresis: Option<f64>,
+    own_process: Option<OwnProcess>,
+    watch: Option<WatchExpression>,
+    pin: Option<WatchExpression>,
+    exec: Option<CommandTemplate>,
+    service: Option<String>,
+    proc_root: Option<PathBuf>,
+}
+
+impl ConfigBuilder {
+    pub fn refresh_period(mut self, refresh_period: Duration) -> Self {
+        self.refresh_period = Some(refresh_period);
+        self
+    }
+
+    pub fn span_duration(mut self, span_duration: Duration) -> Self {
+        self.span_duration = Some(span_duration);
+        self
+    }
+
+    pub fn probes(mut self, probes: Vec<ProbeKind>) -> Self {
+        self.probes = Some(probes);
+        self
+    }
+
+    pub fn combined_probes(mut self, combined_probes: Vec<ProbeKind>) -> Self {
+        self.combined_probes = Some(combined_probes);
+        self
+    }
+
+    pub fn subsampled_probes(mut self, subsampled_probes: Vec<ProbeKind>) -> Self {
+        self.subsampled_probes = Some(subsampled_probes);
+        self
+    }
+
+    pub fn sample_period(mut self, sample_period: Duration) -> Self {
+        self.sample_period = Some(sample_period);
+        self
+    }
+
+    /// Sets the amount of decimals with which the metrics of each of the given probes are displayed
+    pub fn precisions(mut self, precisions: Vec<(ProbeKind, usize)>) -> Self {
+        self.precisions = Some(precisions);
+        self
+    }
+
+    /// Sets the rules labelling processes according to their command line
+    pub fn labels(mut self, labels: Vec<LabelRule>) -> Self {
+        self.labels = Some(labels);
+        self
+    }
+
+    pub fn external_probes(mut self, external_probes: Vec<ExternalProbeSpec>) -> Self {
+        self.external_probes = Some(external_probes);
+        self
+    }
+
+    pub fn log_level(mut self, log_level: LevelFilter) -> Self {
+        self.log_level = Some(log_level);
+        self
+    }
+
+    pub fn log_file(mut self, log_file: PathBuf) -> Self {
+        self.log_file = Some(log_file);
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    pub fn ascii_chart(mut self, ascii_chart: bool) -> Self {
+        self.ascii_chart = Some(ascii_chart);
+        self
+    }
+
+    pub fn newest_left(mut self, newest_left: bool) -> Self {
+        self.newest_left = Some(newest_left);
+        self
+    }
+
+    pub fn nice_y_axis(mut self, nice_y_axis: bool) -> Self {
+        self.nice_y_axis = Some(nice_y_axis);
+        self
+    }
+
+    pub fn include_hugepages(mut self, include_hugepages: bool) -> Self {
+        self.include_hugepages = Some(include_hugepages);
+        self
+    }
+
+    pub fn show_cpu_time(mut self, show_cpu_time: bool) -> Self {
+        self.show_cpu_time = Some(show_cpu_time);
+        self
+    }
+
+    /// Sets the columns of the process list, in the order in which they are displayed
+    pub fn columns(mut self, columns: Vec<ColumnKind>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    pub fn anonymize(mut self, anonymize: bool) -> Self {
+        self.anonymize = Some(anonymize);
+        self
+    }
+
+    pub fn include_tmpfs(mut self, include_tmpfs: bool) -> Self {
+        self.include_tmpfs = Some(include_tmpfs);
+        self
+    }
+
+    pub fn confirm_quit(mut self, confirm_quit: bool) -> Self {
+        self.confirm_quit = Some(confirm_quit);
+        self
+    }
+
+    pub fn static_snapshot(mut self, static_snapshot: bool) -> Self {
+        self.static_snapshot = Some(static_snapshot);
+        self
+    }
+
+    pub fn history(mut self, history: Duration) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    pub fn compact_after(mut self, compact_after: Duration) -> Self {
+        self.compact_after = Some(compact_after);
+        self
+    }
+
+    pub fn min_uptime(mut self, min_uptime: Duration) -> Self {
+        self.min_uptime = Some(min_uptime);
+        self
+    }
+
+    pub fn sort_hysteresis(mut self, sort_hysteresis: f64) -> Self {
+        self.sort_hysteresis = Some(sort_hysteresis);
+        self
+    }
+
+    pub fn own_process(mut self, own_process: OwnProcess) -> Self {
+        self.own_process = Some(own_process);
+        self
+    }
+
+    pub fn watch(mut self, watch: WatchExpression) -> Self {
+        self.watch = Some(watch);
+        self
+    }
+
+    pub fn pin(mut self, pin: WatchExpression) -> Self {
+        self.pin = Some(pin);
+        self
+    }
+
+    pub fn exec(mut self, exec: CommandTemplate) -> Self {
+        self.exec = Some(exec);
+        self
+    }
+
+    pub fn service(mut self, service: String) -> Self {
+        self.service = Some(service);
+        self
+    }
+
+    pub fn proc_root(mut self, proc_root: PathBuf) -> Self {
+        self.proc_root = Some(proc_root);
+        self
+    }
+
+    pub fn build(self) -> Result<Config, ConfigError> {
+        let default = Config::default();
+
+        if let Some(period) = self.refresh_period {
+            if period.is_zero() {
+                let reason = "the refresh period must be greater than zero".to_string();
+                return Err(ConfigError::InvalidValue(
+                    "--refresh-period",
+                    format!("{:?}", period),
+                    reason,
+                ));
+            }
+        }
+
+        if let Some(span) = self.span_duration {
+            if span < self.refresh_period.unwrap_or(default.refresh_period) {
+                return Err(ConfigError::ConflictingOptions("span duration", "--refresh-period"));
+            }
+        }
+
+        if let Some(history) = self.history {
+            let span = self.span_duration.unwrap_or(default.span_duration);
+            if history < span {
+                let reason = format!(
+                    "the history must be at least as long as the {:?} span of the chart",
+                    span
+                );
+                return Err(ConfigError::InvalidValue("--history", format!("{:?}", history), reason));
+            }
+        }
+
+        if let Some(probes) = &self.probes {
+            Self::validate_probes(probes)?;
+        }
+
+        if let Some(combined_probes) = &self.combined_probes {
+            Self::validate_combined_probes(combined_probes, self.probes.as_ref().unwrap_or(&default.probes))?;
+        }
+
+        if let Some(subsampled_probes) = &self.subsampled_probes {
+            Self::validate_subsampled_probes(subsampled_probes, self.probes.as_ref().unwrap_or(&default.probes))?;
+
+            let sample_period = self.sample_period.unwrap_or(default.sample_period);
+            if sample_period.is_zero() {
+                let reason = "the sample period must be greater than zero".to_string();
+                return Err(ConfigError::InvalidValue(
+                    "--sample-period",
+                    format!("{:?}", sample_period),
+                    reason,
+                ));
+            }
+            if !subsampled_probes.is_empty() && sample_period >= self.refresh_period.unwrap_or(default.refresh_period) {
+                return Err(ConfigError::ConflictingOptions("--sample-period", "--refresh-period"));
+            }
+        }
+
+        if let Some(precisions) = &self.precisions {
+            Self::validate_precisions(precisions)?;
+        }
+
+        if let Some(columns) = &self.columns {
+            if self
+                .combined_probes
+                .as_ref()
+                .is_some_and(|combined| !combined.is_empty())
+            {
+                return Err(ConfigError::ConflictingOptions("--columns", "--combine"));
+            }
+            if self.show_cpu_time == Some(true) {
+                return Err(ConfigError::ConflictingOptions("--columns", "--cpu-time"));
+            }
+            Self::validate_columns(columns, self.probes.as_ref().unwrap_or(&default.probes))?;
+        }
+
+        let probes = self.probes.as_ref().unwrap_or(&default.probes);
+        let external_probes = self.external_probes.as_ref().unwrap_or(&default.external_probes);
+        if let Some(watch) = &self.watch {
+            Self::validate_expression("--watch", watch, probes, external_probes)?;
+        }
+
+        if let Some(pin) = &self.pin {
+            Self::validate_expression("--pin", pin, probes, external_probes)?;
+        }
+
+        if self.log_level == Some(LevelFilter::Off) && self.log_file.is_some() {
+            return Err(ConfigError::ConflictingOptions("--log-file", "--log-level off"));
+        }
+
+        Ok(Config {
+            refresh_period: self.refresh_period.unwrap_or(default.refresh_period),
+            span_duration: self.span_duration.unwrap_or(default.span_duration),
+            probes: self.probes.unwrap_or(default.probes),
+            combined_probes: self.combined_probes.unwrap_or(default.combined_probes),
+            subsampled_probes: self.subsampled_probes.unwrap_or(default.subsampled_probes),
+            sample_period: self.sample_period.unwrap_or(default.sample_period),
+            precisions: self.precisions.unwrap_or(default.precisions),
+            labels: self.labels.unwrap_or(default.labels),
+            external_probes: self.external_probes.unwrap_or(default.external_probes),
+            log_level: self.log_level.unwrap_or(default.log_level),
+            log_file: self.log_file.unwrap_or(default.log_file),
+            theme: self.theme.unwrap_or(default.theme),
+            ascii_chart: self.ascii_chart.unwrap_or(default.ascii_chart),
+            newest_left: self.newest_left.unwrap_or(default.newest_left),
+            nice_y_axis: self.nice_y_axis.unwrap_or(default.nice_y_axis),
+            include_hugepages: self.include_hugepages.unwrap_or(default.include_hugepages),
+            show_cpu_time: self.show_cpu_time.unwrap_or(default.show_cpu_time),
+            columns: self.columns.or(default.columns),
+            anonymize: self.anonymize.unwrap_or(default.anonymize),
+            include_tmpfs: self.include_tmpfs.unwrap_or(default.include_tmpfs),
+            confirm_quit: self.confirm_quit.unwrap_or(default.confirm_quit),
+            static_snapshot: self.static_snapshot.unwrap_or(default.static_snapshot),
+            history: self.history.or(default.history),
+            compact_after: self.compact_after.or(default.compact_after),
+            min_uptime: self.min_uptime.or(default.min_uptime),
+            sort_hysteresis: self.sort_hysteresis.or(default.sort_hysteresis),
+            own_process: self.own_process.unwrap_or(default.own_process),
+            watch: self.watch.or(default.watch),
+            pin: self.pin.or(default.pin),
+            exec: self.exec.or(default.exec),
+            service: self.service.or(default.service),
+            proc_root: self.proc_root.unwrap_or(default.proc_root),
+        })
+    }
+
+    fn validate_probes(probes: &[ProbeKind]) -> Result<(), ConfigError> {
+        if probes.is_empty() {
+            let reason = "at least one probe must be enabled".to_string();
+            return Err(ConfigError::InvalidValue("--probes", String::new(), reason));
+        }
+
+        let mut enabled = HashSet::new();
+        for probe in probes {
+            if !enabled.insert(probe) {
+                let reason = "the probe is enabled more than once".to_string();
+                return Err(ConfigError::InvalidValue("--probes", probe.name().to_string(), reason));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_combined_probes(combined_probes: &[ProbeKind], probes: &[ProbeKind]) -> Result<(), ConfigError> {
+        let mut combined = HashSet::new();
+        for probe in combined_probes {
+            if !probes.contains(probe) {
+                let reason = "only enabled probes can be combined".to_string();
+                return Err(ConfigError::InvalidValue("--combine", probe.name().to_string(), reason));
+            }
+            if !combined.insert(probe) {
+                let reason = "the probe is combined more than once".to_string();
+                return Err(ConfigError::InvalidValue("--combine", probe.name().to_string(), reason));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_subsampled_probes(subsampled_probes: &[ProbeKind], probes: &[ProbeKind]) -> Result<(), ConfigError> {
+        let mut subsampled = HashSet::new();
+        for probe in subsampled_probes {
+            if !probes.contains(probe) {
+                let reason = "only enabled probes can be subsampled".to_string();
+                return Err(ConfigError::InvalidValue(
+                    "--subsample",
+                    probe.name().to_string(),
+                    reason,
+                ));
+            }
+            if !subsampled.insert(probe) {
+                let reason = "the probe is subsampled more than once".to_string();
+                return Err(ConfigError::InvalidValue(
+                    "--subsample",
+                    probe.name().to_string(),
+                    reason,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_precisions(precisions: &[(ProbeKind, usize)]) -> Result<(), ConfigError> {
+        let mut configured = HashSet::new();
+        for (probe, decimals) in precisions {
+            let repr = format!("{}={}", probe.name(), decimals);
+            if *decimals > MAX_PRECISION {
+                let reason = format!("metrics can not be displayed with more than {} decimals", MAX_PRECISION);
+                return Err(ConfigError::InvalidValue("--precision", repr, reason));
+            }
+            if !configured.insert(probe) {
+                let reason = "the precision of the probe is set more than once".to_string();
+                return Err(ConfigError::InvalidValue("--precision", repr, reason));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_columns(columns: &[ColumnKind], probes: &[ProbeKind]) -> Result<(), ConfigError> {
+        if columns.is_empty() {
+            let reason = "at least one column must be displayed".to_string();
+            return Err(ConfigError::InvalidValue("--columns", String::new(), reason));
+        }
+
+        let mut displayed = HashSet::new();
+        for column in columns {
+            if let ColumnKind::Probe(probe) = column {
+                if !probes.contains(probe) {
+                    let reason = "only the metrics of enabled probes can be displayed".to_string();
+                    return Err(ConfigError::InvalidValue(
+                        "--columns",
+                        column.name().to_string(),
+                        reason,
+                    ));
+                }
+            }
+            if !displayed.insert(column) {
+                let reason = "the column is displayed more than once".to_string();
+                return Err(ConfigError::InvalidValue(
+                    "--columns",
+                    column.name().to_string(),
+                    reason,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the expression passed to the given option only refers to the metrics of enabled probes, including
+    /// the external probes, which are referred to by their name
+    fn validate_expression(
+        option: &'static str,
+        expression: &WatchExpression,
+        probes: &[ProbeKind],
+        external_probes: &[ExternalProbeSpec],
+    ) -> Result<(), ConfigError> {
+        for metric in expression.metrics() {
+            if external_probes.iter().any(|spec| spec.name() == metric) {
+                continue;
+            }
+
+            let probe = metric
+                .parse::<ProbeKind>()
+                .map_err(|reason| ConfigError::InvalidValue(option, metric.to_string(), reason))?;
+            if !probes.contains(&probe) {
+                let reason = "only the metrics of enabled probes can be referred to".to_string();
+                return Err(ConfigError::InvalidValue(option, metric.to_string(), reason));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits the content of a configuration file into command line arguments
+///
+/// Each line holds an option, optionally followed by its value after a space (e.g. `--watch cpu > 50`) or an equal sign
+/// (e.g. `--watch=cpu > 50`). Empty lines and lines starting with `#` are ignored.
+fn parse_config_file(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .flat_map(|line| match line.split_once(char::is_whitespace) {
+            Some((option, value)) if !option.contains('=') => vec![option.to_string(), value.trim().to_string()],
+            _ => vec![line.to_string()],
+        })
+        .collect()
+}
+
+/// Parses the precision of the metrics of a probe, such as `cpu=2`
+fn parse_precision(repr: &str) -> Result<(ProbeKind, usize), String> {
+    let (probe, decimals) = repr
+        .split_once('=')
+        .ok_or_else(|| "expected a precision such as cpu=2".to_string())?;
+
+    let probe = probe.parse::<ProbeKind>()?;
+    let decimals = decimals
+        .trim()
+        .parse()
+        .map_err(|_| "the precision must be a positive integer".to_string())?;
+
+    Ok((probe, decimals))
+}
+
+/// Parses a rule labelling the processes whose command line matches a pattern, such as `java .*kafka=kafka`
+///
+/// The pattern may itself contain `=`, so the label is what follows the last `=`.
+fn parse_label(repr: &str) -> Result<LabelRule, String> {
+    let (pattern, label) = repr
+        .rsplit_once('=')
+        .ok_or_else(|| "expected a label such as java .*kafka=kafka".to_string())?;
+
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("the label must not be empty".to_string());
+    }
+
+    let pattern = Regex::new(pattern).map_err(|e| format!("invalid pattern: {}", e))?;
+
+    Ok(LabelRule::new(pattern, label))
+}
+
+/// Parses a duration such as `500ms`, `2s`, `5m` or `1h`. A value without unit is interpreted in seconds.
+fn parse_duration(repr: &str) -> Result<Duration, String> {
+    let repr = repr.trim();
+    let unit_index = repr.find(|c: char| c.is_alphabetic()).unwrap_or(repr.len());
+    let (value, unit) = repr.split_at(unit_index);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| "expected a duration such as 2s".to_string())?;
+    if !value.is_finite() || value < 0. {
+        return Err("the duration must be a positive number".to_string());
+    }
+
+    let secs = match unit {
+        "ms" => value / 1000.,
+        "" | "s" => value,
+        "m" => value * 60.,
+        "h" => value * 3600.,
+        _ => return Err(format!("unknown duration unit '{}'", unit)),
+    };
+
+    Duration::try_from_secs_f64(secs).map_err(|_| "the duration is too long".to_string())
+}
+
+/// Parses a percentage such as `10` or `2.5`
+fn parse_percentage(repr: &str) -> Result<f64, String> {
+    let value: f64 = repr
+        .trim()
+        .parse()
+        .map_err(|_| "expected a percentage such as 10".to_string())?;
+
+    if !value.is_finite() || value < 0. {
+        return Err("the percentage must be a positive number".to_string());
+    }
+
+    Ok(value)
+}
+
+/// Parses a log level, rejecting `trace` as it is not compiled into spv
+fn parse_log_level(repr: &str) -> Result<LevelFilter, String> {
+    let level = LevelFilter::from_str(repr).map_err(|_| "unknown log level".to_string())?;
+
+    if level > LevelFilter::Debug {
+        return Err("the most verbose supported level is debug".to_string());
+    }
+
+    Ok(level)
+}
+
+#[cfg(test)]
+mod test_parse_duration {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::config::parse_duration;
+
+    #[rstest]
+    #[case("500ms", Duration::from_millis(500))]
+    #[case("2s", Duration::from_secs(2))]
+    #[case("2", Duration::from_secs(2))]
+    #[case("1.5s", Duration::from_millis(1500))]
+    #[case("5m", Duration::from_secs(300))]
+    #[case("1h", Duration::from_secs(3600))]
+    fn test_should_parse_duration(#[case] repr: &str, #[case] expected: Duration) {
+        assert_eq!(parse_duration(repr), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("s")]
+    #[case("-1s")]
+    #[case("2 days")]
+    #[case("2d")]
+    #[case("99999999999999999999")]
+    #[case("99999999999999999h")]
+    fn test_should_reject_invalid_duration(#[case] repr: &str) {
+        assert!(parse_duration(repr).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_config_builder {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use log::LevelFilter;
+    use rstest::*;
+    use tui::style::Color;
+
+    use crate::config::{ColumnKind, Config, ConfigBuilder, ConfigError, ProbeKind, Theme, MAX_PRECISION};
+
+    #[test]
+    fn test_default_config_should_have_expected_values() {
+        let config = Config::default();
+
+        assert_eq!(config.refresh_period(), Duration::from_secs(1));
+        assert_eq!(config.span_duration(), Duration::from_secs(60));
+        assert_eq!(config.probes(), &[ProbeKind::Cpu, ProbeKind::DiskIO]);
+        assert_eq!(config.log_level(), LevelFilter::Debug);
+        assert_eq!(config.log_file(), &PathBuf::from("spv.log"));
+        assert_eq!(config.theme(), &Theme::default());
+        assert!(!config.ascii_chart());
+        assert!(!config.newest_left());
+        assert!(!config.nice_y_axis());
+        assert!(!config.include_hugepages());
+        assert!(!config.show_cpu_time());
+        assert!(!config.anonymize());
+        assert!(!config.include_tmpfs());
+        assert!(!config.confirm_quit());
+        assert!(!config.static_snapshot());
+        assert_eq!(config.history(), None);
+        assert_eq!(config.compact_after(), None);
+        assert_eq!(config.service(), None);
+    }
+
+    #[test]
+    fn test_should_use_default_values_when_nothing_is_set() {
+        let config = ConfigBuilder::default().build().unwrap();
+
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_should_override_set_values() {
+        let config = ConfigBuilder::default()
+            .refresh_period(Duration::from_millis(500))
+            .span_duration(Duration::from_secs(120))
+            .probes(vec![ProbeKind::DiskIO])
+            .log_level(LevelFilter::Warn)
+            .log_file(PathBuf::from("/tmp/spv.log"))
+            .theme(Theme::new(vec![Color::Red, Color::Yellow]))
+            .build()
+            .unwrap();
+
+        assert_eq!(config.refresh_period(), Duration::from_millis(500));
+        assert_eq!(config.span_duration(), Duration::from_secs(120));
+        assert_eq!(config.probes(), &[ProbeKind::DiskIO]);
+        assert_eq!(config.log_level(), LevelFilter::Warn);
+        assert_eq!(config.log_file(), &PathBuf::from("/tmp/spv.log"));
+        assert_eq!(config.theme().chart_color(1), Color::Yellow);
+    }
+
+    #[test]
+    fn test_default_theme_should_have_distinct_colors_for_several_components() {
+        let theme = Theme::default();
+
+        let colors: Vec<_> = (0..5).map(|i| theme.chart_color(i)).collect();
+
+        assert!(colors.iter().enumerate().all(|(i, c)| !colors[..i].contains(c)));
+    }
+
+    #[test]
+    fn test_theme_should_cycle_through_palette() {
+        let theme = Theme::new(vec![Color::Red, Color::Yellow]);
+
+        assert_eq!(theme.chart_color(2), Color::Red);
+    }
+
+    #[test]
+    fn test_should_keep_default_values_of_fields_which_are_not_set() {
+        let config = ConfigBuilder::default()
+            .refresh_period(Duration::from_secs(2))
+            .build()
+            .unwrap();
+
+        assert_eq!(config.span_duration(), Config::default().span_duration());
+        assert_eq!(config.probes(), Config::default().probes());
+        assert_eq!(config.theme(), Config::default().theme());
+    }
+
+    #[test]
+    fn test_should_reject_span_shorter_than_refresh_period() {
+        let ret = ConfigBuilder::default()
+            .refresh_period(Duration::from_secs(10))
+            .span_duration(Duration::from_secs(5))
+            .build();
+
+        assert_eq!(
+            ret,
+            Err(ConfigError::ConflictingOptions("span duration", "--refresh-period"))
+        );
+    }
+
+    #[test]
+    fn test_should_reject_history_shorter_than_span() {
+        let ret = ConfigBuilder::default().history(Duration::from_secs(30)).build();
+
+        assert_eq!(
+            ret.unwrap_err().to_string(),
+            "Invalid value '30s' for --history: the history must be at least as long as the 60s span of the chart"
+        );
+    }
+
+    #[test]
+    fn test_should_reject_zero_refresh_period() {
+        let ret = ConfigBuilder::default().refresh_period(Duration::ZERO).build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--refresh-period", _, _))));
+    }
+
+    #[test]
+    fn test_should_reject_empty_probes_list() {
+        let ret = ConfigBuilder::default().probes(vec![]).build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--probes", _, _))));
+    }
+
+    #[test]
+    fn test_should_reject_probe_enabled_twice() {
+        let ret = ConfigBuilder::default()
+            .probes(vec![ProbeKind::Cpu, ProbeKind::Cpu])
+            .build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--probes", _, _))));
+    }
+
+    #[test]
+    fn test_should_reject_column_of_probe_which_is_not_enabled() {
+        let ret = ConfigBuilder::default()
+            .probes(vec![ProbeKind::Cpu])
+            .columns(vec![ColumnKind::Command, ColumnKind::Probe(ProbeKind::Memory)])
+            .build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--columns", _, _))));
+    }
+
+    #[rstest]
+    #[case(vec![])]
+    #[case(vec![ColumnKind::Command, ColumnKind::Identifier, ColumnKind::Command])]
+    fn test_should_reject_empty_or_repeated_columns(#[case] columns: Vec<ColumnKind>) {
+        let ret = ConfigBuilder::default().columns(columns).build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--columns", _, _))));
+    }
+
+    #[test]
+    fn test_should_reject_columns_along_with_combined_probes() {
+        let ret = ConfigBuilder::default()
+            .probes(vec![ProbeKind::Cpu, ProbeKind::Memory])
+            .combined_probes(vec![ProbeKind::Memory])
+            .columns(vec![ColumnKind::Command, ColumnKind::Metric])
+            .build();
+
+        assert_eq!(ret, Err(ConfigError::ConflictingOptions("--columns", "--combine")));
+    }
+
+    #[test]
+    fn test_should_reject_combined_probe_which_is_not_enabled() {
+        let ret = ConfigBuilder::default()
+            .probes(vec![ProbeKind::Cpu])
+            .combined_probes(vec![ProbeKind::Memory])
+            .build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--combine", _, _))));
+    }
+
+    #[rstest]
+    #[case("memory > 1G")]
+    #[case("cpu > 50 OR gpu > 50")]
+    fn test_should_reject_watch_expression_on_metrics_of_disabled_probes(#[case] expression: &str) {
+        let ret = ConfigBuilder::default()
+            .probes(vec![ProbeKind::Cpu])
+            .watch(expression.parse().unwrap())
+            .build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--watch", _, _))));
+    }
+
+    #[test]
+    fn test_should_reject_pin_expression_on_metrics_of_disabled_probes() {
+        let ret = ConfigBuilder::default()
+            .probes(vec![ProbeKind::Cpu])
+            .pin("memory > 1G".parse().unwrap())
+            .build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--pin", _, _))));
+    }
+
+    #[test]
+    fn test_should_reject_subsampled_probe_which_is_not_enabled() {
+        let ret = ConfigBuilder::default()
+            .probes(vec![ProbeKind::Cpu])
+            .subsampled_probes(vec![ProbeKind::Memory])
+            .build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--subsample", _, _))));
+    }
+
+    #[test]
+    fn test_should_reject_probe_subsampled_twice() {
+        let ret = ConfigBuilder::default()
+            .subsampled_probes(vec![ProbeKind::Cpu, ProbeKind::Cpu])
+            .build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--subsample", _, _))));
+    }
+
+    #[rstest]
+    #[case(Duration::from_secs(1))]
+    #[case(Duration::from_secs(2))]
+    fn test_should_detect_sample_period_not_shorter_than_refresh_period(#[case] sample_period: Duration) {
+        let ret = ConfigBuilder::default()
+            .refresh_period(Duration::from_secs(1))
+            .subsampled_probes(vec![ProbeKind::Cpu])
+            .sample_period(sample_period)
+            .build();
+
+        assert_eq!(
+            ret,
+            Err(ConfigError::ConflictingOptions("--sample-period", "--refresh-period"))
+        );
+    }
+
+    #[test]
+    fn test_should_reject_zero_sample_period() {
+        let ret = ConfigBuilder::default()
+            .subsampled_probes(vec![ProbeKind::Cpu])
+            .sample_period(Duration::ZERO)
+            .build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--sample-period", _, _))));
+    }
+
+    #[test]
+    fn test_should_reject_precision_too_high_to_be_displayed() {
+        let ret = ConfigBuilder::default()
+            .precisions(vec![(ProbeKind::Cpu, MAX_PRECISION + 1)])
+            .build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--precision", _, _))));
+    }
+
+    #[test]
+    fn test_should_reject_precision_set_twice() {
+        let ret = ConfigBuilder::default()
+            .precisions(vec![(ProbeKind::Cpu, 1), (ProbeKind::Cpu, 2)])
+            .build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--precision", _, _))));
+    }
+
+    #[test]
+    fn test_should_reject_probe_combined_twice() {
+        let ret = ConfigBuilder::default()
+            .combined_probes(vec![ProbeKind::Cpu, ProbeKind::Cpu])
+            .build();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidValue("--combine", _, _))));
+    }
+
+    #[test]
+    fn test_should_detect_log_file_conflicting_with_disabled_logs() {
+        let ret = ConfigBuilder::default()
+            .log_level(LevelFilter::Off)
+            .log_file(PathBuf::from("spv.log"))
+            .build();
+
+        assert_eq!(
+            ret,
+            Err(ConfigError::ConflictingOptions("--log-file", "--log-level off"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_config_from_args {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use log::LevelFilter;
+    use regex::Regex;
+    use rstest::*;
+    use tui::style::Color;
+
+    use crate::config::{Arguments, ColumnKind, Config, ConfigError, OwnProcess, ProbeKind, Theme};
+    use crate::core::external::ExternalProbeSpec;
+    use crate::core::labels::LabelRule;
+
+    #[test]
+    fn test_should_resolve_default_config_without_arguments() {
+        let config = Config::from_args(&Arguments::default()).unwrap();
+
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_should_resolve_refresh_period() {
+        let args = Arguments {
+            refresh_period: Some("250ms".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args).unwrap().refresh_period(),
+            Duration::from_millis(250)
+        );
+    }
+
+    #[test]
+    fn test_should_resolve_probes() {
+        let args = Arguments {
+            probes: Some(vec![
+                "diskio".to_string(),
+                "lifetime-cpu".to_string(),
+                "cpu".to_string(),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args).unwrap().probes(),
+            &[ProbeKind::DiskIO, ProbeKind::LifetimeCpu, ProbeKind::Cpu]
+        );
+    }
+
+    #[test]
+    fn test_should_resolve_combined_probes() {
+        let args = Arguments {
+            probes: Some(vec!["cpu".to_string(), "memory".to_string()]),
+            combine: Some(vec!["memory".to_string()]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args).unwrap().combined_probes(),
+            &[ProbeKind::Memory]
+        );
+    }
+
+    #[test]
+    fn test_should_not_combine_probes_by_default() {
+        assert!(Config::default().combined_probes().is_empty());
+    }
+
+    #[test]
+    fn test_should_resolve_subsampled_probes_and_sample_period() {
+        let args = Arguments {
+            probes: Some(vec!["cpu".to_string(), "diskio".to_string()]),
+            subsample: Some(vec!["cpu".to_string()]),
+            sample_period: Some("250ms".to_string()),
+            ..Default::default()
+        };
+
+        let config = Config::from_args(&args).unwrap();
+
+        assert_eq!(config.subsampled_probes(), &[ProbeKind::Cpu]);
+        assert_eq!(config.sample_period(), Duration::from_millis(250));
+        assert_eq!(config.samples_per_refresh(), 4);
+    }
+
+    #[test]
+    fn test_should_sample_once_per_refresh_without_subsampled_probes() {
+        let args = Arguments {
+            sample_period: Some("100ms".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(Config::from_args(&args).unwrap().samples_per_refresh(), 1);
+    }
+
+    #[test]
+    fn test_should_resolve_precisions() {
+        let args = Arguments {
+            precision: Some(vec!["cpu=2".to_string(), "memory=0".to_string()]),
+            ..Default::default()
+        };
+
+        let config = Config::from_args(&args).unwrap();
+
+        assert_eq!(config.precision(ProbeKind::Cpu), Some(2));
+        assert_eq!(config.precision(ProbeKind::Memory), Some(0));
+        assert_eq!(config.precision(ProbeKind::DiskIO), None);
+    }
+
+    #[test]
+    fn test_should_resolve_labels_in_order() {
+        let args = Arguments {
+            label: Some(vec![
+                "java .*kafka=kafka".to_string(),
+                "--mode=(a|b)=worker".to_string(),
+            ]),
+            ..Default::default()
+        };
+
+        let config = Config::from_args(&args).unwrap();
+
+        let expected = vec![
+            LabelRule::new(Regex::new("java .*kafka").unwrap(), "kafka"),
+            LabelRule::new(Regex::new("--mode=(a|b)").unwrap(), "worker"),
+        ];
+        assert_eq!(config.labels(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_should_resolve_ascii_chart() {
+        let args = Arguments {
+            ascii: true,
+            ..Default::default()
+        };
+
+        assert!(Config::from_args(&args).unwrap().ascii_chart());
+    }
+
+    #[test]
+    fn test_should_resolve_newest_left() {
+        let args = Arguments {
+            newest_left: true,
+            ..Default::default()
+        };
+
+        assert!(Config::from_args(&args).unwrap().newest_left());
+    }
+
+    #[test]
+    fn test_should_resolve_nice_y_axis() {
+        let args = Arguments {
+            nice_y_axis: true,
+            ..Default::default()
+        };
+
+        assert!(Config::from_args(&args).unwrap().nice_y_axis());
+    }
+
+    #[test]
+    fn test_should_resolve_hugepages_inclusion() {
+        let args = Arguments {
+            hugepages: true,
+            ..Default::default()
+        };
+
+        assert!(Config::from_args(&args).unwrap().include_hugepages());
+    }
+
+    #[test]
+    fn test_should_resolve_cpu_time_display() {
+        let args = Arguments {
+            cpu_time: true,
+            ..Default::default()
+        };
+
+        assert!(Config::from_args(&args).unwrap().show_cpu_time());
+    }
+
+    #[test]
+    fn test_should_resolve_columns_in_given_order() {
+        let args = Arguments {
+            probes: Some(vec!["cpu".to_string(), "memory".to_string()]),
+            columns: Some(vec![
+                "pid".to_string(),
+                "command".to_string(),
+                "cpu".to_string(),
+                "memory".to_string(),
+                "uptime".to_string(),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args).unwrap().columns(),
+            vec![
+                ColumnKind::Identifier,
+                ColumnKind::Command,
+                ColumnKind::Probe(ProbeKind::Cpu),
+                ColumnKind::Probe(ProbeKind::Memory),
+                ColumnKind::Uptime
+            ]
+        );
+    }
+
+    #[test]
+    fn test_should_reject_unknown_column() {
+        let args = Arguments {
+            columns: Some(vec!["command".to_string(), "user".to_string()]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args),
+            Err(ConfigError::InvalidValue(
+                "--columns",
+                "user".to_string(),
+                "unknown column".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_should_resolve_chart_colors_in_given_order() {
+        let args = Arguments {
+            chart_colors: Some(vec!["white".to_string(), "cyan".to_string()]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args).unwrap().theme(),
+            &Theme::new(vec![Color::White, Color::Cyan])
+        );
+    }
+
+    #[test]
+    fn test_should_reject_unknown_chart_color() {
+        let args = Arguments {
+            chart_colors: Some(vec!["blue".to_string(), "teal".to_string()]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args),
+            Err(ConfigError::InvalidValue(
+                "--chart-colors",
+                "teal".to_string(),
+                "unknown color".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_should_deduce_default_columns_from_cpu_time_and_combined_probes() {
+        let args = Arguments {
+            probes: Some(vec!["cpu".to_string(), "memory".to_string()]),
+            combine: Some(vec!["memory".to_string()]),
+            cpu_time: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args).unwrap().columns(),
+            vec![
+                ColumnKind::Command,
+                ColumnKind::Identifier,
+                ColumnKind::CpuTime,
+                ColumnKind::Metric,
+                ColumnKind::Probe(ProbeKind::Memory)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_should_resolve_anonymization() {
+        let args = Arguments {
+            anonymize: true,
+            ..Default::default()
+        };
+
+        assert!(Config::from_args(&args).unwrap().anonymize());
+    }
+
+    #[test]
+    fn test_should_resolve_tmpfs_inclusion() {
+        let args = Arguments {
+            tmpfs: true,
+            ..Default::default()
+        };
+
+        assert!(Config::from_args(&args).unwrap().include_tmpfs());
+    }
+
+    #[test]
+    fn test_should_resolve_external_probes_in_order() {
+        let args = Arguments {
+            external_probe: Some(vec![
+                "queue:/usr/local/bin/queue-depth".to_string(),
+                "lag:/opt/lag.sh".to_string(),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args).unwrap().external_probes(),
+            &[
+                ExternalProbeSpec::new("queue", "/usr/local/bin/queue-depth"),
+                ExternalProbeSpec::new("lag", "/opt/lag.sh"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_should_resolve_quit_confirmation() {
+        let args = Arguments {
+            confirm_quit: true,
+            ..Default::default()
+        };
+
+        assert!(Config::from_args(&args).unwrap().confirm_quit());
+    }
+
+    #[test]
+    fn test_should_resolve_static_snapshot() {
+        let args = Arguments {
+            static_snapshot: true,
+            ..Default::default()
+        };
+
+        assert!(Config::from_args(&args).unwrap().static_snapshot());
+    }
+
+    #[test]
+    fn test_should_resolve_history() {
+        let args = Arguments {
+            history: Some("10m".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args).unwrap().history(),
+            Some(Duration::from_secs(600))
+        );
+    }
+
+    #[test]
+    fn test_should_resolve_compact_after() {
+        let args = Arguments {
+            compact_after: Some("5m".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args).unwrap().compact_after(),
+            Some(Duration::from_secs(300))
+        );
+    }
+
+    #[test]
+    fn test_should_resolve_min_uptime() {
+        let args = Arguments {
+            min_uptime: Some("5s".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args).unwrap().min_uptime(),
+            Some(Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn test_should_resolve_sort_hysteresis() {
+        let args = Arguments {
+            sort_hysteresis: Some("12.5".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(Config::from_args(&args).unwrap().sort_hysteresis(), Some(12.5));
+    }
+
+    #[rstest]
+    #[case("show", OwnProcess::Show)]
+    #[case("hide", OwnProcess::Hide)]
+    #[case("highlight", OwnProcess::Highlight)]
+    fn test_should_resolve_own_process(#[case] repr: &str, #[case] expected: OwnProcess) {
+        let args = Arguments {
+            own_process: Some(repr.to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(Config::from_args(&args).unwrap().own_process(), expected);
+    }
+
+    #[test]
+    fn test_should_resolve_watch_expression() {
+        let args = Arguments {
+            probes: Some(vec!["cpu".to_string(), "memory".to_string()]),
+            watch: Some("cpu > 50 OR memory > 1G".to_string()),
+            ..Default::default()
+        };
+
+        let config = Config::from_args(&args).unwrap();
+
+        assert_eq!(config.watch().map(|w| w.metrics()), Some(vec!["cpu", "memory"]));
+    }
+
+    #[test]
+    fn test_should_resolve_pin_expression() {
+        let args = Arguments {
+            probes: Some(vec!["cpu".to_string()]),
+            pin: Some("cpu > 90".to_string()),
+            ..Default::default()
+        };
+
+        let config = Config::from_args(&args).unwrap();
+
+        assert_eq!(config.pin().map(|p| p.metrics()), Some(vec!["cpu"]));
+    }
+
+    #[test]
+    fn test_should_resolve_exec_command() {
+        let args = Arguments {
+            exec: Some("strace -p {pid}".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args).unwrap().exec(),
+            Some(&"strace -p {pid}".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_should_resolve_service() {
+        let args = Arguments {
+            service: Some("nginx".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(Config::from_args(&args).unwrap().service(), Some("nginx"));
+    }
+
+    #[test]
+    fn test_should_resolve_proc_root() {
+        let args = Arguments {
+            proc_root: Some(PathBuf::from("/mnt/host_proc")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Config::from_args(&args).unwrap().proc_root(),
+            &PathBuf::from("/mnt/host_proc")
+        );
+    }
+
+    #[rstest]
+    #[case("off", LevelFilter::Off)]
+    #[case("warn", LevelFilter::Warn)]
+    #[case("DEBUG", LevelFilter::Debug)]
+    fn test_should_resolve_log_level(#[case] level: &str, #[case] expected: LevelFilter) {
+        let args = Arguments {
+            log_level: Some(level.to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(Config::from_args(&args).unwrap().log_level(), expected);
+    }
+
+    #[rstest]
+    #[case(Arguments { refresh_period: Some("fast".to_string()), ..Default::default() }, "--refresh-period")]
+    #[case(Arguments { probes: Some(vec!["npu".to_string()]), ..Default::default() }, "--probes")]
+    #[case(Arguments { combine: Some(vec!["npu".to_string()]), ..Default::default() }, "--combine")]
+    #[case(Arguments { subsample: Some(vec!["npu".to_string()]), ..Default::default() }, "--subsample")]
+    #[case(Arguments { sample_period: Some("often".to_string()), ..Default::default() }, "--sample-period")]
+    #[case(Arguments { precision: Some(vec!["cpu".to_string()]), ..Default::default() }, "--precision")]
+    #[case(Arguments { precision: Some(vec!["npu=2".to_string()]), ..Default::default() }, "--precision")]
+    #[case(Arguments { precision: Some(vec!["cpu=-1".to_string()]), ..Default::default() }, "--precision")]
+    #[case(Arguments { label: Some(vec!["kafka".to_string()]), ..Default::default() }, "--label")]
+    #[case(Arguments { label: Some(vec!["java=".to_string()]), ..Default::default() }, "--label")]
+    #[case(Arguments { label: Some(vec!["java (=kafka".to_string()]), ..Default::default() }, "--label")]
+    #[case(Arguments { log_level: Some("verbose".to_string()), ..Default::default() }, "--log-level")]
+    #[case(Arguments { log_level: Some("trace".to_string()), ..Default::default() }, "--log-level")]
+    #[case(Arguments { history: Some("forever".to_string()), ..Default::default() }, "--history")]
+    #[case(Arguments { compact_after: Some("later".to_string()), ..Default::default() }, "--compact-after")]
+    #[case(Arguments { min_uptime: Some("young".to_string()), ..Default::default() }, "--min-uptime")]
+    #[case(Arguments { sort_hysteresis: Some("-5".to_string()), ..Default::default() }, "--sort-hysteresis")]
+    #[case(Arguments { sort_hysteresis: Some("lots".to_string()), ..Default::default() }, "--sort-hysteresis")]
+    #[case(Arguments { own_process: Some("blink".to_string()), ..Default::default() }, "--own-process")]
+    #[case(Arguments { watch: Some("cpu >".to_string()), ..Default::default() }, "--watch")]
+    #[case(Arguments { exec: Some("strace -p '{pid}".to_string()), ..Default::default() }, "--exec")]
+    #[case(Arguments { external_probe: Some(vec!["/usr/local/bin/queue-depth".to_string()]), ..Default::default() }, "--external-probe")]
+    #[case(Arguments { service: Some(String::new()), ..Default::default() }, "--service")]
+    fn test_should_report_flag_of_invalid_value(#[case] args: Arguments, #[case] expected_flag: &str) {
+        match Config::from_args(&args) {
+            Err(ConfigError::InvalidValue(flag, _, _)) => assert_eq!(flag, expected_flag),
+            ret => panic!("Expected an invalid value error, got {:?}", ret),
+        }
+    }
+
+    #[test]
+    fn test_should_print_effective_configuration() {
+        let args = Arguments {
+            refresh_period: Some("2s".to_string()),
+            probes: Some(vec!["cpu".to_string()]),
+            log_level: Some("info".to_string()),
+            ..Default::default()
+        };
+
+        let repr = Config::from_args(&args).unwrap().to_string();
+
+        assert_eq!(
+            repr,
+            "refresh period: 2s\nspan duration: 60s\nprobes: cpu\ncombined probes: \nsubsampled probes: \nsample period: 200ms\nprecisions: \nlabels: \nexternal probes: \nlog level: info\nlog file: spv.log\nascii chart: false\nchart colors: blue, green, yellow, magenta, cyan\nnewest left: false\nnice y axis: false\ninclude hugepages: false\nshow cpu time: false\ncolumns: command, pid, metric\nanonymize: false\ninclude tmpfs: false\nconfirm quit: false\nstatic snapshot: false\nproc root: /proc\nmin uptime: none\nsort hysteresis: none\nown process: show\nwatch: none\npin: none\nexec: none\nservice: none\nhistory: unlimited\ncompact after: none"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_parse_config_file {
+    use crate::config::parse_config_file;
+
+    #[test]
+    fn test_should_split_options_from_their_values() {
+        let content = "--refresh-period 2s\n--watch cpu > 50 OR memory > 1G\n--ascii\n";
+
+        assert_eq!(
+            parse_config_file(content),
+            vec![
+                "--refresh-period",
+                "2s",
+                "--watch",
+                "cpu > 50 OR memory > 1G",
+                "--ascii"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_should_ignore_comments_and_empty_lines() {
+        let content = "# Highlights busy processes\n\n  --watch=cpu > 50  \n";
+
+        assert_eq!(parse_config_file(content), vec!["--watch=cpu > 50"]);
+    }
+}
+
+#[cfg(test)]
+mod test_config_source {
+    use std::ffi::OsString;
+    use std::io::Write;
+    use std::time::Duration;
+
+    use tempfile::NamedTempFile;
+
+    use crate::config::{ConfigError, ConfigSource};
+
+    fn config_file(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    fn source(args: &[&str]) -> ConfigSource {
+        ConfigSource::new(args.iter().map(OsString::from).collect())
+    }
+
+    #[test]
+    fn test_should_read_options_of_configuration_file() {
+        let file = config_file("--min-uptime 5s\n--watch cpu > 50\n");
+        let path = file.path().to_str().unwrap();
+
+        let config = source(&["spv", "--config", path]).load().unwrap();
+
+        assert_eq!(config.min_uptime(), Some(Duration::from_secs(5)));
+        assert_eq!(
+            config.watch().map(|watch| watch.to_string()),
+            Some("cpu > 50".to_string())
+        );
+    }
+
+    #[test]
+    fn test_options_of_command_line_should_override_options_of_file() {
+        let file = config_file("--min-uptime 5s\n");
+        let path = file.path().to_str().unwrap();
+
+        let config = source(&["spv", "--config", path, "--min-uptime", "10s"])
+            .load()
+            .unwrap();
+
+        assert_eq!(config.min_uptime(), Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn test_should_read_changes_of_configuration_file_on_each_load() {
+        let file = config_file("--min-uptime 5s\n");
+        let path = file.path().to_str().unwrap();
+        let source = source(&["spv", "--config", path]);
+        source.load().unwrap();
+
+        std::fs::write(path, "--min-uptime 20s\n").unwrap();
+
+        assert_eq!(source.load().unwrap().min_uptime(), Some(Duration::from_secs(20)));
+    }
+
+    #[test]
+    fn test_should_reload_configuration_changing_live_options() {
+        let file = config_file("--probes cpu,memory\n--min-uptime 5s\n");
+        let path = file.path().to_str().unwrap();
+        let source = source(&["spv", "--config", path]);
+        let current = source.load().unwrap();
+
+        std::fs::write(path, "--probes cpu,memory\n--min-uptime 20s\n--watch memory > 50\n").unwrap();
+        let reloaded = source.reload(&current).unwrap();
+
+        assert_eq!(reloaded.min_uptime(), Some(Duration::from_secs(20)));
+        assert_eq!(
+            reloaded.watch().map(|watch| watch.to_string()),
+            Some("memory > 50".to_string())
+        );
+    }
+
+    #[test]
+    fn test_should_reject_reloaded_configuration_changing_fixed_options() {
+        let file = config_file("--probes cpu,memory\n");
+        let path = file.path().to_str().unwrap();
+        let source = source(&["spv", "--config", path]);
+        let current = source.load().unwrap();
+
+        std::fs::write(path, "--probes cpu\n--min-uptime 20s\n").unwrap();
+
+        assert!(matches!(
+            source.reload(&current),
+            Err(ConfigError::NotReloadable("--probes"))
+        ));
+    }
+
+    #[test]
+    fn test_should_reject_invalid_reloaded_configuration() {
+        let file = config_file("--min-uptime 5s\n");
+        let path = file.path().to_str().unwrap();
+        let source = source(&["spv", "--config", path]);
+        let current = source.load().unwrap();
+
+        std::fs::write(path, "--min-uptime soon\n").unwrap();
+
+        assert!(matches!(
+            source.reload(&current),
+            Err(ConfigError::InvalidValue("--min-uptime", _, _))
+        ));
+    }
+
+    #[test]
+    fn test_should_fail_to_read_missing_configuration_file() {
+        let ret = source(&["spv", "--config", "/non/existent/spv.conf"]).load();
+
+        assert!(matches!(ret, Err(ConfigError::UnreadableFile(_, _))));
+    }
+
+    #[test]
+    fn test_should_reject_unknown_option_of_configuration_file() {
+        let file = config_file("--frobnicate\n");
+        let path = file.path().to_str().unwrap();
+
+        let ret = source(&["spv", "--config", path]).load();
+
+        assert!(matches!(ret, Err(ConfigError::InvalidArguments(_))));
+    }
+}
+
+#[cfg(test)]
+mod test_check_reloadable {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use regex::Regex;
+    use tui::style::Color;
+
+    use crate::config::{Config, ConfigBuilder, ConfigError, OwnProcess, ProbeKind, Theme};
+    use crate::core::labels::LabelRule;
+
+    #[test]
+    fn test_should_accept_changes_of_live_options() {
+        let reloaded = ConfigBuilder::default()
+            .labels(vec![LabelRule::new(Regex::new("nginx").unwrap(), "web")])
+            .min_uptime(Duration::from_secs(5))
+            .sort_hysteresis(10.)
+            .own_process(OwnProcess::Hide)
+            .watch("cpu > 50".parse().unwrap())
+            .pin("cpu > 90".parse().unwrap())
+            .exec("kill -STOP {pid}".parse().unwrap())
+            .build()
+            .unwrap();
+
+        assert!(Config::default().check_reloadable(&reloaded).is_ok());
+    }
+
+    #[test]
+    fn test_should_reject_changes_of_collected_probes() {
+        let reloaded = ConfigBuilder::default().probes(vec![ProbeKind::Cpu]).build().unwrap();
+
+        assert!(matches!(
+            Config::default().check_reloadable(&reloaded),
+            Err(ConfigError::NotReloadable("--probes"))
+        ));
+    }
+
+    #[test]
+    fn test_should_reject_changes_of_refresh_period() {
+        let reloaded = ConfigBuilder::default()
+            .refresh_period(Duration::from_millis(250))
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            Config::default().check_reloadable(&reloaded),
+            Err(ConfigError::NotReloadable("--refresh-period"))
+        ));
+    }
+
+    #[test]
+    fn test_should_reject_changes_of_chart_colors() {
+        let reloaded = ConfigBuilder::default()
+            .theme(Theme::new(vec![Color::White]))
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            Config::default().check_reloadable(&reloaded),
+            Err(ConfigError::NotReloadable("--chart-colors"))
+        ));
+    }
+
+    #[test]
+    fn test_should_reject_changes_of_proc_root() {
+        let reloaded = ConfigBuilder::default()
+            .proc_root(PathBuf::from("/mnt/proc"))
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            Config::default().check_reloadable(&reloaded),
+            Err(ConfigError::NotReloadable("--proc-root"))
+        ));
+    }
+}
@@ -0,0 +1,154 @@
+//! Backoff of processes which repeatedly fail to be probed
+
+use std::collections::HashMap;
+
+use crate::core::process::Pid;
+
+/// Maximum amount of consecutive iterations during which a failing process is not probed
+const MAX_SKIPPED_ITERATIONS: u32 = 64;
+
+/// Tracks the consecutive probing failures of each process, to skip probing processes which keep failing
+///
+/// After `n` consecutive failures, a process is not probed during the next `2^(n-1) - 1` iterations, up to
+/// `MAX_SKIPPED_ITERATIONS`. A single success resets the schedule.
+#[derive(Default)]
+pub struct ProbingBackoff {
+    failing_processes: HashMap<Pid, FailureRecord>,
+}
+
+struct FailureRecord {
+    consecutive_failures: u32,
+    remaining_skips: u32,
+}
+
+impl ProbingBackoff {
+    /// Indicates if the process should not be probed during the current iteration
+    ///
+    /// This method must be called once per iteration for each process, as it consumes one of the skipped iterations.
+    pub fn should_skip(&mut self, pid: Pid) -> bool {
+        match self.failing_processes.get_mut(&pid) {
+            Some(record) if record.remaining_skips > 0 => {
+                record.remaining_skips -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Indicates if the process is currently skipped, without consuming any of its skipped iterations
+    pub fn is_backed_off(&self, pid: Pid) -> bool {
+        self.failing_processes
+            .get(&pid)
+            .is_some_and(|record| record.remaining_skips > 0)
+    }
+
+    /// Indicates that the process has successfully been probed
+    pub fn record_success(&mut self, pid: Pid) {
+        self.failing_processes.remove(&pid);
+    }
+
+    /// Indicates that the probing of the process failed
+    pub fn record_failure(&mut self, pid: Pid) {
+        let record = self.failing_processes.entry(pid).or_insert(FailureRecord {
+            consecutive_failures: 0,
+            remaining_skips: 0,
+        });
+
+        record.consecutive_failures += 1;
+        record.remaining_skips = skipped_iterations(record.consecutive_failures);
+    }
+
+    /// Forgets the failures of the given processes
+    pub fn cleanup(&mut self, pids: &[Pid]) {
+        for pid in pids {
+            self.failing_processes.remove(pid);
+        }
+    }
+}
+
+fn skipped_iterations(consecutive_failures: u32) -> u32 {
+    let exponent = consecutive_failures.saturating_sub(1).min(31);
+    (2_u32.pow(exponent) - 1).min(MAX_SKIPPED_ITERATIONS)
+}
+
+#[cfg(test)]
+mod test_probing_backoff {
+    use rstest::*;
+
+    use crate::core::backoff::{skipped_iterations, ProbingBackoff, MAX_SKIPPED_ITERATIONS};
+
+    fn count_skipped_iterations(backoff: &mut ProbingBackoff, pid: u32) -> u32 {
+        let mut skipped = 0;
+        while backoff.should_skip(pid) {
+            skipped += 1;
+        }
+        skipped
+    }
+
+    #[rstest]
+    #[case(1, 0)]
+    #[case(2, 1)]
+    #[case(3, 3)]
+    #[case(4, 7)]
+    #[case(7, 63)]
+    #[case(8, MAX_SKIPPED_ITERATIONS)]
+    #[case(100, MAX_SKIPPED_ITERATIONS)]
+    fn test_skipped_iterations_should_grow_exponentially(#[case] failures: u32, #[case] expected: u32) {
+        assert_eq!(skipped_iterations(failures), expected);
+    }
+
+    #[test]
+    fn test_should_not_skip_process_which_never_failed() {
+        let mut backoff = ProbingBackoff::default();
+
+        assert!(!backoff.should_skip(1));
+    }
+
+    #[test]
+    fn test_should_skip_process_for_increasing_iterations_after_each_failure() {
+        let mut backoff = ProbingBackoff::default();
+
+        let skipped: Vec<u32> = (0..4)
+            .map(|_| {
+                backoff.record_failure(1);
+                count_skipped_iterations(&mut backoff, 1)
+            })
+            .collect();
+
+        assert_eq!(skipped, vec![0, 1, 3, 7]);
+    }
+
+    #[test]
+    fn test_should_reset_backoff_on_success() {
+        let mut backoff = ProbingBackoff::default();
+        backoff.record_failure(1);
+        backoff.record_failure(1);
+        count_skipped_iterations(&mut backoff, 1);
+
+        backoff.record_success(1);
+        backoff.record_failure(1);
+
+        assert_eq!(count_skipped_iterations(&mut backoff, 1), 0);
+    }
+
+    #[test]
+    fn test_should_forget_failures_of_cleaned_up_processes() {
+        let mut backoff = ProbingBackoff::default();
+        backoff.record_failure(1);
+        backoff.record_failure(1);
+
+        backoff.cleanup(&[1]);
+
+        assert!(!backoff.should_skip(1));
+    }
+
+    #[test]
+    fn test_should_track_processes_independently() {
+        let mut backoff = ProbingBackoff::default();
+        backoff.record_failure(1);
+        backoff.record_failure(1);
+
+        assert!(backoff.should_skip(1));
+        assert!(!backoff.should_skip(2));
+    }
+}
@@ -0,0 +1,150 @@
+//! Detection of processes which rapidly spawn and die
+
+use std::collections::VecDeque;
+
+use crate::core::time::Timestamp;
+
+/// Over how many scans the churn rate is computed
+const CHURN_WINDOW_SCANS: usize = 10;
+
+/// Rate of spawned or dead processes per second, from which the churn is considered as high
+pub const HIGH_CHURN_THRESHOLD: f64 = 10.;
+
+/// Amount of processes spawned or dead per second over the latest scans
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub struct ChurnRate {
+    spawned_per_sec: f64,
+    dead_per_sec: f64,
+}
+
+impl ChurnRate {
+    pub fn new(spawned_per_sec: f64, dead_per_sec: f64) -> Self {
+        Self {
+            spawned_per_sec,
+            dead_per_sec,
+        }
+    }
+
+    /// Returns the amount of processes spawned per second
+    pub fn spawned_per_sec(&self) -> f64 {
+        self.spawned_per_sec
+    }
+
+    /// Returns the amount of processes which died per second
+    pub fn dead_per_sec(&self) -> f64 {
+        self.dead_per_sec
+    }
+
+    /// Indicates if processes spawn or die faster than `HIGH_CHURN_THRESHOLD`
+    pub fn is_high(&self) -> bool {
+        self.spawned_per_sec.max(self.dead_per_sec) >= HIGH_CHURN_THRESHOLD
+    }
+}
+
+/// Records the amount of spawned and dead processes of each scan, to compute the churn rate of the latest scans
+#[derive(Default)]
+pub struct ProcessChurn {
+    scans: VecDeque<ScanEvents>,
+}
+
+struct ScanEvents {
+    timestamp: Timestamp,
+    spawned: usize,
+    dead: usize,
+}
+
+impl ProcessChurn {
+    /// Records the amount of processes which spawned and died since the previous scan
+    pub fn record_scan(&mut self, timestamp: Timestamp, spawned: usize, dead: usize) {
+        // The events of the oldest scan are excluded from the rate, as they happened before the window began,
+        // hence the additional scan
+        if self.scans.len() > CHURN_WINDOW_SCANS {
+            self.scans.pop_front();
+        }
+
+        self.scans.push_back(ScanEvents {
+            timestamp,
+            spawned,
+            dead,
+        });
+    }
+
+    /// Returns the churn rate over the recorded scans
+    ///
+    /// At least two scans must have been recorded, and their timestamps must differ, to compute a non-zero rate.
+    pub fn rate(&self) -> ChurnRate {
+        let (oldest, latest) = match (self.scans.front(), self.scans.back()) {
+            (Some(oldest), Some(latest)) => (oldest, latest),
+            _ => return ChurnRate::default(),
+        };
+
+        let elapsed = latest.timestamp.duration_since(&oldest.timestamp).as_secs_f64();
+        if elapsed == 0. {
+            return ChurnRate::default();
+        }
+
+        let (spawned, dead) = self.scans.iter().skip(1).fold((0, 0), |(spawned, dead), scan| {
+            (spawned + scan.spawned, dead + scan.dead)
+        });
+
+        ChurnRate::new(spawned as f64 / elapsed, dead as f64 / elapsed)
+    }
+}
+
+#[cfg(test)]
+mod test_process_churn {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::core::churn::{ChurnRate, ProcessChurn, CHURN_WINDOW_SCANS, HIGH_CHURN_THRESHOLD};
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::core::time::Timestamp;
+
+    /// Records one scan per second, the latest one being recorded at `Timestamp::now()`
+    fn churn_from_scans(scans: &[(usize, usize)]) -> ProcessChurn {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let mut churn = ProcessChurn::default();
+
+        for (idx, (spawned, dead)) in scans.iter().enumerate() {
+            let age = Duration::from_secs((scans.len() - idx - 1) as u64);
+            churn.record_scan(Timestamp::now() - age, *spawned, *dead);
+        }
+
+        churn
+    }
+
+    #[test]
+    fn test_rate_should_be_zero_without_scan() {
+        assert_eq!(ProcessChurn::default().rate(), ChurnRate::default());
+    }
+
+    #[test]
+    fn test_rate_should_be_zero_after_single_scan() {
+        assert_eq!(churn_from_scans(&[(5, 5)]).rate(), ChurnRate::default());
+    }
+
+    #[rstest]
+    #[case(&[(0, 0), (4, 2)], ChurnRate::new(4., 2.))]
+    #[case(&[(0, 0), (4, 2), (2, 0)], ChurnRate::new(3., 1.))]
+    #[case(&[(100, 100), (3, 3), (3, 3), (0, 6)], ChurnRate::new(2., 4.))]
+    fn test_rate_should_ignore_events_of_oldest_scan(#[case] scans: &[(usize, usize)], #[case] expected: ChurnRate) {
+        assert_eq!(churn_from_scans(scans).rate(), expected);
+    }
+
+    #[test]
+    fn test_rate_should_only_cover_latest_scans() {
+        let mut scans = vec![(50, 50); 5];
+        scans.extend(vec![(1, 2); CHURN_WINDOW_SCANS + 1]);
+
+        assert_eq!(churn_from_scans(&scans).rate(), ChurnRate::new(1., 2.));
+    }
+
+    #[rstest]
+    #[case(ChurnRate::new(HIGH_CHURN_THRESHOLD, 0.), true)]
+    #[case(ChurnRate::new(0., HIGH_CHURN_THRESHOLD), true)]
+    #[case(ChurnRate::new(HIGH_CHURN_THRESHOLD - 1., HIGH_CHURN_THRESHOLD - 1.), false)]
+    fn test_churn_should_be_high_from_threshold(#[case] rate: ChurnRate, #[case] expected: bool) {
+        assert_eq!(rate.is_high(), expected);
+    }
+}
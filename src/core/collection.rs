@@ -3,7 +3,11 @@
 use std::cmp::Ordering;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::time::Duration;
 
+use log::warn;
+
+use crate::core::backoff::ProbingBackoff;
 use crate::core::metrics::{DatedMetric, Metric};
 use crate::core::probe::Probe;
 use crate::core::process::Pid;
@@ -32,6 +36,26 @@ pub trait MetricCollector {
     ///  * `pids`: The IDs of the processes to cleanup
     fn cleanup(&mut self, pids: &[Pid]);
 
+    /// Discards the metrics collected before the given timestamp, to bound the memory used by the collector.
+    ///
+    /// # Arguments
+    ///  * `oldest`: The timestamp of the oldest metrics to retain
+    fn purge(&mut self, oldest: Timestamp);
+
+    /// Merges the metrics collected before the given timestamp into buckets, keeping the highest metric of each
+    /// bucket, to bound the memory used by the collector while preserving the shape of the older metrics.
+    ///
+    /// # Arguments
+    ///  * `older_than`: The timestamp of the oldest metrics to leave untouched
+    ///  * `bucket`: The duration covered by each bucket
+    fn compact(&mut self, older_than: Timestamp, bucket: Duration);
+
+    /// Discards all the metrics collected for the given process, as if it had never been collected.
+    ///
+    /// # Arguments
+    ///  * `pid`: The ID of the process whose metrics are discarded
+    fn clear(&mut self, pid: Pid);
+
     /// Probes metrics for the given processes, without storing them.
     ///
     /// Some probe implementations require an initial measurement to be calibrated. As this first
@@ -41,6 +65,16 @@ pub trait MetricCollector {
     ///  * `pids`: A slice containing the [`Pids`](Pid) to probe.
     fn calibrate(&mut self, pids: &[Pid]) -> Result<(), Error>;
 
+    /// Probes metrics for the given processes between two collections, to catch the spikes that they would miss.
+    ///
+    /// Only the collectors which are subsampled take samples, which are aggregated into their next collected metrics.
+    ///
+    /// # Arguments
+    ///  * `pids`: A slice containing the [`Pids`](Pid) to probe.
+    fn sample(&mut self, _pids: &[Pid]) -> Result<(), Error> {
+        Ok(())
+    }
+
     /// Compares two processes by their last collected metric.
     ///
     /// As we do not allow comparison between [`Metric`](Metric) trait objects, we
@@ -55,7 +89,7 @@ pub trait MetricCollector {
     fn compare_pids_by_last_metrics(&self, pid1: Pid, pid2: Pid) -> Ordering;
 
     /// Returns a name describing the collected metrics.
-    fn name(&self) -> &'static str;
+    fn name(&self) -> &str;
 
     /// Builds a [`MetricView`](MetricView), offering insight on the collected
     /// metrics of a given process.
@@ -63,22 +97,127 @@ pub trait MetricCollector {
     /// # Arguments
     ///  * `pid`: The ID of the process for which to view metrics
     ///  * `span`: The time period covered by the metric view. Metrics adjacent to this span are also returned.
-    fn view(&self, pid: Pid, span: Span) -> MetricView;
+    fn view(&self, pid: Pid, span: Span) -> MetricView<'_>;
+
+    /// Returns the average value of the metrics collected for a given process during the given span
+    ///
+    /// Sorting processes by this average, rather than by their last metrics, prevents momentary spikes from reordering
+    /// them.
+    ///
+    /// # Arguments
+    ///  * `pid`: The ID of the process
+    ///  * `span`: The time period over which the metrics are averaged
+    fn span_average(&self, pid: Pid, span: Span) -> f64 {
+        self.view(pid, span).average_f64()
+    }
 
     /// Builds a [`MetricsOverview`](MetricsOverview), containing the last metrics
     /// of all running processes.
-    fn overview(&self) -> MetricsOverview;
+    fn overview(&self) -> MetricsOverview<'_>;
+
+    /// Returns a copy of the last metric collected for a given process, if any metric has been collected for it
+    ///
+    /// # Arguments
+    ///  * `pid`: The ID of the process
+    fn last_metric(&self, pid: Pid) -> Option<Box<dyn Metric>>;
+
+    /// Returns the timestamp of the oldest metric retained for a given process, if any metric has been collected for it
+    ///
+    /// # Arguments
+    ///  * `pid`: The ID of the process
+    fn first_timestamp(&self, pid: Pid) -> Option<Timestamp>;
+
+    /// Indicates how many iterations separate two collections: a collector with a divisor of 5 only collects metrics
+    /// every 5th iteration.
+    ///
+    /// Metrics which change slowly can be collected less often than others, to save resources.
+    fn refresh_divisor(&self) -> usize {
+        1
+    }
+
+    /// Indicates if metrics should be collected during the given iteration, according to the refresh divisor
+    ///
+    /// # Arguments
+    ///  * `iteration`: The index of the iteration, starting at 0 for the first collection
+    fn is_due(&self, iteration: usize) -> bool {
+        iteration.is_multiple_of(self.refresh_divisor().max(1))
+    }
+
+    /// Returns the outcome of probing each process during the latest collection
+    ///
+    /// Processes which were not probed, for instance because they are backed off, have no outcome.
+    fn latest_outcomes(&self) -> &[ProbeOutcome] {
+        &[]
+    }
+
+    /// Sets how many decimals the views of the collector represent metrics with
+    ///
+    /// By default, each type of metric is represented with its own precision.
+    fn set_precision(&mut self, _precision: usize) {}
+
+    /// Makes the collector take samples between two collections, so that each collected metric is the highest of the
+    /// samples taken since the previous collection
+    ///
+    /// Collectors which can not be subsampled ignore this call.
+    fn set_subsampled(&mut self) {}
+
+    /// Switches to the next source of metrics, for collectors reporting several aspects of a same resource
+    ///
+    /// Collectors with a single source of metrics ignore this call.
+    fn cycle_source(&mut self) {}
+
+    /// Returns the label of the source of the reported metrics, for collectors with several sources
+    fn source_label(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// The outcome of an attempt to probe a process
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ProbeOutcome {
+    Success,
+    /// The probe was not allowed to access the data of the process
+    PermissionDenied,
+    Failure,
+}
+
+/// Share of the attempts to probe processes which must be denied to consider that spv lacks permissions
+const PERMISSION_DENIED_THRESHOLD: f64 = 0.25;
+
+/// Indicates if the permission to probe processes was denied so often that spv most likely lacks the privileges to
+/// monitor processes which do not belong to the current user
+///
+/// # Arguments
+///  * `outcomes`: The outcomes of the attempts to probe processes, across all collectors
+pub fn lacks_permissions(outcomes: &[ProbeOutcome]) -> bool {
+    let denied = outcomes
+        .iter()
+        .filter(|outcome| **outcome == ProbeOutcome::PermissionDenied)
+        .count();
+
+    !outcomes.is_empty() && denied as f64 / outcomes.len() as f64 >= PERMISSION_DENIED_THRESHOLD
 }
 
 /// An implementation of [`MetricCollector`](MetricCollector)
 ///
-/// Uses a [`Probe`](Probe) object to probe metrics.
+/// Uses a [`Probe`](Probe) object to probe metrics.<br/>
+/// Processes which repeatedly fail to be probed are skipped for an increasing number of iterations, during which
+/// their default metric is collected.<br/>
+/// When subsampled, the collector keeps the highest of the metrics sampled between two collections, and collects it
+/// unless the metric probed during the collection is higher.
 pub struct ProbeCollector<M>
 where
     M: Metric + Copy + PartialOrd + Default,
 {
     collection: MetricCollection<M>,
     probe: Box<dyn Probe<M>>,
+    backoff: ProbingBackoff,
+    refresh_divisor: usize,
+    latest_outcomes: Vec<ProbeOutcome>,
+    precision: Option<usize>,
+    subsampled: bool,
+    // The highest metric sampled for each process since the latest collection
+    samples: HashMap<Pid, M>,
 }
 
 impl<M: 'static> ProbeCollector<M>
@@ -89,6 +228,47 @@ where
         Self {
             collection: MetricCollection::<M>::new(),
             probe: Box::new(probe),
+            backoff: ProbingBackoff::default(),
+            refresh_divisor: 1,
+            latest_outcomes: vec![],
+            precision: None,
+            subsampled: false,
+            samples: HashMap::new(),
+        }
+    }
+
+    /// Only collects metrics every `refresh_divisor` iterations
+    ///
+    /// Panics if `refresh_divisor` is 0.
+    pub fn with_refresh_divisor(mut self, refresh_divisor: usize) -> Self {
+        assert!(
+            refresh_divisor > 0,
+            "The refresh divisor of a collector must be greater than 0"
+        );
+        self.refresh_divisor = refresh_divisor;
+        self
+    }
+
+    fn probe_with_backoff(&mut self, pid: Pid) -> M {
+        if self.backoff.should_skip(pid) {
+            return M::default();
+        }
+
+        match self.probe.probe(pid) {
+            Ok(metric) => {
+                self.backoff.record_success(pid);
+                self.latest_outcomes.push(ProbeOutcome::Success);
+                metric
+            }
+            Err(e) => {
+                warn!("Could not probe {} metric for pid {}: {}", self.probe.name(), pid, e);
+                self.latest_outcomes.push(match e.is_permission_denied() {
+                    true => ProbeOutcome::PermissionDenied,
+                    false => ProbeOutcome::Failure,
+                });
+                self.backoff.record_failure(pid);
+                M::default()
+            }
         }
     }
 }
@@ -98,23 +278,64 @@ where
     M: Metric + Copy + PartialOrd + Default,
 {
     fn collect(&mut self, pids: &[Pid]) -> Result<(), Error> {
-        let metrics = self.probe.probe_processes(pids)?;
-
-        for (pid, m) in metrics.into_iter() {
-            self.collection.push(pid, m);
+        self.probe.init_iteration()?;
+        self.latest_outcomes.clear();
+
+        for pid in pids {
+            let mut metric = self.probe_with_backoff(*pid);
+            if let Some(sample) = self.samples.remove(pid) {
+                metric = highest(metric, sample);
+            }
+            self.collection.push(*pid, metric);
         }
+        self.samples.clear();
 
         Ok(())
     }
 
     fn cleanup(&mut self, pids: &[Pid]) {
         self.probe.cleanup(pids);
+        self.backoff.cleanup(pids);
+        pids.iter().for_each(|pid| {
+            self.samples.remove(pid);
+        });
+    }
+
+    fn purge(&mut self, oldest: Timestamp) {
+        self.collection.purge(oldest);
+    }
+
+    fn compact(&mut self, older_than: Timestamp, bucket: Duration) {
+        self.collection.compact(older_than, bucket);
+    }
+
+    fn clear(&mut self, pid: Pid) {
+        self.collection.clear(pid);
+        self.samples.remove(&pid);
     }
 
     fn calibrate(&mut self, pids: &[Pid]) -> Result<(), Error> {
         self.probe.probe_processes(pids).map(|_| ())
     }
 
+    fn sample(&mut self, pids: &[Pid]) -> Result<(), Error> {
+        if !self.subsampled {
+            return Ok(());
+        }
+
+        self.probe.init_iteration()?;
+
+        // Failures are left to the next collection to report, and backed off processes are not sampled
+        for pid in pids.iter().filter(|pid| !self.backoff.is_backed_off(**pid)) {
+            if let Ok(metric) = self.probe.probe(*pid) {
+                let sample = self.samples.entry(*pid).or_insert(metric);
+                *sample = highest(*sample, metric);
+            }
+        }
+
+        Ok(())
+    }
+
     fn compare_pids_by_last_metrics(&self, pid1: Pid, pid2: Pid) -> Ordering {
         let last_pid1 = self.collection.last_or_default(pid1);
         let last_pid2 = self.collection.last_or_default(pid2);
@@ -122,16 +343,76 @@ where
         last_pid1.partial_cmp(last_pid2).unwrap_or(Ordering::Equal)
     }
 
-    fn name(&self) -> &'static str {
+    fn name(&self) -> &str {
         self.probe.name()
     }
 
-    fn view(&self, pid: Pid, span: Span) -> MetricView {
-        self.collection.view(pid, span)
+    fn view(&self, pid: Pid, span: Span) -> MetricView<'_> {
+        self.collection.view(pid, span).with_precision(self.precision)
+    }
+
+    fn latest_outcomes(&self) -> &[ProbeOutcome] {
+        &self.latest_outcomes
+    }
+
+    fn overview(&self) -> MetricsOverview<'_> {
+        self.collection.overview().with_precision(self.precision)
     }
 
-    fn overview(&self) -> MetricsOverview {
-        self.collection.overview()
+    fn last_metric(&self, pid: Pid) -> Option<Box<dyn Metric>> {
+        self.collection
+            .last(pid)
+            .map(|metric| Box::new(*metric) as Box<dyn Metric>)
+    }
+
+    fn first_timestamp(&self, pid: Pid) -> Option<Timestamp> {
+        self.collection.first_timestamp(pid)
+    }
+
+    fn set_precision(&mut self, precision: usize) {
+        self.precision = Some(precision);
+    }
+
+    fn set_subsampled(&mut self) {
+        self.subsampled = true;
+    }
+
+    fn refresh_divisor(&self) -> usize {
+        self.refresh_divisor
+    }
+}
+
+/// Returns the highest of two metrics, or the first one if they can not be compared
+fn highest<M: PartialOrd>(metric: M, other: M) -> M {
+    match other.partial_cmp(&metric) {
+        Some(Ordering::Greater) => other,
+        _ => metric,
+    }
+}
+
+#[cfg(test)]
+mod test_lacks_permissions {
+    use rstest::rstest;
+
+    use crate::core::collection::{lacks_permissions, ProbeOutcome};
+
+    const S: ProbeOutcome = ProbeOutcome::Success;
+    const D: ProbeOutcome = ProbeOutcome::PermissionDenied;
+    const F: ProbeOutcome = ProbeOutcome::Failure;
+
+    #[rstest]
+    #[case(&[D, S, S, S], true)]
+    #[case(&[D, D, S, S], true)]
+    #[case(&[D, S, S, S, S], false)]
+    #[case(&[D, F, F, S, S], false)]
+    #[case(&[S, S, F, F], false)]
+    #[case(&[D], true)]
+    #[case(&[], false)]
+    fn test_should_detect_lack_of_permissions_from_share_of_denied_probings(
+        #[case] outcomes: &[ProbeOutcome],
+        #[case] expected: bool,
+    ) {
+        assert_eq!(lacks_permissions(outcomes), expected);
     }
 }
 
@@ -143,8 +424,8 @@ mod test_probe_collector {
 
     use rstest::rstest;
 
-    use crate::core::collection::{MetricCollector, ProbeCollector};
-    use crate::core::metrics::PercentMetric;
+    use crate::core::collection::{MetricCollector, ProbeCollector, ProbeOutcome};
+    use crate::core::metrics::{Metric, PercentMetric};
     use crate::core::probe::fakes::FakeProbe;
     use crate::core::process::Pid;
     use crate::core::time::{Span, Timestamp};
@@ -187,6 +468,18 @@ mod test_probe_collector {
         assert_eq!(collector.compare_pids_by_last_metrics(1, 2), Ordering::Greater);
     }
 
+    #[test]
+    fn test_collector_should_copy_last_metric_of_collected_process_only() {
+        let mut collector = create_collector_with_map(hashmap!(1 => 50.));
+        collector.collect(&[1]).unwrap();
+
+        assert_eq!(
+            collector.last_metric(1).as_deref(),
+            Some(&PercentMetric::new(50.) as &dyn Metric)
+        );
+        assert!(collector.last_metric(2).is_none());
+    }
+
     #[rstest]
     fn test_process_metrics_should_be_empty_when_not_collected() {
         let collector = create_collector_with_map(hashmap!());
@@ -218,6 +511,220 @@ mod test_probe_collector {
 
         assert_eq!(view.as_slice().len(), 1);
     }
+
+    #[rstest]
+    fn test_collector_should_empty_view_of_cleared_process_only() {
+        let mut collector = create_collector_with_map(hashmap!(1 => 10., 2 => 20.));
+        collector.collect(&[1, 2]).unwrap();
+
+        collector.clear(1);
+
+        let span = Span::new(Timestamp::now(), Timestamp::now() + Duration::from_secs(60));
+        assert!(collector.view(1, span).as_slice().is_empty());
+        assert_eq!(collector.view(2, span).as_slice().len(), 1);
+        assert!(collector.last_metric(1).is_none());
+    }
+
+    #[rstest]
+    fn test_collector_should_collect_default_metric_when_probing_fails() {
+        let mut probe = FakeProbe::from_percent_map(hashmap!(1 => 10.));
+        probe.make_pid_fail(2);
+        let mut collector = ProbeCollector::new(probe);
+
+        collector.collect(&[1, 2]).unwrap();
+
+        assert_eq!(collector.overview().last_or_default(2).as_f64(0).unwrap(), 0.);
+    }
+
+    #[rstest]
+    fn test_collector_should_back_off_process_failing_repeatedly() {
+        let mut probe = FakeProbe::<PercentMetric>::new();
+        probe.make_pid_fail(1);
+        let mut collector = ProbeCollector::new(probe);
+
+        collector.collect(&[1]).unwrap();
+        collector.collect(&[1]).unwrap();
+
+        assert!(collector.backoff.should_skip(1));
+    }
+
+    #[rstest]
+    fn test_collector_should_forget_backoff_of_cleaned_up_process() {
+        let mut probe = FakeProbe::<PercentMetric>::new();
+        probe.make_pid_fail(1);
+        let mut collector = ProbeCollector::new(probe);
+        collector.collect(&[1]).unwrap();
+        collector.collect(&[1]).unwrap();
+
+        collector.cleanup(&[1]);
+
+        assert!(!collector.backoff.should_skip(1));
+    }
+
+    #[rstest]
+    fn test_collector_should_record_outcome_of_latest_probings() {
+        let mut probe = FakeProbe::from_percent_map(hashmap!(1 => 10.));
+        probe.make_pid_fail(2);
+        probe.make_pid_denied(3);
+        let mut collector = ProbeCollector::new(probe);
+
+        collector.collect(&[1, 2, 3]).unwrap();
+
+        assert_eq!(
+            collector.latest_outcomes(),
+            [
+                ProbeOutcome::Success,
+                ProbeOutcome::Failure,
+                ProbeOutcome::PermissionDenied
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_collector_should_not_record_outcome_of_backed_off_process() {
+        let mut probe = FakeProbe::<PercentMetric>::new();
+        probe.make_pid_denied(1);
+        let mut collector = ProbeCollector::new(probe);
+        collector.collect(&[1]).unwrap();
+        collector.collect(&[1]).unwrap();
+
+        collector.collect(&[1]).unwrap();
+
+        assert!(collector.latest_outcomes().is_empty());
+    }
+
+    #[rstest]
+    fn test_collector_views_should_represent_metrics_with_set_precision() {
+        let mut collector = create_collector_with_map(hashmap!(1 => 12.345));
+        collector.collect(&[1]).unwrap();
+
+        collector.set_precision(2);
+
+        let span = Span::new(Timestamp::now(), Timestamp::now() + Duration::from_secs(60));
+        assert_eq!(collector.overview().concise_repr(1), "12.35");
+        assert_eq!(collector.view(1, span).concise_repr_of_value(5.), "5.00");
+    }
+
+    #[test]
+    fn test_collector_should_be_due_every_iteration_by_default() {
+        let collector = create_collector_with_map(hashmap!());
+
+        assert!((0..10).all(|iteration| collector.is_due(iteration)));
+    }
+
+    #[test]
+    fn test_collector_should_only_be_due_every_divisor_iterations() {
+        let collector = create_collector_with_map(hashmap!()).with_refresh_divisor(5);
+
+        let due_iterations: Vec<_> = (0..12).filter(|iteration| collector.is_due(*iteration)).collect();
+
+        assert_eq!(due_iterations, vec![0, 5, 10]);
+    }
+}
+
+#[cfg(test)]
+mod test_subsampling {
+    use std::collections::VecDeque;
+
+    use rstest::*;
+
+    use crate::core::collection::{MetricCollector, ProbeCollector};
+    use crate::core::metrics::PercentMetric;
+    use crate::core::probe::Probe;
+    use crate::core::process::Pid;
+    use crate::core::Error;
+
+    /// Probes the given values one after the other, whichever the process
+    struct SequenceProbe {
+        values: VecDeque<f64>,
+    }
+
+    impl Probe<PercentMetric> for SequenceProbe {
+        fn name(&self) -> &'static str {
+            "sequence"
+        }
+
+        fn probe(&mut self, pid: Pid) -> Result<PercentMetric, Error> {
+            self.values
+                .pop_front()
+                .map(PercentMetric::new)
+                .ok_or(Error::InvalidPID(pid))
+        }
+
+        fn cleanup(&mut self, _pids: &[Pid]) {}
+    }
+
+    fn build_collector(values: &[f64]) -> ProbeCollector<PercentMetric> {
+        let mut collector = ProbeCollector::new(SequenceProbe {
+            values: values.iter().copied().collect(),
+        });
+        collector.set_subsampled();
+        collector
+    }
+
+    fn last_metric(collector: &ProbeCollector<PercentMetric>) -> f64 {
+        collector.overview().last_or_default(1).as_f64(0).unwrap()
+    }
+
+    #[rstest]
+    #[case(&[10., 80., 20., 30., 15.], 80.)]
+    #[case(&[10., 20., 30., 40., 50.], 50.)]
+    #[case(&[0., 0., 0., 0., 0.], 0.)]
+    fn test_should_collect_highest_of_samples_and_collected_metric(#[case] values: &[f64], #[case] expected: f64) {
+        let mut collector = build_collector(values);
+
+        (0..values.len() - 1).for_each(|_| collector.sample(&[1]).unwrap());
+        collector.collect(&[1]).unwrap();
+
+        assert_eq!(last_metric(&collector), expected);
+    }
+
+    #[rstest]
+    fn test_should_only_aggregate_samples_since_latest_collection() {
+        let mut collector = build_collector(&[90., 10., 20., 30.]);
+        collector.sample(&[1]).unwrap();
+        collector.collect(&[1]).unwrap();
+
+        collector.sample(&[1]).unwrap();
+        collector.sample(&[1]).unwrap();
+        collector.collect(&[1]).unwrap();
+
+        assert_eq!(last_metric(&collector), 30.);
+    }
+
+    #[rstest]
+    fn test_should_keep_highest_sample_when_collection_fails() {
+        let mut collector = build_collector(&[10., 50.]);
+        collector.sample(&[1]).unwrap();
+        collector.sample(&[1]).unwrap();
+
+        collector.collect(&[1]).unwrap();
+
+        assert_eq!(last_metric(&collector), 50.);
+    }
+
+    #[rstest]
+    fn test_should_not_sample_when_not_subsampled() {
+        let mut collector = ProbeCollector::new(SequenceProbe {
+            values: VecDeque::from([80., 10.]),
+        });
+
+        collector.sample(&[1]).unwrap();
+        collector.collect(&[1]).unwrap();
+
+        assert_eq!(last_metric(&collector), 80.);
+    }
+
+    #[rstest]
+    fn test_should_forget_samples_of_cleaned_up_processes() {
+        let mut collector = build_collector(&[80., 10.]);
+        collector.sample(&[1]).unwrap();
+
+        collector.cleanup(&[1]);
+        collector.collect(&[1]).unwrap();
+
+        assert_eq!(last_metric(&collector), 10.);
+    }
 }
 
 /// MetricCollection manages ProcessData instances to store processes' metrics.<br/>
@@ -249,6 +756,35 @@ where
         process_data.push(metric);
     }
 
+    /// Discards the metrics older than `oldest`, and forgets the processes which have no metric left
+    pub fn purge(&mut self, oldest: Timestamp) {
+        self.processes_data.retain(|_, pd| {
+            pd.purge(oldest);
+            pd.last().is_some()
+        });
+    }
+
+    /// Merges the metrics older than `older_than` into buckets of the given duration, keeping the highest of each
+    pub fn compact(&mut self, older_than: Timestamp, bucket: Duration) {
+        self.processes_data
+            .values_mut()
+            .for_each(|pd| pd.compact(older_than, bucket));
+    }
+
+    /// Forgets all the metrics of the given process
+    pub fn clear(&mut self, pid: Pid) {
+        self.processes_data.remove(&pid);
+    }
+
+    pub fn last(&self, pid: Pid) -> Option<&M> {
+        self.processes_data.get(&pid).and_then(|pd| pd.last())
+    }
+
+    /// Returns the timestamp of the oldest metric of the given process, if it has any
+    pub fn first_timestamp(&self, pid: Pid) -> Option<Timestamp> {
+        self.processes_data.get(&pid).and_then(|pd| pd.first_timestamp())
+    }
+
     pub fn last_or_default(&self, pid: Pid) -> &M {
         self.processes_data
             .get(&pid)
@@ -256,7 +792,7 @@ where
             .unwrap_or(&self.default)
     }
 
-    pub fn view(&self, pid: Pid, span: Span) -> MetricView {
+    pub fn view(&self, pid: Pid, span: Span) -> MetricView<'_> {
         self.processes_data
             .get(&pid)
             .map(|pd| pd.view(span))
@@ -267,22 +803,31 @@ where
         MetricView::new(vec![], Box::new(M::default()) as Box<dyn Metric>, span)
     }
 
-    pub fn overview(&self) -> MetricsOverview {
+    pub fn overview(&self) -> MetricsOverview<'_> {
         let last_metrics = self
             .processes_data
             .keys()
             .copied()
             .map(|pid| (pid, self.last_or_default(pid) as &dyn Metric))
             .collect();
+        let previous_metrics = self
+            .processes_data
+            .iter()
+            .filter_map(|(pid, pd)| pd.previous().map(|metric| (*pid, metric as &dyn Metric)))
+            .collect();
 
-        MetricsOverview::new(last_metrics, &self.default)
+        MetricsOverview::new(last_metrics, &self.default).with_previous_metrics(previous_metrics)
     }
 }
 
 #[cfg(test)]
 mod test_metric_collection {
+    use std::time::Duration;
+
     use crate::core::collection::MetricCollection;
     use crate::core::metrics::PercentMetric;
+    use crate::core::time::test_utils::advance_time_and_refresh_timestamp;
+    use crate::core::time::Timestamp;
 
     #[test]
     fn test_should_return_default_when_no_metric() {
@@ -298,6 +843,63 @@ mod test_metric_collection {
 
         assert_eq!(collection.last_or_default(1), &PercentMetric::new(2.));
     }
+
+    #[test]
+    fn test_should_forget_processes_without_metrics_after_purge() {
+        let mut collection = MetricCollection::<PercentMetric>::new();
+        collection.push(1, PercentMetric::new(1.));
+        advance_time_and_refresh_timestamp(Duration::from_secs(1));
+        collection.push(2, PercentMetric::new(2.));
+
+        collection.purge(Timestamp::now());
+
+        assert_eq!(collection.processes_data.keys().collect::<Vec<_>>(), vec![&2]);
+    }
+
+    #[test]
+    fn test_should_only_forget_cleared_process() {
+        let mut collection = MetricCollection::<PercentMetric>::new();
+        collection.push(1, PercentMetric::new(1.));
+        collection.push(2, PercentMetric::new(2.));
+
+        collection.clear(1);
+
+        assert_eq!(collection.last(1), None);
+        assert_eq!(collection.last(2), Some(&PercentMetric::new(2.)));
+    }
+
+    #[test]
+    fn test_should_return_timestamp_of_oldest_retained_metric() {
+        let mut collection = MetricCollection::<PercentMetric>::new();
+        let first = Timestamp::now();
+        collection.push(1, PercentMetric::new(1.));
+        advance_time_and_refresh_timestamp(Duration::from_secs(1));
+        let second = Timestamp::now();
+        collection.push(1, PercentMetric::new(2.));
+
+        assert_eq!(collection.first_timestamp(1), Some(first));
+        assert_eq!(collection.first_timestamp(2), None);
+
+        collection.purge(second);
+
+        assert_eq!(collection.first_timestamp(1), Some(second));
+    }
+
+    #[test]
+    fn test_compact_should_reduce_metrics_of_each_process() {
+        let mut collection = MetricCollection::<PercentMetric>::new();
+        for value in [1., 8., 2., 3.] {
+            collection.push(1, PercentMetric::new(value));
+            collection.push(2, PercentMetric::new(value / 2.));
+            advance_time_and_refresh_timestamp(Duration::from_secs(1));
+        }
+
+        collection.compact(Timestamp::now(), Duration::from_secs(3600));
+
+        assert_eq!(collection.processes_data[&1].metrics.len(), 1);
+        assert_eq!(collection.last(1), Some(&PercentMetric::new(8.)));
+        assert_eq!(collection.last(2), Some(&PercentMetric::new(4.)));
+    }
 }
 
 /// Just like `DatedMetric`, except here the metric type is a concrete type
@@ -336,16 +938,56 @@ where
         self.metrics.last().map(|m| &m.metric)
     }
 
-    pub fn view(&self, span: Span) -> MetricView {
+    /// Returns the metric collected before the latest one, if any
+    pub fn previous(&self) -> Option<&M> {
+        self.metrics.iter().rev().nth(1).map(|m| &m.metric)
+    }
+
+    pub fn first_timestamp(&self) -> Option<Timestamp> {
+        self.metrics.first().map(|m| m.timestamp)
+    }
+
+    /// Discards the metrics older than `oldest`
+    pub fn purge(&mut self, oldest: Timestamp) {
+        let retained_index = self.metrics.partition_point(|cdm| cdm.timestamp < oldest);
+        self.metrics.drain(..retained_index);
+    }
+
+    /// Merges the metrics older than `older_than` into buckets of the given duration, keeping the highest metric of
+    /// each bucket along with its timestamp
+    ///
+    /// Buckets are aligned on the start of the application, so that compacting the same metrics again leaves them
+    /// unchanged.
+    pub fn compact(&mut self, older_than: Timestamp, bucket: Duration)
+    where
+        M: PartialOrd,
+    {
+        let compacted_index = self.metrics.partition_point(|cdm| cdm.timestamp < older_than);
+        let mut compacted: Vec<ConcreteDatedMetric<M>> = Vec::new();
+
+        for cdm in self.metrics.drain(..compacted_index) {
+            match compacted.last_mut() {
+                Some(kept) if bucket_index(kept.timestamp, bucket) == bucket_index(cdm.timestamp, bucket) => {
+                    if cdm.metric.partial_cmp(&kept.metric) == Some(Ordering::Greater) {
+                        *kept = cdm;
+                    }
+                }
+                _ => compacted.push(cdm),
+            }
+        }
+
+        self.metrics.splice(..0, compacted);
+    }
+
+    pub fn view(&self, span: Span) -> MetricView<'_> {
         let metrics = self.extract_metrics_around_span(&span);
         let default = Box::new(M::default()) as Box<dyn Metric>;
         MetricView::new(metrics, default, span)
     }
 
     /** Builds and returns a sorted list of dated metrics who are included in the span, or adjacent to this span **/
-    fn extract_metrics_around_span(&self, span: &Span) -> Vec<DatedMetric> {
-        return self
-            .metrics
+    fn extract_metrics_around_span(&self, span: &Span) -> Vec<DatedMetric<'_>> {
+        self.metrics
             .iter()
             .enumerate()
             .filter(|(idx, cdm)| {
@@ -354,8 +996,19 @@ where
                     || (idx < &(self.metrics.len() - 1) && span.contains(self.metrics[idx + 1].timestamp))
             })
             .map(|(_, cdm)| DatedMetric::new(&cdm.metric as &dyn Metric, cdm.timestamp))
-            .collect();
+            .collect()
+    }
+}
+
+/// Returns the index of the bucket of the given duration containing the timestamp, counting from the start of the
+/// application
+fn bucket_index(timestamp: Timestamp, bucket: Duration) -> u128 {
+    let app_init = Timestamp::app_init();
+    if timestamp <= app_init {
+        return 0;
     }
+
+    timestamp.duration_since(&app_init).as_nanos() / bucket.as_nanos().max(1)
 }
 
 #[cfg(test)]
@@ -377,7 +1030,7 @@ mod test_process_data {
     fn build_process_data_and_push(metrics: &[f64]) -> ProcessData<PercentMetric> {
         let mut process_data = ProcessData::new();
 
-        metrics.into_iter().for_each(|v| {
+        metrics.iter().for_each(|v| {
             advance_time_and_refresh_timestamp(Duration::from_secs(1));
             process_data.push(PercentMetric::new(*v));
         });
@@ -387,7 +1040,7 @@ mod test_process_data {
 
     // Builds PercentMetric instances from `percent_values` and compares them to the metrics in the view
     fn assert_view_metrics_equals_percent_metrics(view: &MetricView, percent_values: &[f64]) {
-        let pct_metrics: Vec<PercentMetric> = percent_values.iter().copied().map(|v| PercentMetric::new(v)).collect();
+        let pct_metrics: Vec<PercentMetric> = percent_values.iter().copied().map(PercentMetric::new).collect();
 
         let pct_dyn_metrics: Vec<&dyn Metric> = pct_metrics.iter().map(|p| p as &dyn Metric).collect();
 
@@ -406,6 +1059,25 @@ mod test_process_data {
         assert_view_metrics_equals_percent_metrics(&view, &[]);
     }
 
+    #[rstest]
+    #[case(&[], None)]
+    #[case(&[10.], None)]
+    #[case(&[10., 20., 30.], Some(20.))]
+    fn test_previous_should_be_metric_before_last(#[case] metrics: &[f64], #[case] expected: Option<f64>) {
+        let process_data = build_process_data_and_push(metrics);
+
+        assert_eq!(process_data.previous().copied(), expected.map(PercentMetric::new));
+    }
+
+    #[rstest]
+    fn test_view_should_average_metrics_in_span() {
+        let process_data = build_process_data_and_push(&[10., 20., 30., 40.]);
+
+        let span = Span::new(Timestamp::now() - Duration::from_secs(2), Timestamp::now());
+
+        assert_eq!(process_data.view(span).average_f64(), 30.);
+    }
+
     #[rstest]
     fn test_view_should_include_metrics_in_span() {
         let process_data = build_process_data_and_push(&[0., 1., 2., 3.]);
@@ -494,4 +1166,52 @@ mod test_process_data {
 
         assert_eq!(view.max_f64(), 2.);
     }
+
+    #[rstest]
+    fn test_purge_should_discard_metrics_older_than_given_timestamp() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let mut process_data = build_process_data_and_push(&[0., 1., 2., 3.]);
+
+        process_data.purge(Timestamp::now() - Duration::from_secs(1));
+
+        let span = Span::new(Timestamp::now() - Duration::from_secs(10), Timestamp::now());
+        assert_view_metrics_equals_percent_metrics(&process_data.view(span), &[2., 3.]);
+    }
+
+    #[rstest]
+    fn test_compact_should_keep_highest_metric_of_each_bucket() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        // Metrics are pushed from 301s to 308s after the start of the application, so that buckets of 3s hold the
+        // metrics pushed at 301-302s, 303-305s and 306-308s
+        let mut process_data = build_process_data_and_push(&[1., 7., 3., 2., 9., 4., 5., 6.]);
+
+        process_data.compact(Timestamp::now() - Duration::from_secs(2), Duration::from_secs(3));
+
+        let span = Span::new(Timestamp::now() - Duration::from_secs(20), Timestamp::now());
+        assert_eq!(process_data.metrics.len(), 5);
+        assert_view_metrics_equals_percent_metrics(&process_data.view(span), &[7., 9., 4., 5., 6.]);
+    }
+
+    #[rstest]
+    fn test_compact_should_leave_recent_metrics_untouched() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let mut process_data = build_process_data_and_push(&[1., 2., 3., 4.]);
+
+        process_data.compact(Timestamp::now() - Duration::from_secs(10), Duration::from_secs(60));
+
+        let span = Span::new(Timestamp::now() - Duration::from_secs(10), Timestamp::now());
+        assert_view_metrics_equals_percent_metrics(&process_data.view(span), &[1., 2., 3., 4.]);
+    }
+
+    #[rstest]
+    fn test_compacting_twice_should_leave_compacted_metrics_unchanged() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let mut process_data = build_process_data_and_push(&[1., 7., 3., 2., 9., 4.]);
+
+        process_data.compact(Timestamp::now(), Duration::from_secs(2));
+        process_data.compact(Timestamp::now(), Duration::from_secs(2));
+
+        let span = Span::new(Timestamp::now() - Duration::from_secs(20), Timestamp::now());
+        assert_view_metrics_equals_percent_metrics(&process_data.view(span), &[1., 7., 9., 4.]);
+    }
 }
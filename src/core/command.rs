@@ -0,0 +1,150 @@
+//! External commands launched on the selected process, such as `strace -p {pid}`
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::core::process::ProcessMetadata;
+
+/// Placeholder replaced by the PID of the process
+const PID_PLACEHOLDER: &str = "{pid}";
+/// Placeholder replaced by the command of the process
+const COMMAND_PLACEHOLDER: &str = "{command}";
+
+/// The arguments of a command, in which `{pid}` and `{command}` are replaced by the PID and the command of a process
+///
+/// The template is split into arguments on whitespaces, except within single or double quotes. The command is not
+/// run by a shell, so the substituted values are never interpreted.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommandTemplate {
+    args: Vec<String>,
+}
+
+impl CommandTemplate {
+    /// Returns the program and the arguments of the command to run for the given process
+    pub fn expand(&self, process: &ProcessMetadata) -> Vec<String> {
+        let pid = process.pid().to_string();
+
+        self.args
+            .iter()
+            .map(|arg| {
+                arg.replace(PID_PLACEHOLDER, &pid)
+                    .replace(COMMAND_PLACEHOLDER, process.command())
+            })
+            .collect()
+    }
+}
+
+impl FromStr for CommandTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let args = split_arguments(s)?;
+
+        if args.is_empty() {
+            return Err("the command must not be empty".to_string());
+        }
+
+        Ok(Self { args })
+    }
+}
+
+impl Display for CommandTemplate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let args: Vec<_> = self
+            .args
+            .iter()
+            .map(|arg| match arg.contains(char::is_whitespace) || arg.is_empty() {
+                true => format!("'{}'", arg),
+                false => arg.clone(),
+            })
+            .collect();
+
+        write!(f, "{}", args.join(" "))
+    }
+}
+
+/// Splits a command line into arguments on whitespaces, except within single or double quotes
+fn split_arguments(repr: &str) -> Result<Vec<String>, String> {
+    let mut args = vec![];
+    // The argument being read, if any, which may be empty if it is an empty quoted string
+    let mut current: Option<String> = None;
+    let mut quote: Option<char> = None;
+
+    for c in repr.chars() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => current.get_or_insert_with(String::new).push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None, _) if c.is_whitespace() => args.extend(current.take()),
+            (None, _) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    if let Some(q) = quote {
+        return Err(format!("missing closing quote {}", q));
+    }
+    args.extend(current);
+
+    Ok(args)
+}
+
+#[cfg(test)]
+mod test_command_template {
+    use rstest::*;
+
+    use crate::core::command::CommandTemplate;
+    use crate::core::process::ProcessMetadata;
+    use crate::core::time::Timestamp;
+
+    fn expand(template: &str) -> Vec<String> {
+        let process = ProcessMetadata::new(1234, "nginx: worker", Timestamp::now());
+
+        template.parse::<CommandTemplate>().unwrap().expand(&process)
+    }
+
+    #[test]
+    fn test_should_substitute_pid() {
+        assert_eq!(expand("strace -p {pid}"), vec!["strace", "-p", "1234"]);
+    }
+
+    #[test]
+    fn test_should_substitute_command_as_a_single_argument() {
+        assert_eq!(expand("pgrep -f {command}"), vec!["pgrep", "-f", "nginx: worker"]);
+    }
+
+    #[test]
+    fn test_should_substitute_placeholders_within_arguments() {
+        assert_eq!(
+            expand("cat /proc/{pid}/status --label={command}:{pid}"),
+            vec!["cat", "/proc/1234/status", "--label=nginx: worker:1234"]
+        );
+    }
+
+    #[test]
+    fn test_should_keep_quoted_whitespaces_in_arguments() {
+        assert_eq!(
+            expand(r#"sh -c 'lsof -p {pid} | less' "" x"#),
+            vec!["sh", "-c", "lsof -p 1234 | less", "", "x"]
+        );
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("   ")]
+    #[case("strace -p '{pid}")]
+    fn test_should_reject_invalid_template(#[case] template: &str) {
+        assert!(template.parse::<CommandTemplate>().is_err());
+    }
+
+    #[rstest]
+    #[case("strace -p {pid}")]
+    #[case(r#"sh -c "lsof -p {pid} | less" ''"#)]
+    fn test_display_should_be_parsed_back_into_same_template(#[case] repr: &str) {
+        let template: CommandTemplate = repr.parse().unwrap();
+
+        assert_eq!(template.to_string().parse::<CommandTemplate>(), Ok(template));
+    }
+}
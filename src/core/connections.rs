@@ -0,0 +1,75 @@
+//! Network connections opened by processes
+
+use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
+
+use crate::core::process::Pid;
+use crate::core::Error;
+
+/// A TCP connection, or a TCP socket listening for connections
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Connection {
+    local: SocketAddr,
+    remote: SocketAddr,
+    // The name of the state of the connection, as known to `netstat` (e.g. ESTABLISHED)
+    state: &'static str,
+}
+
+impl Connection {
+    pub fn new(local: SocketAddr, remote: SocketAddr, state: &'static str) -> Self {
+        Self { local, remote, state }
+    }
+
+    pub fn local(&self) -> SocketAddr {
+        self.local
+    }
+
+    pub fn remote(&self) -> SocketAddr {
+        self.remote
+    }
+
+    pub fn state(&self) -> &'static str {
+        self.state
+    }
+}
+
+impl Display for Connection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -> {} {}", self.local, self.remote, self.state)
+    }
+}
+
+/// Lists the network connections of processes
+pub trait ConnectionLister {
+    /// Returns the TCP connections of the given process, along with its listening TCP sockets
+    ///
+    /// # Arguments
+    ///  * `pid`: The process identifier of the currently running process
+    fn connections(&mut self, pid: Pid) -> Result<Vec<Connection>, Error>;
+}
+
+#[cfg(test)]
+mod test_connection {
+    use crate::core::connections::Connection;
+
+    #[test]
+    fn test_should_display_addresses_and_state() {
+        let connection = Connection::new(
+            "10.0.2.15:41652".parse().unwrap(),
+            "172.26.250.142:443".parse().unwrap(),
+            "ESTABLISHED",
+        );
+
+        assert_eq!(
+            connection.to_string(),
+            "10.0.2.15:41652 -> 172.26.250.142:443 ESTABLISHED"
+        );
+    }
+
+    #[test]
+    fn test_should_display_ipv6_addresses_in_brackets() {
+        let connection = Connection::new("[::1]:631".parse().unwrap(), "[::]:0".parse().unwrap(), "LISTEN");
+
+        assert_eq!(connection.to_string(), "[::1]:631 -> [::]:0 LISTEN");
+    }
+}
@@ -0,0 +1,117 @@
+//! Attribution of the files opened by processes to the devices storing them
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::core::process::Pid;
+use crate::core::Error;
+
+/// Identifies a device by its major and minor numbers
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Debug)]
+pub struct DeviceId {
+    major: u32,
+    minor: u32,
+}
+
+impl DeviceId {
+    pub fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl Display for DeviceId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.major, self.minor)
+    }
+}
+
+/// A device storing files, along with its name (e.g. `nvme0n1p2`) when it is known
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Device {
+    id: DeviceId,
+    name: Option<String>,
+}
+
+impl Device {
+    pub fn new(id: DeviceId, name: Option<String>) -> Self {
+        Self { id, name }
+    }
+}
+
+impl Display for Device {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} ({})", name, self.id),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
+/// Finds out on which devices the files opened by processes are stored
+pub trait DeviceResolver {
+    /// Returns the device storing most of the regular files opened by the given process
+    ///
+    /// Returns `None` if the process has no regular file open.
+    ///
+    /// # Arguments
+    ///  * `pid`: The process identifier of the currently running process
+    fn dominant_device(&mut self, pid: Pid) -> Result<Option<Device>, Error>;
+}
+
+/// Returns the device appearing the most often among the devices of the opened files
+///
+/// When several devices store as many files, the one with the lowest ID is returned.
+pub fn most_frequent_device(devices: &[DeviceId]) -> Option<DeviceId> {
+    let mut counts: HashMap<DeviceId, usize> = HashMap::new();
+    for device in devices {
+        *counts.entry(*device).or_default() += 1;
+    }
+
+    counts
+        .into_iter()
+        .max_by(|(dev1, count1), (dev2, count2)| count1.cmp(count2).then(dev2.cmp(dev1)))
+        .map(|(device, _)| device)
+}
+
+#[cfg(test)]
+mod test_most_frequent_device {
+    use crate::core::devices::{most_frequent_device, DeviceId};
+
+    #[test]
+    fn test_should_be_none_without_device() {
+        assert_eq!(most_frequent_device(&[]), None);
+    }
+
+    #[test]
+    fn test_should_return_device_storing_most_files() {
+        let nvme = DeviceId::new(259, 2);
+        let hdd = DeviceId::new(8, 1);
+
+        assert_eq!(most_frequent_device(&[hdd, nvme, nvme, hdd, nvme]), Some(nvme));
+    }
+
+    #[test]
+    fn test_should_return_lowest_device_on_tie() {
+        let nvme = DeviceId::new(259, 2);
+        let hdd = DeviceId::new(8, 1);
+
+        assert_eq!(most_frequent_device(&[nvme, hdd]), Some(hdd));
+    }
+}
+
+#[cfg(test)]
+mod test_device {
+    use crate::core::devices::{Device, DeviceId};
+
+    #[test]
+    fn test_should_display_name_of_device_along_with_its_id() {
+        let device = Device::new(DeviceId::new(259, 2), Some("nvme0n1p2".to_string()));
+
+        assert_eq!(device.to_string(), "nvme0n1p2 (259:2)");
+    }
+
+    #[test]
+    fn test_should_display_id_of_unnamed_device() {
+        assert_eq!(Device::new(DeviceId::new(0, 45), None).to_string(), "0:45");
+    }
+}
@@ -0,0 +1,462 @@
+//! Metrics reported by external scripts, such as `/usr/local/bin/queue-depth`
+//!
+//! On each iteration, the script prints a `PID VALUE` line for each process it measures, e.g. `1234 42.5`.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::Read;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::str::FromStr;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::core::metrics::ValueMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::libc::kill_process_group;
+use crate::procfs::ProcfsError;
+
+/// The share of the refresh period during which a script can run before it is killed and its collector skips the
+/// iteration
+///
+/// Scripts run in the main loop, which must be left enough time to probe processes and render the metrics.
+const SCRIPT_TIMEOUT_RATIO: u32 = 2;
+/// How often a running script is checked for completion
+const SCRIPT_POLL_PERIOD: Duration = Duration::from_millis(10);
+
+#[derive(thiserror::Error, Debug)]
+pub enum ScriptError {
+    #[error("The script did not complete within {0:?}")]
+    Timeout(Duration),
+    #[error("The script failed: {0}")]
+    Failed(ExitStatus),
+    #[error("Invalid output on line {0}: '{1}'")]
+    InvalidOutput(usize, String),
+    #[error("Could not kill the script")]
+    KillError(#[source] ProcfsError),
+    #[error(transparent)]
+    IOError(#[from] io::Error),
+}
+
+/// An external probe, named after the tab displaying its metrics, along with the path of its script
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExternalProbeSpec {
+    name: String,
+    path: PathBuf,
+}
+
+impl ExternalProbeSpec {
+    pub fn new(name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl FromStr for ExternalProbeSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, path) = s
+            .split_once(':')
+            .ok_or_else(|| "expected a probe such as queue:/path/to/script".to_string())?;
+
+        let (name, path) = (name.trim(), path.trim());
+        if name.is_empty() {
+            return Err("the name of the probe must not be empty".to_string());
+        }
+        if path.is_empty() {
+            return Err("the path of the script must not be empty".to_string());
+        }
+
+        Ok(ExternalProbeSpec::new(name, path))
+    }
+}
+
+impl Display for ExternalProbeSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.name, self.path.display())
+    }
+}
+
+/// Types which can run a script and capture its output
+pub trait ScriptRunner {
+    /// Runs the script at the given path, and returns its standard output
+    ///
+    /// The script is killed if it does not complete within `timeout`.
+    fn run(&mut self, path: &Path, timeout: Duration) -> Result<String, ScriptError>;
+}
+
+/// Runs scripts as child processes
+///
+/// Each script runs in its own process group, so that the processes it spawns are killed along with it when it times
+/// out.
+pub struct ChildScriptRunner;
+
+impl ScriptRunner for ChildScriptRunner {
+    fn run(&mut self, path: &Path, timeout: Duration) -> Result<String, ScriptError> {
+        let mut child = Command::new(path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .process_group(0)
+            .spawn()?;
+
+        // The output is read from another thread, as a script filling the pipe would not complete until it is read
+        let mut stdout = child.stdout.take().expect("The standard output of the script is piped");
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut output = String::new();
+            // The receiver is gone if the script timed out, in which case its output is not needed anymore
+            let _ = sender.send(stdout.read_to_string(&mut output).map(|_| output));
+        });
+
+        let deadline = Instant::now() + timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                return Err(kill_script(&mut child, timeout));
+            }
+            thread::sleep(SCRIPT_POLL_PERIOD);
+        };
+
+        // Processes spawned by the script may still hold its standard output after it exited
+        let output = match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(output) => output?,
+            Err(RecvTimeoutError::Timeout) => return Err(kill_script(&mut child, timeout)),
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(io::Error::other("Could not read the output of the script").into())
+            }
+        };
+
+        if !status.success() {
+            return Err(ScriptError::Failed(status));
+        }
+
+        Ok(output)
+    }
+}
+
+/// Kills the process group of a script which did not complete in time, and returns the error reporting it
+///
+/// Killing the whole group closes the standard output held by the processes spawned by the script, which ends the
+/// thread reading it.
+fn kill_script(child: &mut Child, timeout: Duration) -> ScriptError {
+    // The script leads its process group, whose ID is therefore its PID
+    if let Err(e) = kill_process_group(child.id() as Pid) {
+        return ScriptError::KillError(e);
+    }
+
+    match child.wait() {
+        Ok(_) => ScriptError::Timeout(timeout),
+        Err(e) => e.into(),
+    }
+}
+
+/// Probe implementation reading the metrics of processes from the output of an external script
+///
+/// The script runs once per iteration, before processes are probed. Processes which the script leaves out have a
+/// value of 0. If the script fails or times out, the collector of the probe skips the iteration.
+pub struct ExternalProbe {
+    name: String,
+    path: PathBuf,
+    runner: Box<dyn ScriptRunner>,
+    timeout: Duration,
+    values: HashMap<Pid, f64>,
+}
+
+impl ExternalProbe {
+    /// Creates a probe running the script of the given external probe
+    ///
+    /// # Arguments
+    ///  * `spec`: The external probe whose script to run
+    ///  * `refresh_period`: The period between two iterations, from which the timeout of the script is derived
+    pub fn new(spec: &ExternalProbeSpec, refresh_period: Duration) -> Self {
+        Self::from_runner(spec, Box::new(ChildScriptRunner), refresh_period)
+    }
+
+    fn from_runner(spec: &ExternalProbeSpec, runner: Box<dyn ScriptRunner>, refresh_period: Duration) -> Self {
+        Self {
+            name: spec.name().to_string(),
+            path: spec.path().to_path_buf(),
+            runner,
+            timeout: refresh_period / SCRIPT_TIMEOUT_RATIO,
+            values: HashMap::new(),
+        }
+    }
+}
+
+impl Probe<ValueMetric> for ExternalProbe {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn init_iteration(&mut self) -> Result<(), Error> {
+        self.values.clear();
+
+        let values = self
+            .runner
+            .run(&self.path, self.timeout)
+            .and_then(|output| parse_script_output(&output))
+            .map_err(|e| {
+                Error::ProbingError(format!("Could not run the script of the {} probe", self.name), e.into())
+            })?;
+
+        self.values = values;
+        Ok(())
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<ValueMetric, Error> {
+        Ok(ValueMetric::new(self.values.get(&pid).copied().unwrap_or_default()))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().for_each(|pid| {
+            self.values.remove(pid);
+        });
+    }
+}
+
+/// Parses the `PID VALUE` lines printed by a script, ignoring blank lines
+fn parse_script_output(output: &str) -> Result<HashMap<Pid, f64>, ScriptError> {
+    output
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let invalid = || ScriptError::InvalidOutput(index + 1, line.to_string());
+            let mut tokens = line.split_whitespace();
+
+            let pid = tokens.next().and_then(|pid| pid.parse().ok()).ok_or_else(invalid)?;
+            let value = tokens
+                .next()
+                .and_then(|value| value.parse::<f64>().ok())
+                .filter(|value| value.is_finite())
+                .ok_or_else(invalid)?;
+
+            match tokens.next() {
+                Some(_) => Err(invalid()),
+                None => Ok((pid, value)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_external_probe_spec {
+    use std::path::Path;
+
+    use rstest::*;
+
+    use crate::core::external::ExternalProbeSpec;
+
+    #[test]
+    fn test_should_parse_name_and_path_of_script() {
+        let spec: ExternalProbeSpec = "queue:/usr/local/bin/queue-depth".parse().unwrap();
+
+        assert_eq!(spec.name(), "queue");
+        assert_eq!(spec.path(), Path::new("/usr/local/bin/queue-depth"));
+    }
+
+    #[rstest]
+    #[case("/usr/local/bin/queue-depth")]
+    #[case(":/usr/local/bin/queue-depth")]
+    #[case("queue:")]
+    fn test_should_reject_incomplete_probe(#[case] repr: &str) {
+        assert!(repr.parse::<ExternalProbeSpec>().is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_script_output {
+    use rstest::*;
+
+    use crate::core::external::{parse_script_output, ScriptError};
+
+    #[test]
+    fn test_should_parse_value_of_each_pid() {
+        let values = parse_script_output("1 42\n20  0.5\n300\t-3.25\n").unwrap();
+
+        assert_eq!(values, hashmap!(1 => 42., 20 => 0.5, 300 => -3.25));
+    }
+
+    #[test]
+    fn test_should_ignore_blank_lines() {
+        let values = parse_script_output("\n1 42\n   \n2 7\n").unwrap();
+
+        assert_eq!(values, hashmap!(1 => 42., 2 => 7.));
+    }
+
+    #[test]
+    fn test_should_be_empty_when_script_prints_nothing() {
+        assert!(parse_script_output("").unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[case("1 42\nabc 3", 2)]
+    #[case("1", 1)]
+    #[case("1 high", 1)]
+    #[case("1 NaN", 1)]
+    #[case("1 42 43", 1)]
+    #[case("-1 42", 1)]
+    fn test_should_reject_invalid_lines(#[case] output: &str, #[case] line: usize) {
+        assert!(matches!(
+            parse_script_output(output),
+            Err(ScriptError::InvalidOutput(l, _)) if l == line
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test_child_script_runner {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+
+    use tempfile::{tempdir, TempDir};
+
+    use crate::core::external::{ChildScriptRunner, ScriptError, ScriptRunner};
+
+    fn script(content: &str) -> (TempDir, PathBuf) {
+        let dir = tempdir().expect("Could not create script dir");
+        let path = dir.path().join("script.sh");
+        fs::write(&path, format!("#!/bin/sh\n{}", content)).expect("Could not write script");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o700)).expect("Could not make script executable");
+
+        (dir, path)
+    }
+
+    #[test]
+    fn test_should_return_output_of_script() {
+        let (_dir, path) = script("echo '1 42'\n");
+
+        assert_eq!(ChildScriptRunner.run(&path, Duration::from_secs(5)).unwrap(), "1 42\n");
+    }
+
+    #[test]
+    fn test_should_report_failure_of_script() {
+        let (_dir, path) = script("exit 3\n");
+
+        assert!(matches!(
+            ChildScriptRunner.run(&path, Duration::from_secs(5)),
+            Err(ScriptError::Failed(_))
+        ));
+    }
+
+    #[test]
+    fn test_should_time_out_when_child_of_script_holds_its_output() {
+        let (_dir, path) = script("echo '1 42'\nsleep 30 &\n");
+        let start = Instant::now();
+
+        let result = ChildScriptRunner.run(&path, Duration::from_millis(200));
+
+        assert!(matches!(result, Err(ScriptError::Timeout(_))));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
+
+#[cfg(test)]
+mod test_external_probe {
+    use std::path::Path;
+    use std::time::Duration;
+
+    use crate::core::collection::{MetricCollector, ProbeCollector};
+    use crate::core::external::{ExternalProbe, ExternalProbeSpec, ScriptError, ScriptRunner};
+    use crate::core::metrics::ValueMetric;
+    use crate::core::probe::Probe;
+
+    /// Returns the given outputs on successive runs, or times out once they are exhausted
+    struct FakeScriptRunner {
+        outputs: Vec<Result<String, ScriptError>>,
+    }
+
+    impl FakeScriptRunner {
+        fn new(mut outputs: Vec<Result<String, ScriptError>>) -> Self {
+            outputs.reverse();
+            Self { outputs }
+        }
+    }
+
+    impl ScriptRunner for FakeScriptRunner {
+        fn run(&mut self, _path: &Path, timeout: Duration) -> Result<String, ScriptError> {
+            self.outputs.pop().unwrap_or(Err(ScriptError::Timeout(timeout)))
+        }
+    }
+
+    fn external_probe(outputs: Vec<Result<String, ScriptError>>) -> ExternalProbe {
+        let spec = ExternalProbeSpec::new("queue", "/usr/local/bin/queue-depth");
+        ExternalProbe::from_runner(&spec, Box::new(FakeScriptRunner::new(outputs)), Duration::from_secs(1))
+    }
+
+    #[test]
+    fn test_should_be_named_after_its_spec() {
+        assert_eq!(external_probe(vec![]).name(), "queue");
+    }
+
+    #[test]
+    fn test_should_probe_values_printed_by_script() {
+        let mut probe = external_probe(vec![Ok("1 42\n2 7.5".to_string())]);
+
+        let metrics = probe.probe_processes(&[1, 2, 3]).unwrap();
+
+        assert_eq!(
+            metrics,
+            hashmap!(1 => ValueMetric::new(42.), 2 => ValueMetric::new(7.5), 3 => ValueMetric::new(0.))
+        );
+    }
+
+    #[test]
+    fn test_should_fail_iteration_when_script_times_out() {
+        let mut probe = external_probe(vec![]);
+
+        assert!(probe.init_iteration().is_err());
+    }
+
+    #[test]
+    fn test_should_time_out_script_after_half_of_refresh_period() {
+        let spec = ExternalProbeSpec::new("queue", "/usr/local/bin/queue-depth");
+
+        let probe = ExternalProbe::new(&spec, Duration::from_secs(2));
+
+        assert_eq!(probe.timeout, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_should_not_keep_values_of_previous_run_when_script_fails() {
+        let mut probe = external_probe(vec![Ok("1 42".to_string())]);
+        probe.init_iteration().unwrap();
+
+        assert!(probe.init_iteration().is_err());
+        assert_eq!(probe.probe(1).unwrap(), ValueMetric::new(0.));
+    }
+
+    #[test]
+    fn test_collector_should_skip_iteration_when_script_times_out() {
+        let mut collector = ProbeCollector::new(external_probe(vec![Ok("1 42".to_string())]));
+        collector.collect(&[1]).unwrap();
+
+        assert!(collector.collect(&[1]).is_err());
+        assert_eq!(
+            collector.last_metric(1).map(|metric| metric.as_f64(0).unwrap()),
+            Some(42.)
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! Disk space usage of the mounted filesystems
+
+use crate::core::Error;
+
+/// The space used on a mounted filesystem
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct FilesystemUsage {
+    mount_point: String,
+    fs_type: String,
+    // The bytes of the filesystem used by files
+    used_bytes: u64,
+    // The bytes of the filesystem left to unprivileged users, which excludes the blocks reserved to root
+    available_bytes: u64,
+}
+
+impl FilesystemUsage {
+    pub fn new(
+        mount_point: impl Into<String>,
+        fs_type: impl Into<String>,
+        used_bytes: u64,
+        available_bytes: u64,
+    ) -> Self {
+        Self {
+            mount_point: mount_point.into(),
+            fs_type: fs_type.into(),
+            used_bytes,
+            available_bytes,
+        }
+    }
+
+    pub fn mount_point(&self) -> &str {
+        &self.mount_point
+    }
+
+    /// The type of the filesystem, such as `ext4`
+    pub fn fs_type(&self) -> &str {
+        &self.fs_type
+    }
+
+    pub fn used_bytes(&self) -> u64 {
+        self.used_bytes
+    }
+
+    pub fn available_bytes(&self) -> u64 {
+        self.available_bytes
+    }
+
+    /// Returns the share of the space usable by unprivileged users which is used, in percent
+    ///
+    /// As `df` does, the blocks reserved to root are left out, so that a filesystem is full at 100%.
+    pub fn used_percent(&self) -> f64 {
+        let usable_bytes = self.used_bytes + self.available_bytes;
+        if usable_bytes == 0 {
+            return 0.;
+        }
+
+        100. * self.used_bytes as f64 / usable_bytes as f64
+    }
+}
+
+/// Lists the mounted filesystems storing files, along with their disk space usage
+pub trait FilesystemScanner {
+    /// Returns the usage of each mounted filesystem, in the order in which they were mounted
+    fn filesystems(&mut self) -> Result<Vec<FilesystemUsage>, Error>;
+}
+
+#[cfg(test)]
+mod test_filesystem_usage {
+    use rstest::*;
+
+    use crate::core::filesystems::FilesystemUsage;
+
+    #[rstest]
+    #[case(0, 100, 0.)]
+    #[case(25, 75, 25.)]
+    #[case(100, 0, 100.)]
+    #[case(0, 0, 0.)]
+    fn test_used_percent_should_leave_out_reserved_blocks(
+        #[case] used_bytes: u64,
+        #[case] available_bytes: u64,
+        #[case] expected: f64,
+    ) {
+        let usage = FilesystemUsage::new("/", "ext4", used_bytes, available_bytes);
+
+        assert_eq!(usage.used_percent(), expected);
+    }
+}
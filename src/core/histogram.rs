@@ -0,0 +1,126 @@
+//! Distribution of the latest metrics of processes
+
+/// A range of values, along with how many values it contains
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Bin {
+    lower: f64,
+    upper: f64,
+    count: usize,
+}
+
+impl Bin {
+    /// The lowest value of the bin, which the bin contains
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    /// The highest value of the bin, which only the last bin of a histogram contains
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    /// How many values the bin contains
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// Splits the range of the given values into `bin_count` bins of equal width, and counts the values of each bin
+///
+/// The range starts at 0, or at the lowest value if some values are negative, and ends at the highest value. Values
+/// which are not finite are ignored.
+///
+/// Panics if `bin_count` is 0
+///
+/// # Arguments
+///  * `values`: The values to distribute, such as the latest metric of each process
+///  * `bin_count`: How many bins the range of values is split into
+pub fn histogram(values: &[f64], bin_count: usize) -> Vec<Bin> {
+    assert!(bin_count > 0, "A histogram must have at least one bin");
+
+    let values: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    let lower = values.iter().copied().fold(0., f64::min);
+    let mut upper = values.iter().copied().fold(0., f64::max);
+    if upper <= lower {
+        // All the values are equal to the lower bound, which the first bin of any width contains
+        upper = lower + 1.;
+    }
+    let width = (upper - lower) / bin_count as f64;
+
+    let mut bins: Vec<Bin> = (0..bin_count)
+        .map(|idx| Bin {
+            lower: lower + idx as f64 * width,
+            upper: lower + (idx + 1) as f64 * width,
+            count: 0,
+        })
+        .collect();
+
+    for value in values {
+        // The highest value would start a bin of its own, so it falls back to the last bin
+        let idx = (((value - lower) / width) as usize).min(bin_count - 1);
+        bins[idx].count += 1;
+    }
+
+    bins
+}
+
+#[cfg(test)]
+mod test_histogram {
+    use rstest::*;
+
+    use crate::core::histogram::histogram;
+
+    fn counts(values: &[f64], bin_count: usize) -> Vec<usize> {
+        histogram(values, bin_count).iter().map(|bin| bin.count()).collect()
+    }
+
+    #[rstest]
+    fn test_should_split_range_from_zero_to_highest_value() {
+        let bins = histogram(&[10., 100.], 10);
+
+        assert_eq!(bins.len(), 10);
+        assert_eq!((bins[0].lower(), bins[0].upper()), (0., 10.));
+        assert_eq!((bins[9].lower(), bins[9].upper()), (90., 100.));
+    }
+
+    #[rstest]
+    fn test_should_count_values_of_each_bin() {
+        let values = [0., 5., 9.9, 10., 35., 50., 72.5, 100.];
+
+        assert_eq!(counts(&values, 10), vec![3, 1, 0, 1, 0, 1, 0, 1, 0, 1]);
+    }
+
+    #[rstest]
+    fn test_should_count_highest_value_in_last_bin() {
+        assert_eq!(counts(&[20., 40.], 4), vec![0, 0, 1, 1]);
+    }
+
+    #[rstest]
+    fn test_should_start_range_at_lowest_negative_value() {
+        let bins = histogram(&[-20., 0., 20.], 2);
+
+        assert_eq!((bins[0].lower(), bins[1].upper()), (-20., 20.));
+        assert_eq!(counts(&[-20., 0., 20.], 2), vec![1, 2]);
+    }
+
+    #[rstest]
+    #[case(&[])]
+    #[case(&[0., 0., 0.])]
+    fn test_should_count_values_equal_to_zero_in_first_bin(#[case] values: &[f64]) {
+        let counts = counts(values, 5);
+
+        assert_eq!(counts[0], values.len());
+        assert_eq!(counts[1..].iter().sum::<usize>(), 0);
+    }
+
+    #[rstest]
+    fn test_should_ignore_values_which_are_not_finite() {
+        assert_eq!(counts(&[f64::NAN, f64::INFINITY, 1.], 2), vec![0, 1]);
+    }
+
+    #[rstest]
+    #[should_panic]
+    fn test_should_panic_without_bins() {
+        histogram(&[1.], 0);
+    }
+}
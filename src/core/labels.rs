@@ -0,0 +1,101 @@
+//! Human-readable labels of processes, assigned from their command line
+
+use std::fmt::{Display, Formatter};
+
+use regex::Regex;
+
+/// Assigns a label to the processes whose command line matches a pattern
+#[derive(Debug, Clone)]
+pub struct LabelRule {
+    pattern: Regex,
+    label: String,
+}
+
+impl LabelRule {
+    pub fn new<T>(pattern: Regex, label: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            pattern,
+            label: label.into(),
+        }
+    }
+
+    /// Returns the label of the rule, if the given command line matches its pattern
+    fn label_of(&self, cmdline: &str) -> Option<&str> {
+        self.pattern.is_match(cmdline).then_some(self.label.as_str())
+    }
+}
+
+impl PartialEq for LabelRule {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern.as_str() == other.pattern.as_str() && self.label == other.label
+    }
+}
+
+impl Display for LabelRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.pattern, self.label)
+    }
+}
+
+/// Labels processes according to a list of rules
+///
+/// When several rules match the command line of a process, the first one prevails.
+#[derive(Default)]
+pub struct ProcessLabels {
+    rules: Vec<LabelRule>,
+}
+
+impl ProcessLabels {
+    pub fn new(rules: Vec<LabelRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Returns the label of the first rule matching the given command line
+    pub fn label_of(&self, cmdline: &str) -> Option<&str> {
+        self.rules.iter().find_map(|rule| rule.label_of(cmdline))
+    }
+}
+
+#[cfg(test)]
+mod test_process_labels {
+    use regex::Regex;
+    use rstest::*;
+
+    use crate::core::labels::{LabelRule, ProcessLabels};
+
+    #[fixture]
+    fn labels() -> ProcessLabels {
+        ProcessLabels::new(vec![
+            LabelRule::new(Regex::new("java .*kafka").unwrap(), "kafka"),
+            LabelRule::new(Regex::new("java .*zookeeper").unwrap(), "zookeeper"),
+            LabelRule::new(Regex::new("^java ").unwrap(), "other java"),
+        ])
+    }
+
+    #[rstest]
+    #[case("java -cp /opt/kafka/libs kafka.Kafka", Some("kafka"))]
+    #[case("java -cp /opt/zookeeper/lib org.apache.zookeeper.Server", Some("zookeeper"))]
+    #[case("java -jar app.jar", Some("other java"))]
+    #[case("/usr/bin/bash", None)]
+    #[case("", None)]
+    fn test_should_label_processes_by_cmdline(
+        labels: ProcessLabels,
+        #[case] cmdline: &str,
+        #[case] expected_label: Option<&str>,
+    ) {
+        assert_eq!(labels.label_of(cmdline), expected_label);
+    }
+
+    #[rstest]
+    fn test_first_matching_rule_should_prevail(labels: ProcessLabels) {
+        assert_eq!(labels.label_of("java -Dname=zookeeper kafka.Kafka"), Some("kafka"));
+    }
+
+    #[test]
+    fn test_should_not_label_processes_without_rule() {
+        assert_eq!(ProcessLabels::default().label_of("java -jar app.jar"), None);
+    }
+}
@@ -28,7 +28,7 @@ pub trait Metric: Debug {
     fn max_value(&self) -> f64;
 
     /// Returns the unit representation of the metric
-    fn unit(&self) -> &'static str;
+    fn unit(&self) -> &str;
 
     /// Returns a concise representation of the metric
     ///
@@ -42,127 +42,1147 @@ pub trait Metric: Debug {
     /// * `value`: The value for which to generate a concise representation
     fn concise_repr_of_value(&self, value: f64) -> String;
 
+    /// Same as [`concise_repr_of_value()`](#method.concise_repr_of_value), with `precision` decimals instead of the
+    /// default precision of the metric
+    ///
+    /// Metrics whose concise representation has no decimals, such as counts, ignore `precision`.
+    fn concise_repr_of_value_with_precision(&self, value: f64, _precision: usize) -> String {
+        self.concise_repr_of_value(value)
+    }
+
+    /// Returns a concise representation of the metric, with the given amount of decimals if any, or with the default
+    /// precision of the metric otherwise
+    fn concise_repr_with_precision(&self, precision: Option<usize>) -> String {
+        match precision {
+            Some(precision) => self.concise_repr_of_value_with_precision(self.max_value(), precision),
+            None => self.concise_repr(),
+        }
+    }
+
     /// Returns an explicit representation of a component of the metric
     ///
     /// # Arguments
     ///   * index: Indicates the component of which to get a representation
     fn explicit_repr(&self, index: usize) -> Result<String, Error>;
+
+    /// Indicates if the metric exceeds the level from which it reveals a problem, so that it is highlighted
+    ///
+    /// Metrics which have no such level are never alarming.
+    fn is_alarming(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+impl PartialEq for &dyn Metric {
+    // Helper PartialEq impl to make tests more readable
+    fn eq(&self, other: &Self) -> bool {
+        if self.cardinality() != other.cardinality() || self.unit() != other.unit() {
+            return false;
+        }
+
+        for i in 0..self.cardinality() {
+            if self.as_f64(i).unwrap() != other.as_f64(i).unwrap() {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Bundles a reference to a metric, and its timestamp
+pub struct DatedMetric<'a> {
+    /// The timestamp at which the metric was collected
+    pub timestamp: Timestamp,
+    /// A reference to a metric trait object
+    pub metric: &'a dyn Metric,
+}
+
+impl<'a> DatedMetric<'a> {
+    pub fn new(metric: &'a dyn Metric, timestamp: Timestamp) -> Self {
+        Self { metric, timestamp }
+    }
+}
+
+/// Metric representing a percent value (e.g. CPU usage)
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct PercentMetric {
+    value: f64,
+}
+
+impl PercentMetric {
+    pub fn new(percent_usage: f64) -> Self {
+        Self { value: percent_usage }
+    }
+}
+
+impl Default for PercentMetric {
+    fn default() -> Self {
+        PercentMetric::new(0.)
+    }
+}
+
+impl Metric for PercentMetric {
+    /// Returns 1, as PercentMetric is only composed of one element: the percent value.
+    fn cardinality(&self) -> usize {
+        1
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.value),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.value
+    }
+
+    fn unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.value)
+    }
+
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        self.concise_repr_of_value_with_precision(value, 1)
+    }
+
+    fn concise_repr_of_value_with_precision(&self, value: f64, precision: usize) -> String {
+        format!("{:.precision$}", value)
+    }
+
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("Usage {:.2}%", self.value)),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+}
+
+impl PartialOrd for PercentMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+#[cfg(test)]
+mod test_percent_metric {
+    use std::cmp::Ordering;
+
+    use rstest::rstest;
+
+    use crate::core::metrics::{Metric, PercentMetric};
+
+    #[test]
+    fn test_should_return_sole_value_as_max_value() {
+        let metric = PercentMetric::new(10.);
+        assert_eq!(metric.max_value(), 10.);
+    }
+
+    #[rstest]
+    #[case(None, "12.3")]
+    #[case(Some(0), "12")]
+    #[case(Some(1), "12.3")]
+    #[case(Some(2), "12.35")]
+    fn test_should_represent_value_with_given_precision(#[case] precision: Option<usize>, #[case] expected: &str) {
+        assert_eq!(
+            PercentMetric::new(12.345).concise_repr_with_precision(precision),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_should_correctly_compare_metrics_based_on_percent_value() {
+        let lesser_metric = PercentMetric::new(10.);
+        let greater_metric = PercentMetric::new(20.);
+
+        assert_eq!(lesser_metric.partial_cmp(&greater_metric), Some(Ordering::Less));
+        assert_eq!(greater_metric.partial_cmp(&lesser_metric), Some(Ordering::Greater));
+    }
 }
 
-#[cfg(test)]
-impl PartialEq for &dyn Metric {
-    // Helper PartialEq impl to make tests more readable
-    fn eq(&self, other: &Self) -> bool {
-        if self.cardinality() != other.cardinality() {
-            return false;
-        } else if self.unit() != other.unit() {
-            return false;
-        }
+/// Metric representing the CPU usage of a process split between its own usage and the usage of its children, in percent
+///
+/// The usage of children is only accounted for once they have terminated and have been waited for by the process.
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub struct CpuSplitMetric {
+    own: f64,
+    children: f64,
+}
+
+impl CpuSplitMetric {
+    pub fn new(own: f64, children: f64) -> Self {
+        Self { own, children }
+    }
+}
+
+impl Metric for CpuSplitMetric {
+    /// Returns 2, as a CpuSplitMetric is composed of the own usage and the children usage of a process
+    fn cardinality(&self) -> usize {
+        2
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.own),
+            1 => Ok(self.children),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.own.max(self.children)
+    }
+
+    fn unit(&self) -> &'static str {
+        "%"
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.max_value())
+    }
+
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        self.concise_repr_of_value_with_precision(value, 1)
+    }
+
+    fn concise_repr_of_value_with_precision(&self, value: f64, precision: usize) -> String {
+        format!("{:.precision$}", value)
+    }
+
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("Own     : {:.2}%", self.own)),
+            1 => Ok(format!("Children: {:.2}%", self.children)),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+}
+
+impl PartialOrd for CpuSplitMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.max_value().partial_cmp(&other.max_value())
+    }
+}
+
+#[cfg(test)]
+mod test_cpu_split_metric {
+    use std::cmp::Ordering;
+
+    use crate::core::metrics::{CpuSplitMetric, Metric};
+
+    #[test]
+    fn test_should_return_greatest_usage_as_max_value() {
+        assert_eq!(CpuSplitMetric::new(10., 30.).max_value(), 30.);
+    }
+
+    #[test]
+    fn test_should_represent_own_and_children_usages() {
+        let metric = CpuSplitMetric::new(10., 30.);
+
+        assert_eq!(metric.explicit_repr(0).unwrap(), "Own     : 10.00%");
+        assert_eq!(metric.explicit_repr(1).unwrap(), "Children: 30.00%");
+    }
+
+    #[test]
+    fn test_should_compare_metrics_based_on_greatest_usage() {
+        assert_eq!(
+            CpuSplitMetric::new(10., 30.).partial_cmp(&CpuSplitMetric::new(20., 5.)),
+            Some(Ordering::Greater)
+        );
+    }
+}
+
+/// Metric representing a power consumption, in watts
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct WattMetric {
+    watts: f64,
+}
+
+impl WattMetric {
+    pub fn new(watts: f64) -> Self {
+        Self { watts }
+    }
+}
+
+impl Default for WattMetric {
+    fn default() -> Self {
+        WattMetric::new(0.)
+    }
+}
+
+impl Metric for WattMetric {
+    /// Returns 1, as WattMetric is only composed of one element: the power value.
+    fn cardinality(&self) -> usize {
+        1
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.watts),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.watts
+    }
+
+    fn unit(&self) -> &'static str {
+        "W"
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.watts)
+    }
+
+    /// Represents the value with one decimal, unless it is high enough for the decimal to be irrelevant
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        match value.abs() < 100. {
+            true => self.concise_repr_of_value_with_precision(value, 1),
+            false => self.concise_repr_of_value_with_precision(value, 0),
+        }
+    }
+
+    fn concise_repr_of_value_with_precision(&self, value: f64, precision: usize) -> String {
+        format!("{:.precision$}", value)
+    }
+
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("Power {:.2}W", self.watts)),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+}
+
+impl PartialOrd for WattMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.watts.partial_cmp(&other.watts)
+    }
+}
+
+#[cfg(test)]
+mod test_watt_metric {
+    use std::cmp::Ordering;
+
+    use rstest::*;
+
+    use crate::core::metrics::{Metric, WattMetric};
+
+    #[test]
+    fn test_should_return_sole_value_as_max_value() {
+        let metric = WattMetric::new(12.5);
+        assert_eq!(metric.max_value(), 12.5);
+    }
+
+    #[test]
+    fn test_should_have_a_single_component() {
+        let metric = WattMetric::new(12.5);
+
+        assert_eq!(metric.cardinality(), 1);
+        assert_eq!(metric.as_f64(0).unwrap(), 12.5);
+        assert!(metric.as_f64(1).is_err());
+    }
+
+    #[test]
+    fn test_default_metric_should_be_zero_watts() {
+        assert_eq!(WattMetric::default().max_value(), 0.);
+    }
+
+    #[test]
+    fn test_should_correctly_compare_metrics_based_on_watts() {
+        let lesser_metric = WattMetric::new(10.);
+        let greater_metric = WattMetric::new(20.);
+
+        assert_eq!(lesser_metric.partial_cmp(&greater_metric), Some(Ordering::Less));
+        assert_eq!(greater_metric.partial_cmp(&lesser_metric), Some(Ordering::Greater));
+    }
+
+    #[rstest]
+    #[case(0., "0.0")]
+    #[case(12.34, "12.3")]
+    #[case(99.94, "99.9")]
+    #[case(123.4, "123")]
+    #[case(1500.7, "1501")]
+    fn test_should_represent_value_concisely(#[case] watts: f64, #[case] expected: &str) {
+        assert_eq!(WattMetric::new(watts).concise_repr(), expected);
+    }
+
+    #[test]
+    fn test_should_represent_value_explicitly_with_unit() {
+        assert_eq!(WattMetric::new(12.345).explicit_repr(0).unwrap(), "Power 12.35W");
+        assert_eq!(WattMetric::new(12.345).unit(), "W");
+    }
+}
+
+/// Metric representing a number of items (e.g. established sockets)
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct CountMetric {
+    count: usize,
+}
+
+impl CountMetric {
+    pub fn new(count: usize) -> Self {
+        Self { count }
+    }
+}
+
+impl Metric for CountMetric {
+    /// Returns 1, as CountMetric is only composed of one element: the count.
+    fn cardinality(&self) -> usize {
+        1
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.count as f64),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.count as f64
+    }
+
+    fn unit(&self) -> &'static str {
+        "#"
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.max_value())
+    }
+
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        format!("{:.0}", value)
+    }
+
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("Count {}", self.count)),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+}
+
+impl PartialOrd for CountMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.count.partial_cmp(&other.count)
+    }
+}
+
+#[cfg(test)]
+mod test_count_metric {
+    use std::cmp::Ordering;
+
+    use crate::core::metrics::{CountMetric, Metric};
+
+    #[test]
+    fn test_should_return_count_as_max_value() {
+        assert_eq!(CountMetric::new(3).max_value(), 3.);
+    }
+
+    #[test]
+    fn test_should_represent_count_without_decimals() {
+        assert_eq!(CountMetric::new(42).concise_repr(), "42");
+        assert_eq!(CountMetric::new(42).concise_repr_of_value(46.2), "46");
+    }
+
+    #[test]
+    fn test_should_ignore_precision_of_count() {
+        assert_eq!(CountMetric::new(42).concise_repr_with_precision(Some(2)), "42");
+    }
+
+    #[test]
+    fn test_should_correctly_compare_metrics_based_on_count() {
+        assert_eq!(
+            CountMetric::new(1).partial_cmp(&CountMetric::new(2)),
+            Some(Ordering::Less)
+        );
+    }
+}
+
+/// Metric representing a score assigned by the kernel, without unit (e.g. the OOM score of a process)
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct ScoreMetric {
+    score: u32,
+}
+
+impl ScoreMetric {
+    pub fn new(score: u32) -> Self {
+        Self { score }
+    }
+}
+
+impl Metric for ScoreMetric {
+    /// Returns 1, as ScoreMetric is only composed of one element: the score.
+    fn cardinality(&self) -> usize {
+        1
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.score as f64),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.score as f64
+    }
+
+    fn unit(&self) -> &'static str {
+        "pts"
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.max_value())
+    }
+
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        format!("{:.0}", value)
+    }
+
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("Score {}", self.score)),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+}
+
+impl PartialOrd for ScoreMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.score.partial_cmp(&other.score)
+    }
+}
+
+#[cfg(test)]
+mod test_score_metric {
+    use std::cmp::Ordering;
+
+    use crate::core::metrics::{Metric, ScoreMetric};
+
+    #[test]
+    fn test_should_represent_score_without_decimals() {
+        assert_eq!(ScoreMetric::new(667).concise_repr(), "667");
+        assert_eq!(ScoreMetric::new(667).explicit_repr(0).unwrap(), "Score 667");
+    }
+
+    #[test]
+    fn test_should_correctly_compare_metrics_based_on_score() {
+        assert_eq!(
+            ScoreMetric::new(300).partial_cmp(&ScoreMetric::new(20)),
+            Some(Ordering::Greater)
+        );
+    }
+}
+
+/// Metric representing an arbitrary value without unit (e.g. a value reported by an external script)
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub struct ValueMetric {
+    value: f64,
+}
+
+impl ValueMetric {
+    pub fn new(value: f64) -> Self {
+        Self { value }
+    }
+}
+
+impl Metric for ValueMetric {
+    /// Returns 1, as ValueMetric is only composed of one element: the value.
+    fn cardinality(&self) -> usize {
+        1
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.value),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.value
+    }
+
+    fn unit(&self) -> &'static str {
+        ""
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.max_value())
+    }
+
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        self.concise_repr_of_value_with_precision(value, 1)
+    }
+
+    fn concise_repr_of_value_with_precision(&self, value: f64, precision: usize) -> String {
+        format!("{:.precision$}", value)
+    }
+
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("Value {:.2}", self.value)),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+}
+
+impl PartialOrd for ValueMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+#[cfg(test)]
+mod test_value_metric {
+    use crate::core::metrics::{Metric, ValueMetric};
+
+    #[test]
+    fn test_should_represent_value_without_unit() {
+        assert_eq!(ValueMetric::new(12.345).concise_repr(), "12.3");
+        assert_eq!(ValueMetric::new(12.345).explicit_repr(0).unwrap(), "Value 12.35");
+    }
+}
+
+/// Metric representing a temperature, in degrees Celsius
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub struct TemperatureMetric {
+    celsius: f64,
+}
+
+impl TemperatureMetric {
+    pub fn new(celsius: f64) -> Self {
+        Self { celsius }
+    }
+}
+
+impl Metric for TemperatureMetric {
+    /// Returns 1, as TemperatureMetric is only composed of one element: the temperature.
+    fn cardinality(&self) -> usize {
+        1
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.celsius),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.celsius
+    }
+
+    fn unit(&self) -> &'static str {
+        "°C"
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.celsius)
+    }
+
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        self.concise_repr_of_value_with_precision(value, 1)
+    }
+
+    fn concise_repr_of_value_with_precision(&self, value: f64, precision: usize) -> String {
+        format!("{:.precision$}", value)
+    }
+
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("Temperature {:.1}°C", self.celsius)),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+}
+
+impl PartialOrd for TemperatureMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.celsius.partial_cmp(&other.celsius)
+    }
+}
+
+#[cfg(test)]
+mod test_temperature_metric {
+    use crate::core::metrics::{Metric, TemperatureMetric};
+
+    #[test]
+    fn test_should_represent_temperature_in_celsius() {
+        let metric = TemperatureMetric::new(45.25);
+
+        assert_eq!(metric.concise_repr(), "45.2");
+        assert_eq!(metric.explicit_repr(0).unwrap(), "Temperature 45.2°C");
+    }
+}
+
+/// Metric representing a frequency of events, per second
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct RateMetric {
+    rate: f64,
+}
+
+impl RateMetric {
+    pub fn new(rate: f64) -> Self {
+        Self { rate }
+    }
+}
+
+impl Default for RateMetric {
+    fn default() -> Self {
+        RateMetric::new(0.)
+    }
+}
+
+impl Metric for RateMetric {
+    /// Returns 1, as RateMetric is only composed of one element: the rate.
+    fn cardinality(&self) -> usize {
+        1
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.rate),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.rate
+    }
+
+    fn unit(&self) -> &'static str {
+        "/s"
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.rate)
+    }
+
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        self.concise_repr_of_value_with_precision(value, 1)
+    }
+
+    fn concise_repr_of_value_with_precision(&self, value: f64, precision: usize) -> String {
+        format!("{:.precision$}", value)
+    }
+
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("Rate {:.2}/s", self.rate)),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+}
+
+impl PartialOrd for RateMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.rate.partial_cmp(&other.rate)
+    }
+}
+
+#[cfg(test)]
+mod test_rate_metric {
+    use std::cmp::Ordering;
+
+    use crate::core::metrics::{Metric, RateMetric};
+
+    #[test]
+    fn test_should_return_rate_as_max_value() {
+        assert_eq!(RateMetric::new(2.5).max_value(), 2.5);
+    }
+
+    #[test]
+    fn test_should_correctly_compare_metrics_based_on_rate() {
+        assert_eq!(
+            RateMetric::new(1.).partial_cmp(&RateMetric::new(2.)),
+            Some(Ordering::Less)
+        );
+    }
+}
+
+/// The rate of major page faults, per second, from which a process is considered to be short of memory
+pub const MAJOR_FAULTS_ALARM_RATE: f64 = 50.;
+
+/// Metric representing the rate of major page faults of a process, per second
+///
+/// A major fault loads a page from the disk, so that a process whose pages keep being evicted and loaded again, for
+/// lack of memory, faults at a high rate.
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub struct MajorFaultsMetric {
+    rate: f64,
+}
+
+impl MajorFaultsMetric {
+    pub fn new(rate: f64) -> Self {
+        Self { rate }
+    }
+}
+
+impl Metric for MajorFaultsMetric {
+    /// Returns 1, as MajorFaultsMetric is only composed of one element: the rate of major faults.
+    fn cardinality(&self) -> usize {
+        1
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.rate),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.rate
+    }
+
+    fn unit(&self) -> &'static str {
+        "/s"
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.rate)
+    }
+
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        self.concise_repr_of_value_with_precision(value, 1)
+    }
+
+    fn concise_repr_of_value_with_precision(&self, value: f64, precision: usize) -> String {
+        format!("{:.precision$}", value)
+    }
+
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("Major faults {:.2}/s", self.rate)),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn is_alarming(&self) -> bool {
+        self.rate >= MAJOR_FAULTS_ALARM_RATE
+    }
+}
+
+impl PartialOrd for MajorFaultsMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.rate.partial_cmp(&other.rate)
+    }
+}
+
+#[cfg(test)]
+mod test_major_faults_metric {
+    use rstest::*;
+
+    use crate::core::metrics::{MajorFaultsMetric, Metric, PercentMetric, MAJOR_FAULTS_ALARM_RATE};
+
+    #[test]
+    fn test_should_represent_rate_of_major_faults() {
+        let metric = MajorFaultsMetric::new(12.345);
+
+        assert_eq!(metric.concise_repr(), "12.3");
+        assert_eq!(metric.explicit_repr(0).unwrap(), "Major faults 12.35/s");
+    }
+
+    #[rstest]
+    #[case(0., false)]
+    #[case(MAJOR_FAULTS_ALARM_RATE - 1., false)]
+    #[case(MAJOR_FAULTS_ALARM_RATE, true)]
+    #[case(MAJOR_FAULTS_ALARM_RATE * 10., true)]
+    fn test_should_be_alarming_from_threshold(#[case] rate: f64, #[case] expected: bool) {
+        assert_eq!(MajorFaultsMetric::new(rate).is_alarming(), expected);
+    }
+
+    #[test]
+    fn test_metrics_without_threshold_should_never_be_alarming() {
+        assert!(!PercentMetric::new(100.).is_alarming());
+    }
+}
+
+/// Metric representing an amount of bytes (e.g. memory footprint)
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct BytesMetric {
+    bytes: usize,
+}
+
+impl BytesMetric {
+    pub fn new(bytes: usize) -> Self {
+        Self { bytes }
+    }
+}
+
+impl Metric for BytesMetric {
+    /// Returns 1, as BytesMetric is only composed of one element: the amount of bytes.
+    fn cardinality(&self) -> usize {
+        1
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.bytes as f64),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.bytes as f64
+    }
+
+    fn unit(&self) -> &'static str {
+        "B"
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.max_value())
+    }
+
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        self.concise_repr_of_value_with_precision(value, 1)
+    }
+
+    fn concise_repr_of_value_with_precision(&self, value: f64, precision: usize) -> String {
+        format_bytes(value as usize, precision)
+    }
+
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("Size {}B", format_bytes(self.bytes, 2))),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+}
+
+impl PartialOrd for BytesMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.bytes.partial_cmp(&other.bytes)
+    }
+}
+
+#[cfg(test)]
+mod test_bytes_metric {
+    use std::cmp::Ordering;
+
+    use crate::core::metrics::{BytesMetric, Metric};
+
+    #[test]
+    fn test_should_represent_bytes_with_prefix() {
+        assert_eq!(BytesMetric::new(3 * 1024 * 1024).concise_repr(), "3.0M");
+        assert_eq!(BytesMetric::new(1536).explicit_repr(0).unwrap(), "Size 1.50kB");
+    }
+
+    #[test]
+    fn test_should_represent_bytes_with_given_precision() {
+        assert_eq!(BytesMetric::new(1536).concise_repr_with_precision(Some(0)), "2k");
+        assert_eq!(BytesMetric::new(1536).concise_repr_with_precision(Some(2)), "1.50k");
+    }
+
+    #[test]
+    fn test_should_correctly_compare_metrics_based_on_bytes() {
+        assert_eq!(
+            BytesMetric::new(1).partial_cmp(&BytesMetric::new(2)),
+            Some(Ordering::Less)
+        );
+    }
+}
+
+/// Metric representing the sizes of the stack and of the data segment of a process, in bytes
+///
+/// The data segment holds the heap of the process, so that it grows with its allocations while the stack grows with its
+/// recursion.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct MemorySegmentsMetric {
+    stack: usize,
+    data: usize,
+}
+
+impl MemorySegmentsMetric {
+    pub fn new(stack: usize, data: usize) -> Self {
+        Self { stack, data }
+    }
+}
+
+impl Metric for MemorySegmentsMetric {
+    /// Returns 2, as a MemorySegmentsMetric is composed of the size of the stack and the size of the data segment
+    fn cardinality(&self) -> usize {
+        2
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.stack as f64),
+            1 => Ok(self.data as f64),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.stack.max(self.data) as f64
+    }
+
+    fn unit(&self) -> &'static str {
+        "B"
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.max_value())
+    }
+
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        self.concise_repr_of_value_with_precision(value, 1)
+    }
 
-        for i in 0..self.cardinality() {
-            if self.as_f64(i).unwrap() != other.as_f64(i).unwrap() {
-                return false;
-            }
-        }
+    fn concise_repr_of_value_with_precision(&self, value: f64, precision: usize) -> String {
+        format_bytes(value as usize, precision)
+    }
 
-        true
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("Stack: {}B", format_bytes(self.stack, 2))),
+            1 => Ok(format!("Data : {}B", format_bytes(self.data, 2))),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
     }
 }
 
-/// Bundles a reference to a metric, and its timestamp
-pub struct DatedMetric<'a> {
-    /// The timestamp at which the metric was collected
-    pub timestamp: Timestamp,
-    /// A reference to a metric trait object
-    pub metric: &'a dyn Metric,
+impl PartialOrd for MemorySegmentsMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.max_value().partial_cmp(&other.max_value())
+    }
 }
 
-impl<'a> DatedMetric<'a> {
-    pub fn new(metric: &'a dyn Metric, timestamp: Timestamp) -> Self {
-        Self { metric, timestamp }
+#[cfg(test)]
+mod test_memory_segments_metric {
+    use crate::core::metrics::{MemorySegmentsMetric, Metric};
+
+    #[test]
+    fn test_should_expose_stack_and_data_sizes_as_components() {
+        let metric = MemorySegmentsMetric::new(132 * 1024, 8 * 1024 * 1024);
+
+        assert_eq!(metric.as_f64(0).unwrap(), 135168.);
+        assert_eq!(metric.as_f64(1).unwrap(), 8388608.);
+        assert!(metric.as_f64(2).is_err());
     }
-}
 
-/// Metric representing a percent value (e.g. CPU usage)
-#[derive(Debug, PartialEq, Copy, Clone)]
-pub struct PercentMetric {
-    value: f64,
-}
+    #[test]
+    fn test_should_represent_stack_and_data_sizes() {
+        let metric = MemorySegmentsMetric::new(132 * 1024, 8 * 1024 * 1024);
 
-impl PercentMetric {
-    pub fn new(percent_usage: f64) -> Self {
-        Self { value: percent_usage }
+        assert_eq!(metric.concise_repr(), "8.0M");
+        assert_eq!(metric.explicit_repr(0).unwrap(), "Stack: 132.00kB");
+        assert_eq!(metric.explicit_repr(1).unwrap(), "Data : 8.00MB");
     }
 }
 
-impl Default for PercentMetric {
-    fn default() -> Self {
-        PercentMetric::new(0.)
+/// Metric representing a signed variation of an amount of bytes, per second (e.g. memory growth)
+///
+/// Unlike other metrics, its value is negative when the amount of bytes decreases.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct SignedBytesMetric {
+    bytes: i64,
+}
+
+impl SignedBytesMetric {
+    pub fn new(bytes: i64) -> Self {
+        Self { bytes }
     }
 }
 
-impl Metric for PercentMetric {
-    /// Returns 1, as PercentMetric is only composed of one element: the percent value.
+impl Metric for SignedBytesMetric {
+    /// Returns 1, as SignedBytesMetric is only composed of one element: the variation of bytes.
     fn cardinality(&self) -> usize {
         1
     }
 
     fn as_f64(&self, index: usize) -> Result<f64, Error> {
         match index {
-            0 => Ok(self.value),
+            0 => Ok(self.bytes as f64),
             _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
         }
     }
 
     fn max_value(&self) -> f64 {
-        self.value
+        self.bytes as f64
     }
 
     fn unit(&self) -> &'static str {
-        "%"
+        "B/s"
     }
 
     fn concise_repr(&self) -> String {
-        self.concise_repr_of_value(self.value)
+        self.concise_repr_of_value(self.max_value())
     }
 
     fn concise_repr_of_value(&self, value: f64) -> String {
-        format!("{:.1}", value)
+        self.concise_repr_of_value_with_precision(value, 1)
+    }
+
+    fn concise_repr_of_value_with_precision(&self, value: f64, precision: usize) -> String {
+        format_signed_bytes(value as i64, precision)
     }
 
     fn explicit_repr(&self, index: usize) -> Result<String, Error> {
         match index {
-            0 => Ok(format!("Usage {:.2}%", self.value)),
+            0 => Ok(format!("Growth {}B/s", format_signed_bytes(self.bytes, 2))),
             _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
         }
     }
 }
 
-impl PartialOrd for PercentMetric {
+impl PartialOrd for SignedBytesMetric {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.value.partial_cmp(&other.value)
+        self.bytes.partial_cmp(&other.bytes)
     }
 }
 
 #[cfg(test)]
-mod test_percent_metric {
+mod test_signed_bytes_metric {
     use std::cmp::Ordering;
 
-    use crate::core::metrics::{Metric, PercentMetric};
+    use crate::core::metrics::{Metric, SignedBytesMetric};
 
     #[test]
-    fn test_should_return_sole_value_as_max_value() {
-        let metric = PercentMetric::new(10.);
-        assert_eq!(metric.max_value(), 10.);
+    fn test_should_represent_positive_variation_with_prefix() {
+        assert_eq!(SignedBytesMetric::new(3 * 1024 * 1024).concise_repr(), "+3.0M");
+        assert_eq!(
+            SignedBytesMetric::new(1536).explicit_repr(0).unwrap(),
+            "Growth +1.50kB/s"
+        );
     }
 
     #[test]
-    fn test_should_correctly_compare_metrics_based_on_percent_value() {
-        let lesser_metric = PercentMetric::new(10.);
-        let greater_metric = PercentMetric::new(20.);
+    fn test_should_represent_negative_variation_with_prefix() {
+        assert_eq!(SignedBytesMetric::new(-3 * 1024 * 1024).concise_repr(), "-3.0M");
+        assert_eq!(
+            SignedBytesMetric::new(-1536).explicit_repr(0).unwrap(),
+            "Growth -1.50kB/s"
+        );
+    }
 
-        assert_eq!(lesser_metric.partial_cmp(&greater_metric), Some(Ordering::Less));
-        assert_eq!(greater_metric.partial_cmp(&lesser_metric), Some(Ordering::Greater));
+    #[test]
+    fn test_should_represent_null_variation_without_sign() {
+        assert_eq!(SignedBytesMetric::new(0).concise_repr(), "0");
+    }
+
+    #[test]
+    fn test_should_return_negative_variation_as_f64() {
+        assert_eq!(SignedBytesMetric::new(-42).as_f64(0).unwrap(), -42.);
+    }
+
+    #[test]
+    fn test_should_order_negative_variations_before_positive_ones() {
+        assert_eq!(
+            SignedBytesMetric::new(-10).partial_cmp(&SignedBytesMetric::new(2)),
+            Some(Ordering::Less)
+        );
     }
 }
 
@@ -212,7 +1232,11 @@ impl Metric for IOMetric {
     }
 
     fn concise_repr_of_value(&self, value: f64) -> String {
-        format_bytes(value as usize, 1)
+        self.concise_repr_of_value_with_precision(value, 1)
+    }
+
+    fn concise_repr_of_value_with_precision(&self, value: f64, precision: usize) -> String {
+        format_bytes(value as usize, precision)
     }
 
     fn explicit_repr(&self, index: usize) -> Result<String, Error> {
@@ -255,6 +1279,17 @@ fn format_bytes(bytes_val: usize, precision: usize) -> String {
     )
 }
 
+/// Formats a signed amount of bytes, prefixed by its sign unless it is null
+fn format_signed_bytes(bytes_val: i64, precision: usize) -> String {
+    let formatted = format_bytes(bytes_val.unsigned_abs() as usize, precision);
+
+    match bytes_val.signum() {
+        1 => format!("+{}", formatted),
+        -1 => format!("-{}", formatted),
+        _ => formatted,
+    }
+}
+
 impl PartialOrd for IOMetric {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.max_value().partial_cmp(&other.max_value())
@@ -301,6 +1336,293 @@ mod test_io_metric {
     }
 }
 
+/// Metric representing the rates of read and write operations, in operations/sec
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct OperationsMetric {
+    reads: usize,
+    writes: usize,
+}
+
+impl OperationsMetric {
+    pub fn new(reads: usize, writes: usize) -> Self {
+        Self { reads, writes }
+    }
+}
+
+impl Metric for OperationsMetric {
+    /// Returns 2, as an OperationsMetric is composed of two elements: the read and the write rates
+    fn cardinality(&self) -> usize {
+        2
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.reads as f64),
+            1 => Ok(self.writes as f64),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.reads.max(self.writes) as f64
+    }
+
+    fn unit(&self) -> &'static str {
+        "op/s"
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.max_value())
+    }
+
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        format!("{:.0}", value)
+    }
+
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("Reads : {} op/s", self.reads)),
+            1 => Ok(format!("Writes: {} op/s", self.writes)),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+}
+
+impl PartialOrd for OperationsMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.max_value().partial_cmp(&other.max_value())
+    }
+}
+
+#[cfg(test)]
+mod test_operations_metric {
+    use std::cmp::Ordering;
+
+    use crate::core::metrics::{Metric, OperationsMetric};
+
+    #[test]
+    fn test_components_should_be_reads_then_writes() {
+        let metric = OperationsMetric::new(3, 7);
+
+        assert_eq!(metric.as_f64(0).unwrap(), 3.);
+        assert_eq!(metric.as_f64(1).unwrap(), 7.);
+    }
+
+    #[test]
+    fn test_should_order_metrics_based_on_max_value() {
+        assert_eq!(
+            OperationsMetric::new(10, 20).partial_cmp(&OperationsMetric::new(25, 5)),
+            Some(Ordering::Less)
+        );
+    }
+
+    #[test]
+    fn test_explicit_repr_should_name_operations() {
+        assert_eq!(OperationsMetric::new(3, 7).explicit_repr(1).unwrap(), "Writes: 7 op/s");
+    }
+}
+
+/// Metric representing the amount of sockets in each tracked TCP state (e.g. to detect connection leaks)
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct SocketStatesMetric {
+    established: usize,
+    time_wait: usize,
+    close_wait: usize,
+}
+
+impl SocketStatesMetric {
+    pub fn new(established: usize, time_wait: usize, close_wait: usize) -> Self {
+        Self {
+            established,
+            time_wait,
+            close_wait,
+        }
+    }
+}
+
+impl Metric for SocketStatesMetric {
+    /// Returns 3, as a SocketStatesMetric is composed of the counts of ESTABLISHED, TIME_WAIT and CLOSE_WAIT sockets
+    fn cardinality(&self) -> usize {
+        3
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.established as f64),
+            1 => Ok(self.time_wait as f64),
+            2 => Ok(self.close_wait as f64),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.established.max(self.time_wait).max(self.close_wait) as f64
+    }
+
+    fn unit(&self) -> &'static str {
+        "#"
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.max_value())
+    }
+
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        format!("{:.0}", value)
+    }
+
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("Established: {}", self.established)),
+            1 => Ok(format!("Time wait  : {}", self.time_wait)),
+            2 => Ok(format!("Close wait : {}", self.close_wait)),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+}
+
+impl PartialOrd for SocketStatesMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.max_value().partial_cmp(&other.max_value())
+    }
+}
+
+#[cfg(test)]
+mod test_socket_states_metric {
+    use crate::core::metrics::{Metric, SocketStatesMetric};
+
+    #[test]
+    fn test_components_should_be_established_then_time_wait_then_close_wait() {
+        let metric = SocketStatesMetric::new(3, 5, 7);
+
+        assert_eq!(metric.as_f64(0).unwrap(), 3.);
+        assert_eq!(metric.as_f64(1).unwrap(), 5.);
+        assert_eq!(metric.as_f64(2).unwrap(), 7.);
+        assert!(metric.as_f64(3).is_err());
+    }
+
+    #[test]
+    fn test_max_value_should_be_count_of_most_frequent_state() {
+        assert_eq!(SocketStatesMetric::new(3, 9, 7).max_value(), 9.);
+    }
+
+    #[test]
+    fn test_explicit_repr_should_name_states() {
+        assert_eq!(
+            SocketStatesMetric::new(3, 5, 7).explicit_repr(2).unwrap(),
+            "Close wait : 7"
+        );
+    }
+}
+
+/// Metric representing the rate of bytes that a process receives and sends over TCP and over UDP
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct ProtocolIOMetric {
+    tcp_input: usize,
+    tcp_output: usize,
+    udp_input: usize,
+    udp_output: usize,
+}
+
+impl ProtocolIOMetric {
+    pub fn new(tcp_input: usize, tcp_output: usize, udp_input: usize, udp_output: usize) -> Self {
+        Self {
+            tcp_input,
+            tcp_output,
+            udp_input,
+            udp_output,
+        }
+    }
+}
+
+impl Metric for ProtocolIOMetric {
+    /// Returns 4, as a ProtocolIOMetric is composed of the TCP input and output rates, then of the UDP ones
+    fn cardinality(&self) -> usize {
+        4
+    }
+
+    fn as_f64(&self, index: usize) -> Result<f64, Error> {
+        match index {
+            0 => Ok(self.tcp_input as f64),
+            1 => Ok(self.tcp_output as f64),
+            2 => Ok(self.udp_input as f64),
+            3 => Ok(self.udp_output as f64),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.tcp_input
+            .max(self.tcp_output)
+            .max(self.udp_input)
+            .max(self.udp_output) as f64
+    }
+
+    fn unit(&self) -> &'static str {
+        "B/s"
+    }
+
+    fn concise_repr(&self) -> String {
+        self.concise_repr_of_value(self.max_value())
+    }
+
+    fn concise_repr_of_value(&self, value: f64) -> String {
+        self.concise_repr_of_value_with_precision(value, 1)
+    }
+
+    fn concise_repr_of_value_with_precision(&self, value: f64, precision: usize) -> String {
+        format_bytes(value as usize, precision)
+    }
+
+    fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+        match index {
+            0 => Ok(format!("TCP input : {}B/s", format_bytes(self.tcp_input, 2))),
+            1 => Ok(format!("TCP output: {}B/s", format_bytes(self.tcp_output, 2))),
+            2 => Ok(format!("UDP input : {}B/s", format_bytes(self.udp_input, 2))),
+            3 => Ok(format!("UDP output: {}B/s", format_bytes(self.udp_output, 2))),
+            _ => Err(Error::RawMetricAccessError(index, self.cardinality())),
+        }
+    }
+}
+
+impl PartialOrd for ProtocolIOMetric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.max_value().partial_cmp(&other.max_value())
+    }
+}
+
+#[cfg(test)]
+mod test_protocol_io_metric {
+    use crate::core::metrics::{Metric, ProtocolIOMetric};
+
+    fn metric() -> ProtocolIOMetric {
+        ProtocolIOMetric::new(1, 2, 3, 4)
+    }
+
+    #[test]
+    fn test_components_should_be_tcp_then_udp() {
+        let metric = metric();
+
+        assert_eq!(metric.as_f64(0).unwrap(), 1.);
+        assert_eq!(metric.as_f64(1).unwrap(), 2.);
+        assert_eq!(metric.as_f64(2).unwrap(), 3.);
+        assert_eq!(metric.as_f64(3).unwrap(), 4.);
+        assert!(metric.as_f64(4).is_err());
+    }
+
+    #[test]
+    fn test_max_value_should_be_largest_rate_of_all_protocols() {
+        assert_eq!(metric().max_value(), 4.);
+    }
+
+    #[test]
+    fn test_explicit_repr_should_name_protocol_and_direction() {
+        assert_eq!(metric().explicit_repr(0).unwrap(), "TCP input : 1.00B/s");
+        assert_eq!(metric().explicit_repr(3).unwrap(), "UDP output: 4.00B/s");
+        assert!(metric().explicit_repr(4).is_err());
+    }
+}
+
 #[cfg(test)]
 mod test_formatted_bytes {
     use rstest::*;
@@ -6,13 +6,30 @@ use thiserror::Error;
 
 use crate::core::process::Pid;
 
+pub mod backoff;
+pub mod churn;
 pub mod collection;
+pub mod command;
+pub mod connections;
+pub mod devices;
+pub mod external;
+pub mod filesystems;
+pub mod histogram;
+pub mod labels;
 pub mod metrics;
 pub mod ordering;
 pub mod probe;
 pub mod process;
+pub mod sample;
+pub mod service;
+pub mod signal;
+pub mod sources;
+pub mod summary;
 pub mod time;
+pub mod tree;
+pub mod units;
 pub mod view;
+pub mod watch;
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -27,4 +44,59 @@ pub enum Error {
     IOError(#[from] io::Error),
     #[error("Error accessing raw value {0:?} (cardinality: {1:?})")]
     RawMetricAccessError(usize, usize),
+    #[error("PID {0:?} has been recycled by another process")]
+    RecycledPID(Pid),
+    #[error("PID {0:?} is the process of spv, which can not signal itself")]
+    OwnProcess(Pid),
+    #[error("Could not signal process {0:?}: {1:?}")]
+    SignalError(Pid, #[source] anyhow::Error),
+}
+
+impl Error {
+    /// Indicates if the error was caused by a lack of permission to access a resource
+    pub fn is_permission_denied(&self) -> bool {
+        match self {
+            Error::IOError(e) => e.kind() == io::ErrorKind::PermissionDenied,
+            Error::ScanProcessesError(source) | Error::ProbingError(_, source) => source.chain().any(|cause| {
+                cause
+                    .downcast_ref::<io::Error>()
+                    .is_some_and(|e| e.kind() == io::ErrorKind::PermissionDenied)
+            }),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_error {
+    use std::io;
+
+    use crate::core::Error;
+
+    #[derive(thiserror::Error, Debug)]
+    #[error("Could not read file")]
+    struct ReadError(#[source] io::Error);
+
+    #[test]
+    fn test_should_detect_denied_io() {
+        let error = Error::IOError(io::Error::from(io::ErrorKind::PermissionDenied));
+
+        assert!(error.is_permission_denied());
+    }
+
+    #[test]
+    fn test_should_detect_permission_denied_causing_probing_error() {
+        let cause = ReadError(io::Error::from(io::ErrorKind::PermissionDenied));
+        let error = Error::ProbingError("Could not probe".to_string(), cause.into());
+
+        assert!(error.is_permission_denied());
+    }
+
+    #[test]
+    fn test_should_not_detect_other_errors_as_permission_denied() {
+        let cause = ReadError(io::Error::from(io::ErrorKind::NotFound));
+
+        assert!(!Error::ProbingError("Could not probe".to_string(), cause.into()).is_permission_denied());
+        assert!(!Error::InvalidPID(1).is_permission_denied());
+    }
 }
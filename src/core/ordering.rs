@@ -1,67 +1,118 @@
 use std::cmp::Ordering;
+use std::collections::HashSet;
 
 use crate::core::collection::MetricCollector;
-use crate::core::process::{ProcessMetadata, Status};
+use crate::core::process::{Pid, ProcessMetadata, Status};
+use crate::core::time::Span;
 
 /// Defines on which criteria processes should be sorted
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, PartialEq, Debug)]
 pub enum ProcessOrdering {
-    /// Orders the processes by their current metrics, in a descending order
+    /// Orders the processes by the current metrics of the displayed collector, in a descending order
     CurrentMetric,
+    /// Orders the processes by the current metrics of the collector with the given name, in a descending order
+    Metric(String),
     /// Orders the processes by their Pid, in an ascending order
     Pid,
     /// Orders the processes by their command, in an alphabetically ascending order
     Command,
 }
 
-// As it is not possible to iterate over enumeration variants, we use this list to iterate over them in multiple parts
-// of the code.
-pub const PROCESS_ORDERING_CRITERIA: [ProcessOrdering; 3] = [
-    ProcessOrdering::CurrentMetric,
-    ProcessOrdering::Pid,
-    ProcessOrdering::Command,
-];
+/// Lists the criteria on which processes can be sorted, with one criteria for each of the given collectors
+///
+/// # Arguments
+///  * `collector_names`: The names of the collectors whose metrics can be used to sort processes
+pub fn ordering_criteria(collector_names: &[&str]) -> Vec<ProcessOrdering> {
+    let mut criteria = vec![ProcessOrdering::CurrentMetric];
+    criteria.extend(
+        collector_names
+            .iter()
+            .map(|name| ProcessOrdering::Metric(name.to_string())),
+    );
+    criteria.extend([ProcessOrdering::Pid, ProcessOrdering::Command]);
+
+    criteria
+}
 
 /// Sort processes based on the specified criteria
 ///
 /// Regardless of the criteria, running processes are displayed before dead processes
+///
+/// # Arguments
+///  * `processes`: The processes to sort
+///  * `criteria`: The criteria on which to sort the processes
+///  * `metrics_collector`: The collector whose metrics are compared when sorting processes by their metrics
+///  * `averaged_span`: If set, processes sorted by their metrics are compared by the average of their metrics over this
+///    span, instead of their last metrics
 pub fn sort_processes(
     processes: &mut [ProcessMetadata],
     criteria: ProcessOrdering,
-    current_collector: &dyn MetricCollector,
+    metrics_collector: &dyn MetricCollector,
+    averaged_span: Option<Span>,
 ) {
     processes.sort_by(|pm1, pm2| match (pm1.status(), pm2.status()) {
         (Status::RUNNING, Status::DEAD) => Ordering::Less,
         (Status::DEAD, Status::RUNNING) => Ordering::Greater,
-        (_, _) => order_processes_based_on_criteria(pm1, pm2, criteria, current_collector),
+        (_, _) => order_processes_based_on_criteria(pm1, pm2, &criteria, metrics_collector, averaged_span),
     });
 }
 
+/// Moves the pinned processes ahead of the other processes, such as those whose metrics breach a threshold
+///
+/// Both the pinned processes and the other processes keep their order among themselves, and running processes are
+/// still displayed before dead processes.
+///
+/// # Arguments
+///  * `processes`: The processes, already sorted
+///  * `pinned`: The PIDs of the pinned processes
+pub fn pin_processes(processes: &mut [ProcessMetadata], pinned: &HashSet<Pid>) {
+    if pinned.is_empty() {
+        return;
+    }
+
+    processes.sort_by_key(|pm| (pm.status() == Status::DEAD, !pinned.contains(&pm.pid())));
+}
+
 fn order_processes_based_on_criteria(
     pm1: &ProcessMetadata,
     pm2: &ProcessMetadata,
-    criteria: ProcessOrdering,
-    current_collector: &dyn MetricCollector,
+    criteria: &ProcessOrdering,
+    metrics_collector: &dyn MetricCollector,
+    averaged_span: Option<Span>,
 ) -> Ordering {
-    match criteria {
-        ProcessOrdering::CurrentMetric => current_collector
+    match (criteria, averaged_span) {
+        (ProcessOrdering::CurrentMetric | ProcessOrdering::Metric(_), Some(span)) => {
+            let average1 = metrics_collector.span_average(pm1.pid(), span);
+            let average2 = metrics_collector.span_average(pm2.pid(), span);
+
+            average2.total_cmp(&average1)
+        }
+        (ProcessOrdering::CurrentMetric | ProcessOrdering::Metric(_), None) => metrics_collector
             .compare_pids_by_last_metrics(pm1.pid(), pm2.pid())
             .reverse(),
-        ProcessOrdering::Pid => pm1.pid().cmp(&pm2.pid()),
-        ProcessOrdering::Command => pm1.command().cmp(pm2.command()),
+        (ProcessOrdering::Pid, _) => pm1.pid().cmp(&pm2.pid()),
+        (ProcessOrdering::Command, _) => pm1.display_name().cmp(pm2.display_name()),
     }
 }
 
 #[cfg(test)]
 mod test_ordering {
+    use std::collections::{HashMap, HashSet, VecDeque};
+    use std::time::Duration;
+
     use rstest::{fixture, rstest};
 
     use crate::core::collection::{MetricCollector, ProbeCollector};
     use crate::core::metrics::PercentMetric;
-    use crate::core::ordering::{sort_processes, ProcessOrdering};
+    use crate::core::ordering::{ordering_criteria, pin_processes, sort_processes, ProcessOrdering};
     use crate::core::probe::fakes::FakeProbe;
-    use crate::core::process::ProcessMetadata;
-    use crate::core::time::Timestamp;
+    use crate::core::probe::Probe;
+    use crate::core::process::{Pid, ProcessMetadata};
+    use crate::core::time::test_utils::{
+        advance_time_and_refresh_timestamp, setup_fake_clock_to_prevent_substract_overflow,
+    };
+    use crate::core::time::{Span, Timestamp};
+    use crate::core::Error;
 
     #[fixture]
     fn processes() -> Vec<ProcessMetadata> {
@@ -87,7 +138,7 @@ mod test_ordering {
 
         processes[0].mark_dead(); // Process with Pid 1 is dead
 
-        sort_processes(&mut processes, ProcessOrdering::Pid, &default_collector);
+        sort_processes(&mut processes, ProcessOrdering::Pid, &default_collector, None);
 
         let sorted_processes_pids: Vec<_> = processes.iter().map(|pm| pm.pid()).collect();
         assert_eq!(&sorted_processes_pids, &[2, 1]);
@@ -98,18 +149,30 @@ mod test_ordering {
         mut processes: Vec<ProcessMetadata>,
         default_collector: ProbeCollector<PercentMetric>,
     ) {
-        sort_processes(&mut processes, ProcessOrdering::Command, &default_collector);
+        sort_processes(&mut processes, ProcessOrdering::Command, &default_collector, None);
 
         let sorted_processes_commands: Vec<_> = processes.iter().map(|pm| pm.command()).collect();
         assert_eq!(&sorted_processes_commands, &["aa", "ab", "c"]);
     }
 
+    #[rstest]
+    fn should_sort_labelled_processes_by_their_label(
+        mut processes: Vec<ProcessMetadata>,
+        default_collector: ProbeCollector<PercentMetric>,
+    ) {
+        processes[0].set_label("a");
+        sort_processes(&mut processes, ProcessOrdering::Command, &default_collector, None);
+
+        let sorted_processes_names: Vec<_> = processes.iter().map(|pm| pm.display_name()).collect();
+        assert_eq!(&sorted_processes_names, &["a", "aa", "ab"]);
+    }
+
     #[rstest]
     fn should_sort_processes_by_their_pid(
         mut processes: Vec<ProcessMetadata>,
         default_collector: ProbeCollector<PercentMetric>,
     ) {
-        sort_processes(&mut processes, ProcessOrdering::Pid, &default_collector);
+        sort_processes(&mut processes, ProcessOrdering::Pid, &default_collector, None);
 
         let sorted_processes_pids: Vec<_> = processes.iter().map(|pm| pm.pid()).collect();
         assert_eq!(&sorted_processes_pids, &[1, 2, 25]);
@@ -121,9 +184,126 @@ mod test_ordering {
         let mut collector = ProbeCollector::new(probe);
         collector.collect(&[1, 2, 25]).unwrap();
 
-        sort_processes(&mut processes, ProcessOrdering::CurrentMetric, &collector);
+        sort_processes(&mut processes, ProcessOrdering::CurrentMetric, &collector, None);
 
         let sorted_processes_pids: Vec<_> = processes.iter().map(|pm| pm.pid()).collect();
         assert_eq!(&sorted_processes_pids, &[2, 1, 25]);
     }
+
+    /// Probes the given values of each process one after the other
+    struct SequenceProbe {
+        values: HashMap<Pid, VecDeque<f64>>,
+    }
+
+    impl Probe<PercentMetric> for SequenceProbe {
+        fn name(&self) -> &'static str {
+            "sequence"
+        }
+
+        fn probe(&mut self, pid: Pid) -> Result<PercentMetric, Error> {
+            self.values
+                .get_mut(&pid)
+                .and_then(|values| values.pop_front())
+                .map(PercentMetric::new)
+                .ok_or(Error::InvalidPID(pid))
+        }
+
+        fn cleanup(&mut self, _pids: &[Pid]) {}
+    }
+
+    /// Collects metrics of processes 1, 2 and 25, of which process 2 has a momentary spike on the last collection
+    #[fixture]
+    fn spiking_collector() -> ProbeCollector<PercentMetric> {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let values = hashmap!(1 => [50., 50.].into(), 2 => [5., 90.].into(), 25 => [20., 20.].into());
+        let mut collector = ProbeCollector::new(SequenceProbe { values });
+
+        for _ in 0..2 {
+            advance_time_and_refresh_timestamp(Duration::from_secs(1));
+            collector.collect(&[1, 2, 25]).unwrap();
+        }
+
+        collector
+    }
+
+    #[rstest]
+    fn should_sort_processes_by_average_of_their_metrics_over_span(
+        mut processes: Vec<ProcessMetadata>,
+        spiking_collector: ProbeCollector<PercentMetric>,
+    ) {
+        let span = Span::new(Timestamp::now() - Duration::from_secs(10), Timestamp::now());
+
+        sort_processes(
+            &mut processes,
+            ProcessOrdering::CurrentMetric,
+            &spiking_collector,
+            Some(span),
+        );
+
+        assert_eq!(pids(&processes), vec![1, 2, 25]);
+    }
+
+    #[rstest]
+    fn should_sort_processes_by_last_metrics_without_averaged_span(
+        mut processes: Vec<ProcessMetadata>,
+        spiking_collector: ProbeCollector<PercentMetric>,
+    ) {
+        sort_processes(&mut processes, ProcessOrdering::CurrentMetric, &spiking_collector, None);
+
+        assert_eq!(pids(&processes), vec![2, 1, 25]);
+    }
+
+    fn pids(processes: &[ProcessMetadata]) -> Vec<Pid> {
+        processes.iter().map(|pm| pm.pid()).collect()
+    }
+
+    #[rstest]
+    fn should_sort_pinned_processes_before_other_processes_by_their_metric() {
+        let mut processes: Vec<_> = (1..=5)
+            .map(|pid| ProcessMetadata::new(pid, "cmd", Timestamp::now()))
+            .collect();
+        let probe = FakeProbe::from_percent_map(hashmap!(1 => 95., 2 => 40., 3 => 99., 4 => 60., 5 => 92.));
+        let mut collector = ProbeCollector::new(probe);
+        collector.collect(&[1, 2, 3, 4, 5]).unwrap();
+
+        sort_processes(&mut processes, ProcessOrdering::CurrentMetric, &collector, None);
+        pin_processes(&mut processes, &HashSet::from([1, 3, 5]));
+
+        assert_eq!(pids(&processes), vec![3, 1, 5, 4, 2]);
+    }
+
+    #[rstest]
+    fn should_keep_order_of_processes_when_none_is_pinned(
+        mut processes: Vec<ProcessMetadata>,
+        default_collector: ProbeCollector<PercentMetric>,
+    ) {
+        sort_processes(&mut processes, ProcessOrdering::Command, &default_collector, None);
+
+        pin_processes(&mut processes, &HashSet::new());
+
+        assert_eq!(pids(&processes), vec![2, 25, 1]);
+    }
+
+    #[rstest]
+    fn should_sort_running_processes_before_pinned_dead_processes(mut processes: Vec<ProcessMetadata>) {
+        processes[0].mark_dead();
+
+        pin_processes(&mut processes, &HashSet::from([1, 2]));
+
+        assert_eq!(pids(&processes), vec![2, 25, 1]);
+    }
+
+    #[test]
+    fn should_list_one_criteria_per_collector() {
+        assert_eq!(
+            ordering_criteria(&["CPU usage", "Disk I/O"]),
+            vec![
+                ProcessOrdering::CurrentMetric,
+                ProcessOrdering::Metric("CPU usage".to_string()),
+                ProcessOrdering::Metric("Disk I/O".to_string()),
+                ProcessOrdering::Pid,
+                ProcessOrdering::Command,
+            ]
+        );
+    }
 }
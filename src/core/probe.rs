@@ -14,7 +14,7 @@ where
     M: Metric + Copy + Default,
 {
     /// The name of the probe, as displayed in the application tab
-    fn name(&self) -> &'static str;
+    fn name(&self) -> &str;
 
     /// Called on each probe refresh, before all processes are probed
     fn init_iteration(&mut self) -> Result<(), Error> {
@@ -61,7 +61,8 @@ where
 
 #[cfg(test)]
 pub mod fakes {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
+    use std::io;
 
     use crate::core::metrics::{Metric, PercentMetric};
     use crate::core::probe::Probe;
@@ -73,6 +74,8 @@ pub mod fakes {
         M: Metric + Copy + Default,
     {
         probed_metrics: HashMap<Pid, Result<M, Error>>,
+        denied_pids: HashSet<Pid>,
+        name: &'static str,
     }
 
     impl FakeProbe<PercentMetric> {
@@ -82,7 +85,20 @@ pub mod fakes {
                 .map(|(pid, val)| (pid, Ok(PercentMetric::new(val))))
                 .collect();
 
-            FakeProbe { probed_metrics }
+            FakeProbe {
+                probed_metrics,
+                denied_pids: HashSet::new(),
+                name: "fake",
+            }
+        }
+    }
+
+    impl<M> Default for FakeProbe<M>
+    where
+        M: Metric + Copy + Default,
+    {
+        fn default() -> Self {
+            Self::new()
         }
     }
 
@@ -93,17 +109,31 @@ pub mod fakes {
         pub fn new() -> Self {
             Self {
                 probed_metrics: hashmap!(),
+                denied_pids: HashSet::new(),
+                name: "fake",
             }
         }
+
+        /// Names the probe, to tell it apart from other fake probes
+        pub fn with_name(mut self, name: &'static str) -> Self {
+            self.name = name;
+            self
+        }
     }
 
     impl<M> FakeProbe<M>
     where
         M: Metric + Copy + Default,
     {
+        /// Makes every subsequent probing of the given process fail
         pub fn make_pid_fail(&mut self, pid: Pid) {
             self.probed_metrics.insert(pid, Err(Error::InvalidPID(pid)));
         }
+
+        /// Makes every subsequent probing of the given process fail, as if the permission to probe it was denied
+        pub fn make_pid_denied(&mut self, pid: Pid) {
+            self.denied_pids.insert(pid);
+        }
     }
 
     impl<M> Probe<M> for FakeProbe<M>
@@ -111,13 +141,24 @@ pub mod fakes {
         M: Metric + Copy + Default,
     {
         fn name(&self) -> &'static str {
-            "fake"
+            self.name
         }
 
         fn probe(&mut self, pid: Pid) -> Result<M, Error> {
-            self.probed_metrics
+            if self.denied_pids.contains(&pid) {
+                return Err(Error::IOError(io::Error::from(io::ErrorKind::PermissionDenied)));
+            }
+
+            let ret = self
+                .probed_metrics
                 .remove(&pid)
-                .expect("No metric has been set for this pid")
+                .expect("No metric has been set for this pid");
+
+            if ret.is_err() {
+                self.make_pid_fail(pid);
+            }
+
+            ret
         }
 
         fn cleanup(&mut self, _pids: &[Pid]) {
@@ -1,11 +1,14 @@
 //! Process discovery utilities
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::mem::take;
+use std::time::Duration;
 
 use log::warn;
 
+use crate::core::churn::{ChurnRate, ProcessChurn};
+use crate::core::labels::ProcessLabels;
 use crate::core::time::{Span, Timestamp};
 use crate::core::Error;
 
@@ -18,9 +21,21 @@ pub type Pid = u32; // TODO add new type UPID (Unique PID) through the entire ex
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub struct ProcessMetadata {
     pid: Pid,
+    tgid: Pid,
+    ppid: Pid,
     command: String,
+    cmdline: String,
+    label: Option<String>,
     status: Status,
     running_span: Span,
+    // When spv first saw the process, from which its metrics are collected
+    first_seen: Timestamp,
+    cpu_time: Duration,
+    isolated: bool,
+    // The systemd unit of the process, such as nginx.service
+    unit: Option<String>,
+    starttime: u64,
+    stopped: bool,
 }
 
 #[derive(Eq, PartialEq, Debug, Copy, Clone)]
@@ -29,6 +44,41 @@ pub enum Status {
     DEAD,
 }
 
+/// The identifier under which processes are displayed
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Default)]
+pub enum ProcessIdentifier {
+    #[default]
+    Pid,
+    Tgid,
+}
+
+impl ProcessIdentifier {
+    /// Returns the identifier of the given process
+    pub fn of(&self, process: &ProcessMetadata) -> Pid {
+        match self {
+            ProcessIdentifier::Pid => process.pid(),
+            ProcessIdentifier::Tgid => process.tgid(),
+        }
+    }
+
+    /// Returns the other identifier
+    pub fn toggled(&self) -> Self {
+        match self {
+            ProcessIdentifier::Pid => ProcessIdentifier::Tgid,
+            ProcessIdentifier::Tgid => ProcessIdentifier::Pid,
+        }
+    }
+}
+
+impl Display for ProcessIdentifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessIdentifier::Pid => f.write_str("PID"),
+            ProcessIdentifier::Tgid => f.write_str("TGID"),
+        }
+    }
+}
+
 impl Display for Status {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -40,16 +90,26 @@ impl Display for Status {
 
 /// Describes a process
 impl ProcessMetadata {
-    /// Returns a new instance of a ProcessMetadata
+    /// Returns a new instance of a ProcessMetadata, first seen by spv at the current timestamp
     pub fn new<T>(pid: Pid, command: T, spawntime: Timestamp) -> Self
     where
         T: Into<String>,
     {
         ProcessMetadata {
             pid,
+            tgid: pid,
+            ppid: 0,
             command: command.into(),
+            cmdline: String::new(),
+            label: None,
             status: Status::RUNNING,
             running_span: Span::from_begin(spawntime),
+            first_seen: Timestamp::now(),
+            cpu_time: Duration::ZERO,
+            isolated: false,
+            unit: None,
+            starttime: 0,
+            stopped: false,
         }
     }
 
@@ -60,6 +120,81 @@ impl ProcessMetadata {
         self.pid
     }
 
+    /// Sets the thread group ID of the process, which otherwise defaults to its PID
+    pub fn with_tgid(mut self, tgid: Pid) -> Self {
+        self.tgid = tgid;
+        self
+    }
+
+    /// Returns the thread group ID of the process
+    ///
+    /// It is the PID of the main thread of the process, shared by all its threads
+    pub fn tgid(&self) -> Pid {
+        self.tgid
+    }
+
+    /// Sets the PID of the parent of the process, which otherwise defaults to 0
+    pub fn with_ppid(mut self, ppid: Pid) -> Self {
+        self.ppid = ppid;
+        self
+    }
+
+    /// Returns the PID of the parent of the process, as of its discovery
+    ///
+    /// It is 0 for processes without parent, such as init and kernel threads spawned by the kernel itself.
+    pub fn ppid(&self) -> Pid {
+        self.ppid
+    }
+
+    /// Indicates whether the process runs in other namespaces than spv, which is usually the case of containers
+    pub fn with_isolation(mut self, isolated: bool) -> Self {
+        self.isolated = isolated;
+        self
+    }
+
+    /// Indicates if the process runs in a different PID or mount namespace than spv
+    pub fn is_isolated(&self) -> bool {
+        self.isolated
+    }
+
+    /// Sets the systemd unit to which the process belongs, such as nginx.service
+    pub fn with_unit<T>(mut self, unit: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    /// Returns the systemd unit to which the process belongs, if any
+    pub fn unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+
+    /// Sets the time at which the process started after boot, as reported by the OS, which otherwise defaults to 0
+    pub fn with_starttime(mut self, starttime: u64) -> Self {
+        self.starttime = starttime;
+        self
+    }
+
+    /// Returns the time at which the process started after boot, as reported by the OS
+    ///
+    /// As two processes which successively use the same PID can not start at the same time, this time tells whether a
+    /// PID has been recycled by another process.
+    pub fn starttime(&self) -> u64 {
+        self.starttime
+    }
+
+    /// Indicates if the process is stopped by a signal (e.g. SIGSTOP), as of the latest collection
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Marks the process as stopped by a signal, or as resumed
+    pub fn set_stopped(&mut self, stopped: bool) {
+        self.stopped = stopped;
+    }
+
     /// Returns the command used to execute the given process
     ///
     /// This method does not return the arguments passed to the command
@@ -67,6 +202,51 @@ impl ProcessMetadata {
         self.command.as_str()
     }
 
+    /// Replaces the command of the process, as processes may rename themselves while running
+    pub fn set_command<T>(&mut self, command: T)
+    where
+        T: Into<String>,
+    {
+        self.command = command.into();
+    }
+
+    /// Sets the full command line of the process, including its arguments
+    pub fn with_cmdline<T>(mut self, cmdline: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.cmdline = cmdline.into();
+        self
+    }
+
+    /// Returns the full command line of the process, which is empty for kernel threads
+    pub fn cmdline(&self) -> &str {
+        self.cmdline.as_str()
+    }
+
+    /// Assigns a human-readable label to the process, displayed alongside its command
+    pub fn set_label<T>(&mut self, label: T)
+    where
+        T: Into<String>,
+    {
+        self.label = Some(label.into());
+    }
+
+    /// Removes the label assigned to the process, which is then displayed under its command
+    pub fn clear_label(&mut self) {
+        self.label = None;
+    }
+
+    /// Returns the label assigned to the process, if any
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Returns the name under which the process is displayed: its label if it has one, its command otherwise
+    pub fn display_name(&self) -> &str {
+        self.label().unwrap_or_else(|| self.command())
+    }
+
     /// Returns the status of the process, indicating if it is still running or not
     pub fn status(&self) -> Status {
         self.status
@@ -82,6 +262,28 @@ impl ProcessMetadata {
         &self.running_span
     }
 
+    /// Returns the time at which the process was spawned, as reported by the OS
+    pub fn spawn_time(&self) -> Timestamp {
+        self.running_span.begin()
+    }
+
+    /// Returns the time at which spv first saw the process
+    ///
+    /// The processes running before spv started were spawned before being seen, and have no metrics before that time.
+    pub fn first_seen(&self) -> Timestamp {
+        self.first_seen
+    }
+
+    /// Returns the cumulative CPU time consumed by the process, as of the latest collection which tracked it
+    pub fn cpu_time(&self) -> Duration {
+        self.cpu_time
+    }
+
+    /// Replaces the cumulative CPU time consumed by the process
+    pub fn set_cpu_time(&mut self, cpu_time: Duration) {
+        self.cpu_time = cpu_time;
+    }
+
     /// Updates the span of the process, indicating that it is still running at the current timestamp
     fn refresh_running_span(&mut self) {
         self.running_span.set_end_and_resize(Timestamp::now());
@@ -92,8 +294,10 @@ impl ProcessMetadata {
 mod test_process_metadata {
     use std::time::Duration;
 
-    use crate::core::process::{ProcessMetadata, Status};
-    use crate::core::time::test_utils::advance_time_and_refresh_timestamp;
+    use crate::core::process::{ProcessIdentifier, ProcessMetadata, Status};
+    use crate::core::time::test_utils::{
+        advance_time_and_refresh_timestamp, setup_fake_clock_to_prevent_substract_overflow,
+    };
     use crate::core::time::{Span, Timestamp};
 
     #[test]
@@ -101,6 +305,37 @@ mod test_process_metadata {
         assert_eq!(ProcessMetadata::new(123, "command", Timestamp::now()).pid(), 123);
     }
 
+    #[test]
+    fn test_tgid_should_be_pid_by_default() {
+        assert_eq!(ProcessMetadata::new(123, "command", Timestamp::now()).tgid(), 123);
+    }
+
+    #[test]
+    fn test_tgid_should_be_overridable() {
+        let pm = ProcessMetadata::new(123, "command", Timestamp::now()).with_tgid(100);
+
+        assert_eq!(pm.pid(), 123);
+        assert_eq!(pm.tgid(), 100);
+    }
+
+    #[test]
+    fn test_process_should_not_be_isolated_by_default() {
+        assert!(!ProcessMetadata::new(123, "command", Timestamp::now()).is_isolated());
+    }
+
+    #[test]
+    fn test_process_should_belong_to_no_unit_by_default() {
+        assert_eq!(ProcessMetadata::new(123, "command", Timestamp::now()).unit(), None);
+    }
+
+    #[test]
+    fn test_process_identifier_should_select_identifier_of_process() {
+        let pm = ProcessMetadata::new(123, "command", Timestamp::now()).with_tgid(100);
+
+        assert_eq!(ProcessIdentifier::Pid.of(&pm), 123);
+        assert_eq!(ProcessIdentifier::Tgid.of(&pm), 100);
+    }
+
     #[test]
     fn test_command_should_be_pm_command() {
         assert_eq!(
@@ -109,6 +344,44 @@ mod test_process_metadata {
         );
     }
 
+    #[test]
+    fn test_command_should_be_updatable() {
+        let mut pm = ProcessMetadata::new(123, "command", Timestamp::now());
+        pm.set_command("renamed");
+
+        assert_eq!(pm.command(), "renamed");
+    }
+
+    #[test]
+    fn test_display_name_should_be_command_without_label() {
+        let pm = ProcessMetadata::new(123, "java", Timestamp::now());
+
+        assert_eq!(pm.label(), None);
+        assert_eq!(pm.display_name(), "java");
+    }
+
+    #[test]
+    fn test_display_name_should_be_label_when_set() {
+        let mut pm = ProcessMetadata::new(123, "java", Timestamp::now());
+        pm.set_label("kafka");
+
+        assert_eq!(pm.display_name(), "kafka");
+        assert_eq!(pm.command(), "java");
+    }
+
+    #[test]
+    fn test_cpu_time_should_be_zero_by_default() {
+        assert_eq!(
+            ProcessMetadata::new(123, "command", Timestamp::now()).cpu_time(),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_process_should_not_be_stopped_by_default() {
+        assert!(!ProcessMetadata::new(123, "command", Timestamp::now()).is_stopped());
+    }
+
     #[test]
     fn test_status_should_be_running_by_default() {
         assert_eq!(
@@ -144,13 +417,50 @@ mod test_process_metadata {
 
         assert_eq!(pm.running_span(), &Span::new(spawn_time, Timestamp::now()));
     }
+
+    #[test]
+    fn test_process_spawned_before_being_seen_should_keep_both_times() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let spawn_time = Timestamp::now() - Duration::from_secs(30);
+
+        let pm = ProcessMetadata::new(456, "command", spawn_time);
+
+        assert_eq!(pm.spawn_time(), spawn_time);
+        assert_eq!(pm.first_seen(), Timestamp::now());
+    }
+
+    #[test]
+    fn test_first_seen_should_not_change_while_process_runs() {
+        let mut pm = ProcessMetadata::new(456, "command", Timestamp::now());
+        let first_seen = Timestamp::now();
+
+        advance_time_and_refresh_timestamp(Duration::from_secs(42));
+        pm.refresh_running_span();
+
+        assert_eq!(pm.first_seen(), first_seen);
+    }
 }
 
+/// The commands of known processes are refreshed once every `COMMAND_REFRESH_PERIOD` collections
+///
+/// Processes rarely rename themselves, so there is no need to re-read their command on every collection.
+const COMMAND_REFRESH_PERIOD: usize = 5;
+
+/// During how many collections a process is considered as recently spawned, once it has been discovered
+const RECENTLY_SPAWNED_COLLECTIONS: usize = 2;
+
 /// Collects the running processes
 pub struct ProcessCollector {
     scanner: Box<dyn ProcessScanner>,
     registered_processes: HashMap<Pid, ProcessMetadata>,
     latest_dead_processes: Vec<Pid>,
+    collection_count: usize,
+    // Associates the PIDs of recently spawned processes to the number of collections left before they are not
+    // considered as recently spawned anymore
+    recently_spawned: HashMap<Pid, usize>,
+    churn: ProcessChurn,
+    labels: ProcessLabels,
+    track_cpu_time: bool,
 }
 
 impl ProcessCollector {
@@ -159,9 +469,40 @@ impl ProcessCollector {
             scanner,
             registered_processes: HashMap::new(),
             latest_dead_processes: Vec::new(),
+            collection_count: 0,
+            recently_spawned: HashMap::new(),
+            churn: ProcessChurn::default(),
+            labels: ProcessLabels::default(),
+            track_cpu_time: false,
         }
     }
 
+    /// Reads the cumulative CPU time of running processes on each collection
+    ///
+    /// This is disabled by default, as it requires an additional read per process and per collection.
+    pub fn with_cpu_time_tracking(mut self) -> Self {
+        self.track_cpu_time = true;
+        self
+    }
+
+    /// Labels the discovered processes whose command line matches one of the given rules
+    pub fn with_labels(mut self, labels: ProcessLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Replaces the rules labelling processes, and labels the already discovered processes anew
+    pub fn set_labels(&mut self, labels: ProcessLabels) {
+        for pm in self.registered_processes.values_mut() {
+            match labels.label_of(pm.cmdline()) {
+                Some(label) => pm.set_label(label),
+                None => pm.clear_label(),
+            }
+        }
+
+        self.labels = labels;
+    }
+
     /// Returns the list of all processes, regardless of their status (running or not)
     pub fn processes(&self) -> Vec<ProcessMetadata> {
         self.registered_processes.values().cloned().collect()
@@ -190,19 +531,91 @@ impl ProcessCollector {
         take(&mut self.latest_dead_processes)
     }
 
+    /// Returns the PIDs of the processes which have been discovered during the last few collections
+    ///
+    /// The processes running when the first collection occurs are not considered as recently spawned.
+    pub fn recently_spawned(&self) -> HashSet<Pid> {
+        self.recently_spawned.keys().copied().collect()
+    }
+
+    /// Returns the rate at which processes spawned and died during the last few collections
+    pub fn churn_rate(&self) -> ChurnRate {
+        self.churn.rate()
+    }
+
     /// Scans and retrieves information about running processes
     pub fn collect_processes(&mut self) -> Result<(), Error> {
         let running_pids = self.scanner.scan()?;
 
-        for pm in self.parse_new_processes(&running_pids) {
+        self.collection_count += 1;
+        if self.collection_count.is_multiple_of(COMMAND_REFRESH_PERIOD) {
+            self.refresh_commands(&running_pids);
+        }
+
+        self.recently_spawned.retain(|_, collections_left| {
+            *collections_left -= 1;
+            *collections_left > 0
+        });
+
+        let mut spawned_count = 0;
+        for mut pm in self.parse_new_processes(&running_pids) {
+            if let Some(label) = self.labels.label_of(pm.cmdline()) {
+                pm.set_label(label);
+            }
+            if self.collection_count > 1 {
+                self.recently_spawned.insert(pm.pid(), RECENTLY_SPAWNED_COLLECTIONS);
+                spawned_count += 1;
+            }
             self.registered_processes.insert(pm.pid(), pm);
         }
 
-        self.update_processes_statuses(&running_pids);
+        let dead_count = self.update_processes_statuses(&running_pids);
+        self.churn.record_scan(Timestamp::now(), spawned_count, dead_count);
+
+        if self.track_cpu_time {
+            self.refresh_cpu_times(&running_pids);
+        }
+        self.refresh_stopped_states(&running_pids);
 
         Ok(())
     }
 
+    /// Re-reads whether the known processes which are still running are stopped by a signal
+    fn refresh_stopped_states(&mut self, running_pids: &[Pid]) {
+        for pid in running_pids {
+            if let Some(pm) = self.registered_processes.get_mut(pid) {
+                match self.scanner.fetch_stopped(*pid) {
+                    Ok(stopped) => pm.set_stopped(stopped),
+                    Err(e) => warn!("Error refreshing process state: {:?}", e),
+                }
+            }
+        }
+    }
+
+    /// Re-reads the cumulative CPU time of the known processes which are still running
+    fn refresh_cpu_times(&mut self, running_pids: &[Pid]) {
+        for pid in running_pids {
+            if let Some(pm) = self.registered_processes.get_mut(pid) {
+                match self.scanner.fetch_cpu_time(*pid) {
+                    Ok(cpu_time) => pm.set_cpu_time(cpu_time),
+                    Err(e) => warn!("Error refreshing process CPU time: {:?}", e),
+                }
+            }
+        }
+    }
+
+    /// Re-reads the command of the known processes which are still running
+    fn refresh_commands(&mut self, running_pids: &[Pid]) {
+        for pid in running_pids {
+            if let Some(pm) = self.registered_processes.get_mut(pid) {
+                match self.scanner.fetch_command(*pid) {
+                    Ok(command) => pm.set_command(command),
+                    Err(e) => warn!("Error refreshing process command: {:?}", e),
+                }
+            }
+        }
+    }
+
     fn parse_new_processes(&mut self, running_pids: &[Pid]) -> Vec<ProcessMetadata> {
         running_pids
             .iter()
@@ -218,7 +631,11 @@ impl ProcessCollector {
     }
 
     /// Mark new dead processes as dead, and update the running span of processes still running
-    fn update_processes_statuses(&mut self, running_pids: &[Pid]) {
+    ///
+    /// Returns the amount of processes which have been marked as dead.
+    fn update_processes_statuses(&mut self, running_pids: &[Pid]) -> usize {
+        let previously_dead_count = self.latest_dead_processes.len();
+
         self.registered_processes
             .values_mut()
             .filter(|pm| pm.status() == Status::RUNNING) // No need to mark dead processes
@@ -230,6 +647,8 @@ impl ProcessCollector {
                     pm.refresh_running_span();
                 }
             });
+
+        self.latest_dead_processes.len() - previously_dead_count
     }
 }
 
@@ -237,6 +656,10 @@ impl ProcessCollector {
 mod test_process_collector {
     use std::time::Duration;
 
+    use regex::Regex;
+
+    use crate::core::churn::ChurnRate;
+    use crate::core::labels::{LabelRule, ProcessLabels};
     use crate::core::process::{Pid, ProcessCollector, ProcessMetadata, ProcessScanner, Status};
     use crate::core::time::test_utils::advance_time_and_refresh_timestamp;
     use crate::core::time::{Span, Timestamp};
@@ -247,6 +670,7 @@ mod test_process_collector {
         scan_count: usize,
         scanned_pids: Vec<Vec<Pid>>,
         failing_processes: Vec<Pid>,
+        stopped_processes: Vec<Pid>,
     }
 
     impl ScannerStub {
@@ -259,6 +683,7 @@ mod test_process_collector {
                 scan_count: 0,
                 scanned_pids: vec![scanned_pids],
                 failing_processes,
+                stopped_processes: vec![],
             }
         }
 
@@ -274,12 +699,25 @@ mod test_process_collector {
         }
 
         fn fetch_metadata(&mut self, pid: Pid) -> Result<ProcessMetadata, Error> {
+            let command = self.fetch_command(pid)?;
+            Ok(ProcessMetadata::new(pid, command, Timestamp::now()).with_cmdline(format!("command --id {}", pid)))
+        }
+
+        fn fetch_command(&mut self, pid: Pid) -> Result<String, Error> {
             if self.failing_processes.contains(&pid) {
                 Err(InvalidPID(pid))
             } else {
-                Ok(ProcessMetadata::new(pid, "command", Timestamp::now()))
+                Ok("command".to_string())
             }
         }
+
+        fn fetch_cpu_time(&mut self, pid: Pid) -> Result<Duration, Error> {
+            Ok(Duration::from_secs((self.scan_count * pid as usize) as u64))
+        }
+
+        fn fetch_stopped(&mut self, pid: Pid) -> Result<bool, Error> {
+            Ok(self.stopped_processes.contains(&pid))
+        }
     }
 
     fn build_process_collector(scanned_pids: Vec<Pid>) -> ProcessCollector {
@@ -298,6 +736,7 @@ mod test_process_collector {
         let mut boxed_scanner = Box::new(ScannerStub::new(pids_sequence.pop().unwrap()));
         pids_sequence
             .into_iter()
+            .rev()
             .for_each(|pids| boxed_scanner.set_next_scanned_pids(pids));
 
         ProcessCollector::new(boxed_scanner)
@@ -409,7 +848,7 @@ mod test_process_collector {
         latest_dead_processes.sort();
         assert_eq!(latest_dead_processes, vec![2, 3]);
         let latest_dead_processes = collector.latest_dead_processes();
-        assert_eq!(latest_dead_processes, vec![]);
+        assert_eq!(latest_dead_processes, Vec::<Pid>::new());
     }
 
     #[test]
@@ -441,6 +880,246 @@ mod test_process_collector {
             &Span::new(now, now + Duration::from_secs(1))
         );
     }
+
+    #[test]
+    fn test_cpu_time_should_not_be_tracked_by_default() {
+        let collector = build_collector_with_sequence_and_collect(vec![vec![1], vec![1]]);
+
+        assert_eq!(collector.running_processes()[0].cpu_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_cpu_time_of_running_processes_should_be_refreshed_when_tracked() {
+        // The CPU time reported by the stub increases by 2 seconds on each scan of process 2
+        let mut collector = build_collector_with_sequence(vec![vec![2], vec![2]]).with_cpu_time_tracking();
+
+        collector.collect_processes().unwrap();
+        assert_eq!(collector.running_processes()[0].cpu_time(), Duration::from_secs(2));
+
+        collector.collect_processes().unwrap();
+        assert_eq!(collector.running_processes()[0].cpu_time(), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_stopped_state_of_running_processes_should_be_refreshed() {
+        let mut scanner = ScannerStub::new(vec![1, 2]);
+        scanner.stopped_processes = vec![2];
+        let mut collector = ProcessCollector::new(Box::new(scanner));
+
+        collector.collect_processes().unwrap();
+        let mut processes = collector.running_processes();
+        processes.sort_by_key(|pm| pm.pid());
+
+        assert!(!processes[0].is_stopped());
+        assert!(processes[1].is_stopped());
+    }
+
+    #[test]
+    fn test_churn_rate_should_count_spawned_and_dead_processes_per_second() {
+        let pids_sequence = vec![
+            vec![1, 2, 3],       // Processes running before the first collection are not spawned
+            vec![1, 4, 5, 6, 7], // 4 processes spawned, 2 processes died
+            vec![1, 8, 9],       // 2 processes spawned, 4 processes died
+        ];
+        let sequence_count = pids_sequence.len();
+        let mut collector = build_collector_with_sequence(pids_sequence);
+
+        for _ in 0..sequence_count {
+            advance_time_and_refresh_timestamp(Duration::from_secs(2));
+            collector.collect_processes().unwrap();
+        }
+
+        assert_eq!(collector.churn_rate(), ChurnRate::new(1.5, 1.5));
+    }
+
+    #[test]
+    fn test_processes_should_be_labelled_by_matching_rules() {
+        let labels = ProcessLabels::new(vec![LabelRule::new(Regex::new("--id 2$").unwrap(), "second")]);
+        let mut collector = build_process_collector(vec![1, 2]).with_labels(labels);
+        collector.collect_processes().unwrap();
+
+        let mut processes = collector.running_processes();
+        processes.sort_by_key(|pm| pm.pid());
+        let names: Vec<_> = processes.iter().map(|pm| pm.display_name()).collect();
+
+        assert_eq!(names, vec!["command", "second"]);
+    }
+
+    #[test]
+    fn test_should_label_discovered_processes_anew_when_rules_change() {
+        let labels = ProcessLabels::new(vec![LabelRule::new(Regex::new("--id 2$").unwrap(), "second")]);
+        let mut collector = build_process_collector(vec![1, 2]).with_labels(labels);
+        collector.collect_processes().unwrap();
+
+        collector.set_labels(ProcessLabels::new(vec![LabelRule::new(
+            Regex::new("--id 1$").unwrap(),
+            "first",
+        )]));
+
+        let mut processes = collector.running_processes();
+        processes.sort_by_key(|pm| pm.pid());
+        let names: Vec<_> = processes.iter().map(|pm| pm.display_name()).collect();
+
+        assert_eq!(names, vec!["first", "command"]);
+    }
+
+    #[test]
+    fn test_churn_rate_should_be_zero_when_processes_are_stable() {
+        let mut collector = build_collector_with_sequence(vec![vec![1, 2], vec![1, 2], vec![1, 2]]);
+
+        for _ in 0..3 {
+            advance_time_and_refresh_timestamp(Duration::from_secs(1));
+            collector.collect_processes().unwrap();
+        }
+
+        assert_eq!(collector.churn_rate(), ChurnRate::new(0., 0.));
+    }
+}
+
+#[cfg(test)]
+mod test_recently_spawned {
+    use std::time::Duration;
+
+    use std::collections::HashSet;
+
+    use crate::core::process::{Pid, ProcessCollector, ProcessMetadata, ProcessScanner, RECENTLY_SPAWNED_COLLECTIONS};
+    use crate::core::time::Timestamp;
+    use crate::core::Error;
+
+    /// Scans one more process on each scan: process 1 on the first scan, processes 1 and 2 on the second scan...
+    struct SpawningScannerStub {
+        scan_count: usize,
+    }
+
+    impl ProcessScanner for SpawningScannerStub {
+        fn scan(&mut self) -> Result<Vec<Pid>, Error> {
+            self.scan_count += 1;
+            Ok((1..=self.scan_count as Pid).collect())
+        }
+
+        fn fetch_metadata(&mut self, pid: Pid) -> Result<ProcessMetadata, Error> {
+            Ok(ProcessMetadata::new(pid, "command", Timestamp::now()))
+        }
+
+        fn fetch_command(&mut self, _pid: Pid) -> Result<String, Error> {
+            Ok("command".to_string())
+        }
+
+        fn fetch_cpu_time(&mut self, _pid: Pid) -> Result<Duration, Error> {
+            Ok(Duration::ZERO)
+        }
+
+        fn fetch_stopped(&mut self, _pid: Pid) -> Result<bool, Error> {
+            Ok(false)
+        }
+    }
+
+    fn collect_n_times(count: usize) -> ProcessCollector {
+        let mut collector = ProcessCollector::new(Box::new(SpawningScannerStub { scan_count: 0 }));
+        for _ in 0..count {
+            collector.collect_processes().unwrap();
+        }
+
+        collector
+    }
+
+    #[test]
+    fn test_processes_of_first_collection_should_not_be_recently_spawned() {
+        let collector = collect_n_times(1);
+
+        assert_eq!(collector.recently_spawned(), HashSet::new());
+    }
+
+    #[test]
+    fn test_process_discovered_by_last_collection_should_be_recently_spawned() {
+        let collector = collect_n_times(2);
+
+        assert_eq!(collector.recently_spawned(), HashSet::from([2]));
+    }
+
+    #[test]
+    fn test_process_should_stay_recently_spawned_during_configured_collections() {
+        let collector = collect_n_times(1 + RECENTLY_SPAWNED_COLLECTIONS);
+
+        let expected: HashSet<Pid> = (2..=1 + RECENTLY_SPAWNED_COLLECTIONS as Pid).collect();
+        assert_eq!(collector.recently_spawned(), expected);
+    }
+
+    #[test]
+    fn test_process_should_not_be_recently_spawned_after_configured_collections() {
+        let collector = collect_n_times(2 + RECENTLY_SPAWNED_COLLECTIONS);
+
+        assert!(!collector.recently_spawned().contains(&2));
+    }
+}
+
+#[cfg(test)]
+mod test_command_refresh {
+    use std::time::Duration;
+
+    use crate::core::process::{Pid, ProcessCollector, ProcessMetadata, ProcessScanner, COMMAND_REFRESH_PERIOD};
+    use crate::core::time::Timestamp;
+    use crate::core::Error;
+
+    /// Scans a single process, which renames itself after its first scan
+    struct RenamingScannerStub {
+        scan_count: usize,
+    }
+
+    impl ProcessScanner for RenamingScannerStub {
+        fn scan(&mut self) -> Result<Vec<Pid>, Error> {
+            self.scan_count += 1;
+            Ok(vec![1])
+        }
+
+        fn fetch_metadata(&mut self, pid: Pid) -> Result<ProcessMetadata, Error> {
+            let command = self.fetch_command(pid)?;
+            Ok(ProcessMetadata::new(pid, command, Timestamp::now()))
+        }
+
+        fn fetch_command(&mut self, _pid: Pid) -> Result<String, Error> {
+            match self.scan_count {
+                1 => Ok("original".to_string()),
+                _ => Ok("renamed".to_string()),
+            }
+        }
+
+        fn fetch_cpu_time(&mut self, _pid: Pid) -> Result<Duration, Error> {
+            Ok(Duration::ZERO)
+        }
+
+        fn fetch_stopped(&mut self, _pid: Pid) -> Result<bool, Error> {
+            Ok(false)
+        }
+    }
+
+    fn collect_n_times(collector: &mut ProcessCollector, count: usize) {
+        for _ in 0..count {
+            collector.collect_processes().unwrap();
+        }
+    }
+
+    fn command_of_process(collector: &ProcessCollector) -> String {
+        collector.running_processes()[0].command().to_string()
+    }
+
+    #[test]
+    fn test_should_not_refresh_command_before_refresh_period() {
+        let mut collector = ProcessCollector::new(Box::new(RenamingScannerStub { scan_count: 0 }));
+
+        collect_n_times(&mut collector, COMMAND_REFRESH_PERIOD - 1);
+
+        assert_eq!(command_of_process(&collector), "original");
+    }
+
+    #[test]
+    fn test_should_refresh_command_of_renamed_process() {
+        let mut collector = ProcessCollector::new(Box::new(RenamingScannerStub { scan_count: 0 }));
+
+        collect_n_times(&mut collector, COMMAND_REFRESH_PERIOD);
+
+        assert_eq!(command_of_process(&collector), "renamed");
+    }
 }
 
 /// Trait with methods to retrieve information about running processes
@@ -454,4 +1133,25 @@ pub trait ProcessScanner {
     ///
     /// * `pid`: The process identifier of the currently running process
     fn fetch_metadata(&mut self, pid: Pid) -> Result<ProcessMetadata, Error>;
+
+    /// Returns the current command of the running process with the given PID
+    ///
+    /// # Arguments
+    ///
+    /// * `pid`: The process identifier of the currently running process
+    fn fetch_command(&mut self, pid: Pid) -> Result<String, Error>;
+
+    /// Returns the cumulative CPU time consumed by the running process with the given PID
+    ///
+    /// # Arguments
+    ///
+    /// * `pid`: The process identifier of the currently running process
+    fn fetch_cpu_time(&mut self, pid: Pid) -> Result<Duration, Error>;
+
+    /// Indicates if the running process with the given PID is stopped by a signal
+    ///
+    /// # Arguments
+    ///
+    /// * `pid`: The process identifier of the currently running process
+    fn fetch_stopped(&mut self, pid: Pid) -> Result<bool, Error>;
 }
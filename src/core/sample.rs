@@ -0,0 +1,301 @@
+//! Snapshot of the metrics of a single process
+
+use std::collections::BTreeMap;
+
+use crate::core::metrics::Metric;
+use crate::core::process::{Pid, ProcessMetadata};
+
+/// The latest metrics of a process, as collected by each collector
+#[derive(Debug)]
+pub struct ProcessSample {
+    metadata: ProcessMetadata,
+    metrics: BTreeMap<String, Box<dyn Metric>>,
+}
+
+impl ProcessSample {
+    /// Builds a sample of the metrics of a process
+    ///
+    /// # Arguments
+    ///  * `metadata`: The metadata of the sampled process
+    ///  * `metrics`: The metric of the process collected by each collector, by the name of the collector
+    pub fn new(metadata: ProcessMetadata, metrics: BTreeMap<String, Box<dyn Metric>>) -> Self {
+        Self { metadata, metrics }
+    }
+
+    pub fn pid(&self) -> Pid {
+        self.metadata.pid()
+    }
+
+    pub fn metadata(&self) -> &ProcessMetadata {
+        &self.metadata
+    }
+
+    /// Returns the metric collected by the collector with the given name, if it collected a metric for the process
+    pub fn metric(&self, collector_name: &str) -> Option<&dyn Metric> {
+        self.metrics.get(collector_name).map(|metric| metric.as_ref())
+    }
+
+    /// Returns the greatest value of the metric collected by the collector with the given name
+    pub fn value(&self, collector_name: &str) -> Option<f64> {
+        self.metric(collector_name).map(|metric| metric.max_value())
+    }
+
+    /// Returns the value of each component of the metric collected by the collector with the given name
+    pub fn values(&self, collector_name: &str) -> Option<Vec<f64>> {
+        self.metric(collector_name).map(component_values)
+    }
+
+    /// Returns a concise representation of the metric collected by the collector with the given name
+    pub fn repr(&self, collector_name: &str) -> Option<String> {
+        self.metric(collector_name).map(|metric| metric.concise_repr())
+    }
+
+    /// Returns the unit of the metric collected by the collector with the given name
+    pub fn unit(&self, collector_name: &str) -> Option<&str> {
+        self.metric(collector_name).map(|metric| metric.unit())
+    }
+
+    /// Returns the names of the collectors which collected a metric for the process, in alphabetical order
+    pub fn collector_names(&self) -> Vec<&str> {
+        self.metrics.keys().map(String::as_str).collect()
+    }
+}
+
+fn component_values(metric: &dyn Metric) -> Vec<f64> {
+    (0..metric.cardinality())
+        .filter_map(|index| metric.as_f64(index).ok())
+        .collect()
+}
+
+#[cfg(feature = "serde")]
+mod serialization {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::core::metrics::Metric;
+    use crate::core::process::{Pid, ProcessMetadata};
+    use crate::core::sample::{component_values, ProcessSample};
+    use crate::core::time::Timestamp;
+    use crate::core::Error;
+
+    /// The serialized form of a sample
+    ///
+    /// The spawn time of the process is not serialized, as it is only meaningful to the running instance of spv.
+    #[derive(Serialize, Deserialize)]
+    struct SampleRecord {
+        pid: Pid,
+        tgid: Pid,
+        ppid: Pid,
+        command: String,
+        cmdline: String,
+        label: Option<String>,
+        metrics: BTreeMap<String, MetricRecord>,
+    }
+
+    /// The serialized form of a metric, which retains its values and their representations
+    #[derive(Serialize, Deserialize)]
+    struct MetricRecord {
+        unit: String,
+        values: Vec<f64>,
+        concise: String,
+        explicit: Vec<String>,
+    }
+
+    impl From<&dyn Metric> for MetricRecord {
+        fn from(metric: &dyn Metric) -> Self {
+            MetricRecord {
+                unit: metric.unit().to_string(),
+                values: component_values(metric),
+                concise: metric.concise_repr(),
+                explicit: (0..metric.cardinality())
+                    .filter_map(|index| metric.explicit_repr(index).ok())
+                    .collect(),
+            }
+        }
+    }
+
+    /// A metric restored from its serialized form
+    #[derive(Debug)]
+    struct RecordedMetric {
+        unit: String,
+        values: Vec<f64>,
+        concise: String,
+        explicit: Vec<String>,
+    }
+
+    impl From<MetricRecord> for RecordedMetric {
+        fn from(record: MetricRecord) -> Self {
+            RecordedMetric {
+                unit: record.unit,
+                values: record.values,
+                concise: record.concise,
+                explicit: record.explicit,
+            }
+        }
+    }
+
+    impl Metric for RecordedMetric {
+        fn cardinality(&self) -> usize {
+            self.values.len()
+        }
+
+        fn as_f64(&self, index: usize) -> Result<f64, Error> {
+            self.values
+                .get(index)
+                .copied()
+                .ok_or(Error::RawMetricAccessError(index, self.cardinality()))
+        }
+
+        fn max_value(&self) -> f64 {
+            self.values.iter().copied().fold(0., f64::max)
+        }
+
+        fn unit(&self) -> &str {
+            &self.unit
+        }
+
+        fn concise_repr(&self) -> String {
+            self.concise.clone()
+        }
+
+        fn concise_repr_of_value(&self, value: f64) -> String {
+            format!("{:.1}", value)
+        }
+
+        fn explicit_repr(&self, index: usize) -> Result<String, Error> {
+            self.explicit
+                .get(index)
+                .cloned()
+                .ok_or(Error::RawMetricAccessError(index, self.cardinality()))
+        }
+    }
+
+    impl Serialize for ProcessSample {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let metadata = self.metadata();
+
+            SampleRecord {
+                pid: metadata.pid(),
+                tgid: metadata.tgid(),
+                ppid: metadata.ppid(),
+                command: metadata.command().to_string(),
+                cmdline: metadata.cmdline().to_string(),
+                label: metadata.label().map(str::to_string),
+                metrics: self
+                    .metrics
+                    .iter()
+                    .map(|(name, metric)| (name.clone(), MetricRecord::from(metric.as_ref())))
+                    .collect(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ProcessSample {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let record = SampleRecord::deserialize(deserializer)?;
+
+            let mut metadata = ProcessMetadata::new(record.pid, record.command, Timestamp::now())
+                .with_tgid(record.tgid)
+                .with_ppid(record.ppid)
+                .with_cmdline(record.cmdline);
+            if let Some(label) = record.label {
+                metadata.set_label(label);
+            }
+
+            let metrics = record
+                .metrics
+                .into_iter()
+                .map(|(name, metric)| (name, Box::new(RecordedMetric::from(metric)) as Box<dyn Metric>))
+                .collect();
+
+            Ok(ProcessSample::new(metadata, metrics))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_process_sample {
+    use std::collections::BTreeMap;
+
+    use rstest::*;
+
+    use crate::core::metrics::{BytesMetric, IOMetric, Metric, PercentMetric};
+    use crate::core::process::ProcessMetadata;
+    use crate::core::sample::ProcessSample;
+    use crate::core::time::Timestamp;
+
+    #[fixture]
+    fn sample() -> ProcessSample {
+        let mut metrics: BTreeMap<String, Box<dyn Metric>> = BTreeMap::new();
+        metrics.insert("CPU usage".into(), Box::new(PercentMetric::new(12.5)));
+        metrics.insert("Disk I/O".into(), Box::new(IOMetric::new(1024, 4096)));
+        metrics.insert("Memory".into(), Box::new(BytesMetric::new(4096)));
+
+        let mut metadata = ProcessMetadata::new(42, "cargo", Timestamp::now())
+            .with_ppid(7)
+            .with_cmdline("cargo build");
+        metadata.set_label("build");
+
+        ProcessSample::new(metadata, metrics)
+    }
+
+    #[rstest]
+    fn test_should_return_metric_of_each_collector(sample: ProcessSample) {
+        assert_eq!(sample.pid(), 42);
+        assert_eq!(sample.metadata().display_name(), "build");
+        assert_eq!(sample.collector_names(), vec!["CPU usage", "Disk I/O", "Memory"]);
+        assert_eq!(
+            sample.metric("CPU usage"),
+            Some(&PercentMetric::new(12.5) as &dyn Metric)
+        );
+        assert!(sample.metric("Temperature").is_none());
+    }
+
+    #[rstest]
+    fn test_should_return_values_of_metric(sample: ProcessSample) {
+        assert_eq!(sample.value("Disk I/O"), Some(4096.));
+        assert_eq!(sample.values("Disk I/O"), Some(vec![1024., 4096.]));
+        assert_eq!(sample.unit("Disk I/O"), Some("B/s"));
+        assert_eq!(sample.repr("Memory"), Some(BytesMetric::new(4096).concise_repr()));
+        assert_eq!(sample.value("Temperature"), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    fn test_should_restore_serialized_sample(sample: ProcessSample) {
+        let json = serde_json::to_string(&sample).unwrap();
+
+        let restored: ProcessSample = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.pid(), 42);
+        assert_eq!(restored.metadata().ppid(), 7);
+        assert_eq!(restored.metadata().command(), "cargo");
+        assert_eq!(restored.metadata().cmdline(), "cargo build");
+        assert_eq!(restored.metadata().label(), Some("build"));
+        assert_eq!(restored.collector_names(), sample.collector_names());
+        for name in sample.collector_names() {
+            assert_eq!(restored.metric(name), sample.metric(name));
+            assert_eq!(restored.repr(name), sample.repr(name));
+        }
+        assert_eq!(
+            restored.metric("Disk I/O").unwrap().explicit_repr(1).unwrap(),
+            sample.metric("Disk I/O").unwrap().explicit_repr(1).unwrap()
+        );
+        for name in sample.collector_names() {
+            assert_eq!(restored.unit(name), sample.unit(name));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_should_restore_unit_of_metric_unknown_to_this_version() {
+        let json = r#"{"pid":1,"tgid":1,"ppid":0,"command":"nginx","cmdline":"nginx","label":null,
+            "metrics":{"Requests":{"unit":"req/s","values":[12.0],"concise":"12.0","explicit":["12.0req/s"]}}}"#;
+
+        let restored: ProcessSample = serde_json::from_str(json).unwrap();
+
+        assert_eq!(restored.unit("Requests"), Some("req/s"));
+    }
+}
@@ -0,0 +1,242 @@
+//! Restriction of the scanned processes to the processes of a service
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use log::warn;
+
+use crate::core::process::{Pid, ProcessMetadata, ProcessScanner};
+use crate::core::Error;
+
+/// Scans the processes of a service, leaving out the other running processes
+///
+/// The processes of the service are the processes whose command is the name of the service on the first scan, along
+/// with the processes which they, or their descendants, spawn afterwards.
+pub struct ServiceScanner {
+    scanner: Box<dyn ProcessScanner>,
+    name: String,
+    // Whether the processes of the service have been matched by their name yet
+    attached: bool,
+    tracked: HashSet<Pid>,
+    // The running processes which are known not to belong to the service, whose parent is not read again
+    ignored: HashSet<Pid>,
+}
+
+impl ServiceScanner {
+    /// Creates a scanner which only retains the processes of the given service
+    ///
+    /// # Arguments
+    ///  * `scanner`: Scans all running processes
+    ///  * `name`: The command of the processes of the service when the scanner is first used
+    pub fn new(scanner: Box<dyn ProcessScanner>, name: impl Into<String>) -> Self {
+        Self {
+            scanner,
+            name: name.into(),
+            attached: false,
+            tracked: HashSet::new(),
+            ignored: HashSet::new(),
+        }
+    }
+
+    /// Tracks the running processes whose command is the name of the service
+    fn attach(&mut self, running_pids: &[Pid]) {
+        for pid in running_pids {
+            match self.scanner.fetch_command(*pid) {
+                Ok(command) if command == self.name => self.tracked.insert(*pid),
+                Ok(_) => self.ignored.insert(*pid),
+                Err(e) => {
+                    warn!("Error fetching process command: {:?}", e);
+                    false
+                }
+            };
+        }
+
+        self.attached = true;
+    }
+
+    /// Tracks the newly spawned processes whose parent is tracked
+    fn follow_children(&mut self, running_pids: &[Pid]) {
+        let mut new_processes: Vec<(Pid, Pid)> = running_pids
+            .iter()
+            .filter(|pid| !self.tracked.contains(pid) && !self.ignored.contains(pid))
+            .filter_map(|pid| match self.scanner.fetch_metadata(*pid) {
+                Ok(pm) => Some((pm.pid(), pm.ppid())),
+                Err(e) => {
+                    warn!("Error fetching process metadata: {:?}", e);
+                    None
+                }
+            })
+            .collect();
+
+        // A child may have forked its own child since the previous scan, so that its parent is only tracked once the
+        // child itself is
+        loop {
+            let (children, others): (Vec<_>, Vec<_>) = new_processes
+                .into_iter()
+                .partition(|(_, ppid)| self.tracked.contains(ppid));
+
+            new_processes = others;
+            if children.is_empty() {
+                break;
+            }
+            self.tracked.extend(children.into_iter().map(|(pid, _)| pid));
+        }
+
+        self.ignored.extend(new_processes.into_iter().map(|(pid, _)| pid));
+    }
+}
+
+impl ProcessScanner for ServiceScanner {
+    fn scan(&mut self) -> Result<Vec<Pid>, Error> {
+        let running_pids = self.scanner.scan()?;
+
+        // Forget dead processes, as their PID may be reused by unrelated processes
+        self.tracked.retain(|pid| running_pids.contains(pid));
+        self.ignored.retain(|pid| running_pids.contains(pid));
+
+        if self.attached {
+            self.follow_children(&running_pids);
+        } else {
+            self.attach(&running_pids);
+        }
+
+        Ok(running_pids
+            .into_iter()
+            .filter(|pid| self.tracked.contains(pid))
+            .collect())
+    }
+
+    fn fetch_metadata(&mut self, pid: Pid) -> Result<ProcessMetadata, Error> {
+        self.scanner.fetch_metadata(pid)
+    }
+
+    fn fetch_command(&mut self, pid: Pid) -> Result<String, Error> {
+        self.scanner.fetch_command(pid)
+    }
+
+    fn fetch_cpu_time(&mut self, pid: Pid) -> Result<Duration, Error> {
+        self.scanner.fetch_cpu_time(pid)
+    }
+
+    fn fetch_stopped(&mut self, pid: Pid) -> Result<bool, Error> {
+        self.scanner.fetch_stopped(pid)
+    }
+}
+
+#[cfg(test)]
+mod test_service_scanner {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use crate::core::process::{Pid, ProcessMetadata, ProcessScanner};
+    use crate::core::service::ServiceScanner;
+    use crate::core::time::Timestamp;
+    use crate::core::Error;
+
+    /// Scans a sequence of process tables, each process being described by its PID, its parent and its command
+    struct ProcessTableStub {
+        tables: Vec<Vec<(Pid, Pid, &'static str)>>,
+        current: HashMap<Pid, (Pid, &'static str)>,
+    }
+
+    impl ProcessTableStub {
+        fn new(mut tables: Vec<Vec<(Pid, Pid, &'static str)>>) -> Self {
+            tables.reverse();
+            Self {
+                tables,
+                current: HashMap::new(),
+            }
+        }
+    }
+
+    impl ProcessScanner for ProcessTableStub {
+        fn scan(&mut self) -> Result<Vec<Pid>, Error> {
+            let table = self.tables.pop().unwrap();
+            let pids = table.iter().map(|(pid, _, _)| *pid).collect();
+            self.current = table
+                .into_iter()
+                .map(|(pid, ppid, command)| (pid, (ppid, command)))
+                .collect();
+
+            Ok(pids)
+        }
+
+        fn fetch_metadata(&mut self, pid: Pid) -> Result<ProcessMetadata, Error> {
+            let (ppid, command) = self.current.get(&pid).ok_or(Error::InvalidPID(pid))?;
+            Ok(ProcessMetadata::new(pid, *command, Timestamp::now()).with_ppid(*ppid))
+        }
+
+        fn fetch_command(&mut self, pid: Pid) -> Result<String, Error> {
+            self.fetch_metadata(pid).map(|pm| pm.command().to_string())
+        }
+
+        fn fetch_cpu_time(&mut self, _pid: Pid) -> Result<Duration, Error> {
+            Ok(Duration::ZERO)
+        }
+
+        fn fetch_stopped(&mut self, _pid: Pid) -> Result<bool, Error> {
+            Ok(false)
+        }
+    }
+
+    fn service_scanner(tables: Vec<Vec<(Pid, Pid, &'static str)>>) -> ServiceScanner {
+        ServiceScanner::new(Box::new(ProcessTableStub::new(tables)), "nginx")
+    }
+
+    #[test]
+    fn test_should_only_scan_processes_named_after_service_at_startup() {
+        let mut scanner = service_scanner(vec![vec![
+            (1, 0, "init"),
+            (10, 1, "nginx"),
+            (11, 10, "nginx"),
+            (20, 1, "bash"),
+            (21, 20, "vim"),
+        ]]);
+
+        assert_eq!(scanner.scan().unwrap(), vec![10, 11]);
+    }
+
+    #[test]
+    fn test_should_pick_up_children_forked_by_tracked_processes() {
+        let mut scanner = service_scanner(vec![
+            vec![(1, 0, "init"), (10, 1, "nginx")],
+            vec![
+                (1, 0, "init"),
+                (10, 1, "nginx"),
+                (12, 10, "worker"),
+                (13, 12, "sh"),
+                (30, 1, "bash"),
+            ],
+        ]);
+
+        scanner.scan().unwrap();
+
+        assert_eq!(scanner.scan().unwrap(), vec![10, 12, 13]);
+    }
+
+    #[test]
+    fn test_should_not_pick_up_processes_named_after_service_after_startup() {
+        let mut scanner = service_scanner(vec![
+            vec![(1, 0, "init"), (10, 1, "nginx")],
+            vec![(1, 0, "init"), (10, 1, "nginx"), (40, 1, "nginx")],
+        ]);
+
+        scanner.scan().unwrap();
+
+        assert_eq!(scanner.scan().unwrap(), vec![10]);
+    }
+
+    #[test]
+    fn test_should_not_track_process_reusing_pid_of_dead_tracked_process() {
+        let mut scanner = service_scanner(vec![
+            vec![(1, 0, "init"), (10, 1, "nginx"), (11, 10, "nginx")],
+            vec![(1, 0, "init"), (10, 1, "nginx")],
+            vec![(1, 0, "init"), (10, 1, "nginx"), (11, 1, "cron")],
+        ]);
+
+        scanner.scan().unwrap();
+        scanner.scan().unwrap();
+
+        assert_eq!(scanner.scan().unwrap(), vec![10]);
+    }
+}
@@ -0,0 +1,76 @@
+//! Signalling of the monitored processes
+
+use std::fmt::{Display, Formatter};
+
+use crate::core::process::ProcessMetadata;
+use crate::core::Error;
+
+/// The signals which can be sent to the monitored processes
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum ProcessSignal {
+    /// Pauses the process (SIGSTOP)
+    Stop,
+    /// Resumes a stopped process (SIGCONT)
+    Continue,
+}
+
+impl ProcessSignal {
+    /// Returns the signal which pauses the given process if it is running, or resumes it if it is stopped
+    pub fn toggling(process: &ProcessMetadata) -> Self {
+        if process.is_stopped() {
+            ProcessSignal::Continue
+        } else {
+            ProcessSignal::Stop
+        }
+    }
+}
+
+impl Display for ProcessSignal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessSignal::Stop => f.write_str("SIGSTOP"),
+            ProcessSignal::Continue => f.write_str("SIGCONT"),
+        }
+    }
+}
+
+/// Sends signals to processes
+pub trait SignalSender {
+    /// Sends a signal to the given process
+    ///
+    /// As the PID of a dead process may have been recycled since the process was discovered, implementors must not
+    /// send the signal if the PID now identifies another process.
+    ///
+    /// # Arguments
+    ///  * `process`: The process to signal
+    ///  * `signal`: The signal to send
+    fn send(&mut self, process: &ProcessMetadata, signal: ProcessSignal) -> Result<(), Error>;
+}
+
+#[cfg(test)]
+mod test_process_signal {
+    use crate::core::process::ProcessMetadata;
+    use crate::core::signal::ProcessSignal;
+    use crate::core::time::Timestamp;
+
+    #[test]
+    fn test_should_stop_running_process() {
+        let pm = ProcessMetadata::new(1, "command", Timestamp::now());
+
+        assert_eq!(ProcessSignal::toggling(&pm), ProcessSignal::Stop);
+    }
+
+    #[test]
+    fn test_should_resume_stopped_process() {
+        let mut pm = ProcessMetadata::new(1, "command", Timestamp::now());
+        pm.set_stopped(true);
+
+        assert_eq!(ProcessSignal::toggling(&pm), ProcessSignal::Continue);
+    }
+
+    #[test]
+    fn test_should_name_signals_after_their_posix_name() {
+        assert_eq!(ProcessSignal::Stop.to_string(), "SIGSTOP");
+        assert_eq!(ProcessSignal::Continue.to_string(), "SIGCONT");
+    }
+}
@@ -0,0 +1,217 @@
+//! Collection of several aspects of a same resource, displayed one at a time
+
+use std::cmp::Ordering;
+use std::time::Duration;
+
+use crate::core::collection::{MetricCollector, ProbeOutcome};
+use crate::core::metrics::Metric;
+use crate::core::process::Pid;
+use crate::core::time::{Span, Timestamp};
+use crate::core::view::{MetricView, MetricsOverview};
+use crate::core::Error;
+
+/// A collector gathering the metrics of several collectors, called sources, and reporting those of one of them
+///
+/// All the sources keep collecting metrics, so that no metric is missing when switching to another source.
+pub struct SourcesCollector {
+    name: &'static str,
+    sources: Vec<(&'static str, Box<dyn MetricCollector>)>,
+    current: usize,
+}
+
+impl SourcesCollector {
+    /// Builds a collector reporting the metrics of its first source
+    ///
+    /// Panics if no sources are given in parameter
+    ///
+    /// # Arguments
+    ///  * `name`: The name of the collector, whichever its current source
+    ///  * `sources`: The sources of metrics, along with the label describing them
+    pub fn new(name: &'static str, sources: Vec<(&'static str, Box<dyn MetricCollector>)>) -> Self {
+        if sources.is_empty() {
+            panic!("No sources have been defined for collector {}", name);
+        }
+
+        Self {
+            name,
+            sources,
+            current: 0,
+        }
+    }
+
+    fn current(&self) -> &dyn MetricCollector {
+        self.sources[self.current].1.as_ref()
+    }
+
+    fn sources_mut(&mut self) -> impl Iterator<Item = &mut Box<dyn MetricCollector>> {
+        self.sources.iter_mut().map(|(_, source)| source)
+    }
+}
+
+impl MetricCollector for SourcesCollector {
+    fn collect(&mut self, pids: &[Pid]) -> Result<(), Error> {
+        self.sources_mut().try_for_each(|source| source.collect(pids))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        self.sources_mut().for_each(|source| source.cleanup(pids));
+    }
+
+    fn purge(&mut self, oldest: Timestamp) {
+        self.sources_mut().for_each(|source| source.purge(oldest));
+    }
+
+    fn compact(&mut self, older_than: Timestamp, bucket: Duration) {
+        self.sources_mut().for_each(|source| source.compact(older_than, bucket));
+    }
+
+    fn clear(&mut self, pid: Pid) {
+        self.sources_mut().for_each(|source| source.clear(pid));
+    }
+
+    fn calibrate(&mut self, pids: &[Pid]) -> Result<(), Error> {
+        self.sources_mut().try_for_each(|source| source.calibrate(pids))
+    }
+
+    fn sample(&mut self, pids: &[Pid]) -> Result<(), Error> {
+        self.sources_mut().try_for_each(|source| source.sample(pids))
+    }
+
+    fn compare_pids_by_last_metrics(&self, pid1: Pid, pid2: Pid) -> Ordering {
+        self.current().compare_pids_by_last_metrics(pid1, pid2)
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn view(&self, pid: Pid, span: Span) -> MetricView<'_> {
+        self.current().view(pid, span)
+    }
+
+    fn overview(&self) -> MetricsOverview<'_> {
+        self.current().overview()
+    }
+
+    fn last_metric(&self, pid: Pid) -> Option<Box<dyn Metric>> {
+        self.current().last_metric(pid)
+    }
+
+    fn first_timestamp(&self, pid: Pid) -> Option<Timestamp> {
+        self.current().first_timestamp(pid)
+    }
+
+    fn latest_outcomes(&self) -> &[ProbeOutcome] {
+        self.current().latest_outcomes()
+    }
+
+    fn set_precision(&mut self, precision: usize) {
+        self.sources_mut().for_each(|source| source.set_precision(precision));
+    }
+
+    fn set_subsampled(&mut self) {
+        self.sources_mut().for_each(|source| source.set_subsampled());
+    }
+
+    fn cycle_source(&mut self) {
+        self.current = (self.current + 1) % self.sources.len();
+    }
+
+    fn source_label(&self) -> Option<&'static str> {
+        Some(self.sources[self.current].0)
+    }
+}
+
+#[cfg(test)]
+mod test_sources_collector {
+    use std::cmp::Ordering;
+
+    use rstest::*;
+
+    use crate::core::collection::{MetricCollector, ProbeCollector};
+    use crate::core::probe::fakes::FakeProbe;
+    use crate::core::sources::SourcesCollector;
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+
+    #[fixture]
+    fn collector() -> SourcesCollector {
+        setup_fake_clock_to_prevent_substract_overflow();
+
+        SourcesCollector::new(
+            "Disk",
+            vec![
+                (
+                    "bytes",
+                    Box::new(ProbeCollector::new(FakeProbe::from_percent_map(
+                        hashmap!(1 => 10., 2 => 20.),
+                    ))),
+                ),
+                (
+                    "operations",
+                    Box::new(ProbeCollector::new(FakeProbe::from_percent_map(
+                        hashmap!(1 => 30., 2 => 5.),
+                    ))),
+                ),
+            ],
+        )
+    }
+
+    fn last_metric(collector: &SourcesCollector, pid: u32) -> f64 {
+        collector.overview().last_or_default(pid).as_f64(0).unwrap()
+    }
+
+    #[rstest]
+    fn test_should_report_first_source_by_default(mut collector: SourcesCollector) {
+        collector.collect(&[1, 2]).unwrap();
+
+        assert_eq!(collector.source_label(), Some("bytes"));
+        assert_eq!(last_metric(&collector, 1), 10.);
+    }
+
+    #[rstest]
+    fn test_should_report_next_source_once_cycled(mut collector: SourcesCollector) {
+        // The metrics of the second source are collected while it is inactive
+        collector.collect(&[1, 2]).unwrap();
+
+        collector.cycle_source();
+
+        assert_eq!(collector.source_label(), Some("operations"));
+        assert_eq!(last_metric(&collector, 1), 30.);
+    }
+
+    #[rstest]
+    fn test_should_go_back_to_first_source_after_last_one(mut collector: SourcesCollector) {
+        collector.cycle_source();
+        collector.cycle_source();
+
+        assert_eq!(collector.source_label(), Some("bytes"));
+    }
+
+    #[rstest]
+    fn test_should_keep_its_name_whichever_the_source(mut collector: SourcesCollector) {
+        collector.cycle_source();
+
+        assert_eq!(collector.name(), "Disk");
+    }
+
+    #[rstest]
+    fn test_should_compare_processes_by_metrics_of_current_source(mut collector: SourcesCollector) {
+        collector.collect(&[1, 2]).unwrap();
+        assert_eq!(collector.compare_pids_by_last_metrics(1, 2), Ordering::Less);
+
+        collector.cycle_source();
+        assert_eq!(collector.compare_pids_by_last_metrics(1, 2), Ordering::Greater);
+    }
+
+    #[rstest]
+    fn test_should_clear_process_from_all_sources(mut collector: SourcesCollector) {
+        collector.collect(&[1, 2]).unwrap();
+
+        collector.clear(1);
+
+        assert!(collector.last_metric(1).is_none());
+        collector.cycle_source();
+        assert!(collector.last_metric(1).is_none());
+        assert_eq!(last_metric(&collector, 2), 5.);
+    }
+}
@@ -0,0 +1,166 @@
+//! Summary of the processes with the highest metrics, across collectors
+
+use crate::core::collection::MetricCollector;
+use crate::core::process::{ProcessMetadata, Status};
+use crate::core::view::MetricsOverview;
+
+/// The processes with the highest latest metrics of a collector
+pub struct CollectorLeaders<'a> {
+    name: &'a str,
+    // The leading processes, from the highest metric down, along with a concise representation of their metric
+    leaders: Vec<(&'a ProcessMetadata, String)>,
+}
+
+impl<'a> CollectorLeaders<'a> {
+    /// The name of the collector
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Returns the leading processes, from the highest metric down, along with the representation of their metric
+    pub fn leaders(&self) -> &[(&'a ProcessMetadata, String)] {
+        &self.leaders
+    }
+}
+
+/// Picks the leading processes of each of the given collectors
+///
+/// # Arguments
+///  * `collectors`: The collectors whose leaders are picked, in the order in which they are summarized
+///  * `processes`: The processes among which the leaders are picked
+///  * `count`: How many leaders are picked for each collector, at most
+pub fn summarize<'a>(
+    collectors: impl IntoIterator<Item = &'a dyn MetricCollector>,
+    processes: &'a [ProcessMetadata],
+    count: usize,
+) -> Vec<CollectorLeaders<'a>> {
+    collectors
+        .into_iter()
+        .map(|collector| {
+            let overview = collector.overview();
+            let leaders = top_processes(&overview, processes, count)
+                .into_iter()
+                .map(|pm| (pm, overview.concise_repr(pm.pid())))
+                .collect();
+
+            CollectorLeaders {
+                name: collector.name(),
+                leaders,
+            }
+        })
+        .collect()
+}
+
+/// Returns the `count` running processes with the highest latest metrics, from the highest metric down
+///
+/// Processes whose metrics are equal are ordered by their PID.
+///
+/// # Arguments
+///  * `overview`: The latest metrics of the processes
+///  * `processes`: The processes among which to pick the leaders
+///  * `count`: How many processes to return, at most
+pub fn top_processes<'a>(
+    overview: &MetricsOverview,
+    processes: &'a [ProcessMetadata],
+    count: usize,
+) -> Vec<&'a ProcessMetadata> {
+    let mut running: Vec<&ProcessMetadata> = processes.iter().filter(|pm| pm.status() == Status::RUNNING).collect();
+
+    running.sort_by(|pm1, pm2| {
+        let value1 = overview.last_or_default(pm1.pid()).max_value();
+        let value2 = overview.last_or_default(pm2.pid()).max_value();
+
+        value2.total_cmp(&value1).then(pm1.pid().cmp(&pm2.pid()))
+    });
+    running.truncate(count);
+
+    running
+}
+
+#[cfg(test)]
+mod test_summary {
+    use rstest::*;
+
+    use crate::core::collection::{MetricCollector, ProbeCollector};
+    use crate::core::metrics::PercentMetric;
+    use crate::core::probe::fakes::FakeProbe;
+    use crate::core::process::{Pid, ProcessMetadata};
+    use crate::core::summary::{summarize, top_processes};
+    use crate::core::time::Timestamp;
+
+    #[fixture]
+    fn processes() -> Vec<ProcessMetadata> {
+        (1..=5)
+            .map(|pid| ProcessMetadata::new(pid, "cmd", Timestamp::now()))
+            .collect()
+    }
+
+    fn collector(name: &'static str, values: [f64; 5]) -> ProbeCollector<PercentMetric> {
+        let metrics = (1..=5).zip(values).collect();
+        let mut collector = ProbeCollector::new(FakeProbe::from_percent_map(metrics).with_name(name));
+        collector.collect(&[1, 2, 3, 4, 5]).unwrap();
+
+        collector
+    }
+
+    fn pids(processes: &[&ProcessMetadata]) -> Vec<Pid> {
+        processes.iter().map(|pm| pm.pid()).collect()
+    }
+
+    #[rstest]
+    fn test_should_pick_processes_with_highest_metrics(processes: Vec<ProcessMetadata>) {
+        let collector = collector("cpu", [10., 50., 20., 80., 5.]);
+
+        let top = top_processes(&collector.overview(), &processes, 3);
+
+        assert_eq!(pids(&top), vec![4, 2, 3]);
+    }
+
+    #[rstest]
+    fn test_should_order_processes_with_equal_metrics_by_pid(processes: Vec<ProcessMetadata>) {
+        let collector = collector("cpu", [0., 30., 0., 30., 0.]);
+
+        let top = top_processes(&collector.overview(), &processes, 3);
+
+        assert_eq!(pids(&top), vec![2, 4, 1]);
+    }
+
+    #[rstest]
+    fn test_should_pick_all_processes_when_fewer_than_count(processes: Vec<ProcessMetadata>) {
+        let collector = collector("cpu", [10., 50., 20., 80., 5.]);
+
+        let top = top_processes(&collector.overview(), &processes[..2], 3);
+
+        assert_eq!(pids(&top), vec![2, 1]);
+    }
+
+    #[rstest]
+    fn test_should_not_pick_dead_processes(mut processes: Vec<ProcessMetadata>) {
+        let collector = collector("cpu", [10., 50., 20., 80., 5.]);
+        processes[3].mark_dead();
+
+        let top = top_processes(&collector.overview(), &processes, 3);
+
+        assert_eq!(pids(&top), vec![2, 3, 1]);
+    }
+
+    #[rstest]
+    fn test_should_pick_leaders_of_each_collector(processes: Vec<ProcessMetadata>) {
+        let cpu = collector("cpu", [10., 50., 20., 80., 5.]);
+        let memory = collector("memory", [90., 5., 40., 1., 60.]);
+        let collectors: [&dyn MetricCollector; 2] = [&cpu, &memory];
+
+        let summary = summarize(collectors, &processes, 2);
+
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].name(), "cpu");
+        assert_eq!(summary[1].name(), "memory");
+
+        let memory_leaders: Vec<_> = summary[1]
+            .leaders()
+            .iter()
+            .map(|(pm, metric)| (pm.pid(), metric.as_str()))
+            .collect();
+        assert_eq!(memory_leaders, vec![(1, "90.0"), (5, "60.0")]);
+    }
+}
@@ -241,7 +241,6 @@ impl Span {
     ///
     /// # Arguments
     /// * `begin`: The first timestamp covered by the span
-
     pub fn set_begin_and_resize(&mut self, begin: Timestamp) {
         if begin > self.end {
             panic!("Invalid begin for span {:?}: {:?}", self, begin);
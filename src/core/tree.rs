@@ -0,0 +1,161 @@
+//! Export of the parent/child relationships of processes
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use crate::core::process::{Pid, ProcessMetadata};
+
+/// Describes the tree of the given processes as a Graphviz DOT graph
+///
+/// Each process is a node labelled with its command and PID, linked to its parent by an edge. Processes whose parent
+/// is not among the given processes, such as orphans whose parent died since they were discovered, are drawn with a
+/// dashed border, as roots of the tree.
+pub fn process_tree_dot(processes: &[ProcessMetadata]) -> String {
+    let mut processes: Vec<_> = processes.iter().collect();
+    processes.sort_by_key(|pm| pm.pid());
+
+    let pids: HashSet<Pid> = processes.iter().map(|pm| pm.pid()).collect();
+
+    let mut dot = String::from("digraph processes {\n    node [shape=box];\n");
+
+    for pm in &processes {
+        let orphaned = pm.ppid() != 0 && !pids.contains(&pm.ppid());
+        let style = if orphaned { ", style=dashed" } else { "" };
+        let _ = writeln!(
+            dot,
+            "    {} [label=\"{}\\n{}\"{}];",
+            pm.pid(),
+            escape(pm.command()),
+            pm.pid(),
+            style
+        );
+    }
+
+    for pm in &processes {
+        if pids.contains(&pm.ppid()) {
+            let _ = writeln!(dot, "    {} -> {};", pm.ppid(), pm.pid());
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Counts the direct children of each process
+///
+/// Processes without children are counted as having zero children. Parents which are not among the given processes,
+/// such as the kernel (PID 0), are not counted.
+///
+/// # Arguments
+///  * `parents`: The PID of the parent of each process, by PID
+pub fn count_children(parents: &HashMap<Pid, Pid>) -> HashMap<Pid, usize> {
+    let mut counts: HashMap<Pid, usize> = parents.keys().map(|pid| (*pid, 0)).collect();
+
+    for ppid in parents.values() {
+        if let Some(count) = counts.get_mut(ppid) {
+            *count += 1;
+        }
+    }
+
+    counts
+}
+
+/// Escapes the characters which would end a quoted DOT string
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod test_process_tree_dot {
+    use crate::core::process::{Pid, ProcessMetadata};
+    use crate::core::time::Timestamp;
+    use crate::core::tree::process_tree_dot;
+
+    fn process(pid: Pid, ppid: Pid, command: &str) -> ProcessMetadata {
+        ProcessMetadata::new(pid, command, Timestamp::now()).with_ppid(ppid)
+    }
+
+    #[test]
+    fn test_should_describe_empty_graph_without_process() {
+        assert_eq!(process_tree_dot(&[]), "digraph processes {\n    node [shape=box];\n}\n");
+    }
+
+    #[test]
+    fn test_should_link_processes_to_their_parent() {
+        let processes = vec![
+            process(42, 10, "bash"),
+            process(1, 0, "systemd"),
+            process(10, 1, "sshd"),
+        ];
+
+        assert_eq!(
+            process_tree_dot(&processes),
+            "digraph processes {
+    node [shape=box];
+    1 [label=\"systemd\\n1\"];
+    10 [label=\"sshd\\n10\"];
+    42 [label=\"bash\\n42\"];
+    1 -> 10;
+    10 -> 42;
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_reparented_processes_should_be_linked_to_their_new_parent() {
+        // The parent of process 42 died, so that the process was adopted by init
+        let processes = vec![process(1, 0, "systemd"), process(42, 1, "daemon")];
+
+        assert!(process_tree_dot(&processes).contains("    1 -> 42;\n"));
+    }
+
+    #[test]
+    fn test_orphaned_processes_should_be_dashed_roots() {
+        // The parent of process 42 died after it was discovered
+        let processes = vec![process(1, 0, "systemd"), process(42, 30, "worker")];
+
+        let dot = process_tree_dot(&processes);
+
+        assert!(dot.contains("    42 [label=\"worker\\n42\", style=dashed];\n"));
+        assert!(!dot.contains("-> 42"));
+    }
+
+    #[test]
+    fn test_should_escape_quotes_of_commands() {
+        let dot = process_tree_dot(&[process(1, 0, "say \"hi\"")]);
+
+        assert!(dot.contains("    1 [label=\"say \\\"hi\\\"\\n1\"];\n"));
+    }
+}
+
+#[cfg(test)]
+mod test_count_children {
+    use crate::core::tree::count_children;
+
+    #[test]
+    fn test_should_count_no_children_without_process() {
+        assert!(count_children(&hashmap!()).is_empty());
+    }
+
+    #[test]
+    fn test_should_count_direct_children_of_each_process() {
+        // 1 ─┬─ 10 ─┬─ 100
+        //    │      ├─ 101
+        //    │      └─ 102
+        //    └─ 20 ─── 200
+        let parents = hashmap!(1 => 0, 10 => 1, 20 => 1, 100 => 10, 101 => 10, 102 => 10, 200 => 20);
+
+        assert_eq!(
+            count_children(&parents),
+            hashmap!(1 => 2, 10 => 3, 20 => 1, 100 => 0, 101 => 0, 102 => 0, 200 => 0)
+        );
+    }
+
+    #[test]
+    fn test_should_not_count_children_of_unknown_parents() {
+        let parents = hashmap!(42 => 30, 43 => 30);
+
+        assert_eq!(count_children(&parents), hashmap!(42 => 0, 43 => 0));
+    }
+}
@@ -0,0 +1,137 @@
+//! Aggregation of the metrics of processes by systemd unit
+
+use std::collections::HashMap;
+
+use crate::core::process::{ProcessMetadata, Status};
+use crate::core::view::MetricsOverview;
+
+/// The sum of the latest metrics of the running processes of a systemd unit
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitUsage {
+    name: String,
+    processes: usize,
+    total: f64,
+}
+
+impl UnitUsage {
+    /// The name of the unit, e.g. `nginx.service`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// How many running processes belong to the unit
+    pub fn processes(&self) -> usize {
+        self.processes
+    }
+
+    /// The sum of the latest metrics of the processes of the unit
+    pub fn total(&self) -> f64 {
+        self.total
+    }
+}
+
+/// Sums the latest metrics of the running processes of each systemd unit, from the highest sum down
+///
+/// Processes which do not belong to a unit are left out. Units whose sums are equal are ordered by their name.
+///
+/// # Arguments
+///  * `overview`: The latest metrics of the processes
+///  * `processes`: The processes whose metrics are summed
+pub fn sum_by_unit(overview: &MetricsOverview, processes: &[ProcessMetadata]) -> Vec<UnitUsage> {
+    let mut units: HashMap<&str, UnitUsage> = HashMap::new();
+
+    for pm in processes.iter().filter(|pm| pm.status() == Status::RUNNING) {
+        let Some(name) = pm.unit() else {
+            continue;
+        };
+
+        let usage = units.entry(name).or_insert_with(|| UnitUsage {
+            name: name.to_string(),
+            processes: 0,
+            total: 0.,
+        });
+        usage.processes += 1;
+        usage.total += overview.last_or_default(pm.pid()).max_value();
+    }
+
+    let mut units: Vec<UnitUsage> = units.into_values().collect();
+    units.sort_by(|u1, u2| u2.total.total_cmp(&u1.total).then_with(|| u1.name.cmp(&u2.name)));
+
+    units
+}
+
+#[cfg(test)]
+mod test_sum_by_unit {
+    use crate::core::metrics::{Metric, PercentMetric};
+    use crate::core::process::ProcessMetadata;
+    use crate::core::time::Timestamp;
+    use crate::core::units::sum_by_unit;
+    use crate::core::view::MetricsOverview;
+
+    fn process(pid: u32, unit: Option<&str>) -> ProcessMetadata {
+        let pm = ProcessMetadata::new(pid, "cmd", Timestamp::now());
+        match unit {
+            Some(unit) => pm.with_unit(unit),
+            None => pm,
+        }
+    }
+
+    #[test]
+    fn test_should_sum_metrics_of_processes_of_each_unit() {
+        let processes = vec![
+            process(1, Some("nginx.service")),
+            process(2, Some("nginx.service")),
+            process(3, Some("sshd.service")),
+            process(4, None),
+        ];
+        let metrics = [
+            PercentMetric::new(10.),
+            PercentMetric::new(20.),
+            PercentMetric::new(50.),
+            PercentMetric::new(90.),
+        ];
+        let default = PercentMetric::default();
+        let overview = MetricsOverview::new(
+            hashmap!(
+                1 => &metrics[0] as &dyn Metric,
+                2 => &metrics[1] as &dyn Metric,
+                3 => &metrics[2] as &dyn Metric,
+                4 => &metrics[3] as &dyn Metric
+            ),
+            &default,
+        );
+
+        let units = sum_by_unit(&overview, &processes);
+
+        let summed: Vec<_> = units.iter().map(|u| (u.name(), u.processes(), u.total())).collect();
+        assert_eq!(summed, vec![("sshd.service", 1, 50.), ("nginx.service", 2, 30.)]);
+    }
+
+    #[test]
+    fn test_should_leave_out_dead_processes() {
+        let mut dead = process(2, Some("nginx.service"));
+        dead.mark_dead();
+        let processes = vec![process(1, Some("nginx.service")), dead];
+        let default = PercentMetric::default();
+        let overview = MetricsOverview::new(hashmap!(), &default);
+
+        let units = sum_by_unit(&overview, &processes);
+
+        assert_eq!(units.len(), 1);
+        assert_eq!(units[0].processes(), 1);
+    }
+
+    #[test]
+    fn test_should_order_units_with_equal_sums_by_name() {
+        let processes = vec![process(1, Some("b.service")), process(2, Some("a.service"))];
+        let default = PercentMetric::default();
+        let overview = MetricsOverview::new(hashmap!(), &default);
+
+        let names: Vec<_> = sum_by_unit(&overview, &processes)
+            .iter()
+            .map(|u| u.name().to_string())
+            .collect();
+
+        assert_eq!(names, vec!["a.service", "b.service"]);
+    }
+}
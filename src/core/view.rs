@@ -1,11 +1,12 @@
 //! Immutable views of application data
 
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 use crate::core::metrics::{DatedMetric, Metric};
-use crate::core::process::{Pid, ProcessMetadata};
-use crate::core::time::Span;
+use crate::core::process::{Pid, ProcessIdentifier, ProcessMetadata};
+use crate::core::time::{Span, Timestamp};
 
 /// Snapshot of a slice of collected metrics of a single process, from a single probe
 ///
@@ -14,6 +15,7 @@ pub struct MetricView<'a> {
     dated_metrics: Vec<DatedMetric<'a>>,
     default: Box<dyn Metric>,
     span: Span,
+    precision: Option<usize>,
 }
 
 impl<'a> MetricView<'a> {
@@ -22,17 +24,24 @@ impl<'a> MetricView<'a> {
             dated_metrics,
             default,
             span,
+            precision: None,
         }
     }
 
+    /// Represents values with `precision` decimals, instead of the default precision of the metric
+    pub(crate) fn with_precision(mut self, precision: Option<usize>) -> Self {
+        self.precision = precision;
+        self
+    }
+
     /// Returns a slice of the metrics contained in this view.
     /// The slice only covers the last metrics covered by the `span` parameter.
-    pub fn as_slice(&'a self) -> &[DatedMetric<'a>] {
+    pub fn as_slice(&'a self) -> &'a [DatedMetric<'a>] {
         &self.dated_metrics
     }
 
     /// Returns the unit representation of the metrics contained in this view
-    pub fn unit(&self) -> &'static str {
+    pub fn unit(&self) -> &str {
         self.default.unit()
     }
 
@@ -54,6 +63,21 @@ impl<'a> MetricView<'a> {
         self.max_metric().max_value()
     }
 
+    /// Returns the lowest f64 component of the metrics of the view, or of the default metric if the view is empty
+    ///
+    /// Most metrics are never negative, unlike metrics representing a variation such as memory growth.
+    pub fn min_f64(&self) -> f64 {
+        let metrics: Vec<&dyn Metric> = match self.dated_metrics.is_empty() {
+            true => vec![&*self.default],
+            false => self.dated_metrics.iter().map(|dm| dm.metric).collect(),
+        };
+
+        metrics
+            .into_iter()
+            .flat_map(|m| (0..m.cardinality()).filter_map(move |i| m.as_f64(i).ok()))
+            .fold(f64::INFINITY, f64::min)
+    }
+
     /// Returns a concise representation of the greatest metric in the given span. See [`MetricView::new()`](#method.extract) for
     /// the behavior of `span`.
     ///
@@ -63,7 +87,29 @@ impl<'a> MetricView<'a> {
     /// # Arguments
     ///  * span: Indicates from how long ago the metrics should be compared
     pub fn concise_repr_of_value(&self, value: f64) -> String {
-        self.default.concise_repr_of_value(value)
+        match self.precision {
+            Some(precision) => self.default.concise_repr_of_value_with_precision(value, precision),
+            None => self.default.concise_repr_of_value(value),
+        }
+    }
+
+    /// Returns the average of the f64 values of the metrics collected during the span of the view, or the f64 value of
+    /// the default metric if no metric has been collected during the span
+    ///
+    /// If the metrics have a cardinality greater than one, the max f64 component of each metric is averaged. The
+    /// metrics adjacent to the span, which the view also holds, are left out.
+    pub fn average_f64(&self) -> f64 {
+        let values: Vec<f64> = self
+            .dated_metrics
+            .iter()
+            .filter(|dm| self.span.contains(dm.timestamp))
+            .map(|dm| dm.metric.max_value())
+            .collect();
+
+        match values.is_empty() {
+            true => self.default.max_value(),
+            false => values.iter().sum::<f64>() / values.len() as f64,
+        }
     }
 
     fn max_metric(&self) -> &dyn Metric {
@@ -82,6 +128,32 @@ impl<'a> MetricView<'a> {
     pub fn span(&self) -> &Span {
         &self.span
     }
+
+    /// Returns the f64 value of the metric collected the closest to the given timestamp.
+    ///
+    /// If the metrics have a cardinality greater than one, the max f64 component of the metric is returned.
+    /// Returns `None` if the timestamp is not covered by the span of the view, or if the view is empty.
+    ///
+    /// # Arguments
+    ///  * timestamp: The time at which the metric is read
+    pub fn value_at(&self, timestamp: Timestamp) -> Option<f64> {
+        if !self.span.contains(timestamp) {
+            return None;
+        }
+
+        self.dated_metrics
+            .iter()
+            .min_by_key(|dm| time_between(dm.timestamp, timestamp))
+            .map(|dm| dm.metric.max_value())
+    }
+}
+
+/// Returns the absolute duration between two timestamps, whichever comes first
+fn time_between(ts1: Timestamp, ts2: Timestamp) -> Duration {
+    match ts1.cmp(&ts2) {
+        Ordering::Less => ts2.duration_since(&ts1),
+        _ => ts1.duration_since(&ts2),
+    }
 }
 
 #[cfg(test)]
@@ -90,7 +162,7 @@ mod test_metric_view {
 
     use rstest::*;
 
-    use crate::core::metrics::{DatedMetric, IOMetric, Metric, PercentMetric};
+    use crate::core::metrics::{DatedMetric, IOMetric, Metric, PercentMetric, SignedBytesMetric};
     use crate::core::time::{Span, Timestamp};
     use crate::core::view::MetricView;
 
@@ -103,7 +175,7 @@ mod test_metric_view {
         ]
     }
 
-    fn percents_to_dated_metrics(metrics: &Vec<PercentMetric>) -> Vec<DatedMetric> {
+    fn percents_to_dated_metrics(metrics: &[PercentMetric]) -> Vec<DatedMetric<'_>> {
         let now = Timestamp::now();
         metrics
             .iter()
@@ -157,6 +229,76 @@ mod test_metric_view {
         assert_eq!(view.max_f64(), PercentMetric::default().as_f64(0).unwrap());
     }
 
+    #[rstest]
+    fn test_average_f64_should_average_values_of_span(
+        metrics: Vec<PercentMetric>,
+        default: Box<dyn Metric>,
+        span: Span,
+    ) {
+        let view = MetricView::new(percents_to_dated_metrics(&metrics), default, span);
+
+        assert_eq!(view.average_f64(), 15.);
+    }
+
+    #[rstest]
+    fn test_average_f64_should_average_highest_component_of_metrics(default: Box<dyn Metric>, span: Span) {
+        let metrics = [IOMetric::new(10, 30), IOMetric::new(20, 5)];
+        let now = Timestamp::now();
+        let dated_metrics = metrics.iter().map(|m| DatedMetric::new(m, now)).collect();
+        let view = MetricView::new(dated_metrics, default, span);
+
+        assert_eq!(view.average_f64(), 25.);
+    }
+
+    #[rstest]
+    fn test_average_f64_should_leave_out_metrics_adjacent_to_span(
+        metrics: Vec<PercentMetric>,
+        default: Box<dyn Metric>,
+    ) {
+        // The first metric precedes the span, which only covers the two latest metrics
+        let span = Span::new(
+            Timestamp::now() + Duration::from_secs(1),
+            Timestamp::now() + Duration::from_secs(10),
+        );
+        let view = MetricView::new(percents_to_dated_metrics(&metrics), default, span);
+
+        assert_eq!(view.average_f64(), 17.5);
+    }
+
+    #[rstest]
+    fn test_average_f64_should_return_default_f64_when_empty(default: Box<dyn Metric>, span: Span) {
+        let view = MetricView::new(vec![], default, span);
+
+        assert_eq!(view.average_f64(), PercentMetric::default().as_f64(0).unwrap());
+    }
+
+    #[rstest]
+    fn test_min_f64_should_return_lowest_component(default: Box<dyn Metric>, span: Span) {
+        let metrics = [IOMetric::new(10, 30), IOMetric::new(20, 5)];
+        let now = Timestamp::now();
+        let dated_metrics = metrics.iter().map(|m| DatedMetric::new(m, now)).collect();
+        let view = MetricView::new(dated_metrics, default, span);
+
+        assert_eq!(view.min_f64(), 5.);
+    }
+
+    #[rstest]
+    fn test_min_f64_should_return_negative_values(default: Box<dyn Metric>, span: Span) {
+        let metrics = [SignedBytesMetric::new(10), SignedBytesMetric::new(-20)];
+        let now = Timestamp::now();
+        let dated_metrics = metrics.iter().map(|m| DatedMetric::new(m, now)).collect();
+        let view = MetricView::new(dated_metrics, default, span);
+
+        assert_eq!(view.min_f64(), -20.);
+    }
+
+    #[rstest]
+    fn test_min_f64_should_return_default_f64_when_empty(default: Box<dyn Metric>, span: Span) {
+        let view = MetricView::new(vec![], default, span);
+
+        assert_eq!(view.min_f64(), PercentMetric::default().as_f64(0).unwrap());
+    }
+
     #[rstest]
     fn test_concise_repr_should_return_repr_of_default_metric(span: Span) {
         let default = Box::new(IOMetric::default()) as Box<dyn Metric>;
@@ -171,6 +313,92 @@ mod test_metric_view {
 
         assert_eq!(view.span(), &span);
     }
+
+    #[rstest]
+    #[case(0, 10.)]
+    #[case(1000, 20.)]
+    #[case(2000, 15.)]
+    fn test_value_at_should_return_value_collected_at_timestamp(
+        metrics: Vec<PercentMetric>,
+        default: Box<dyn Metric>,
+        span: Span,
+        #[case] offset_ms: u64,
+        #[case] expected: f64,
+    ) {
+        let view = MetricView::new(percents_to_dated_metrics(&metrics), default, span);
+
+        assert_eq!(
+            view.value_at(span.begin() + Duration::from_millis(offset_ms)),
+            Some(expected)
+        );
+    }
+
+    #[rstest]
+    #[case(400, 10.)]
+    #[case(600, 20.)]
+    #[case(1499, 20.)]
+    #[case(9000, 15.)]
+    fn test_value_at_should_return_value_collected_nearest_to_timestamp(
+        metrics: Vec<PercentMetric>,
+        default: Box<dyn Metric>,
+        span: Span,
+        #[case] offset_ms: u64,
+        #[case] expected: f64,
+    ) {
+        let view = MetricView::new(percents_to_dated_metrics(&metrics), default, span);
+
+        assert_eq!(
+            view.value_at(span.begin() + Duration::from_millis(offset_ms)),
+            Some(expected)
+        );
+    }
+
+    #[rstest]
+    fn test_value_at_should_return_none_outside_of_span(metrics: Vec<PercentMetric>, default: Box<dyn Metric>) {
+        let span = Span::new(
+            Timestamp::now() + Duration::from_secs(1),
+            Timestamp::now() + Duration::from_secs(2),
+        );
+        let view = MetricView::new(percents_to_dated_metrics(&metrics), default, span);
+
+        assert_eq!(view.value_at(Timestamp::now()), None);
+        assert_eq!(view.value_at(Timestamp::now() + Duration::from_secs(3)), None);
+    }
+
+    #[rstest]
+    fn test_value_at_should_return_none_when_view_is_empty(default: Box<dyn Metric>, span: Span) {
+        let view = MetricView::new(vec![], default, span);
+
+        assert_eq!(view.value_at(span.begin()), None);
+    }
+}
+
+/// Relative change below which a metric is considered to hold, so that small fluctuations do not flip its trend
+const TREND_DEAD_BAND: f64 = 0.05;
+
+/// Direction in which a metric went since the previous iteration
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Trend {
+    Rising,
+    Falling,
+    Steady,
+}
+
+impl Trend {
+    /// Compares the last two values of a metric
+    ///
+    /// Changes smaller than 5% of the greatest of both values are ignored, and make the metric steady.
+    pub fn between(previous: f64, last: f64) -> Self {
+        let dead_band = previous.abs().max(last.abs()) * TREND_DEAD_BAND;
+
+        if last - previous > dead_band {
+            Trend::Rising
+        } else if previous - last > dead_band {
+            Trend::Falling
+        } else {
+            Trend::Steady
+        }
+    }
 }
 
 /// Overview of a single probe's latest metric, for each running processes
@@ -178,12 +406,48 @@ mod test_metric_view {
 /// Refer to the [`MetricCollector`](crate::core::collection::MetricCollector) trait to instanciate a `MetricsOverview`
 pub struct MetricsOverview<'a> {
     last_metrics: HashMap<Pid, &'a dyn Metric>,
+    previous_metrics: HashMap<Pid, &'a dyn Metric>,
     default: &'a dyn Metric,
+    precision: Option<usize>,
 }
 
 impl<'a> MetricsOverview<'a> {
     pub fn new(last_metrics: HashMap<Pid, &'a dyn Metric>, default: &'a dyn Metric) -> Self {
-        Self { last_metrics, default }
+        Self {
+            last_metrics,
+            previous_metrics: HashMap::new(),
+            default,
+            precision: None,
+        }
+    }
+
+    /// Sets the metric collected before the latest one for each process, from which their trend is computed
+    pub(crate) fn with_previous_metrics(mut self, previous_metrics: HashMap<Pid, &'a dyn Metric>) -> Self {
+        self.previous_metrics = previous_metrics;
+        self
+    }
+
+    /// Represents metrics with `precision` decimals, instead of the default precision of the metric
+    pub(crate) fn with_precision(mut self, precision: Option<usize>) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Returns a concise representation of the latest collected `Metric` of a given process, or of the default
+    /// `Metric` if no metric has been collected for this process
+    ///
+    /// # Arguments
+    ///  * pid: The ID of the process
+    pub fn concise_repr(&self, pid: Pid) -> String {
+        self.last_or_default(pid).concise_repr_with_precision(self.precision)
+    }
+
+    /// Returns a concise representation of the given value, interpreted as the highest component of a metric
+    pub fn concise_repr_of_value(&self, value: f64) -> String {
+        match self.precision {
+            Some(precision) => self.default.concise_repr_of_value_with_precision(value, precision),
+            None => self.default.concise_repr_of_value(value),
+        }
     }
 
     /// Returns the latest collected `Metric` for a given process
@@ -195,8 +459,20 @@ impl<'a> MetricsOverview<'a> {
         *(self.last_metrics.get(&pid).unwrap_or(&self.default))
     }
 
+    /// Returns the direction in which the metric of a given process went since the previous iteration, or `None` if
+    /// fewer than two metrics have been collected for this process
+    ///
+    /// # Arguments
+    ///  * pid: The ID of the process
+    pub fn trend(&self, pid: Pid) -> Option<Trend> {
+        let previous = self.previous_metrics.get(&pid)?;
+        let last = self.last_metrics.get(&pid)?;
+
+        Some(Trend::between(previous.max_value(), last.max_value()))
+    }
+
     /// Returns the unit representation of the metrics contained in this view
-    pub fn unit(&self) -> &'static str {
+    pub fn unit(&self) -> &str {
         self.default.unit()
     }
 }
@@ -206,9 +482,9 @@ mod test_metric_overview {
     use crate::core::collection::MetricCollection;
     use crate::core::metrics::{Metric, PercentMetric};
     use crate::core::process::Pid;
-    use crate::core::view::MetricsOverview;
+    use crate::core::view::{MetricsOverview, Trend};
 
-    fn build_overview(collection: &MetricCollection<PercentMetric>) -> MetricsOverview {
+    fn build_overview(collection: &MetricCollection<PercentMetric>) -> MetricsOverview<'_> {
         collection.overview()
     }
 
@@ -250,19 +526,79 @@ mod test_metric_overview {
 
         assert_eq!(overview.last_or_default(2), &PercentMetric::default());
     }
+
+    #[test]
+    fn test_trend_should_compare_last_two_metrics() {
+        let collection = produce_metrics_collection(1, vec![10., 50., 20.]);
+        let overview = build_overview(&collection);
+
+        assert_eq!(overview.trend(0), Some(Trend::Falling));
+    }
+
+    #[test]
+    fn test_trend_should_be_none_with_single_metric() {
+        let collection = produce_metrics_collection(1, vec![10.]);
+        let overview = build_overview(&collection);
+
+        assert_eq!(overview.trend(0), None);
+    }
+
+    #[test]
+    fn test_trend_should_be_none_when_pid_is_unknown() {
+        let collection = produce_metrics_collection(1, vec![10., 20.]);
+        let overview = build_overview(&collection);
+
+        assert_eq!(overview.trend(1), None);
+    }
+}
+
+#[cfg(test)]
+mod test_trend {
+    use rstest::*;
+
+    use crate::core::view::Trend;
+
+    #[rstest]
+    #[case(10., 20., Trend::Rising)]
+    #[case(20., 10., Trend::Falling)]
+    #[case(10., 10., Trend::Steady)]
+    #[case(0., 0., Trend::Steady)]
+    #[case(0., 1., Trend::Rising)]
+    #[case(-10., -20., Trend::Falling)]
+    fn test_should_compare_last_two_values(#[case] previous: f64, #[case] last: f64, #[case] expected: Trend) {
+        assert_eq!(Trend::between(previous, last), expected);
+    }
+
+    #[rstest]
+    #[case(100., 104.)]
+    #[case(100., 96.)]
+    #[case(100., 105.)]
+    fn test_should_hold_steady_within_dead_band(#[case] previous: f64, #[case] last: f64) {
+        assert_eq!(Trend::between(previous, last), Trend::Steady);
+    }
 }
 
 /// Contains the processes to display to the user, as well as the process that is currently selected
 pub struct ProcessesView<'a> {
     sorted_processes: &'a [ProcessMetadata],
     selected_index: Option<usize>,
+    identifier: ProcessIdentifier,
+    recently_spawned: Option<&'a HashSet<Pid>>,
+    watched: Option<&'a HashSet<Pid>>,
+    // The PID of spv, when its own process is highlighted
+    own_pid: Option<Pid>,
 }
 
 impl<'a> ProcessesView<'a> {
-    /// Creates a view containing the processes to list on the UI, and the selected process if any
+    /// Creates a view containing the processes to list on the UI, the selected process if any, and the identifier
+    /// under which processes are displayed
     ///
     /// Panics if `selected_index` is out of bound of `sorted_processes`
-    pub fn new(sorted_processes: &'a [ProcessMetadata], selected_index: Option<usize>) -> ProcessesView<'a> {
+    pub fn new(
+        sorted_processes: &'a [ProcessMetadata],
+        selected_index: Option<usize>,
+        identifier: ProcessIdentifier,
+    ) -> ProcessesView<'a> {
         if let Some(selected_index) = selected_index {
             if selected_index >= sorted_processes.len() {
                 panic!("Selected process index if out of bound {:?}", selected_index);
@@ -272,6 +608,53 @@ impl<'a> ProcessesView<'a> {
         Self {
             sorted_processes,
             selected_index,
+            identifier,
+            recently_spawned: None,
+            watched: None,
+            own_pid: None,
+        }
+    }
+
+    /// Sets the PIDs of the processes which have recently been spawned, to distinguish them from other processes
+    pub fn with_recently_spawned(mut self, recently_spawned: &'a HashSet<Pid>) -> Self {
+        self.recently_spawned = Some(recently_spawned);
+        self
+    }
+
+    /// Sets the PIDs of the processes whose metrics satisfy the watch expression, to highlight them
+    pub fn with_watched(mut self, watched: &'a HashSet<Pid>) -> Self {
+        self.watched = Some(watched);
+        self
+    }
+
+    /// Sets the PID of spv, to highlight its own process
+    pub fn with_own_pid(mut self, own_pid: Pid) -> Self {
+        self.own_pid = Some(own_pid);
+        self
+    }
+
+    /// Returns the same view, listing other copies of the same processes, e.g. under different names
+    ///
+    /// Panics if `sorted_processes` does not contain as many processes as the view
+    pub fn with_processes<'b>(&self, sorted_processes: &'b [ProcessMetadata]) -> ProcessesView<'b>
+    where
+        'a: 'b,
+    {
+        if sorted_processes.len() != self.sorted_processes.len() {
+            panic!(
+                "The view lists {} processes, but {} were given",
+                self.sorted_processes.len(),
+                sorted_processes.len()
+            );
+        }
+
+        ProcessesView {
+            sorted_processes,
+            selected_index: self.selected_index,
+            identifier: self.identifier,
+            recently_spawned: self.recently_spawned,
+            watched: self.watched,
+            own_pid: self.own_pid,
         }
     }
 
@@ -286,13 +669,34 @@ impl<'a> ProcessesView<'a> {
     pub fn selected_process(&self) -> Option<&ProcessMetadata> {
         self.selected_index.map(|idx| &self.sorted_processes[idx])
     }
+
+    pub fn identifier(&self) -> ProcessIdentifier {
+        self.identifier
+    }
+
+    /// Indicates if the process with the given PID has recently been spawned
+    pub fn is_recently_spawned(&self, pid: Pid) -> bool {
+        self.recently_spawned.is_some_and(|pids| pids.contains(&pid))
+    }
+
+    /// Indicates if the metrics of the process with the given PID satisfy the watch expression
+    pub fn is_watched(&self, pid: Pid) -> bool {
+        self.watched.is_some_and(|pids| pids.contains(&pid))
+    }
+
+    /// Indicates if the process with the given PID is the process of spv, when it is highlighted
+    pub fn is_own_process(&self, pid: Pid) -> bool {
+        self.own_pid == Some(pid)
+    }
 }
 
 #[cfg(test)]
 mod test_process_view {
+    use std::collections::HashSet;
+
     use rstest::*;
 
-    use crate::core::process::ProcessMetadata;
+    use crate::core::process::{ProcessIdentifier, ProcessMetadata};
     use crate::core::time::Timestamp;
     use crate::core::view::ProcessesView;
 
@@ -306,14 +710,14 @@ mod test_process_view {
 
     #[rstest]
     fn test_should_contain_all_processes_in_slice(processes: Vec<ProcessMetadata>) {
-        let view = ProcessesView::new(&processes, None);
+        let view = ProcessesView::new(&processes, None, ProcessIdentifier::Pid);
 
         assert_eq!(view.as_slice(), &processes);
     }
 
     #[rstest]
     fn test_should_have_no_selected_process_when_given_index_is_none(processes: Vec<ProcessMetadata>) {
-        let view = ProcessesView::new(&processes, None);
+        let view = ProcessesView::new(&processes, None, ProcessIdentifier::Pid);
 
         assert_eq!(view.selected_index(), None);
         assert_eq!(view.selected_process(), None);
@@ -321,7 +725,7 @@ mod test_process_view {
 
     #[rstest]
     fn test_should_return_correct_selected_process(processes: Vec<ProcessMetadata>) {
-        let view = ProcessesView::new(&processes, Some(1));
+        let view = ProcessesView::new(&processes, Some(1), ProcessIdentifier::Pid);
 
         assert_eq!(view.selected_index(), Some(1));
         assert_eq!(view.selected_process(), Some(&processes[1]));
@@ -330,18 +734,77 @@ mod test_process_view {
     #[rstest]
     #[should_panic]
     fn test_should_panic_when_index_out_of_bound(processes: Vec<ProcessMetadata>) {
-        ProcessesView::new(&processes, Some(2));
+        ProcessesView::new(&processes, Some(2), ProcessIdentifier::Pid);
+    }
+
+    #[rstest]
+    fn test_should_only_flag_given_processes_as_recently_spawned(processes: Vec<ProcessMetadata>) {
+        let recently_spawned = HashSet::from([2]);
+        let view =
+            ProcessesView::new(&processes, None, ProcessIdentifier::Pid).with_recently_spawned(&recently_spawned);
+
+        assert!(!view.is_recently_spawned(1));
+        assert!(view.is_recently_spawned(2));
+    }
+
+    #[rstest]
+    fn test_should_flag_no_process_as_recently_spawned_by_default(processes: Vec<ProcessMetadata>) {
+        let view = ProcessesView::new(&processes, None, ProcessIdentifier::Pid);
+
+        assert!(!view.is_recently_spawned(1));
+    }
+
+    #[rstest]
+    fn test_should_only_flag_given_processes_as_watched(processes: Vec<ProcessMetadata>) {
+        let watched = HashSet::from([1]);
+        let view = ProcessesView::new(&processes, None, ProcessIdentifier::Pid).with_watched(&watched);
+
+        assert!(view.is_watched(1));
+        assert!(!view.is_watched(2));
+    }
+
+    #[rstest]
+    fn test_should_only_flag_own_process(processes: Vec<ProcessMetadata>) {
+        let view = ProcessesView::new(&processes, None, ProcessIdentifier::Pid).with_own_pid(2);
+
+        assert!(!view.is_own_process(1));
+        assert!(view.is_own_process(2));
+    }
+
+    #[rstest]
+    fn test_should_keep_selection_and_flags_with_other_processes(processes: Vec<ProcessMetadata>) {
+        let watched = HashSet::from([2]);
+        let view = ProcessesView::new(&processes, Some(1), ProcessIdentifier::Tgid).with_watched(&watched);
+        let renamed = vec![
+            ProcessMetadata::new(1, "renamed_1", Timestamp::now()),
+            ProcessMetadata::new(2, "renamed_2", Timestamp::now()),
+        ];
+
+        let renamed_view = view.with_processes(&renamed);
+
+        assert_eq!(renamed_view.selected_process(), Some(&renamed[1]));
+        assert_eq!(renamed_view.identifier(), ProcessIdentifier::Tgid);
+        assert!(renamed_view.is_watched(2));
+    }
+
+    #[rstest]
+    #[should_panic]
+    fn test_should_panic_when_other_processes_differ_in_count(processes: Vec<ProcessMetadata>) {
+        let view = ProcessesView::new(&processes, None, ProcessIdentifier::Pid);
+
+        view.with_processes(&processes[..1]);
     }
 }
 
 /// Contains information about the available metrics collectors, and the collector currently selected by the user
-pub struct CollectorsView {
-    collectors_names: Vec<&'static str>,
+pub struct CollectorsView<'a> {
+    collectors_names: Vec<&'a str>,
     selected_index: usize,
+    selected_source: Option<&'static str>,
 }
 
-impl CollectorsView {
-    pub fn new(collectors_names: Vec<&'static str>, selected_index: usize) -> Self {
+impl<'a> CollectorsView<'a> {
+    pub fn new(collectors_names: Vec<&'a str>, selected_index: usize) -> Self {
         if selected_index >= collectors_names.len() {
             panic!("The collectors selected index {} is out of bound", selected_index)
         }
@@ -349,16 +812,28 @@ impl CollectorsView {
         Self {
             collectors_names,
             selected_index,
+            selected_source: None,
         }
     }
 
+    /// Sets the label of the source of metrics of the selected collector, if it has several sources
+    pub fn with_selected_source(mut self, selected_source: Option<&'static str>) -> Self {
+        self.selected_source = selected_source;
+        self
+    }
+
     pub fn selected_index(&self) -> usize {
         self.selected_index
     }
 
-    pub fn collectors_names(&self) -> &[&'static str] {
+    pub fn collectors_names(&self) -> &[&'a str] {
         &self.collectors_names
     }
+
+    /// Returns the label of the source of metrics of the selected collector, if it has several sources
+    pub fn selected_source(&self) -> Option<&'static str> {
+        self.selected_source
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,412 @@
+//! Conditions on the latest metrics of processes, such as `cpu > 50 OR memory > 1G`
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+use crate::core::process::Pid;
+use crate::core::view::MetricsOverview;
+
+/// How the latest metric of a process is compared to a threshold
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Lower,
+    LowerOrEqual,
+}
+
+impl Comparison {
+    fn holds(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Greater => value > threshold,
+            Comparison::GreaterOrEqual => value >= threshold,
+            Comparison::Lower => value < threshold,
+            Comparison::LowerOrEqual => value <= threshold,
+        }
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let repr = match self {
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Lower => "<",
+            Comparison::LowerOrEqual => "<=",
+        };
+        write!(f, "{}", repr)
+    }
+}
+
+/// A condition on the metrics of processes, which processes are watched for
+///
+/// Metrics are referred to by a name, such as the name of the probe collecting them. The highest component of a
+/// metric is compared to the threshold, as when sorting processes.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WatchExpression {
+    Condition(String, Comparison, f64),
+    And(Box<WatchExpression>, Box<WatchExpression>),
+    Or(Box<WatchExpression>, Box<WatchExpression>),
+}
+
+impl WatchExpression {
+    /// Returns the names of the metrics which the expression refers to
+    pub fn metrics(&self) -> Vec<&str> {
+        match self {
+            WatchExpression::Condition(metric, _, _) => vec![metric.as_str()],
+            WatchExpression::And(lhs, rhs) | WatchExpression::Or(lhs, rhs) => {
+                let mut metrics = lhs.metrics();
+                metrics.extend(rhs.metrics());
+                metrics
+            }
+        }
+    }
+
+    /// Indicates if the latest metrics of a process satisfy the expression
+    ///
+    /// A condition on a metric which is missing from `overviews` is never satisfied.
+    ///
+    /// # Arguments
+    ///  * `pid`: The ID of the process whose metrics are evaluated
+    ///  * `overviews`: The latest metrics of all processes, by the name under which the expression refers to them
+    pub fn matches(&self, pid: Pid, overviews: &HashMap<&str, MetricsOverview>) -> bool {
+        match self {
+            WatchExpression::Condition(metric, comparison, threshold) => overviews
+                .get(metric.as_str())
+                .is_some_and(|overview| comparison.holds(overview.last_or_default(pid).max_value(), *threshold)),
+            WatchExpression::And(lhs, rhs) => lhs.matches(pid, overviews) && rhs.matches(pid, overviews),
+            WatchExpression::Or(lhs, rhs) => lhs.matches(pid, overviews) || rhs.matches(pid, overviews),
+        }
+    }
+}
+
+impl FromStr for WatchExpression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?.into_iter().peekable(),
+        };
+
+        let expression = parser.parse_or()?;
+        match parser.tokens.next() {
+            None => Ok(expression),
+            Some(token) => Err(format!("unexpected {}", token)),
+        }
+    }
+}
+
+impl Display for WatchExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WatchExpression::Condition(metric, comparison, threshold) => {
+                write!(f, "{} {} {}", metric, comparison, threshold)
+            }
+            WatchExpression::And(lhs, rhs) => {
+                for (idx, operand) in [lhs, rhs].into_iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, " AND ")?;
+                    }
+                    match operand.as_ref() {
+                        WatchExpression::Or(_, _) => write!(f, "({})", operand)?,
+                        _ => write!(f, "{}", operand)?,
+                    }
+                }
+                Ok(())
+            }
+            WatchExpression::Or(lhs, rhs) => write!(f, "{} OR {}", lhs, rhs),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Metric(String),
+    Number(f64),
+    Comparison(Comparison),
+    And,
+    Or,
+    OpeningParenthesis,
+    ClosingParenthesis,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Metric(metric) => write!(f, "metric '{}'", metric),
+            Token::Number(number) => write!(f, "number '{}'", number),
+            Token::Comparison(comparison) => write!(f, "'{}'", comparison),
+            Token::And => write!(f, "'AND'"),
+            Token::Or => write!(f, "'OR'"),
+            Token::OpeningParenthesis => write!(f, "'('"),
+            Token::ClosingParenthesis => write!(f, "')'"),
+        }
+    }
+}
+
+/// Splits an expression into tokens
+fn tokenize(repr: &str) -> Result<Vec<Token>, String> {
+    let mut chars = repr.chars().peekable();
+    let mut tokens = vec![];
+
+    while let Some(&c) = chars.peek() {
+        let token = match c {
+            _ if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '(' => {
+                chars.next();
+                Token::OpeningParenthesis
+            }
+            ')' => {
+                chars.next();
+                Token::ClosingParenthesis
+            }
+            '>' | '<' => {
+                chars.next();
+                let or_equal = chars.next_if_eq(&'=').is_some();
+                Token::Comparison(match (c, or_equal) {
+                    ('>', false) => Comparison::Greater,
+                    ('>', true) => Comparison::GreaterOrEqual,
+                    (_, false) => Comparison::Lower,
+                    (_, true) => Comparison::LowerOrEqual,
+                })
+            }
+            _ if c.is_ascii_digit() || c == '.' => Token::Number(parse_number(&mut chars)?),
+            _ if c.is_alphabetic() => {
+                let word = take_while(&mut chars, |c| c.is_alphanumeric() || c == '-' || c == '_');
+                match word.to_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    _ => Token::Metric(word),
+                }
+            }
+            _ => return Err(format!("unexpected character '{}'", c)),
+        };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+fn take_while(chars: &mut Peekable<Chars>, predicate: impl Fn(char) -> bool) -> String {
+    let mut taken = String::new();
+    while let Some(c) = chars.next_if(|c| predicate(*c)) {
+        taken.push(c);
+    }
+    taken
+}
+
+/// Parses a number, optionally followed by a binary multiple suffix (`K`, `M`, `G` or `T`) or by `%`
+fn parse_number(chars: &mut Peekable<Chars>) -> Result<f64, String> {
+    let digits = take_while(chars, |c| c.is_ascii_digit() || c == '.');
+    let value: f64 = digits.parse().map_err(|_| format!("invalid number '{}'", digits))?;
+
+    let suffix = take_while(chars, |c| c.is_alphabetic() || c == '%');
+    let multiplier = match suffix.to_uppercase().as_str() {
+        "" | "%" => 1.,
+        "K" => 1024.,
+        "M" => 1024. * 1024.,
+        "G" => 1024. * 1024. * 1024.,
+        "T" => 1024. * 1024. * 1024. * 1024.,
+        _ => return Err(format!("unknown suffix '{}' of number {}", suffix, digits)),
+    };
+
+    Ok(value * multiplier)
+}
+
+/// Parses tokens by recursive descent, with `AND` taking precedence over `OR`
+struct Parser {
+    tokens: Peekable<std::vec::IntoIter<Token>>,
+}
+
+impl Parser {
+    fn parse_or(&mut self) -> Result<WatchExpression, String> {
+        let mut expression = self.parse_and()?;
+        while self.tokens.next_if_eq(&Token::Or).is_some() {
+            expression = WatchExpression::Or(Box::new(expression), Box::new(self.parse_and()?));
+        }
+        Ok(expression)
+    }
+
+    fn parse_and(&mut self) -> Result<WatchExpression, String> {
+        let mut expression = self.parse_operand()?;
+        while self.tokens.next_if_eq(&Token::And).is_some() {
+            expression = WatchExpression::And(Box::new(expression), Box::new(self.parse_operand()?));
+        }
+        Ok(expression)
+    }
+
+    fn parse_operand(&mut self) -> Result<WatchExpression, String> {
+        match self.tokens.next() {
+            Some(Token::OpeningParenthesis) => {
+                let expression = self.parse_or()?;
+                match self.tokens.next() {
+                    Some(Token::ClosingParenthesis) => Ok(expression),
+                    _ => Err("missing closing parenthesis".to_string()),
+                }
+            }
+            Some(Token::Metric(metric)) => {
+                let comparison = match self.tokens.next() {
+                    Some(Token::Comparison(comparison)) => comparison,
+                    _ => return Err(format!("expected a comparison after metric '{}'", metric)),
+                };
+                match self.tokens.next() {
+                    Some(Token::Number(threshold)) => Ok(WatchExpression::Condition(metric, comparison, threshold)),
+                    _ => Err(format!("expected a number after '{} {}'", metric, comparison)),
+                }
+            }
+            Some(token) => Err(format!("unexpected {}", token)),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_watch_expression_parsing {
+    use rstest::*;
+
+    use crate::core::watch::{Comparison, WatchExpression};
+
+    fn condition(metric: &str, comparison: Comparison, threshold: f64) -> WatchExpression {
+        WatchExpression::Condition(metric.to_string(), comparison, threshold)
+    }
+
+    #[rstest]
+    #[case("cpu > 50", condition("cpu", Comparison::Greater, 50.))]
+    #[case("cpu>=50", condition("cpu", Comparison::GreaterOrEqual, 50.))]
+    #[case("cpu < 2.5%", condition("cpu", Comparison::Lower, 2.5))]
+    #[case("memory <= 1G", condition("memory", Comparison::LowerOrEqual, 1024. * 1024. * 1024.))]
+    #[case("memory-growth > 512k", condition("memory-growth", Comparison::Greater, 512. * 1024.))]
+    fn test_should_parse_condition(#[case] repr: &str, #[case] expected: WatchExpression) {
+        assert_eq!(repr.parse::<WatchExpression>(), Ok(expected));
+    }
+
+    #[test]
+    fn test_and_should_take_precedence_over_or() {
+        let expected = WatchExpression::Or(
+            Box::new(condition("cpu", Comparison::Greater, 50.)),
+            Box::new(WatchExpression::And(
+                Box::new(condition("memory", Comparison::Greater, 1024.)),
+                Box::new(condition("diskio", Comparison::Greater, 0.)),
+            )),
+        );
+
+        assert_eq!(
+            "cpu > 50 OR memory > 1K and diskio > 0".parse::<WatchExpression>(),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn test_parentheses_should_group_conditions() {
+        let expected = WatchExpression::And(
+            Box::new(WatchExpression::Or(
+                Box::new(condition("cpu", Comparison::Greater, 50.)),
+                Box::new(condition("memory", Comparison::Greater, 1024.)),
+            )),
+            Box::new(condition("diskio", Comparison::Greater, 0.)),
+        );
+
+        assert_eq!(
+            "(cpu > 50 OR memory > 1K) AND diskio > 0".parse::<WatchExpression>(),
+            Ok(expected)
+        );
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("cpu")]
+    #[case("cpu > ")]
+    #[case("cpu = 50")]
+    #[case("cpu > high")]
+    #[case("cpu > 50X")]
+    #[case("cpu > 50 OR")]
+    #[case("cpu > 50 memory > 1")]
+    #[case("(cpu > 50")]
+    #[case("cpu > 50)")]
+    fn test_should_reject_invalid_expression(#[case] repr: &str) {
+        assert!(repr.parse::<WatchExpression>().is_err());
+    }
+
+    #[rstest]
+    #[case("cpu > 50")]
+    #[case("cpu > 50 OR memory <= 1024 AND diskio >= 1")]
+    #[case("(cpu > 50 OR memory < 1024) AND diskio > 1")]
+    fn test_display_should_be_parsed_back_into_same_expression(#[case] repr: &str) {
+        let expression: WatchExpression = repr.parse().unwrap();
+
+        assert_eq!(expression.to_string().parse::<WatchExpression>(), Ok(expression));
+    }
+
+    #[test]
+    fn test_should_list_metrics_of_all_conditions() {
+        let expression: WatchExpression = "cpu > 50 OR (memory > 1G AND cpu < 90)".parse().unwrap();
+
+        assert_eq!(expression.metrics(), vec!["cpu", "memory", "cpu"]);
+    }
+}
+
+#[cfg(test)]
+mod test_watch_expression_evaluation {
+    use std::collections::HashMap;
+
+    use rstest::*;
+
+    use crate::core::collection::{MetricCollector, ProbeCollector};
+    use crate::core::metrics::PercentMetric;
+    use crate::core::probe::fakes::FakeProbe;
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::core::watch::WatchExpression;
+
+    /// Two collectors, named `cpu` and `memory` in expressions, having collected the metrics of processes 1 to 3
+    #[fixture]
+    fn collectors() -> (ProbeCollector<PercentMetric>, ProbeCollector<PercentMetric>) {
+        setup_fake_clock_to_prevent_substract_overflow();
+
+        let mut cpu = ProbeCollector::new(FakeProbe::from_percent_map(hashmap!(1 => 80., 2 => 10., 3 => 30.)));
+        let mut memory = ProbeCollector::new(FakeProbe::from_percent_map(hashmap!(1 => 5., 2 => 60., 3 => 30.)));
+        cpu.collect(&[1, 2, 3]).unwrap();
+        memory.collect(&[1, 2, 3]).unwrap();
+
+        (cpu, memory)
+    }
+
+    fn matching_pids(
+        expression: &str,
+        collectors: &(ProbeCollector<PercentMetric>, ProbeCollector<PercentMetric>),
+    ) -> Vec<u32> {
+        let expression: WatchExpression = expression.parse().unwrap();
+        let overviews = HashMap::from([("cpu", collectors.0.overview()), ("memory", collectors.1.overview())]);
+
+        (1..=3).filter(|pid| expression.matches(*pid, &overviews)).collect()
+    }
+
+    #[rstest]
+    #[case("cpu > 50", vec![1])]
+    #[case("cpu >= 30", vec![1, 3])]
+    #[case("memory < 30", vec![1])]
+    #[case("memory <= 30", vec![1, 3])]
+    #[case("cpu > 50 OR memory > 50", vec![1, 2])]
+    #[case("cpu > 20 AND memory > 20", vec![3])]
+    #[case("(cpu > 50 OR memory > 50) AND cpu < 50", vec![2])]
+    fn test_should_match_processes_whose_metrics_satisfy_expression(
+        collectors: (ProbeCollector<PercentMetric>, ProbeCollector<PercentMetric>),
+        #[case] expression: &str,
+        #[case] expected: Vec<u32>,
+    ) {
+        assert_eq!(matching_pids(expression, &collectors), expected);
+    }
+
+    #[rstest]
+    fn test_conditions_on_unknown_metrics_should_never_match(
+        collectors: (ProbeCollector<PercentMetric>, ProbeCollector<PercentMetric>),
+    ) {
+        assert_eq!(matching_pids("diskio >= 0", &collectors), Vec::<u32>::new());
+        assert_eq!(matching_pids("diskio >= 0 OR cpu > 50", &collectors), vec![1]);
+    }
+}
@@ -1,13 +1,19 @@
 //! Manages the selection of which type of metrics to render
 
+use std::collections::BTreeSet;
+
 use crate::core::collection::MetricCollector;
 use crate::core::view::CollectorsView;
 
 /// Contains the list of collectors available to the application,
 /// and allow a user from selecting one
+///
+/// Collectors can be hidden from the user: they can not be selected anymore, but they keep collecting metrics, so that
+/// no metric is missing once they are restored.
 pub struct Collectors {
     collectors: Vec<Box<dyn MetricCollector>>,
     selected_index: usize,
+    hidden: BTreeSet<usize>,
 }
 
 impl Collectors {
@@ -21,40 +27,138 @@ impl Collectors {
         Self {
             collectors,
             selected_index: 0,
+            hidden: BTreeSet::new(),
         }
     }
 
     pub fn next_collector(&mut self) {
-        self.selected_index = (self.selected_index + 1) % self.collectors.len();
+        let count = self.collectors.len();
+        self.selected_index = (1..=count)
+            .map(|offset| (self.selected_index + offset) % count)
+            .find(|index| !self.hidden.contains(index))
+            .unwrap_or(self.selected_index);
     }
 
     pub fn previous_collector(&mut self) {
-        self.selected_index = self.selected_index.checked_sub(1).unwrap_or(self.collectors.len() - 1);
+        let count = self.collectors.len();
+        self.selected_index = (1..=count)
+            .map(|offset| (self.selected_index + count - offset) % count)
+            .find(|index| !self.hidden.contains(index))
+            .unwrap_or(self.selected_index);
+    }
+
+    /// Hides the current collector, and selects the next visible one
+    ///
+    /// The last visible collector can not be hidden.
+    pub fn hide_current(&mut self) {
+        if self.hidden.len() + 1 < self.collectors.len() {
+            self.hidden.insert(self.selected_index);
+            self.next_collector();
+        }
+    }
+
+    /// Makes all the hidden collectors visible again
+    pub fn restore_hidden(&mut self) {
+        self.hidden.clear();
+    }
+
+    /// Selects the collector with the given name, unless it is hidden or there is no such collector
+    pub fn select_by_name(&mut self, name: &str) {
+        if let Some(index) = self.collectors.iter().position(|mc| mc.name() == name) {
+            if !self.hidden.contains(&index) {
+                self.selected_index = index;
+            }
+        }
+    }
+
+    /// Hides the collectors with the given names, and makes the other collectors visible
+    ///
+    /// The current collector remains visible, even if its name is given.
+    pub fn set_hidden_names(&mut self, names: &[String]) {
+        self.hidden = (0..self.collectors.len())
+            .filter(|index| *index != self.selected_index)
+            .filter(|index| names.iter().any(|name| name == self.collectors[*index].name()))
+            .collect();
+    }
+
+    /// Returns the names of the hidden collectors
+    pub fn hidden_names(&self) -> Vec<&str> {
+        self.hidden.iter().map(|index| self.collectors[*index].name()).collect()
     }
 
     pub fn current(&self) -> &dyn MetricCollector {
         self.collectors[self.selected_index].as_ref()
     }
 
+    /// Returns the index of the current collector
+    pub fn current_index(&self) -> usize {
+        self.selected_index
+    }
+
+    pub fn current_mut(&mut self) -> &mut dyn MetricCollector {
+        self.collectors[self.selected_index].as_mut()
+    }
+
+    /// Returns the collector with the given name, if there is one
+    pub fn by_name(&self, name: &str) -> Option<&dyn MetricCollector> {
+        self.collectors
+            .iter()
+            .find(|mc| mc.name() == name)
+            .map(|mc| mc.as_ref())
+    }
+
+    /// Returns the names of all the collectors
+    pub fn names(&self) -> Vec<&str> {
+        self.collectors.iter().map(|mc| mc.name()).collect()
+    }
+
+    /// Returns the visible collectors, in the order of their tabs
+    pub fn visible(&self) -> Vec<&dyn MetricCollector> {
+        self.collectors
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.hidden.contains(index))
+            .map(|(_, mc)| mc.as_ref())
+            .collect()
+    }
+
+    pub fn as_slice(&self) -> &[Box<dyn MetricCollector>] {
+        self.collectors.as_slice()
+    }
+
     pub fn as_mut_slice(&mut self) -> &mut [Box<dyn MetricCollector>] {
         self.collectors.as_mut_slice()
     }
 
-    pub fn to_view(&self) -> CollectorsView {
-        let names = self.collectors.iter().map(|mc| mc.name()).collect();
-        CollectorsView::new(names, self.selected_index)
+    /// Builds a view of the visible collectors
+    pub fn to_view(&self) -> CollectorsView<'_> {
+        let visible_indices: Vec<usize> = (0..self.collectors.len())
+            .filter(|index| !self.hidden.contains(index))
+            .collect();
+        let names = visible_indices
+            .iter()
+            .map(|index| self.collectors[*index].name())
+            .collect();
+        let selected_index = visible_indices
+            .iter()
+            .position(|index| *index == self.selected_index)
+            .expect("The selected collector is hidden");
+
+        CollectorsView::new(names, selected_index).with_selected_source(self.current().source_label())
     }
 }
 
 #[cfg(test)]
 mod test_collectors_selector {
     use std::cmp::Ordering;
+    use std::time::Duration;
 
     use rstest::*;
 
     use crate::core::collection::MetricCollector;
+    use crate::core::metrics::Metric;
     use crate::core::process::Pid;
-    use crate::core::time::Span;
+    use crate::core::time::{Span, Timestamp};
     use crate::core::view::{MetricView, MetricsOverview};
     use crate::core::Error;
     use crate::ctrl::collectors::Collectors;
@@ -72,6 +176,18 @@ mod test_collectors_selector {
             unimplemented!()
         }
 
+        fn purge(&mut self, _oldest: Timestamp) {
+            unimplemented!()
+        }
+
+        fn compact(&mut self, _older_than: Timestamp, _bucket: Duration) {
+            unimplemented!()
+        }
+
+        fn clear(&mut self, _pid: Pid) {
+            unimplemented!()
+        }
+
         fn calibrate(&mut self, _pids: &[Pid]) -> Result<(), Error> {
             unimplemented!()
         }
@@ -84,11 +200,19 @@ mod test_collectors_selector {
             self.name
         }
 
-        fn view(&self, _pid: Pid, _span: Span) -> MetricView {
+        fn view(&self, _pid: Pid, _span: Span) -> MetricView<'_> {
+            unimplemented!()
+        }
+
+        fn overview(&self) -> MetricsOverview<'_> {
             unimplemented!()
         }
 
-        fn overview(&self) -> MetricsOverview {
+        fn last_metric(&self, _pid: Pid) -> Option<Box<dyn Metric>> {
+            unimplemented!()
+        }
+
+        fn first_timestamp(&self, _pid: Pid) -> Option<Timestamp> {
             unimplemented!()
         }
     }
@@ -157,4 +281,95 @@ mod test_collectors_selector {
         assert_eq!(view.selected_index(), 1);
         assert_eq!(view.collectors_names(), ["collector_1", "collector_2"])
     }
+
+    #[fixture]
+    fn three_collectors() -> Vec<Box<dyn MetricCollector>> {
+        vec![
+            Box::new(FakeCollector { name: "collector_1" }),
+            Box::new(FakeCollector { name: "collector_2" }),
+            Box::new(FakeCollector { name: "collector_3" }),
+        ]
+    }
+
+    #[rstest]
+    fn test_should_select_next_collector_when_current_is_hidden(three_collectors: Vec<Box<dyn MetricCollector>>) {
+        let mut selector = Collectors::new(three_collectors);
+        selector.next_collector();
+
+        selector.hide_current();
+
+        assert_eq!(selector.current().name(), "collector_3");
+        assert_eq!(selector.hidden_names(), ["collector_2"]);
+    }
+
+    #[rstest]
+    fn test_should_skip_hidden_collectors(three_collectors: Vec<Box<dyn MetricCollector>>) {
+        let mut selector = Collectors::new(three_collectors);
+        selector.next_collector();
+        selector.hide_current();
+
+        selector.previous_collector();
+        assert_eq!(selector.current().name(), "collector_1");
+
+        selector.next_collector();
+        assert_eq!(selector.current().name(), "collector_3");
+    }
+
+    #[rstest]
+    fn test_should_not_hide_last_visible_collector(collectors: Vec<Box<dyn MetricCollector>>) {
+        let mut selector = Collectors::new(collectors);
+        selector.hide_current();
+        selector.hide_current();
+
+        assert_eq!(selector.current().name(), "collector_2");
+        assert_eq!(selector.hidden_names(), ["collector_1"]);
+    }
+
+    #[rstest]
+    fn test_should_build_view_of_visible_collectors(three_collectors: Vec<Box<dyn MetricCollector>>) {
+        let mut selector = Collectors::new(three_collectors);
+        selector.hide_current();
+        selector.next_collector();
+
+        let view = selector.to_view();
+
+        assert_eq!(view.collectors_names(), ["collector_2", "collector_3"]);
+        assert_eq!(view.selected_index(), 1);
+    }
+
+    #[rstest]
+    fn test_should_restore_hidden_collectors(three_collectors: Vec<Box<dyn MetricCollector>>) {
+        let mut selector = Collectors::new(three_collectors);
+        selector.hide_current();
+        selector.hide_current();
+
+        selector.restore_hidden();
+
+        assert_eq!(selector.current().name(), "collector_3");
+        assert!(selector.hidden_names().is_empty());
+        assert_eq!(
+            selector.to_view().collectors_names(),
+            ["collector_1", "collector_2", "collector_3"]
+        );
+        assert_eq!(selector.to_view().selected_index(), 2);
+    }
+
+    #[rstest]
+    fn test_should_only_list_visible_collectors(three_collectors: Vec<Box<dyn MetricCollector>>) {
+        let mut selector = Collectors::new(three_collectors);
+        selector.next_collector();
+        selector.hide_current();
+
+        let names: Vec<_> = selector.visible().iter().map(|mc| mc.name()).collect();
+
+        assert_eq!(names, vec!["collector_1", "collector_3"]);
+    }
+
+    #[rstest]
+    fn test_should_find_collector_by_name(collectors: Vec<Box<dyn MetricCollector>>) {
+        let selector = Collectors::new(collectors);
+
+        assert_eq!(selector.by_name("collector_2").map(|mc| mc.name()), Some("collector_2"));
+        assert!(selector.by_name("unknown").is_none());
+    }
 }
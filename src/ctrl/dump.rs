@@ -0,0 +1,159 @@
+//! Describes the state of spv in a readable report, to help reproducing bugs
+use std::fmt::{Display, Formatter};
+
+use crate::core::collection::MetricCollector;
+use crate::core::ordering::ProcessOrdering;
+use crate::core::process::{Pid, ProcessMetadata};
+use crate::core::time::{Span, Timestamp};
+
+/// A snapshot of the processes, collectors and controls of spv, which is formatted as a diagnostic report
+pub struct StateDump<'a> {
+    pub(crate) processes: &'a [ProcessMetadata],
+    pub(crate) collectors: &'a [Box<dyn MetricCollector>],
+    pub(crate) current_collector: &'a str,
+    pub(crate) hidden_collectors: Vec<&'a str>,
+    pub(crate) span: Span,
+    pub(crate) following: bool,
+    pub(crate) selected_pid: Option<Pid>,
+    pub(crate) ordering: ProcessOrdering,
+}
+
+impl StateDump<'_> {
+    fn fmt_controls(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let now = Timestamp::now();
+        writeln!(f, "[controls]")?;
+        writeln!(
+            f,
+            "span: {:?} ending {:?} ago (following: {})",
+            self.span.duration(),
+            now.duration_since(&self.span.end()),
+            self.following
+        )?;
+        match self.selected_pid {
+            Some(pid) => writeln!(f, "selected process: {}", pid)?,
+            None => writeln!(f, "selected process: none")?,
+        }
+        writeln!(f, "displayed collector: {}", self.current_collector)?;
+        writeln!(f, "hidden collectors: {}", self.hidden_collectors.join(", "))?;
+        writeln!(f, "process ordering: {:?}", self.ordering)
+    }
+
+    fn fmt_processes(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let now = Timestamp::now();
+        writeln!(f, "[processes]")?;
+        for pm in self.processes {
+            let running_span = pm.running_span();
+            writeln!(
+                f,
+                "{} (TGID {}) {:?}{}: {}{}, spawned {:?} ago, last seen {:?} ago",
+                pm.pid(),
+                pm.tgid(),
+                pm.command(),
+                pm.label()
+                    .map(|label| format!(" labelled {:?}", label))
+                    .unwrap_or_default(),
+                pm.status(),
+                if pm.is_isolated() { " in container" } else { "" },
+                now.duration_since(&running_span.begin()),
+                now.duration_since(&running_span.end()),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_collectors(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[collectors]")?;
+        for collector in self.collectors {
+            let overview = collector.overview();
+            writeln!(f, "{} ({}):", collector.name(), overview.unit())?;
+            for pm in self.processes {
+                writeln!(f, "  {}: {}", pm.pid(), overview.concise_repr(pm.pid()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for StateDump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fmt_controls(f)?;
+        writeln!(f)?;
+        self.fmt_processes(f)?;
+        writeln!(f)?;
+        self.fmt_collectors(f)
+    }
+}
+
+#[cfg(test)]
+mod test_state_dump {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::core::collection::{MetricCollector, ProbeCollector};
+    use crate::core::ordering::ProcessOrdering;
+    use crate::core::probe::fakes::FakeProbe;
+    use crate::core::process::ProcessMetadata;
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::core::time::{Span, Timestamp};
+    use crate::ctrl::dump::StateDump;
+
+    #[fixture]
+    fn dump_text() -> String {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let mut collector = ProbeCollector::new(FakeProbe::from_percent_map(hashmap!(1 => 10., 2 => 20.)));
+        collector.collect(&[1, 2]).unwrap();
+        let collectors: Vec<Box<dyn MetricCollector>> = vec![Box::new(collector)];
+
+        let mut dead_process = ProcessMetadata::new(2, "make", Timestamp::now() - Duration::from_secs(3));
+        dead_process.mark_dead();
+        let processes = vec![
+            ProcessMetadata::new(1, "firefox", Timestamp::now() - Duration::from_secs(30)),
+            dead_process,
+        ];
+
+        let dump = StateDump {
+            processes: &processes,
+            collectors: &collectors,
+            current_collector: "fake",
+            hidden_collectors: vec!["hidden_1", "hidden_2"],
+            span: Span::from_duration(Duration::from_secs(60)),
+            following: true,
+            selected_pid: Some(1),
+            ordering: ProcessOrdering::Command,
+        };
+
+        dump.to_string()
+    }
+
+    #[rstest]
+    fn test_should_include_all_sections(dump_text: String) {
+        let controls_idx = dump_text.find("[controls]").unwrap();
+        let processes_idx = dump_text.find("[processes]").unwrap();
+        let collectors_idx = dump_text.find("[collectors]").unwrap();
+
+        assert!(controls_idx < processes_idx && processes_idx < collectors_idx);
+    }
+
+    #[rstest]
+    fn test_should_describe_controls(dump_text: String) {
+        assert!(dump_text.contains("span: 60s ending 0ns ago (following: true)"));
+        assert!(dump_text.contains("selected process: 1"));
+        assert!(dump_text.contains("displayed collector: fake"));
+        assert!(dump_text.contains("hidden collectors: hidden_1, hidden_2"));
+        assert!(dump_text.contains("process ordering: Command"));
+    }
+
+    #[rstest]
+    fn test_should_describe_each_process(dump_text: String) {
+        assert!(dump_text.contains("1 (TGID 1) \"firefox\": running, spawned 30s ago"));
+        assert!(dump_text.contains("2 (TGID 2) \"make\": dead, spawned 3s ago"));
+    }
+
+    #[rstest]
+    fn test_should_include_latest_metric_of_each_process(dump_text: String) {
+        assert!(dump_text.contains("fake (%):\n  1: 10.0\n  2: 20.0\n"));
+    }
+}
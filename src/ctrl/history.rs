@@ -0,0 +1,102 @@
+//! Keeps track of the previous states of the view, so that the user can step back to them
+
+use std::collections::VecDeque;
+
+use crate::core::process::Pid;
+use crate::ctrl::span::RenderingSpan;
+
+/// How many states of the view are kept by default
+const DEFAULT_CAPACITY: usize = 32;
+
+/// The state of the view that the user can step back to
+#[derive(Clone, PartialEq, Debug)]
+pub struct ViewSnapshot {
+    pub rendering_span: RenderingSpan,
+    pub selected_pid: Option<Pid>,
+}
+
+/// A bounded stack of the previous states of the view
+///
+/// When the stack is full, pushing a new state discards the oldest one.
+pub struct ViewHistory {
+    snapshots: VecDeque<ViewSnapshot>,
+    capacity: usize,
+}
+
+impl ViewHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            snapshots: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Saves a state of the view, discarding the oldest saved state if the history is full
+    pub fn push(&mut self, snapshot: ViewSnapshot) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(snapshot);
+    }
+
+    /// Removes and returns the latest saved state of the view, or `None` if no state is saved
+    pub fn pop(&mut self) -> Option<ViewSnapshot> {
+        self.snapshots.pop_back()
+    }
+}
+
+impl Default for ViewHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod test_view_history {
+    use std::time::Duration;
+
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::ctrl::history::{ViewHistory, ViewSnapshot};
+    use crate::ctrl::span::RenderingSpan;
+
+    fn snapshot(selected_pid: u32) -> ViewSnapshot {
+        setup_fake_clock_to_prevent_substract_overflow();
+
+        ViewSnapshot {
+            rendering_span: RenderingSpan::new(Duration::from_secs(60)),
+            selected_pid: Some(selected_pid),
+        }
+    }
+
+    #[test]
+    fn test_should_pop_nothing_from_empty_history() {
+        assert_eq!(ViewHistory::default().pop(), None);
+    }
+
+    #[test]
+    fn test_should_pop_snapshots_in_reverse_order() {
+        let mut history = ViewHistory::default();
+        history.push(snapshot(1));
+        history.push(snapshot(2));
+
+        assert_eq!(history.pop(), Some(snapshot(2)));
+        assert_eq!(history.pop(), Some(snapshot(1)));
+        assert_eq!(history.pop(), None);
+    }
+
+    #[test]
+    fn test_should_discard_oldest_snapshot_when_full() {
+        let mut history = ViewHistory::new(2);
+        history.push(snapshot(1));
+        history.push(snapshot(2));
+        history.push(snapshot(3));
+
+        assert_eq!(history.pop(), Some(snapshot(3)));
+        assert_eq!(history.pop(), Some(snapshot(2)));
+        assert_eq!(history.pop(), None);
+    }
+}
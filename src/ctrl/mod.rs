@@ -1,31 +1,105 @@
 //! Interprets user inputs
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process;
 use std::time::Duration;
 
+use crate::config::OwnProcess;
 use crate::core::collection::MetricCollector;
 use crate::core::ordering::ProcessOrdering;
-use crate::core::process::ProcessMetadata;
+use crate::core::process::{Pid, ProcessMetadata};
+use crate::core::signal::ProcessSignal;
 use crate::core::time::Span;
 use crate::core::view::{CollectorsView, ProcessesView};
 use crate::ctrl::collectors::Collectors;
-use crate::ctrl::processes::{ProcessSelector, SortCriteriaSelector};
+use crate::ctrl::dump::StateDump;
+use crate::ctrl::history::{ViewHistory, ViewSnapshot};
+use crate::ctrl::preset::{ordering_repr, PresetPrompt, ViewPreset};
+use crate::ctrl::processes::{ProcessSelector, SortCriteriaSelector, SortHysteresis, UptimeFilter};
 use crate::ctrl::span::RenderingSpan;
-use crate::triggers::Input;
+use crate::triggers::{Input, MouseEvent};
 
 pub mod collectors;
+pub mod dump;
+pub mod history;
+pub mod preset;
 pub mod processes;
 pub mod span;
 
 /// Indicates the effect caused by a user input
-#[derive(Eq, PartialEq)]
+#[derive(Eq, PartialEq, Debug)]
 pub enum Effect {
     None,
     ProcessesSorted(ProcessOrdering),
+    /// The user asked for the state of the application to be dumped to a file
+    StateDumpRequested,
+    StateDumped(PathBuf),
+    StateDumpFailed,
+    /// The user asked for the selected process to be sent the given signal
+    SignalRequested(ProcessSignal),
+    ProcessSignalled(Pid, ProcessSignal),
+    SignalFailed(Pid, ProcessSignal),
+    /// The user asked for the external command to be run on the selected process
+    CommandRequested,
+    /// No external command has been configured
+    CommandNotConfigured,
+    /// The external command run on the process with the given PID could not be launched, or failed
+    CommandFailed(Pid),
+    /// The user asked for the view to be saved as a preset with the given name
+    PresetSaveRequested(String),
+    PresetSaved(String),
+    PresetSaveFailed(String),
+    /// The user asked to select a saved preset
+    PresetSelectionRequested,
+    /// The saved presets could not be listed
+    PresetsUnavailable,
+    /// The user asked for the preset with the given name to be loaded
+    PresetLoadRequested(String),
+    PresetLoaded(String),
+    PresetLoadFailed(String),
+    /// Processes sorted by their metrics are compared by the average of their metrics over the span of the chart if
+    /// true, or by their last metrics otherwise
+    SpanAverageSorting(bool),
+    /// The user asked for the metrics collected for the process with the given PID to be cleared
+    MetricsClearRequested(Pid),
+    MetricsCleared(Pid),
+    /// The user confirmed that spv must quit
+    QuitConfirmed,
 }
 
-#[derive(Copy, Clone)]
+/// The element of the UI located under the mouse cursor
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MouseTarget {
+    /// The row of the process list displaying the process at the given index
+    ProcessRow(usize),
+    /// The chart, at the given column of the chart, which is `width` columns wide
+    Chart {
+        column: u16,
+        width: u16,
+    },
+    None,
+}
+
+#[derive(Clone)]
 pub enum State {
     Spv,
     SortingPrompt(ProcessOrdering),
+    /// The details of the selected process are displayed in place of the main view
+    ProcessDetail,
+    /// A warning explains that spv lacks the permissions to monitor most processes, until the user dismisses it
+    PermissionWarning,
+    /// The user types the name under which to save the view as a preset
+    PresetNaming,
+    /// The user selects the saved preset to load
+    PresetSelection,
+    /// The leading processes of every visible tab are summarized in place of the main view
+    Summary,
+    /// The disk space usage of the mounted filesystems is displayed in place of the main view
+    Filesystems,
+    /// The metrics of the current tab summed by systemd unit are displayed in place of the main view
+    Units,
+    /// The user is asked to confirm that spv must quit
+    QuitConfirmation,
 }
 
 /// Wraps all controls utilities within a single unit
@@ -35,31 +109,145 @@ pub struct Controls {
     process_selector: ProcessSelector,
     sort_criteria_selector: SortCriteriaSelector,
     current_state: State,
+    // The column of the chart on which the mouse was last pressed or dragged, while the chart is being dragged
+    drag_column: Option<u16>,
+    // The state of the view before the chart started being dragged
+    drag_origin: Option<ViewSnapshot>,
+    view_history: ViewHistory,
+    // If true, the chart draws the newest metrics on its left, which reverses the direction of drags
+    newest_left_chart: bool,
+    chart_only: bool,
+    histogram_mode: bool,
+    // If true, processes sorted by their metrics are compared by the average of their metrics over the rendered span
+    span_average_sorting: bool,
+    preset_prompt: PresetPrompt,
+    // The state to restore if the user does not confirm that spv must quit
+    state_before_quit: State,
 }
 
 impl Controls {
-    pub fn new(collectors: Vec<Box<dyn MetricCollector>>, initial_span_duration: Duration) -> Self {
+    pub fn new(
+        collectors: Vec<Box<dyn MetricCollector>>,
+        initial_span_duration: Duration,
+        history: Option<Duration>,
+    ) -> Self {
+        let collectors = Collectors::new(collectors);
+        let sort_criteria_selector = SortCriteriaSelector::new(&collectors.names());
+
         Self {
-            collectors: Collectors::new(collectors),
-            rendering_span: RenderingSpan::new(initial_span_duration),
+            collectors,
+            rendering_span: RenderingSpan::new(initial_span_duration).with_history(history),
             process_selector: ProcessSelector::default(),
-            sort_criteria_selector: SortCriteriaSelector::default(),
+            sort_criteria_selector,
             current_state: State::Spv,
+            drag_column: None,
+            drag_origin: None,
+            view_history: ViewHistory::default(),
+            newest_left_chart: false,
+            chart_only: false,
+            histogram_mode: false,
+            span_average_sorting: false,
+            preset_prompt: PresetPrompt::default(),
+            state_before_quit: State::Spv,
         }
     }
 
+    /// Indicates if the chart draws the newest metrics on its left, so that dragging the chart follows the mouse
+    pub fn with_newest_left_chart(mut self, newest_left_chart: bool) -> Self {
+        self.newest_left_chart = newest_left_chart;
+        self
+    }
+
+    /// Hides the processes which have been running for less than `min_uptime`.
+    /// If `min_uptime` is `None`, all processes are displayed until the user toggles the uptime filter.
+    pub fn with_min_uptime(mut self, min_uptime: Option<Duration>) -> Self {
+        self.set_min_uptime(min_uptime);
+        self
+    }
+
+    /// Keeps processes sorted by their metrics in their previous order until their metrics differ by more than the
+    /// threshold of `sort_hysteresis`. If `sort_hysteresis` is `None`, processes are fully sorted on each refresh.
+    pub fn with_sort_hysteresis(mut self, sort_hysteresis: Option<SortHysteresis>) -> Self {
+        self.set_sort_hysteresis(sort_hysteresis);
+        self
+    }
+
+    /// Shows, hides or highlights the process of spv in the process list
+    pub fn with_own_process(mut self, own_process: OwnProcess) -> Self {
+        self.set_own_process(own_process);
+        self
+    }
+
+    /// Replaces the way the process of spv is listed
+    pub fn set_own_process(&mut self, own_process: OwnProcess) {
+        self.process_selector.set_own_process(process::id(), own_process);
+    }
+
+    /// Replaces the uptime filter, which applies from the next processes being set
+    pub fn set_min_uptime(&mut self, min_uptime: Option<Duration>) {
+        let uptime_filter = min_uptime.map(UptimeFilter::new).unwrap_or_default();
+        self.process_selector = std::mem::take(&mut self.process_selector).with_uptime_filter(uptime_filter);
+    }
+
+    /// Replaces the sort hysteresis, which applies from the next processes being set
+    pub fn set_sort_hysteresis(&mut self, sort_hysteresis: Option<SortHysteresis>) {
+        self.process_selector = std::mem::take(&mut self.process_selector).with_sort_hysteresis(sort_hysteresis);
+    }
+
     /// Interprets the user input to control the application.
     /// The input will have a different effect depending on the state of the application.
     ///
     /// Returns the effect caused by the input.
     pub fn interpret_input(&mut self, input: Input) -> Effect {
+        if let (Input::Q, false) = (&input, matches!(self.current_state, State::QuitConfirmation)) {
+            self.state_before_quit = self.current_state.clone();
+            self.current_state = State::QuitConfirmation;
+            return Effect::None;
+        }
+
         match self.current_state {
             State::Spv => self.interpret_spv_input(input),
             State::SortingPrompt(_) => self.interpret_sorting_prompt_input(input),
+            State::ProcessDetail => self.interpret_process_detail_input(input),
+            State::PermissionWarning => self.interpret_permission_warning_input(input),
+            State::PresetNaming => self.interpret_preset_naming_input(input),
+            State::PresetSelection => self.interpret_preset_selection_input(input),
+            State::Summary => self.interpret_summary_input(input),
+            State::Filesystems => self.interpret_filesystems_input(input),
+            State::Units => self.interpret_units_input(input),
+            State::QuitConfirmation => self.interpret_quit_confirmation_input(input),
         }
     }
 
+    /// Quits when the user confirms it, and restores the previous state on any other input
+    fn interpret_quit_confirmation_input(&mut self, input: Input) -> Effect {
+        match input {
+            Input::Y | Input::Submit => Effect::QuitConfirmed,
+            _ => {
+                self.current_state = self.state_before_quit.clone();
+                Effect::None
+            }
+        }
+    }
+
+    /// Warns the user that spv lacks the permissions to monitor most processes
+    pub fn warn_about_permissions(&mut self) {
+        if let State::Spv = self.current_state {
+            self.current_state = State::PermissionWarning;
+        }
+    }
+
+    fn interpret_permission_warning_input(&mut self, input: Input) -> Effect {
+        if let Input::Escape | Input::Submit = input {
+            self.current_state = State::Spv;
+        }
+
+        Effect::None
+    }
+
     fn interpret_spv_input(&mut self, input: Input) -> Effect {
+        let previous_view = self.snapshot_view();
+
         match input {
             Input::Left => self.collectors.previous_collector(),
             Input::Right => self.collectors.next_collector(),
@@ -70,13 +258,209 @@ impl Controls {
             Input::AltRight => self.rendering_span.scroll_right(),
             Input::AltUp => self.rendering_span.zoom_in(),
             Input::AltDown => self.rendering_span.zoom_out(),
-            Input::S => self.current_state = State::SortingPrompt(self.sort_criteria_selector.applied()),
+            Input::S => {
+                self.sort_criteria_selector.select_applied();
+                self.current_state = State::SortingPrompt(self.sort_criteria_selector.applied());
+            }
+            Input::T => self.process_selector.toggle_identifier(),
+            Input::U => self.process_selector.toggle_uptime_filter(),
+            Input::N => self.process_selector.cycle_isolation_filter(),
+            Input::F => self.rendering_span.set_follow(!self.rendering_span.is_following()),
+            Input::D => return Effect::StateDumpRequested,
+            Input::P => {
+                if let Some(process) = self.process_selector.selected_process() {
+                    return Effect::SignalRequested(ProcessSignal::toggling(process));
+                }
+            }
+            Input::X => self.collectors.hide_current(),
+            Input::R => self.collectors.restore_hidden(),
+            Input::C => self.chart_only = !self.chart_only,
+            Input::B => self.histogram_mode = !self.histogram_mode,
+            Input::W => {
+                self.preset_prompt = PresetPrompt::default();
+                self.current_state = State::PresetNaming;
+            }
+            Input::O => return Effect::PresetSelectionRequested,
+            Input::A => self.current_state = State::Summary,
+            Input::M => self.current_state = State::Filesystems,
+            Input::ShiftU => self.current_state = State::Units,
+            Input::Z => self.fit_span_to_selected_process(),
+            Input::V => {
+                self.span_average_sorting = !self.span_average_sorting;
+                return Effect::SpanAverageSorting(self.span_average_sorting);
+            }
+            Input::I => self.collectors.current_mut().cycle_source(),
+            Input::E if self.process_selector.selected_process().is_some() => return Effect::CommandRequested,
+            Input::Delete => {
+                if let Some(process) = self.process_selector.selected_process() {
+                    return Effect::MetricsClearRequested(process.pid());
+                }
+            }
+            Input::Submit if self.process_selector.selected_process().is_some() => {
+                self.current_state = State::ProcessDetail
+            }
+            Input::Backspace => {
+                self.undo_view_change();
+                return Effect::None;
+            }
+            _ => {}
+        }
+
+        self.save_view_if_changed(previous_view);
+        Effect::None
+    }
+
+    /// Captures the span and the selected process, which the user can step back to
+    fn snapshot_view(&self) -> ViewSnapshot {
+        ViewSnapshot {
+            rendering_span: self.rendering_span.clone(),
+            selected_pid: self.process_selector.selected_process().map(|pm| pm.pid()),
+        }
+    }
+
+    /// Saves the given previous state of the view in the history, unless the view is still in this state
+    fn save_view_if_changed(&mut self, previous_view: ViewSnapshot) {
+        if previous_view != self.snapshot_view() {
+            self.view_history.push(previous_view);
+        }
+    }
+
+    /// Restores the latest state of the view saved in the history, if any
+    fn undo_view_change(&mut self) {
+        if let Some(snapshot) = self.view_history.pop() {
+            self.rendering_span = snapshot.rendering_span;
+            self.rendering_span.follow(); // A span which was following the current time catches up with it
+            self.process_selector.select_pid(snapshot.selected_pid);
+        }
+    }
+
+    fn interpret_process_detail_input(&mut self, input: Input) -> Effect {
+        if let Input::Escape | Input::Submit = input {
+            self.current_state = State::Spv;
+        }
+
+        Effect::None
+    }
+
+    fn interpret_summary_input(&mut self, input: Input) -> Effect {
+        if let Input::A | Input::Escape = input {
+            self.current_state = State::Spv;
+        }
+
+        Effect::None
+    }
+
+    fn interpret_filesystems_input(&mut self, input: Input) -> Effect {
+        if let Input::M | Input::Escape = input {
+            self.current_state = State::Spv;
+        }
+
+        Effect::None
+    }
+
+    fn interpret_units_input(&mut self, input: Input) -> Effect {
+        if let Input::ShiftU | Input::Escape = input {
+            self.current_state = State::Spv;
+        }
+
+        Effect::None
+    }
+
+    fn interpret_preset_naming_input(&mut self, input: Input) -> Effect {
+        match input {
+            Input::Char(c) => self.preset_prompt.type_char(c),
+            Input::Backspace => self.preset_prompt.erase_char(),
+            Input::Escape => self.current_state = State::Spv,
+            Input::Submit if !self.preset_prompt.typed_name().is_empty() => {
+                self.current_state = State::Spv;
+                return Effect::PresetSaveRequested(self.preset_prompt.typed_name().to_string());
+            }
             _ => {}
         }
 
         Effect::None
     }
 
+    /// Prompts the user to select one of the presets with the given names
+    pub fn prompt_preset_selection(&mut self, names: Vec<String>) {
+        if let State::Spv = self.current_state {
+            self.preset_prompt = PresetPrompt::selection(names);
+            self.current_state = State::PresetSelection;
+        }
+    }
+
+    fn interpret_preset_selection_input(&mut self, input: Input) -> Effect {
+        match input {
+            Input::O | Input::Escape => self.current_state = State::Spv,
+            Input::Down => self.preset_prompt.next(),
+            Input::Up => self.preset_prompt.previous(),
+            Input::Submit => {
+                self.current_state = State::Spv;
+                if let Some(name) = self.preset_prompt.selected_name() {
+                    return Effect::PresetLoadRequested(name.to_string());
+                }
+            }
+            _ => {}
+        }
+
+        Effect::None
+    }
+
+    /// Indicates if a prompt expects the user to type text, in which case characters are not interpreted as shortcuts
+    pub fn is_typing(&self) -> bool {
+        matches!(self.current_state, State::PresetNaming)
+    }
+
+    pub fn preset_prompt(&self) -> &PresetPrompt {
+        &self.preset_prompt
+    }
+
+    /// Captures the configuration of the view, to be saved as a preset
+    pub fn preset(&self) -> ViewPreset {
+        ViewPreset {
+            collector: self.collectors.current().name().to_string(),
+            ordering: ordering_repr(&self.sort_criteria_selector.applied()),
+            span_duration: self.rendering_span.to_span().duration(),
+            hidden_collectors: self.collectors.hidden_names().into_iter().map(String::from).collect(),
+            uptime_filter: self.process_selector.is_uptime_filter_enabled(),
+            isolation_filter: self.process_selector.isolation_filter(),
+            identifier: self.process_selector.identifier(),
+            chart_only: self.chart_only,
+            histogram_mode: self.histogram_mode,
+        }
+    }
+
+    /// Restores the configuration of the view saved in the given preset
+    ///
+    /// The collectors of the preset which are not enabled are ignored, and so is its sort criteria if it refers to
+    /// such a collector.
+    pub fn apply_preset(&mut self, preset: &ViewPreset) {
+        let previous_view = self.snapshot_view();
+
+        self.collectors.restore_hidden();
+        self.collectors.select_by_name(&preset.collector);
+        self.collectors.set_hidden_names(&preset.hidden_collectors);
+
+        if let Some(criteria) = self
+            .sort_criteria_selector
+            .criteria()
+            .iter()
+            .find(|criteria| ordering_repr(criteria) == preset.ordering)
+            .cloned()
+        {
+            self.sort_criteria_selector.apply_criteria(criteria);
+        }
+
+        self.rendering_span.resize_to(preset.span_duration);
+        self.process_selector
+            .set_filters(preset.uptime_filter, preset.isolation_filter);
+        self.process_selector.set_identifier(preset.identifier);
+        self.chart_only = preset.chart_only;
+        self.histogram_mode = preset.histogram_mode;
+
+        self.save_view_if_changed(previous_view);
+    }
+
     fn interpret_sorting_prompt_input(&mut self, input: Input) -> Effect {
         match input {
             Input::S | Input::Escape => self.current_state = State::Spv,
@@ -99,6 +483,71 @@ impl Controls {
         Effect::None
     }
 
+    /// Interprets a mouse event: pressing a process row selects the process, and dragging the chart scrolls it
+    ///
+    /// # Arguments
+    ///  * `event`: The mouse event
+    ///  * `target`: The element of the UI located under the mouse cursor
+    pub fn interpret_mouse(&mut self, event: MouseEvent, target: MouseTarget) {
+        if !matches!(self.current_state, State::Spv) {
+            return;
+        }
+
+        match (event, target) {
+            (MouseEvent::Press(_, _), MouseTarget::ProcessRow(index)) => {
+                let previous_view = self.snapshot_view();
+                self.process_selector.select_process_at(index);
+                self.save_view_if_changed(previous_view);
+            }
+            (MouseEvent::Press(_, _), MouseTarget::Chart { column, .. }) => {
+                self.drag_column = Some(column);
+                self.drag_origin = Some(self.snapshot_view());
+            }
+            (MouseEvent::Drag(_, _), MouseTarget::Chart { column, width }) => {
+                if let Some(previous_column) = self.drag_column.replace(column) {
+                    self.drag_chart(previous_column, column, width);
+                }
+            }
+            (MouseEvent::Release(_, _), _) => {
+                // A whole drag of the chart is undone at once
+                self.drag_column = None;
+                if let Some(drag_origin) = self.drag_origin.take() {
+                    self.save_view_if_changed(drag_origin);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Scrolls the span so that the chart follows the mouse: dragging the chart to the right reveals older metrics,
+    /// unless the newest metrics are drawn on the left of the chart
+    fn drag_chart(&mut self, from_column: u16, to_column: u16, width: u16) {
+        let offset = drag_offset(
+            self.rendering_span.to_span().duration(),
+            from_column.abs_diff(to_column),
+            width,
+        );
+
+        if (to_column > from_column) != self.newest_left_chart {
+            self.rendering_span.scroll_left_by(offset);
+        } else {
+            self.rendering_span.scroll_right_by(offset);
+        }
+    }
+
+    /// Resizes the span so that it covers the metrics of the current tab collected for the selected process, from the
+    /// oldest one to now
+    fn fit_span_to_selected_process(&mut self) {
+        let first_timestamp = self
+            .process_selector
+            .selected_process()
+            .and_then(|process| self.collectors.current().first_timestamp(process.pid()));
+
+        if let Some(first_timestamp) = first_timestamp {
+            self.rendering_span.fit_since(first_timestamp);
+        }
+    }
+
     fn refresh_state(&mut self) {
         if let State::SortingPrompt(_) = self.current_state {
             self.current_state = State::SortingPrompt(self.sort_criteria_selector.selected());
@@ -109,15 +558,65 @@ impl Controls {
         self.rendering_span.follow();
     }
 
+    /// Returns `true` if the chart of the selected process is displayed alone, without the process list
+    pub fn is_chart_only(&self) -> bool {
+        self.chart_only
+    }
+
+    /// Indicates if the distribution of the latest metrics of processes is displayed in place of the chart
+    pub fn is_histogram_mode(&self) -> bool {
+        self.histogram_mode
+    }
+
+    /// Returns `true` if the rendered span does not track the current time
+    /// Returns the span over which the metrics of processes are averaged to sort them, or `None` if processes are
+    /// sorted by their last metrics
+    pub fn averaged_span(&self) -> Option<Span> {
+        self.span_average_sorting.then(|| self.to_span())
+    }
+
+    pub fn is_view_frozen(&self) -> bool {
+        !self.rendering_span.is_following()
+    }
+
     pub fn to_span(&self) -> Span {
         self.rendering_span.to_span()
     }
 
+    /// Sets the processes, sorted by the applied criteria
     pub fn set_processes(&mut self, processes: Vec<ProcessMetadata>) {
-        self.process_selector.set_processes(processes);
+        match self.sort_criteria_selector.applied() {
+            ProcessOrdering::CurrentMetric | ProcessOrdering::Metric(_) => {
+                let collector = ordering_collector(&self.collectors, self.sort_criteria_selector.applied());
+                match self.averaged_span() {
+                    Some(span) => self
+                        .process_selector
+                        .set_processes_sorted_by_metrics(processes, |pid| collector.span_average(pid, span)),
+                    None => {
+                        let overview = collector.overview();
+                        self.process_selector
+                            .set_processes_sorted_by_metrics(processes, |pid| overview.last_or_default(pid).max_value())
+                    }
+                }
+            }
+            ProcessOrdering::Pid | ProcessOrdering::Command => self.process_selector.set_processes(processes),
+        }
+    }
+
+    pub fn set_recently_spawned(&mut self, recently_spawned: HashSet<Pid>) {
+        self.process_selector.set_recently_spawned(recently_spawned);
+    }
+
+    pub fn set_watched(&mut self, watched: HashSet<Pid>) {
+        self.process_selector.set_watched(watched);
     }
 
-    pub fn to_processes_view(&self) -> ProcessesView {
+    /// Sets the PIDs of the processes to display ahead of the others, from the next processes being set
+    pub fn set_pinned(&mut self, pinned: HashSet<Pid>) {
+        self.process_selector.set_pinned(pinned);
+    }
+
+    pub fn to_processes_view(&self) -> ProcessesView<'_> {
         self.process_selector.to_view()
     }
 
@@ -125,19 +624,733 @@ impl Controls {
         self.collectors.as_mut_slice()
     }
 
+    pub fn collectors_as_slice(&self) -> &[Box<dyn MetricCollector>] {
+        self.collectors.as_slice()
+    }
+
+    /// Returns the collectors of the visible tabs
+    pub fn visible_collectors(&self) -> Vec<&dyn MetricCollector> {
+        self.collectors.visible()
+    }
+
     pub fn current_collector(&self) -> &dyn MetricCollector {
         self.collectors.current()
     }
 
-    pub fn to_collectors_view(&self) -> CollectorsView {
+    /// Returns the index of the collector of the current tab in the collectors
+    pub fn current_collector_index(&self) -> usize {
+        self.collectors.current_index()
+    }
+
+    pub fn to_collectors_view(&self) -> CollectorsView<'_> {
         self.collectors.to_view()
     }
 
     pub fn state(&self) -> State {
-        self.current_state
+        self.current_state.clone()
     }
 
     pub fn process_ordering_criteria(&self) -> ProcessOrdering {
         self.sort_criteria_selector.applied()
     }
+
+    /// Returns all the criteria on which processes can be sorted
+    pub fn sort_criteria(&self) -> &[ProcessOrdering] {
+        self.sort_criteria_selector.criteria()
+    }
+
+    /// Describes the state of the controls, along with the given processes and the metrics collected for them
+    ///
+    /// # Arguments
+    ///  * `processes`: The processes to describe, which may include the processes hidden from the user
+    pub fn state_dump<'a>(&'a self, processes: &'a [ProcessMetadata]) -> StateDump<'a> {
+        StateDump {
+            processes,
+            collectors: self.collectors.as_slice(),
+            current_collector: self.collectors.current().name(),
+            hidden_collectors: self.collectors.hidden_names(),
+            span: self.to_span(),
+            following: self.rendering_span.is_following(),
+            selected_pid: self.process_selector.selected_process().map(|pm| pm.pid()),
+            ordering: self.sort_criteria_selector.applied(),
+        }
+    }
+
+    /// Returns the collector whose metrics are compared to sort processes by the applied criteria
+    pub fn ordering_collector(&self) -> &dyn MetricCollector {
+        ordering_collector(&self.collectors, self.sort_criteria_selector.applied())
+    }
+}
+
+/// Returns the collector whose metrics are compared to sort processes by the given criteria
+fn ordering_collector(collectors: &Collectors, criteria: ProcessOrdering) -> &dyn MetricCollector {
+    match criteria {
+        ProcessOrdering::Metric(name) => collectors.by_name(&name).unwrap_or(collectors.current()),
+        _ => collectors.current(),
+    }
+}
+
+/// Returns the duration represented by `columns` columns of a chart, `width` columns wide, displaying `span_duration`
+fn drag_offset(span_duration: Duration, columns: u16, width: u16) -> Duration {
+    if width == 0 {
+        return Duration::ZERO;
+    }
+
+    span_duration * columns as u32 / width as u32
+}
+
+#[cfg(test)]
+mod test_controls {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::core::collection::{MetricCollector, ProbeCollector};
+    use crate::core::ordering::ProcessOrdering;
+    use crate::core::probe::fakes::FakeProbe;
+    use crate::core::process::ProcessMetadata;
+    use crate::core::signal::ProcessSignal;
+    use crate::core::sources::SourcesCollector;
+    use crate::core::time::test_utils::{
+        advance_time_and_refresh_timestamp, setup_fake_clock_to_prevent_substract_overflow,
+    };
+    use crate::core::time::Timestamp;
+    use crate::ctrl::preset::ViewPreset;
+    use crate::ctrl::{Controls, Effect, MouseTarget, State};
+    use crate::triggers::{Input, MouseEvent};
+
+    #[fixture]
+    fn controls() -> Controls {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let collector = ProbeCollector::new(FakeProbe::from_percent_map(hashmap!(1 => 10.)));
+
+        Controls::new(vec![Box::new(collector)], Duration::from_secs(60), None)
+    }
+
+    #[rstest]
+    fn test_should_freeze_view_while_metrics_are_collected(mut controls: Controls) {
+        let frozen_span = controls.to_span();
+
+        controls.interpret_input(Input::F);
+        advance_time_and_refresh_timestamp(Duration::from_secs(10));
+        controls.refresh_span();
+        controls.collectors_as_mut_slice()[0].collect(&[1]).unwrap();
+
+        assert!(controls.is_view_frozen());
+        assert_eq!(controls.to_span().end(), frozen_span.end());
+        assert_eq!(
+            controls
+                .current_collector()
+                .overview()
+                .last_or_default(1)
+                .as_f64(0)
+                .unwrap(),
+            10.
+        );
+    }
+
+    #[rstest]
+    fn test_should_follow_current_time_when_view_is_unfrozen(mut controls: Controls) {
+        controls.interpret_input(Input::F);
+        advance_time_and_refresh_timestamp(Duration::from_secs(10));
+        controls.refresh_span();
+
+        controls.interpret_input(Input::F);
+
+        assert!(!controls.is_view_frozen());
+        assert_eq!(controls.to_span().end(), Timestamp::now());
+    }
+
+    #[rstest]
+    fn test_sorting_prompt_should_start_on_applied_criteria(mut controls: Controls) {
+        controls.interpret_input(Input::S);
+        controls.interpret_input(Input::Down);
+        controls.interpret_input(Input::Submit);
+
+        controls.interpret_input(Input::S);
+        controls.interpret_input(Input::Down);
+        controls.interpret_input(Input::Escape);
+        controls.interpret_input(Input::S);
+
+        assert!(matches!(
+            controls.state(),
+            State::SortingPrompt(ProcessOrdering::Metric(name)) if name == "fake"
+        ));
+    }
+
+    #[rstest]
+    fn test_should_toggle_summary(mut controls: Controls) {
+        controls.interpret_input(Input::A);
+        assert!(matches!(controls.state(), State::Summary));
+
+        controls.interpret_input(Input::Down); // Ignored while the summary is displayed
+        controls.interpret_input(Input::A);
+        assert!(matches!(controls.state(), State::Spv));
+
+        controls.interpret_input(Input::A);
+        controls.interpret_input(Input::Escape);
+        assert!(matches!(controls.state(), State::Spv));
+    }
+
+    #[rstest]
+    fn test_should_toggle_filesystems(mut controls: Controls) {
+        controls.interpret_input(Input::M);
+        assert!(matches!(controls.state(), State::Filesystems));
+
+        controls.interpret_input(Input::Down); // Ignored while the filesystems are displayed
+        controls.interpret_input(Input::M);
+        assert!(matches!(controls.state(), State::Spv));
+
+        controls.interpret_input(Input::M);
+        controls.interpret_input(Input::Escape);
+        assert!(matches!(controls.state(), State::Spv));
+    }
+
+    #[rstest]
+    fn test_should_toggle_units(mut controls: Controls) {
+        controls.interpret_input(Input::ShiftU);
+        assert!(matches!(controls.state(), State::Units));
+
+        controls.interpret_input(Input::Down); // Ignored while the units are displayed
+        controls.interpret_input(Input::ShiftU);
+        assert!(matches!(controls.state(), State::Spv));
+
+        controls.interpret_input(Input::ShiftU);
+        controls.interpret_input(Input::Escape);
+        assert!(matches!(controls.state(), State::Spv));
+    }
+
+    #[rstest]
+    fn test_should_fit_span_to_metrics_of_selected_process(mut controls: Controls) {
+        controls.set_processes(vec![ProcessMetadata::new(1, "cmd", Timestamp::now())]);
+        advance_time_and_refresh_timestamp(Duration::from_secs(100));
+        let first_metric = Timestamp::now();
+        controls.collectors_as_mut_slice()[0].collect(&[1]).unwrap();
+        advance_time_and_refresh_timestamp(Duration::from_secs(200));
+
+        controls.interpret_input(Input::Z);
+
+        assert_eq!(controls.to_span().begin(), first_metric);
+        assert_eq!(controls.to_span().end(), Timestamp::now());
+    }
+
+    #[rstest]
+    fn test_should_not_fit_span_to_process_without_metrics(mut controls: Controls) {
+        controls.set_processes(vec![ProcessMetadata::new(2, "cmd", Timestamp::now())]);
+        let span = controls.to_span();
+
+        controls.interpret_input(Input::Z);
+
+        assert_eq!(controls.to_span(), span);
+    }
+
+    #[rstest]
+    fn test_should_open_details_of_selected_process_on_submit(mut controls: Controls) {
+        controls.set_processes(vec![ProcessMetadata::new(1, "cmd", Timestamp::now())]);
+
+        controls.interpret_input(Input::Submit);
+
+        assert!(matches!(controls.state(), State::ProcessDetail));
+    }
+
+    #[rstest]
+    fn test_should_toggle_sorting_by_span_average(mut controls: Controls) {
+        assert_eq!(controls.averaged_span(), None);
+
+        assert_eq!(controls.interpret_input(Input::V), Effect::SpanAverageSorting(true));
+        assert_eq!(controls.averaged_span(), Some(controls.to_span()));
+
+        assert_eq!(controls.interpret_input(Input::V), Effect::SpanAverageSorting(false));
+        assert_eq!(controls.averaged_span(), None);
+    }
+
+    #[rstest]
+    fn test_should_keep_collected_metrics_when_uptime_filter_changes(mut controls: Controls) {
+        controls.collectors_as_mut_slice()[0].collect(&[1]).unwrap();
+        controls.set_processes(vec![ProcessMetadata::new(1, "cmd", Timestamp::now())]);
+
+        controls.set_min_uptime(Some(Duration::from_secs(10)));
+        controls.set_processes(vec![ProcessMetadata::new(1, "cmd", Timestamp::now())]);
+
+        assert!(controls.to_processes_view().as_slice().is_empty());
+        assert!(controls.current_collector().last_metric(1).is_some());
+    }
+
+    #[rstest]
+    fn test_should_not_open_details_without_selected_process(mut controls: Controls) {
+        controls.interpret_input(Input::Submit);
+
+        assert!(matches!(controls.state(), State::Spv));
+    }
+
+    #[rstest]
+    fn test_should_close_process_details_on_escape(mut controls: Controls) {
+        controls.set_processes(vec![
+            ProcessMetadata::new(1, "cmd", Timestamp::now()),
+            ProcessMetadata::new(2, "cmd", Timestamp::now()),
+        ]);
+        controls.interpret_input(Input::Submit);
+
+        controls.interpret_input(Input::Down); // Ignored while the details are displayed
+        controls.interpret_input(Input::Escape);
+
+        assert!(matches!(controls.state(), State::Spv));
+        assert_eq!(
+            controls.to_processes_view().selected_process().map(|pm| pm.pid()),
+            Some(1)
+        );
+    }
+
+    #[rstest]
+    #[case(Input::Y)]
+    #[case(Input::Submit)]
+    fn test_should_quit_when_confirmed(mut controls: Controls, #[case] input: Input) {
+        controls.interpret_input(Input::Q);
+        assert!(matches!(controls.state(), State::QuitConfirmation));
+
+        assert_eq!(controls.interpret_input(input), Effect::QuitConfirmed);
+    }
+
+    #[rstest]
+    #[case(Input::Q)]
+    #[case(Input::Escape)]
+    #[case(Input::Down)]
+    fn test_should_cancel_quitting_on_other_inputs(mut controls: Controls, #[case] input: Input) {
+        controls.interpret_input(Input::Q);
+
+        assert_eq!(controls.interpret_input(input), Effect::None);
+        assert!(matches!(controls.state(), State::Spv));
+    }
+
+    #[rstest]
+    fn test_should_restore_previous_state_when_quitting_is_cancelled(mut controls: Controls) {
+        controls.interpret_input(Input::A);
+        controls.interpret_input(Input::Q);
+
+        controls.interpret_input(Input::Escape);
+
+        assert!(matches!(controls.state(), State::Summary));
+    }
+
+    #[rstest]
+    fn test_should_dismiss_permission_warning(mut controls: Controls) {
+        controls.warn_about_permissions();
+        assert!(matches!(controls.state(), State::PermissionWarning));
+
+        controls.interpret_input(Input::Right); // Ignored while the warning is displayed
+        controls.interpret_input(Input::Escape);
+
+        assert!(matches!(controls.state(), State::Spv));
+    }
+
+    #[rstest]
+    fn test_should_not_warn_about_permissions_over_sorting_prompt(mut controls: Controls) {
+        controls.interpret_input(Input::S);
+
+        controls.warn_about_permissions();
+
+        assert!(matches!(controls.state(), State::SortingPrompt(_)));
+    }
+
+    #[rstest]
+    fn test_should_toggle_chart_only_display(mut controls: Controls) {
+        assert!(!controls.is_chart_only());
+
+        controls.interpret_input(Input::C);
+        assert!(controls.is_chart_only());
+
+        controls.interpret_input(Input::C);
+        assert!(!controls.is_chart_only());
+    }
+
+    #[rstest]
+    fn test_should_toggle_histogram_mode(mut controls: Controls) {
+        assert!(!controls.is_histogram_mode());
+
+        controls.interpret_input(Input::B);
+        assert!(controls.is_histogram_mode());
+
+        controls.interpret_input(Input::B);
+        assert!(!controls.is_histogram_mode());
+    }
+
+    #[test]
+    fn test_should_cycle_source_of_current_collector() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let sources: Vec<(&'static str, Box<dyn MetricCollector>)> = vec![
+            (
+                "bytes",
+                Box::new(ProbeCollector::new(FakeProbe::from_percent_map(hashmap!(1 => 10.)))),
+            ),
+            (
+                "operations",
+                Box::new(ProbeCollector::new(FakeProbe::from_percent_map(hashmap!(1 => 20.)))),
+            ),
+        ];
+        let collector = SourcesCollector::new("disk", sources);
+        let mut controls = Controls::new(vec![Box::new(collector)], Duration::from_secs(60), None);
+        controls.collectors_as_mut_slice()[0].collect(&[1]).unwrap();
+
+        controls.interpret_input(Input::I);
+
+        assert_eq!(controls.to_collectors_view().selected_source(), Some("operations"));
+        assert_eq!(
+            controls
+                .current_collector()
+                .overview()
+                .last_or_default(1)
+                .as_f64(0)
+                .unwrap(),
+            20.
+        );
+    }
+
+    #[rstest]
+    fn test_should_request_state_dump(mut controls: Controls) {
+        assert_eq!(controls.interpret_input(Input::D), Effect::StateDumpRequested);
+    }
+
+    #[rstest]
+    #[case(false, ProcessSignal::Stop)]
+    #[case(true, ProcessSignal::Continue)]
+    fn test_should_request_signal_toggling_selected_process(
+        mut controls: Controls,
+        #[case] stopped: bool,
+        #[case] expected: ProcessSignal,
+    ) {
+        let mut process = ProcessMetadata::new(1, "cmd", Timestamp::now());
+        process.set_stopped(stopped);
+        controls.set_processes(vec![process]);
+
+        assert_eq!(controls.interpret_input(Input::P), Effect::SignalRequested(expected));
+    }
+
+    #[rstest]
+    fn test_should_not_request_signal_without_selected_process(mut controls: Controls) {
+        assert_eq!(controls.interpret_input(Input::P), Effect::None);
+    }
+
+    #[rstest]
+    fn test_should_request_clearing_metrics_of_selected_process(mut controls: Controls) {
+        assert_eq!(controls.interpret_input(Input::Delete), Effect::None);
+
+        controls.set_processes(vec![ProcessMetadata::new(1, "cmd", Timestamp::now())]);
+
+        assert_eq!(
+            controls.interpret_input(Input::Delete),
+            Effect::MetricsClearRequested(1)
+        );
+    }
+
+    #[rstest]
+    fn test_should_request_command_on_selected_process(mut controls: Controls) {
+        assert_eq!(controls.interpret_input(Input::E), Effect::None);
+
+        controls.set_processes(vec![ProcessMetadata::new(1, "cmd", Timestamp::now())]);
+
+        assert_eq!(controls.interpret_input(Input::E), Effect::CommandRequested);
+    }
+
+    #[test]
+    fn test_should_hide_and_restore_tabs_while_collecting_all_metrics() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let collectors: Vec<Box<dyn MetricCollector>> = vec![
+            Box::new(ProbeCollector::new(FakeProbe::from_percent_map(hashmap!(1 => 10.)))),
+            Box::new(ProbeCollector::new(FakeProbe::from_percent_map(hashmap!(1 => 20.)))),
+        ];
+        let mut controls = Controls::new(collectors, Duration::from_secs(60), None);
+
+        controls.interpret_input(Input::X);
+        assert_eq!(controls.to_collectors_view().collectors_names().len(), 1);
+        assert_eq!(controls.collectors_as_slice().len(), 2);
+
+        controls.interpret_input(Input::R);
+        assert_eq!(controls.to_collectors_view().collectors_names().len(), 2);
+        assert_eq!(controls.to_collectors_view().selected_index(), 1);
+    }
+
+    #[rstest]
+    fn test_should_select_pressed_process_row(mut controls: Controls) {
+        controls.set_processes(vec![
+            ProcessMetadata::new(1, "cmd_1", Timestamp::now()),
+            ProcessMetadata::new(2, "cmd_2", Timestamp::now()),
+        ]);
+
+        controls.interpret_mouse(MouseEvent::Press(5, 10), MouseTarget::ProcessRow(1));
+
+        assert_eq!(
+            controls.to_processes_view().selected_process().map(|pm| pm.pid()),
+            Some(2)
+        );
+    }
+
+    #[rstest]
+    fn test_dragging_chart_right_should_scroll_to_older_metrics(mut controls: Controls) {
+        advance_time_and_refresh_timestamp(Duration::from_secs(120));
+        controls.refresh_span();
+        let chart = |column| MouseTarget::Chart { column, width: 60 };
+
+        controls.interpret_mouse(MouseEvent::Press(40, 10), chart(10));
+        controls.interpret_mouse(MouseEvent::Drag(45, 10), chart(15));
+        controls.interpret_mouse(MouseEvent::Release(45, 10), chart(15));
+
+        // The chart is 60 columns wide and covers 60 seconds: each column represents a second
+        assert_eq!(controls.to_span().end(), Timestamp::now() - Duration::from_secs(5));
+        assert!(controls.is_view_frozen());
+    }
+
+    #[rstest]
+    fn test_dragging_newest_left_chart_left_should_scroll_to_older_metrics(controls: Controls) {
+        let mut controls = controls.with_newest_left_chart(true);
+        advance_time_and_refresh_timestamp(Duration::from_secs(120));
+        controls.refresh_span();
+        let chart = |column| MouseTarget::Chart { column, width: 60 };
+
+        controls.interpret_mouse(MouseEvent::Press(45, 10), chart(15));
+        controls.interpret_mouse(MouseEvent::Drag(40, 10), chart(10));
+        controls.interpret_mouse(MouseEvent::Release(40, 10), chart(10));
+
+        assert_eq!(controls.to_span().end(), Timestamp::now() - Duration::from_secs(5));
+    }
+
+    #[rstest]
+    fn test_should_not_scroll_chart_when_dragging_without_pressing_it(mut controls: Controls) {
+        advance_time_and_refresh_timestamp(Duration::from_secs(120));
+        controls.refresh_span();
+
+        controls.interpret_mouse(MouseEvent::Drag(45, 10), MouseTarget::Chart { column: 15, width: 60 });
+
+        assert_eq!(controls.to_span().end(), Timestamp::now());
+    }
+
+    #[rstest]
+    fn test_undo_should_do_nothing_without_view_change(mut controls: Controls) {
+        let span = controls.to_span();
+
+        controls.interpret_input(Input::Backspace);
+
+        assert_eq!(controls.to_span(), span);
+        assert!(!controls.is_view_frozen());
+    }
+
+    #[rstest]
+    fn test_should_undo_view_changes_through_several_steps(mut controls: Controls) {
+        advance_time_and_refresh_timestamp(Duration::from_secs(120));
+        controls.refresh_span();
+        let initial_span = controls.to_span();
+
+        controls.interpret_input(Input::AltLeft);
+        let scrolled_span = controls.to_span();
+        controls.interpret_input(Input::AltDown);
+
+        controls.interpret_input(Input::Backspace);
+        assert_eq!(controls.to_span(), scrolled_span);
+
+        controls.interpret_input(Input::Backspace);
+        assert_eq!(controls.to_span(), initial_span);
+        assert!(!controls.is_view_frozen());
+
+        controls.interpret_input(Input::Backspace);
+        assert_eq!(controls.to_span(), initial_span);
+    }
+
+    #[rstest]
+    fn test_should_undo_process_selection(mut controls: Controls) {
+        controls.set_processes(vec![
+            ProcessMetadata::new(1, "cmd", Timestamp::now()),
+            ProcessMetadata::new(2, "cmd", Timestamp::now()),
+        ]);
+
+        controls.interpret_input(Input::Down);
+        controls.interpret_input(Input::Backspace);
+
+        assert_eq!(
+            controls.to_processes_view().selected_process().map(|pm| pm.pid()),
+            Some(1)
+        );
+    }
+
+    #[rstest]
+    fn test_should_not_save_inputs_which_do_not_change_view(mut controls: Controls) {
+        controls.set_processes(vec![
+            ProcessMetadata::new(1, "cmd", Timestamp::now()),
+            ProcessMetadata::new(2, "cmd", Timestamp::now()),
+        ]);
+
+        controls.interpret_input(Input::Down);
+        controls.interpret_input(Input::Down); // The last process is already selected
+        controls.interpret_input(Input::Backspace);
+
+        assert_eq!(
+            controls.to_processes_view().selected_process().map(|pm| pm.pid()),
+            Some(1)
+        );
+    }
+
+    #[rstest]
+    fn test_should_undo_whole_drag_of_chart_at_once(mut controls: Controls) {
+        advance_time_and_refresh_timestamp(Duration::from_secs(120));
+        controls.refresh_span();
+        let initial_span = controls.to_span();
+        let chart = |column| MouseTarget::Chart { column, width: 60 };
+
+        controls.interpret_mouse(MouseEvent::Press(40, 10), chart(10));
+        controls.interpret_mouse(MouseEvent::Drag(45, 10), chart(15));
+        controls.interpret_mouse(MouseEvent::Drag(50, 10), chart(20));
+        controls.interpret_mouse(MouseEvent::Release(50, 10), chart(20));
+        controls.interpret_input(Input::Backspace);
+
+        assert_eq!(controls.to_span(), initial_span);
+    }
+
+    fn named_collectors_controls() -> Controls {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let collectors: Vec<Box<dyn MetricCollector>> = ["cpu", "memory", "disk"]
+            .into_iter()
+            .map(|name| {
+                let probe = FakeProbe::from_percent_map(hashmap!(1 => 10.)).with_name(name);
+                Box::new(ProbeCollector::new(probe)) as Box<dyn MetricCollector>
+            })
+            .collect();
+
+        Controls::new(collectors, Duration::from_secs(60), None)
+    }
+
+    #[test]
+    fn test_should_capture_view_as_preset() {
+        let mut controls = named_collectors_controls();
+        controls.interpret_input(Input::Right);
+        controls.interpret_input(Input::S);
+        controls.interpret_input(Input::Down);
+        controls.interpret_input(Input::Submit);
+        controls.interpret_input(Input::AltDown);
+        controls.interpret_input(Input::U);
+        controls.interpret_input(Input::B);
+
+        let preset = controls.preset();
+
+        assert_eq!(preset.collector, "memory");
+        assert_eq!(preset.ordering, "metric cpu");
+        assert_eq!(preset.span_duration, Duration::from_secs(120));
+        assert!(preset.uptime_filter);
+        assert!(preset.histogram_mode);
+        assert!(!preset.chart_only);
+    }
+
+    #[test]
+    fn test_should_restore_saved_preset_in_fresh_controls() {
+        let mut controls = named_collectors_controls();
+        controls.interpret_input(Input::Right);
+        controls.interpret_input(Input::Right);
+        controls.interpret_input(Input::X); // Hides the disk tab, and displays the cpu tab
+        controls.interpret_input(Input::S);
+        controls.interpret_input(Input::Down);
+        controls.interpret_input(Input::Down);
+        controls.interpret_input(Input::Submit);
+        controls.interpret_input(Input::AltUp);
+        controls.interpret_input(Input::N);
+        controls.interpret_input(Input::T);
+        controls.interpret_input(Input::C);
+        let saved: ViewPreset = controls.preset().to_string().parse().unwrap();
+
+        let mut fresh_controls = named_collectors_controls();
+        fresh_controls.apply_preset(&saved);
+
+        assert_eq!(fresh_controls.preset(), controls.preset());
+        assert_eq!(fresh_controls.current_collector().name(), "cpu");
+        assert_eq!(
+            fresh_controls.to_collectors_view().collectors_names(),
+            &["cpu", "memory"]
+        );
+        assert_eq!(
+            fresh_controls.process_ordering_criteria(),
+            ProcessOrdering::Metric("memory".to_string())
+        );
+        assert_eq!(fresh_controls.to_span().duration(), Duration::from_secs(30));
+        assert!(fresh_controls.is_chart_only());
+    }
+
+    #[test]
+    fn test_should_ignore_collectors_of_preset_which_are_not_enabled() {
+        let mut controls = named_collectors_controls();
+        let mut preset = controls.preset();
+        preset.collector = "gpu".to_string();
+        preset.ordering = "metric gpu".to_string();
+        preset.hidden_collectors = vec!["gpu".to_string()];
+
+        controls.apply_preset(&preset);
+
+        assert_eq!(controls.current_collector().name(), "cpu");
+        assert_eq!(controls.process_ordering_criteria(), ProcessOrdering::CurrentMetric);
+        assert_eq!(controls.to_collectors_view().collectors_names().len(), 3);
+    }
+
+    #[rstest]
+    fn test_should_request_saving_preset_under_typed_name(mut controls: Controls) {
+        controls.interpret_input(Input::W);
+        assert!(controls.is_typing());
+
+        "cpuu".chars().for_each(|c| {
+            controls.interpret_input(Input::Char(c));
+        });
+        controls.interpret_input(Input::Backspace);
+        controls.interpret_input(Input::Char('/'));
+
+        assert_eq!(
+            controls.interpret_input(Input::Submit),
+            Effect::PresetSaveRequested("cpu".to_string())
+        );
+        assert!(matches!(controls.state(), State::Spv));
+        assert!(!controls.is_typing());
+    }
+
+    #[rstest]
+    fn test_should_not_save_preset_without_name(mut controls: Controls) {
+        controls.interpret_input(Input::W);
+
+        assert_eq!(controls.interpret_input(Input::Submit), Effect::None);
+        assert!(matches!(controls.state(), State::PresetNaming));
+    }
+
+    #[rstest]
+    fn test_should_request_loading_selected_preset(mut controls: Controls) {
+        assert_eq!(controls.interpret_input(Input::O), Effect::PresetSelectionRequested);
+
+        controls.prompt_preset_selection(vec!["cpu".to_string(), "memory".to_string()]);
+        controls.interpret_input(Input::Down);
+
+        assert_eq!(
+            controls.interpret_input(Input::Submit),
+            Effect::PresetLoadRequested("memory".to_string())
+        );
+        assert!(matches!(controls.state(), State::Spv));
+    }
+
+    #[rstest]
+    fn test_should_close_preset_selection_without_saved_preset(mut controls: Controls) {
+        controls.prompt_preset_selection(vec![]);
+
+        assert_eq!(controls.interpret_input(Input::Submit), Effect::None);
+        assert!(matches!(controls.state(), State::Spv));
+    }
+
+    #[test]
+    fn test_should_sort_by_metrics_of_chosen_collector_regardless_of_displayed_one() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let collectors: Vec<Box<dyn MetricCollector>> = vec![
+            Box::new(ProbeCollector::new(FakeProbe::from_percent_map(hashmap!(1 => 10.)))),
+            Box::new(ProbeCollector::new(FakeProbe::from_percent_map(hashmap!(1 => 20.)))),
+        ];
+        let mut controls = Controls::new(collectors, Duration::from_secs(60), None);
+        // Only the first collector has metrics, to tell it apart from the second one which has the same name
+        controls.collectors_as_mut_slice()[0].collect(&[1]).unwrap();
+
+        controls.interpret_input(Input::S);
+        controls.interpret_input(Input::Down);
+        controls.interpret_input(Input::Submit);
+        controls.interpret_input(Input::Right);
+
+        let ordering_collector = controls.ordering_collector();
+        assert_eq!(ordering_collector.overview().last_or_default(1).as_f64(0).unwrap(), 10.);
+    }
 }
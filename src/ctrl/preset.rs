@@ -0,0 +1,450 @@
+//! Named presets of the view, which the user saves and recalls to switch between investigations
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::core::ordering::ProcessOrdering;
+use crate::core::process::ProcessIdentifier;
+use crate::ctrl::processes::IsolationFilter;
+
+/// The extension of the files in which presets are stored
+const PRESET_EXTENSION: &str = "preset";
+
+#[derive(Error, Debug)]
+pub enum PresetError {
+    #[error("Invalid preset name {0:?}")]
+    InvalidName(String),
+    #[error("Invalid line in preset: {0:?}")]
+    InvalidLine(String),
+    #[error("Invalid value {1:?} for {0} in preset")]
+    InvalidValue(&'static str, String),
+    #[error("No directory to store presets in, as neither XDG_CONFIG_HOME nor HOME is set")]
+    NoPresetsDirectory,
+    #[error(transparent)]
+    IOError(#[from] io::Error),
+}
+
+/// The configuration of the view, restored as a whole when recalling the preset
+///
+/// Collectors are referred to by name, so that a preset saved with other probes enabled can still be applied: the
+/// collectors which are not enabled are ignored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViewPreset {
+    /// The name of the displayed collector
+    pub collector: String,
+    /// The criteria on which processes are sorted, as formatted by [`ordering_repr`]
+    pub ordering: String,
+    pub span_duration: Duration,
+    /// The names of the hidden collectors
+    pub hidden_collectors: Vec<String>,
+    pub uptime_filter: bool,
+    pub isolation_filter: IsolationFilter,
+    pub identifier: ProcessIdentifier,
+    pub chart_only: bool,
+    pub histogram_mode: bool,
+}
+
+/// Formats the given criteria so that it identifies the same criteria across runs of spv
+pub fn ordering_repr(ordering: &ProcessOrdering) -> String {
+    match ordering {
+        ProcessOrdering::CurrentMetric => "current metric".to_string(),
+        ProcessOrdering::Metric(name) => format!("metric {}", name),
+        ProcessOrdering::Pid => "pid".to_string(),
+        ProcessOrdering::Command => "command".to_string(),
+    }
+}
+
+impl Display for ViewPreset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "tab: {}", self.collector)?;
+        writeln!(f, "sort: {}", self.ordering)?;
+        writeln!(f, "span: {}s", self.span_duration.as_secs())?;
+        writeln!(f, "hidden tabs: {}", self.hidden_collectors.join(", "))?;
+        writeln!(f, "uptime filter: {}", self.uptime_filter)?;
+        writeln!(f, "isolation filter: {}", isolation_filter_repr(self.isolation_filter))?;
+        writeln!(f, "identifier: {}", self.identifier)?;
+        writeln!(f, "chart only: {}", self.chart_only)?;
+        writeln!(f, "histogram: {}", self.histogram_mode)
+    }
+}
+
+impl FromStr for ViewPreset {
+    type Err = PresetError;
+
+    /// Parses a preset formatted by its `Display` implementation
+    ///
+    /// The settings missing from the preset keep their default value, and unknown settings are ignored, so that
+    /// presets remain usable across versions of spv.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut preset = ViewPreset {
+            collector: String::new(),
+            ordering: ordering_repr(&ProcessOrdering::CurrentMetric),
+            span_duration: Duration::from_secs(60),
+            hidden_collectors: vec![],
+            uptime_filter: false,
+            isolation_filter: IsolationFilter::All,
+            identifier: ProcessIdentifier::Pid,
+            chart_only: false,
+            histogram_mode: false,
+        };
+
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| PresetError::InvalidLine(line.to_string()))?;
+            let value = value.trim();
+
+            match key.trim() {
+                "tab" => preset.collector = value.to_string(),
+                "sort" => preset.ordering = value.to_string(),
+                "span" => preset.span_duration = parse_span(value)?,
+                "hidden tabs" => {
+                    preset.hidden_collectors = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .map(String::from)
+                        .collect()
+                }
+                "uptime filter" => preset.uptime_filter = parse_bool("uptime filter", value)?,
+                "isolation filter" => preset.isolation_filter = parse_isolation_filter(value)?,
+                "identifier" => preset.identifier = parse_identifier(value)?,
+                "chart only" => preset.chart_only = parse_bool("chart only", value)?,
+                "histogram" => preset.histogram_mode = parse_bool("histogram", value)?,
+                _ => {}
+            }
+        }
+
+        Ok(preset)
+    }
+}
+
+fn isolation_filter_repr(filter: IsolationFilter) -> &'static str {
+    match filter {
+        IsolationFilter::All => "all",
+        IsolationFilter::HostOnly => "host",
+        IsolationFilter::ContainersOnly => "containers",
+    }
+}
+
+fn parse_isolation_filter(repr: &str) -> Result<IsolationFilter, PresetError> {
+    match repr {
+        "all" => Ok(IsolationFilter::All),
+        "host" => Ok(IsolationFilter::HostOnly),
+        "containers" => Ok(IsolationFilter::ContainersOnly),
+        _ => Err(PresetError::InvalidValue("isolation filter", repr.to_string())),
+    }
+}
+
+fn parse_identifier(repr: &str) -> Result<ProcessIdentifier, PresetError> {
+    match repr {
+        "PID" => Ok(ProcessIdentifier::Pid),
+        "TGID" => Ok(ProcessIdentifier::Tgid),
+        _ => Err(PresetError::InvalidValue("identifier", repr.to_string())),
+    }
+}
+
+fn parse_span(repr: &str) -> Result<Duration, PresetError> {
+    repr.strip_suffix('s')
+        .and_then(|secs| secs.parse().ok())
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs)
+        .ok_or_else(|| PresetError::InvalidValue("span", repr.to_string()))
+}
+
+fn parse_bool(key: &'static str, repr: &str) -> Result<bool, PresetError> {
+    repr.parse()
+        .map_err(|_| PresetError::InvalidValue(key, repr.to_string()))
+}
+
+/// Indicates if the given name can name a preset, which is stored in a file of the same name
+pub fn is_valid_preset_name(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with('.') && name.chars().all(is_valid_preset_name_char)
+}
+
+/// Indicates if the given character can be typed in the name of a preset
+pub fn is_valid_preset_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ' ')
+}
+
+/// The prompt in which the user types the name of the preset to save, or selects the saved preset to load
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PresetPrompt {
+    typed_name: String,
+    names: Vec<String>,
+    selected_index: usize,
+}
+
+impl PresetPrompt {
+    /// Builds a prompt offering to select one of the presets with the given names
+    pub fn selection(names: Vec<String>) -> Self {
+        Self {
+            names,
+            ..Default::default()
+        }
+    }
+
+    /// Appends the given character to the typed name, unless it can not be part of the name of a preset
+    pub fn type_char(&mut self, c: char) {
+        if is_valid_preset_name_char(c) {
+            self.typed_name.push(c);
+        }
+    }
+
+    /// Removes the last character of the typed name
+    pub fn erase_char(&mut self) {
+        self.typed_name.pop();
+    }
+
+    pub fn typed_name(&self) -> &str {
+        &self.typed_name
+    }
+
+    /// Selects the next preset
+    pub fn next(&mut self) {
+        self.selected_index = (self.selected_index + 1).min(self.names.len().saturating_sub(1));
+    }
+
+    /// Selects the previous preset
+    pub fn previous(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    /// Returns the names of the presets which can be selected
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the index of the selected preset, or `None` if there is no preset to select
+    pub fn selected_index(&self) -> Option<usize> {
+        (self.selected_index < self.names.len()).then_some(self.selected_index)
+    }
+
+    /// Returns the name of the selected preset, or `None` if there is no preset to select
+    pub fn selected_name(&self) -> Option<&str> {
+        self.names.get(self.selected_index).map(String::as_str)
+    }
+}
+
+/// Saves and loads presets, each of which is stored in its own file of a directory
+pub struct PresetStore {
+    directory: Option<PathBuf>,
+}
+
+impl PresetStore {
+    /// # Arguments
+    ///  * `directory`: The directory in which presets are stored, which is created when saving the first preset.
+    ///    Without a directory, no preset can be saved nor loaded.
+    pub fn new(directory: Option<PathBuf>) -> Self {
+        Self { directory }
+    }
+
+    /// Stores the presets in the `spv/presets` directory of the configuration directory of the user
+    pub fn from_env() -> Self {
+        let config_dir = env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+        Self::new(config_dir.map(|dir| dir.join("spv").join("presets")))
+    }
+
+    /// Saves the preset under the given name, replacing the preset previously saved under this name
+    pub fn save(&self, name: &str, preset: &ViewPreset) -> Result<(), PresetError> {
+        let path = self.path_of(name)?;
+        fs::create_dir_all(path.parent().expect("A preset file has no parent directory"))?;
+
+        Ok(fs::write(path, preset.to_string())?)
+    }
+
+    /// Loads the preset saved under the given name
+    pub fn load(&self, name: &str) -> Result<ViewPreset, PresetError> {
+        fs::read_to_string(self.path_of(name)?)?.parse()
+    }
+
+    /// Returns the names of all the saved presets, in alphabetical order
+    pub fn names(&self) -> Result<Vec<String>, PresetError> {
+        let directory = self.directory.as_ref().ok_or(PresetError::NoPresetsDirectory)?;
+
+        let entries = match fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut names = vec![];
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == PRESET_EXTENSION) {
+                names.extend(path.file_stem().and_then(|stem| stem.to_str()).map(String::from));
+            }
+        }
+        names.sort();
+
+        Ok(names)
+    }
+
+    fn path_of(&self, name: &str) -> Result<PathBuf, PresetError> {
+        if !is_valid_preset_name(name) {
+            return Err(PresetError::InvalidName(name.to_string()));
+        }
+        let directory = self.directory.as_ref().ok_or(PresetError::NoPresetsDirectory)?;
+
+        Ok(directory.join(format!("{}.{}", name, PRESET_EXTENSION)))
+    }
+}
+
+#[cfg(test)]
+mod test_view_preset {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::core::ordering::ProcessOrdering;
+    use crate::core::process::ProcessIdentifier;
+    use crate::ctrl::preset::{ordering_repr, ViewPreset};
+    use crate::ctrl::processes::IsolationFilter;
+
+    #[fixture]
+    fn preset() -> ViewPreset {
+        ViewPreset {
+            collector: "Memory usage".to_string(),
+            ordering: ordering_repr(&ProcessOrdering::Metric("CPU usage".to_string())),
+            span_duration: Duration::from_secs(240),
+            hidden_collectors: vec!["Disk usage".to_string(), "Sockets".to_string()],
+            uptime_filter: true,
+            isolation_filter: IsolationFilter::ContainersOnly,
+            identifier: ProcessIdentifier::Tgid,
+            chart_only: false,
+            histogram_mode: true,
+        }
+    }
+
+    #[rstest]
+    fn test_should_serialize_preset_as_readable_text(preset: ViewPreset) {
+        assert_eq!(
+            preset.to_string(),
+            "tab: Memory usage\nsort: metric CPU usage\nspan: 240s\nhidden tabs: Disk usage, Sockets\n\
+             uptime filter: true\nisolation filter: containers\nidentifier: TGID\nchart only: false\nhistogram: true\n"
+        );
+    }
+
+    #[rstest]
+    fn test_should_parse_serialized_preset_back(preset: ViewPreset) {
+        assert_eq!(preset.to_string().parse::<ViewPreset>().unwrap(), preset);
+    }
+
+    #[rstest]
+    fn test_should_default_missing_settings_and_ignore_unknown_ones() {
+        let preset: ViewPreset = "tab: CPU usage\ncolor: blue\n".parse().unwrap();
+
+        assert_eq!(preset.collector, "CPU usage");
+        assert_eq!(preset.ordering, "current metric");
+        assert_eq!(preset.span_duration, Duration::from_secs(60));
+        assert!(preset.hidden_collectors.is_empty());
+    }
+
+    #[rstest]
+    #[case("tab CPU usage")]
+    #[case("span: forever")]
+    #[case("span: 0s")]
+    #[case("uptime filter: maybe")]
+    #[case("isolation filter: vms")]
+    #[case("identifier: UID")]
+    fn test_should_reject_invalid_preset(#[case] repr: &str) {
+        assert!(repr.parse::<ViewPreset>().is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_preset_store {
+    use std::time::Duration;
+
+    use rstest::*;
+    use tempfile::{tempdir, TempDir};
+
+    use crate::core::process::ProcessIdentifier;
+    use crate::ctrl::preset::{PresetError, PresetStore, ViewPreset};
+    use crate::ctrl::processes::IsolationFilter;
+
+    #[fixture]
+    fn directory() -> TempDir {
+        tempdir().unwrap()
+    }
+
+    fn preset(collector: &str) -> ViewPreset {
+        ViewPreset {
+            collector: collector.to_string(),
+            ordering: "pid".to_string(),
+            span_duration: Duration::from_secs(60),
+            hidden_collectors: vec![],
+            uptime_filter: false,
+            isolation_filter: IsolationFilter::All,
+            identifier: ProcessIdentifier::Pid,
+            chart_only: true,
+            histogram_mode: false,
+        }
+    }
+
+    #[rstest]
+    fn test_should_load_saved_preset(directory: TempDir) {
+        let store = PresetStore::new(Some(directory.path().join("presets")));
+
+        store.save("cpu", &preset("CPU usage")).unwrap();
+
+        assert_eq!(store.load("cpu").unwrap(), preset("CPU usage"));
+    }
+
+    #[rstest]
+    fn test_should_replace_preset_saved_under_same_name(directory: TempDir) {
+        let store = PresetStore::new(Some(directory.path().to_path_buf()));
+
+        store.save("investigation", &preset("CPU usage")).unwrap();
+        store.save("investigation", &preset("Memory usage")).unwrap();
+
+        assert_eq!(store.load("investigation").unwrap(), preset("Memory usage"));
+    }
+
+    #[rstest]
+    fn test_should_list_names_of_saved_presets(directory: TempDir) {
+        let store = PresetStore::new(Some(directory.path().to_path_buf()));
+        store.save("memory", &preset("Memory usage")).unwrap();
+        store.save("cpu", &preset("CPU usage")).unwrap();
+        std::fs::write(directory.path().join("notes.txt"), "not a preset").unwrap();
+
+        assert_eq!(store.names().unwrap(), vec!["cpu", "memory"]);
+    }
+
+    #[rstest]
+    fn test_should_list_no_preset_before_first_save(directory: TempDir) {
+        let store = PresetStore::new(Some(directory.path().join("presets")));
+
+        assert!(store.names().unwrap().is_empty());
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("../escape")]
+    #[case(".hidden")]
+    fn test_should_reject_invalid_name(directory: TempDir, #[case] name: &str) {
+        let store = PresetStore::new(Some(directory.path().to_path_buf()));
+
+        assert!(matches!(
+            store.save(name, &preset("CPU usage")),
+            Err(PresetError::InvalidName(_))
+        ));
+    }
+
+    #[rstest]
+    fn test_should_fail_without_directory() {
+        let store = PresetStore::new(None);
+
+        assert!(matches!(store.names(), Err(PresetError::NoPresetsDirectory)));
+    }
+}
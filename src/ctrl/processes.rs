@@ -1,23 +1,299 @@
 //! Manages the selection of the current process
-use crate::core::ordering::{ProcessOrdering, PROCESS_ORDERING_CRITERIA};
-use crate::core::process::{Pid, ProcessMetadata};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use crate::config::OwnProcess;
+use crate::core::ordering::{ordering_criteria, pin_processes, ProcessOrdering};
+use crate::core::process::{Pid, ProcessIdentifier, ProcessMetadata, Status};
+use crate::core::time::Timestamp;
 use crate::core::view::ProcessesView;
 
+/// The uptime under which processes are hidden when the uptime filter is toggled without a configured threshold
+const DEFAULT_MIN_UPTIME: Duration = Duration::from_secs(5);
+
+/// Hides the processes which have been running for less than a given duration
+#[derive(Copy, Clone, Debug)]
+pub struct UptimeFilter {
+    min_uptime: Duration,
+    enabled: bool,
+}
+
+impl UptimeFilter {
+    /// Creates an enabled filter
+    ///
+    /// # Arguments
+    ///  * `min_uptime`: The minimum time a process must have been running to be displayed
+    pub fn new(min_uptime: Duration) -> Self {
+        Self {
+            min_uptime,
+            enabled: true,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    /// Indicates if the filter hides the processes which have not been running for long enough
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Indicates if the given process should be displayed
+    pub fn accepts(&self, process: &ProcessMetadata) -> bool {
+        !self.enabled || uptime(process) >= self.min_uptime
+    }
+}
+
+/// Returns how long the process has been running
+///
+/// The uptime of a dead process is the time during which it ran, so that short-lived processes remain hidden after
+/// they die.
+fn uptime(process: &ProcessMetadata) -> Duration {
+    let running_span = process.running_span();
+    match process.status() {
+        Status::RUNNING => Timestamp::now().duration_since(&running_span.begin()),
+        Status::DEAD => running_span.duration(),
+    }
+}
+
+impl Default for UptimeFilter {
+    fn default() -> Self {
+        Self {
+            min_uptime: DEFAULT_MIN_UPTIME,
+            enabled: false,
+        }
+    }
+}
+
+/// Restricts the displayed processes depending on whether they are isolated in other namespaces, as in containers
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum IsolationFilter {
+    #[default]
+    All,
+    HostOnly,
+    ContainersOnly,
+}
+
+impl IsolationFilter {
+    /// Returns the filter which follows this one, in the order in which the filters are cycled through
+    pub fn next(&self) -> Self {
+        match self {
+            IsolationFilter::All => IsolationFilter::HostOnly,
+            IsolationFilter::HostOnly => IsolationFilter::ContainersOnly,
+            IsolationFilter::ContainersOnly => IsolationFilter::All,
+        }
+    }
+
+    /// Indicates if the given process should be displayed
+    pub fn accepts(&self, process: &ProcessMetadata) -> bool {
+        match self {
+            IsolationFilter::All => true,
+            IsolationFilter::HostOnly => !process.is_isolated(),
+            IsolationFilter::ContainersOnly => process.is_isolated(),
+        }
+    }
+}
+
+/// Keeps the order of processes sorted by their metrics stable while their metrics only vary slightly
+///
+/// A process only moves ahead of the process preceding it once its metric exceeds the metric of this process by more
+/// than the threshold, so that processes with close metrics do not swap positions on each refresh.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SortHysteresis {
+    // The relative difference between two metrics above which processes are reordered
+    threshold: f64,
+}
+
+impl SortHysteresis {
+    /// # Arguments
+    ///  * `threshold_percent`: By how many percent the metric of a process must exceed the metric of the process
+    ///    preceding it for the process to move ahead
+    pub fn new(threshold_percent: f64) -> Self {
+        Self {
+            threshold: threshold_percent / 100.,
+        }
+    }
+
+    /// Orders processes as close as possible to their previous order, only moving the processes whose metric exceeds
+    /// the metric of their predecessor by more than the threshold
+    ///
+    /// # Arguments
+    ///  * `processes`: The processes, sorted by decreasing metrics
+    ///  * `previous`: The processes, in the order in which they were previously sorted
+    ///  * `metric`: Returns the metric of the process with the given PID
+    pub fn stabilize(
+        &self,
+        mut processes: Vec<ProcessMetadata>,
+        previous: &[ProcessMetadata],
+        metric: impl Fn(Pid) -> f64,
+    ) -> Vec<ProcessMetadata> {
+        let previous_positions: HashMap<Pid, usize> =
+            previous.iter().enumerate().map(|(pos, pm)| (pm.pid(), pos)).collect();
+
+        // New processes follow the previous ones, keeping their sorted order as the sort is stable
+        processes.sort_by_key(|pm| previous_positions.get(&pm.pid()).copied().unwrap_or(usize::MAX));
+
+        for idx in 1..processes.len() {
+            let mut pos = idx;
+            while pos > 0 && self.overtakes(&processes[pos], &processes[pos - 1], &metric) {
+                processes.swap(pos, pos - 1);
+                pos -= 1;
+            }
+        }
+
+        processes
+    }
+
+    /// Indicates if `process` should move ahead of `preceding`
+    ///
+    /// As when sorting processes, running processes are always displayed before dead processes.
+    fn overtakes(&self, process: &ProcessMetadata, preceding: &ProcessMetadata, metric: &impl Fn(Pid) -> f64) -> bool {
+        match (process.status(), preceding.status()) {
+            (Status::RUNNING, Status::DEAD) => true,
+            (Status::DEAD, Status::RUNNING) => false,
+            (_, _) => metric(process.pid()) > metric(preceding.pid()) * (1. + self.threshold),
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct ProcessSelector {
+    // All the processes, including those hidden by filters
+    processes: Vec<ProcessMetadata>,
+    // The processes which are displayed, in order
     sorted_processes: Vec<ProcessMetadata>,
     // We have to track the selected process using its Pid and not its index, as the position of the selected process
     // might change in sorted_processes over time
     selected_pid: Option<Pid>,
+    identifier: ProcessIdentifier,
+    recently_spawned: HashSet<Pid>,
+    watched: HashSet<Pid>,
+    // The processes displayed ahead of the others, regardless of how processes are sorted
+    pinned: HashSet<Pid>,
+    uptime_filter: UptimeFilter,
+    isolation_filter: IsolationFilter,
+    sort_hysteresis: Option<SortHysteresis>,
+    // The PID of spv, and whether its own process is shown, hidden or highlighted
+    own_pid: Option<Pid>,
+    own_process: OwnProcess,
 }
 
 impl ProcessSelector {
+    /// Sets the filter hiding the processes which have not been running for long enough
+    pub fn with_uptime_filter(mut self, uptime_filter: UptimeFilter) -> Self {
+        self.uptime_filter = uptime_filter;
+        self
+    }
+
+    /// Sets the hysteresis preventing processes sorted by their metrics from being reordered on slight variations
+    pub fn with_sort_hysteresis(mut self, sort_hysteresis: Option<SortHysteresis>) -> Self {
+        self.sort_hysteresis = sort_hysteresis;
+        self
+    }
+
     /// Sets the processes that the user can selected
-    pub fn set_processes(&mut self, processes: Vec<ProcessMetadata>) {
-        self.sorted_processes = processes;
+    ///
+    /// The processes which do not pass the filters of the selector can not be selected, and the pinned processes are
+    /// moved ahead of the others.
+    pub fn set_processes(&mut self, mut processes: Vec<ProcessMetadata>) {
+        pin_processes(&mut processes, &self.pinned);
+        self.processes = processes;
+        self.refresh_displayed_processes();
+    }
+
+    /// Sets the processes that the user can select, sorted by decreasing metrics
+    ///
+    /// If a sort hysteresis is set, the processes keep their previous order as long as their metrics are close.
+    ///
+    /// # Arguments
+    ///  * `processes`: The processes, sorted by decreasing metrics
+    ///  * `metric`: Returns the value of the metric by which a process is sorted
+    pub fn set_processes_sorted_by_metrics(&mut self, processes: Vec<ProcessMetadata>, metric: impl Fn(Pid) -> f64) {
+        let processes = match self.sort_hysteresis {
+            Some(hysteresis) => hysteresis.stabilize(processes, &self.processes, metric),
+            None => processes,
+        };
+
+        self.set_processes(processes);
+    }
+
+    /// Shows or hides the processes which have been running for less than the minimum uptime
+    pub fn toggle_uptime_filter(&mut self) {
+        self.uptime_filter.toggle();
+        self.refresh_displayed_processes();
+    }
+
+    /// Switches between displaying all processes, those of the host only, and those of containers only
+    pub fn cycle_isolation_filter(&mut self) {
+        self.isolation_filter = self.isolation_filter.next();
+        self.refresh_displayed_processes();
+    }
+
+    /// Indicates if the processes which have been running for less than the minimum uptime are hidden
+    pub fn is_uptime_filter_enabled(&self) -> bool {
+        self.uptime_filter.is_enabled()
+    }
+
+    pub fn isolation_filter(&self) -> IsolationFilter {
+        self.isolation_filter
+    }
+
+    /// Sets the filters restricting the displayed processes, keeping the minimum uptime of the uptime filter
+    pub fn set_filters(&mut self, uptime_filter_enabled: bool, isolation_filter: IsolationFilter) {
+        self.uptime_filter.set_enabled(uptime_filter_enabled);
+        self.isolation_filter = isolation_filter;
+        self.refresh_displayed_processes();
+    }
+
+    fn refresh_displayed_processes(&mut self) {
+        self.sorted_processes = self
+            .processes
+            .iter()
+            .filter(|pm| self.is_displayed(pm))
+            .cloned()
+            .collect();
         self.selected_pid = self.selected_process().map(|pm| pm.pid());
     }
 
+    /// Sets how the process of spv, whose PID is `own_pid`, is listed
+    pub fn set_own_process(&mut self, own_pid: Pid, own_process: OwnProcess) {
+        self.own_pid = Some(own_pid);
+        self.own_process = own_process;
+        self.refresh_displayed_processes();
+    }
+
+    /// Indicates if the process passes all the filters of the selector
+    fn is_displayed(&self, process: &ProcessMetadata) -> bool {
+        let hidden_own_process = self.own_process == OwnProcess::Hide && self.own_pid == Some(process.pid());
+
+        !hidden_own_process && self.uptime_filter.accepts(process) && self.isolation_filter.accepts(process)
+    }
+
+    /// Sets the PIDs of the processes which have recently been spawned
+    pub fn set_recently_spawned(&mut self, recently_spawned: HashSet<Pid>) {
+        self.recently_spawned = recently_spawned;
+    }
+
+    /// Sets the PIDs of the processes whose metrics satisfy the watch expression
+    pub fn set_watched(&mut self, watched: HashSet<Pid>) {
+        self.watched = watched;
+    }
+
+    /// Sets the PIDs of the processes to display ahead of the others, which applies from the next processes being set
+    pub fn set_pinned(&mut self, pinned: HashSet<Pid>) {
+        self.pinned = pinned;
+    }
+
+    /// Selects the process with the given PID, or the first displayed process if it is not displayed
+    pub fn select_pid(&mut self, pid: Option<Pid>) {
+        self.selected_pid = pid;
+    }
+
     pub fn selected_process(&self) -> Option<&ProcessMetadata> {
         self.selected_index().map(|idx| self.sorted_processes.get(idx).unwrap())
     }
@@ -25,7 +301,7 @@ impl ProcessSelector {
     fn selected_index(&self) -> Option<usize> {
         self.selected_pid
             .and_then(|pid| self.find_index_of_process(pid))
-            .or_else(|| self.sorted_processes.get(0).and(Some(0)))
+            .or_else(|| self.sorted_processes.first().and(Some(0)))
     }
 
     pub fn next_process(&mut self) {
@@ -36,6 +312,13 @@ impl ProcessSelector {
         self.set_selected_process_from_index(next_index);
     }
 
+    /// Selects the process displayed at the given index, if there is one
+    pub fn select_process_at(&mut self, index: usize) {
+        if index < self.sorted_processes.len() {
+            self.set_selected_process_from_index(Some(index));
+        }
+    }
+
     pub fn previous_process(&mut self) {
         let prev_index = self.selected_index().map(|idx| idx.saturating_sub(1));
         self.set_selected_process_from_index(prev_index);
@@ -52,16 +335,39 @@ impl ProcessSelector {
         }
     }
 
-    pub fn to_view(&self) -> ProcessesView {
-        ProcessesView::new(&self.sorted_processes, self.selected_index())
+    /// Switches the identifier under which processes are displayed between PID and TGID
+    pub fn toggle_identifier(&mut self) {
+        self.identifier = self.identifier.toggled();
+    }
+
+    pub fn identifier(&self) -> ProcessIdentifier {
+        self.identifier
+    }
+
+    pub fn set_identifier(&mut self, identifier: ProcessIdentifier) {
+        self.identifier = identifier;
+    }
+
+    pub fn to_view(&self) -> ProcessesView<'_> {
+        let view = ProcessesView::new(&self.sorted_processes, self.selected_index(), self.identifier)
+            .with_recently_spawned(&self.recently_spawned)
+            .with_watched(&self.watched);
+
+        match (self.own_process, self.own_pid) {
+            (OwnProcess::Highlight, Some(own_pid)) => view.with_own_pid(own_pid),
+            _ => view,
+        }
     }
 }
 
 #[cfg(test)]
 mod test_processes {
+    use std::collections::HashSet;
+
     use rstest::{fixture, rstest};
 
-    use crate::core::process::ProcessMetadata;
+    use crate::config::OwnProcess;
+    use crate::core::process::{ProcessIdentifier, ProcessMetadata};
     use crate::core::time::Timestamp;
     use crate::ctrl::processes::ProcessSelector;
 
@@ -81,6 +387,24 @@ mod test_processes {
         assert_eq!(selector.selected_process(), None);
     }
 
+    #[rstest]
+    fn test_should_identify_processes_by_pid_by_default() {
+        let selector = ProcessSelector::default();
+
+        assert_eq!(selector.to_view().identifier(), ProcessIdentifier::Pid);
+    }
+
+    #[rstest]
+    fn test_should_switch_identifier_when_toggled() {
+        let mut selector = ProcessSelector::default();
+
+        selector.toggle_identifier();
+        assert_eq!(selector.to_view().identifier(), ProcessIdentifier::Tgid);
+
+        selector.toggle_identifier();
+        assert_eq!(selector.to_view().identifier(), ProcessIdentifier::Pid);
+    }
+
     #[rstest]
     fn test_should_select_first_process_by_default(processes: Vec<ProcessMetadata>) {
         let mut selector = ProcessSelector::default();
@@ -107,6 +431,17 @@ mod test_processes {
         assert_eq!(selector.selected_index(), Some(processes.len() - 1));
     }
 
+    #[rstest]
+    fn test_should_display_pinned_processes_first(processes: Vec<ProcessMetadata>) {
+        let mut selector = ProcessSelector::default();
+        selector.set_pinned(HashSet::from([3]));
+
+        selector.set_processes(processes);
+
+        let pids: Vec<_> = selector.to_view().as_slice().iter().map(|pm| pm.pid()).collect();
+        assert_eq!(pids, vec![3, 1, 2]);
+    }
+
     #[rstest]
     fn test_should_select_next_process(processes: Vec<ProcessMetadata>) {
         let mut selector = ProcessSelector::default();
@@ -144,6 +479,60 @@ mod test_processes {
         assert_eq!(selector.selected_process(), Some(processes.last().unwrap()));
     }
 
+    #[rstest]
+    fn test_should_select_process_at_index(processes: Vec<ProcessMetadata>) {
+        let mut selector = ProcessSelector::default();
+        selector.set_processes(processes.clone());
+        selector.select_process_at(2);
+
+        assert_eq!(selector.selected_process(), Some(&processes[2]));
+    }
+
+    #[rstest]
+    fn test_should_hide_own_process(processes: Vec<ProcessMetadata>) {
+        let mut selector = ProcessSelector::default();
+        selector.set_own_process(2, OwnProcess::Hide);
+
+        selector.set_processes(processes);
+
+        let pids: Vec<_> = selector.to_view().as_slice().iter().map(|pm| pm.pid()).collect();
+        assert_eq!(pids, vec![1, 3]);
+        assert!(!selector.to_view().is_own_process(2));
+    }
+
+    #[rstest]
+    fn test_should_highlight_own_process(processes: Vec<ProcessMetadata>) {
+        let mut selector = ProcessSelector::default();
+        selector.set_own_process(2, OwnProcess::Highlight);
+
+        selector.set_processes(processes.clone());
+
+        let view = selector.to_view();
+        assert_eq!(view.as_slice(), &processes);
+        assert!(view.is_own_process(2));
+        assert!(!view.is_own_process(1));
+    }
+
+    #[rstest]
+    fn test_should_show_own_process_as_any_other_by_default(processes: Vec<ProcessMetadata>) {
+        let mut selector = ProcessSelector::default();
+
+        selector.set_processes(processes.clone());
+
+        assert_eq!(selector.to_view().as_slice(), &processes);
+        assert!(!selector.to_view().is_own_process(2));
+    }
+
+    #[rstest]
+    fn test_should_ignore_selection_of_index_without_process(processes: Vec<ProcessMetadata>) {
+        let mut selector = ProcessSelector::default();
+        selector.set_processes(processes.clone());
+        selector.next_process();
+        selector.select_process_at(processes.len());
+
+        assert_eq!(selector.selected_process(), Some(&processes[1]));
+    }
+
     #[rstest]
     fn test_should_keep_track_of_selected_process_on_reorder(mut processes: Vec<ProcessMetadata>) {
         let first_process = processes[0].clone();
@@ -174,17 +563,253 @@ mod test_processes {
     }
 }
 
+#[cfg(test)]
+mod test_uptime_filter {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::core::process::ProcessMetadata;
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::core::time::Timestamp;
+    use crate::ctrl::processes::{ProcessSelector, UptimeFilter};
+
+    fn process_running_for(pid: u32, uptime: Duration) -> ProcessMetadata {
+        ProcessMetadata::new(pid, "cmd", Timestamp::now() - uptime)
+    }
+
+    #[fixture]
+    fn selector() -> ProcessSelector {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let mut selector = ProcessSelector::default().with_uptime_filter(UptimeFilter::new(Duration::from_secs(5)));
+        selector.set_processes(vec![
+            process_running_for(1, Duration::from_secs(60)),
+            process_running_for(2, Duration::from_secs(1)),
+        ]);
+        selector
+    }
+
+    #[rstest]
+    #[case(Duration::from_secs(5), true)]
+    #[case(Duration::from_millis(4999), false)]
+    #[case(Duration::from_secs(60), true)]
+    #[case(Duration::ZERO, false)]
+    fn test_should_only_accept_processes_running_for_min_uptime(#[case] uptime: Duration, #[case] expected: bool) {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let process = ProcessMetadata::new(1, "cmd", Timestamp::now() - uptime);
+        let filter = UptimeFilter::new(Duration::from_secs(5));
+
+        assert_eq!(filter.accepts(&process), expected);
+    }
+
+    #[test]
+    fn test_should_measure_uptime_of_dead_process_until_its_death() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let mut process = ProcessMetadata::new(1, "cmd", Timestamp::now() - Duration::from_secs(60));
+        process.mark_dead(); // The running span of this process was never refreshed: it died right after spawning
+
+        assert!(!UptimeFilter::new(Duration::from_secs(5)).accepts(&process));
+    }
+
+    #[test]
+    fn test_should_accept_all_processes_when_disabled() {
+        let mut filter = UptimeFilter::new(Duration::from_secs(5));
+        filter.toggle();
+
+        assert!(filter.accepts(&ProcessMetadata::new(1, "cmd", Timestamp::now())));
+    }
+
+    #[test]
+    fn test_should_be_disabled_by_default() {
+        assert!(UptimeFilter::default().accepts(&ProcessMetadata::new(1, "cmd", Timestamp::now())));
+    }
+
+    #[rstest]
+    fn test_selector_should_hide_young_processes(selector: ProcessSelector) {
+        let pids: Vec<_> = selector.to_view().as_slice().iter().map(|pm| pm.pid()).collect();
+
+        assert_eq!(pids, vec![1]);
+    }
+
+    #[rstest]
+    fn test_selector_should_show_young_processes_once_filter_is_toggled(mut selector: ProcessSelector) {
+        selector.toggle_uptime_filter();
+
+        let pids: Vec<_> = selector.to_view().as_slice().iter().map(|pm| pm.pid()).collect();
+        assert_eq!(pids, vec![1, 2]);
+    }
+
+    #[rstest]
+    fn test_selector_should_not_select_hidden_process(mut selector: ProcessSelector) {
+        selector.toggle_uptime_filter();
+        selector.next_process();
+        assert_eq!(selector.selected_process().map(|pm| pm.pid()), Some(2));
+
+        selector.toggle_uptime_filter();
+
+        assert_eq!(selector.selected_process().map(|pm| pm.pid()), Some(1));
+    }
+}
+
+#[cfg(test)]
+mod test_isolation_filter {
+    use rstest::*;
+
+    use crate::core::process::ProcessMetadata;
+    use crate::core::time::Timestamp;
+    use crate::ctrl::processes::{IsolationFilter, ProcessSelector};
+
+    fn process(pid: u32, isolated: bool) -> ProcessMetadata {
+        ProcessMetadata::new(pid, "cmd", Timestamp::now()).with_isolation(isolated)
+    }
+
+    fn displayed_pids(selector: &ProcessSelector) -> Vec<u32> {
+        selector.to_view().as_slice().iter().map(|pm| pm.pid()).collect()
+    }
+
+    #[fixture]
+    fn selector() -> ProcessSelector {
+        let mut selector = ProcessSelector::default();
+        selector.set_processes(vec![process(1, false), process(2, true)]);
+        selector
+    }
+
+    #[rstest]
+    #[case(IsolationFilter::All, false, true)]
+    #[case(IsolationFilter::All, true, true)]
+    #[case(IsolationFilter::HostOnly, false, true)]
+    #[case(IsolationFilter::HostOnly, true, false)]
+    #[case(IsolationFilter::ContainersOnly, false, false)]
+    #[case(IsolationFilter::ContainersOnly, true, true)]
+    fn test_should_accept_processes_depending_on_isolation(
+        #[case] filter: IsolationFilter,
+        #[case] isolated: bool,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(filter.accepts(&process(1, isolated)), expected);
+    }
+
+    #[rstest]
+    fn test_selector_should_display_all_processes_by_default(selector: ProcessSelector) {
+        assert_eq!(displayed_pids(&selector), vec![1, 2]);
+    }
+
+    #[rstest]
+    fn test_selector_should_cycle_through_isolation_filters(mut selector: ProcessSelector) {
+        selector.cycle_isolation_filter();
+        assert_eq!(displayed_pids(&selector), vec![1]);
+
+        selector.cycle_isolation_filter();
+        assert_eq!(displayed_pids(&selector), vec![2]);
+
+        selector.cycle_isolation_filter();
+        assert_eq!(displayed_pids(&selector), vec![1, 2]);
+    }
+}
+
+#[cfg(test)]
+mod test_sort_hysteresis {
+    use std::collections::HashMap;
+
+    use rstest::*;
+
+    use crate::core::process::{Pid, ProcessMetadata};
+    use crate::core::time::Timestamp;
+    use crate::ctrl::processes::SortHysteresis;
+
+    fn processes(pids: &[Pid]) -> Vec<ProcessMetadata> {
+        pids.iter()
+            .map(|pid| ProcessMetadata::new(*pid, "cmd", Timestamp::now()))
+            .collect()
+    }
+
+    fn stabilized_pids(previous: &[Pid], sorted: &[Pid], metrics: HashMap<Pid, f64>) -> Vec<Pid> {
+        SortHysteresis::new(10.)
+            .stabilize(processes(sorted), &processes(previous), |pid| metrics[&pid])
+            .iter()
+            .map(|pm| pm.pid())
+            .collect()
+    }
+
+    #[rstest]
+    fn test_should_not_reorder_processes_on_changes_below_threshold() {
+        let metrics = hashmap!(1 => 50., 2 => 54., 3 => 10.);
+
+        assert_eq!(stabilized_pids(&[1, 2, 3], &[2, 1, 3], metrics), vec![1, 2, 3]);
+    }
+
+    #[rstest]
+    fn test_should_reorder_processes_on_changes_above_threshold() {
+        let metrics = hashmap!(1 => 50., 2 => 56., 3 => 10.);
+
+        assert_eq!(stabilized_pids(&[1, 2, 3], &[2, 1, 3], metrics), vec![2, 1, 3]);
+    }
+
+    #[rstest]
+    fn test_should_keep_previous_order_of_processes_with_equal_metrics() {
+        let metrics = hashmap!(1 => 5., 2 => 5., 3 => 5.);
+
+        assert_eq!(stabilized_pids(&[3, 1, 2], &[1, 2, 3], metrics), vec![3, 1, 2]);
+    }
+
+    #[rstest]
+    fn test_should_move_process_ahead_of_all_processes_it_exceeds() {
+        let metrics = hashmap!(1 => 50., 2 => 30., 3 => 80.);
+
+        assert_eq!(stabilized_pids(&[1, 2, 3], &[3, 1, 2], metrics), vec![3, 1, 2]);
+    }
+
+    #[rstest]
+    fn test_should_insert_new_processes_according_to_their_metrics() {
+        let metrics = hashmap!(1 => 50., 2 => 30., 4 => 40.);
+
+        assert_eq!(stabilized_pids(&[1, 2], &[1, 4, 2], metrics), vec![1, 4, 2]);
+    }
+
+    #[rstest]
+    fn test_should_forget_processes_which_are_no_longer_sorted() {
+        let metrics = hashmap!(1 => 50., 3 => 10.);
+
+        assert_eq!(stabilized_pids(&[1, 2, 3], &[1, 3], metrics), vec![1, 3]);
+    }
+
+    #[rstest]
+    fn test_should_move_running_processes_ahead_of_dead_processes() {
+        let mut sorted = processes(&[2, 1]);
+        sorted[1].mark_dead();
+        let metrics = hashmap!(1 => 50., 2 => 10.);
+
+        let stabilized = SortHysteresis::new(10.).stabilize(sorted, &processes(&[1, 2]), |pid| metrics[&pid]);
+
+        let pids: Vec<Pid> = stabilized.iter().map(|pm| pm.pid()).collect();
+        assert_eq!(pids, vec![2, 1]);
+    }
+}
+
 /// Allows the selection of processes sorting criteria
-#[derive(Default)]
 pub struct SortCriteriaSelector {
+    criteria: Vec<ProcessOrdering>,
     selected_index: usize,
     applied_selection: usize,
 }
 
 impl SortCriteriaSelector {
+    /// Builds a selector offering to sort processes by the metrics of each of the given collectors, in addition to the
+    /// criteria which do not depend on collectors
+    ///
+    /// # Arguments
+    ///  * `collector_names`: The names of the active collectors
+    pub fn new(collector_names: &[&str]) -> Self {
+        Self {
+            criteria: ordering_criteria(collector_names),
+            selected_index: 0,
+            applied_selection: 0,
+        }
+    }
+
     /// Select the next criteria
     pub fn next(&mut self) {
-        let max_index = PROCESS_ORDERING_CRITERIA.len() - 1;
+        let max_index = self.criteria.len() - 1;
         self.selected_index = (self.selected_index + 1).min(max_index);
     }
 
@@ -193,9 +818,14 @@ impl SortCriteriaSelector {
         self.selected_index = self.selected_index.saturating_sub(1);
     }
 
+    /// Moves the selection back to the criteria which is currently applied
+    pub fn select_applied(&mut self) {
+        self.selected_index = self.applied_selection;
+    }
+
     /// Returns the criteria which is currently selected, but not necessarily applied
     pub fn selected(&self) -> ProcessOrdering {
-        PROCESS_ORDERING_CRITERIA[self.selected_index]
+        self.criteria[self.selected_index].clone()
     }
 
     /// Applies the selected criteria as the critieria to use to sort processes
@@ -205,52 +835,100 @@ impl SortCriteriaSelector {
 
     /// Returns the criteria which is currently applied, even if it is not selected
     pub fn applied(&self) -> ProcessOrdering {
-        PROCESS_ORDERING_CRITERIA[self.applied_selection]
+        self.criteria[self.applied_selection].clone()
+    }
+
+    /// Returns all the criteria which can be selected
+    pub fn criteria(&self) -> &[ProcessOrdering] {
+        &self.criteria
+    }
+
+    /// Applies the given criteria, if it is one of the criteria which can be selected
+    pub fn apply_criteria(&mut self, criteria: ProcessOrdering) {
+        if let Some(index) = self.criteria.iter().position(|c| *c == criteria) {
+            self.selected_index = index;
+            self.applied_selection = index;
+        }
     }
 }
 
 #[cfg(test)]
 mod test_process_criteria_selector {
-    use crate::core::ordering::PROCESS_ORDERING_CRITERIA;
+    use rstest::*;
+
+    use crate::core::ordering::ProcessOrdering;
     use crate::ctrl::processes::SortCriteriaSelector;
 
-    #[test]
-    fn should_select_first_criteria_by_default() {
-        let selector = SortCriteriaSelector::default();
-        assert_eq!(selector.selected(), PROCESS_ORDERING_CRITERIA[0]);
+    #[fixture]
+    fn selector() -> SortCriteriaSelector {
+        SortCriteriaSelector::new(&["CPU usage", "Disk I/O"])
     }
 
-    #[test]
-    fn should_select_next_process() {
-        let mut selector = SortCriteriaSelector::default();
+    #[rstest]
+    fn should_offer_one_criteria_per_collector(selector: SortCriteriaSelector) {
+        assert_eq!(
+            selector.criteria(),
+            &[
+                ProcessOrdering::CurrentMetric,
+                ProcessOrdering::Metric("CPU usage".to_string()),
+                ProcessOrdering::Metric("Disk I/O".to_string()),
+                ProcessOrdering::Pid,
+                ProcessOrdering::Command,
+            ]
+        );
+    }
+
+    #[rstest]
+    fn should_select_first_criteria_by_default(selector: SortCriteriaSelector) {
+        assert_eq!(selector.selected(), ProcessOrdering::CurrentMetric);
+    }
+
+    #[rstest]
+    fn should_select_next_process(mut selector: SortCriteriaSelector) {
         selector.next();
 
-        assert_eq!(selector.selected(), PROCESS_ORDERING_CRITERIA[1]);
+        assert_eq!(selector.selected(), ProcessOrdering::Metric("CPU usage".to_string()));
     }
 
-    #[test]
-    fn should_select_previous_process() {
-        let mut selector = SortCriteriaSelector::default();
+    #[rstest]
+    fn should_select_previous_process(mut selector: SortCriteriaSelector) {
         selector.next();
         selector.previous();
 
-        assert_eq!(selector.selected(), PROCESS_ORDERING_CRITERIA[0]);
+        assert_eq!(selector.selected(), ProcessOrdering::CurrentMetric);
     }
 
-    #[test]
-    fn should_not_apply_selection_by_default() {
-        let mut selector = SortCriteriaSelector::default();
+    #[rstest]
+    fn should_not_select_after_last_criteria(mut selector: SortCriteriaSelector) {
+        (0..10).for_each(|_| selector.next());
+
+        assert_eq!(selector.selected(), ProcessOrdering::Command);
+    }
+
+    #[rstest]
+    fn should_not_apply_selection_by_default(mut selector: SortCriteriaSelector) {
         selector.next();
 
-        assert_eq!(selector.applied(), PROCESS_ORDERING_CRITERIA[0]);
+        assert_eq!(selector.applied(), ProcessOrdering::CurrentMetric);
     }
 
-    #[test]
-    fn should_return_selected_selection_as_applied_once_the_selection_is_applied() {
-        let mut selector = SortCriteriaSelector::default();
+    #[rstest]
+    fn should_return_selected_selection_as_applied_once_the_selection_is_applied(mut selector: SortCriteriaSelector) {
         selector.next();
         selector.apply();
 
-        assert_eq!(selector.applied(), PROCESS_ORDERING_CRITERIA[1]);
+        assert_eq!(selector.applied(), ProcessOrdering::Metric("CPU usage".to_string()));
+    }
+
+    #[rstest]
+    fn should_move_selection_back_to_applied_criteria(mut selector: SortCriteriaSelector) {
+        selector.next();
+        selector.next();
+        selector.apply();
+        selector.next();
+
+        selector.select_applied();
+
+        assert_eq!(selector.selected(), ProcessOrdering::Metric("Disk I/O".to_string()));
     }
 }
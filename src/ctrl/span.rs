@@ -1,5 +1,4 @@
 //! Manages the configuration of the span of metrics to render
-use log::info;
 use std::cmp::min;
 use std::time::Duration;
 
@@ -15,26 +14,37 @@ const SPAN_UNIT: Duration = Duration::from_secs(15);
 
 const DEFAULT_SPAN_DURATION: Duration = Duration::from_secs(60);
 
+#[derive(Clone, PartialEq, Debug)]
 pub struct RenderingSpan {
     span: Span,
     follow: bool,
     // Span size can be calculated from zoom_level using this formula: 15s * 2^zoom_level
     zoom_level: u32,
+    history: Option<Duration>,
 }
 
 impl RenderingSpan {
     /// # Arguments
     /// - `duration`: Indicates the amount of time that the span covers
     /// - `tolerance`: Tracking time precisely to the nanosecond is difficult.<br/>
-    ///     The tolerance, will loosen the constraints of the span, by shifting its begin to the past.
+    ///   The tolerance, will loosen the constraints of the span, by shifting its begin to the past.
     pub fn new(duration: Duration) -> Self {
         Self {
             span: Span::from_duration(duration),
             follow: true,
             zoom_level: 2,
+            history: None,
         }
     }
 
+    /// Prevents the span from covering metrics older than `history`, as they are not retained
+    ///
+    /// If `history` is `None`, the span can cover any time since the application started.
+    pub fn with_history(mut self, history: Option<Duration>) -> Self {
+        self.history = history;
+        self
+    }
+
     /// Shifts the rendering span so that it ends at the current time
     pub fn follow(&mut self) {
         if self.follow {
@@ -42,11 +52,33 @@ impl RenderingSpan {
             self.set_follow_if_span_is_tracking_current_timestamp();
         }
     }
+
+    /// Indicates if the span should keep tracking the current time
+    ///
+    /// When set to `false`, the span is frozen: it stops advancing, while metrics keep being collected behind it.
+    /// When set to `true`, the span immediately catches up with the current time.
+    pub fn set_follow(&mut self, follow: bool) {
+        match follow {
+            true => self.reset_scroll(),
+            false => self.follow = false,
+        }
+    }
+
+    /// Returns `true` if the span is tracking the current time
+    pub fn is_following(&self) -> bool {
+        self.follow
+    }
+
     /// Updates the span by offseting the `begin` and `end` attributes of the span toward the past
     ///
-    /// The span cannot be scrolled before the first iteration of the program
+    /// The span cannot be scrolled before the first iteration of the program, nor before the retained history
     pub fn scroll_left(&mut self) {
-        self.set_bounded_end_and_shift(self.span.end() - Duration::from_secs(1));
+        self.scroll_left_by(Duration::from_secs(1));
+    }
+
+    /// Same as [`scroll_left`](#method.scroll_left), but offsets the span by the given duration
+    pub fn scroll_left_by(&mut self, offset: Duration) {
+        self.set_bounded_end_and_shift(self.span.end() - offset);
         self.set_follow_if_span_is_tracking_current_timestamp();
     }
 
@@ -54,7 +86,12 @@ impl RenderingSpan {
     ///
     /// The span cannot be scrolled after the current timestamp.
     pub fn scroll_right(&mut self) {
-        self.set_bounded_end_and_shift(self.span.end() + Duration::from_secs(1));
+        self.scroll_right_by(Duration::from_secs(1));
+    }
+
+    /// Same as [`scroll_right`](#method.scroll_right), but offsets the span by the given duration
+    pub fn scroll_right_by(&mut self, offset: Duration) {
+        self.set_bounded_end_and_shift(self.span.end() + offset);
         self.set_follow_if_span_is_tracking_current_timestamp();
     }
 
@@ -69,9 +106,10 @@ impl RenderingSpan {
     }
 
     /// Sets the end of the span and shift it (without resizing it)
-    /// The end is capped so that the span cannot cover a time before the application started, or after the current time
+    /// The end is capped so that the span cannot cover a time before the oldest retained metrics, or after the current
+    /// time
     fn set_bounded_end_and_shift(&mut self, unbounded_end: Timestamp) {
-        let min_end = Timestamp::app_init() + self.span.duration();
+        let min_end = self.oldest_retained_timestamp() + self.span.duration();
         let max_end = Timestamp::now();
         let bounded_end = unbounded_end.max(min_end).min(max_end);
         self.span.set_end_and_shift(bounded_end);
@@ -83,14 +121,29 @@ impl RenderingSpan {
     }
 
     pub fn zoom_in(&mut self) {
-        let new_zoom_level = self.zoom_level.checked_sub(1).unwrap_or(0);
+        let new_zoom_level = self.zoom_level.saturating_sub(1);
         self.resize(new_zoom_level);
     }
 
+    /// Returns the timestamp of the oldest metrics that can be rendered
+    fn oldest_retained_timestamp(&self) -> Timestamp {
+        let now = Timestamp::now();
+
+        match self.history {
+            Some(history) if now.duration_since(&Timestamp::app_init()) > history => now - history,
+            _ => Timestamp::app_init(),
+        }
+    }
+
+    /// Enlarges the span, until it covers all the retained history
     pub fn zoom_out(&mut self) {
         let now = Timestamp::now();
         let min_begin = min(Timestamp::app_init(), now - DEFAULT_SPAN_DURATION);
-        let max_span_duration = now.duration_since(&min_begin);
+        let mut max_span_duration = now.duration_since(&min_begin);
+        if let Some(history) = self.history {
+            max_span_duration = max_span_duration.min(history);
+        }
+
         let max_units_to_display = max_span_duration.as_secs() as f64 / SPAN_UNIT.as_secs() as f64;
         let max_zoom_level = f64::log2(max_units_to_display).ceil() as u32;
 
@@ -99,6 +152,28 @@ impl RenderingSpan {
         }
     }
 
+    /// Resizes the span to the zoom level whose duration is the closest to `duration`
+    pub fn resize_to(&mut self, duration: Duration) {
+        self.resize(closest_zoom_level(duration));
+    }
+
+    /// Resizes the span so that it covers exactly the time elapsed since `begin`, and tracks the current time
+    ///
+    /// The span covers at least `SPAN_UNIT`, and can not begin before the oldest retained metrics. Zooming in or out
+    /// afterwards resumes from the zoom level whose duration is the closest to the covered duration.
+    ///
+    /// # Arguments
+    ///  * `begin`: The first timestamp to cover, such as the timestamp of the oldest metric of a process
+    pub fn fit_since(&mut self, begin: Timestamp) {
+        let now = Timestamp::now();
+        let begin = begin.max(self.oldest_retained_timestamp()).min(now - SPAN_UNIT);
+
+        self.span.set_end_and_shift(now);
+        self.span.set_begin_and_resize(begin);
+        self.zoom_level = closest_zoom_level(self.span.duration());
+        self.follow = true;
+    }
+
     fn resize(&mut self, zoom_level: u32) {
         let target_size: Duration = Duration::from_secs(SPAN_UNIT.as_secs() * (1 << zoom_level));
         self.span.set_begin_and_resize(self.span.end() - target_size);
@@ -106,6 +181,12 @@ impl RenderingSpan {
     }
 }
 
+/// Returns the zoom level whose span duration is the closest to `duration`
+fn closest_zoom_level(duration: Duration) -> u32 {
+    let units = duration.as_secs_f64() / SPAN_UNIT.as_secs_f64();
+    units.log2().round().max(0.) as u32
+}
+
 #[cfg(test)]
 mod test_rendering_span {
     use std::time::Duration;
@@ -274,4 +355,129 @@ mod test_rendering_span {
 
         assert!(rendering_span.to_span().duration() > 2 * initial_duration);
     }
+
+    #[rstest]
+    fn test_should_not_advance_when_frozen(mut rendering_span: RenderingSpan) {
+        let frozen_span = rendering_span.to_span();
+
+        rendering_span.set_follow(false);
+        advance_time_and_refresh_timestamp(Duration::from_secs(10));
+        rendering_span.follow();
+
+        assert_eq!(rendering_span.to_span().end(), frozen_span.end());
+        assert!(!rendering_span.is_following());
+    }
+
+    #[rstest]
+    fn test_should_catch_up_with_current_time_when_unfrozen(mut rendering_span: RenderingSpan) {
+        rendering_span.set_follow(false);
+        advance_time_and_refresh_timestamp(Duration::from_secs(10));
+        rendering_span.follow();
+
+        rendering_span.set_follow(true);
+
+        assert_eq!(rendering_span.to_span().end(), Timestamp::now());
+        assert!(rendering_span.is_following());
+    }
+
+    #[rstest]
+    fn test_should_not_zoom_out_past_history(rendering_span: RenderingSpan) {
+        let mut rendering_span = rendering_span.with_history(Some(Duration::from_secs(120)));
+
+        for _ in 0..1000 {
+            rendering_span.zoom_out();
+        }
+
+        assert_eq!(rendering_span.to_span().duration(), Duration::from_secs(120));
+    }
+
+    #[rstest]
+    fn test_should_zoom_out_past_history_when_history_is_unlimited(rendering_span: RenderingSpan) {
+        let mut rendering_span = rendering_span.with_history(None);
+
+        for _ in 0..1000 {
+            rendering_span.zoom_out();
+        }
+
+        assert!(rendering_span.to_span().duration() > Duration::from_secs(120));
+    }
+
+    #[rstest]
+    fn test_should_not_scroll_before_history(rendering_span: RenderingSpan) {
+        let mut rendering_span = rendering_span.with_history(Some(Duration::from_secs(90)));
+
+        for _ in 0..1000 {
+            rendering_span.scroll_left();
+        }
+
+        assert_eq!(
+            rendering_span.to_span().begin(),
+            Timestamp::now() - Duration::from_secs(90)
+        );
+    }
+
+    #[rstest]
+    fn test_should_fit_span_to_time_elapsed_since_given_timestamp(mut rendering_span: RenderingSpan) {
+        advance_time_and_refresh_timestamp(Duration::from_secs(100));
+        let first_metric = Timestamp::now();
+        advance_time_and_refresh_timestamp(Duration::from_secs(40));
+
+        rendering_span.fit_since(first_metric);
+
+        let span = rendering_span.to_span();
+        assert_eq!(span.begin(), first_metric);
+        assert_eq!(span.end(), Timestamp::now());
+        assert!(rendering_span.is_following());
+    }
+
+    #[rstest]
+    fn test_fitted_span_should_cover_at_least_one_unit(mut rendering_span: RenderingSpan) {
+        advance_time_and_refresh_timestamp(Duration::from_secs(100));
+        let first_metric = Timestamp::now();
+        advance_time_and_refresh_timestamp(Duration::from_secs(3));
+
+        rendering_span.fit_since(first_metric);
+
+        assert_eq!(rendering_span.to_span().duration(), Duration::from_secs(15));
+    }
+
+    #[rstest]
+    fn test_should_follow_current_time_once_fitted(mut rendering_span: RenderingSpan) {
+        advance_time_and_refresh_timestamp(Duration::from_secs(100));
+        let first_metric = Timestamp::now();
+        advance_time_and_refresh_timestamp(Duration::from_secs(40));
+        rendering_span.scroll_left_by(Duration::from_secs(20));
+
+        rendering_span.fit_since(first_metric);
+
+        assert!(rendering_span.is_following());
+        assert_eq!(rendering_span.to_span().end(), Timestamp::now());
+    }
+
+    #[rstest]
+    fn test_should_not_fit_span_before_history(rendering_span: RenderingSpan) {
+        let mut rendering_span = rendering_span.with_history(Some(Duration::from_secs(90)));
+        let first_metric = Timestamp::now();
+        advance_time_and_refresh_timestamp(Duration::from_secs(200));
+
+        rendering_span.fit_since(first_metric);
+
+        assert_eq!(
+            rendering_span.to_span().begin(),
+            Timestamp::now() - Duration::from_secs(90)
+        );
+    }
+
+    #[rstest]
+    fn test_should_zoom_from_closest_level_once_fitted(mut rendering_span: RenderingSpan) {
+        advance_time_and_refresh_timestamp(Duration::from_secs(200));
+        let first_metric = Timestamp::now();
+        advance_time_and_refresh_timestamp(Duration::from_secs(100));
+        rendering_span.fit_since(first_metric);
+
+        rendering_span.zoom_in();
+
+        // The 100s span is the closest to the 120s zoom level, one level above the 60s level
+        assert_eq!(rendering_span.to_span().duration(), Duration::from_secs(60));
+    }
 }
@@ -6,6 +6,7 @@ use thiserror::Error;
 #[macro_use]
 mod macros;
 
+pub mod config;
 pub mod core;
 mod ctrl;
 pub mod procfs;
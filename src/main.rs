@@ -1,44 +1,130 @@
+use std::fs;
 use std::fs::OpenOptions;
+use std::path::Path;
 use std::sync::mpsc::channel;
-use std::time::Duration;
 
-use log::error;
-use log::LevelFilter;
+use anyhow::Context;
+use log::{error, warn};
 use simplelog::{ConfigBuilder, WriteLogger};
 
+use spv::config::{ColumnKind, Config, ConfigSource, ProbeKind};
 use spv::core::collection::{MetricCollector, ProbeCollector};
-use spv::core::process::ProcessCollector;
-use spv::procfs::cpu_probe::CpuProbe;
+use spv::core::external::ExternalProbe;
+use spv::core::labels::ProcessLabels;
+use spv::core::process::{ProcessCollector, ProcessScanner};
+use spv::core::service::ServiceScanner;
+use spv::core::sources::SourcesCollector;
+use spv::core::tree::process_tree_dot;
+use spv::core::Error as CoreError;
+use spv::procfs::child_count_probe::ChildCountProbe;
+use spv::procfs::connections::ProcfsConnectionLister;
+use spv::procfs::cpu_probe::{ChildrenCpuProbe, CpuProbe};
+use spv::procfs::devices::FdDeviceResolver;
+use spv::procfs::disk_wait_probe::DiskWaitProbe;
 use spv::procfs::diskio_probe::DiskIOProbe;
+use spv::procfs::fd_limit_probe::FdLimitProbe;
+use spv::procfs::filesystems::StatvfsScanner;
+#[cfg(feature = "gpu")]
+use spv::procfs::gpu_probe::{GpuMemoryProbe, GpuMetric, GpuProbe, GpuSources};
+use spv::procfs::iops_probe::IopsProbe;
 use spv::procfs::libc::open_file_limit;
+use spv::procfs::lifetime_cpu_probe::LifetimeCpuProbe;
+use spv::procfs::logical_io_probe::LogicalIOProbe;
+use spv::procfs::major_faults_probe::MajorFaultsProbe;
+use spv::procfs::mapped_files_probe::MappedFilesProbe;
+use spv::procfs::memory_growth_probe::MemoryGrowthProbe;
+use spv::procfs::memory_probe::MemoryProbe;
+use spv::procfs::memory_segments_probe::MemorySegmentsProbe;
+use spv::procfs::memory_volatility_probe::MemoryVolatilityProbe;
 #[cfg(feature = "netio")]
 use spv::procfs::net_io_probe::NetIoProbe;
+use spv::procfs::oom_score_probe::OomScoreProbe;
+use spv::procfs::power_probe::PowerProbe;
+use spv::procfs::pressure_probe::PressureProbe;
 use spv::procfs::process::ProcfsScanner;
-use spv::spv::SpvApplication;
-use spv::triggers::TriggersEmitter;
+use spv::procfs::protocol_io_probe::ProtocolIOProbe;
+use spv::procfs::sched_probe::SchedProbe;
+use spv::procfs::set_proc_root;
+use spv::procfs::signal::ProcfsSignalSender;
+use spv::procfs::socket_probe::SocketProbe;
+use spv::procfs::socket_states_probe::SocketStatesProbe;
+use spv::procfs::thermal_probe::ThermalProbe;
+use spv::procfs::yield_probe::YieldProbe;
+use spv::spv::{EnabledCollectors, SpvApplication};
+use spv::triggers::{TriggersEmitter, TypingMode};
 use spv::Error;
 
+/// Memory usage is only collected every `MEMORY_REFRESH_DIVISOR` iterations
+const MEMORY_REFRESH_DIVISOR: usize = 5;
+
 fn main() -> anyhow::Result<()> {
+    let config_source = ConfigSource::from_env();
+    let args = config_source.read_args()?;
+    let config = Config::from_args(&args)?;
+
+    if args.check_config {
+        println!("{}", config);
+        return Ok(());
+    }
+
+    if let Some(path) = &args.export_tree {
+        set_proc_root(config.proc_root());
+        return export_process_tree(path);
+    }
+
     setup_panic_logging();
-    init_logging();
+    init_logging(&config);
 
     let (tx, rx) = channel();
 
-    let refresh_period = Duration::from_secs(1);
-    TriggersEmitter::launch_async(tx, refresh_period);
-    let impulse_tolerance = TriggersEmitter::impulse_time_tolerance(refresh_period);
+    let typing_mode = TypingMode::default();
+    TriggersEmitter::launch_async(tx, &config, typing_mode.clone());
+
+    // The scanner and the probes read the proc filesystem from this thread, which the proc root is local to
+    set_proc_root(config.proc_root());
 
-    let process_scanner = ProcfsScanner::new()?;
-    let process_view = ProcessCollector::new(Box::new(process_scanner));
+    let mut process_scanner: Box<dyn ProcessScanner> = Box::new(ProcfsScanner::new()?);
+    if let Some(service) = config.service() {
+        process_scanner = Box::new(ServiceScanner::new(process_scanner, service));
+    }
+    let mut process_view = ProcessCollector::new(process_scanner);
+    if config.columns().contains(&ColumnKind::CpuTime) {
+        process_view = process_view.with_cpu_time_tracking();
+    }
+    if !config.labels().is_empty() {
+        process_view = process_view.with_labels(ProcessLabels::new(config.labels().to_vec()));
+    }
 
-    let collectors = build_collectors()?;
+    let collectors = build_collectors(&config)?;
 
-    let app = SpvApplication::new(rx, collectors, process_view, impulse_tolerance)?;
+    let app = SpvApplication::new(
+        rx,
+        collectors,
+        process_view,
+        Box::new(ProcfsSignalSender::new()),
+        Box::new(FdDeviceResolver),
+        typing_mode,
+        &config,
+    )?
+    .with_config_source(config_source)
+    .with_connection_lister(Box::new(ProcfsConnectionLister::default()))
+    .with_filesystem_scanner(Box::new(StatvfsScanner::new(config.include_tmpfs())?));
     app.run()?;
 
     Ok(())
 }
 
+/// Scans the running processes once, and writes their tree to the given path as a DOT graph
+fn export_process_tree(path: &Path) -> anyhow::Result<()> {
+    let mut process_collector = ProcessCollector::new(Box::new(ProcfsScanner::new()?));
+    process_collector.collect_processes()?;
+
+    fs::write(path, process_tree_dot(&process_collector.running_processes()))
+        .with_context(|| format!("Could not write the process tree to {}", path.display()))?;
+
+    Ok(())
+}
+
 fn setup_panic_logging() {
     // As panics are erased by the application exiting, log the panic as an error
     let default_hook = std::panic::take_hook();
@@ -49,39 +135,212 @@ fn setup_panic_logging() {
     }))
 }
 
-fn init_logging() {
+fn init_logging(config: &Config) {
     let log_file = OpenOptions::new()
-        .write(true)
         .append(true)
         .create(true)
-        .open("spv.log")
+        .open(config.log_file())
         .expect("Could not open log file");
 
     let log_config = ConfigBuilder::default().set_time_format_rfc2822().build();
 
-    WriteLogger::init(LevelFilter::Debug, log_config, log_file).expect("Could not initialize logging");
+    WriteLogger::init(config.log_level(), log_config, log_file).expect("Could not initialize logging");
 }
 
-fn build_collectors() -> Result<Vec<Box<dyn MetricCollector>>, Error> {
+/// Builds the collectors of the enabled probes, along with the kind of their probe
+///
+/// Probes which are not available on all systems are disabled when they can not be built, unless no other probe is
+/// enabled.
+fn build_collectors(config: &Config) -> Result<EnabledCollectors, Error> {
+    // External probes are displayed after the probes of spv
+    let external_probes = (0..config.external_probes().len()).map(ProbeKind::External);
+    let probes: Vec<_> = config.probes().iter().copied().chain(external_probes).collect();
     let fd_not_for_probes = 10; // ~ the no of files that the application will keep open not for probing purposes
     let max_fd = open_file_limit().expect("Could not read process file limits") as usize - fd_not_for_probes;
+    let max_fd_per_probe = max_fd / probes.len();
 
     let mut collectors = vec![];
+    let mut unavailable = None;
 
-    let cpu_probe = CpuProbe::new(max_fd / 2).map_err(Error::CoreError)?;
-    let cpu_collector = ProbeCollector::new(cpu_probe);
-    collectors.push(Box::new(cpu_collector) as Box<dyn MetricCollector>);
+    for probe in &probes {
+        let mut collector: Box<dyn MetricCollector> = match probe {
+            ProbeKind::Cpu => {
+                let cpu_probe = CpuProbe::new(max_fd_per_probe).map_err(Error::CoreError)?;
+                Box::new(ProbeCollector::new(cpu_probe))
+            }
+            ProbeKind::ChildrenCpu => {
+                let children_cpu_probe = ChildrenCpuProbe::new(max_fd_per_probe).map_err(Error::CoreError)?;
+                Box::new(ProbeCollector::new(children_cpu_probe))
+            }
+            ProbeKind::ChildCount => Box::new(ProbeCollector::new(ChildCountProbe::new(max_fd_per_probe))),
+            ProbeKind::DiskIO => {
+                // The disk tab switches between the block I/O, the logical I/O and the I/O operations, whose probes
+                // share the file descriptors of the tab
+                let max_fd_per_source = max_fd_per_probe / 3;
+                let sources: Vec<(&'static str, Box<dyn MetricCollector>)> = vec![
+                    (
+                        "block",
+                        Box::new(ProbeCollector::new(DiskIOProbe::new(max_fd_per_source))),
+                    ),
+                    (
+                        "logical",
+                        Box::new(ProbeCollector::new(LogicalIOProbe::new(max_fd_per_source))),
+                    ),
+                    (
+                        "operations",
+                        Box::new(ProbeCollector::new(IopsProbe::new(max_fd_per_source))),
+                    ),
+                ];
+                Box::new(SourcesCollector::new("Disk I/O", sources))
+            }
+            ProbeKind::DiskWait => {
+                let disk_wait_probe = DiskWaitProbe::new(max_fd_per_probe).map_err(Error::CoreError)?;
+                Box::new(ProbeCollector::new(disk_wait_probe))
+            }
+            ProbeKind::FdLimit => Box::new(ProbeCollector::new(FdLimitProbe::new(max_fd_per_probe))),
+            #[cfg(feature = "gpu")]
+            ProbeKind::Gpu => {
+                let gpu_sources = GpuSources::detect(max_fd_per_probe);
+                let Some(sources) = available(probe, gpu_sources, &mut unavailable) else {
+                    continue;
+                };
+                match sources.metric() {
+                    GpuMetric::Utilization => Box::new(ProbeCollector::new(GpuProbe::new(sources))),
+                    GpuMetric::Memory => {
+                        warn!(
+                            "The GPUs do not report the utilization of processes, measuring their GPU memory instead"
+                        );
+                        Box::new(ProbeCollector::new(GpuMemoryProbe::new(sources)))
+                    }
+                }
+            }
+            ProbeKind::Iops => Box::new(ProbeCollector::new(IopsProbe::new(max_fd_per_probe))),
+            ProbeKind::LifetimeCpu => {
+                let lifetime_cpu_probe = LifetimeCpuProbe::new(max_fd_per_probe).map_err(Error::CoreError)?;
+                Box::new(ProbeCollector::new(lifetime_cpu_probe))
+            }
+            ProbeKind::LogicalIO => Box::new(ProbeCollector::new(LogicalIOProbe::new(max_fd_per_probe))),
+            ProbeKind::MajorFaults => Box::new(ProbeCollector::new(MajorFaultsProbe::new(max_fd_per_probe))),
+            ProbeKind::MappedFiles => Box::new(ProbeCollector::new(MappedFilesProbe::new())),
+            ProbeKind::Memory => {
+                let memory_probe =
+                    MemoryProbe::new(max_fd_per_probe, config.include_hugepages()).map_err(Error::CoreError)?;
+                // Memory usage evolves slowly: it does not need to be collected as often as other metrics
+                Box::new(ProbeCollector::new(memory_probe).with_refresh_divisor(MEMORY_REFRESH_DIVISOR))
+            }
+            ProbeKind::MemoryGrowth => {
+                let memory_growth_probe = MemoryGrowthProbe::new(max_fd_per_probe).map_err(Error::CoreError)?;
+                Box::new(ProbeCollector::new(memory_growth_probe))
+            }
+            ProbeKind::MemorySegments => Box::new(ProbeCollector::new(MemorySegmentsProbe::new(max_fd_per_probe))),
+            ProbeKind::MemoryVolatility => {
+                let memory_volatility_probe = MemoryVolatilityProbe::new(max_fd_per_probe).map_err(Error::CoreError)?;
+                Box::new(ProbeCollector::new(memory_volatility_probe))
+            }
+            ProbeKind::OomScore => {
+                let oom_score_probe = OomScoreProbe::new(max_fd_per_probe).map(ProbeCollector::new);
+                let Some(collector) = available(probe, oom_score_probe, &mut unavailable) else {
+                    continue;
+                };
+                Box::new(collector)
+            }
+            ProbeKind::Power => {
+                let power_probe = PowerProbe::new(max_fd_per_probe).map_err(Error::CoreError)?;
+                Box::new(ProbeCollector::new(power_probe))
+            }
+            ProbeKind::ProtocolIO => {
+                // Capturing packets requires the CAP_NET_RAW capability
+                let protocol_io_probe = ProtocolIOProbe::new().map(ProbeCollector::new);
+                let Some(collector) = available(probe, protocol_io_probe, &mut unavailable) else {
+                    continue;
+                };
+                Box::new(collector)
+            }
+            ProbeKind::SchedLatency => Box::new(ProbeCollector::new(SchedProbe::new(max_fd_per_probe))),
+            ProbeKind::Sockets => {
+                let socket_probe = SocketProbe::new().map_err(Error::CoreError)?;
+                Box::new(ProbeCollector::new(socket_probe))
+            }
+            ProbeKind::SocketStates => {
+                let socket_states_probe = SocketStatesProbe::new().map_err(Error::CoreError)?;
+                Box::new(ProbeCollector::new(socket_states_probe))
+            }
+            ProbeKind::Thermal => {
+                // Servers often have no thermal zone
+                let thermal_probe = ThermalProbe::new().map(ProbeCollector::new);
+                let Some(collector) = available(probe, thermal_probe, &mut unavailable) else {
+                    continue;
+                };
+                Box::new(collector)
+            }
+            ProbeKind::CpuPressure => {
+                let Some(collector) = available(probe, PressureProbe::cpu().map(ProbeCollector::new), &mut unavailable)
+                else {
+                    continue;
+                };
+                Box::new(collector)
+            }
+            ProbeKind::IoPressure => {
+                let Some(collector) = available(probe, PressureProbe::io().map(ProbeCollector::new), &mut unavailable)
+                else {
+                    continue;
+                };
+                Box::new(collector)
+            }
+            ProbeKind::MemoryPressure => {
+                let memory_pressure_probe = PressureProbe::memory().map(ProbeCollector::new);
+                let Some(collector) = available(probe, memory_pressure_probe, &mut unavailable) else {
+                    continue;
+                };
+                Box::new(collector)
+            }
+            ProbeKind::Yields => {
+                let yield_probe = YieldProbe::new(max_fd_per_probe).map_err(Error::CoreError)?;
+                Box::new(ProbeCollector::new(yield_probe))
+            }
+            ProbeKind::External(index) => {
+                let external_probe = ExternalProbe::new(&config.external_probes()[*index], config.refresh_period());
+                Box::new(ProbeCollector::new(external_probe))
+            }
+            #[cfg(feature = "netio")]
+            ProbeKind::NetIO => {
+                let netio_probe = NetIoProbe::new().map_err(Error::CoreError)?;
+                Box::new(ProbeCollector::new(netio_probe))
+            }
+        };
 
-    let disk_io_probe = DiskIOProbe::new(max_fd / 2);
-    let disk_io_collector = ProbeCollector::new(disk_io_probe);
-    collectors.push(Box::new(disk_io_collector) as Box<dyn MetricCollector>);
+        if let Some(precision) = config.precision(*probe) {
+            collector.set_precision(precision);
+        }
+        if config.subsampled_probes().contains(probe) {
+            collector.set_subsampled();
+        }
+        collectors.push((*probe, collector));
+    }
 
-    #[cfg(feature = "netio")]
-    {
-        let netio_probe = NetIoProbe::new().map_err(Error::CoreError)?;
-        let net_collector = ProbeCollector::new(netio_probe);
-        collectors.push(Box::new(net_collector) as Box<dyn MetricCollector>);
+    match unavailable {
+        Some(e) if collectors.is_empty() => Err(Error::CoreError(e)),
+        _ => Ok(collectors),
     }
+}
 
-    Ok(collectors)
+/// Returns the given collector if its probe is available, or logs why it is not otherwise
+///
+/// # Arguments
+///  * `probe`: The kind of the probe of the collector
+///  * `collector`: The collector, or the error which occurred while building its probe
+///  * `unavailable`: Receives the error which occurred while building the probe, if any
+fn available<C>(probe: &ProbeKind, collector: Result<C, CoreError>, unavailable: &mut Option<CoreError>) -> Option<C> {
+    match collector {
+        Ok(collector) => Some(collector),
+        Err(e) => {
+            warn!(
+                "The {} probe is not available, and has been disabled: {}",
+                probe.name(),
+                e
+            );
+            unavailable.replace(e);
+            None
+        }
+    }
 }
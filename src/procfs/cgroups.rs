@@ -0,0 +1,88 @@
+//! Detection of the systemd units of processes, from their control groups
+
+use std::fs;
+
+use crate::core::process::Pid;
+use crate::procfs::proc_root;
+
+/// Reads the systemd unit of a process from `/proc/[pid]/cgroup`
+///
+/// Returns `None` if the file can not be read, or if the process does not belong to a service or a scope, as is the
+/// case of kernel threads and of hosts without systemd.
+pub fn read_unit(pid: Pid) -> Option<String> {
+    let content = fs::read_to_string(proc_root().join(pid.to_string()).join("cgroup")).ok()?;
+
+    parse_unit(&content)
+}
+
+/// Extracts the systemd unit of a process from the content of its cgroup file
+///
+/// The unit is read from the path of the unified hierarchy of cgroup v2 (`0::/system.slice/nginx.service`), or from
+/// the path of the systemd hierarchy of cgroup v1 (`1:name=systemd:/system.slice/nginx.service`). It is the innermost
+/// service or scope of the path, as slices only group other units, and as services may create sub-groups of their own.
+fn parse_unit(cgroup: &str) -> Option<String> {
+    cgroup
+        .lines()
+        .filter_map(systemd_path)
+        .find_map(innermost_unit)
+        .map(str::to_string)
+}
+
+/// Returns the path of a line of a cgroup file, if the line describes a hierarchy managed by systemd
+fn systemd_path(line: &str) -> Option<&str> {
+    let mut fields = line.splitn(3, ':');
+    let (hierarchy, controllers, path) = (fields.next()?, fields.next()?, fields.next()?);
+
+    let unified = hierarchy == "0" && controllers.is_empty();
+    (unified || controllers == "name=systemd").then_some(path)
+}
+
+fn innermost_unit(path: &str) -> Option<&str> {
+    path.rsplit('/')
+        .find(|component| component.ends_with(".service") || component.ends_with(".scope"))
+}
+
+#[cfg(test)]
+mod test_parse_unit {
+    use rstest::*;
+
+    use crate::procfs::cgroups::parse_unit;
+
+    #[rstest]
+    #[case("0::/system.slice/nginx.service\n", Some("nginx.service"))]
+    #[case("0::/init.scope\n", Some("init.scope"))]
+    #[case(
+        "0::/user.slice/user-1000.slice/user@1000.service/app.slice/app-firefox-1234.scope\n",
+        Some("app-firefox-1234.scope")
+    )]
+    #[case("0::/system.slice/docker.service/payload\n", Some("docker.service"))]
+    #[case("0::/system.slice\n", None)]
+    #[case("0::/\n", None)]
+    #[case("", None)]
+    fn test_should_extract_innermost_unit_from_unified_hierarchy(#[case] cgroup: &str, #[case] expected: Option<&str>) {
+        assert_eq!(parse_unit(cgroup).as_deref(), expected);
+    }
+
+    #[test]
+    fn test_should_extract_unit_from_systemd_hierarchy_of_cgroup_v1() {
+        let cgroup = "12:memory:/system.slice/sshd.service\n\
+                      4:cpu,cpuacct:/system.slice/sshd.service\n\
+                      1:name=systemd:/system.slice/sshd.service\n";
+
+        assert_eq!(parse_unit(cgroup).as_deref(), Some("sshd.service"));
+    }
+
+    #[test]
+    fn test_should_ignore_hierarchies_not_managed_by_systemd() {
+        let cgroup = "12:memory:/system.slice/sshd.service\n4:cpu,cpuacct:/docker/1234.scope\n";
+
+        assert_eq!(parse_unit(cgroup), None);
+    }
+
+    #[test]
+    fn test_should_fall_back_to_systemd_hierarchy_when_unified_hierarchy_has_no_unit() {
+        let cgroup = "1:name=systemd:/system.slice/cron.service\n0::/\n";
+
+        assert_eq!(parse_unit(cgroup).as_deref(), Some("cron.service"));
+    }
+}
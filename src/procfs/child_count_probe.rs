@@ -0,0 +1,126 @@
+//! Child processes probing
+
+use std::collections::HashMap;
+
+use crate::core::metrics::CountMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::tree::count_children;
+use crate::core::Error;
+use crate::procfs::parsers::process::PidStatus;
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData};
+use crate::procfs::proc_root;
+use crate::procfs::process::scan_pids;
+
+/// Probe implementation to count the direct children of processes
+///
+/// Supervisors and worker pools are easier to spot by the number of processes they spawned. The parent of every
+/// running process is read once per iteration, from which the children of each process are counted.
+pub struct ChildCountProbe {
+    status_reader: Box<dyn ReadProcessData<PidStatus>>,
+    children_counts: HashMap<Pid, usize>,
+}
+
+impl ChildCountProbe {
+    /// Creates a new probe that can count the child processes of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many files descriptor the probe should keep open at most
+    pub fn new(fd_limit: usize) -> Self {
+        Self::from_reader(Box::new(ProcessDataReader::with_capacity(fd_limit)))
+    }
+
+    fn from_reader(status_reader: Box<dyn ReadProcessData<PidStatus>>) -> Self {
+        ChildCountProbe {
+            status_reader,
+            children_counts: HashMap::new(),
+        }
+    }
+}
+
+impl Probe<CountMetric> for ChildCountProbe {
+    fn name(&self) -> &'static str {
+        "Child processes"
+    }
+
+    fn init_iteration(&mut self) -> Result<(), Error> {
+        // Processes may die while being read, in which case they are simply left out of the parent map
+        let parents: HashMap<Pid, Pid> = scan_pids(&proc_root())?
+            .into_iter()
+            .filter_map(|pid| self.status_reader.read(pid).ok().map(|status| (pid, status.ppid())))
+            .collect();
+
+        self.children_counts = count_children(&parents);
+
+        Ok(())
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<CountMetric, Error> {
+        let count = self.children_counts.get(&pid).copied().unwrap_or_default();
+
+        Ok(CountMetric::new(count))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().for_each(|pid| {
+            self.status_reader.cleanup(*pid);
+            self.children_counts.remove(pid);
+        });
+    }
+}
+
+#[cfg(test)]
+mod test_child_count_probe {
+    use rstest::*;
+
+    use crate::core::metrics::CountMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::child_count_probe::ChildCountProbe;
+    use crate::procfs::fake_procfs::FakeProcfs;
+
+    #[fixture]
+    fn procfs() -> FakeProcfs {
+        let procfs = FakeProcfs::new();
+        procfs.spawn_child_process(1, 0, "init");
+        procfs.spawn_child_process(10, 1, "nginx");
+        procfs.spawn_child_process(11, 10, "nginx: worker");
+        procfs.spawn_child_process(12, 10, "nginx: worker");
+        procfs.spawn_child_process(20, 1, "sshd");
+
+        procfs
+    }
+
+    fn probe_all(probe: &mut ChildCountProbe, pid: u32) -> CountMetric {
+        probe.init_iteration().unwrap();
+
+        probe.probe(pid).unwrap()
+    }
+
+    #[rstest]
+    #[case(1, 2)]
+    #[case(10, 2)]
+    #[case(11, 0)]
+    #[case(20, 0)]
+    fn test_should_count_direct_children(_procfs: FakeProcfs, #[case] pid: u32, #[case] expected: usize) {
+        let mut probe = ChildCountProbe::new(10);
+
+        assert_eq!(probe_all(&mut probe, pid), CountMetric::new(expected));
+    }
+
+    #[rstest]
+    fn test_should_update_count_when_child_dies(procfs: FakeProcfs) {
+        let mut probe = ChildCountProbe::new(10);
+        probe_all(&mut probe, 10);
+
+        procfs.kill_process(12);
+
+        assert_eq!(probe_all(&mut probe, 10), CountMetric::new(1));
+    }
+
+    #[rstest]
+    fn test_should_count_no_children_for_unknown_process(_procfs: FakeProcfs) {
+        let mut probe = ChildCountProbe::new(10);
+
+        assert_eq!(probe_all(&mut probe, 99), CountMetric::new(0));
+    }
+}
@@ -0,0 +1,98 @@
+//! Listing of the TCP connections of processes
+
+use std::collections::HashMap;
+
+use crate::core::connections::{Connection, ConnectionLister};
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::parsers::system::{Tcp6Connections, TcpConnections};
+use crate::procfs::parsers::{ReadSystemData, SystemDataReader};
+use crate::procfs::socket_probe::{FdSocketInodesReader, ReadSocketInodes};
+
+/// Implementation of ConnectionLister which correlates the inodes of the sockets of a process, as listed in
+/// `/proc/[pid]/fd`, with the connections listed in `/proc/net/tcp` and `/proc/net/tcp6`
+///
+/// The tables of connections are only read when the connections of a process are listed, as it is only done for the
+/// selected process, unlike the metrics of probes.
+pub struct ProcfsConnectionLister {
+    inodes_reader: Box<dyn ReadSocketInodes>,
+}
+
+impl Default for ProcfsConnectionLister {
+    fn default() -> Self {
+        Self {
+            inodes_reader: Box::new(FdSocketInodesReader),
+        }
+    }
+}
+
+impl ConnectionLister for ProcfsConnectionLister {
+    fn connections(&mut self, pid: Pid) -> Result<Vec<Connection>, Error> {
+        let process_inodes = self
+            .inodes_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not list sockets of PID {}", pid), e.into()))?;
+
+        let mut connections = SystemDataReader::<TcpConnections>::new()
+            .and_then(|mut reader| reader.read())
+            .map_err(|e| Error::ProbingError("Could not read TCP connections".to_string(), e.into()))?
+            .connections()
+            .clone();
+
+        // /proc/net/tcp6 does not exist when IPv6 is disabled
+        if let Ok(tcp6) = SystemDataReader::<Tcp6Connections>::new().and_then(|mut reader| reader.read()) {
+            connections.extend(tcp6.connections());
+        }
+
+        Ok(process_connections(&process_inodes, &connections))
+    }
+}
+
+/// Returns the connections of the sockets of a process, in the order of its file descriptors
+///
+/// # Arguments
+///  * `process_inodes`: The inodes of the sockets opened by the process
+///  * `connections`: All the TCP connections of the system, by the inode of their socket
+fn process_connections(process_inodes: &[u64], connections: &HashMap<u64, Connection>) -> Vec<Connection> {
+    process_inodes
+        .iter()
+        .filter_map(|inode| connections.get(inode).copied())
+        .collect()
+}
+
+#[cfg(test)]
+mod test_process_connections {
+    use std::collections::HashMap;
+
+    use crate::core::connections::Connection;
+    use crate::procfs::connections::process_connections;
+
+    fn connection(local: &str, state: &'static str) -> Connection {
+        Connection::new(local.parse().unwrap(), "10.0.0.1:443".parse().unwrap(), state)
+    }
+
+    fn connections() -> HashMap<u64, Connection> {
+        hashmap!(
+            10 => connection("127.0.0.1:8080", "LISTEN"),
+            20 => connection("10.0.2.15:41652", "ESTABLISHED"),
+            30 => connection("[::1]:631", "LISTEN")
+        )
+    }
+
+    #[test]
+    fn test_should_list_connections_of_sockets_of_process_only() {
+        assert_eq!(
+            process_connections(&[30, 20], &connections()),
+            vec![
+                connection("[::1]:631", "LISTEN"),
+                connection("10.0.2.15:41652", "ESTABLISHED")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_should_ignore_sockets_which_are_not_tcp_connections() {
+        // e.g. UNIX or UDP sockets
+        assert!(process_connections(&[40, 50], &connections()).is_empty());
+    }
+}
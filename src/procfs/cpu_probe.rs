@@ -2,7 +2,7 @@
 
 use std::collections::HashMap;
 
-use crate::core::metrics::PercentMetric;
+use crate::core::metrics::{CpuSplitMetric, PercentMetric};
 use crate::core::probe::Probe;
 use crate::core::process::Pid;
 use crate::core::Error;
@@ -82,6 +82,78 @@ impl Probe<PercentMetric> for CpuProbe {
     }
 }
 
+/// Probe implementation to measure the CPU usage (in percent) of processes, split between their own usage and the usage
+/// of their children
+///
+/// The CPU time of children is only accounted to their parent once they terminate and are waited for, so the children
+/// usage of a supervisor spikes when its children exit.
+pub struct ChildrenCpuProbe {
+    stat_reader: Box<dyn ReadSystemData<Stat>>,
+    pid_stat_reader: Box<dyn ReadProcessData<PidStat>>,
+    calculator: UsageCalculator,
+}
+
+impl ChildrenCpuProbe {
+    /// Creates a new probe that can detect the own and children CPU usages of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many files descriptor the probe should keep open at most
+    pub fn new(fd_limit: usize) -> Result<Self, Error> {
+        let stat_reader = SystemDataReader::new()
+            .map_err(|e| Error::ProbingError("Could not access /proc directory".to_string(), e.into()))?;
+
+        Ok(Self::from_readers(
+            Box::new(stat_reader),
+            Box::new(ProcessDataReader::with_capacity(fd_limit)),
+        ))
+    }
+
+    fn from_readers(
+        stat_reader: Box<dyn ReadSystemData<Stat>>,
+        pid_stat_reader: Box<dyn ReadProcessData<PidStat>>,
+    ) -> Self {
+        ChildrenCpuProbe {
+            stat_reader,
+            pid_stat_reader,
+            calculator: UsageCalculator::default(),
+        }
+    }
+}
+
+impl Probe<CpuSplitMetric> for ChildrenCpuProbe {
+    fn name(&self) -> &'static str {
+        "CPU usage (children)"
+    }
+
+    fn init_iteration(&mut self) -> Result<(), Error> {
+        let new_stat: Stat = self
+            .stat_reader
+            .read()
+            .map_err(|e| Error::ProbingError("Could not read system CPU stats".to_string(), e.into()))?;
+
+        self.calculator.compute_new_runtime_diff(new_stat);
+
+        Ok(())
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<CpuSplitMetric, Error> {
+        let pid_stat = self
+            .pid_stat_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read process CPU stats for PID {}", pid), e.into()))?;
+
+        let (own, children) = self.calculator.calculate_pid_split_usage(pid, pid_stat);
+        Ok(CpuSplitMetric::new(own, children))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().copied().for_each(|pid| {
+            self.pid_stat_reader.cleanup(pid);
+            self.calculator.cleanup(pid);
+        });
+    }
+}
+
 struct UsageCalculator {
     processes_prev_stats: HashMap<Pid, PidStat>,
     prev_global_stat: Stat,
@@ -125,15 +197,27 @@ impl UsageCalculator {
     ///  * `pid_stat_data`: The new content of the stat file of the process with ID `pid`
     ///
     pub fn calculate_pid_usage(&mut self, pid: Pid, pid_stat_data: PidStat) -> f64 {
-        let last_iter_runtime = match self.processes_prev_stats.get(&pid) {
-            Some(stat_data) => stat_data.running_time(),
-            None => 0,
+        let (own_usage, children_usage) = self.calculate_pid_split_usage(pid, pid_stat_data);
+
+        own_usage + children_usage
+    }
+
+    /// Same as [`Self::calculate_pid_usage()`], but returns the usage of the process itself and the usage of its
+    /// children separately
+    pub fn calculate_pid_split_usage(&mut self, pid: Pid, pid_stat_data: PidStat) -> (f64, f64) {
+        let (last_own_runtime, last_children_runtime) = match self.processes_prev_stats.get(&pid) {
+            Some(stat_data) => (stat_data.own_runtime(), stat_data.children_runtime()),
+            None => (0, 0),
         };
 
-        let pid_runtime_diff = pid_stat_data.running_time() - last_iter_runtime;
+        let own_runtime_diff = pid_stat_data.own_runtime() - last_own_runtime;
+        let children_runtime_diff = pid_stat_data.children_runtime() - last_children_runtime;
         self.processes_prev_stats.insert(pid, pid_stat_data);
 
-        100. * pid_runtime_diff as f64 / self.global_runtime_diff
+        (
+            100. * own_runtime_diff as f64 / self.global_runtime_diff,
+            100. * children_runtime_diff as f64 / self.global_runtime_diff,
+        )
     }
 
     /// Cleanup data allocated for the given process
@@ -162,7 +246,7 @@ mod test_cpu_probe {
         let pid_stat_reader = FakeProcessDataReader::new();
 
         let mut probe = build_probe(stat_reader, pid_stat_reader);
-        let probed_metrics = probe.probe_processes(&vec![]).unwrap();
+        let probed_metrics = probe.probe_processes(&[]).unwrap();
 
         assert_eq!(probed_metrics, hashmap!());
     }
@@ -175,10 +259,10 @@ mod test_cpu_probe {
         pid_stat_reader.set_pid_sequence(1, vec![create_pid_stat(0), create_pid_stat(100)]);
 
         let mut probe = build_probe(stat_reader, pid_stat_reader);
-        probe.probe_processes(&vec![1]).unwrap(); // First calibration probing
+        probe.probe_processes(&[1]).unwrap(); // First calibration probing
 
         assert_eq!(
-            probe.probe_processes(&vec![1]).unwrap(),
+            probe.probe_processes(&[1]).unwrap(),
             hashmap!(1 => PercentMetric::new(50.))
         );
     }
@@ -192,9 +276,9 @@ mod test_cpu_probe {
         pid_stat_reader.set_pid_sequence(2, vec![create_pid_stat(0), create_pid_stat(100)]);
 
         let mut probe = build_probe(stat_reader, pid_stat_reader);
-        probe.probe_processes(&vec![1, 2]).unwrap(); // calibrating probe
+        probe.probe_processes(&[1, 2]).unwrap(); // calibrating probe
 
-        let metrics = probe.probe_processes(&vec![1, 2]).unwrap();
+        let metrics = probe.probe_processes(&[1, 2]).unwrap();
 
         let expected_metrics = hashmap!(1 => PercentMetric::new(25.), 2 => PercentMetric::new(50.));
         assert_eq!(metrics, expected_metrics);
@@ -209,7 +293,7 @@ mod test_cpu_probe {
 
         let mut probe = build_probe(stat_reader, pid_stat_reader);
 
-        let collected_metrics = probe.probe_processes(&vec![1]).unwrap();
+        let collected_metrics = probe.probe_processes(&[1]).unwrap();
 
         assert_eq!(collected_metrics, hashmap!(1 => PercentMetric::default()));
     }
@@ -247,6 +331,53 @@ mod test_cpu_calculator {
 
         assert_eq!(calc.calculate_pid_usage(1, pid_stat), 100.);
     }
+
+    #[test]
+    fn test_should_split_own_and_children_usages() {
+        let mut calc = create_initialized_calc(200);
+        calc.calculate_pid_split_usage(1, PidStat::new(10, 10, 5, 5, 0));
+
+        calc.compute_new_runtime_diff(create_stat(500));
+        let usage = calc.calculate_pid_split_usage(1, PidStat::new(50, 30, 35, 25, 0));
+
+        assert_eq!(usage, (30., 25.));
+    }
+
+    #[test]
+    fn test_split_usages_should_add_up_to_total_usage() {
+        let mut split_calc = create_initialized_calc(200);
+        let mut total_calc = create_initialized_calc(200);
+        let pid_stat = PidStat::new(60, 20, 10, 10, 0);
+
+        let (own, children) = split_calc.calculate_pid_split_usage(1, pid_stat);
+
+        assert_eq!(own + children, total_calc.calculate_pid_usage(1, pid_stat));
+    }
+}
+
+#[cfg(test)]
+mod test_children_cpu_probe {
+    use crate::core::metrics::CpuSplitMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::cpu_probe::common_test_utils::create_stat;
+    use crate::procfs::cpu_probe::ChildrenCpuProbe;
+    use crate::procfs::parsers::fakes::{FakeProcessDataReader, FakeSystemDataReader};
+    use crate::procfs::parsers::process::PidStat;
+
+    #[test]
+    fn test_should_report_own_and_children_usages_of_process() {
+        let stat_reader = FakeSystemDataReader::from_sequence(vec![create_stat(0), create_stat(400)]);
+        let mut pid_stat_reader = FakeProcessDataReader::new();
+        pid_stat_reader.set_pid_sequence(1, vec![PidStat::new(0, 0, 0, 0, 0), PidStat::new(60, 40, 150, 50, 0)]);
+
+        let mut probe = ChildrenCpuProbe::from_readers(Box::new(stat_reader), Box::new(pid_stat_reader));
+        probe.probe_processes(&[1]).unwrap(); // First calibration probing
+
+        assert_eq!(
+            probe.probe_processes(&[1]).unwrap(),
+            hashmap!(1 => CpuSplitMetric::new(25., 50.))
+        );
+    }
 }
 
 #[cfg(test)]
@@ -272,8 +403,8 @@ mod common_test_utils {
 
     pub fn create_pid_stat(running_time: u32) -> PidStat {
         // Same operation as above but returns a PidStat instance
-        let individual_ticks = (running_time / 4) as u32;
-        let leftover = (running_time - 4 * individual_ticks) as u32;
+        let individual_ticks = running_time / 4;
+        let leftover = running_time - 4 * individual_ticks;
 
         PidStat::new(
             individual_ticks,
@@ -0,0 +1,115 @@
+//! Attribution of the files opened by processes to the devices storing them
+
+use std::fs::{metadata, read_dir, read_link};
+use std::os::unix::fs::MetadataExt;
+use std::path::PathBuf;
+
+use crate::core::devices::{most_frequent_device, Device, DeviceId, DeviceResolver};
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::{proc_root, ProcfsError};
+
+/// The directory listing the block devices of the system by their ID
+const SYS_BLOCK_DEVICES_DIR: &str = "/sys/dev/block";
+
+/// Implementation of DeviceResolver which follows the links of `/proc/[pid]/fd` to the opened files
+#[derive(Default)]
+pub struct FdDeviceResolver;
+
+impl DeviceResolver for FdDeviceResolver {
+    fn dominant_device(&mut self, pid: Pid) -> Result<Option<Device>, Error> {
+        let devices = opened_files_devices(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not list the files opened by PID {}", pid), e.into()))?;
+
+        Ok(most_frequent_device(&devices).map(|id| Device::new(id, device_name(id))))
+    }
+}
+
+/// Lists the devices storing the regular files opened by a process, once per opened file
+fn opened_files_devices(pid: Pid) -> Result<Vec<DeviceId>, ProcfsError> {
+    let fd_dir = proc_root().join(pid.to_string()).join("fd");
+
+    let devices = read_dir(fd_dir)?
+        .filter_map(|entry| entry.ok())
+        // The file descriptor may have been closed since the directory was listed
+        .filter_map(|entry| metadata(entry.path()).ok())
+        // Pipes, sockets and other special files are not stored on a device
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| device_id(metadata.dev()))
+        .collect();
+
+    Ok(devices)
+}
+
+/// Splits a device number, as encoded by the kernel, into its major and minor numbers
+fn device_id(dev: u64) -> DeviceId {
+    let major = ((dev >> 8) & 0xfff) | ((dev >> 32) & !0xfff);
+    let minor = (dev & 0xff) | ((dev >> 12) & !0xff);
+
+    DeviceId::new(major as u32, minor as u32)
+}
+
+/// Returns the name of a block device, which is `None` for virtual filesystems such as tmpfs
+fn device_name(id: DeviceId) -> Option<String> {
+    let link = PathBuf::from(SYS_BLOCK_DEVICES_DIR).join(id.to_string());
+
+    read_link(link)
+        .ok()
+        .and_then(|target| target.file_name().map(|name| name.to_string_lossy().into_owned()))
+}
+
+#[cfg(test)]
+mod test_device_id {
+    use rstest::*;
+
+    use crate::core::devices::DeviceId;
+    use crate::procfs::devices::device_id;
+
+    #[rstest]
+    #[case(0x0801, DeviceId::new(8, 1))]
+    #[case(0x10302, DeviceId::new(259, 2))]
+    #[case(0x2d, DeviceId::new(0, 45))]
+    #[case(0x12c05, DeviceId::new(300, 5))]
+    #[case(0x10082c, DeviceId::new(8, 300))]
+    fn test_should_split_device_number(#[case] dev: u64, #[case] expected: DeviceId) {
+        assert_eq!(device_id(dev), expected);
+    }
+}
+
+#[cfg(test)]
+mod test_opened_files_devices {
+    use std::fs;
+    use std::os::unix::fs::{symlink, MetadataExt};
+
+    use tempfile::tempdir;
+
+    use crate::procfs::devices::{device_id, opened_files_devices};
+    use crate::procfs::test_utils::ProcRootOverride;
+
+    #[test]
+    fn test_should_resolve_device_of_regular_files_only() {
+        let dir = tempdir().unwrap();
+        let _proc_root = ProcRootOverride::new(dir.path());
+
+        let fd_dir = dir.path().join("42").join("fd");
+        fs::create_dir_all(&fd_dir).unwrap();
+        let file = dir.path().join("data.db");
+        fs::write(&file, "content").unwrap();
+
+        symlink(&file, fd_dir.join("3")).unwrap();
+        symlink(&file, fd_dir.join("4")).unwrap();
+        symlink(dir.path(), fd_dir.join("5")).unwrap(); // A directory is not a regular file
+        symlink(dir.path().join("deleted"), fd_dir.join("6")).unwrap(); // Closed since the directory was listed
+
+        let expected = device_id(fs::metadata(&file).unwrap().dev());
+        assert_eq!(opened_files_devices(42).unwrap(), vec![expected, expected]);
+    }
+
+    #[test]
+    fn test_should_fail_when_process_does_not_exist() {
+        let dir = tempdir().unwrap();
+        let _proc_root = ProcRootOverride::new(dir.path());
+
+        assert!(opened_files_devices(42).is_err());
+    }
+}
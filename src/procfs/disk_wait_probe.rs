@@ -0,0 +1,155 @@
+//! Lifetime disk wait probing
+
+use crate::core::metrics::PercentMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::libc::clock_ticks;
+use crate::procfs::parsers::process::PidStat;
+use crate::procfs::parsers::system::Uptime;
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData, ReadSystemData, SystemDataReader};
+
+/// Probe implementation to measure the portion of their life that processes spent waiting for block I/O, in percent
+///
+/// It highlights the processes which are chronically bound by the disk, rather than the processes with short bursts
+/// of I/O. The time spent waiting for block I/O is only accounted for when the kernel tracks the delays of tasks
+/// (`delayacct` boot parameter, or `kernel.task_delayacct` sysctl), so that the metric is zero otherwise.
+pub struct DiskWaitProbe {
+    uptime_reader: Box<dyn ReadSystemData<Uptime>>,
+    pid_stat_reader: Box<dyn ReadProcessData<PidStat>>,
+    clock_ticks: u64,
+    uptime_ticks: u64,
+}
+
+impl DiskWaitProbe {
+    /// Creates a new probe that can measure the lifetime disk wait of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many files descriptor the probe should keep open at most
+    pub fn new(fd_limit: usize) -> Result<Self, Error> {
+        let uptime_reader = SystemDataReader::new()
+            .map_err(|e| Error::ProbingError("Could not access /proc directory".to_string(), e.into()))?;
+        let clock_ticks =
+            clock_ticks().map_err(|e| Error::ProbingError("Could not read clock ticks".to_string(), e.into()))?;
+
+        Ok(Self::from_readers(
+            Box::new(uptime_reader),
+            Box::new(ProcessDataReader::with_capacity(fd_limit)),
+            clock_ticks,
+        ))
+    }
+
+    fn from_readers(
+        uptime_reader: Box<dyn ReadSystemData<Uptime>>,
+        pid_stat_reader: Box<dyn ReadProcessData<PidStat>>,
+        clock_ticks: u64,
+    ) -> Self {
+        DiskWaitProbe {
+            uptime_reader,
+            pid_stat_reader,
+            clock_ticks,
+            uptime_ticks: 0,
+        }
+    }
+}
+
+impl Probe<PercentMetric> for DiskWaitProbe {
+    fn name(&self) -> &'static str {
+        "Disk wait"
+    }
+
+    fn init_iteration(&mut self) -> Result<(), Error> {
+        let uptime = self
+            .uptime_reader
+            .read()
+            .map_err(|e| Error::ProbingError("Could not read system uptime".to_string(), e.into()))?;
+
+        self.uptime_ticks = uptime.uptime_ticks(self.clock_ticks);
+
+        Ok(())
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<PercentMetric, Error> {
+        let pid_stat = self
+            .pid_stat_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read process stats for PID {}", pid), e.into()))?;
+
+        let age_ticks = self.uptime_ticks.saturating_sub(pid_stat.starttime());
+
+        Ok(PercentMetric::new(wait_share(pid_stat.blkio_ticks(), age_ticks)))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().for_each(|pid| self.pid_stat_reader.cleanup(*pid));
+    }
+}
+
+/// Calculates the portion of the life of a process that it spent waiting for block I/O
+///
+/// # Arguments
+///  * `blkio_ticks`: The time spent by the process waiting for block I/O, in clock ticks
+///  * `age_ticks`: The time elapsed since the process started, in clock ticks
+fn wait_share(blkio_ticks: u64, age_ticks: u64) -> f64 {
+    if age_ticks == 0 {
+        return 0.;
+    }
+
+    (100. * blkio_ticks as f64 / age_ticks as f64).min(100.)
+}
+
+#[cfg(test)]
+mod test_wait_share {
+    use rstest::*;
+
+    use crate::procfs::disk_wait_probe::wait_share;
+
+    #[rstest]
+    #[case(0, 100, 0.)]
+    #[case(25, 100, 25.)]
+    #[case(100, 100, 100.)]
+    #[case(30, 400, 7.5)]
+    fn test_should_divide_blkio_time_by_age(#[case] blkio_ticks: u64, #[case] age_ticks: u64, #[case] expected: f64) {
+        assert_eq!(wait_share(blkio_ticks, age_ticks), expected);
+    }
+
+    #[test]
+    fn test_should_be_zero_when_process_just_started() {
+        assert_eq!(wait_share(10, 0), 0.);
+    }
+
+    #[test]
+    fn test_should_not_exceed_hundred_percent() {
+        // With an uptime precise to the second, a young process can appear to have waited longer than its age
+        assert_eq!(wait_share(150, 100), 100.);
+    }
+}
+
+#[cfg(test)]
+mod test_disk_wait_probe {
+    use crate::core::metrics::PercentMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::disk_wait_probe::DiskWaitProbe;
+    use crate::procfs::parsers::fakes::{FakeProcessDataReader, FakeSystemDataReader};
+    use crate::procfs::parsers::process::PidStat;
+    use crate::procfs::parsers::system::Uptime;
+
+    const CLOCK_TICKS: u64 = 100;
+
+    #[test]
+    fn test_should_compute_wait_share_from_blkio_time_and_age_of_process() {
+        // The system booted 100s ago, and the process started 20s after boot
+        let uptime_reader = FakeSystemDataReader::from_sequence(vec![Uptime::new(100)]);
+        let mut pid_stat_reader = FakeProcessDataReader::new();
+        let pid_stat = PidStat::new(0, 0, 0, 0, 20 * CLOCK_TICKS).with_blkio_ticks(20 * CLOCK_TICKS);
+        pid_stat_reader.set_pid_sequence(1, vec![pid_stat]);
+
+        let mut probe = DiskWaitProbe::from_readers(Box::new(uptime_reader), Box::new(pid_stat_reader), CLOCK_TICKS);
+
+        // The process waited for the disk for 20s of its 80s lifetime
+        assert_eq!(
+            probe.probe_processes(&[1]).unwrap(),
+            hashmap!(1 => PercentMetric::new(25.))
+        );
+    }
+}
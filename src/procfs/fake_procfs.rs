@@ -0,0 +1,320 @@
+//! Test harness mimicking the proc filesystem in a temporary directory
+//!
+//! Contrary to the fakes of the parsers module, the files written by this harness are read by the actual scanner,
+//! parsers and probes, which allows to test them together.
+
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tempfile::{tempdir, TempDir};
+
+use crate::core::process::Pid;
+use crate::procfs::test_utils::ProcRootOverride;
+
+/// A fake proc filesystem, from which the proc filesystem is read until it is dropped
+pub struct FakeProcfs {
+    _proc_root: ProcRootOverride,
+    dir: TempDir,
+}
+
+impl FakeProcfs {
+    /// Creates a fake proc filesystem without any process, of a system which has just booted
+    pub fn new() -> Self {
+        let dir = tempdir().expect("Could not create fake procfs dir");
+        let procfs = FakeProcfs {
+            _proc_root: ProcRootOverride::new(dir.path()),
+            dir,
+        };
+
+        procfs.write_file("uptime", "0.00 0.00\n");
+        procfs.set_cpu_time(0, 0, 0);
+
+        procfs
+    }
+
+    /// Sets the time elapsed since the system booted, from which the spawn time of processes is computed
+    pub fn set_uptime(&self, uptime: Duration) {
+        self.write_file("uptime", &format!("{:.2} 0.00\n", uptime.as_secs_f64()));
+    }
+
+    /// Sets the amount of ticks that the CPUs of the system spent in user mode, system mode and idle
+    pub fn set_cpu_time(&self, user: u64, system: u64, idle: u64) {
+        let content = format!(
+            "cpu  {} 0 {} {} 0 0 0 0 0 0\ncpu0 {} 0 {} {} 0 0 0 0 0 0\n",
+            user, system, idle, user, system, idle
+        );
+        self.write_file("stat", &content);
+    }
+
+    /// Adds a process which has not consumed any resource yet
+    pub fn spawn_process(&self, pid: Pid, command: &str) {
+        self.spawn_child_process(pid, 1, command);
+    }
+
+    /// Adds a process spawned by the process `ppid`, which has not consumed any resource yet
+    pub fn spawn_child_process(&self, pid: Pid, ppid: Pid, command: &str) {
+        fs::create_dir(self.path().join(pid.to_string())).expect("Could not create fake process dir");
+
+        self.write_process_file(pid, "comm", &format!("{}\n", command));
+        self.write_process_file(
+            pid,
+            "status",
+            &format!(
+                "Name:\t{}\nTgid:\t{}\nPPid:\t{}\nvoluntary_ctxt_switches:\t0\n",
+                command, pid, ppid
+            ),
+        );
+        self.set_process_cpu_time(pid, 0, 0);
+        self.set_process_io(pid, 0, 0);
+    }
+
+    /// Removes a process, as if it had died
+    pub fn kill_process(&self, pid: Pid) {
+        fs::remove_dir_all(self.path().join(pid.to_string())).expect("Could not remove fake process dir");
+    }
+
+    /// Sets the amount of ticks that a process spent in user mode and system mode
+    pub fn set_process_cpu_time(&self, pid: Pid, utime: u64, stime: u64) {
+        let content = format!(
+            "{} (fake) S 1 {} {} 0 -1 4194304 0 0 0 0 {} {} 0 0 20 0 1 0 0 0 0\n",
+            pid, pid, pid, utime, stime
+        );
+        self.write_process_file(pid, "stat", &content);
+    }
+
+    /// Sets the amount of bytes that a process read from and wrote to the disk
+    pub fn set_process_io(&self, pid: Pid, read_bytes: usize, write_bytes: usize) {
+        let content = format!(
+            "rchar: {}\nwchar: {}\nsyscr: 0\nsyscw: 0\nread_bytes: {}\nwrite_bytes: {}\ncancelled_write_bytes: 0\n",
+            read_bytes, write_bytes, read_bytes, write_bytes
+        );
+        self.write_process_file(pid, "io", &content);
+    }
+
+    /// Sets the amount of memory pages of a process which are resident in RAM
+    pub fn set_resident_pages(&self, pid: Pid, resident_pages: u64) {
+        let content = format!("{} {} 0 0 0 0 0\n", resident_pages * 2, resident_pages);
+        self.write_process_file(pid, "statm", &content);
+    }
+
+    /// Replaces the file descriptors opened by a process with `count` descriptors
+    pub fn set_open_files(&self, pid: Pid, count: usize) {
+        let fd_dir = self.path().join(pid.to_string()).join("fd");
+        let _ = fs::remove_dir_all(&fd_dir);
+        fs::create_dir(&fd_dir).expect("Could not create fake fd dir");
+
+        for fd in 0..count {
+            fs::write(fd_dir.join(fd.to_string()), "").expect("Could not create fake fd");
+        }
+    }
+
+    /// Sets the soft limit of files that a process can open, or makes it unlimited if `limit` is `None`
+    pub fn set_open_files_limit(&self, pid: Pid, limit: Option<u64>) {
+        let limit = limit.map_or("unlimited".to_string(), |limit| limit.to_string());
+        let content = format!(
+            "Limit                     Soft Limit           Hard Limit           Units     \n\
+             Max open files            {:<20} unlimited            files     \n",
+            limit
+        );
+        self.write_process_file(pid, "limits", &content);
+    }
+
+    /// Replaces the memory mappings of a process with mappings of the given files, in the given order
+    pub fn set_mapped_files(&self, pid: Pid, paths: &[&str]) {
+        let content: String = paths
+            .iter()
+            .enumerate()
+            .map(|(idx, path)| {
+                format!(
+                    "7f2c4a{:02x}0000-7f2c4a{:02x}0000 r--p 00000000 08:01 {:<10} {}\n",
+                    idx,
+                    idx + 1,
+                    1000 + idx,
+                    path
+                )
+            })
+            .collect();
+        self.write_process_file(pid, "maps", &content);
+    }
+
+    /// Sets the OOM score of a process
+    ///
+    /// # Arguments
+    ///  * `process_dir`: The directory of the process: its PID, or `self` for the process reading the filesystem
+    ///  * `score`: The OOM score of the process
+    pub fn set_oom_score(&self, process_dir: &str, score: u32) {
+        fs::create_dir_all(self.path().join(process_dir)).expect("Could not create fake process dir");
+        self.write_file(&format!("{}/oom_score", process_dir), &format!("{}\n", score));
+    }
+
+    /// Sets the PID and mount namespaces of a process
+    ///
+    /// # Arguments
+    ///  * `process_dir`: The directory of the process: its PID, or `self` for the process reading the filesystem
+    ///  * `pid_ns`, `mnt_ns`: The inodes of the namespaces of the process
+    pub fn set_namespaces(&self, process_dir: &str, pid_ns: u64, mnt_ns: u64) {
+        let ns_dir = self.path().join(process_dir).join("ns");
+        fs::create_dir_all(&ns_dir).expect("Could not create fake namespaces dir");
+
+        for (kind, inode) in [("pid", pid_ns), ("mnt", mnt_ns)] {
+            let link = ns_dir.join(kind);
+            let _ = fs::remove_file(&link);
+            symlink(format!("{}:[{}]", kind, inode), link).expect("Could not create fake namespace link");
+        }
+    }
+
+    /// Opens a file descriptor of a process on the given file, replacing the file it was opened on, if any
+    ///
+    /// # Arguments
+    ///  * `pid`, `fd`: The process and its file descriptor
+    ///  * `target`: The path of the file, to which the link of the file descriptor points
+    ///  * `fdinfo`: The content of the fdinfo file of the file descriptor
+    #[cfg(feature = "gpu")]
+    pub fn open_file(&self, pid: Pid, fd: u32, target: &str, fdinfo: &str) {
+        let process_dir = self.path().join(pid.to_string());
+        fs::create_dir_all(process_dir.join("fd")).expect("Could not create fake fd dir");
+        fs::create_dir_all(process_dir.join("fdinfo")).expect("Could not create fake fdinfo dir");
+
+        let link = process_dir.join("fd").join(fd.to_string());
+        let _ = fs::remove_file(&link);
+        symlink(target, link).expect("Could not create fake fd link");
+        self.write_process_file(pid, &format!("fdinfo/{}", fd), fdinfo);
+    }
+
+    fn path(&self) -> PathBuf {
+        self.dir.path().to_path_buf()
+    }
+
+    fn write_process_file(&self, pid: Pid, name: &str, content: &str) {
+        self.write_file(&format!("{}/{}", pid, name), content);
+    }
+
+    fn write_file(&self, name: &str, content: &str) {
+        fs::write(self.path().join(name), content).expect("Could not write fake procfs file");
+    }
+}
+
+mod test_end_to_end {
+    use std::time::Duration;
+
+    use rstest::*;
+    use sn_fake_clock::FakeClock;
+
+    use crate::core::metrics::{IOMetric, PercentMetric};
+    use crate::core::probe::Probe;
+    use crate::core::process::ProcessCollector;
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::core::time::{refresh_current_timestamp, Timestamp};
+    use crate::procfs::cpu_probe::CpuProbe;
+    use crate::procfs::diskio_probe::DiskIOProbe;
+    use crate::procfs::fake_procfs::FakeProcfs;
+    use crate::procfs::process::ProcfsScanner;
+
+    #[fixture]
+    fn procfs() -> FakeProcfs {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let procfs = FakeProcfs::new();
+        procfs.spawn_process(10, "compiler");
+        procfs.spawn_process(20, "editor");
+        procfs
+    }
+
+    fn build_collector() -> ProcessCollector {
+        ProcessCollector::new(Box::new(ProcfsScanner::new().expect("Could not create procfs scanner")))
+    }
+
+    fn next_iteration(collector: &mut ProcessCollector) {
+        FakeClock::advance_time(1000);
+        refresh_current_timestamp();
+        collector.collect_processes().expect("Could not collect processes");
+    }
+
+    #[rstest]
+    fn test_should_measure_cpu_usage_of_scanned_processes(procfs: FakeProcfs) {
+        let mut collector = build_collector();
+        let mut probe = CpuProbe::new(10).unwrap();
+
+        collector.collect_processes().expect("Could not collect processes");
+        probe.probe_processes(&collector.running_pids()).unwrap(); // calibrating probe
+
+        procfs.set_cpu_time(100, 50, 50);
+        procfs.set_process_cpu_time(10, 40, 10);
+        procfs.set_process_cpu_time(20, 80, 20);
+        next_iteration(&mut collector);
+
+        assert_eq!(
+            probe.probe_processes(&collector.running_pids()).unwrap(),
+            hashmap!(10 => PercentMetric::new(25.), 20 => PercentMetric::new(50.))
+        );
+    }
+
+    #[rstest]
+    fn test_should_measure_disk_read_rate_of_scanned_processes(procfs: FakeProcfs) {
+        let mut collector = build_collector();
+        let mut probe = DiskIOProbe::new(10);
+
+        collector.collect_processes().expect("Could not collect processes");
+        probe.probe_processes(&collector.running_pids()).unwrap();
+
+        procfs.set_process_io(10, 4096, 0);
+        procfs.set_process_io(20, 0, 1024);
+        next_iteration(&mut collector);
+
+        assert_eq!(
+            probe.probe_processes(&collector.running_pids()).unwrap(),
+            hashmap!(10 => IOMetric::new(4096, 0), 20 => IOMetric::new(0, 1024))
+        );
+    }
+
+    #[rstest]
+    fn test_should_detect_processes_isolated_in_other_namespaces(procfs: FakeProcfs) {
+        procfs.set_namespaces("self", 100, 200);
+        procfs.set_namespaces("10", 100, 200);
+        procfs.set_namespaces("20", 101, 201);
+        let mut collector = build_collector();
+
+        collector.collect_processes().expect("Could not collect processes");
+
+        let mut isolation: Vec<_> = collector
+            .running_processes()
+            .iter()
+            .map(|pm| (pm.pid(), pm.is_isolated()))
+            .collect();
+        isolation.sort();
+        assert_eq!(isolation, vec![(10, false), (20, true)]);
+    }
+
+    #[rstest]
+    fn test_should_distinguish_spawn_time_of_running_process_from_its_observation(procfs: FakeProcfs) {
+        // The processes started with the system, 100s before spv
+        procfs.set_uptime(Duration::from_secs(100));
+        let mut collector = build_collector();
+
+        collector.collect_processes().expect("Could not collect processes");
+
+        let process = &collector.running_processes()[0];
+        assert_eq!(process.spawn_time(), Timestamp::now() - Duration::from_secs(100));
+        assert_eq!(process.first_seen(), Timestamp::now());
+    }
+
+    #[rstest]
+    fn test_should_stop_probing_killed_processes(procfs: FakeProcfs) {
+        let mut collector = build_collector();
+        let mut probe = CpuProbe::new(10).unwrap();
+
+        collector.collect_processes().expect("Could not collect processes");
+        probe.probe_processes(&collector.running_pids()).unwrap();
+
+        procfs.kill_process(20);
+        procfs.set_cpu_time(100, 0, 100);
+        procfs.set_process_cpu_time(10, 50, 0);
+        next_iteration(&mut collector);
+
+        assert_eq!(
+            probe.probe_processes(&collector.running_pids()).unwrap(),
+            hashmap!(10 => PercentMetric::new(25.))
+        );
+    }
+}
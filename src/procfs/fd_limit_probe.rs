@@ -0,0 +1,141 @@
+//! File descriptor limit usage probing
+
+use std::fs::read_dir;
+
+use crate::core::metrics::PercentMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::parsers::process::PidLimits;
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData};
+use crate::procfs::{proc_root, ProcfsError};
+
+/// Probe implementation to measure how close processes are to exhausting their file descriptors
+///
+/// The files opened by a process, as listed in `/proc/[pid]/fd`, are compared to the soft limit of open files of the
+/// process, read from `/proc/[pid]/limits`. A process which reaches this limit can not open any more files or
+/// sockets.
+pub struct FdLimitProbe {
+    limits_reader: Box<dyn ReadProcessData<PidLimits>>,
+}
+
+impl FdLimitProbe {
+    /// Creates a new probe that can measure the usage of the file descriptor limit of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many files descriptor the probe should keep open at most
+    pub fn new(fd_limit: usize) -> Self {
+        Self::from_reader(Box::new(ProcessDataReader::with_capacity(fd_limit)))
+    }
+
+    fn from_reader(limits_reader: Box<dyn ReadProcessData<PidLimits>>) -> Self {
+        FdLimitProbe { limits_reader }
+    }
+}
+
+impl Probe<PercentMetric> for FdLimitProbe {
+    fn name(&self) -> &'static str {
+        "Open files limit"
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<PercentMetric, Error> {
+        let open_files = count_open_files(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not list open files of PID {}", pid), e.into()))?;
+        let limits = self
+            .limits_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read limits of PID {}", pid), e.into()))?;
+
+        Ok(PercentMetric::new(limit_usage(open_files, limits.max_open_files())))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().for_each(|pid| self.limits_reader.cleanup(*pid));
+    }
+}
+
+/// Counts the file descriptors opened by a process, as listed in `/proc/[pid]/fd`
+fn count_open_files(pid: Pid) -> Result<usize, ProcfsError> {
+    Ok(read_dir(proc_root().join(pid.to_string()).join("fd"))?.count())
+}
+
+/// Returns the percentage of the limit of open files used by a process
+///
+/// # Arguments
+///  * `open_files`: The amount of files opened by the process
+///  * `limit`: The soft limit of open files of the process, or `None` if it is unlimited
+fn limit_usage(open_files: usize, limit: Option<u64>) -> f64 {
+    match limit {
+        Some(limit) if limit > 0 => 100. * open_files as f64 / limit as f64,
+        // A process which can not open any file has exhausted its limit as soon as it tries to
+        Some(_) => 100.,
+        None => 0.,
+    }
+}
+
+#[cfg(test)]
+mod test_limit_usage {
+    use rstest::*;
+
+    use crate::procfs::fd_limit_probe::limit_usage;
+
+    #[rstest]
+    #[case(256, Some(1024), 25.)]
+    #[case(1024, Some(1024), 100.)]
+    #[case(0, Some(1024), 0.)]
+    #[case(100_000, None, 0.)]
+    #[case(3, Some(0), 100.)]
+    fn test_should_compute_percentage_of_limit(
+        #[case] open_files: usize,
+        #[case] limit: Option<u64>,
+        #[case] expected: f64,
+    ) {
+        assert_eq!(limit_usage(open_files, limit), expected);
+    }
+}
+
+#[cfg(test)]
+mod test_fd_limit_probe {
+    use rstest::*;
+
+    use crate::core::metrics::PercentMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::fake_procfs::FakeProcfs;
+    use crate::procfs::fd_limit_probe::FdLimitProbe;
+
+    #[fixture]
+    fn procfs() -> FakeProcfs {
+        let procfs = FakeProcfs::new();
+        procfs.spawn_process(10, "nginx");
+        procfs.set_open_files(10, 64);
+        procfs.set_open_files_limit(10, Some(256));
+
+        procfs
+    }
+
+    #[rstest]
+    fn test_should_measure_open_files_relative_to_soft_limit(_procfs: FakeProcfs) {
+        let mut probe = FdLimitProbe::new(10);
+
+        assert_eq!(probe.probe(10).unwrap(), PercentMetric::new(25.));
+    }
+
+    #[rstest]
+    fn test_should_follow_changes_of_open_files(procfs: FakeProcfs) {
+        let mut probe = FdLimitProbe::new(10);
+        probe.probe(10).unwrap();
+
+        procfs.set_open_files(10, 128);
+
+        assert_eq!(probe.probe(10).unwrap(), PercentMetric::new(50.));
+    }
+
+    #[rstest]
+    fn test_should_fail_to_probe_process_without_limits(procfs: FakeProcfs) {
+        procfs.spawn_process(20, "sshd");
+        procfs.set_open_files(20, 3);
+        let mut probe = FdLimitProbe::new(10);
+
+        assert!(probe.probe(20).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+//! Disk space usage of the filesystems listed in `/proc/mounts`
+
+use std::path::Path;
+
+use log::warn;
+
+use crate::core::filesystems::{FilesystemScanner, FilesystemUsage};
+use crate::core::Error;
+use crate::procfs::libc::filesystem_space;
+use crate::procfs::parsers::system::{MountEntry, Mounts};
+use crate::procfs::parsers::{ReadSystemData, SystemDataReader};
+
+/// The types of the filesystems which do not store files on a disk, such as the proc filesystem
+const PSEUDO_FS_TYPES: [&str; 23] = [
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "devtmpfs",
+    "efivarfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "nsfs",
+    "proc",
+    "pstore",
+    "ramfs",
+    "rpc_pipefs",
+    "securityfs",
+    "selinuxfs",
+    "sysfs",
+    "tracefs",
+    "tmpfs",
+];
+
+/// Implementation of FilesystemScanner which measures the filesystems listed in `/proc/mounts` with statvfs
+pub struct StatvfsScanner {
+    mounts_reader: Box<dyn ReadSystemData<Mounts>>,
+    include_tmpfs: bool,
+}
+
+impl StatvfsScanner {
+    /// Creates a scanner of the mounted filesystems
+    ///
+    /// # Arguments
+    ///  * `include_tmpfs`: If true, the tmpfs filesystems, which are stored in memory, are listed along with the
+    ///    filesystems stored on disks
+    pub fn new(include_tmpfs: bool) -> Result<Self, Error> {
+        let mounts_reader = SystemDataReader::new()
+            .map_err(|e| Error::ProbingError("Could not access /proc/mounts".to_string(), e.into()))?;
+
+        Ok(Self::from_reader(Box::new(mounts_reader), include_tmpfs))
+    }
+
+    fn from_reader(mounts_reader: Box<dyn ReadSystemData<Mounts>>, include_tmpfs: bool) -> Self {
+        Self {
+            mounts_reader,
+            include_tmpfs,
+        }
+    }
+
+    /// Returns the mounted filesystems which store files on a disk, or in memory for tmpfs if included
+    fn disk_filesystems(&mut self) -> Result<Vec<MountEntry>, Error> {
+        let mounts = self
+            .mounts_reader
+            .read()
+            .map_err(|e| Error::ProbingError("Could not read /proc/mounts".to_string(), e.into()))?;
+
+        Ok(mounts
+            .entries()
+            .iter()
+            .filter(|entry| !is_pseudo_filesystem(entry.fs_type(), self.include_tmpfs))
+            .cloned()
+            .collect())
+    }
+}
+
+impl FilesystemScanner for StatvfsScanner {
+    fn filesystems(&mut self) -> Result<Vec<FilesystemUsage>, Error> {
+        let usages = self
+            .disk_filesystems()?
+            .into_iter()
+            .filter_map(|entry| match filesystem_space(Path::new(entry.mount_point())) {
+                Ok(space) => Some(FilesystemUsage::new(
+                    entry.mount_point(),
+                    entry.fs_type(),
+                    space.used_bytes,
+                    space.available_bytes,
+                )),
+                Err(e) => {
+                    warn!("Could not measure filesystem {}: {}", entry.mount_point(), e);
+                    None
+                }
+            })
+            .collect();
+
+        Ok(usages)
+    }
+}
+
+/// Indicates if a filesystem of the given type does not store files on a disk
+///
+/// # Arguments
+///  * `fs_type`: The type of the filesystem, as listed in `/proc/mounts`
+///  * `include_tmpfs`: If true, tmpfs filesystems are not considered as pseudo-filesystems
+fn is_pseudo_filesystem(fs_type: &str, include_tmpfs: bool) -> bool {
+    if fs_type == "tmpfs" {
+        return !include_tmpfs;
+    }
+
+    PSEUDO_FS_TYPES.contains(&fs_type)
+}
+
+#[cfg(test)]
+mod test_pseudo_filesystems {
+    use rstest::*;
+
+    use crate::procfs::filesystems::is_pseudo_filesystem;
+
+    #[rstest]
+    #[case("proc")]
+    #[case("sysfs")]
+    #[case("cgroup2")]
+    #[case("devtmpfs")]
+    fn test_should_skip_pseudo_filesystems(#[case] fs_type: &str) {
+        assert!(is_pseudo_filesystem(fs_type, true));
+    }
+
+    #[rstest]
+    #[case("ext4")]
+    #[case("xfs")]
+    #[case("btrfs")]
+    #[case("vfat")]
+    fn test_should_keep_disk_filesystems(#[case] fs_type: &str) {
+        assert!(!is_pseudo_filesystem(fs_type, false));
+    }
+
+    #[rstest]
+    #[case(false, true)]
+    #[case(true, false)]
+    fn test_should_only_skip_tmpfs_when_not_included(#[case] include_tmpfs: bool, #[case] expected: bool) {
+        assert_eq!(is_pseudo_filesystem("tmpfs", include_tmpfs), expected);
+    }
+}
+
+#[cfg(test)]
+mod test_statvfs_scanner {
+    use crate::procfs::filesystems::StatvfsScanner;
+    use crate::procfs::parsers::fakes::FakeSystemDataReader;
+    use crate::procfs::parsers::system::{MountEntry, Mounts};
+
+    fn mounts() -> Mounts {
+        Mounts::new(vec![
+            MountEntry::new("/proc", "proc"),
+            MountEntry::new("/", "ext4"),
+            MountEntry::new("/run", "tmpfs"),
+            MountEntry::new("/sys", "sysfs"),
+            MountEntry::new("/home", "btrfs"),
+        ])
+    }
+
+    fn mount_points(scanner: &mut StatvfsScanner) -> Vec<String> {
+        scanner
+            .disk_filesystems()
+            .unwrap()
+            .iter()
+            .map(|entry| entry.mount_point().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_should_list_disk_filesystems_in_order_of_mounting() {
+        let reader = FakeSystemDataReader::from_sequence(vec![mounts()]);
+        let mut scanner = StatvfsScanner::from_reader(Box::new(reader), false);
+
+        assert_eq!(mount_points(&mut scanner), vec!["/", "/home"]);
+    }
+
+    #[test]
+    fn test_should_list_tmpfs_filesystems_when_included() {
+        let reader = FakeSystemDataReader::from_sequence(vec![mounts()]);
+        let mut scanner = StatvfsScanner::from_reader(Box::new(reader), true);
+
+        assert_eq!(mount_points(&mut scanner), vec!["/", "/run", "/home"]);
+    }
+}
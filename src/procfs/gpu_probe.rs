@@ -0,0 +1,799 @@
+//! GPU utilization probing
+//!
+//! The usage of the GPUs by processes is read from two sources:
+//!  * The DRM drivers (e.g. i915, amdgpu) report it for each of the clients that a process opened, in
+//!    `/proc/[pid]/fdinfo/[fd]`.
+//!  * The NVIDIA driver reports it through `nvidia-smi pmon` (see [`crate::procfs::nvidia_pmon`]).
+//!
+//! Not all drivers report the utilization of processes, in which case the probe falls back to measuring the GPU
+//! memory they allocated.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{read_dir, read_link, File};
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
+#[cfg(not(test))]
+use std::time::Instant;
+
+#[cfg(test)]
+use sn_fake_clock::FakeClock as Instant;
+
+use crate::core::metrics::{BytesMetric, PercentMetric};
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::nvidia_pmon::PmonUsageReader;
+use crate::procfs::parsers::process::DrmFdinfo;
+use crate::procfs::parsers::{FileDescriptorDataReader, ReadFileDescriptorData};
+use crate::procfs::{proc_root, ProcfsError};
+
+/// The directory of the device files of the DRM devices
+const DRM_DEVICES_DIR: &str = "/dev/dri";
+
+/// The cumulated time during which each engine of the GPUs was busy running the jobs of a process, in nanoseconds
+///
+/// Engines are identified by the GPU they belong to and by their name (e.g. `render`, `video`).
+type EngineTimes = HashMap<(String, String), u64>;
+
+/// The metric of the usage of GPUs which the GPU probe measures
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GpuMetric {
+    /// The share of time during which the GPUs ran the jobs of processes
+    Utilization,
+    /// The GPU memory allocated by processes
+    Memory,
+}
+
+/// The metrics of the usage of GPUs which a source reports
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub(crate) struct GpuSupport {
+    pub utilization: bool,
+    pub memory: bool,
+}
+
+impl GpuSupport {
+    /// Returns the metrics reported by either of the sources
+    fn union(self, other: GpuSupport) -> GpuSupport {
+        GpuSupport {
+            utilization: self.utilization || other.utilization,
+            memory: self.memory || other.memory,
+        }
+    }
+
+    /// Returns the metric to measure, which is the memory of processes when their utilization is not reported, or
+    /// `None` if no metric is reported
+    fn metric(&self) -> Option<GpuMetric> {
+        if self.utilization {
+            Some(GpuMetric::Utilization)
+        } else if self.memory {
+            Some(GpuMetric::Memory)
+        } else {
+            None
+        }
+    }
+}
+
+/// The usage of the GPUs by a process
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub(crate) struct GpuUsage {
+    /// The percentage of time during which the busiest GPU ran the jobs of the process
+    pub utilization: f64,
+    /// The GPU memory allocated by the process, in bytes
+    pub memory: u64,
+}
+
+impl GpuUsage {
+    /// Combines the usages of distinct GPUs, keeping the utilization of the busiest one
+    fn combine(self, other: GpuUsage) -> GpuUsage {
+        GpuUsage {
+            utilization: self.utilization.max(other.utilization),
+            memory: self.memory + other.memory,
+        }
+    }
+}
+
+/// Types which can read the usage of the GPUs by processes
+pub(crate) trait ReadGpuUsage {
+    /// The metrics that this source reports
+    fn support(&self) -> GpuSupport;
+
+    /// Called on each iteration, before the usage of processes is read
+    fn refresh(&mut self) -> Result<(), ProcfsError>;
+
+    fn read(&mut self, pid: Pid) -> Result<GpuUsage, ProcfsError>;
+
+    fn cleanup(&mut self, pid: Pid);
+}
+
+/// The sources of the usage of GPUs by processes which are available on the system
+pub struct GpuSources {
+    readers: Vec<Box<dyn ReadGpuUsage>>,
+}
+
+impl GpuSources {
+    /// Detects which sources report the usage of GPUs by processes
+    ///
+    /// Fails if neither the DRM drivers nor `nvidia-smi` report the utilization or the memory of processes.
+    ///
+    /// # Arguments
+    ///  * `max_fd`: The maximum amount of files that the sources can keep open
+    pub fn detect(max_fd: usize) -> Result<Self, Error> {
+        let mut readers: Vec<Box<dyn ReadGpuUsage>> = vec![Box::new(DrmUsageReader::new(max_fd))];
+        if let Some(pmon_reader) = PmonUsageReader::spawn() {
+            readers.push(Box::new(pmon_reader));
+        }
+
+        Self::from_readers(readers)
+    }
+
+    fn from_readers(readers: Vec<Box<dyn ReadGpuUsage>>) -> Result<Self, Error> {
+        let readers: Vec<_> = readers
+            .into_iter()
+            .filter(|reader| reader.support().metric().is_some())
+            .collect();
+
+        if readers.is_empty() {
+            return Err(Error::ProbingError(
+                "Could not find a GPU reporting the usage of processes".to_string(),
+                ProcfsError::InvalidFileContent("no DRM driver nor nvidia-smi reports GPU usage".to_string()).into(),
+            ));
+        }
+
+        Ok(GpuSources { readers })
+    }
+
+    /// The metric to measure with these sources: the utilization of processes if any source reports it, or their
+    /// memory otherwise
+    pub fn metric(&self) -> GpuMetric {
+        self.readers
+            .iter()
+            .fold(GpuSupport::default(), |support, reader| support.union(reader.support()))
+            .metric()
+            .expect("Sources which report no metric are discarded")
+    }
+
+    fn refresh(&mut self) -> Result<(), Error> {
+        self.readers
+            .iter_mut()
+            .try_for_each(|reader| reader.refresh())
+            .map_err(|e| Error::ProbingError("Could not read the usage of the GPUs".to_string(), e.into()))
+    }
+
+    fn read(&mut self, pid: Pid) -> Result<GpuUsage, Error> {
+        self.readers.iter_mut().try_fold(GpuUsage::default(), |usage, reader| {
+            let reader_usage = reader
+                .read(pid)
+                .map_err(|e| Error::ProbingError(format!("Could not read GPU usage of PID {}", pid), e.into()))?;
+            Ok(usage.combine(reader_usage))
+        })
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        for reader in self.readers.iter_mut() {
+            pids.iter().for_each(|pid| reader.cleanup(*pid));
+        }
+    }
+}
+
+/// Probe implementation to measure the GPU utilization of processes
+///
+/// The utilization of a process is the share of the time elapsed between two probings during which the busiest
+/// engine of its GPUs was running its jobs.
+///
+/// Processes which do not use any GPU have a utilization of 0%.
+pub struct GpuProbe {
+    sources: GpuSources,
+}
+
+impl GpuProbe {
+    /// Creates a new probe that can measure the GPU utilization of processes
+    ///
+    /// The sources should report the utilization of processes (see [`GpuSources::metric`]).
+    pub fn new(sources: GpuSources) -> Self {
+        GpuProbe { sources }
+    }
+}
+
+impl Probe<PercentMetric> for GpuProbe {
+    fn name(&self) -> &'static str {
+        "GPU"
+    }
+
+    fn init_iteration(&mut self) -> Result<(), Error> {
+        self.sources.refresh()
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<PercentMetric, Error> {
+        Ok(PercentMetric::new(self.sources.read(pid)?.utilization))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        self.sources.cleanup(pids);
+    }
+}
+
+/// Probe implementation to measure the GPU memory allocated by processes
+///
+/// This probe replaces [`GpuProbe`] when the GPUs do not report the utilization of processes.
+pub struct GpuMemoryProbe {
+    sources: GpuSources,
+}
+
+impl GpuMemoryProbe {
+    /// Creates a new probe that can measure the GPU memory of processes
+    pub fn new(sources: GpuSources) -> Self {
+        GpuMemoryProbe { sources }
+    }
+}
+
+impl Probe<BytesMetric> for GpuMemoryProbe {
+    fn name(&self) -> &'static str {
+        "GPU memory"
+    }
+
+    fn init_iteration(&mut self) -> Result<(), Error> {
+        self.sources.refresh()
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<BytesMetric, Error> {
+        Ok(BytesMetric::new(self.sources.read(pid)?.memory as usize))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        self.sources.cleanup(pids);
+    }
+}
+
+/// Reads the usage of GPUs by processes from the DRM clients that they opened
+///
+/// The drivers report the cumulated time during which each engine of the GPU was busy, from which the utilization is
+/// computed between two readings.
+pub(crate) struct DrmUsageReader {
+    clients_reader: Box<dyn ReadDrmClients>,
+    support: GpuSupport,
+    processes_prev_times: HashMap<Pid, (Instant, EngineTimes)>,
+}
+
+impl DrmUsageReader {
+    fn new(max_fd: usize) -> Self {
+        let clients_reader = FdinfoDrmClientsReader {
+            fdinfo_reader: Box::new(FileDescriptorDataReader::with_capacity(max_fd)),
+        };
+
+        Self::from_reader(Box::new(clients_reader), drm_support())
+    }
+
+    fn from_reader(clients_reader: Box<dyn ReadDrmClients>, support: GpuSupport) -> Self {
+        DrmUsageReader {
+            clients_reader,
+            support,
+            processes_prev_times: HashMap::new(),
+        }
+    }
+}
+
+impl ReadGpuUsage for DrmUsageReader {
+    fn support(&self) -> GpuSupport {
+        self.support
+    }
+
+    fn refresh(&mut self) -> Result<(), ProcfsError> {
+        Ok(())
+    }
+
+    fn read(&mut self, pid: Pid) -> Result<GpuUsage, ProcfsError> {
+        let clients = self.clients_reader.read(pid)?;
+
+        let now = Instant::now();
+        let (times, memory) = clients_usage(clients);
+
+        let utilization = match self.processes_prev_times.insert(pid, (now, times.clone())) {
+            Some((prev_date, prev_times)) => busiest_engine_usage(&prev_times, &times, now - prev_date),
+            None => 0.,
+        };
+
+        Ok(GpuUsage { utilization, memory })
+    }
+
+    fn cleanup(&mut self, pid: Pid) {
+        self.processes_prev_times.remove(&pid);
+        self.clients_reader.cleanup(pid);
+    }
+}
+
+/// Returns the metrics which the DRM drivers of the GPUs report
+///
+/// Opening a render node of a GPU makes spv a client of the GPU, whose fdinfo tells what the driver reports.
+fn drm_support() -> GpuSupport {
+    let Ok(entries) = read_dir(DRM_DEVICES_DIR) else {
+        return GpuSupport::default();
+    };
+
+    let mut render_nodes: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("renderD"))
+        .map(|entry| entry.path())
+        .collect();
+    render_nodes.sort();
+
+    let mut fdinfo_reader = FileDescriptorDataReader::<DrmFdinfo>::with_capacity(0);
+    render_nodes
+        .iter()
+        .filter_map(|node| File::open(node).ok())
+        .filter_map(|file| fdinfo_reader.read(process::id(), file.as_raw_fd() as u32).ok())
+        .map(|fdinfo| client_support(&fdinfo))
+        .fold(GpuSupport::default(), GpuSupport::union)
+}
+
+/// Returns the metrics which the driver of a DRM client reports
+fn client_support(fdinfo: &DrmFdinfo) -> GpuSupport {
+    GpuSupport {
+        utilization: !fdinfo.engines().is_empty(),
+        memory: fdinfo.memory().is_some(),
+    }
+}
+
+/// Types which can list the DRM clients opened by a process
+pub(crate) trait ReadDrmClients {
+    fn read(&mut self, pid: Pid) -> Result<Vec<DrmFdinfo>, ProcfsError>;
+
+    fn cleanup(&mut self, pid: Pid);
+}
+
+/// Lists the DRM clients of a process from the files of `/proc/[pid]/fdinfo`
+///
+/// Only the fdinfo files of the file descriptors opened on a DRM device are read.
+pub(crate) struct FdinfoDrmClientsReader {
+    fdinfo_reader: Box<dyn ReadFileDescriptorData<DrmFdinfo>>,
+}
+
+impl ReadDrmClients for FdinfoDrmClientsReader {
+    fn read(&mut self, pid: Pid) -> Result<Vec<DrmFdinfo>, ProcfsError> {
+        let fds = drm_fds(pid)?;
+        self.fdinfo_reader.retain(pid, &fds);
+
+        // The file descriptor may have been closed since it was listed, and the fdinfo of drivers which do not report
+        // the usage of their clients can not be parsed
+        let clients = fds
+            .iter()
+            .filter_map(|fd| self.fdinfo_reader.read(pid, *fd).ok())
+            .collect();
+
+        Ok(clients)
+    }
+
+    fn cleanup(&mut self, pid: Pid) {
+        self.fdinfo_reader.cleanup(pid);
+    }
+}
+
+/// Lists the file descriptors of a process whose links in `/proc/[pid]/fd` point to a DRM device
+fn drm_fds(pid: Pid) -> Result<Vec<u32>, ProcfsError> {
+    let fd_dir = proc_root().join(pid.to_string()).join("fd");
+
+    let fds = read_dir(fd_dir)?
+        .filter_map(|entry| entry.ok())
+        // The file descriptor may have been closed since the directory was listed
+        .filter(|entry| read_link(entry.path()).is_ok_and(|target| is_drm_device(&target)))
+        .filter_map(|entry| entry.file_name().to_str().and_then(|fd| fd.parse().ok()))
+        .collect();
+
+    Ok(fds)
+}
+
+/// Indicates if the target of a file descriptor link is the device file of a DRM device (e.g. `/dev/dri/renderD128`)
+fn is_drm_device(target: &Path) -> bool {
+    target.starts_with(DRM_DEVICES_DIR)
+}
+
+/// Sums the busy times of the engines and the memory of the given clients, counting duplicated clients once
+fn clients_usage(clients: Vec<DrmFdinfo>) -> (EngineTimes, u64) {
+    let mut seen_clients = HashSet::new();
+    let mut times = EngineTimes::new();
+    let mut memory = 0;
+
+    for client in clients {
+        if !seen_clients.insert((client.device().to_string(), client.client_id())) {
+            continue;
+        }
+
+        for (engine, busy_ns) in client.engines() {
+            *times.entry((client.device().to_string(), engine.clone())).or_default() += busy_ns;
+        }
+        memory += client.memory().unwrap_or(0);
+    }
+
+    (times, memory)
+}
+
+/// Returns the percentage of the elapsed time during which the busiest engine ran the jobs of a process
+///
+/// # Arguments
+///  * `prev`, `cur`: The previous and current busy times of the engines used by the process
+///  * `elapsed`: The time elapsed between both readings of the busy times
+fn busiest_engine_usage(prev: &EngineTimes, cur: &EngineTimes, elapsed: Duration) -> f64 {
+    if elapsed.is_zero() {
+        return 0.;
+    }
+
+    // Clients opened since the previous reading started from a busy time of 0
+    let busiest_ns = cur
+        .iter()
+        .map(|(engine, busy_ns)| busy_ns.saturating_sub(prev.get(engine).copied().unwrap_or(0)))
+        .max()
+        .unwrap_or(0);
+
+    (100. * busiest_ns as f64 / elapsed.as_nanos() as f64).min(100.)
+}
+
+#[cfg(test)]
+mod test_gpu_support {
+    use rstest::*;
+
+    use crate::procfs::gpu_probe::{GpuMetric, GpuSupport};
+
+    #[rstest]
+    #[case(true, true, Some(GpuMetric::Utilization))]
+    #[case(true, false, Some(GpuMetric::Utilization))]
+    #[case(false, true, Some(GpuMetric::Memory))]
+    #[case(false, false, None)]
+    fn test_should_fall_back_to_memory_without_utilization(
+        #[case] utilization: bool,
+        #[case] memory: bool,
+        #[case] expected: Option<GpuMetric>,
+    ) {
+        assert_eq!(GpuSupport { utilization, memory }.metric(), expected);
+    }
+}
+
+#[cfg(test)]
+mod test_gpu_sources {
+    use crate::core::metrics::{BytesMetric, PercentMetric};
+    use crate::core::probe::Probe;
+    use crate::core::process::Pid;
+    use crate::procfs::gpu_probe::{
+        GpuMemoryProbe, GpuMetric, GpuProbe, GpuSources, GpuSupport, GpuUsage, ReadGpuUsage,
+    };
+    use crate::procfs::ProcfsError;
+
+    /// Reports the same usage for all processes
+    struct FakeGpuUsageReader {
+        support: GpuSupport,
+        usage: GpuUsage,
+    }
+
+    impl FakeGpuUsageReader {
+        fn boxed(utilization: bool, memory: bool, usage: GpuUsage) -> Box<dyn ReadGpuUsage> {
+            Box::new(FakeGpuUsageReader {
+                support: GpuSupport { utilization, memory },
+                usage,
+            })
+        }
+    }
+
+    impl ReadGpuUsage for FakeGpuUsageReader {
+        fn support(&self) -> GpuSupport {
+            self.support
+        }
+
+        fn refresh(&mut self) -> Result<(), ProcfsError> {
+            Ok(())
+        }
+
+        fn read(&mut self, _pid: Pid) -> Result<GpuUsage, ProcfsError> {
+            Ok(self.usage)
+        }
+
+        fn cleanup(&mut self, _pid: Pid) {}
+    }
+
+    fn usage(utilization: f64, memory: u64) -> GpuUsage {
+        GpuUsage { utilization, memory }
+    }
+
+    #[test]
+    fn test_should_measure_utilization_when_a_source_reports_it() {
+        let sources = GpuSources::from_readers(vec![
+            FakeGpuUsageReader::boxed(false, true, usage(0., 0)),
+            FakeGpuUsageReader::boxed(true, true, usage(0., 0)),
+        ])
+        .unwrap();
+
+        assert_eq!(sources.metric(), GpuMetric::Utilization);
+    }
+
+    #[test]
+    fn test_should_fall_back_to_memory_when_no_source_reports_utilization() {
+        let sources = GpuSources::from_readers(vec![FakeGpuUsageReader::boxed(false, true, usage(0., 0))]).unwrap();
+
+        assert_eq!(sources.metric(), GpuMetric::Memory);
+    }
+
+    #[test]
+    fn test_should_be_unavailable_when_no_source_reports_usage() {
+        let sources = GpuSources::from_readers(vec![FakeGpuUsageReader::boxed(false, false, usage(0., 0))]);
+
+        assert!(sources.is_err());
+    }
+
+    #[test]
+    fn test_should_ignore_sources_which_report_no_usage() {
+        let sources = GpuSources::from_readers(vec![
+            FakeGpuUsageReader::boxed(false, false, usage(80., 4096)),
+            FakeGpuUsageReader::boxed(true, true, usage(20., 1024)),
+        ])
+        .unwrap();
+
+        assert_eq!(GpuProbe::new(sources).probe(1).unwrap(), PercentMetric::new(20.));
+    }
+
+    #[test]
+    fn test_should_keep_utilization_of_busiest_source() {
+        let sources = GpuSources::from_readers(vec![
+            FakeGpuUsageReader::boxed(true, true, usage(30., 1024)),
+            FakeGpuUsageReader::boxed(true, true, usage(70., 2048)),
+        ])
+        .unwrap();
+
+        assert_eq!(GpuProbe::new(sources).probe(1).unwrap(), PercentMetric::new(70.));
+    }
+
+    #[test]
+    fn test_should_sum_memory_of_sources() {
+        let sources = GpuSources::from_readers(vec![
+            FakeGpuUsageReader::boxed(false, true, usage(0., 1024)),
+            FakeGpuUsageReader::boxed(true, true, usage(70., 2048)),
+        ])
+        .unwrap();
+
+        assert_eq!(GpuMemoryProbe::new(sources).probe(1).unwrap(), BytesMetric::new(3072));
+    }
+}
+
+#[cfg(test)]
+mod test_client_support {
+    use std::collections::HashMap;
+
+    use rstest::*;
+
+    use crate::procfs::gpu_probe::{client_support, GpuSupport};
+    use crate::procfs::parsers::process::DrmFdinfo;
+
+    #[rstest]
+    #[case(hashmap!("render".to_string() => 0), Some(0), GpuSupport { utilization: true, memory: true })]
+    #[case(hashmap!(), Some(4096), GpuSupport { utilization: false, memory: true })]
+    #[case(hashmap!(), None, GpuSupport { utilization: false, memory: false })]
+    fn test_should_support_metrics_reported_by_driver(
+        #[case] engines: HashMap<String, u64>,
+        #[case] memory: Option<u64>,
+        #[case] expected: GpuSupport,
+    ) {
+        let fdinfo = DrmFdinfo::new(1, "0000:00:02.0", engines, memory);
+
+        assert_eq!(client_support(&fdinfo), expected);
+    }
+}
+
+#[cfg(test)]
+mod test_fdinfo_drm_clients_reader {
+    use crate::procfs::fake_procfs::FakeProcfs;
+    use crate::procfs::gpu_probe::{FdinfoDrmClientsReader, ReadDrmClients};
+    use crate::procfs::parsers::FileDescriptorDataReader;
+
+    const DRM_FDINFO: &str = "pos:\t0\nflags:\t02100002\ndrm-driver:\ti915\ndrm-pdev:\t0000:00:02.0\n\
+                              drm-client-id:\t17\ndrm-engine-render:\t500 ns\n";
+
+    fn reader() -> FdinfoDrmClientsReader {
+        FdinfoDrmClientsReader {
+            fdinfo_reader: Box::new(FileDescriptorDataReader::with_capacity(10)),
+        }
+    }
+
+    #[test]
+    fn test_should_only_read_fdinfo_of_drm_devices() {
+        let procfs = FakeProcfs::new();
+        procfs.spawn_process(1, "renderer");
+        procfs.open_file(1, 3, "/dev/dri/renderD128", DRM_FDINFO);
+        // Files other than DRM devices are left unread, whatever their fdinfo
+        procfs.open_file(1, 4, "/tmp/frames", &DRM_FDINFO.replace("17", "18"));
+
+        let clients = reader().read(1).unwrap();
+
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].client_id(), 17);
+    }
+
+    #[test]
+    fn test_should_skip_drm_devices_whose_driver_does_not_report_usage() {
+        let procfs = FakeProcfs::new();
+        procfs.spawn_process(1, "renderer");
+        procfs.open_file(1, 3, "/dev/dri/card0", "pos:\t0\nflags:\t02100002\n");
+
+        assert!(reader().read(1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_should_stop_reading_file_descriptor_once_reopened_on_other_file() {
+        let procfs = FakeProcfs::new();
+        procfs.spawn_process(1, "renderer");
+        procfs.open_file(1, 3, "/dev/dri/renderD128", DRM_FDINFO);
+        let mut reader = reader();
+        assert_eq!(reader.read(1).unwrap().len(), 1);
+
+        procfs.open_file(1, 3, "/tmp/frames", DRM_FDINFO);
+
+        assert!(reader.read(1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_should_fail_to_read_clients_of_unknown_process() {
+        let _procfs = FakeProcfs::new();
+
+        assert!(reader().read(1).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_clients_usage {
+    use crate::procfs::gpu_probe::clients_usage;
+    use crate::procfs::parsers::process::DrmFdinfo;
+
+    #[test]
+    fn test_should_count_memory_of_duplicated_clients_once() {
+        let clients = vec![
+            DrmFdinfo::new(7, "0000:01:00.0", hashmap!(), Some(1024)),
+            DrmFdinfo::new(7, "0000:01:00.0", hashmap!(), Some(1024)),
+            DrmFdinfo::new(8, "0000:01:00.0", hashmap!(), Some(2048)),
+            DrmFdinfo::new(7, "0000:02:00.0", hashmap!(), None),
+        ];
+
+        assert_eq!(clients_usage(clients).1, 3072);
+    }
+}
+
+#[cfg(test)]
+mod test_busiest_engine_usage {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::procfs::gpu_probe::{busiest_engine_usage, EngineTimes};
+
+    fn times(render_ns: u64, video_ns: u64) -> EngineTimes {
+        hashmap!(
+            ("gpu".to_string(), "render".to_string()) => render_ns,
+            ("gpu".to_string(), "video".to_string()) => video_ns
+        )
+    }
+
+    #[rstest]
+    #[case(times(0, 0), times(250_000_000, 0), 25.)]
+    #[case(times(0, 0), times(100_000_000, 600_000_000), 60.)]
+    #[case(times(500, 500), times(500, 500), 0.)]
+    #[case(EngineTimes::new(), times(400_000_000, 0), 40.)]
+    #[case(times(0, 0), times(3_000_000_000, 0), 100.)]
+    fn test_should_measure_share_of_time_of_busiest_engine(
+        #[case] prev: EngineTimes,
+        #[case] cur: EngineTimes,
+        #[case] expected: f64,
+    ) {
+        assert_eq!(busiest_engine_usage(&prev, &cur, Duration::from_secs(1)), expected);
+    }
+
+    #[test]
+    fn test_should_be_zero_without_engines() {
+        assert_eq!(
+            busiest_engine_usage(&EngineTimes::new(), &EngineTimes::new(), Duration::from_secs(1)),
+            0.
+        );
+    }
+
+    #[test]
+    fn test_should_be_zero_when_no_time_elapsed() {
+        assert_eq!(busiest_engine_usage(&times(0, 0), &times(500, 0), Duration::ZERO), 0.);
+    }
+}
+
+#[cfg(test)]
+mod test_drm_usage_reader {
+    use std::collections::HashMap;
+
+    use sn_fake_clock::FakeClock;
+
+    use crate::core::process::Pid;
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::procfs::gpu_probe::{DrmUsageReader, GpuSupport, GpuUsage, ReadDrmClients, ReadGpuUsage};
+    use crate::procfs::parsers::process::DrmFdinfo;
+    use crate::procfs::ProcfsError;
+
+    /// Reads the DRM clients of processes from a sequence of readings, one for each probing
+    struct FakeDrmClientsReader {
+        sequences: HashMap<Pid, Vec<Vec<(u64, u64)>>>,
+    }
+
+    impl ReadDrmClients for FakeDrmClientsReader {
+        fn read(&mut self, pid: Pid) -> Result<Vec<DrmFdinfo>, ProcfsError> {
+            let sequence = self.sequences.get_mut(&pid).ok_or(ProcfsError::UnknownPID(pid))?;
+            let clients = sequence.remove(0);
+
+            Ok(clients
+                .into_iter()
+                .map(|(id, render_ns)| {
+                    DrmFdinfo::new(
+                        id,
+                        "0000:01:00.0",
+                        hashmap!("render".to_string() => render_ns),
+                        Some(1024),
+                    )
+                })
+                .collect())
+        }
+
+        fn cleanup(&mut self, _pid: Pid) {}
+    }
+
+    fn reader_with_clients(sequences: HashMap<Pid, Vec<Vec<(u64, u64)>>>) -> DrmUsageReader {
+        let support = GpuSupport {
+            utilization: true,
+            memory: true,
+        };
+        DrmUsageReader::from_reader(Box::new(FakeDrmClientsReader { sequences }), support)
+    }
+
+    #[test]
+    fn test_should_measure_utilization_between_readings() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let mut reader = reader_with_clients(hashmap!(
+            1 => vec![vec![(7, 1_000_000_000)], vec![(7, 1_500_000_000)]],
+            2 => vec![vec![], vec![]]
+        ));
+
+        reader.read(1).unwrap(); // calibrating reader
+        reader.read(2).unwrap();
+        FakeClock::advance_time(1000);
+
+        assert_eq!(
+            reader.read(1).unwrap(),
+            GpuUsage {
+                utilization: 50.,
+                memory: 1024
+            }
+        );
+        assert_eq!(reader.read(2).unwrap(), GpuUsage::default());
+    }
+
+    #[test]
+    fn test_should_count_duplicated_clients_once() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let mut reader = reader_with_clients(hashmap!(
+            1 => vec![vec![(7, 0), (7, 0)], vec![(7, 200_000_000), (7, 200_000_000)]]
+        ));
+
+        reader.read(1).unwrap();
+        FakeClock::advance_time(1000);
+
+        assert_eq!(reader.read(1).unwrap().utilization, 20.);
+    }
+
+    #[test]
+    fn test_should_sum_distinct_clients_of_same_engine() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let mut reader = reader_with_clients(hashmap!(
+            1 => vec![vec![(7, 0), (8, 0)], vec![(7, 200_000_000), (8, 300_000_000)]]
+        ));
+
+        reader.read(1).unwrap();
+        FakeClock::advance_time(1000);
+
+        assert_eq!(reader.read(1).unwrap().utilization, 50.);
+    }
+
+    #[test]
+    fn test_should_fail_to_read_unknown_process() {
+        let mut reader = reader_with_clients(HashMap::new());
+
+        assert!(reader.read(1).is_err());
+    }
+}
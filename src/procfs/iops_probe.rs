@@ -0,0 +1,129 @@
+//! Disk operations probing
+
+use std::time::Duration;
+
+use crate::core::metrics::OperationsMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::parsers::process::PidIO;
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData};
+use crate::procfs::rates::{ProcessesRates, PushMode};
+
+const IOPS_RATE_RETENTION: Duration = Duration::from_secs(1);
+
+/// Probe implementation to measure the rate of read and write operations of processes
+///
+/// Contrary to the [`DiskIOProbe`](crate::procfs::diskio_probe::DiskIOProbe), which measures throughput, this probe
+/// counts operations: a process making many small reads may have a low throughput, but a high operation rate.
+/// The operations are counted from the read and write syscalls, which include those on pipes and sockets.
+pub struct IopsProbe {
+    reader: Box<dyn ReadProcessData<PidIO>>,
+    reads_rate_calculator: ProcessesRates,
+    writes_rate_calculator: ProcessesRates,
+}
+
+impl IopsProbe {
+    /// Creates a new probe that can measure the rate of read and write operations of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many open file descriptors this probe can keep open at most
+    pub fn new(fd_limit: usize) -> Self {
+        Self::from_reader(Box::new(ProcessDataReader::with_capacity(fd_limit)))
+    }
+
+    fn from_reader(reader: Box<dyn ReadProcessData<PidIO>>) -> Self {
+        IopsProbe {
+            reader,
+            reads_rate_calculator: ProcessesRates::new(PushMode::Accumulative, IOPS_RATE_RETENTION),
+            writes_rate_calculator: ProcessesRates::new(PushMode::Accumulative, IOPS_RATE_RETENTION),
+        }
+    }
+}
+
+impl Probe<OperationsMetric> for IopsProbe {
+    fn name(&self) -> &'static str {
+        "Disk IOPS"
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<OperationsMetric, Error> {
+        let pid_io = self
+            .reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError("Could not read process IO stats".to_string(), e.into()))?;
+
+        self.reads_rate_calculator.push(pid, pid_io.read_syscalls());
+        let reads_rate = self
+            .reads_rate_calculator
+            .rate(pid)
+            .map_err(|e| Error::ProbingError("Could not calculate read operations rate".to_string(), e.into()))?;
+
+        self.writes_rate_calculator.push(pid, pid_io.write_syscalls());
+        let writes_rate = self
+            .writes_rate_calculator
+            .rate(pid)
+            .map_err(|e| Error::ProbingError("Could not calculate write operations rate".to_string(), e.into()))?;
+
+        Ok(OperationsMetric::new(reads_rate as usize, writes_rate as usize))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().copied().for_each(|pid| {
+            self.reader.cleanup(pid);
+            self.reads_rate_calculator.cleanup(pid);
+            self.writes_rate_calculator.cleanup(pid);
+        });
+    }
+}
+
+#[cfg(test)]
+mod test_iops_probe {
+    use rstest::*;
+    use sn_fake_clock::FakeClock;
+
+    use crate::core::metrics::OperationsMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::iops_probe::IopsProbe;
+    use crate::procfs::parsers::fakes::FakeProcessDataReader;
+    use crate::procfs::parsers::process::PidIO;
+
+    #[rstest]
+    #[case((0, 0), (0, 0), OperationsMetric::new(0, 0))]
+    #[case((100, 10), (150, 40), OperationsMetric::new(50, 30))]
+    #[case((100, 10), (100, 10), OperationsMetric::new(0, 0))]
+    fn test_should_calculate_operations_rate_between_two_samples(
+        #[case] prev_syscalls: (usize, usize),
+        #[case] cur_syscalls: (usize, usize),
+        #[case] expected: OperationsMetric,
+    ) {
+        let sequence = vec![
+            PidIO::new(0, 0, 0).with_syscalls(prev_syscalls.0, prev_syscalls.1),
+            PidIO::new(0, 0, 0).with_syscalls(cur_syscalls.0, cur_syscalls.1),
+        ];
+
+        let mut reader = FakeProcessDataReader::new();
+        reader.set_pid_sequence(1, sequence);
+
+        let mut probe = IopsProbe::from_reader(Box::new(reader));
+
+        let _ = probe.probe(1).unwrap();
+        FakeClock::advance_time(1000);
+
+        assert_eq!(probe.probe(1).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_should_not_count_transferred_bytes_as_operations() {
+        let sequence = vec![PidIO::new(0, 0, 0), PidIO::new(4096, 8192, 0).with_syscalls(1, 2)];
+
+        let mut reader = FakeProcessDataReader::new();
+        reader.set_pid_sequence(1, sequence);
+
+        let mut probe = IopsProbe::from_reader(Box::new(reader));
+
+        let _ = probe.probe(1).unwrap();
+        FakeClock::advance_time(1000);
+
+        assert_eq!(probe.probe(1).unwrap(), OperationsMetric::new(1, 2));
+    }
+}
@@ -1,4 +1,18 @@
-use libc::{getrlimit64, rlimit64, sysconf, RLIMIT_NOFILE, _SC_CLK_TCK};
+use std::ffi::CString;
+use std::io;
+use std::mem::{size_of, MaybeUninit};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+use libc::{
+    c_int, c_uchar, c_void, getrlimit64, kill, pid_t, recvfrom, rlimit64, sockaddr, sockaddr_ll, socket, socklen_t,
+    statvfs64, sysconf, _SC_CLK_TCK, _SC_NPROCESSORS_ONLN, _SC_PAGESIZE, AF_PACKET, ETH_P_ALL, MSG_TRUNC,
+    RLIMIT_NOFILE, SIGCONT, SIGKILL, SIGSTOP, SOCK_CLOEXEC, SOCK_DGRAM,
+};
+
+use crate::core::process::Pid;
+use crate::core::signal::ProcessSignal;
 
 use crate::procfs::ProcfsError;
 
@@ -26,6 +40,54 @@ mod test_clock_ticks {
     }
 }
 
+/// Returns the number of CPUs currently online
+pub(crate) fn online_cpus() -> Result<u64, ProcfsError> {
+    let online_cpus_value;
+
+    unsafe {
+        online_cpus_value = sysconf(_SC_NPROCESSORS_ONLN);
+    }
+
+    match online_cpus_value {
+        -1 => Err(ProcfsError::SysconfError),
+        _ => Ok(online_cpus_value as u64),
+    }
+}
+
+#[cfg(test)]
+mod test_online_cpus {
+    use crate::procfs::libc::online_cpus;
+
+    #[test]
+    fn test_should_count_at_least_one_cpu() {
+        assert!(online_cpus().unwrap() >= 1);
+    }
+}
+
+/// Returns the size of a memory page, in bytes
+pub(crate) fn page_size() -> Result<u64, ProcfsError> {
+    let page_size_value;
+
+    unsafe {
+        page_size_value = sysconf(_SC_PAGESIZE);
+    }
+
+    match page_size_value {
+        -1 => Err(ProcfsError::SysconfError),
+        _ => Ok(page_size_value as u64),
+    }
+}
+
+#[cfg(test)]
+mod test_page_size {
+    use crate::procfs::libc::page_size;
+
+    #[test]
+    fn test_should_get_page_size() {
+        assert!(page_size().unwrap() > 0);
+    }
+}
+
 /// Returns a value one greater than the maximum amount of files that this process can open at once
 pub fn open_file_limit() -> Result<u64, ProcfsError> {
     let return_value;
@@ -54,3 +116,152 @@ mod test_rlimit_nofile {
         assert!(open_file_limit().is_ok());
     }
 }
+
+/// Sends a signal to the process with the given PID
+pub(crate) fn send_signal(pid: Pid, signal: ProcessSignal) -> Result<(), ProcfsError> {
+    let signal_number = match signal {
+        ProcessSignal::Stop => SIGSTOP,
+        ProcessSignal::Continue => SIGCONT,
+    };
+    let return_value;
+
+    unsafe {
+        return_value = kill(pid as pid_t, signal_number);
+    }
+
+    match return_value {
+        0 => Ok(()),
+        _ => Err(ProcfsError::IOError(io::Error::last_os_error())),
+    }
+}
+
+/// Kills all the processes of the process group with the given ID
+///
+/// The ID of a process group is the PID of its leader.
+pub(crate) fn kill_process_group(pgid: Pid) -> Result<(), ProcfsError> {
+    let return_value;
+
+    unsafe {
+        return_value = kill(-(pgid as pid_t), SIGKILL);
+    }
+
+    match return_value {
+        0 => Ok(()),
+        _ => Err(ProcfsError::IOError(io::Error::last_os_error())),
+    }
+}
+
+// The types of packets received by a packet socket, as defined in `linux/if_packet.h`
+const PACKET_HOST: c_uchar = 0;
+const PACKET_BROADCAST: c_uchar = 1;
+const PACKET_MULTICAST: c_uchar = 2;
+const PACKET_OUTGOING: c_uchar = 4;
+
+/// A packet received by a packet socket
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct ReceivedPacket {
+    /// The length of the packet, which may exceed the size of the buffer it was received in
+    pub length: usize,
+    /// Whether the packet was sent by this host, rather than received by it
+    pub outgoing: bool,
+}
+
+/// Opens a socket receiving a copy of the packets sent and received by all the network interfaces, starting with
+/// their network layer header
+///
+/// This requires the `CAP_NET_RAW` capability.
+pub(crate) fn open_packet_socket() -> Result<OwnedFd, ProcfsError> {
+    let fd = unsafe {
+        socket(
+            AF_PACKET,
+            SOCK_DGRAM | SOCK_CLOEXEC,
+            (ETH_P_ALL as u16).to_be() as c_int,
+        )
+    };
+
+    match fd {
+        -1 => Err(ProcfsError::IOError(io::Error::last_os_error())),
+        _ => Ok(unsafe { OwnedFd::from_raw_fd(fd) }),
+    }
+}
+
+/// Waits for the next packet of a packet socket, and copies as much of it as fits in the buffer
+///
+/// Returns `None` for the packets which are neither sent nor received by this host, such as the packets addressed to
+/// other hosts which an interface in promiscuous mode receives.
+pub(crate) fn receive_packet(socket: &OwnedFd, buffer: &mut [u8]) -> Result<Option<ReceivedPacket>, ProcfsError> {
+    let mut address = MaybeUninit::<sockaddr_ll>::zeroed();
+    let mut address_length = size_of::<sockaddr_ll>() as socklen_t;
+
+    // With MSG_TRUNC, the actual length of the packet is returned even when it does not fit in the buffer
+    let length = unsafe {
+        recvfrom(
+            socket.as_raw_fd(),
+            buffer.as_mut_ptr() as *mut c_void,
+            buffer.len(),
+            MSG_TRUNC,
+            address.as_mut_ptr() as *mut sockaddr,
+            &mut address_length,
+        )
+    };
+    if length == -1 {
+        return Err(ProcfsError::IOError(io::Error::last_os_error()));
+    }
+
+    let address = unsafe { address.assume_init() };
+    let outgoing = match address.sll_pkttype {
+        PACKET_OUTGOING => true,
+        PACKET_HOST | PACKET_BROADCAST | PACKET_MULTICAST => false,
+        _ => return Ok(None),
+    };
+
+    Ok(Some(ReceivedPacket {
+        length: length as usize,
+        outgoing,
+    }))
+}
+
+/// The space of a filesystem, in bytes
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) struct FilesystemSpace {
+    pub used_bytes: u64,
+    /// The free space left to unprivileged users, which excludes the blocks reserved to root
+    pub available_bytes: u64,
+}
+
+/// Returns the space of the filesystem mounted on the given path
+pub(crate) fn filesystem_space(mount_point: &Path) -> Result<FilesystemSpace, ProcfsError> {
+    let path = CString::new(mount_point.as_os_str().as_bytes())
+        .map_err(|_| ProcfsError::InvalidFileContent(format!("Invalid mount point {:?}", mount_point)))?;
+    let mut stats = MaybeUninit::<statvfs64>::uninit();
+
+    let return_value = unsafe { statvfs64(path.as_ptr(), stats.as_mut_ptr()) };
+    if return_value != 0 {
+        return Err(ProcfsError::IOError(io::Error::last_os_error()));
+    }
+
+    let stats = unsafe { stats.assume_init() };
+    let used_blocks = stats.f_blocks.saturating_sub(stats.f_bfree);
+
+    Ok(FilesystemSpace {
+        used_bytes: used_blocks * stats.f_frsize,
+        available_bytes: stats.f_bavail * stats.f_frsize,
+    })
+}
+
+#[cfg(test)]
+mod test_filesystem_space {
+    use std::path::Path;
+
+    use crate::procfs::libc::filesystem_space;
+
+    #[test]
+    fn test_should_get_space_of_root_filesystem() {
+        assert!(filesystem_space(Path::new("/")).is_ok());
+    }
+
+    #[test]
+    fn test_should_fail_to_get_space_of_missing_mount_point() {
+        assert!(filesystem_space(Path::new("/non/existent/mount/point")).is_err());
+    }
+}
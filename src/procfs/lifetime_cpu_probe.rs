@@ -0,0 +1,209 @@
+//! Lifetime average CPU usage probing
+
+use crate::core::metrics::PercentMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::libc::{clock_ticks, online_cpus};
+use crate::procfs::parsers::process::PidStat;
+use crate::procfs::parsers::system::Uptime;
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData, ReadSystemData, SystemDataReader};
+
+/// Probe implementation to measure the average CPU usage (in percent) of processes since they started
+///
+/// The average usage is the CPU time consumed by a process, divided by the CPU time available since the process
+/// started. It highlights processes which are consistently busy, rather than processes with short bursts of activity.
+pub struct LifetimeCpuProbe {
+    uptime_reader: Box<dyn ReadSystemData<Uptime>>,
+    pid_stat_reader: Box<dyn ReadProcessData<PidStat>>,
+    clock_ticks: u64,
+    cpu_count: u64,
+    uptime_ticks: u64,
+}
+
+impl LifetimeCpuProbe {
+    /// Creates a new probe that can detect the lifetime average CPU usage of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many files descriptor the probe should keep open at most
+    pub fn new(fd_limit: usize) -> Result<Self, Error> {
+        let uptime_reader = SystemDataReader::new()
+            .map_err(|e| Error::ProbingError("Could not access /proc directory".to_string(), e.into()))?;
+        let clock_ticks =
+            clock_ticks().map_err(|e| Error::ProbingError("Could not read clock ticks".to_string(), e.into()))?;
+        let cpu_count =
+            online_cpus().map_err(|e| Error::ProbingError("Could not count online CPUs".to_string(), e.into()))?;
+
+        Ok(Self::from_readers(
+            Box::new(uptime_reader),
+            Box::new(ProcessDataReader::with_capacity(fd_limit)),
+            clock_ticks,
+            cpu_count,
+        ))
+    }
+
+    fn from_readers(
+        uptime_reader: Box<dyn ReadSystemData<Uptime>>,
+        pid_stat_reader: Box<dyn ReadProcessData<PidStat>>,
+        clock_ticks: u64,
+        cpu_count: u64,
+    ) -> Self {
+        LifetimeCpuProbe {
+            uptime_reader,
+            pid_stat_reader,
+            clock_ticks,
+            cpu_count,
+            uptime_ticks: 0,
+        }
+    }
+}
+
+impl Probe<PercentMetric> for LifetimeCpuProbe {
+    fn name(&self) -> &'static str {
+        "Lifetime CPU"
+    }
+
+    fn init_iteration(&mut self) -> Result<(), Error> {
+        let uptime = self
+            .uptime_reader
+            .read()
+            .map_err(|e| Error::ProbingError("Could not read system uptime".to_string(), e.into()))?;
+
+        self.uptime_ticks = uptime.uptime_ticks(self.clock_ticks);
+
+        Ok(())
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<PercentMetric, Error> {
+        let pid_stat = self
+            .pid_stat_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read process CPU stats for PID {}", pid), e.into()))?;
+
+        let age_ticks = self.uptime_ticks.saturating_sub(pid_stat.starttime());
+        let percent = lifetime_usage(pid_stat.running_time(), age_ticks, self.cpu_count);
+
+        Ok(PercentMetric::new(percent))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().for_each(|pid| self.pid_stat_reader.cleanup(*pid));
+    }
+}
+
+/// Calculates the portion of the CPU time available since a process started that was consumed by this process
+///
+/// # Arguments
+///  * `running_ticks`: The CPU time consumed by the process, in clock ticks
+///  * `age_ticks`: The time elapsed since the process started, in clock ticks
+///  * `cpu_count`: The number of CPUs sharing the available CPU time
+fn lifetime_usage(running_ticks: i64, age_ticks: u64, cpu_count: u64) -> f64 {
+    let available_ticks = age_ticks * cpu_count.max(1);
+
+    if available_ticks == 0 {
+        return 0.;
+    }
+
+    (100. * running_ticks.max(0) as f64 / available_ticks as f64).min(100.)
+}
+
+#[cfg(test)]
+mod test_lifetime_usage {
+    use rstest::*;
+
+    use crate::procfs::lifetime_cpu_probe::lifetime_usage;
+
+    #[rstest]
+    #[case(0, 100, 1, 0.)]
+    #[case(50, 100, 1, 50.)]
+    #[case(100, 100, 1, 100.)]
+    #[case(100, 100, 4, 25.)]
+    #[case(30, 200, 2, 7.5)]
+    fn test_should_divide_running_time_by_available_time(
+        #[case] running_ticks: i64,
+        #[case] age_ticks: u64,
+        #[case] cpu_count: u64,
+        #[case] expected: f64,
+    ) {
+        assert_eq!(lifetime_usage(running_ticks, age_ticks, cpu_count), expected);
+    }
+
+    #[test]
+    fn test_should_be_zero_when_process_just_started() {
+        assert_eq!(lifetime_usage(10, 0, 1), 0.);
+    }
+
+    #[test]
+    fn test_should_not_exceed_hundred_percent() {
+        // With an uptime precise to the second, a young process can appear to have run longer than its age
+        assert_eq!(lifetime_usage(150, 100, 1), 100.);
+    }
+}
+
+#[cfg(test)]
+mod test_lifetime_cpu_probe {
+    use std::time::Duration;
+
+    use crate::core::metrics::PercentMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::lifetime_cpu_probe::LifetimeCpuProbe;
+    use crate::procfs::parsers::fakes::{FakeProcessDataReader, FakeSystemDataReader};
+    use crate::procfs::parsers::process::PidStat;
+    use crate::procfs::parsers::system::Uptime;
+
+    const CLOCK_TICKS: u64 = 100;
+
+    fn build_probe(
+        uptime_reader: FakeSystemDataReader<Uptime>,
+        pid_reader: FakeProcessDataReader<PidStat>,
+        cpu_count: u64,
+    ) -> LifetimeCpuProbe {
+        LifetimeCpuProbe::from_readers(Box::new(uptime_reader), Box::new(pid_reader), CLOCK_TICKS, cpu_count)
+    }
+
+    #[test]
+    fn test_should_compute_usage_from_runtime_and_age_of_process() {
+        // The system booted 100s ago, and the process started 10s after boot
+        let uptime_reader = FakeSystemDataReader::from_sequence(vec![Uptime::new(100)]);
+        let mut pid_stat_reader = FakeProcessDataReader::new();
+        pid_stat_reader.set_pid_sequence(1, vec![PidStat::new(3000, 1500, 0, 0, 10 * CLOCK_TICKS)]);
+
+        let mut probe = build_probe(uptime_reader, pid_stat_reader, 1);
+
+        // The process has been running for 45s of its 90s lifetime
+        assert_eq!(
+            probe.probe_processes(&[1]).unwrap(),
+            hashmap!(1 => PercentMetric::new(50.))
+        );
+    }
+
+    #[test]
+    fn test_should_share_available_time_between_cpus() {
+        let uptime_reader = FakeSystemDataReader::from_sequence(vec![Uptime::new(100)]);
+        let mut pid_stat_reader = FakeProcessDataReader::new();
+        pid_stat_reader.set_pid_sequence(1, vec![PidStat::new(5000, 0, 0, 0, 0)]);
+
+        let mut probe = build_probe(uptime_reader, pid_stat_reader, 2);
+
+        assert_eq!(
+            probe.probe_processes(&[1]).unwrap(),
+            hashmap!(1 => PercentMetric::new(25.))
+        );
+    }
+
+    #[test]
+    fn test_should_measure_usage_of_process_younger_than_a_second() {
+        // The process started 500ms ago, and has been running for 250ms since
+        let uptime_reader =
+            FakeSystemDataReader::from_sequence(vec![Uptime::from_duration(Duration::from_millis(100_500))]);
+        let mut pid_stat_reader = FakeProcessDataReader::new();
+        pid_stat_reader.set_pid_sequence(1, vec![PidStat::new(25, 0, 0, 0, 100 * CLOCK_TICKS)]);
+
+        let mut probe = build_probe(uptime_reader, pid_stat_reader, 1);
+
+        assert_eq!(
+            probe.probe_processes(&[1]).unwrap(),
+            hashmap!(1 => PercentMetric::new(50.))
+        );
+    }
+}
@@ -0,0 +1,129 @@
+//! Logical I/O probing
+
+use std::time::Duration;
+
+use crate::core::metrics::IOMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::parsers::process::PidIO;
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData};
+use crate::procfs::rates::{ProcessesRates, PushMode};
+
+const LOGICAL_IO_RATE_RETENTION: Duration = Duration::from_secs(1);
+
+/// Probe implementation to measure the rate of bytes processes read and write through syscalls
+///
+/// Contrary to the [`DiskIOProbe`](crate::procfs::diskio_probe::DiskIOProbe), which measures the I/O actually reaching
+/// the disk, this probe measures the logical I/O, including the reads served from the page cache and the transfers
+/// through pipes and sockets. Comparing both tells how well the I/O of a process is cached.
+pub struct LogicalIOProbe {
+    reader: Box<dyn ReadProcessData<PidIO>>,
+    input_rate_calculator: ProcessesRates,
+    output_rate_calculator: ProcessesRates,
+}
+
+impl LogicalIOProbe {
+    /// Creates a new probe that can measure the logical I/O of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many open file descriptors this probe can keep open at most
+    pub fn new(fd_limit: usize) -> Self {
+        Self::from_reader(Box::new(ProcessDataReader::with_capacity(fd_limit)))
+    }
+
+    fn from_reader(reader: Box<dyn ReadProcessData<PidIO>>) -> Self {
+        LogicalIOProbe {
+            reader,
+            input_rate_calculator: ProcessesRates::new(PushMode::Accumulative, LOGICAL_IO_RATE_RETENTION),
+            output_rate_calculator: ProcessesRates::new(PushMode::Accumulative, LOGICAL_IO_RATE_RETENTION),
+        }
+    }
+}
+
+impl Probe<IOMetric> for LogicalIOProbe {
+    fn name(&self) -> &'static str {
+        "Logical I/O"
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<IOMetric, Error> {
+        let pid_io = self
+            .reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError("Could not read process IO stats".to_string(), e.into()))?;
+
+        self.input_rate_calculator.push(pid, pid_io.read_chars());
+        let input_rate = self
+            .input_rate_calculator
+            .rate(pid)
+            .map_err(|e| Error::ProbingError("Could not calculate logical input rate".to_string(), e.into()))?;
+
+        self.output_rate_calculator.push(pid, pid_io.written_chars());
+        let output_rate = self
+            .output_rate_calculator
+            .rate(pid)
+            .map_err(|e| Error::ProbingError("Could not calculate logical output rate".to_string(), e.into()))?;
+
+        Ok(IOMetric::new(input_rate as usize, output_rate as usize))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().copied().for_each(|pid| {
+            self.reader.cleanup(pid);
+            self.input_rate_calculator.cleanup(pid);
+            self.output_rate_calculator.cleanup(pid);
+        });
+    }
+}
+
+#[cfg(test)]
+mod test_logical_io_probe {
+    use rstest::*;
+    use sn_fake_clock::FakeClock;
+
+    use crate::core::metrics::IOMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::logical_io_probe::LogicalIOProbe;
+    use crate::procfs::parsers::fakes::FakeProcessDataReader;
+    use crate::procfs::parsers::process::PidIO;
+
+    #[rstest]
+    #[case((0, 0), (0, 0), IOMetric::new(0, 0))]
+    #[case((1000, 200), (5000, 1200), IOMetric::new(4000, 1000))]
+    #[case((1000, 200), (1000, 200), IOMetric::new(0, 0))]
+    fn test_should_calculate_logical_io_rate_between_two_samples(
+        #[case] prev_chars: (usize, usize),
+        #[case] cur_chars: (usize, usize),
+        #[case] expected: IOMetric,
+    ) {
+        let sequence = vec![
+            PidIO::new(0, 0, 0).with_chars(prev_chars.0, prev_chars.1),
+            PidIO::new(0, 0, 0).with_chars(cur_chars.0, cur_chars.1),
+        ];
+
+        let mut reader = FakeProcessDataReader::new();
+        reader.set_pid_sequence(1, sequence);
+
+        let mut probe = LogicalIOProbe::from_reader(Box::new(reader));
+
+        let _ = probe.probe(1).unwrap();
+        FakeClock::advance_time(1000);
+
+        assert_eq!(probe.probe(1).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_should_not_count_disk_io_as_logical_io() {
+        let sequence = vec![PidIO::new(0, 0, 0), PidIO::new(4096, 8192, 0).with_chars(100, 200)];
+
+        let mut reader = FakeProcessDataReader::new();
+        reader.set_pid_sequence(1, sequence);
+
+        let mut probe = LogicalIOProbe::from_reader(Box::new(reader));
+
+        let _ = probe.probe(1).unwrap();
+        FakeClock::advance_time(1000);
+
+        assert_eq!(probe.probe(1).unwrap(), IOMetric::new(100, 200));
+    }
+}
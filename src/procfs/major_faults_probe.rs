@@ -0,0 +1,126 @@
+//! Major page faults probing
+
+use std::time::Duration;
+
+use crate::core::metrics::MajorFaultsMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::parsers::process::PidStat;
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData};
+use crate::procfs::rates::{ProcessesRates, PushMode};
+
+const MAJOR_FAULTS_RATE_RETENTION: Duration = Duration::from_secs(1);
+
+/// Probe implementation to measure the rate at which processes load their memory pages from the disk
+///
+/// Minor faults, which map pages already in memory, are left out: a process faulting on the disk at a sustained rate
+/// is likely to have its memory evicted for lack of RAM.
+pub struct MajorFaultsProbe {
+    reader: Box<dyn ReadProcessData<PidStat>>,
+    rate_calculator: ProcessesRates,
+}
+
+impl MajorFaultsProbe {
+    /// Creates a new probe that can measure the rate of major page faults of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many open file descriptors this probe can keep open at most
+    pub fn new(fd_limit: usize) -> Self {
+        Self::from_reader(Box::new(ProcessDataReader::with_capacity(fd_limit)))
+    }
+
+    fn from_reader(reader: Box<dyn ReadProcessData<PidStat>>) -> Self {
+        MajorFaultsProbe {
+            reader,
+            rate_calculator: ProcessesRates::new(PushMode::Accumulative, MAJOR_FAULTS_RATE_RETENTION),
+        }
+    }
+}
+
+impl Probe<MajorFaultsMetric> for MajorFaultsProbe {
+    fn name(&self) -> &'static str {
+        "Major faults"
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<MajorFaultsMetric, Error> {
+        let pid_stat = self
+            .reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read process stats for PID {}", pid), e.into()))?;
+
+        self.rate_calculator.push(pid, pid_stat.major_faults() as usize);
+        let rate = self
+            .rate_calculator
+            .rate(pid)
+            .map_err(|e| Error::ProbingError("Could not calculate major faults rate".to_string(), e.into()))?;
+
+        Ok(MajorFaultsMetric::new(rate))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().copied().for_each(|pid| {
+            self.reader.cleanup(pid);
+            self.rate_calculator.cleanup(pid);
+        });
+    }
+}
+
+#[cfg(test)]
+mod test_major_faults_probe {
+    use rstest::*;
+    use sn_fake_clock::FakeClock;
+
+    use crate::core::metrics::MajorFaultsMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::major_faults_probe::MajorFaultsProbe;
+    use crate::procfs::parsers::fakes::FakeProcessDataReader;
+    use crate::procfs::parsers::process::PidStat;
+
+    fn probe_with_faults(faults_sequence: &[u64]) -> MajorFaultsProbe {
+        let sequence = faults_sequence
+            .iter()
+            .map(|faults| PidStat::new(0, 0, 0, 0, 0).with_major_faults(*faults))
+            .collect();
+
+        let mut reader = FakeProcessDataReader::new();
+        reader.set_pid_sequence(1, sequence);
+
+        MajorFaultsProbe::from_reader(Box::new(reader))
+    }
+
+    #[test]
+    fn test_should_be_zero_on_first_sample() {
+        let mut probe = probe_with_faults(&[500]);
+
+        assert_eq!(probe.probe(1).unwrap(), MajorFaultsMetric::new(0.));
+    }
+
+    #[rstest]
+    #[case(0, 0, 0.)]
+    #[case(100, 160, 60.)]
+    #[case(100, 100, 0.)]
+    fn test_should_calculate_faults_rate_between_two_samples(
+        #[case] prev_faults: u64,
+        #[case] cur_faults: u64,
+        #[case] expected: f64,
+    ) {
+        let mut probe = probe_with_faults(&[prev_faults, cur_faults]);
+
+        let _ = probe.probe(1).unwrap();
+        FakeClock::advance_time(1000);
+
+        assert_eq!(probe.probe(1).unwrap(), MajorFaultsMetric::new(expected));
+    }
+
+    #[test]
+    fn test_should_restart_from_zero_after_cleanup() {
+        let mut probe = probe_with_faults(&[100, 160]);
+
+        let _ = probe.probe(1).unwrap();
+        probe.cleanup(&[1]);
+        FakeClock::advance_time(1000);
+
+        assert_eq!(probe.probe(1).unwrap(), MajorFaultsMetric::new(0.));
+    }
+}
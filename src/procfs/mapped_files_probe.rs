@@ -0,0 +1,181 @@
+//! Memory-mapped files probing
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use crate::core::metrics::CountMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::{proc_root, ProcfsError};
+
+/// The maximum amount of mappings read from the maps file of a process
+///
+/// This is the default limit of mappings of a process (`vm.max_map_count`), which only processes whose limit has been
+/// raised exceed. Their remaining mappings are not scanned, to bound the time spent probing a single process.
+const MAX_MAPPINGS: usize = 65_530;
+
+/// Probe implementation to count the distinct files mapped in the memory of processes
+///
+/// The files are read from `/proc/[pid]/maps`. A file mapped several times, as is the case of shared libraries whose
+/// code and data segments are mapped separately, is only counted once. Anonymous mappings and pseudo-paths such as
+/// `[heap]` are ignored.
+pub struct MappedFilesProbe;
+
+impl MappedFilesProbe {
+    /// Creates a new probe that can count the files mapped in the memory of processes
+    pub fn new() -> Self {
+        MappedFilesProbe
+    }
+}
+
+impl Default for MappedFilesProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Probe<CountMetric> for MappedFilesProbe {
+    fn name(&self) -> &'static str {
+        "Mapped files"
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<CountMetric, Error> {
+        let count = read_mapped_files(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read memory mappings of PID {}", pid), e.into()))?;
+
+        Ok(CountMetric::new(count))
+    }
+
+    fn cleanup(&mut self, _pids: &[Pid]) {
+        // Nothing to do, as the maps file is only opened while probing
+    }
+}
+
+/// Counts the distinct files mapped by a process, as listed in `/proc/[pid]/maps`
+///
+/// The maps file is read line by line rather than at once, as it can be large for processes loading many libraries.
+fn read_mapped_files(pid: Pid) -> Result<usize, ProcfsError> {
+    let maps = File::open(proc_root().join(pid.to_string()).join("maps"))?;
+
+    count_mapped_files(BufReader::new(maps), MAX_MAPPINGS)
+}
+
+/// Counts the distinct paths of the file-backed mappings listed in the content of a maps file
+///
+/// # Arguments
+///  * `maps`: The content of the maps file
+///  * `max_mappings`: How many mappings are read at most
+fn count_mapped_files<R: BufRead>(mut maps: R, max_mappings: usize) -> Result<usize, ProcfsError> {
+    let mut paths = HashSet::new();
+    let mut line = String::new();
+
+    for _ in 0..max_mappings {
+        line.clear();
+        if maps.read_line(&mut line)? == 0 {
+            break;
+        }
+
+        if let Some(path) = mapped_file(&line) {
+            if !paths.contains(path) {
+                paths.insert(path.to_string());
+            }
+        }
+    }
+
+    Ok(paths.len())
+}
+
+/// Returns the path of the file backing a mapping, or `None` if the mapping is not backed by a file
+///
+/// A mapping is described by a line such as `7f2c4a1d3000-7f2c4a1f9000 r--p 00000000 08:01 1835018   /usr/lib/libc.so`,
+/// whose inode is 0 for anonymous mappings and whose path is bracketed for the heap, the stack and the kernel pages.
+///
+/// # Arguments
+///  * `line`: A line of a maps file
+fn mapped_file(line: &str) -> Option<&str> {
+    let mut fields = line.splitn(6, ' ');
+    let inode = fields.nth(4)?;
+    let path = fields.next()?.trim();
+
+    (inode != "0" && path.starts_with('/')).then_some(path)
+}
+
+#[cfg(test)]
+mod test_count_mapped_files {
+    use rstest::*;
+
+    use crate::procfs::mapped_files_probe::{count_mapped_files, mapped_file};
+
+    const MAPS: &str = "\
+55d0c6a00000-55d0c6a2e000 r--p 00000000 08:01 1311050                    /usr/sbin/nginx
+55d0c6a2e000-55d0c6b1f000 r-xp 0002e000 08:01 1311050                    /usr/sbin/nginx
+55d0c7e4a000-55d0c7f8c000 rw-p 00000000 00:00 0                          [heap]
+7f2c4a1d3000-7f2c4a1f9000 r--p 00000000 08:01 1835018                    /usr/lib/x86_64-linux-gnu/libc.so.6
+7f2c4a1f9000-7f2c4a34e000 r-xp 00026000 08:01 1835018                    /usr/lib/x86_64-linux-gnu/libc.so.6
+7f2c4a3a0000-7f2c4a3ac000 rw-p 00000000 00:00 0
+7f2c4a3ac000-7f2c4a3b0000 rw-s 00000000 00:05 2048                       /dev/zero (deleted)
+7f2c4a3b0000-7f2c4a3b2000 r--p 00000000 08:01 1835020                    /usr/lib/x86_64-linux-gnu/ld-linux-x86-64.so.2
+7ffd1b9e1000-7ffd1ba02000 rw-p 00000000 00:00 0                          [stack]
+7ffd1bbd4000-7ffd1bbd8000 r--p 00000000 00:00 0                          [vvar]
+ffffffffff600000-ffffffffff601000 --xp 00000000 00:00 0                  [vsyscall]
+";
+
+    #[test]
+    fn test_should_count_distinct_mapped_files() {
+        assert_eq!(count_mapped_files(MAPS.as_bytes(), 100).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_should_count_no_file_when_maps_is_empty() {
+        assert_eq!(count_mapped_files("".as_bytes(), 100).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_should_stop_reading_after_max_mappings() {
+        assert_eq!(count_mapped_files(MAPS.as_bytes(), 4).unwrap(), 2);
+    }
+
+    #[rstest]
+    #[case(
+        "7f2c4a1d3000-7f2c4a1f9000 r--p 00000000 08:01 1835018    /usr/lib/libc.so.6\n",
+        Some("/usr/lib/libc.so.6")
+    )]
+    #[case(
+        "7f2c4a1d3000-7f2c4a1f9000 r--p 00000000 08:01 1835018    /opt/my app/lib.so\n",
+        Some("/opt/my app/lib.so")
+    )]
+    #[case("7f2c4a3a0000-7f2c4a3ac000 rw-p 00000000 00:00 0 \n", None)]
+    #[case("55d0c7e4a000-55d0c7f8c000 rw-p 00000000 00:00 0          [heap]\n", None)]
+    #[case("7f2c4a3a0000-7f2c4a3ac000 rw-p\n", None)]
+    fn test_should_extract_path_of_file_backed_mappings(#[case] line: &str, #[case] expected: Option<&str>) {
+        assert_eq!(mapped_file(line), expected);
+    }
+}
+
+#[cfg(test)]
+mod test_mapped_files_probe {
+    use crate::core::metrics::CountMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::fake_procfs::FakeProcfs;
+    use crate::procfs::mapped_files_probe::MappedFilesProbe;
+
+    #[test]
+    fn test_should_count_mapped_files_of_process() {
+        let procfs = FakeProcfs::new();
+        procfs.spawn_process(10, "nginx");
+        procfs.set_mapped_files(10, &["/usr/sbin/nginx", "/usr/lib/libc.so.6", "/usr/lib/libc.so.6"]);
+        let mut probe = MappedFilesProbe::new();
+
+        assert_eq!(probe.probe(10).unwrap(), CountMetric::new(2));
+    }
+
+    #[test]
+    fn test_should_fail_to_probe_unknown_process() {
+        let _procfs = FakeProcfs::new();
+        let mut probe = MappedFilesProbe::new();
+
+        assert!(probe.probe(99).is_err());
+    }
+}
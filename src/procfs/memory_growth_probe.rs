@@ -0,0 +1,176 @@
+//! Memory growth rate probing
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::core::metrics::SignedBytesMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::time::Timestamp;
+use crate::core::Error;
+use crate::procfs::libc::page_size;
+use crate::procfs::parsers::process::PidStatm;
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData};
+
+/// Probe implementation to measure how fast the resident memory of processes grows, in bytes per second
+///
+/// A process steadily leaking memory is easier to spot from the growth of its resident memory than from its size. The
+/// growth is negative when a process releases memory.
+pub struct MemoryGrowthProbe {
+    statm_reader: Box<dyn ReadProcessData<PidStatm>>,
+    page_size: u64,
+    processes_prev_reading: HashMap<Pid, (u64, Timestamp)>,
+}
+
+impl MemoryGrowthProbe {
+    /// Creates a new probe that can measure the memory growth rate of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many files descriptor the probe should keep open at most
+    pub fn new(fd_limit: usize) -> Result<Self, Error> {
+        let page_size =
+            page_size().map_err(|e| Error::ProbingError("Could not read memory page size".to_string(), e.into()))?;
+
+        Ok(Self::from_reader(
+            Box::new(ProcessDataReader::with_capacity(fd_limit)),
+            page_size,
+        ))
+    }
+
+    fn from_reader(statm_reader: Box<dyn ReadProcessData<PidStatm>>, page_size: u64) -> Self {
+        MemoryGrowthProbe {
+            statm_reader,
+            page_size,
+            processes_prev_reading: HashMap::new(),
+        }
+    }
+}
+
+impl Probe<SignedBytesMetric> for MemoryGrowthProbe {
+    fn name(&self) -> &'static str {
+        "Memory growth"
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<SignedBytesMetric, Error> {
+        let statm = self
+            .statm_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read memory usage of PID {}", pid), e.into()))?;
+
+        let resident_bytes = statm.resident_pages() * self.page_size;
+        let now = Timestamp::now();
+
+        let growth = match self.processes_prev_reading.insert(pid, (resident_bytes, now)) {
+            Some((prev_bytes, prev_timestamp)) => {
+                growth_rate(prev_bytes, resident_bytes, now.duration_since(&prev_timestamp))
+            }
+            None => 0,
+        };
+
+        Ok(SignedBytesMetric::new(growth))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().for_each(|pid| {
+            self.statm_reader.cleanup(*pid);
+            self.processes_prev_reading.remove(pid);
+        });
+    }
+}
+
+/// Calculates the variation of an amount of bytes per second between two readings
+///
+/// If no time elapsed between the readings, the rate is 0.
+///
+/// # Arguments
+///  * `prev_bytes`, `cur_bytes`: The previous and current amount of bytes
+///  * `elapsed`: The time elapsed between the two readings
+fn growth_rate(prev_bytes: u64, cur_bytes: u64, elapsed: Duration) -> i64 {
+    if elapsed.is_zero() {
+        return 0;
+    }
+
+    let variation = cur_bytes as f64 - prev_bytes as f64;
+    (variation / elapsed.as_secs_f64()).round() as i64
+}
+
+#[cfg(test)]
+mod test_growth_rate {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::procfs::memory_growth_probe::growth_rate;
+
+    #[rstest]
+    #[case(1000, 3000, Duration::from_secs(2), 1000)]
+    #[case(3000, 1000, Duration::from_secs(2), -1000)]
+    #[case(1000, 1000, Duration::from_secs(2), 0)]
+    #[case(1000, 1500, Duration::from_millis(500), 1000)]
+    fn test_should_calculate_growth_per_second(
+        #[case] prev_bytes: u64,
+        #[case] cur_bytes: u64,
+        #[case] elapsed: Duration,
+        #[case] expected: i64,
+    ) {
+        assert_eq!(growth_rate(prev_bytes, cur_bytes, elapsed), expected);
+    }
+
+    #[test]
+    fn test_growth_should_be_zero_when_no_time_elapsed() {
+        assert_eq!(growth_rate(1000, 3000, Duration::ZERO), 0);
+    }
+}
+
+#[cfg(test)]
+mod test_memory_growth_probe {
+    use std::time::Duration;
+
+    use crate::core::metrics::SignedBytesMetric;
+    use crate::core::probe::Probe;
+    use crate::core::time::test_utils::advance_time_and_refresh_timestamp;
+    use crate::procfs::memory_growth_probe::MemoryGrowthProbe;
+    use crate::procfs::parsers::fakes::FakeProcessDataReader;
+    use crate::procfs::parsers::process::PidStatm;
+
+    fn build_probe(resident_pages: Vec<u64>) -> MemoryGrowthProbe {
+        let mut statm_reader = FakeProcessDataReader::new();
+        statm_reader.set_pid_sequence(1, resident_pages.into_iter().map(PidStatm::new).collect());
+
+        MemoryGrowthProbe::from_reader(Box::new(statm_reader), 4096)
+    }
+
+    #[test]
+    fn test_growth_should_be_zero_on_first_probing() {
+        let mut probe = build_probe(vec![100]);
+
+        assert_eq!(
+            probe.probe_processes(&[1]).unwrap(),
+            hashmap!(1 => SignedBytesMetric::new(0))
+        );
+    }
+
+    #[test]
+    fn test_should_report_positive_growth_of_growing_process() {
+        let mut probe = build_probe(vec![100, 110]);
+        probe.probe_processes(&[1]).unwrap();
+        advance_time_and_refresh_timestamp(Duration::from_secs(2));
+
+        assert_eq!(
+            probe.probe_processes(&[1]).unwrap(),
+            hashmap!(1 => SignedBytesMetric::new(5 * 4096))
+        );
+    }
+
+    #[test]
+    fn test_should_report_negative_growth_of_process_releasing_memory() {
+        let mut probe = build_probe(vec![110, 100]);
+        probe.probe_processes(&[1]).unwrap();
+        advance_time_and_refresh_timestamp(Duration::from_secs(2));
+
+        assert_eq!(
+            probe.probe_processes(&[1]).unwrap(),
+            hashmap!(1 => SignedBytesMetric::new(-5 * 4096))
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! Memory usage probing
+
+use crate::core::metrics::BytesMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::libc::page_size;
+use crate::procfs::parsers::process::{PidStatm, PidStatus};
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData};
+
+/// Probe implementation to measure the resident memory of processes
+///
+/// Hugetlb memory is not accounted for in the resident memory reported by the kernel. It can optionally be added to the
+/// footprint of processes, for processes such as databases which rely on hugepages.
+pub struct MemoryProbe {
+    statm_reader: Box<dyn ReadProcessData<PidStatm>>,
+    // Only set when hugetlb memory is included in the footprint of processes
+    status_reader: Option<Box<dyn ReadProcessData<PidStatus>>>,
+    page_size: u64,
+}
+
+impl MemoryProbe {
+    /// Creates a new probe that can measure the memory footprint of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many files descriptor the probe should keep open at most
+    ///  * `include_hugepages`: Indicates if the hugetlb memory of processes is added to their resident memory
+    pub fn new(fd_limit: usize, include_hugepages: bool) -> Result<Self, Error> {
+        let page_size =
+            page_size().map_err(|e| Error::ProbingError("Could not read memory page size".to_string(), e.into()))?;
+
+        let (statm_reader, status_reader) = if include_hugepages {
+            // Each process has two files to read, so each reader can only keep half of the allowed files open
+            let status_reader: Box<dyn ReadProcessData<PidStatus>> =
+                Box::new(ProcessDataReader::with_capacity(fd_limit / 2));
+            (ProcessDataReader::with_capacity(fd_limit / 2), Some(status_reader))
+        } else {
+            (ProcessDataReader::with_capacity(fd_limit), None)
+        };
+
+        Ok(Self::from_readers(Box::new(statm_reader), status_reader, page_size))
+    }
+
+    fn from_readers(
+        statm_reader: Box<dyn ReadProcessData<PidStatm>>,
+        status_reader: Option<Box<dyn ReadProcessData<PidStatus>>>,
+        page_size: u64,
+    ) -> Self {
+        MemoryProbe {
+            statm_reader,
+            status_reader,
+            page_size,
+        }
+    }
+}
+
+impl Probe<BytesMetric> for MemoryProbe {
+    fn name(&self) -> &'static str {
+        "Memory"
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<BytesMetric, Error> {
+        let statm = self
+            .statm_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read memory usage of PID {}", pid), e.into()))?;
+
+        let hugetlb_bytes = match self.status_reader.as_mut() {
+            Some(status_reader) => {
+                let status = status_reader.read(pid).map_err(|e| {
+                    Error::ProbingError(format!("Could not read process status for PID {}", pid), e.into())
+                })?;
+                Some(status.hugetlb_bytes())
+            }
+            None => None,
+        };
+
+        let footprint = memory_footprint(statm.resident_pages(), self.page_size, hugetlb_bytes);
+        Ok(BytesMetric::new(footprint as usize))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().for_each(|pid| {
+            self.statm_reader.cleanup(*pid);
+            if let Some(status_reader) = self.status_reader.as_mut() {
+                status_reader.cleanup(*pid);
+            }
+        });
+    }
+}
+
+/// Calculates the memory footprint of a process, in bytes
+///
+/// # Arguments
+///  * `resident_pages`: The amount of pages of the process resident in memory
+///  * `page_size`: The size of a page, in bytes
+///  * `hugetlb_bytes`: The size of the hugetlb memory of the process, if it must be included in the footprint
+fn memory_footprint(resident_pages: u64, page_size: u64, hugetlb_bytes: Option<u64>) -> u64 {
+    resident_pages * page_size + hugetlb_bytes.unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test_memory_footprint {
+    use crate::procfs::memory_probe::memory_footprint;
+
+    #[test]
+    fn test_should_convert_resident_pages_to_bytes() {
+        assert_eq!(memory_footprint(10, 4096, None), 40960);
+    }
+
+    #[test]
+    fn test_should_add_hugetlb_memory_when_included() {
+        assert_eq!(memory_footprint(10, 4096, Some(2048)), 43008);
+    }
+
+    #[test]
+    fn test_should_not_change_footprint_of_process_without_hugepages() {
+        assert_eq!(memory_footprint(10, 4096, Some(0)), 40960);
+    }
+}
+
+#[cfg(test)]
+mod test_memory_probe {
+    use crate::core::metrics::BytesMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::memory_probe::MemoryProbe;
+    use crate::procfs::parsers::fakes::FakeProcessDataReader;
+    use crate::procfs::parsers::process::{PidStatm, PidStatus};
+
+    fn build_probe(include_hugepages: bool) -> MemoryProbe {
+        let mut statm_reader = FakeProcessDataReader::new();
+        statm_reader.set_pid_sequence(1, vec![PidStatm::new(100)]);
+        let mut status_reader = FakeProcessDataReader::new();
+        status_reader.set_pid_sequence(1, vec![PidStatus::with_hugetlb_pages(1, 2048)]);
+
+        let status_reader = include_hugepages.then(|| Box::new(status_reader) as _);
+        MemoryProbe::from_readers(Box::new(statm_reader), status_reader, 4096)
+    }
+
+    #[test]
+    fn test_should_report_resident_memory() {
+        let mut probe = build_probe(false);
+
+        assert_eq!(
+            probe.probe_processes(&[1]).unwrap(),
+            hashmap!(1 => BytesMetric::new(409600))
+        );
+    }
+
+    #[test]
+    fn test_should_report_resident_and_hugetlb_memory_when_hugepages_are_included() {
+        let mut probe = build_probe(true);
+
+        assert_eq!(
+            probe.probe_processes(&[1]).unwrap(),
+            hashmap!(1 => BytesMetric::new(409600 + 2048 * 1024))
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//! Stack and data segments probing
+
+use crate::core::metrics::MemorySegmentsMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::parsers::process::PidStatus;
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData};
+
+/// Probe implementation to measure the sizes of the stack and of the data segment of processes
+///
+/// Unlike the resident memory, these sizes tell a growing heap from a growing stack.
+pub struct MemorySegmentsProbe {
+    reader: Box<dyn ReadProcessData<PidStatus>>,
+}
+
+impl MemorySegmentsProbe {
+    /// Creates a new probe that can measure the memory segments of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many files descriptor the probe should keep open at most
+    pub fn new(fd_limit: usize) -> Self {
+        Self::from_reader(Box::new(ProcessDataReader::with_capacity(fd_limit)))
+    }
+
+    fn from_reader(reader: Box<dyn ReadProcessData<PidStatus>>) -> Self {
+        MemorySegmentsProbe { reader }
+    }
+}
+
+impl Probe<MemorySegmentsMetric> for MemorySegmentsProbe {
+    fn name(&self) -> &'static str {
+        "Stack & data"
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<MemorySegmentsMetric, Error> {
+        let status = self
+            .reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read process status for PID {}", pid), e.into()))?;
+
+        Ok(MemorySegmentsMetric::new(
+            status.stack_bytes() as usize,
+            status.data_bytes() as usize,
+        ))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().for_each(|pid| self.reader.cleanup(*pid));
+    }
+}
+
+#[cfg(test)]
+mod test_memory_segments_probe {
+    use crate::core::metrics::MemorySegmentsMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::memory_segments_probe::MemorySegmentsProbe;
+    use crate::procfs::parsers::fakes::FakeProcessDataReader;
+    use crate::procfs::parsers::process::PidStatus;
+
+    #[test]
+    fn test_should_report_stack_and_data_sizes_in_bytes() {
+        let mut reader = FakeProcessDataReader::new();
+        reader.set_pid_sequence(1, vec![PidStatus::new(1).with_segments(132, 2048)]);
+        let mut probe = MemorySegmentsProbe::from_reader(Box::new(reader));
+
+        assert_eq!(
+            probe.probe(1).unwrap(),
+            MemorySegmentsMetric::new(132 * 1024, 2048 * 1024)
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Memory volatility probing
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::core::metrics::CountMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::libc::page_size;
+use crate::procfs::parsers::process::PidStatm;
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData};
+
+/// Over how many consecutive variations of the resident memory the volatility is measured
+const VOLATILITY_WINDOW: usize = 10;
+
+/// Variation of the resident memory between two probings, in bytes, from which it is considered as a large jump
+const LARGE_JUMP_THRESHOLD: u64 = 1024 * 1024;
+
+/// Probe implementation to measure how often the resident memory of processes jumps by a large amount
+///
+/// Processes frequently mapping and releasing large memory regions (e.g. through `mmap` or `brk`) suffer from
+/// allocation pressure, which their resident memory alone does not reveal. The volatility is the number of variations
+/// larger than 1MiB among the latest 10 variations of the resident memory.
+pub struct MemoryVolatilityProbe {
+    statm_reader: Box<dyn ReadProcessData<PidStatm>>,
+    page_size: u64,
+    processes_readings: HashMap<Pid, VecDeque<u64>>,
+}
+
+impl MemoryVolatilityProbe {
+    /// Creates a new probe that can measure the memory volatility of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many files descriptor the probe should keep open at most
+    pub fn new(fd_limit: usize) -> Result<Self, Error> {
+        let page_size =
+            page_size().map_err(|e| Error::ProbingError("Could not read memory page size".to_string(), e.into()))?;
+
+        Ok(Self::from_reader(
+            Box::new(ProcessDataReader::with_capacity(fd_limit)),
+            page_size,
+        ))
+    }
+
+    fn from_reader(statm_reader: Box<dyn ReadProcessData<PidStatm>>, page_size: u64) -> Self {
+        MemoryVolatilityProbe {
+            statm_reader,
+            page_size,
+            processes_readings: HashMap::new(),
+        }
+    }
+}
+
+impl Probe<CountMetric> for MemoryVolatilityProbe {
+    fn name(&self) -> &'static str {
+        "Memory volatility"
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<CountMetric, Error> {
+        let statm = self
+            .statm_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read memory usage of PID {}", pid), e.into()))?;
+
+        let readings = self.processes_readings.entry(pid).or_default();
+
+        // N variations are measured from N+1 readings
+        if readings.len() > VOLATILITY_WINDOW {
+            readings.pop_front();
+        }
+        readings.push_back(statm.resident_pages() * self.page_size);
+
+        Ok(CountMetric::new(count_large_jumps(readings, LARGE_JUMP_THRESHOLD)))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().for_each(|pid| {
+            self.statm_reader.cleanup(*pid);
+            self.processes_readings.remove(pid);
+        });
+    }
+}
+
+/// Counts how many times the resident memory varied by more than `threshold` bytes between two consecutive readings
+fn count_large_jumps(readings: &VecDeque<u64>, threshold: u64) -> usize {
+    readings
+        .iter()
+        .zip(readings.iter().skip(1))
+        .filter(|(prev, cur)| prev.abs_diff(**cur) > threshold)
+        .count()
+}
+
+#[cfg(test)]
+mod test_count_large_jumps {
+    use std::collections::VecDeque;
+
+    use rstest::*;
+
+    use crate::procfs::memory_volatility_probe::count_large_jumps;
+
+    #[rstest]
+    #[case(&[], 0)]
+    #[case(&[1000], 0)]
+    #[case(&[1000, 1100, 1050, 1000], 0)]
+    #[case(&[1000, 1101], 1)]
+    #[case(&[1000, 1100], 0)]
+    #[case(&[1000, 5000, 1000, 1050, 3000], 3)]
+    fn test_should_count_variations_above_threshold(#[case] readings: &[u64], #[case] expected: usize) {
+        let readings: VecDeque<u64> = readings.iter().copied().collect();
+
+        assert_eq!(count_large_jumps(&readings, 100), expected);
+    }
+}
+
+#[cfg(test)]
+mod test_memory_volatility_probe {
+    use crate::core::metrics::CountMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::memory_volatility_probe::{MemoryVolatilityProbe, VOLATILITY_WINDOW};
+    use crate::procfs::parsers::fakes::FakeProcessDataReader;
+    use crate::procfs::parsers::process::PidStatm;
+
+    /// With pages of 1MiB, any variation of more than one page is a large jump
+    const PAGE_SIZE: u64 = 1024 * 1024;
+
+    fn build_probe(resident_pages: Vec<u64>) -> MemoryVolatilityProbe {
+        let mut statm_reader = FakeProcessDataReader::new();
+        statm_reader.set_pid_sequence(1, resident_pages.into_iter().map(PidStatm::new).collect());
+
+        MemoryVolatilityProbe::from_reader(Box::new(statm_reader), PAGE_SIZE)
+    }
+
+    fn probe_n_times(probe: &mut MemoryVolatilityProbe, n: usize) -> CountMetric {
+        (0..n).map(|_| probe.probe(1).unwrap()).last().unwrap()
+    }
+
+    #[test]
+    fn test_volatility_should_be_zero_on_first_probing() {
+        let mut probe = build_probe(vec![100]);
+
+        assert_eq!(probe.probe(1).unwrap(), CountMetric::new(0));
+    }
+
+    #[test]
+    fn test_should_count_large_jumps_of_resident_memory() {
+        let mut probe = build_probe(vec![100, 110, 111, 100, 100, 120]);
+
+        assert_eq!(probe_n_times(&mut probe, 6), CountMetric::new(3));
+    }
+
+    #[test]
+    fn test_should_only_count_jumps_of_latest_probings() {
+        let mut resident_pages = vec![100, 200, 100];
+        resident_pages.extend(vec![100; VOLATILITY_WINDOW]);
+        let count = resident_pages.len();
+        let mut probe = build_probe(resident_pages);
+
+        assert_eq!(probe_n_times(&mut probe, count), CountMetric::new(0));
+    }
+
+    #[test]
+    fn test_should_forget_readings_of_cleaned_up_processes() {
+        let mut probe = build_probe(vec![100, 200]);
+        probe.probe(1).unwrap();
+
+        probe.cleanup(&[1]);
+
+        assert_eq!(probe.probe(1).unwrap(), CountMetric::new(0));
+    }
+}
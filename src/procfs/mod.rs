@@ -1,6 +1,8 @@
 //! Processes monitoring based on /proc filesystem
 
+use std::cell::RefCell;
 use std::io;
+use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
@@ -8,23 +10,82 @@ use crate::core::process::Pid;
 
 pub mod process;
 
+pub mod child_count_probe;
+pub mod connections;
 pub mod cpu_probe;
+pub mod devices;
+pub mod disk_wait_probe;
 pub mod diskio_probe;
+pub mod fd_limit_probe;
+pub mod filesystems;
+pub mod iops_probe;
+pub mod lifetime_cpu_probe;
+pub mod logical_io_probe;
+pub mod major_faults_probe;
+pub mod mapped_files_probe;
+pub mod memory_growth_probe;
+pub mod memory_probe;
+pub mod memory_segments_probe;
+pub mod memory_volatility_probe;
+pub mod oom_score_probe;
+pub mod power_probe;
+pub mod pressure_probe;
+pub mod protocol_io_probe;
+pub mod sampler;
+pub mod sched_probe;
+pub mod signal;
+pub mod socket_probe;
+pub mod socket_protocols;
+pub mod socket_states_probe;
+pub mod thermal_probe;
+pub mod yield_probe;
 
+#[cfg(feature = "gpu")]
+pub mod gpu_probe;
 #[cfg(feature = "netio")]
 pub mod net_io_probe;
+#[cfg(feature = "gpu")]
+mod nvidia_pmon;
 
+mod cgroups;
 pub mod libc;
+mod namespaces;
+mod packet_capture;
 mod parsers;
 mod rates;
 
+#[cfg(test)]
+mod fake_procfs;
+
+/// The directory where the proc filesystem is usually mounted
+const DEFAULT_PROC_ROOT: &str = "/proc";
+
+thread_local! {
+    static PROC_ROOT: RefCell<PathBuf> = RefCell::new(PathBuf::from(DEFAULT_PROC_ROOT));
+}
+
+/// Sets the directory from which the proc filesystem is read
+///
+/// Only the scanners and readers created afterwards, from the current thread, are affected.
+///
+/// # Arguments
+///  * `root`: The directory where the proc filesystem is mounted, or where a copy of it is stored
+pub fn set_proc_root<P: AsRef<Path>>(root: P) {
+    PROC_ROOT.with(|proc_root| proc_root.replace(root.as_ref().to_path_buf()));
+}
+
+/// Returns the directory from which the proc filesystem is read
+pub fn proc_root() -> PathBuf {
+    PROC_ROOT.with(|proc_root| proc_root.borrow().clone())
+}
+
 #[derive(Error, Debug)]
 pub enum ProcfsError {
     #[error("Invalid file content: '{0:?}'")]
     InvalidFileContent(String),
     #[error("Invalid file format: '{0:?}'")]
     InvalidFileFormat(String),
-    #[error(transparent)]
+    #[error("I/O error: {0}")]
     IOError(#[from] io::Error),
     #[error("PID is not known: '{0:?}'")]
     UnknownPID(Pid),
@@ -35,3 +96,131 @@ pub enum ProcfsError {
     #[error("Error while reading system limits")]
     RLimitError,
 }
+
+#[cfg(test)]
+pub mod test_utils {
+    use std::path::Path;
+
+    use crate::procfs::{set_proc_root, DEFAULT_PROC_ROOT};
+
+    /// Reads the proc filesystem from another directory, until this guard is dropped
+    pub struct ProcRootOverride;
+
+    impl ProcRootOverride {
+        pub fn new<P: AsRef<Path>>(root: P) -> Self {
+            set_proc_root(root);
+            ProcRootOverride
+        }
+    }
+
+    impl Drop for ProcRootOverride {
+        fn drop(&mut self) {
+            set_proc_root(DEFAULT_PROC_ROOT);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_proc_root {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use tempfile::tempdir;
+
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::procfs::parsers::process::PidStat;
+    use crate::procfs::parsers::system::Uptime;
+    use crate::procfs::parsers::{ProcessData, ReadSystemData, SystemDataReader};
+    use crate::procfs::proc_root;
+    use crate::procfs::test_utils::ProcRootOverride;
+
+    #[test]
+    fn test_should_read_from_proc_by_default() {
+        assert_eq!(proc_root(), PathBuf::from("/proc"));
+    }
+
+    #[test]
+    fn test_process_files_should_be_located_in_proc_root() {
+        let _proc_root = ProcRootOverride::new("/mnt/other_proc");
+
+        assert_eq!(PidStat::filepath(42), PathBuf::from("/mnt/other_proc/42/stat"));
+    }
+
+    #[test]
+    fn test_should_restore_default_proc_root_when_override_is_dropped() {
+        drop(ProcRootOverride::new("/mnt/other_proc"));
+
+        assert_eq!(proc_root(), PathBuf::from("/proc"));
+    }
+
+    #[test]
+    fn test_system_data_reader_should_parse_file_from_proc_root() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let fixture_dir = tempdir().expect("Could not create tmp dir");
+        fs::write(fixture_dir.path().join("uptime"), "4242.10 12345.67\n").expect("Could not write uptime");
+        let _proc_root = ProcRootOverride::new(fixture_dir.path());
+
+        let uptime = SystemDataReader::<Uptime>::new().unwrap().read().unwrap();
+
+        assert_eq!(uptime.uptime().as_secs(), 4242);
+    }
+}
+
+#[cfg(test)]
+mod test_proc_snapshot {
+    use std::path::PathBuf;
+
+    use crate::core::metrics::{BytesMetric, PercentMetric};
+    use crate::core::probe::Probe;
+    use crate::core::process::ProcessScanner;
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::procfs::cpu_probe::CpuProbe;
+    use crate::procfs::libc::page_size;
+    use crate::procfs::memory_probe::MemoryProbe;
+    use crate::procfs::process::ProcfsScanner;
+    use crate::procfs::test_utils::ProcRootOverride;
+
+    /// A copy of a proc filesystem holding the processes `systemd` (PID 1) and `nginx` (PID 4242)
+    fn snapshot_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/proc_snapshot")
+    }
+
+    #[test]
+    fn test_scanner_should_read_processes_of_snapshot() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let _proc_root = ProcRootOverride::new(snapshot_dir());
+        let mut scanner = ProcfsScanner::new().unwrap();
+
+        let mut pids = scanner.scan().unwrap();
+        pids.sort();
+        let nginx = scanner.fetch_metadata(4242).unwrap();
+
+        assert_eq!(pids, vec![1, 4242]);
+        assert_eq!(nginx.command(), "nginx");
+        assert_eq!(nginx.cmdline(), "nginx: master process /usr/sbin/nginx");
+        assert_eq!(nginx.ppid(), 1);
+        assert_eq!(nginx.unit(), Some("nginx.service"));
+    }
+
+    #[test]
+    fn test_cpu_probe_should_read_usage_of_snapshot() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let _proc_root = ProcRootOverride::new(snapshot_dir());
+        let mut probe = CpuProbe::new(10).unwrap();
+
+        probe.init_iteration().unwrap();
+
+        assert_eq!(probe.probe(4242).unwrap(), PercentMetric::new(10.));
+        assert_eq!(probe.probe(1).unwrap(), PercentMetric::new(5.));
+    }
+
+    #[test]
+    fn test_memory_probe_should_read_footprint_of_snapshot() {
+        let _proc_root = ProcRootOverride::new(snapshot_dir());
+        let mut probe = MemoryProbe::new(10, true).unwrap();
+
+        let footprint = probe.probe(4242).unwrap();
+
+        assert_eq!(footprint, BytesMetric::new(2560 * page_size().unwrap() as usize));
+    }
+}
@@ -0,0 +1,142 @@
+//! Detection of the processes isolated in other namespaces, such as the processes of containers
+
+use std::fs::read_link;
+
+use crate::procfs::proc_root;
+
+/// The identifiers of the PID and mount namespaces of a process
+///
+/// An identifier is `None` when it could not be read, which is the case for the processes of other users unless spv
+/// runs as root.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Namespaces {
+    pid: Option<u64>,
+    mnt: Option<u64>,
+}
+
+impl Namespaces {
+    /// Reads the namespaces of a process from the links of `/proc/[process_dir]/ns`
+    ///
+    /// # Arguments
+    ///  * `process_dir`: The directory of the process in the proc filesystem: its PID, or `self` for spv itself
+    pub fn read(process_dir: &str) -> Self {
+        Namespaces {
+            pid: read_namespace(process_dir, "pid"),
+            mnt: read_namespace(process_dir, "mnt"),
+        }
+    }
+
+    /// Indicates if a process in these namespaces is isolated from a process in the `other` namespaces
+    ///
+    /// Namespaces which could not be read on either side are not compared.
+    pub fn isolated_from(&self, other: &Namespaces) -> bool {
+        differs(self.pid, other.pid) || differs(self.mnt, other.mnt)
+    }
+}
+
+fn differs(namespace: Option<u64>, other_namespace: Option<u64>) -> bool {
+    matches!((namespace, other_namespace), (Some(ns), Some(other_ns)) if ns != other_ns)
+}
+
+fn read_namespace(process_dir: &str, kind: &str) -> Option<u64> {
+    let link = proc_root().join(process_dir).join("ns").join(kind);
+
+    read_link(link)
+        .ok()
+        .and_then(|target| target.to_str().and_then(|t| parse_namespace_inode(t, kind)))
+}
+
+/// Extracts the inode of a namespace from the target of a namespace link (e.g. `pid:[4026531836]`)
+///
+/// Returns `None` if the link does not refer to a namespace of the given kind
+fn parse_namespace_inode(link_target: &str, kind: &str) -> Option<u64> {
+    link_target
+        .strip_prefix(kind)
+        .and_then(|s| s.strip_prefix(":["))
+        .and_then(|s| s.strip_suffix(']'))
+        .and_then(|inode| inode.parse().ok())
+}
+
+#[cfg(test)]
+mod test_namespace_comparison {
+    use rstest::*;
+
+    use crate::procfs::namespaces::{parse_namespace_inode, Namespaces};
+
+    #[rstest]
+    #[case("pid:[4026531836]", "pid", Some(4026531836))]
+    #[case("mnt:[4026531840]", "mnt", Some(4026531840))]
+    #[case("mnt:[4026531840]", "pid", None)]
+    #[case("pid:[abc]", "pid", None)]
+    #[case("/dev/null", "pid", None)]
+    fn test_should_parse_inode_of_namespace_links_of_given_kind(
+        #[case] link_target: &str,
+        #[case] kind: &str,
+        #[case] expected: Option<u64>,
+    ) {
+        assert_eq!(parse_namespace_inode(link_target, kind), expected);
+    }
+
+    #[rstest]
+    #[case(Namespaces { pid: Some(1), mnt: Some(2) }, false)]
+    #[case(Namespaces { pid: Some(3), mnt: Some(2) }, true)]
+    #[case(Namespaces { pid: Some(1), mnt: Some(3) }, true)]
+    #[case(Namespaces { pid: None, mnt: Some(2) }, false)]
+    #[case(Namespaces { pid: None, mnt: None }, false)]
+    fn test_should_detect_isolation_from_differing_namespaces(#[case] namespaces: Namespaces, #[case] expected: bool) {
+        let own_namespaces = Namespaces {
+            pid: Some(1),
+            mnt: Some(2),
+        };
+
+        assert_eq!(namespaces.isolated_from(&own_namespaces), expected);
+    }
+
+    #[test]
+    fn test_should_not_detect_isolation_when_own_namespaces_are_unknown() {
+        let namespaces = Namespaces {
+            pid: Some(1),
+            mnt: Some(2),
+        };
+
+        assert!(!namespaces.isolated_from(&Namespaces::default()));
+    }
+}
+
+#[cfg(test)]
+mod test_namespaces_reading {
+    use std::fs;
+    use std::os::unix::fs::symlink;
+
+    use tempfile::tempdir;
+
+    use crate::procfs::namespaces::Namespaces;
+    use crate::procfs::test_utils::ProcRootOverride;
+
+    #[test]
+    fn test_should_read_namespaces_from_links_of_process() {
+        let fixture_dir = tempdir().expect("Could not create tmp dir");
+        let ns_dir = fixture_dir.path().join("123").join("ns");
+        fs::create_dir_all(&ns_dir).expect("Could not create ns dir");
+        // The targets of namespace links do not exist on the filesystem
+        symlink("pid:[4026532200]", ns_dir.join("pid")).expect("Could not create pid ns link");
+        symlink("mnt:[4026532100]", ns_dir.join("mnt")).expect("Could not create mnt ns link");
+        let _proc_root = ProcRootOverride::new(fixture_dir.path());
+
+        assert_eq!(
+            Namespaces::read("123"),
+            Namespaces {
+                pid: Some(4026532200),
+                mnt: Some(4026532100)
+            }
+        );
+    }
+
+    #[test]
+    fn test_namespaces_should_be_unknown_when_links_are_missing() {
+        let fixture_dir = tempdir().expect("Could not create tmp dir");
+        let _proc_root = ProcRootOverride::new(fixture_dir.path());
+
+        assert_eq!(Namespaces::read("123"), Namespaces::default());
+    }
+}
@@ -0,0 +1,435 @@
+//! Usage of NVIDIA GPUs by processes, as monitored by `nvidia-smi pmon`
+//!
+//! `nvidia-smi pmon` prints the usage of the GPUs by each process once per second, below a header naming its columns:
+//!
+//! ```text
+//! # gpu         pid   type     sm    mem    enc    dec    jpg    ofa     fb   command
+//! # Idx           #    C/G      %      %      %      %      %      %     MB   name
+//!     0       2345     G      3      1      -      -      -      -     52   Xorg
+//! ```
+//!
+//! The columns vary with the version of the driver, and a `-` replaces the values which are not available.
+
+use std::collections::HashMap;
+use std::io;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::thread;
+use std::time::Duration;
+#[cfg(not(test))]
+use std::time::Instant;
+
+#[cfg(test)]
+use sn_fake_clock::FakeClock as Instant;
+
+use crate::core::process::Pid;
+use crate::procfs::gpu_probe::{GpuSupport, GpuUsage, ReadGpuUsage};
+use crate::procfs::ProcfsError;
+
+/// How long `nvidia-smi` can take to print its header, after which it is considered unavailable
+const PMON_STARTUP_TIMEOUT: Duration = Duration::from_secs(2);
+/// How long a sample is kept once received, as `nvidia-smi pmon` stops listing the processes which stop using a GPU
+const PMON_SAMPLE_LIFETIME: Duration = Duration::from_secs(3);
+
+/// The positions of the columns of the output of `nvidia-smi pmon`
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct PmonColumns {
+    gpu: usize,
+    pid: usize,
+    /// The utilization of the streaming multiprocessors, which older drivers do not report for each process
+    sm: Option<usize>,
+    /// The frame buffer memory, in MB
+    fb: Option<usize>,
+}
+
+impl PmonColumns {
+    fn support(&self) -> GpuSupport {
+        GpuSupport {
+            utilization: self.sm.is_some(),
+            memory: self.fb.is_some(),
+        }
+    }
+}
+
+/// The usage of a GPU by a process, as sampled by `nvidia-smi pmon`
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct PmonSample {
+    gpu: u32,
+    pid: Pid,
+    /// The percentage of time during which the GPU ran the kernels of the process
+    sm: Option<f64>,
+    /// The GPU memory used by the process, in bytes
+    fb: Option<u64>,
+}
+
+/// A line of the output of `nvidia-smi pmon`
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) enum PmonLine {
+    Header(PmonColumns),
+    Sample(PmonSample),
+}
+
+/// Parses the output of `nvidia-smi pmon`, line by line
+#[derive(Default)]
+pub(crate) struct PmonParser {
+    columns: Option<PmonColumns>,
+}
+
+impl PmonParser {
+    /// Parses a line of the output, which is ignored if it is neither the header naming the columns nor a sample
+    fn parse_line(&mut self, line: &str) -> Option<PmonLine> {
+        if let Some(columns) = parse_pmon_header(line) {
+            self.columns = Some(columns.clone());
+            return Some(PmonLine::Header(columns));
+        }
+
+        let columns = self.columns.as_ref()?;
+        parse_pmon_sample(columns, line).map(PmonLine::Sample)
+    }
+}
+
+/// Parses the header naming the columns of the output (e.g. `# gpu pid type sm mem fb command`)
+///
+/// Returns `None` for the other lines, including the header giving the units of the columns.
+fn parse_pmon_header(line: &str) -> Option<PmonColumns> {
+    let names: Vec<&str> = line.strip_prefix('#')?.split_whitespace().collect();
+    let position = |name: &str| names.iter().position(|n| *n == name);
+
+    Some(PmonColumns {
+        gpu: position("gpu")?,
+        pid: position("pid")?,
+        sm: position("sm"),
+        fb: position("fb"),
+    })
+}
+
+/// Parses a sample of the usage of a GPU by a process
+///
+/// Returns `None` for comments, and for the lines of GPUs which no process uses, whose PID is `-`.
+fn parse_pmon_sample(columns: &PmonColumns, line: &str) -> Option<PmonSample> {
+    if line.starts_with('#') {
+        return None;
+    }
+
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let value = |pos: usize| tokens.get(pos).copied().filter(|token| *token != "-");
+
+    Some(PmonSample {
+        gpu: value(columns.gpu)?.parse().ok()?,
+        pid: value(columns.pid)?.parse().ok()?,
+        sm: columns.sm.and_then(value).and_then(|sm| sm.parse().ok()),
+        fb: columns
+            .fb
+            .and_then(value)
+            .and_then(|fb| fb.parse::<u64>().ok())
+            .map(|mb| mb * 1024 * 1024),
+    })
+}
+
+/// The latest samples of the usage of each GPU by each process
+#[derive(Default)]
+struct PmonUsages {
+    samples: HashMap<(u32, Pid), (Instant, PmonSample)>,
+}
+
+impl PmonUsages {
+    fn record(&mut self, sample: PmonSample, now: Instant) {
+        self.samples.insert((sample.gpu, sample.pid), (now, sample));
+    }
+
+    /// Discards the samples of the processes which `nvidia-smi` stopped listing
+    fn expire(&mut self, now: Instant) {
+        self.samples
+            .retain(|_, (received, _)| now - *received <= PMON_SAMPLE_LIFETIME);
+    }
+
+    /// Returns the usage of the GPUs by a process, which is null if the process does not use any GPU
+    fn usage(&self, pid: Pid) -> GpuUsage {
+        self.samples
+            .values()
+            .filter(|(_, sample)| sample.pid == pid)
+            .fold(GpuUsage::default(), |usage, (_, sample)| GpuUsage {
+                utilization: usage.utilization.max(sample.sm.unwrap_or(0.)),
+                memory: usage.memory + sample.fb.unwrap_or(0),
+            })
+    }
+
+    fn cleanup(&mut self, pid: Pid) {
+        self.samples.retain(|(_, sample_pid), _| *sample_pid != pid);
+    }
+}
+
+/// Reads the usage of NVIDIA GPUs by processes from a `nvidia-smi pmon` child process
+///
+/// The child process runs as long as the reader, and prints its samples to a thread which forwards them to the reader.
+pub(crate) struct PmonUsageReader {
+    child: Child,
+    receiver: Receiver<PmonLine>,
+    support: GpuSupport,
+    usages: PmonUsages,
+}
+
+impl PmonUsageReader {
+    /// Starts monitoring the NVIDIA GPUs
+    ///
+    /// Returns `None` if `nvidia-smi` is not installed, or does not find a GPU to monitor.
+    pub fn spawn() -> Option<Self> {
+        let mut child = Command::new("nvidia-smi")
+            .args(["pmon", "--select", "um"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+
+        let stdout = child.stdout.take().expect("The standard output of nvidia-smi is piped");
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut parser = PmonParser::default();
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                // The receiver is gone once the reader is dropped, along with its child process
+                if let Some(line) = parser.parse_line(&line) {
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        let mut reader = PmonUsageReader {
+            child,
+            receiver,
+            support: GpuSupport::default(),
+            usages: PmonUsages::default(),
+        };
+
+        // The child process is killed when the reader is dropped
+        match reader.receiver.recv_timeout(PMON_STARTUP_TIMEOUT) {
+            Ok(PmonLine::Header(columns)) => {
+                reader.support = columns.support();
+                Some(reader)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ReadGpuUsage for PmonUsageReader {
+    fn support(&self) -> GpuSupport {
+        self.support
+    }
+
+    fn refresh(&mut self) -> Result<(), ProcfsError> {
+        let now = Instant::now();
+
+        loop {
+            match self.receiver.try_recv() {
+                Ok(PmonLine::Sample(sample)) => self.usages.record(sample, now),
+                Ok(PmonLine::Header(_)) => (),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    return Err(io::Error::other("nvidia-smi pmon stopped monitoring the GPUs").into())
+                }
+            }
+        }
+
+        self.usages.expire(now);
+        Ok(())
+    }
+
+    fn read(&mut self, pid: Pid) -> Result<GpuUsage, ProcfsError> {
+        Ok(self.usages.usage(pid))
+    }
+
+    fn cleanup(&mut self, pid: Pid) {
+        self.usages.cleanup(pid);
+    }
+}
+
+impl Drop for PmonUsageReader {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod test_parse_pmon_header {
+    use rstest::*;
+
+    use crate::procfs::nvidia_pmon::{parse_pmon_header, PmonColumns};
+
+    #[rstest]
+    #[case(
+        "# gpu         pid   type     sm    mem    enc    dec    jpg    ofa     fb   command",
+        PmonColumns { gpu: 0, pid: 1, sm: Some(3), fb: Some(9) }
+    )]
+    #[case(
+        "# gpu        pid  type    sm   mem   enc   dec    fb   command",
+        PmonColumns { gpu: 0, pid: 1, sm: Some(3), fb: Some(7) }
+    )]
+    #[case("# gpu        pid  type    fb   command", PmonColumns { gpu: 0, pid: 1, sm: None, fb: Some(3) })]
+    fn test_should_locate_columns(#[case] line: &str, #[case] expected: PmonColumns) {
+        assert_eq!(parse_pmon_header(line), Some(expected));
+    }
+
+    #[rstest]
+    #[case("# Idx           #    C/G      %      %      %      %      %      %     MB   name")]
+    #[case("    0       2345     G      3      1      -      -      -      -     52   Xorg")]
+    #[case("")]
+    fn test_should_ignore_other_lines(#[case] line: &str) {
+        assert_eq!(parse_pmon_header(line), None);
+    }
+}
+
+#[cfg(test)]
+mod test_parse_pmon_sample {
+    use rstest::*;
+
+    use crate::procfs::nvidia_pmon::{parse_pmon_header, parse_pmon_sample, PmonColumns, PmonSample};
+
+    fn columns() -> PmonColumns {
+        parse_pmon_header("# gpu         pid   type     sm    mem    enc    dec    jpg    ofa     fb   command")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_should_parse_utilization_and_memory_of_process() {
+        let line = "    1       2345     C     87     40      -      -      -      -   1530   python";
+
+        assert_eq!(
+            parse_pmon_sample(&columns(), line),
+            Some(PmonSample {
+                gpu: 1,
+                pid: 2345,
+                sm: Some(87.),
+                fb: Some(1530 * 1024 * 1024),
+            })
+        );
+    }
+
+    #[test]
+    fn test_should_parse_unavailable_values_as_none() {
+        let line = "    0       2345     G      -      -      -      -      -      -     52   Xorg";
+
+        assert_eq!(
+            parse_pmon_sample(&columns(), line),
+            Some(PmonSample {
+                gpu: 0,
+                pid: 2345,
+                sm: None,
+                fb: Some(52 * 1024 * 1024),
+            })
+        );
+    }
+
+    #[rstest]
+    #[case("    0          -     -      -      -      -      -      -      -      -   -")]
+    #[case("# Idx           #    C/G      %      %      %      %      %      %     MB   name")]
+    #[case("")]
+    fn test_should_ignore_lines_without_process(#[case] line: &str) {
+        assert_eq!(parse_pmon_sample(&columns(), line), None);
+    }
+}
+
+#[cfg(test)]
+mod test_pmon_parser {
+    use crate::procfs::nvidia_pmon::{PmonLine, PmonParser};
+
+    #[test]
+    fn test_should_parse_samples_following_header() {
+        let output = "# gpu        pid  type    sm   mem   enc   dec    fb   command\n\
+                      # Idx          #   C/G     %     %     %     %    MB   name\n\
+                      \x20   0       2345     C    12     3     -     -   200   python\n";
+        let mut parser = PmonParser::default();
+
+        let lines: Vec<PmonLine> = output.lines().filter_map(|line| parser.parse_line(line)).collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(matches!(lines[0], PmonLine::Header(_)));
+        assert!(matches!(lines[1], PmonLine::Sample(sample) if sample.pid == 2345 && sample.sm == Some(12.)));
+    }
+
+    #[test]
+    fn test_should_ignore_samples_preceding_header() {
+        let mut parser = PmonParser::default();
+
+        assert_eq!(
+            parser.parse_line("    0       2345     C    12     3     -     -   200   python"),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_pmon_usages {
+    use std::time::Duration;
+
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::procfs::gpu_probe::GpuUsage;
+    use crate::procfs::nvidia_pmon::{Instant, PmonSample, PmonUsages, PMON_SAMPLE_LIFETIME};
+
+    fn sample(gpu: u32, pid: u32, sm: Option<f64>, fb: Option<u64>) -> PmonSample {
+        PmonSample { gpu, pid, sm, fb }
+    }
+
+    #[test]
+    fn test_should_combine_usage_of_gpus_by_process() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let now = Instant::now();
+        let mut usages = PmonUsages::default();
+
+        usages.record(sample(0, 1, Some(20.), Some(1024)), now);
+        usages.record(sample(1, 1, Some(60.), None), now);
+        usages.record(sample(1, 2, Some(90.), Some(4096)), now);
+
+        assert_eq!(
+            usages.usage(1),
+            GpuUsage {
+                utilization: 60.,
+                memory: 1024
+            }
+        );
+    }
+
+    #[test]
+    fn test_should_have_no_usage_for_unlisted_process() {
+        let usages = PmonUsages::default();
+
+        assert_eq!(usages.usage(1), GpuUsage::default());
+    }
+
+    #[test]
+    fn test_should_replace_previous_sample_of_same_gpu() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let now = Instant::now();
+        let mut usages = PmonUsages::default();
+
+        usages.record(sample(0, 1, Some(20.), Some(1024)), now);
+        usages.record(sample(0, 1, Some(30.), Some(2048)), now);
+
+        assert_eq!(
+            usages.usage(1),
+            GpuUsage {
+                utilization: 30.,
+                memory: 2048
+            }
+        );
+    }
+
+    #[test]
+    fn test_should_expire_samples_of_processes_which_are_not_listed_anymore() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let start = Instant::now();
+        let mut usages = PmonUsages::default();
+        usages.record(sample(0, 1, Some(20.), Some(1024)), start);
+        usages.record(sample(0, 2, Some(20.), Some(1024)), start + Duration::from_secs(2));
+
+        usages.expire(start + PMON_SAMPLE_LIFETIME + Duration::from_secs(1));
+
+        assert_eq!(usages.usage(1), GpuUsage::default());
+        assert_eq!(usages.usage(2).utilization, 20.);
+    }
+}
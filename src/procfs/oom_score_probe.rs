@@ -0,0 +1,104 @@
+//! OOM score probing
+
+use crate::core::metrics::ScoreMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::parsers::process::PidOomScore;
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData};
+use crate::procfs::proc_root;
+
+/// Probe implementation to read the OOM score of processes
+///
+/// When the system runs out of memory, the OOM killer kills the process with the highest score. Sorting processes by
+/// their score then shows which processes would be killed first under memory pressure.
+pub struct OomScoreProbe {
+    oom_score_reader: Box<dyn ReadProcessData<PidOomScore>>,
+}
+
+impl OomScoreProbe {
+    /// Creates a new probe that can read the OOM score of processes
+    ///
+    /// Fails if the kernel does not expose the OOM score of processes, as on some emulated kernels.
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many files descriptor the probe should keep open at most
+    pub fn new(fd_limit: usize) -> Result<Self, Error> {
+        let own_score = proc_root().join("self").join("oom_score");
+        if let Err(e) = own_score.metadata() {
+            return Err(Error::ProbingError(
+                "The OOM score of processes is not available".to_string(),
+                e.into(),
+            ));
+        }
+
+        Ok(Self::from_reader(Box::new(ProcessDataReader::with_capacity(fd_limit))))
+    }
+
+    fn from_reader(oom_score_reader: Box<dyn ReadProcessData<PidOomScore>>) -> Self {
+        OomScoreProbe { oom_score_reader }
+    }
+}
+
+impl Probe<ScoreMetric> for OomScoreProbe {
+    fn name(&self) -> &'static str {
+        "OOM score"
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<ScoreMetric, Error> {
+        let oom_score = self
+            .oom_score_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read OOM score of PID {}", pid), e.into()))?;
+
+        Ok(ScoreMetric::new(oom_score.score()))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().for_each(|pid| self.oom_score_reader.cleanup(*pid));
+    }
+}
+
+#[cfg(test)]
+mod test_oom_score_probe {
+    use rstest::*;
+
+    use crate::core::metrics::ScoreMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::fake_procfs::FakeProcfs;
+    use crate::procfs::oom_score_probe::OomScoreProbe;
+
+    #[fixture]
+    fn procfs() -> FakeProcfs {
+        let procfs = FakeProcfs::new();
+        procfs.spawn_process(10, "postgres");
+        procfs.set_oom_score("self", 0);
+        procfs.set_oom_score("10", 667);
+
+        procfs
+    }
+
+    #[rstest]
+    fn test_should_read_oom_score_of_process(_procfs: FakeProcfs) {
+        let mut probe = OomScoreProbe::new(10).unwrap();
+
+        assert_eq!(probe.probe(10).unwrap(), ScoreMetric::new(667));
+    }
+
+    #[rstest]
+    fn test_should_fail_to_probe_process_which_died(procfs: FakeProcfs) {
+        let mut probe = OomScoreProbe::new(10).unwrap();
+
+        procfs.kill_process(10);
+
+        assert!(probe.probe(10).is_err());
+    }
+
+    #[rstest]
+    fn test_should_be_unavailable_without_oom_score() {
+        let procfs = FakeProcfs::new();
+        procfs.spawn_process(10, "postgres");
+
+        assert!(OomScoreProbe::new(10).is_err());
+    }
+}
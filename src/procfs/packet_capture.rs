@@ -0,0 +1,340 @@
+//! Capture of the TCP and UDP traffic of the host
+//!
+//! The kernel does not account the traffic of each process. Instead, a packet socket receives a copy of the packets
+//! sent and received by the host, whose headers tell the protocol and the endpoints of the socket they belong to.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::os::fd::OwnedFd;
+use std::sync::mpsc;
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
+use std::thread;
+
+use crate::procfs::libc::{open_packet_socket, receive_packet};
+use crate::procfs::socket_protocols::Protocol;
+use crate::procfs::ProcfsError;
+
+/// How many bytes of each packet are copied, which covers the IP header and the ports of the TCP or UDP header
+const CAPTURED_HEADERS_SIZE: usize = 128;
+
+const IPV4_HEADER_MIN_SIZE: usize = 20;
+const IPV6_HEADER_SIZE: usize = 40;
+const TCP_PROTOCOL_NUMBER: u8 = 6;
+const UDP_PROTOCOL_NUMBER: u8 = 17;
+
+/// The direction of a packet, as seen from the host
+#[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
+pub enum Direction {
+    Incoming,
+    Outgoing,
+}
+
+/// The packets exchanged in one direction between a local and a remote endpoint, over TCP or UDP
+#[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
+pub struct Flow {
+    pub protocol: Protocol,
+    pub direction: Direction,
+    pub local: SocketAddr,
+    pub remote: SocketAddr,
+}
+
+/// A TCP or UDP packet sent or received by the host
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+struct Packet {
+    flow: Flow,
+    /// The size of the IP packet, headers included, in bytes
+    length: usize,
+}
+
+/// Types which can capture the TCP and UDP traffic of the host
+pub(crate) trait CaptureTraffic {
+    /// Returns the bytes exchanged by each flow since the last call
+    fn take(&mut self) -> Result<HashMap<Flow, usize>, ProcfsError>;
+}
+
+/// Captures the TCP and UDP packets sent and received by all the network interfaces of the host
+///
+/// The packets are received from a packet socket by a background thread, which stops on the first packet received
+/// after the capture is dropped.
+pub(crate) struct PacketCapture {
+    receiver: Receiver<Result<Packet, ProcfsError>>,
+}
+
+impl PacketCapture {
+    /// Starts capturing packets, which requires the `CAP_NET_RAW` capability
+    pub fn start() -> Result<Self, ProcfsError> {
+        let socket = open_packet_socket()?;
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || capture_packets(socket, sender));
+
+        Ok(PacketCapture { receiver })
+    }
+}
+
+impl CaptureTraffic for PacketCapture {
+    fn take(&mut self) -> Result<HashMap<Flow, usize>, ProcfsError> {
+        let mut traffic = HashMap::new();
+
+        loop {
+            match self.receiver.try_recv() {
+                Ok(packet) => {
+                    let packet = packet?;
+                    *traffic.entry(packet.flow).or_default() += packet.length;
+                }
+                Err(TryRecvError::Empty) => return Ok(traffic),
+                Err(TryRecvError::Disconnected) => {
+                    return Err(io::Error::other("The capture of packets stopped").into());
+                }
+            }
+        }
+    }
+}
+
+/// Receives the packets of the socket until the receiving end of the channel is dropped, or until the socket fails
+fn capture_packets(socket: OwnedFd, sender: Sender<Result<Packet, ProcfsError>>) {
+    let mut buffer = [0; CAPTURED_HEADERS_SIZE];
+
+    loop {
+        let packet = match receive_packet(&socket, &mut buffer) {
+            Ok(Some(received)) => {
+                let direction = if received.outgoing {
+                    Direction::Outgoing
+                } else {
+                    Direction::Incoming
+                };
+                let headers = &buffer[..received.length.min(buffer.len())];
+                match parse_packet(headers, received.length, direction) {
+                    Some(packet) => Ok(packet),
+                    None => continue,
+                }
+            }
+            Ok(None) => continue,
+            // The reception is interrupted when a signal is delivered to this thread
+            Err(ProcfsError::IOError(e)) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => Err(e),
+        };
+
+        let failed = packet.is_err();
+        if sender.send(packet).is_err() || failed {
+            break;
+        }
+    }
+}
+
+/// Parses the headers of an IPv4 or IPv6 packet, and returns it if it carries a TCP or UDP segment
+///
+/// Fragments other than the first one of a packet are not parsed, as they do not carry the ports of the segment.
+///
+/// # Arguments
+///  * `headers`: The beginning of the packet, starting with its IP header
+///  * `length`: The size of the whole packet, in bytes
+///  * `direction`: Whether the packet was sent or received by the host
+fn parse_packet(headers: &[u8], length: usize, direction: Direction) -> Option<Packet> {
+    let version = headers.first()? >> 4;
+
+    let (protocol_number, source_ip, destination_ip, segment) = match version {
+        4 => {
+            let header_size = usize::from(headers[0] & 0x0F) * 4;
+            let fragment_offset = u16::from_be_bytes(headers.get(6..8)?.try_into().ok()?) & 0x1FFF;
+            if header_size < IPV4_HEADER_MIN_SIZE || fragment_offset != 0 {
+                return None;
+            }
+
+            let source: [u8; 4] = headers.get(12..16)?.try_into().ok()?;
+            let destination: [u8; 4] = headers.get(16..20)?.try_into().ok()?;
+            (
+                *headers.get(9)?,
+                IpAddr::V4(Ipv4Addr::from(source)),
+                IpAddr::V4(Ipv4Addr::from(destination)),
+                headers.get(header_size..)?,
+            )
+        }
+        6 => {
+            let source: [u8; 16] = headers.get(8..24)?.try_into().ok()?;
+            let destination: [u8; 16] = headers.get(24..40)?.try_into().ok()?;
+            // Packets with extension headers are not parsed, as their segment does not directly follow this header
+            (
+                *headers.get(6)?,
+                IpAddr::V6(Ipv6Addr::from(source)),
+                IpAddr::V6(Ipv6Addr::from(destination)),
+                headers.get(IPV6_HEADER_SIZE..)?,
+            )
+        }
+        _ => return None,
+    };
+
+    let protocol = match protocol_number {
+        TCP_PROTOCOL_NUMBER => Protocol::Tcp,
+        UDP_PROTOCOL_NUMBER => Protocol::Udp,
+        _ => return None,
+    };
+
+    let source = SocketAddr::new(source_ip, u16::from_be_bytes(segment.get(0..2)?.try_into().ok()?));
+    let destination = SocketAddr::new(destination_ip, u16::from_be_bytes(segment.get(2..4)?.try_into().ok()?));
+    let (local, remote) = match direction {
+        Direction::Outgoing => (source, destination),
+        Direction::Incoming => (destination, source),
+    };
+
+    Some(Packet {
+        flow: Flow {
+            protocol,
+            direction,
+            local,
+            remote,
+        },
+        length,
+    })
+}
+
+#[cfg(test)]
+mod test_parse_packet {
+    use rstest::*;
+
+    use crate::procfs::packet_capture::{parse_packet, Direction, Flow, Packet};
+    use crate::procfs::socket_protocols::Protocol;
+
+    /// Returns the headers of an IPv4 packet carrying a segment from 10.0.2.15:41652 to 172.26.250.142:443
+    fn ipv4_headers(protocol_number: u8) -> Vec<u8> {
+        let mut headers = vec![
+            0x45,
+            0x00,
+            0x05,
+            0xDC, // Version, header size, DSCP, total length
+            0x1C,
+            0x46,
+            0x40,
+            0x00, // Identification, flags and fragment offset
+            0x40,
+            protocol_number,
+            0x00,
+            0x00, // TTL, protocol, checksum
+            10,
+            0,
+            2,
+            15, // Source address
+            172,
+            26,
+            250,
+            142, // Destination address
+        ];
+        headers.extend([0xA2, 0xB4, 0x01, 0xBB]); // Source and destination ports
+        headers
+    }
+
+    /// Returns the headers of an IPv6 packet carrying a segment from [2001:db8::1]:8080 to [2001:db8::2]:53
+    fn ipv6_headers(next_header: u8) -> Vec<u8> {
+        let mut headers = vec![0x60, 0x00, 0x00, 0x00, 0x05, 0xB4, next_header, 0x40];
+        headers.extend([0x20, 0x01, 0x0D, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        headers.extend([0x20, 0x01, 0x0D, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2]);
+        headers.extend([0x1F, 0x90, 0x00, 0x35]);
+        headers
+    }
+
+    fn packet(protocol: Protocol, direction: Direction, local: &str, remote: &str, length: usize) -> Packet {
+        Packet {
+            flow: Flow {
+                protocol,
+                direction,
+                local: local.parse().unwrap(),
+                remote: remote.parse().unwrap(),
+            },
+            length,
+        }
+    }
+
+    #[rstest]
+    #[case(6, Protocol::Tcp)]
+    #[case(17, Protocol::Udp)]
+    fn test_should_parse_protocol_of_ipv4_packet(#[case] protocol_number: u8, #[case] expected: Protocol) {
+        let headers = ipv4_headers(protocol_number);
+
+        assert_eq!(
+            parse_packet(&headers, 1500, Direction::Outgoing),
+            Some(packet(
+                expected,
+                Direction::Outgoing,
+                "10.0.2.15:41652",
+                "172.26.250.142:443",
+                1500
+            ))
+        );
+    }
+
+    #[test]
+    fn test_should_take_destination_of_incoming_packet_as_local_endpoint() {
+        let headers = ipv4_headers(6);
+
+        assert_eq!(
+            parse_packet(&headers, 1500, Direction::Incoming),
+            Some(packet(
+                Protocol::Tcp,
+                Direction::Incoming,
+                "172.26.250.142:443",
+                "10.0.2.15:41652",
+                1500
+            ))
+        );
+    }
+
+    #[test]
+    fn test_should_skip_options_of_ipv4_header() {
+        let mut headers = ipv4_headers(17);
+        headers[0] = 0x46;
+        headers.splice(20..20, [0x01, 0x01, 0x01, 0x00]);
+
+        assert_eq!(
+            parse_packet(&headers, 100, Direction::Outgoing),
+            Some(packet(
+                Protocol::Udp,
+                Direction::Outgoing,
+                "10.0.2.15:41652",
+                "172.26.250.142:443",
+                100
+            ))
+        );
+    }
+
+    #[rstest]
+    #[case(6, Protocol::Tcp)]
+    #[case(17, Protocol::Udp)]
+    fn test_should_parse_ipv6_packet(#[case] next_header: u8, #[case] expected: Protocol) {
+        let headers = ipv6_headers(next_header);
+
+        assert_eq!(
+            parse_packet(&headers, 1460, Direction::Outgoing),
+            Some(packet(
+                expected,
+                Direction::Outgoing,
+                "[2001:db8::1]:8080",
+                "[2001:db8::2]:53",
+                1460
+            ))
+        );
+    }
+
+    #[test]
+    fn test_should_ignore_packets_of_other_protocols() {
+        assert_eq!(parse_packet(&ipv4_headers(1), 84, Direction::Incoming), None);
+        assert_eq!(parse_packet(&ipv6_headers(58), 104, Direction::Incoming), None);
+    }
+
+    #[test]
+    fn test_should_ignore_fragments_without_ports() {
+        let mut headers = ipv4_headers(17);
+        headers[6] = 0x00;
+        headers[7] = 0xB9;
+
+        assert_eq!(parse_packet(&headers, 1500, Direction::Incoming), None);
+    }
+
+    #[rstest]
+    #[case(&[])]
+    #[case(&[0x45, 0x00, 0x05, 0xDC])]
+    #[case(&[0x20, 0x00, 0x05, 0xDC])]
+    fn test_should_ignore_truncated_or_unknown_packets(#[case] headers: &[u8]) {
+        assert_eq!(parse_packet(headers, 1500, Direction::Incoming), None);
+    }
+}
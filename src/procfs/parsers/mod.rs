@@ -3,9 +3,12 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::fs::File;
+#[cfg(test)]
 use std::io::{Read, Seek, SeekFrom};
 use std::marker::PhantomData;
+use std::os::unix::fs::FileExt;
 use std::path::{Path, PathBuf};
+use std::{io, str};
 
 use crate::core::process::Pid;
 use crate::procfs::ProcfsError;
@@ -28,6 +31,12 @@ pub trait ProcessData: Parse {
     fn filepath(pid: Pid) -> PathBuf;
 }
 
+/// Specialization of a `Data` type which is associated to a file descriptor of a process
+#[cfg(feature = "gpu")]
+pub trait FileDescriptorData: Parse {
+    fn filepath(pid: Pid, fd: u32) -> PathBuf;
+}
+
 /// Type which can read a `SystemData`
 pub trait ReadSystemData<D>
 where
@@ -149,6 +158,76 @@ where
     }
 }
 
+/// Type which can read a `FileDescriptorData`
+#[cfg(feature = "gpu")]
+pub trait ReadFileDescriptorData<D>
+where
+    D: FileDescriptorData + Sized,
+{
+    fn read(&mut self, pid: Pid, fd: u32) -> Result<D, ProcfsError>;
+
+    /// Closes the files kept open for the file descriptors of the process which are not among the given ones
+    fn retain(&mut self, pid: Pid, fds: &[u32]);
+
+    fn cleanup(&mut self, pid: Pid);
+}
+
+/// Reads data from procfs files bound to a file descriptor of a process (in `/proc/[pid]/fdinfo/[fd]`)
+///
+/// Like `ProcessDataReader`, this reader keeps the files it read open, until it holds as many as its capacity.
+#[cfg(feature = "gpu")]
+pub struct FileDescriptorDataReader<D>
+where
+    D: FileDescriptorData + Sized,
+{
+    readers: HashMap<(Pid, u32), ProcfsFileReader<D>>,
+    capacity: usize,
+}
+
+#[cfg(feature = "gpu")]
+impl<D> FileDescriptorDataReader<D>
+where
+    D: FileDescriptorData + Sized,
+{
+    pub fn with_capacity(capacity: usize) -> Self {
+        FileDescriptorDataReader {
+            readers: HashMap::new(),
+            capacity,
+        }
+    }
+}
+
+#[cfg(feature = "gpu")]
+impl<D> ReadFileDescriptorData<D> for FileDescriptorDataReader<D>
+where
+    D: FileDescriptorData + Sized,
+{
+    fn read(&mut self, pid: Pid, fd: u32) -> Result<D, ProcfsError> {
+        let full = self.readers.len() >= self.capacity;
+
+        let data_ret = match self.readers.entry((pid, fd)) {
+            Entry::Occupied(mut o) => o.get_mut().read(),
+            Entry::Vacant(_) if full => ProcfsFileReader::new(D::filepath(pid, fd).as_path())?.read(),
+            Entry::Vacant(v) => v.insert(ProcfsFileReader::new(D::filepath(pid, fd).as_path())?).read(),
+        };
+
+        if data_ret.is_err() {
+            self.readers.remove(&(pid, fd));
+        }
+
+        data_ret
+    }
+
+    fn retain(&mut self, pid: Pid, fds: &[u32]) {
+        self.readers
+            .retain(|(reader_pid, reader_fd), _| *reader_pid != pid || fds.contains(reader_fd));
+    }
+
+    fn cleanup(&mut self, pid: Pid) {
+        self.readers.retain(|(reader_pid, _), _| *reader_pid != pid);
+    }
+}
+
 /// This structure is there to help `ProcessDataReader` limit the amount of opened files.
 ///
 /// As opening a file is an expensive operation, we want to keep as many files open as possible.
@@ -281,42 +360,95 @@ where
     }
 }
 
-/// This reader parses a struct implementing `Parse` from any structure which implements the `Read + Seek` traits
+/// How many bytes are read at most by each read syscall
+const READ_CHUNK_SIZE: usize = 4096;
+
+/// Sources whose whole content can be read repeatedly, from their start
+trait ReadFromStart {
+    /// Replaces the content of `buffer` with the whole content of the source
+    fn read_from_start(&mut self, buffer: &mut Vec<u8>) -> io::Result<()>;
+}
+
+/// Files are read with positional reads (`pread`), which leave the offset of the file untouched
+///
+/// Rewinding a file then reading it takes a `lseek` syscall on top of the `read` syscalls, which go on until one of
+/// them reads nothing. Reading from offset 0 with `pread` spares the `lseek` syscall, so that a file fitting in a
+/// chunk, such as `/proc/[pid]/stat`, is read with two syscalls instead of three.
+impl ReadFromStart for File {
+    fn read_from_start(&mut self, buffer: &mut Vec<u8>) -> io::Result<()> {
+        buffer.clear();
+
+        loop {
+            let offset = buffer.len();
+            buffer.resize(offset + READ_CHUNK_SIZE, 0);
+
+            match self.read_at(&mut buffer[offset..], offset as u64) {
+                Ok(0) => {
+                    buffer.truncate(offset);
+                    return Ok(());
+                }
+                Ok(read_bytes) => buffer.truncate(offset + read_bytes),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => buffer.truncate(offset),
+                Err(e) => {
+                    buffer.truncate(offset);
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+/// Reads a source by rewinding it then reading it until its end, as spv used to read procfs files
+#[cfg(test)]
+struct RewindingSource<R: Read + Seek>(R);
+
+#[cfg(test)]
+impl<R: Read + Seek> ReadFromStart for RewindingSource<R> {
+    fn read_from_start(&mut self, buffer: &mut Vec<u8>) -> io::Result<()> {
+        buffer.clear();
+        self.0.seek(SeekFrom::Start(0))?;
+        self.0.read_to_end(buffer).map(|_| ())
+    }
+}
+
+/// This reader parses a struct implementing `Parse` from any source which can be read from its start
+///
+/// The buffer in which the source is read is reused by each read.
 struct DataReader<R, D>
 where
-    R: Read + Seek,
+    R: ReadFromStart,
     D: Parse + Sized,
 {
     src: R,
+    buffer: Vec<u8>,
     phantom: PhantomData<D>,
 }
 
 impl<R, D> DataReader<R, D>
 where
-    R: Read + Seek,
+    R: ReadFromStart,
     D: Parse + Sized,
 {
     pub fn new(src: R) -> Self {
         DataReader {
             src,
+            buffer: Vec::with_capacity(READ_CHUNK_SIZE),
             phantom: PhantomData,
         }
     }
 
     pub fn read(&mut self) -> Result<D, ProcfsError> {
-        self.src.seek(SeekFrom::Start(0))?;
-
-        // Might be optimized, by not reallocating at each call
-        let mut stat_content = String::new();
-        self.src.read_to_string(&mut stat_content)?;
+        self.src.read_from_start(&mut self.buffer)?;
 
-        let tp = TokenParser::new(&stat_content);
+        let content = str::from_utf8(&self.buffer)
+            .map_err(|e| ProcfsError::InvalidFileContent(format!("Content is not valid UTF-8: {}", e)))?;
+        let tp = TokenParser::new(content);
 
         D::parse(&tp)
     }
 }
 
-/// Parses space-separated token from a given multi-line string slice
+/// Parses space-separated (or tab-separated) token from a given multi-line string slice
 pub struct TokenParser<'a> {
     lines: Vec<Vec<&'a str>>,
 }
@@ -329,13 +461,23 @@ impl<'a> TokenParser<'a> {
         let mut lines = Vec::<Vec<&'a str>>::new();
 
         for line in content.split('\n') {
-            let tokens: Vec<&str> = line.split(' ').filter(|t| !t.is_empty()).collect();
+            let tokens: Vec<&str> = line.split([' ', '\t']).filter(|t| !t.is_empty()).collect();
             lines.push(tokens);
         }
 
         TokenParser { lines }
     }
 
+    /// Returns the number of lines of the parsed content, including empty lines
+    fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Indicates if the given line contains no token
+    fn is_line_empty(&self, line_no: usize) -> bool {
+        self.lines.get(line_no).is_none_or(|tokens| tokens.is_empty())
+    }
+
     /// Get the value of a token from the parser
     /// # Arguments
     ///  * `line_no`: The line number from which to retrieve the token
@@ -365,13 +507,78 @@ impl<'a> TokenParser<'a> {
                 Err(ProcfsError::InvalidFileContent(err_msg))
             })
     }
+
+    /// Get the value of a token which older kernels may not report
+    ///
+    /// Returns `None` if the line has no token at the given position, and an error if the token can not be parsed.
+    /// # Arguments
+    ///  * `line_no`: The line number from which to retrieve the token
+    ///  * `pos`: The position of the token in the line (e.g. 1 for token 'b' in line 'a b c')
+    fn optional_token<T>(&self, line_no: usize, pos: usize) -> Result<Option<T>, ProcfsError>
+    where
+        T: std::str::FromStr,
+    {
+        let is_present = self.lines.get(line_no).is_some_and(|tokens| pos < tokens.len());
+        if !is_present {
+            return Ok(None);
+        }
+
+        self.token(line_no, pos).map(Some)
+    }
+
+    /// Get the value of a token from a line starting with a given key, such as the lines of `/proc/[pid]/status`
+    /// # Arguments
+    ///  * `key`: The key identifying the line, without its trailing colon (e.g. 'Tgid' for line 'Tgid: 123')
+    ///  * `pos`: The position of the token following the key (e.g. 0 for token '123' in line 'Tgid: 123 kB')
+    fn keyed_token<T>(&self, key: &str, pos: usize) -> Result<T, ProcfsError>
+    where
+        T: std::str::FromStr,
+    {
+        let line_no = self
+            .lines
+            .iter()
+            .position(|tokens| tokens.first().and_then(|t| t.strip_suffix(':')) == Some(key))
+            .ok_or_else(|| ProcfsError::InvalidFileFormat(format!("Could not find key {}", key)))?;
+
+        self.token(line_no, pos + 1)
+    }
+
+    /// Get the value of a `field=value` token from a line starting with a given key, such as the lines of
+    /// `/proc/pressure/cpu`
+    /// # Arguments
+    ///  * `key`: The first token of the line (e.g. 'some' for line 'some avg10=1.50 avg60=0.75')
+    ///  * `field`: The name of the field whose value to get (e.g. 'avg10' for value '1.50' in the line above)
+    fn assigned_token<T>(&self, key: &str, field: &str) -> Result<T, ProcfsError>
+    where
+        T: std::str::FromStr,
+    {
+        let line = self
+            .lines
+            .iter()
+            .find(|tokens| tokens.first() == Some(&key))
+            .ok_or_else(|| ProcfsError::InvalidFileFormat(format!("Could not find key {}", key)))?;
+
+        line.iter()
+            .skip(1)
+            .find_map(|token| token.strip_prefix(field).and_then(|t| t.strip_prefix('=')))
+            .ok_or_else(|| ProcfsError::InvalidFileFormat(format!("Could not find field {} of key {}", field, key)))?
+            .parse::<T>()
+            .map_err(|_| {
+                ProcfsError::InvalidFileContent(format!("The field {} of key {} could not be parsed", field, key))
+            })
+    }
 }
 
 #[cfg(test)]
 mod test_data_reader {
+    use std::fs::{self, File};
     use std::io::Cursor;
 
-    use crate::procfs::parsers::{DataReader, Parse, ProcfsError, TokenParser};
+    use tempfile::NamedTempFile;
+
+    use crate::procfs::parsers::{
+        DataReader, Parse, ProcfsError, ProcfsFileReader, RewindingSource, TokenParser, READ_CHUNK_SIZE,
+    };
 
     #[derive(PartialEq, Debug)]
     struct TestSystemData {
@@ -379,6 +586,24 @@ mod test_data_reader {
         field_2: i16,
     }
 
+    /// Parses all the tokens of a file, to compare the whole content read by readers
+    #[derive(PartialEq, Debug)]
+    struct AllTokens(Vec<Vec<String>>);
+
+    impl Parse for AllTokens {
+        fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+            let lines = (0..token_parser.line_count())
+                .map(|line_no| {
+                    (0..)
+                        .map_while(|token_no| token_parser.optional_token(line_no, token_no).ok().flatten())
+                        .collect()
+                })
+                .collect();
+
+            Ok(AllTokens(lines))
+        }
+    }
+
     impl Parse for TestSystemData {
         fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
             Ok(TestSystemData {
@@ -390,7 +615,7 @@ mod test_data_reader {
 
     #[test]
     fn test_load_correctly_data() {
-        let data_src = Cursor::new(b"12 -92 abc");
+        let data_src = RewindingSource(Cursor::new(b"12 -92 abc"));
 
         let mut data_reader = DataReader::new(data_src);
 
@@ -402,6 +627,52 @@ mod test_data_reader {
             })
         ));
     }
+
+    fn read_both_ways(path: &std::path::Path) -> (AllTokens, AllTokens) {
+        let mut rewinding_reader = DataReader::new(RewindingSource(File::open(path).unwrap()));
+        let mut positional_reader = ProcfsFileReader::new(path).unwrap();
+
+        (rewinding_reader.read().unwrap(), positional_reader.read().unwrap())
+    }
+
+    #[test]
+    fn test_positional_reads_should_parse_same_data_as_rewinding_reads() {
+        let file = NamedTempFile::new().unwrap();
+        // Spans several chunks, to be read by several positional reads
+        let content: String = (0..READ_CHUNK_SIZE).map(|i| format!("{} {}\n", i, i * 2)).collect();
+        fs::write(file.path(), content).unwrap();
+
+        let (rewound, positional) = read_both_ways(file.path());
+
+        assert_eq!(positional, rewound);
+        assert_eq!(positional.0[READ_CHUNK_SIZE - 1], vec!["4095", "8190"]);
+    }
+
+    #[test]
+    fn test_positional_reads_should_parse_same_data_as_rewinding_reads_from_procfs() {
+        let (rewound, positional) = read_both_ways("/proc/self/limits".as_ref());
+
+        assert_eq!(positional, rewound);
+    }
+
+    #[test]
+    fn test_positional_reads_should_read_whole_file_again() {
+        let file = NamedTempFile::new().unwrap();
+        fs::write(file.path(), "1 2").unwrap();
+        let mut reader = ProcfsFileReader::<TestSystemData>::new(file.path()).unwrap();
+        reader.read().unwrap();
+
+        fs::write(file.path(), "3 4").unwrap();
+
+        assert_eq!(reader.read().unwrap(), TestSystemData { field_1: 3, field_2: 4 });
+    }
+
+    #[test]
+    fn test_should_fail_to_parse_invalid_utf8() {
+        let mut data_reader = DataReader::<_, TestSystemData>::new(RewindingSource(Cursor::new(b"\xff\xfe")));
+
+        assert!(matches!(data_reader.read(), Err(ProcfsError::InvalidFileContent(_))));
+    }
 }
 
 #[cfg(test)]
@@ -435,6 +706,76 @@ mod test_token_parser {
 
         assert!(tp.token::<u8>(1, 1).is_err());
     }
+
+    #[test]
+    fn test_optional_token_should_be_none_when_absent() {
+        let tp = TokenParser::new("1 2 3");
+
+        assert!(matches!(tp.optional_token::<u8>(0, 3), Ok(None)));
+        assert!(matches!(tp.optional_token::<u8>(1, 0), Ok(None)));
+    }
+
+    #[test]
+    fn test_optional_token_should_be_parsed_when_present() {
+        let tp = TokenParser::new("1 2 3");
+
+        assert!(matches!(tp.optional_token::<u8>(0, 2), Ok(Some(3))));
+    }
+
+    #[test]
+    fn test_optional_token_should_fail_when_unparseable() {
+        let tp = TokenParser::new("1 2 a");
+
+        assert!(tp.optional_token::<u8>(0, 2).is_err());
+    }
+
+    #[test]
+    fn test_extract_tab_separated_data() {
+        let tp = TokenParser::new("1\t2\t3");
+
+        assert!(matches!(tp.token::<u8>(0, 2), Ok(3)));
+    }
+
+    #[test]
+    fn test_count_lines_including_empty_ones() {
+        let tp = TokenParser::new("1 2 3\n4 5 6\n");
+
+        assert_eq!(tp.line_count(), 3);
+        assert!(!tp.is_line_empty(1));
+        assert!(tp.is_line_empty(2));
+    }
+
+    #[test]
+    fn test_extract_keyed_data() {
+        let tp = TokenParser::new("Name:\tbash\nTgid:\t42\nVmRSS:\t 1024 kB");
+
+        assert!(matches!(tp.keyed_token::<u32>("Tgid", 0), Ok(42)));
+        assert!(matches!(tp.keyed_token::<u32>("VmRSS", 0), Ok(1024)));
+    }
+
+    #[test]
+    fn test_returns_err_when_key_is_missing() {
+        let tp = TokenParser::new("Name:\tbash\nPid:\t42");
+
+        assert!(tp.keyed_token::<u32>("Tgid", 0).is_err());
+    }
+
+    #[test]
+    fn test_extract_assigned_data() {
+        let tp = TokenParser::new("some avg10=1.50 avg60=0.75 total=42\nfull avg10=0.25 avg60=0.10 total=12");
+
+        assert!(matches!(tp.assigned_token::<f64>("some", "avg60"), Ok(v) if v == 0.75));
+        assert!(matches!(tp.assigned_token::<u64>("full", "total"), Ok(12)));
+    }
+
+    #[test]
+    fn test_returns_err_when_assigned_field_is_missing() {
+        let tp = TokenParser::new("some avg10=1.50 avg60=0.75");
+
+        assert!(tp.assigned_token::<f64>("full", "avg10").is_err());
+        assert!(tp.assigned_token::<f64>("some", "avg300").is_err());
+        assert!(tp.assigned_token::<f64>("some", "avg").is_err());
+    }
 }
 
 /// Modules containing fake readers to be used in tests
@@ -503,7 +844,7 @@ pub mod fakes {
         }
 
         pub fn make_pid_fail(&mut self, pid: Pid) {
-            let err = Err(ProcfsError::IOError(io::Error::new(io::ErrorKind::Other, "oh no!")));
+            let err = Err(ProcfsError::IOError(io::Error::other("oh no!")));
             self.process_data_sequences.insert(pid, vecdeque!(err));
         }
     }
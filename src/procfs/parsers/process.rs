@@ -1,8 +1,12 @@
+#[cfg(feature = "gpu")]
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::core::process::Pid;
+#[cfg(feature = "gpu")]
+use crate::procfs::parsers::FileDescriptorData;
 use crate::procfs::parsers::{Parse, ProcessData, TokenParser};
-use crate::procfs::ProcfsError;
+use crate::procfs::{proc_root, ProcfsError};
 
 /// Represents data from `/proc/\[pid\]/comm`
 #[derive(Eq, PartialEq, Debug, Clone)]
@@ -39,7 +43,7 @@ impl ProcessData for Comm {
     fn filepath(pid: Pid) -> PathBuf {
         let mut pb = PathBuf::new();
 
-        pb.push("/proc");
+        pb.push(proc_root());
         pb.push(pid.to_string());
         pb.push("comm");
 
@@ -65,9 +69,85 @@ mod test_comm {
     }
 }
 
+/// Represents data from `/proc/\[pid\]/cmdline`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Cmdline {
+    args: String,
+}
+
+impl Cmdline {
+    #[cfg(test)]
+    pub fn new<A>(args: A) -> Self
+    where
+        A: Into<String>,
+    {
+        Cmdline { args: args.into() }
+    }
+
+    /// Returns the command line of the process, with its arguments separated by spaces
+    ///
+    /// The command line of kernel threads and zombie processes is empty.
+    pub fn into_args(self) -> String {
+        self.args
+    }
+}
+
+impl Parse for Cmdline {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        // Arguments are separated by null bytes, and may themselves contain spaces
+        let args: Vec<&str> = token_parser
+            .lines
+            .iter()
+            .flatten()
+            .flat_map(|token| token.split('\0'))
+            .filter(|arg| !arg.is_empty())
+            .collect();
+
+        Ok(Cmdline { args: args.join(" ") })
+    }
+}
+
+impl ProcessData for Cmdline {
+    fn filepath(pid: Pid) -> PathBuf {
+        let mut pb = PathBuf::new();
+
+        pb.push(proc_root());
+        pb.push(pid.to_string());
+        pb.push("cmdline");
+
+        pb
+    }
+}
+
+#[cfg(test)]
+mod test_cmdline {
+    use rstest::*;
+
+    use crate::procfs::parsers::process::Cmdline;
+    use crate::procfs::parsers::{Parse, TokenParser};
+
+    #[rstest]
+    #[case("", "")]
+    #[case("bash\0", "bash")]
+    #[case("java\0-jar\0kafka.jar\0", "java -jar kafka.jar")]
+    #[case("sh\0-c\0sleep 10\0", "sh -c sleep 10")]
+    fn test_should_join_arguments_with_spaces(#[case] cmdline_content: &str, #[case] expected_args: &str) {
+        let parser = TokenParser::new(cmdline_content);
+        let cmdline = Cmdline::parse(&parser).expect("Cannot parse cmdline");
+
+        assert_eq!(cmdline.into_args(), expected_args)
+    }
+}
+
 /// Represents data from `/proc/[PID]/stat`
 #[derive(Eq, PartialEq, Debug, Copy, Clone)]
 pub struct PidStat {
+    /// The state of the process (e.g. 'R' when running, 'T' when stopped by a signal)
+    // scanf format: %c
+    state: char,
+    /// The number of major faults of the process, which required loading a memory page from disk
+    // scanf format: %lu
+    majflt: u64,
     /// Time spent by the process in user mode
     // scanf format: %lu
     utime: u32,
@@ -83,11 +163,26 @@ pub struct PidStat {
     /// The time the process started after system boot, expressed in clock ticks
     // scanf format: %llu
     starttime: u64,
+    /// Time spent by the process waiting for block I/O, in clock ticks. It is only accounted for when the kernel
+    /// tracks the delays of tasks
+    // scanf format: %llu
+    delayacct_blkio_ticks: u64,
 }
 
 impl PidStat {
+    /// The time spent running by the process and by its waited-for children, in clock ticks
     pub fn running_time(&self) -> i64 {
-        self.utime as i64 + self.stime as i64 + self.cutime as i64 + self.cstime as i64
+        self.own_runtime() + self.children_runtime()
+    }
+
+    /// The time spent running by the process itself, in user and kernel mode, in clock ticks
+    pub fn own_runtime(&self) -> i64 {
+        self.utime as i64 + self.stime as i64
+    }
+
+    /// The time spent running by the terminated children of the process it has waited for, in clock ticks
+    pub fn children_runtime(&self) -> i64 {
+        self.cutime as i64 + self.cstime as i64
     }
 
     /// Indicates how long after boot time the process started
@@ -95,16 +190,35 @@ impl PidStat {
     pub fn starttime(&self) -> u64 {
         self.starttime
     }
+
+    /// The time spent by the process waiting for block I/O to complete, in clock ticks
+    pub fn blkio_ticks(&self) -> u64 {
+        self.delayacct_blkio_ticks
+    }
+
+    /// The number of major page faults of the process since it started
+    pub fn major_faults(&self) -> u64 {
+        self.majflt
+    }
+
+    /// Indicates if the process has been stopped by a signal, such as SIGSTOP
+    pub fn is_stopped(&self) -> bool {
+        self.state == 'T'
+    }
 }
 
 impl Parse for PidStat {
     fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
         Ok(PidStat {
-            utime: token_parser.token(0, 12)?,
-            stime: token_parser.token(0, 13)?,
-            cutime: token_parser.token(0, 14)?,
-            cstime: token_parser.token(0, 15)?,
+            state: token_parser.token(0, 2)?,
+            majflt: token_parser.token(0, 11)?,
+            utime: token_parser.token(0, 13)?,
+            stime: token_parser.token(0, 14)?,
+            cutime: token_parser.token(0, 15)?,
+            cstime: token_parser.token(0, 16)?,
             starttime: token_parser.token(0, 21)?,
+            // Kernels older than 2.6.18 do not report this field
+            delayacct_blkio_ticks: token_parser.optional_token(0, 41)?.unwrap_or(0),
         })
     }
 }
@@ -113,7 +227,7 @@ impl ProcessData for PidStat {
     fn filepath(pid: u32) -> PathBuf {
         let mut path = PathBuf::new();
 
-        path.push("/proc");
+        path.push(proc_root());
         path.push(pid.to_string());
         path.push("stat");
 
@@ -126,25 +240,48 @@ impl PidStat {
     /// PidStat constructor for test purposes
     pub fn new(utime: u32, stime: u32, cutime: i32, cstime: i32, starttime: u64) -> Self {
         PidStat {
+            state: 'R',
+            majflt: 0,
             utime,
             stime,
             cutime,
             cstime,
             starttime,
+            delayacct_blkio_ticks: 0,
         }
     }
+
+    /// Replaces the state of the process
+    pub fn with_state(mut self, state: char) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// Replaces the number of major page faults of the process
+    pub fn with_major_faults(mut self, major_faults: u64) -> Self {
+        self.majflt = major_faults;
+        self
+    }
+
+    /// Replaces the time the process spent waiting for block I/O
+    pub fn with_blkio_ticks(mut self, blkio_ticks: u64) -> Self {
+        self.delayacct_blkio_ticks = blkio_ticks;
+        self
+    }
 }
 
 #[cfg(test)]
 mod test_pid_stat {
     use std::string::ToString;
 
+    use rstest::*;
+
     use super::*;
 
     #[test]
     fn test_parse_stat_file() {
-        let content = "1905 (python3) S 1877 1905 1877 34822 1905 4194304 1096 0 0 \
-13 42 11 10 0 20 0 1 0 487679 13963264 2541 18446744073709551615 4194304 7010805 \
+        let content = "1905 (python3) S 1877 1905 1877 34822 1905 4194304 1096 0 7 0 \
+13 42 11 10 20 0 1 0 487679 13963264 2541 18446744073709551615 4194304 7010805 \
 140731882007344 0 0 0 0 16781312 134217730 1 0 0 17 0 0 0 0 0 0 9362864 9653016 \
 10731520 140731882009319 140731882009327 140731882009327 140731882012647 0"
             .to_string();
@@ -156,36 +293,365 @@ mod test_pid_stat {
         assert_eq!(
             pid_stat,
             PidStat {
+                state: 'S',
+                majflt: 7,
                 utime: 13,
                 stime: 42,
                 cutime: 11,
                 cstime: 10,
-                starttime: 487679
+                starttime: 487679,
+                delayacct_blkio_ticks: 0
             }
         );
     }
 
+    #[test]
+    fn test_parse_blkio_ticks() {
+        let content = "1905 (python3) D 1877 1905 1877 34822 1905 4194304 1096 0 0 0 \
+13 42 11 10 20 0 1 0 487679 13963264 2541 18446744073709551615 4194304 7010805 \
+140731882007344 0 0 0 0 16781312 134217730 1 0 0 17 0 0 0 73 0 0 9362864 9653016"
+            .to_string();
+
+        let pid_stat = PidStat::parse(&TokenParser::new(&content)).expect("Could not read PidStat");
+
+        assert_eq!(pid_stat.blkio_ticks(), 73);
+    }
+
+    #[test]
+    fn test_should_not_account_blkio_ticks_when_not_reported() {
+        let content = "1905 (python3) S 1877 1905 1877 34822 1905 4194304 1096 0 0 0 13 42 11 10 20 0 1 0 487679 0 0";
+
+        let pid_stat = PidStat::parse(&TokenParser::new(content)).expect("Could not read PidStat");
+
+        assert_eq!(pid_stat.blkio_ticks(), 0);
+    }
+
     #[test]
     fn test_running_time() {
         let pid_stat = PidStat {
+            state: 'R',
+            majflt: 0,
             utime: 1,
             stime: 2,
             cutime: 4,
             cstime: 8,
             starttime: 10,
+            delayacct_blkio_ticks: 0,
         };
 
         assert_eq!(15, pid_stat.running_time())
     }
 
+    #[test]
+    fn test_should_split_own_and_children_runtimes() {
+        let pid_stat = PidStat {
+            state: 'R',
+            majflt: 0,
+            utime: 1,
+            stime: 2,
+            cutime: 4,
+            cstime: 8,
+            starttime: 10,
+            delayacct_blkio_ticks: 0,
+        };
+
+        assert_eq!(pid_stat.own_runtime(), 3);
+        assert_eq!(pid_stat.children_runtime(), 12);
+    }
+
+    #[rstest]
+    #[case('T', true)]
+    #[case('R', false)]
+    #[case('S', false)]
+    #[case('t', false)]
+    fn test_process_should_only_be_stopped_in_stopped_state(#[case] state: char, #[case] expected: bool) {
+        assert_eq!(PidStat::new(0, 0, 0, 0, 0).with_state(state).is_stopped(), expected);
+    }
+
     #[test]
     fn filepath_should_contain_pid() {
         assert_eq!(PidStat::filepath(456), PathBuf::from("/proc/456/stat"))
     }
 }
 
+/// Represents data from `/proc/[PID]/status`
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct PidStatus {
+    /// The thread group ID of the process
+    tgid: Pid,
+    /// The PID of the parent of the process, or 0 if it has no parent
+    ppid: Pid,
+    /// The number of times the process gave up the CPU before the end of its time slice (e.g. to wait for I/O)
+    voluntary_ctxt_switches: u64,
+    /// The size of the hugetlb memory of the process, in kB
+    hugetlb_pages: u64,
+    /// The size of the stack segment of the process, in kB
+    stack_size: u64,
+    /// The size of the data segment of the process, which holds its heap, in kB
+    data_size: u64,
+}
+
+impl PidStatus {
+    pub fn tgid(&self) -> Pid {
+        self.tgid
+    }
+
+    pub fn ppid(&self) -> Pid {
+        self.ppid
+    }
+
+    pub fn voluntary_ctxt_switches(&self) -> u64 {
+        self.voluntary_ctxt_switches
+    }
+
+    /// Returns the size of the hugetlb memory of the process, in bytes
+    pub fn hugetlb_bytes(&self) -> u64 {
+        self.hugetlb_pages * 1024
+    }
+
+    /// Returns the size of the stack segment of the process, in bytes
+    pub fn stack_bytes(&self) -> u64 {
+        self.stack_size * 1024
+    }
+
+    /// Returns the size of the data segment of the process, in bytes
+    pub fn data_bytes(&self) -> u64 {
+        self.data_size * 1024
+    }
+}
+
+#[cfg(test)]
+impl PidStatus {
+    pub fn new(tgid: Pid) -> Self {
+        PidStatus {
+            tgid,
+            ppid: 0,
+            voluntary_ctxt_switches: 0,
+            hugetlb_pages: 0,
+            stack_size: 0,
+            data_size: 0,
+        }
+    }
+
+    pub fn with_voluntary_ctxt_switches(tgid: Pid, voluntary_ctxt_switches: u64) -> Self {
+        PidStatus {
+            tgid,
+            ppid: 0,
+            voluntary_ctxt_switches,
+            hugetlb_pages: 0,
+            stack_size: 0,
+            data_size: 0,
+        }
+    }
+
+    pub fn with_hugetlb_pages(tgid: Pid, hugetlb_pages: u64) -> Self {
+        PidStatus {
+            tgid,
+            ppid: 0,
+            voluntary_ctxt_switches: 0,
+            hugetlb_pages,
+            stack_size: 0,
+            data_size: 0,
+        }
+    }
+
+    pub fn with_ppid(mut self, ppid: Pid) -> Self {
+        self.ppid = ppid;
+        self
+    }
+
+    /// Sets the sizes of the stack and data segments of the process, in kB
+    pub fn with_segments(mut self, stack_size: u64, data_size: u64) -> Self {
+        self.stack_size = stack_size;
+        self.data_size = data_size;
+        self
+    }
+}
+
+impl Parse for PidStatus {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        Ok(PidStatus {
+            tgid: token_parser.keyed_token("Tgid", 0)?,
+            ppid: token_parser.keyed_token("PPid", 0)?,
+            voluntary_ctxt_switches: token_parser.keyed_token("voluntary_ctxt_switches", 0)?,
+            // Kernels older than 4.4 do not report hugetlb memory
+            hugetlb_pages: token_parser.keyed_token("HugetlbPages", 0).unwrap_or(0),
+            // Kernel threads have no memory segments of their own
+            stack_size: token_parser.keyed_token("VmStk", 0).unwrap_or(0),
+            data_size: token_parser.keyed_token("VmData", 0).unwrap_or(0),
+        })
+    }
+}
+
+impl ProcessData for PidStatus {
+    fn filepath(pid: Pid) -> PathBuf {
+        let mut path = PathBuf::new();
+
+        path.push(proc_root());
+        path.push(pid.to_string());
+        path.push("status");
+
+        path
+    }
+}
+
+#[cfg(test)]
+mod test_pid_status {
+    use std::path::PathBuf;
+
+    use crate::procfs::parsers::process::PidStatus;
+    use crate::procfs::parsers::{Parse, ProcessData, TokenParser};
+
+    #[test]
+    fn test_should_produce_correct_file_path() {
+        assert_eq!(PidStatus::filepath(42), PathBuf::from("/proc/42/status"));
+    }
+
+    const STATUS_CONTENT: &str = "Name:\tfirefox
+Umask:\t0022
+State:\tS (sleeping)
+Tgid:\t4321
+Ngid:\t0
+Pid:\t4325
+PPid:\t1
+TracerPid:\t0
+VmData:\t  123456 kB
+VmStk:\t     132 kB
+HugetlbPages:\t  4096 kB
+voluntary_ctxt_switches:\t1500
+nonvoluntary_ctxt_switches:\t42";
+
+    #[test]
+    fn test_should_parse_tgid() {
+        let token_parser = TokenParser::new(STATUS_CONTENT);
+        let pid_status = PidStatus::parse(&token_parser).unwrap();
+
+        assert_eq!(pid_status.tgid(), 4321);
+    }
+
+    #[test]
+    fn test_should_parse_ppid() {
+        let token_parser = TokenParser::new(STATUS_CONTENT);
+        let pid_status = PidStatus::parse(&token_parser).unwrap();
+
+        assert_eq!(pid_status.ppid(), 1);
+    }
+
+    #[test]
+    fn test_should_parse_voluntary_context_switches() {
+        let token_parser = TokenParser::new(STATUS_CONTENT);
+        let pid_status = PidStatus::parse(&token_parser).unwrap();
+
+        assert_eq!(pid_status.voluntary_ctxt_switches(), 1500);
+    }
+
+    #[test]
+    fn test_should_parse_hugetlb_pages() {
+        let token_parser = TokenParser::new(STATUS_CONTENT);
+        let pid_status = PidStatus::parse(&token_parser).unwrap();
+
+        assert_eq!(pid_status.hugetlb_bytes(), 4096 * 1024);
+    }
+
+    #[test]
+    fn test_should_have_no_hugetlb_pages_when_line_is_missing() {
+        let token_parser = TokenParser::new("Tgid:\t4321\nPPid:\t1\nvoluntary_ctxt_switches:\t1500");
+        let pid_status = PidStatus::parse(&token_parser).unwrap();
+
+        assert_eq!(pid_status.hugetlb_bytes(), 0);
+    }
+
+    #[test]
+    fn test_should_parse_stack_and_data_segments() {
+        let token_parser = TokenParser::new(STATUS_CONTENT);
+        let pid_status = PidStatus::parse(&token_parser).unwrap();
+
+        assert_eq!(pid_status.stack_bytes(), 132 * 1024);
+        assert_eq!(pid_status.data_bytes(), 123456 * 1024);
+    }
+
+    #[test]
+    fn test_should_have_empty_segments_when_lines_are_missing() {
+        let token_parser = TokenParser::new("Tgid:\t2\nPPid:\t0\nvoluntary_ctxt_switches:\t1500");
+        let pid_status = PidStatus::parse(&token_parser).unwrap();
+
+        assert_eq!((pid_status.stack_bytes(), pid_status.data_bytes()), (0, 0));
+    }
+
+    #[test]
+    fn test_should_fail_when_tgid_is_missing() {
+        let token_parser = TokenParser::new("Name:\tfirefox\nPid:\t4325\nvoluntary_ctxt_switches:\t1500");
+
+        assert!(PidStatus::parse(&token_parser).is_err());
+    }
+}
+
+/// Represents data from `/proc/[PID]/statm`
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct PidStatm {
+    /// The amount of pages of the process which are resident in memory
+    resident: u64,
+}
+
+impl PidStatm {
+    pub fn resident_pages(&self) -> u64 {
+        self.resident
+    }
+}
+
+#[cfg(test)]
+impl PidStatm {
+    pub fn new(resident: u64) -> Self {
+        PidStatm { resident }
+    }
+}
+
+impl Parse for PidStatm {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        Ok(PidStatm {
+            resident: token_parser.token(0, 1)?,
+        })
+    }
+}
+
+impl ProcessData for PidStatm {
+    fn filepath(pid: Pid) -> PathBuf {
+        let mut path = PathBuf::new();
+
+        path.push(proc_root());
+        path.push(pid.to_string());
+        path.push("statm");
+
+        path
+    }
+}
+
+#[cfg(test)]
+mod test_pid_statm {
+    use std::path::PathBuf;
+
+    use crate::procfs::parsers::process::PidStatm;
+    use crate::procfs::parsers::{Parse, ProcessData, TokenParser};
+
+    #[test]
+    fn test_should_produce_correct_file_path() {
+        assert_eq!(PidStatm::filepath(42), PathBuf::from("/proc/42/statm"));
+    }
+
+    #[test]
+    fn test_should_parse_resident_pages() {
+        let token_parser = TokenParser::new("3624 1130 780 220 0 410 0");
+
+        assert_eq!(PidStatm::parse(&token_parser).unwrap().resident_pages(), 1130);
+    }
+}
+
 #[derive(Eq, PartialEq, Debug, Copy, Clone)]
 pub struct PidIO {
+    read_chars: usize,
+    written_chars: usize,
+    read_syscalls: usize,
+    write_syscalls: usize,
     read_bytes: usize,
     write_bytes: usize,
     cancelled_write_bytes: usize,
@@ -200,22 +666,64 @@ impl PidIO {
     pub fn written_bytes(&self) -> usize {
         self.write_bytes.saturating_sub(self.cancelled_write_bytes)
     }
+
+    /// Returns the amount of bytes read by the process through syscalls, whether they reached the disk or not
+    ///
+    /// Contrary to `read_bytes()`, this includes the bytes read from the page cache, pipes and sockets.
+    pub fn read_chars(&self) -> usize {
+        self.read_chars
+    }
+
+    /// Returns the amount of bytes written by the process through syscalls, whether they reached the disk or not
+    pub fn written_chars(&self) -> usize {
+        self.written_chars
+    }
+
+    /// Returns the amount of read syscalls made by the process, whether they reached the disk or not
+    pub fn read_syscalls(&self) -> usize {
+        self.read_syscalls
+    }
+
+    /// Returns the amount of write syscalls made by the process, whether they reached the disk or not
+    pub fn write_syscalls(&self) -> usize {
+        self.write_syscalls
+    }
 }
 
 #[cfg(test)]
 impl PidIO {
     pub fn new(read_bytes: usize, write_bytes: usize, cancelled_write_bytes: usize) -> Self {
         PidIO {
+            read_chars: 0,
+            written_chars: 0,
+            read_syscalls: 0,
+            write_syscalls: 0,
             read_bytes,
             write_bytes,
             cancelled_write_bytes,
         }
     }
+
+    pub fn with_syscalls(mut self, read_syscalls: usize, write_syscalls: usize) -> Self {
+        self.read_syscalls = read_syscalls;
+        self.write_syscalls = write_syscalls;
+        self
+    }
+
+    pub fn with_chars(mut self, read_chars: usize, written_chars: usize) -> Self {
+        self.read_chars = read_chars;
+        self.written_chars = written_chars;
+        self
+    }
 }
 
 impl Parse for PidIO {
     fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
         Ok(PidIO {
+            read_chars: token_parser.token(0, 1)?,
+            written_chars: token_parser.token(1, 1)?,
+            read_syscalls: token_parser.token(2, 1)?,
+            write_syscalls: token_parser.token(3, 1)?,
             read_bytes: token_parser.token(4, 1)?,
             write_bytes: token_parser.token(5, 1)?,
             cancelled_write_bytes: token_parser.token(6, 1)?,
@@ -227,7 +735,7 @@ impl ProcessData for PidIO {
     fn filepath(pid: Pid) -> PathBuf {
         let mut path_buf = PathBuf::new();
 
-        path_buf.push("/proc");
+        path_buf.push(proc_root());
         path_buf.push(pid.to_string());
         path_buf.push("io");
 
@@ -263,4 +771,450 @@ mod test_pid_io {
         assert_eq!(pid_io.read_bytes(), 12345);
         assert_eq!(pid_io.written_bytes(), 323932160 - 876);
     }
+
+    #[test]
+    fn test_should_parse_syscalls_apart_from_bytes() {
+        let io_file_content = "rchar: 323934931
+        wchar: 323929600
+        syscr: 632687
+        syscw: 632675
+        read_bytes: 12345
+        write_bytes: 323932160
+        cancelled_write_bytes: 876";
+
+        let token_parser = TokenParser::new(io_file_content);
+        let pid_io = PidIO::parse(&token_parser).unwrap();
+
+        assert_eq!(pid_io.read_syscalls(), 632687);
+        assert_eq!(pid_io.write_syscalls(), 632675);
+    }
+
+    #[test]
+    fn test_should_parse_chars_apart_from_bytes() {
+        let io_file_content = "rchar: 323934931
+        wchar: 323929600
+        syscr: 632687
+        syscw: 632675
+        read_bytes: 12345
+        write_bytes: 323932160
+        cancelled_write_bytes: 876";
+
+        let token_parser = TokenParser::new(io_file_content);
+        let pid_io = PidIO::parse(&token_parser).unwrap();
+
+        assert_eq!(pid_io.read_chars(), 323934931);
+        assert_eq!(pid_io.written_chars(), 323929600);
+    }
+}
+
+/// Represents data from `/proc/[PID]/schedstat`
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct PidSchedstat {
+    /// Time spent by the process waiting on a runqueue for a CPU to be available, in nanoseconds
+    run_delay: usize,
+}
+
+impl PidSchedstat {
+    #[cfg(test)]
+    pub fn new(run_delay: usize) -> Self {
+        PidSchedstat { run_delay }
+    }
+
+    /// Returns the cumulative time spent by the process waiting for a CPU while it was runnable, in nanoseconds
+    pub fn run_delay(&self) -> usize {
+        self.run_delay
+    }
+}
+
+impl Parse for PidSchedstat {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        // The first field is the time spent running on a CPU, which the CPU probe already measures
+        Ok(PidSchedstat {
+            run_delay: token_parser.token(0, 1)?,
+        })
+    }
+}
+
+impl ProcessData for PidSchedstat {
+    fn filepath(pid: Pid) -> PathBuf {
+        let mut path_buf = PathBuf::new();
+
+        path_buf.push(proc_root());
+        path_buf.push(pid.to_string());
+        path_buf.push("schedstat");
+
+        path_buf
+    }
+}
+
+#[cfg(test)]
+mod test_pid_schedstat {
+    use std::path::PathBuf;
+
+    use rstest::*;
+
+    use crate::procfs::parsers::process::PidSchedstat;
+    use crate::procfs::parsers::{Parse, ProcessData, TokenParser};
+
+    #[test]
+    fn test_should_produce_correct_file_path() {
+        assert_eq!(PidSchedstat::filepath(42), PathBuf::from("/proc/42/schedstat"));
+    }
+
+    #[rstest]
+    #[case("58434312 3110289 127\n", 3110289)]
+    #[case("0 0 0", 0)]
+    fn test_should_parse_run_delay_from_second_field(#[case] content: &str, #[case] expected_run_delay: usize) {
+        let schedstat = PidSchedstat::parse(&TokenParser::new(content)).unwrap();
+
+        assert_eq!(schedstat.run_delay(), expected_run_delay);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("58434312")]
+    #[case("58434312 -3 127")]
+    fn test_should_fail_to_parse_invalid_content(#[case] content: &str) {
+        assert!(PidSchedstat::parse(&TokenParser::new(content)).is_err());
+    }
+}
+
+/// Represents data from `/proc/[PID]/limits`
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct PidLimits {
+    /// The soft limit of the amount of files the process can open, or `None` if it is unlimited
+    max_open_files: Option<u64>,
+}
+
+impl PidLimits {
+    /// Returns the soft limit of the amount of files the process can open, or `None` if it is unlimited
+    pub fn max_open_files(&self) -> Option<u64> {
+        self.max_open_files
+    }
+}
+
+impl Parse for PidLimits {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        // The names of the limits span several columns, and are followed by the soft limit, the hard limit and the unit
+        let line_no = token_parser
+            .lines
+            .iter()
+            .position(|tokens| tokens.starts_with(&["Max", "open", "files"]))
+            .ok_or_else(|| ProcfsError::InvalidFileFormat("Could not find limit Max open files".to_string()))?;
+
+        let max_open_files = match token_parser.token::<String>(line_no, 3)?.as_str() {
+            "unlimited" => None,
+            _ => Some(token_parser.token(line_no, 3)?),
+        };
+
+        Ok(PidLimits { max_open_files })
+    }
+}
+
+impl ProcessData for PidLimits {
+    fn filepath(pid: Pid) -> PathBuf {
+        let mut path_buf = PathBuf::new();
+
+        path_buf.push(proc_root());
+        path_buf.push(pid.to_string());
+        path_buf.push("limits");
+
+        path_buf
+    }
+}
+
+#[cfg(test)]
+mod test_pid_limits {
+    use std::path::PathBuf;
+
+    use rstest::*;
+
+    use crate::procfs::parsers::process::PidLimits;
+    use crate::procfs::parsers::{Parse, ProcessData, TokenParser};
+
+    const LIMITS_HEADER: &str = "Limit                     Soft Limit           Hard Limit           Units     \n\
+                                 Max cpu time              unlimited            unlimited            seconds   \n\
+                                 Max file size             unlimited            unlimited            bytes     \n";
+
+    #[test]
+    fn test_should_produce_correct_file_path() {
+        assert_eq!(PidLimits::filepath(42), PathBuf::from("/proc/42/limits"));
+    }
+
+    #[rstest]
+    #[case(
+        "Max open files            1024                 524288               files     \n",
+        Some(1024)
+    )]
+    #[case(
+        "Max open files            unlimited            unlimited            files     \n",
+        None
+    )]
+    fn test_should_parse_soft_limit_of_open_files(#[case] line: &str, #[case] expected: Option<u64>) {
+        let content = format!(
+            "{}{}Max locked memory         8388608              8388608              bytes\n",
+            LIMITS_HEADER, line
+        );
+
+        let limits = PidLimits::parse(&TokenParser::new(&content)).unwrap();
+
+        assert_eq!(limits.max_open_files(), expected);
+    }
+
+    #[rstest]
+    #[case(LIMITS_HEADER)]
+    #[case("Max open files            many                 524288               files     \n")]
+    #[case("Max open files\n")]
+    fn test_should_fail_to_parse_invalid_content(#[case] content: &str) {
+        assert!(PidLimits::parse(&TokenParser::new(content)).is_err());
+    }
+}
+
+/// Represents data from `/proc/[PID]/oom_score`
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct PidOomScore {
+    /// The badness of the process, the process with the highest badness being killed first when memory runs out
+    score: u32,
+}
+
+impl PidOomScore {
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+}
+
+impl Parse for PidOomScore {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        Ok(PidOomScore {
+            score: token_parser.token(0, 0)?,
+        })
+    }
+}
+
+impl ProcessData for PidOomScore {
+    fn filepath(pid: Pid) -> PathBuf {
+        let mut path_buf = PathBuf::new();
+
+        path_buf.push(proc_root());
+        path_buf.push(pid.to_string());
+        path_buf.push("oom_score");
+
+        path_buf
+    }
+}
+
+#[cfg(test)]
+mod test_pid_oom_score {
+    use std::path::PathBuf;
+
+    use rstest::*;
+
+    use crate::procfs::parsers::process::PidOomScore;
+    use crate::procfs::parsers::{Parse, ProcessData, TokenParser};
+
+    #[test]
+    fn test_should_produce_correct_file_path() {
+        assert_eq!(PidOomScore::filepath(42), PathBuf::from("/proc/42/oom_score"));
+    }
+
+    #[rstest]
+    #[case("667\n", 667)]
+    #[case("0", 0)]
+    fn test_should_parse_score(#[case] content: &str, #[case] expected_score: u32) {
+        let oom_score = PidOomScore::parse(&TokenParser::new(content)).unwrap();
+
+        assert_eq!(oom_score.score(), expected_score);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("-3")]
+    #[case("high")]
+    fn test_should_fail_to_parse_invalid_content(#[case] content: &str) {
+        assert!(PidOomScore::parse(&TokenParser::new(content)).is_err());
+    }
+}
+
+/// Represents data from `/proc/\[pid\]/fdinfo/\[fd\]`, for a file descriptor opened on a DRM device (a GPU)
+#[cfg(feature = "gpu")]
+#[derive(Debug, PartialEq, Clone)]
+pub struct DrmFdinfo {
+    client_id: u64,
+    device: String,
+    engines: HashMap<String, u64>,
+    memory: Option<u64>,
+}
+
+#[cfg(feature = "gpu")]
+impl DrmFdinfo {
+    #[cfg(test)]
+    pub fn new(client_id: u64, device: &str, engines: HashMap<String, u64>, memory: Option<u64>) -> Self {
+        DrmFdinfo {
+            client_id,
+            device: device.to_string(),
+            engines,
+            memory,
+        }
+    }
+
+    /// Identifies the client among those of its GPU, even if its file descriptor was duplicated or shared
+    pub fn client_id(&self) -> u64 {
+        self.client_id
+    }
+
+    /// The PCI address of the GPU, or an empty string if the driver does not report it
+    pub fn device(&self) -> &str {
+        &self.device
+    }
+
+    /// The cumulated time during which each engine of the GPU ran the jobs of the client, in nanoseconds
+    ///
+    /// Drivers which do not report the usage of their engines report no engine.
+    pub fn engines(&self) -> &HashMap<String, u64> {
+        &self.engines
+    }
+
+    /// The memory of the GPU allocated by the client, in bytes, if the driver reports it
+    pub fn memory(&self) -> Option<u64> {
+        self.memory
+    }
+}
+
+#[cfg(feature = "gpu")]
+impl Parse for DrmFdinfo {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        let client_id = token_parser.keyed_token("drm-client-id", 0)?;
+        let mut device = String::new();
+        let mut engines = HashMap::new();
+        let mut total_memory = None;
+        let mut legacy_memory = None;
+
+        for line_no in 0..token_parser.line_count() {
+            let Some(key) = token_parser.optional_token::<String>(line_no, 0)? else {
+                continue;
+            };
+            let Some(key) = key.strip_suffix(':') else {
+                continue;
+            };
+
+            if key == "drm-pdev" {
+                device = token_parser.token(line_no, 1)?;
+            } else if key.starts_with("drm-engine-capacity-") {
+                // Holds the amount of engines of a kind, rather than their busy time
+                continue;
+            } else if let Some(engine) = key.strip_prefix("drm-engine-") {
+                engines.insert(engine.to_string(), token_parser.token(line_no, 1)?);
+            } else if key.starts_with("drm-total-") {
+                *total_memory.get_or_insert(0) += memory_size(token_parser, line_no)?;
+            } else if key.starts_with("drm-memory-") {
+                // Older drivers report the memory of each region under these keys, which drm-total-* replaced
+                *legacy_memory.get_or_insert(0) += memory_size(token_parser, line_no)?;
+            }
+        }
+
+        Ok(DrmFdinfo {
+            client_id,
+            device,
+            engines,
+            memory: total_memory.or(legacy_memory),
+        })
+    }
+}
+
+#[cfg(feature = "gpu")]
+impl FileDescriptorData for DrmFdinfo {
+    fn filepath(pid: Pid, fd: u32) -> PathBuf {
+        let mut path = PathBuf::new();
+
+        path.push(proc_root());
+        path.push(pid.to_string());
+        path.push("fdinfo");
+        path.push(fd.to_string());
+
+        path
+    }
+}
+
+/// Parses a size of memory followed by its optional unit, such as `4096 KiB`, in bytes
+#[cfg(feature = "gpu")]
+fn memory_size(token_parser: &TokenParser, line_no: usize) -> Result<u64, ProcfsError> {
+    let size: u64 = token_parser.token(line_no, 1)?;
+    let factor = match token_parser.optional_token::<String>(line_no, 2)?.as_deref() {
+        None => 1,
+        Some("KiB") => 1024,
+        Some("MiB") => 1024 * 1024,
+        Some(unit) => {
+            return Err(ProcfsError::InvalidFileContent(format!(
+                "Unknown unit of memory: {}",
+                unit
+            )))
+        }
+    };
+
+    Ok(size * factor)
+}
+
+#[cfg(all(test, feature = "gpu"))]
+mod test_drm_fdinfo {
+    use std::path::PathBuf;
+
+    use rstest::*;
+
+    use crate::procfs::parsers::process::DrmFdinfo;
+    use crate::procfs::parsers::{FileDescriptorData, Parse, TokenParser};
+
+    #[test]
+    fn test_should_produce_correct_file_path() {
+        assert_eq!(DrmFdinfo::filepath(42, 7), PathBuf::from("/proc/42/fdinfo/7"));
+    }
+
+    #[test]
+    fn test_should_parse_busy_time_of_engines_and_memory() {
+        let content = "pos:\t0\nflags:\t02100002\nmnt_id:\t26\nino:\t1060\ndrm-driver:\ti915\n\
+                       drm-pdev:\t0000:00:02.0\ndrm-client-id:\t17\ndrm-engine-render:\t9288864723 ns\n\
+                       drm-engine-copy:\t0 ns\ndrm-engine-video:\t512 ns\ndrm-engine-capacity-video:\t2\n\
+                       drm-total-system0:\t4096 KiB\ndrm-resident-system0:\t2048 KiB\ndrm-total-stolen-system0:\t0\n";
+
+        assert_eq!(
+            DrmFdinfo::parse(&TokenParser::new(content)).unwrap(),
+            DrmFdinfo::new(
+                17,
+                "0000:00:02.0",
+                hashmap!(
+                    "render".to_string() => 9288864723,
+                    "copy".to_string() => 0,
+                    "video".to_string() => 512
+                ),
+                Some(4 * 1024 * 1024)
+            )
+        );
+    }
+
+    #[rstest]
+    #[case("drm-total-vram0:\t2 MiB\ndrm-total-gtt:\t512 KiB\n", Some(2 * 1024 * 1024 + 512 * 1024))]
+    #[case("drm-memory-vram:\t1024 KiB\ndrm-memory-gtt:\t12\n", Some(1024 * 1024 + 12))]
+    #[case("drm-total-vram0:\t1 KiB\ndrm-memory-vram:\t1024 KiB\n", Some(1024))]
+    #[case("drm-engine-gfx:\t1000 ns\n", None)]
+    fn test_should_sum_memory_of_regions(#[case] memory_lines: &str, #[case] expected: Option<u64>) {
+        let content = format!("drm-driver:\tamdgpu\ndrm-client-id:\t3\n{}", memory_lines);
+
+        assert_eq!(
+            DrmFdinfo::parse(&TokenParser::new(&content)).unwrap().memory(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_should_fail_to_parse_fdinfo_of_file_which_is_not_drm_client() {
+        let content = "pos:\t0\nflags:\t0100002\nmnt_id:\t25\nino:\t41\n";
+
+        assert!(DrmFdinfo::parse(&TokenParser::new(content)).is_err());
+    }
+
+    #[test]
+    fn test_should_fail_to_parse_memory_of_unknown_unit() {
+        let content = "drm-client-id:\t3\ndrm-total-vram0:\t2 GiB\n";
+
+        assert!(DrmFdinfo::parse(&TokenParser::new(content)).is_err());
+    }
 }
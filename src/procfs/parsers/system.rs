@@ -1,3 +1,6 @@
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::path::PathBuf;
 use std::time::Duration;
 #[cfg(not(test))]
@@ -6,10 +9,11 @@ use std::time::Instant;
 #[cfg(test)]
 use sn_fake_clock::FakeClock as Instant;
 
+use crate::core::connections::Connection;
 use crate::core::time::Timestamp;
 use crate::procfs::parsers::{Parse, SystemData, TokenParser};
-use crate::procfs::ProcfsError;
 use crate::procfs::ProcfsError::InvalidFileContent;
+use crate::procfs::{proc_root, ProcfsError};
 
 /// Represents data and additional computed data from `/proc/stat`
 #[derive(Eq, PartialEq, Debug)]
@@ -45,6 +49,11 @@ impl Stat {
     pub fn running_time(&self) -> u64 {
         self.user + self.nice + self.system + self.idle + self.guest + self.guest_nice
     }
+
+    /// Returns the time during which the CPU was not idle
+    pub fn busy_time(&self) -> u64 {
+        self.running_time() - self.idle
+    }
 }
 
 impl Parse for Stat {
@@ -54,15 +63,16 @@ impl Parse for Stat {
             nice: token_parser.token(0, 2)?,
             system: token_parser.token(0, 3)?,
             idle: token_parser.token(0, 4)?,
-            guest: token_parser.token(0, 9)?,
-            guest_nice: token_parser.token(0, 10)?,
+            // Kernels older than 2.6.24 do not report the guest time, nor older than 2.6.33 the guest nice time
+            guest: token_parser.optional_token(0, 9)?.unwrap_or(0),
+            guest_nice: token_parser.optional_token(0, 10)?.unwrap_or(0),
         })
     }
 }
 
 impl SystemData for Stat {
     fn filepath() -> PathBuf {
-        ["/proc", "stat"].iter().collect()
+        proc_root().join("stat")
     }
 }
 
@@ -95,6 +105,32 @@ cpu0 1393280 32966 572056 13343292 6130 0 17875 0 23933 0"
         );
     }
 
+    #[test]
+    fn test_missing_guest_times_should_be_zero() {
+        let token_parser = TokenParser::new("cpu 100 20 30 400 5 0 6 0\ncpu0 100 20 30 400 5 0 6 0");
+
+        let stat = Stat::parse(&token_parser).expect("Could not read Stat");
+
+        assert_eq!(stat, Stat::new(100, 20, 30, 400, 0, 0));
+        assert_eq!(stat.running_time(), 550);
+    }
+
+    #[test]
+    fn test_missing_guest_nice_time_should_be_zero() {
+        let token_parser = TokenParser::new("cpu 100 20 30 400 5 0 6 0 7");
+
+        let stat = Stat::parse(&token_parser).expect("Could not read Stat");
+
+        assert_eq!(stat, Stat::new(100, 20, 30, 400, 7, 0));
+    }
+
+    #[test]
+    fn test_should_fail_when_guest_time_is_invalid() {
+        let token_parser = TokenParser::new("cpu 100 20 30 400 5 0 6 0 guest 0");
+
+        assert!(Stat::parse(&token_parser).is_err());
+    }
+
     #[test]
     fn test_running_time() {
         let stat = Stat {
@@ -108,14 +144,21 @@ cpu0 1393280 32966 572056 13343292 6130 0 17875 0 23933 0"
 
         assert_eq!(63, stat.running_time())
     }
+
+    #[test]
+    fn test_busy_time_should_exclude_idle_time() {
+        let stat = Stat::new(1, 2, 4, 8, 16, 32);
+
+        assert_eq!(55, stat.busy_time())
+    }
 }
 
 /// Represents data from `/proc/uptime`
 #[derive(Eq, PartialEq, Debug, Copy, Clone)]
 pub struct Uptime {
-    /// Represents the amount of seconds elapsed since the system booted
+    /// Represents the time elapsed since the system booted, to the hundredth of a second
     // scanf format: unspecified
-    uptime: u64,
+    uptime: Duration,
     /// Represents the actual timestamp at which the system was booted
     boot_time: Timestamp,
 }
@@ -123,12 +166,14 @@ pub struct Uptime {
 impl Parse for Uptime {
     fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
         let uptime_repr: String = token_parser.token(0, 0)?;
-        let uptime: u64 = uptime_repr
+        let uptime = uptime_repr
             .parse::<f64>()
-            .map_err(|_| InvalidFileContent("Could not parse uptime".to_string()))? as u64;
+            .ok()
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+            .ok_or_else(|| InvalidFileContent("Could not parse uptime".to_string()))?;
 
         let boot_time = Instant::now()
-            .checked_sub(Duration::from_secs(uptime))
+            .checked_sub(Duration::from_secs(uptime.as_secs()))
             .ok_or_else(|| InvalidFileContent("Uptime is greater than current time".to_string()))?;
         let boot_time = Timestamp::from_instant(boot_time);
 
@@ -140,11 +185,42 @@ impl Uptime {
     pub fn boot_time(&self) -> Timestamp {
         self.boot_time
     }
+
+    /// Returns the time elapsed since the system booted, in clock ticks
+    ///
+    /// Keeping the fractions of seconds matters when comparing the uptime with the start time of processes, which are
+    /// expressed in clock ticks too: a process which started less than a second ago would otherwise appear to be born
+    /// now.
+    ///
+    /// # Arguments
+    ///  * `clock_ticks`: The amount of clock ticks per second
+    pub fn uptime_ticks(&self, clock_ticks: u64) -> u64 {
+        (self.uptime.as_secs_f64() * clock_ticks as f64) as u64
+    }
+}
+
+#[cfg(test)]
+impl Uptime {
+    pub fn new(uptime: u64) -> Self {
+        Self::from_duration(Duration::from_secs(uptime))
+    }
+
+    pub fn from_duration(uptime: Duration) -> Self {
+        Uptime {
+            uptime,
+            boot_time: Timestamp::now(),
+        }
+    }
+
+    /// Returns the time elapsed since the system booted
+    pub fn uptime(&self) -> Duration {
+        self.uptime
+    }
 }
 
 impl SystemData for Uptime {
     fn filepath() -> PathBuf {
-        ["/proc", "uptime"].iter().collect()
+        proc_root().join("uptime")
     }
 }
 
@@ -168,7 +244,17 @@ mod test_uptime {
 
         let uptime = Uptime::parse(&token_parser).expect("Could not read Uptime");
 
-        assert_eq!(uptime.uptime, 10281);
+        assert_eq!(uptime.uptime().as_millis(), 10281870);
+    }
+
+    #[test]
+    fn test_should_count_fractions_of_seconds_in_uptime_ticks() {
+        FakeClock::set_time(1000000000);
+        let token_parser = TokenParser::new("12.34 56.78");
+
+        let uptime = Uptime::parse(&token_parser).expect("Could not read Uptime");
+
+        assert_eq!(uptime.uptime_ticks(100), 1234);
     }
 
     #[test]
@@ -184,3 +270,957 @@ mod test_uptime {
         assert_eq!(uptime.boot_time(), expected_boot_time);
     }
 }
+
+/// Represents data from `/sys/class/powercap/intel-rapl:0/energy_uj`
+///
+/// This file exposes the energy consumed by the first CPU package, through the Running Average Power Limit (RAPL)
+/// interface of Intel and AMD processors.
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct RaplEnergy {
+    /// The energy counter of the package, in micro joules. It wraps around when reaching `RaplMaxEnergyRange`.
+    energy_uj: u64,
+}
+
+impl RaplEnergy {
+    pub fn energy_uj(&self) -> u64 {
+        self.energy_uj
+    }
+}
+
+#[cfg(test)]
+impl RaplEnergy {
+    pub fn new(energy_uj: u64) -> Self {
+        RaplEnergy { energy_uj }
+    }
+}
+
+impl Parse for RaplEnergy {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        Ok(RaplEnergy {
+            energy_uj: token_parser.token(0, 0)?,
+        })
+    }
+}
+
+impl SystemData for RaplEnergy {
+    fn filepath() -> PathBuf {
+        ["/sys", "class", "powercap", "intel-rapl:0", "energy_uj"]
+            .iter()
+            .collect()
+    }
+}
+
+/// Represents data from `/sys/class/powercap/intel-rapl:0/max_energy_range_uj`
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct RaplMaxEnergyRange {
+    /// The value at which the energy counter of the package wraps around, in micro joules
+    max_energy_range_uj: u64,
+}
+
+impl RaplMaxEnergyRange {
+    pub fn max_energy_range_uj(&self) -> u64 {
+        self.max_energy_range_uj
+    }
+}
+
+impl Parse for RaplMaxEnergyRange {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        Ok(RaplMaxEnergyRange {
+            max_energy_range_uj: token_parser.token(0, 0)?,
+        })
+    }
+}
+
+impl SystemData for RaplMaxEnergyRange {
+    fn filepath() -> PathBuf {
+        ["/sys", "class", "powercap", "intel-rapl:0", "max_energy_range_uj"]
+            .iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test_rapl {
+    use std::path::PathBuf;
+
+    use crate::procfs::parsers::system::{RaplEnergy, RaplMaxEnergyRange};
+    use crate::procfs::parsers::{Parse, SystemData, TokenParser};
+
+    #[test]
+    fn test_should_parse_energy() {
+        let token_parser = TokenParser::new("56341987521\n");
+
+        assert_eq!(RaplEnergy::parse(&token_parser).unwrap().energy_uj(), 56341987521);
+    }
+
+    #[test]
+    fn test_should_parse_max_energy_range() {
+        let token_parser = TokenParser::new("262143328850\n");
+
+        let max_range = RaplMaxEnergyRange::parse(&token_parser).unwrap();
+
+        assert_eq!(max_range.max_energy_range_uj(), 262143328850);
+    }
+
+    #[test]
+    fn test_should_read_energy_of_first_package() {
+        assert_eq!(
+            RaplEnergy::filepath(),
+            PathBuf::from("/sys/class/powercap/intel-rapl:0/energy_uj")
+        );
+    }
+}
+
+/// The states of a TCP socket tracked by spv, among those defined in the kernel's `include/net/tcp_states.h`
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub enum TcpState {
+    Established,
+    TimeWait,
+    CloseWait,
+    Other,
+}
+
+impl TcpState {
+    /// Returns the state represented by the given hexadecimal code, as found in `/proc/net/tcp`
+    fn from_code(code: &str) -> Self {
+        match code {
+            "01" => TcpState::Established,
+            "06" => TcpState::TimeWait,
+            "08" => TcpState::CloseWait,
+            _ => TcpState::Other,
+        }
+    }
+}
+
+/// The addresses between which a socket exchanges data, as listed in the socket tables of `/proc/net`
+#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+pub struct SocketEndpoints {
+    pub local: SocketAddr,
+    /// The address of the peer of the socket, which is unspecified (e.g. `0.0.0.0:0`) for the sockets listening for
+    /// connections or receiving datagrams from any peer
+    pub remote: SocketAddr,
+}
+
+/// Represents data from `/proc/net/tcp`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct NetTcp {
+    /// The states of the IPv4 TCP sockets, by inode
+    socket_states: HashMap<u64, TcpState>,
+    /// The endpoints of the IPv4 TCP sockets, by inode
+    socket_endpoints: HashMap<u64, SocketEndpoints>,
+}
+
+impl NetTcp {
+    #[cfg(test)]
+    pub fn new(established_inodes: HashSet<u64>) -> Self {
+        Self::with_socket_states(established_states(established_inodes))
+    }
+
+    #[cfg(test)]
+    pub fn with_socket_states(socket_states: HashMap<u64, TcpState>) -> Self {
+        let socket_endpoints = unbound_endpoints(socket_states.keys().copied());
+        NetTcp {
+            socket_states,
+            socket_endpoints,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_socket_endpoints(socket_endpoints: HashMap<u64, SocketEndpoints>) -> Self {
+        let socket_states = socket_endpoints.keys().map(|inode| (*inode, TcpState::Other)).collect();
+        NetTcp {
+            socket_states,
+            socket_endpoints,
+        }
+    }
+
+    /// Returns the inodes of the sockets which are in the ESTABLISHED state
+    pub fn established_inodes(&self) -> HashSet<u64> {
+        established_inodes(&self.socket_states)
+    }
+
+    pub fn socket_states(&self) -> &HashMap<u64, TcpState> {
+        &self.socket_states
+    }
+
+    pub fn socket_endpoints(&self) -> &HashMap<u64, SocketEndpoints> {
+        &self.socket_endpoints
+    }
+}
+
+impl Parse for NetTcp {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        Ok(NetTcp {
+            socket_states: parse_socket_states(token_parser)?,
+            socket_endpoints: parse_socket_endpoints(token_parser)?,
+        })
+    }
+}
+
+impl SystemData for NetTcp {
+    fn filepath() -> PathBuf {
+        proc_root().join("net").join("tcp")
+    }
+}
+
+/// Represents data from `/proc/net/tcp6`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct NetTcp6 {
+    /// The states of the IPv6 TCP sockets, by inode
+    socket_states: HashMap<u64, TcpState>,
+    /// The endpoints of the IPv6 TCP sockets, by inode
+    socket_endpoints: HashMap<u64, SocketEndpoints>,
+}
+
+impl NetTcp6 {
+    #[cfg(test)]
+    pub fn new(established_inodes: HashSet<u64>) -> Self {
+        Self::with_socket_states(established_states(established_inodes))
+    }
+
+    #[cfg(test)]
+    pub fn with_socket_states(socket_states: HashMap<u64, TcpState>) -> Self {
+        let socket_endpoints = unbound_endpoints(socket_states.keys().copied());
+        NetTcp6 {
+            socket_states,
+            socket_endpoints,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_socket_endpoints(socket_endpoints: HashMap<u64, SocketEndpoints>) -> Self {
+        let socket_states = socket_endpoints.keys().map(|inode| (*inode, TcpState::Other)).collect();
+        NetTcp6 {
+            socket_states,
+            socket_endpoints,
+        }
+    }
+
+    /// Returns the inodes of the sockets which are in the ESTABLISHED state
+    pub fn established_inodes(&self) -> HashSet<u64> {
+        established_inodes(&self.socket_states)
+    }
+
+    pub fn socket_states(&self) -> &HashMap<u64, TcpState> {
+        &self.socket_states
+    }
+
+    pub fn socket_endpoints(&self) -> &HashMap<u64, SocketEndpoints> {
+        &self.socket_endpoints
+    }
+}
+
+impl Parse for NetTcp6 {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        Ok(NetTcp6 {
+            socket_states: parse_socket_states(token_parser)?,
+            socket_endpoints: parse_socket_endpoints(token_parser)?,
+        })
+    }
+}
+
+impl SystemData for NetTcp6 {
+    fn filepath() -> PathBuf {
+        proc_root().join("net").join("tcp6")
+    }
+}
+
+/// Parses the states of the sockets from the content of `/proc/net/tcp` or `/proc/net/tcp6`, by inode
+///
+/// The first line is a header. Each following line describes a socket, with its state as 4th token and its inode as
+/// 10th token.
+fn parse_socket_states(token_parser: &TokenParser) -> Result<HashMap<u64, TcpState>, ProcfsError> {
+    let mut socket_states = HashMap::new();
+
+    for line_no in (1..token_parser.line_count()).filter(|l| !token_parser.is_line_empty(*l)) {
+        let state: String = token_parser.token(line_no, 3)?;
+        socket_states.insert(token_parser.token(line_no, 9)?, TcpState::from_code(&state));
+    }
+
+    Ok(socket_states)
+}
+
+/// Represents data from `/proc/net/udp`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct NetUdp {
+    /// The endpoints of the IPv4 UDP sockets, by inode
+    socket_endpoints: HashMap<u64, SocketEndpoints>,
+}
+
+impl NetUdp {
+    #[cfg(test)]
+    pub fn new(socket_inodes: HashSet<u64>) -> Self {
+        Self::with_socket_endpoints(unbound_endpoints(socket_inodes))
+    }
+
+    #[cfg(test)]
+    pub fn with_socket_endpoints(socket_endpoints: HashMap<u64, SocketEndpoints>) -> Self {
+        NetUdp { socket_endpoints }
+    }
+
+    pub fn socket_endpoints(&self) -> &HashMap<u64, SocketEndpoints> {
+        &self.socket_endpoints
+    }
+}
+
+impl Parse for NetUdp {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        Ok(NetUdp {
+            socket_endpoints: parse_socket_endpoints(token_parser)?,
+        })
+    }
+}
+
+impl SystemData for NetUdp {
+    fn filepath() -> PathBuf {
+        proc_root().join("net").join("udp")
+    }
+}
+
+/// Represents data from `/proc/net/udp6`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct NetUdp6 {
+    /// The endpoints of the IPv6 UDP sockets, by inode
+    socket_endpoints: HashMap<u64, SocketEndpoints>,
+}
+
+impl NetUdp6 {
+    #[cfg(test)]
+    pub fn new(socket_inodes: HashSet<u64>) -> Self {
+        Self::with_socket_endpoints(unbound_endpoints(socket_inodes))
+    }
+
+    #[cfg(test)]
+    pub fn with_socket_endpoints(socket_endpoints: HashMap<u64, SocketEndpoints>) -> Self {
+        NetUdp6 { socket_endpoints }
+    }
+
+    pub fn socket_endpoints(&self) -> &HashMap<u64, SocketEndpoints> {
+        &self.socket_endpoints
+    }
+}
+
+impl Parse for NetUdp6 {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        Ok(NetUdp6 {
+            socket_endpoints: parse_socket_endpoints(token_parser)?,
+        })
+    }
+}
+
+impl SystemData for NetUdp6 {
+    fn filepath() -> PathBuf {
+        proc_root().join("net").join("udp6")
+    }
+}
+
+/// Parses the endpoints of the sockets from the content of a socket table of `/proc/net`, by inode
+///
+/// The TCP and UDP tables share the same format: the first line is a header, and each following line describes a
+/// socket, with its local and remote addresses as 2nd and 3rd tokens and its inode as 10th token.
+fn parse_socket_endpoints(token_parser: &TokenParser) -> Result<HashMap<u64, SocketEndpoints>, ProcfsError> {
+    let mut socket_endpoints = HashMap::new();
+
+    for line_no in (1..token_parser.line_count()).filter(|l| !token_parser.is_line_empty(*l)) {
+        let local: String = token_parser.token(line_no, 1)?;
+        let remote: String = token_parser.token(line_no, 2)?;
+        let endpoints = SocketEndpoints {
+            local: decode_socket_address(&local)?,
+            remote: decode_socket_address(&remote)?,
+        };
+        socket_endpoints.insert(token_parser.token(line_no, 9)?, endpoints);
+    }
+
+    Ok(socket_endpoints)
+}
+
+#[cfg(test)]
+fn unbound_endpoints(inodes: impl IntoIterator<Item = u64>) -> HashMap<u64, SocketEndpoints> {
+    let unspecified = SocketAddr::from(([0, 0, 0, 0], 0));
+
+    inodes
+        .into_iter()
+        .map(|inode| {
+            let endpoints = SocketEndpoints {
+                local: unspecified,
+                remote: unspecified,
+            };
+            (inode, endpoints)
+        })
+        .collect()
+}
+
+/// Represents the connections listed in `/proc/net/tcp`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct TcpConnections {
+    /// The IPv4 TCP connections, by the inode of their socket
+    connections: HashMap<u64, Connection>,
+}
+
+impl TcpConnections {
+    pub fn connections(&self) -> &HashMap<u64, Connection> {
+        &self.connections
+    }
+}
+
+impl Parse for TcpConnections {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        Ok(TcpConnections {
+            connections: parse_connections(token_parser)?,
+        })
+    }
+}
+
+impl SystemData for TcpConnections {
+    fn filepath() -> PathBuf {
+        proc_root().join("net").join("tcp")
+    }
+}
+
+/// Represents the connections listed in `/proc/net/tcp6`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Tcp6Connections {
+    /// The IPv6 TCP connections, by the inode of their socket
+    connections: HashMap<u64, Connection>,
+}
+
+impl Tcp6Connections {
+    pub fn connections(&self) -> &HashMap<u64, Connection> {
+        &self.connections
+    }
+}
+
+impl Parse for Tcp6Connections {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        Ok(Tcp6Connections {
+            connections: parse_connections(token_parser)?,
+        })
+    }
+}
+
+impl SystemData for Tcp6Connections {
+    fn filepath() -> PathBuf {
+        proc_root().join("net").join("tcp6")
+    }
+}
+
+/// Parses the connections from the content of `/proc/net/tcp` or `/proc/net/tcp6`, by inode
+///
+/// Each line following the header describes a socket, with its local and remote addresses as 2nd and 3rd tokens, its
+/// state as 4th token and its inode as 10th token.
+fn parse_connections(token_parser: &TokenParser) -> Result<HashMap<u64, Connection>, ProcfsError> {
+    let mut connections = HashMap::new();
+
+    for line_no in (1..token_parser.line_count()).filter(|l| !token_parser.is_line_empty(*l)) {
+        let local: String = token_parser.token(line_no, 1)?;
+        let remote: String = token_parser.token(line_no, 2)?;
+        let state: String = token_parser.token(line_no, 3)?;
+
+        let connection = Connection::new(
+            decode_socket_address(&local)?,
+            decode_socket_address(&remote)?,
+            tcp_state_name(&state),
+        );
+        connections.insert(token_parser.token(line_no, 9)?, connection);
+    }
+
+    Ok(connections)
+}
+
+/// Decodes an address and a port as encoded in `/proc/net/tcp` (e.g. `0100007F:0277` for `127.0.0.1:631`)
+///
+/// The address is made of the 32-bit words of the IP address, each printed in hexadecimal in the byte order of the
+/// host, while the port is printed in hexadecimal as a number.
+fn decode_socket_address(encoded: &str) -> Result<SocketAddr, ProcfsError> {
+    let invalid_address = || InvalidFileContent(format!("Invalid socket address '{}'", encoded));
+
+    let (address, port) = encoded.split_once(':').ok_or_else(invalid_address)?;
+    let port = u16::from_str_radix(port, 16).map_err(|_| invalid_address())?;
+
+    let words = (0..address.len() / 8)
+        .map(|i| {
+            address
+                .get(i * 8..(i + 1) * 8)
+                .and_then(|word| u32::from_str_radix(word, 16).ok())
+        })
+        .collect::<Option<Vec<u32>>>()
+        .ok_or_else(invalid_address)?;
+    let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_ne_bytes()).collect();
+
+    let ip = match (address.len(), bytes.len()) {
+        (8, 4) => IpAddr::V4(Ipv4Addr::from(
+            <[u8; 4]>::try_from(bytes).map_err(|_| invalid_address())?,
+        )),
+        (32, 16) => IpAddr::V6(Ipv6Addr::from(
+            <[u8; 16]>::try_from(bytes).map_err(|_| invalid_address())?,
+        )),
+        _ => return Err(invalid_address()),
+    };
+
+    Ok(SocketAddr::new(ip, port))
+}
+
+/// Returns the name of the TCP state represented by the given hexadecimal code, as defined in the kernel's
+/// `include/net/tcp_states.h`
+fn tcp_state_name(code: &str) -> &'static str {
+    match code {
+        "01" => "ESTABLISHED",
+        "02" => "SYN_SENT",
+        "03" => "SYN_RECV",
+        "04" => "FIN_WAIT1",
+        "05" => "FIN_WAIT2",
+        "06" => "TIME_WAIT",
+        "07" => "CLOSE",
+        "08" => "CLOSE_WAIT",
+        "09" => "LAST_ACK",
+        "0A" => "LISTEN",
+        "0B" => "CLOSING",
+        "0C" => "NEW_SYN_RECV",
+        _ => "UNKNOWN",
+    }
+}
+
+fn established_inodes(socket_states: &HashMap<u64, TcpState>) -> HashSet<u64> {
+    socket_states
+        .iter()
+        .filter(|(_, state)| **state == TcpState::Established)
+        .map(|(inode, _)| *inode)
+        .collect()
+}
+
+#[cfg(test)]
+fn established_states(established_inodes: HashSet<u64>) -> HashMap<u64, TcpState> {
+    established_inodes
+        .into_iter()
+        .map(|inode| (inode, TcpState::Established))
+        .collect()
+}
+
+#[cfg(test)]
+mod test_net_tcp {
+    use std::collections::HashSet;
+
+    use rstest::*;
+
+    use crate::procfs::parsers::system::{NetTcp, NetTcp6, SocketEndpoints, TcpState};
+    use crate::procfs::parsers::{Parse, TokenParser};
+
+    fn endpoints(local: &str, remote: &str) -> SocketEndpoints {
+        SocketEndpoints {
+            local: local.parse().unwrap(),
+            remote: remote.parse().unwrap(),
+        }
+    }
+
+    const NET_TCP_CONTENT: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21432 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:A2B4 8EFA1AAC:01BB 01 0000002A:00000000 02:00000A1B 00000000  1000        0 98765 2 0000000000000000 20 4 30 10 -1
+   2: 0F02000A:D0F2 5DB8D822:01BB 01 00000000:00000200 02:000001F4 00000000  1000        0 98766 2 0000000000000000 20 4 30 10 -1
+   3: 0F02000A:C5E8 5DB8D822:01BB 06 00000000:00000000 03:00000C35 00000000     0        0 0 3 0000000000000000
+";
+
+    #[test]
+    fn test_should_only_parse_inodes_of_established_sockets() {
+        let net_tcp = NetTcp::parse(&TokenParser::new(NET_TCP_CONTENT)).unwrap();
+
+        assert_eq!(net_tcp.established_inodes(), HashSet::from([98765, 98766]));
+    }
+
+    #[test]
+    fn test_should_parse_states_of_all_sockets() {
+        let net_tcp = NetTcp::parse(&TokenParser::new(NET_TCP_CONTENT)).unwrap();
+
+        assert_eq!(
+            net_tcp.socket_states(),
+            &hashmap!(
+                21432 => TcpState::Other,
+                98765 => TcpState::Established,
+                98766 => TcpState::Established,
+                0 => TcpState::TimeWait
+            )
+        );
+    }
+
+    #[rstest]
+    #[case("01", TcpState::Established)]
+    #[case("06", TcpState::TimeWait)]
+    #[case("08", TcpState::CloseWait)]
+    #[case("0A", TcpState::Other)]
+    fn test_should_decode_tcp_state(#[case] code: &str, #[case] expected: TcpState) {
+        assert_eq!(TcpState::from_code(code), expected);
+    }
+
+    #[test]
+    fn test_should_parse_endpoints_of_all_sockets() {
+        let net_tcp = NetTcp::parse(&TokenParser::new(NET_TCP_CONTENT)).unwrap();
+
+        assert_eq!(
+            net_tcp.socket_endpoints(),
+            &hashmap!(
+                21432 => endpoints("127.0.0.1:631", "0.0.0.0:0"),
+                98765 => endpoints("10.0.2.15:41652", "172.26.250.142:443"),
+                98766 => endpoints("10.0.2.15:53490", "34.216.184.93:443"),
+                0 => endpoints("10.0.2.15:50664", "34.216.184.93:443")
+            )
+        );
+    }
+
+    #[test]
+    fn test_should_parse_ipv6_sockets_with_the_same_format() {
+        let net_tcp6 = NetTcp6::parse(&TokenParser::new(NET_TCP_CONTENT)).unwrap();
+
+        assert_eq!(net_tcp6.established_inodes(), HashSet::from([98765, 98766]));
+    }
+
+    #[test]
+    fn test_should_parse_table_without_socket() {
+        let header_only = NET_TCP_CONTENT.lines().next().unwrap();
+
+        let net_tcp = NetTcp::parse(&TokenParser::new(header_only)).unwrap();
+
+        assert!(net_tcp.established_inodes().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_tcp_connections {
+    use rstest::*;
+
+    use crate::core::connections::Connection;
+    use crate::procfs::parsers::system::{decode_socket_address, Tcp6Connections, TcpConnections};
+    use crate::procfs::parsers::{Parse, TokenParser};
+
+    #[rstest]
+    #[case("0100007F:0277", "127.0.0.1:631")]
+    #[case("0F02000A:A2B4", "10.0.2.15:41652")]
+    #[case("8EFA1AAC:01BB", "172.26.250.142:443")]
+    #[case("00000000:0000", "0.0.0.0:0")]
+    #[case("00000000000000000000000001000000:0277", "[::1]:631")]
+    #[case("0000000000000000FFFF00000F02000A:0016", "[::ffff:10.0.2.15]:22")]
+    #[case("B80D0120000000000000000001000000:1F90", "[2001:db8::1]:8080")]
+    fn test_should_decode_address_and_port(#[case] encoded: &str, #[case] expected: &str) {
+        assert_eq!(decode_socket_address(encoded).unwrap(), expected.parse().unwrap());
+    }
+
+    #[rstest]
+    #[case("0100007F")]
+    #[case("0100007F:XYZ")]
+    #[case("0100:0277")]
+    #[case("0100007G:0277")]
+    fn test_should_fail_to_decode_invalid_address(#[case] encoded: &str) {
+        assert!(decode_socket_address(encoded).is_err());
+    }
+
+    #[test]
+    fn test_should_parse_connections_by_inode() {
+        let content = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21432 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:A2B4 8EFA1AAC:01BB 01 00000000:00000000 02:00000A1B 00000000  1000        0 98765 2 0000000000000000 20 4 30 10 -1
+";
+
+        let tcp = TcpConnections::parse(&TokenParser::new(content)).unwrap();
+
+        assert_eq!(
+            tcp.connections(),
+            &hashmap!(
+                21432 => Connection::new("127.0.0.1:631".parse().unwrap(), "0.0.0.0:0".parse().unwrap(), "LISTEN"),
+                98765 => Connection::new(
+                    "10.0.2.15:41652".parse().unwrap(),
+                    "172.26.250.142:443".parse().unwrap(),
+                    "ESTABLISHED"
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_should_parse_ipv6_connections() {
+        let content = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000001000000:0277 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21433 1 0000000000000000 100 0 0 10 0
+";
+
+        let tcp6 = Tcp6Connections::parse(&TokenParser::new(content)).unwrap();
+
+        assert_eq!(
+            tcp6.connections(),
+            &hashmap!(21433 => Connection::new("[::1]:631".parse().unwrap(), "[::]:0".parse().unwrap(), "LISTEN"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_net_udp {
+    use std::collections::HashMap;
+
+    use crate::procfs::parsers::system::{NetUdp, NetUdp6, SocketEndpoints};
+    use crate::procfs::parsers::{Parse, TokenParser};
+
+    fn expected_endpoints() -> HashMap<u64, SocketEndpoints> {
+        hashmap!(
+            18236 => SocketEndpoints {
+                local: "127.0.0.53:53".parse().unwrap(),
+                remote: "0.0.0.0:0".parse().unwrap(),
+            },
+            24977 => SocketEndpoints {
+                local: "10.0.2.15:68".parse().unwrap(),
+                remote: "10.0.2.2:67".parse().unwrap(),
+            }
+        )
+    }
+
+    const NET_UDP_CONTENT: &str = "   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  221: 3500007F:0035 00000000:0000 07 00000000:00000000 00:00000000 00000000   101        0 18236 2 0000000000000000 0
+  596: 0F02000A:0044 0202000A:0043 01 00000000:00000300 00:00000000 00000000     0        0 24977 2 0000000000000000 0
+";
+
+    #[test]
+    fn test_should_parse_endpoints_of_all_sockets() {
+        let net_udp = NetUdp::parse(&TokenParser::new(NET_UDP_CONTENT)).unwrap();
+
+        assert_eq!(net_udp.socket_endpoints(), &expected_endpoints());
+    }
+
+    #[test]
+    fn test_should_parse_ipv6_sockets_with_the_same_format() {
+        let net_udp6 = NetUdp6::parse(&TokenParser::new(NET_UDP_CONTENT)).unwrap();
+
+        assert_eq!(net_udp6.socket_endpoints(), &expected_endpoints());
+    }
+}
+
+/// A resource whose pressure stall information is exposed in `/proc/pressure`
+pub trait PressureResource {
+    /// The name of the file describing the pressure of the resource, in `/proc/pressure`
+    fn file_name() -> &'static str;
+}
+
+#[derive(PartialEq, Debug)]
+pub struct CpuResource;
+
+impl PressureResource for CpuResource {
+    fn file_name() -> &'static str {
+        "cpu"
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct IoResource;
+
+impl PressureResource for IoResource {
+    fn file_name() -> &'static str {
+        "io"
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct MemoryResource;
+
+impl PressureResource for MemoryResource {
+    fn file_name() -> &'static str {
+        "memory"
+    }
+}
+
+/// Represents data from `/proc/pressure/[resource]`
+#[derive(PartialEq, Debug)]
+pub struct Pressure<R: PressureResource> {
+    /// The share of the last 10 seconds during which at least one task was stalled on the resource, in percent
+    some_avg10: f64,
+    resource: PhantomData<R>,
+}
+
+impl<R: PressureResource> Pressure<R> {
+    pub fn new(some_avg10: f64) -> Self {
+        Pressure {
+            some_avg10,
+            resource: PhantomData,
+        }
+    }
+
+    pub fn some_avg10(&self) -> f64 {
+        self.some_avg10
+    }
+}
+
+impl<R: PressureResource> Parse for Pressure<R> {
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        Ok(Pressure::new(token_parser.assigned_token("some", "avg10")?))
+    }
+}
+
+impl<R: PressureResource> SystemData for Pressure<R> {
+    fn filepath() -> PathBuf {
+        proc_root().join("pressure").join(R::file_name())
+    }
+}
+
+#[cfg(test)]
+mod test_pressure {
+    use crate::procfs::parsers::system::{CpuResource, IoResource, Pressure};
+    use crate::procfs::parsers::{Parse, TokenParser};
+
+    #[test]
+    fn test_parse_pressure_file() {
+        let content = "some avg10=12.50 avg60=4.20 avg300=1.05 total=123456
+full avg10=3.00 avg60=1.10 avg300=0.25 total=45678
+";
+
+        let pressure = Pressure::<IoResource>::parse(&TokenParser::new(content)).expect("Could not read Pressure");
+
+        assert_eq!(pressure.some_avg10(), 12.5);
+    }
+
+    #[test]
+    fn test_parse_pressure_file_without_full_line() {
+        // Older kernels do not report the full pressure of the CPU
+        let content = "some avg10=0.30 avg60=0.12 avg300=0.03 total=9876\n";
+
+        let pressure = Pressure::<CpuResource>::parse(&TokenParser::new(content)).expect("Could not read Pressure");
+
+        assert_eq!(pressure.some_avg10(), 0.3);
+    }
+
+    #[test]
+    fn test_fail_to_parse_pressure_file_without_some_line() {
+        let content = "full avg10=3.00 avg60=1.10 avg300=0.25 total=45678\n";
+
+        assert!(Pressure::<CpuResource>::parse(&TokenParser::new(content)).is_err());
+    }
+}
+
+/// A filesystem mounted on the system, as listed in `/proc/mounts`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct MountEntry {
+    mount_point: String,
+    fs_type: String,
+}
+
+impl MountEntry {
+    pub fn new(mount_point: impl Into<String>, fs_type: impl Into<String>) -> Self {
+        Self {
+            mount_point: mount_point.into(),
+            fs_type: fs_type.into(),
+        }
+    }
+
+    pub fn mount_point(&self) -> &str {
+        &self.mount_point
+    }
+
+    pub fn fs_type(&self) -> &str {
+        &self.fs_type
+    }
+}
+
+/// Represents data from `/proc/mounts`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Mounts {
+    /// The mounted filesystems, in the order in which they were mounted
+    entries: Vec<MountEntry>,
+}
+
+impl Mounts {
+    #[cfg(test)]
+    pub fn new(entries: Vec<MountEntry>) -> Self {
+        Mounts { entries }
+    }
+
+    pub fn entries(&self) -> &[MountEntry] {
+        &self.entries
+    }
+}
+
+impl Parse for Mounts {
+    /// Each line describes a mounted filesystem, with its mount point as 2nd token and its type as 3rd token
+    fn parse(token_parser: &TokenParser) -> Result<Self, ProcfsError> {
+        let entries = (0..token_parser.line_count())
+            .filter(|line_no| !token_parser.is_line_empty(*line_no))
+            .map(|line_no| {
+                let mount_point: String = token_parser.token(line_no, 1)?;
+                let fs_type: String = token_parser.token(line_no, 2)?;
+
+                Ok(MountEntry::new(unescape_mount_point(&mount_point), fs_type))
+            })
+            .collect::<Result<_, ProcfsError>>()?;
+
+        Ok(Mounts { entries })
+    }
+}
+
+impl SystemData for Mounts {
+    fn filepath() -> PathBuf {
+        proc_root().join("mounts")
+    }
+}
+
+/// Restores the spaces, tabs, newlines and backslashes of a mount point, which the kernel escapes as octal codes (e.g.
+/// `\040` for a space)
+fn unescape_mount_point(escaped: &str) -> String {
+    let mut unescaped = String::with_capacity(escaped.len());
+    let mut remaining = escaped;
+
+    while let Some(index) = remaining.find('\\') {
+        unescaped.push_str(&remaining[..index]);
+        let code = remaining.get(index + 1..index + 4);
+
+        match code.and_then(|code| u8::from_str_radix(code, 8).ok()) {
+            Some(byte) => {
+                unescaped.push(byte as char);
+                remaining = &remaining[index + 4..];
+            }
+            None => {
+                unescaped.push('\\');
+                remaining = &remaining[index + 1..];
+            }
+        }
+    }
+    unescaped.push_str(remaining);
+
+    unescaped
+}
+
+#[cfg(test)]
+mod test_mounts {
+    use rstest::*;
+
+    use crate::procfs::parsers::system::{unescape_mount_point, MountEntry, Mounts};
+    use crate::procfs::parsers::{Parse, TokenParser};
+
+    #[test]
+    fn test_parse_mounts_file() {
+        let content = "proc /proc proc rw,nosuid,nodev,noexec,relatime 0 0
+/dev/nvme0n1p2 / ext4 rw,relatime 0 0
+tmpfs /run tmpfs rw,nosuid,nodev,size=3256804k,mode=755 0 0
+/dev/sda1 /mnt/backup\\040disk vfat rw,relatime 0 0
+";
+
+        let mounts = Mounts::parse(&TokenParser::new(content)).expect("Could not read Mounts");
+
+        assert_eq!(
+            mounts.entries(),
+            &[
+                MountEntry::new("/proc", "proc"),
+                MountEntry::new("/", "ext4"),
+                MountEntry::new("/run", "tmpfs"),
+                MountEntry::new("/mnt/backup disk", "vfat"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fail_to_parse_truncated_mounts_line() {
+        let content = "/dev/nvme0n1p2 /\n";
+
+        assert!(Mounts::parse(&TokenParser::new(content)).is_err());
+    }
+
+    #[rstest]
+    #[case("/mnt/usb", "/mnt/usb")]
+    #[case("/mnt/my\\040disk", "/mnt/my disk")]
+    #[case("/mnt/tab\\011ulated", "/mnt/tab\tulated")]
+    #[case("/mnt/back\\134slash", "/mnt/back\\slash")]
+    #[case("/mnt/trailing\\", "/mnt/trailing\\")]
+    fn test_should_unescape_mount_point(#[case] escaped: &str, #[case] expected: &str) {
+        assert_eq!(unescape_mount_point(escaped), expected);
+    }
+}
@@ -0,0 +1,305 @@
+//! Power consumption estimation
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::core::metrics::WattMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::time::Timestamp;
+use crate::core::Error;
+use crate::procfs::parsers::process::PidStat;
+use crate::procfs::parsers::system::{RaplEnergy, RaplMaxEnergyRange, Stat};
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData, ReadSystemData, SystemDataReader};
+
+/// Probe implementation to estimate the power consumption (in watts) of processes
+///
+/// The power consumed by the CPU package is measured through RAPL, then apportioned to processes proportionally to
+/// the CPU time they consumed. This is only an estimate, as it ignores the power consumed by other components and
+/// the different power profiles of the workloads.
+pub struct PowerProbe {
+    stat_reader: Box<dyn ReadSystemData<Stat>>,
+    energy_reader: Box<dyn ReadSystemData<RaplEnergy>>,
+    pid_stat_reader: Box<dyn ReadProcessData<PidStat>>,
+    power_calculator: PowerCalculator,
+    processes_prev_runtime: HashMap<Pid, i64>,
+    prev_busy_time: u64,
+    busy_time_diff: u64,
+}
+
+impl PowerProbe {
+    /// Creates a new probe that can estimate the power consumption of processes
+    ///
+    /// Fails if the RAPL interface is not available on this system.
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many files descriptor the probe should keep open at most
+    pub fn new(fd_limit: usize) -> Result<Self, Error> {
+        let stat_reader = SystemDataReader::new()
+            .map_err(|e| Error::ProbingError("Could not access /proc directory".to_string(), e.into()))?;
+        let energy_reader = SystemDataReader::new()
+            .map_err(|e| Error::ProbingError("Could not access RAPL energy counter".to_string(), e.into()))?;
+        let max_energy_range = SystemDataReader::<RaplMaxEnergyRange>::new()
+            .and_then(|mut reader| reader.read())
+            .map_err(|e| Error::ProbingError("Could not read RAPL energy range".to_string(), e.into()))?;
+
+        Ok(Self::from_readers(
+            Box::new(stat_reader),
+            Box::new(energy_reader),
+            Box::new(ProcessDataReader::with_capacity(fd_limit)),
+            max_energy_range.max_energy_range_uj(),
+        ))
+    }
+
+    fn from_readers(
+        stat_reader: Box<dyn ReadSystemData<Stat>>,
+        energy_reader: Box<dyn ReadSystemData<RaplEnergy>>,
+        pid_stat_reader: Box<dyn ReadProcessData<PidStat>>,
+        max_energy_range_uj: u64,
+    ) -> Self {
+        PowerProbe {
+            stat_reader,
+            energy_reader,
+            pid_stat_reader,
+            power_calculator: PowerCalculator::new(max_energy_range_uj),
+            processes_prev_runtime: HashMap::new(),
+            prev_busy_time: 0,
+            busy_time_diff: 0,
+        }
+    }
+}
+
+impl Probe<WattMetric> for PowerProbe {
+    fn name(&self) -> &'static str {
+        "Power (estimate)"
+    }
+
+    fn init_iteration(&mut self) -> Result<(), Error> {
+        let stat = self
+            .stat_reader
+            .read()
+            .map_err(|e| Error::ProbingError("Could not read system CPU stats".to_string(), e.into()))?;
+        self.busy_time_diff = stat.busy_time().saturating_sub(self.prev_busy_time);
+        self.prev_busy_time = stat.busy_time();
+
+        let energy = self
+            .energy_reader
+            .read()
+            .map_err(|e| Error::ProbingError("Could not read RAPL energy counter".to_string(), e.into()))?;
+        self.power_calculator.push(energy.energy_uj(), Timestamp::now());
+
+        Ok(())
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<WattMetric, Error> {
+        let pid_stat = self
+            .pid_stat_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read process CPU stats for PID {}", pid), e.into()))?;
+
+        let prev_runtime = self
+            .processes_prev_runtime
+            .insert(pid, pid_stat.running_time())
+            .unwrap_or(0);
+        let runtime_diff = (pid_stat.running_time() - prev_runtime).max(0) as u64;
+
+        let watts = apportion(self.power_calculator.watts(), runtime_diff, self.busy_time_diff);
+        Ok(WattMetric::new(watts))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().for_each(|pid| {
+            self.pid_stat_reader.cleanup(*pid);
+            self.processes_prev_runtime.remove(pid);
+        });
+    }
+}
+
+/// Calculates the power consumed by the CPU package from successive readings of its energy counter
+struct PowerCalculator {
+    max_energy_range_uj: u64,
+    last_reading: Option<(u64, Timestamp)>,
+    watts: f64,
+}
+
+impl PowerCalculator {
+    fn new(max_energy_range_uj: u64) -> Self {
+        Self {
+            max_energy_range_uj,
+            last_reading: None,
+            watts: 0.,
+        }
+    }
+
+    /// Registers a new reading of the energy counter, and updates the power consumed since the last reading
+    fn push(&mut self, energy_uj: u64, timestamp: Timestamp) {
+        if let Some((last_energy_uj, last_timestamp)) = self.last_reading {
+            let elapsed = timestamp.duration_since(&last_timestamp);
+            self.watts = energy_delta_to_watts(last_energy_uj, energy_uj, self.max_energy_range_uj, elapsed);
+        }
+
+        self.last_reading = Some((energy_uj, timestamp));
+    }
+
+    /// Returns the power consumed between the two last readings
+    fn watts(&self) -> f64 {
+        self.watts
+    }
+}
+
+/// Converts the energy consumed between two readings of a RAPL energy counter into watts
+///
+/// # Arguments
+///  * `prev_uj`, `cur_uj`: The previous and current values of the counter, in micro joules
+///  * `max_range_uj`: The value at which the counter wraps around
+///  * `elapsed`: The time elapsed between the two readings
+fn energy_delta_to_watts(prev_uj: u64, cur_uj: u64, max_range_uj: u64, elapsed: Duration) -> f64 {
+    if elapsed.is_zero() {
+        return 0.;
+    }
+
+    let delta_uj = match cur_uj >= prev_uj {
+        true => cur_uj - prev_uj,
+        false => max_range_uj.saturating_sub(prev_uj) + cur_uj, // The counter wrapped around
+    };
+
+    delta_uj as f64 / 1_000_000. / elapsed.as_secs_f64()
+}
+
+/// Calculates the portion of the package power consumed by a process, proportionally to its CPU time
+///
+/// # Arguments
+///  * `package_watts`: The power consumed by the whole CPU package
+///  * `process_ticks`: The CPU time consumed by the process during the iteration
+///  * `busy_ticks`: The CPU time consumed by all processes during the iteration
+fn apportion(package_watts: f64, process_ticks: u64, busy_ticks: u64) -> f64 {
+    if busy_ticks == 0 {
+        return 0.;
+    }
+
+    package_watts * (process_ticks.min(busy_ticks) as f64 / busy_ticks as f64)
+}
+
+#[cfg(test)]
+mod test_energy_conversion {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::core::time::test_utils::advance_time_and_refresh_timestamp;
+    use crate::core::time::Timestamp;
+    use crate::procfs::power_probe::{energy_delta_to_watts, PowerCalculator};
+
+    #[rstest]
+    #[case(0, 10_000_000, Duration::from_secs(1), 10.)]
+    #[case(5_000_000, 10_000_000, Duration::from_secs(2), 2.5)]
+    #[case(1_000_000, 1_500_000, Duration::from_millis(500), 1.)]
+    fn test_should_convert_energy_delta_to_watts(
+        #[case] prev_uj: u64,
+        #[case] cur_uj: u64,
+        #[case] elapsed: Duration,
+        #[case] expected: f64,
+    ) {
+        assert_eq!(energy_delta_to_watts(prev_uj, cur_uj, u64::MAX, elapsed), expected);
+    }
+
+    #[test]
+    fn test_should_handle_counter_wrapping_around() {
+        let watts = energy_delta_to_watts(95_000_000, 5_000_000, 100_000_000, Duration::from_secs(1));
+
+        assert_eq!(watts, 10.);
+    }
+
+    #[test]
+    fn test_should_return_zero_watts_when_no_time_elapsed() {
+        assert_eq!(energy_delta_to_watts(0, 10, u64::MAX, Duration::ZERO), 0.);
+    }
+
+    #[test]
+    fn test_calculator_should_have_no_power_before_second_reading() {
+        let mut calculator = PowerCalculator::new(u64::MAX);
+
+        calculator.push(10_000_000, Timestamp::now());
+
+        assert_eq!(calculator.watts(), 0.);
+    }
+
+    #[test]
+    fn test_calculator_should_compute_power_between_last_readings() {
+        let mut calculator = PowerCalculator::new(u64::MAX);
+
+        calculator.push(10_000_000, Timestamp::now());
+        advance_time_and_refresh_timestamp(Duration::from_secs(2));
+        calculator.push(50_000_000, Timestamp::now());
+
+        assert_eq!(calculator.watts(), 20.);
+    }
+}
+
+#[cfg(test)]
+mod test_apportionment {
+    use rstest::*;
+
+    use crate::procfs::power_probe::apportion;
+
+    #[rstest]
+    #[case(40., 50, 100, 20.)]
+    #[case(40., 100, 100, 40.)]
+    #[case(40., 0, 100, 0.)]
+    #[case(30., 25, 75, 10.)]
+    fn test_should_apportion_power_proportionally_to_cpu_time(
+        #[case] package_watts: f64,
+        #[case] process_ticks: u64,
+        #[case] busy_ticks: u64,
+        #[case] expected: f64,
+    ) {
+        assert_eq!(apportion(package_watts, process_ticks, busy_ticks), expected);
+    }
+
+    #[test]
+    fn test_should_apportion_nothing_when_cpu_was_idle() {
+        assert_eq!(apportion(40., 0, 0), 0.);
+    }
+
+    #[test]
+    fn test_should_not_apportion_more_than_package_power() {
+        assert_eq!(apportion(40., 150, 100), 40.);
+    }
+}
+
+#[cfg(test)]
+mod test_power_probe {
+    use std::time::Duration;
+
+    use crate::core::metrics::WattMetric;
+    use crate::core::probe::Probe;
+    use crate::core::time::test_utils::advance_time_and_refresh_timestamp;
+    use crate::procfs::parsers::fakes::{FakeProcessDataReader, FakeSystemDataReader};
+    use crate::procfs::parsers::process::PidStat;
+    use crate::procfs::parsers::system::{RaplEnergy, Stat};
+    use crate::procfs::power_probe::PowerProbe;
+
+    #[test]
+    fn test_should_apportion_package_power_between_processes() {
+        // Between both iterations, the CPU was busy for 100 ticks and the package consumed 30W
+        let stat_reader =
+            FakeSystemDataReader::from_sequence(vec![Stat::new(0, 0, 0, 0, 0, 0), Stat::new(100, 0, 0, 50, 0, 0)]);
+        let energy_reader = FakeSystemDataReader::from_sequence(vec![RaplEnergy::new(0), RaplEnergy::new(30_000_000)]);
+        let mut pid_stat_reader = FakeProcessDataReader::new();
+        pid_stat_reader.set_pid_sequence(1, vec![PidStat::new(0, 0, 0, 0, 0), PidStat::new(60, 0, 0, 0, 0)]);
+        pid_stat_reader.set_pid_sequence(2, vec![PidStat::new(0, 0, 0, 0, 0), PidStat::new(30, 10, 0, 0, 0)]);
+
+        let mut probe = PowerProbe::from_readers(
+            Box::new(stat_reader),
+            Box::new(energy_reader),
+            Box::new(pid_stat_reader),
+            u64::MAX,
+        );
+
+        probe.probe_processes(&[1, 2]).unwrap(); // calibrating probe
+        advance_time_and_refresh_timestamp(Duration::from_secs(1));
+        let metrics = probe.probe_processes(&[1, 2]).unwrap();
+
+        assert_eq!(metrics, hashmap!(1 => WattMetric::new(18.), 2 => WattMetric::new(12.)));
+    }
+}
@@ -0,0 +1,112 @@
+//! Pressure stall information probing
+
+use crate::core::metrics::PercentMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::parsers::system::{CpuResource, IoResource, MemoryResource, Pressure, PressureResource};
+use crate::procfs::parsers::{ReadSystemData, SystemDataReader};
+
+/// Probe implementation to measure how much the tasks of the system stall on a resource (CPU, I/O or memory)
+///
+/// The pressure is the share of the last 10 seconds during which at least one task was stalled on the resource.
+/// It is a system-wide metric: the same pressure is reported for all processes, so that it can be charted whichever
+/// process is selected.
+pub struct PressureProbe<R: PressureResource> {
+    pressure_reader: Box<dyn ReadSystemData<Pressure<R>>>,
+    name: &'static str,
+    some_avg10: f64,
+}
+
+impl<R: PressureResource + 'static> PressureProbe<R> {
+    fn open(name: &'static str) -> Result<Self, Error> {
+        // Pressure stall information is only available since Linux 4.20, and can be disabled at boot time
+        let pressure_reader = SystemDataReader::new()
+            .map_err(|e| Error::ProbingError("Could not access pressure stall information".to_string(), e.into()))?;
+
+        Ok(Self::from_reader(Box::new(pressure_reader), name))
+    }
+}
+
+impl<R: PressureResource> PressureProbe<R> {
+    fn from_reader(pressure_reader: Box<dyn ReadSystemData<Pressure<R>>>, name: &'static str) -> Self {
+        PressureProbe {
+            pressure_reader,
+            name,
+            some_avg10: 0.,
+        }
+    }
+}
+
+impl PressureProbe<CpuResource> {
+    /// Creates a new probe measuring the pressure on the CPU
+    ///
+    /// Fails if the kernel does not expose pressure stall information.
+    pub fn cpu() -> Result<Self, Error> {
+        Self::open("CPU pressure")
+    }
+}
+
+impl PressureProbe<IoResource> {
+    /// Creates a new probe measuring the pressure on I/O
+    ///
+    /// Fails if the kernel does not expose pressure stall information.
+    pub fn io() -> Result<Self, Error> {
+        Self::open("I/O pressure")
+    }
+}
+
+impl PressureProbe<MemoryResource> {
+    /// Creates a new probe measuring the pressure on memory
+    ///
+    /// Fails if the kernel does not expose pressure stall information.
+    pub fn memory() -> Result<Self, Error> {
+        Self::open("Memory pressure")
+    }
+}
+
+impl<R: PressureResource> Probe<PercentMetric> for PressureProbe<R> {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn init_iteration(&mut self) -> Result<(), Error> {
+        let pressure = self
+            .pressure_reader
+            .read()
+            .map_err(|e| Error::ProbingError("Could not read pressure stall information".to_string(), e.into()))?;
+        self.some_avg10 = pressure.some_avg10();
+
+        Ok(())
+    }
+
+    fn probe(&mut self, _pid: Pid) -> Result<PercentMetric, Error> {
+        Ok(PercentMetric::new(self.some_avg10))
+    }
+
+    fn cleanup(&mut self, _pids: &[Pid]) {
+        // Nothing to do, as no data is kept per process
+    }
+}
+
+#[cfg(test)]
+mod test_pressure_probe {
+    use crate::core::metrics::PercentMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::parsers::fakes::FakeSystemDataReader;
+    use crate::procfs::parsers::system::{IoResource, Pressure};
+    use crate::procfs::pressure_probe::PressureProbe;
+
+    #[test]
+    fn test_should_report_latest_pressure_for_all_processes() {
+        let reader = FakeSystemDataReader::from_sequence(vec![Pressure::<IoResource>::new(1.5), Pressure::new(12.25)]);
+        let mut probe = PressureProbe::from_reader(Box::new(reader), "I/O pressure");
+
+        probe.probe_processes(&[1, 2]).unwrap();
+
+        assert_eq!(
+            probe.probe_processes(&[1, 2]).unwrap(),
+            hashmap!(1 => PercentMetric::new(12.25), 2 => PercentMetric::new(12.25))
+        );
+    }
+}
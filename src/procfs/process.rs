@@ -2,7 +2,7 @@
 
 use std::fs::{read_dir, DirEntry};
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use log::warn;
@@ -11,11 +11,13 @@ use thiserror::Error;
 use crate::core::process::{Pid, ProcessMetadata, ProcessScanner};
 use crate::core::time::Timestamp;
 use crate::core::Error as CoreError;
+use crate::procfs::cgroups::read_unit;
 use crate::procfs::libc::clock_ticks;
-use crate::procfs::parsers::process::{Comm, PidStat};
+use crate::procfs::namespaces::Namespaces;
+use crate::procfs::parsers::process::{Cmdline, Comm, PidStat, PidStatus};
 use crate::procfs::parsers::system::Uptime;
 use crate::procfs::parsers::{ReadProcessData, ReadSystemData, SystemDataReader, TransientProcessDataReader};
-use crate::procfs::ProcfsError;
+use crate::procfs::{proc_root, ProcfsError};
 
 /// Errors internal to the process module
 #[derive(Error, Debug)]
@@ -40,8 +42,12 @@ impl From<Error> for CoreError {
 pub struct ProcfsScanner {
     proc_dir: PathBuf,
     comm_reader: Box<dyn ReadProcessData<Comm>>,
+    cmdline_reader: Box<dyn ReadProcessData<Cmdline>>,
     stat_reader: Box<dyn ReadProcessData<PidStat>>,
+    status_reader: Box<dyn ReadProcessData<PidStatus>>,
     boot_time: Timestamp,
+    // The namespaces of spv, to detect the processes isolated in other namespaces
+    own_namespaces: Namespaces,
 }
 
 /// Scan running processes on a Linux host by scanning the content of /proc directory
@@ -55,10 +61,13 @@ impl ProcfsScanner {
             .boot_time();
 
         Ok(ProcfsScanner {
-            proc_dir: PathBuf::from("/proc"),
-            comm_reader: Box::new(TransientProcessDataReader::default()),
-            stat_reader: Box::new(TransientProcessDataReader::default()),
+            proc_dir: proc_root(),
+            comm_reader: Box::new(TransientProcessDataReader),
+            cmdline_reader: Box::new(TransientProcessDataReader),
+            stat_reader: Box::new(TransientProcessDataReader),
+            status_reader: Box::new(TransientProcessDataReader),
             boot_time,
+            own_namespaces: Namespaces::read("self"),
         })
     }
 
@@ -77,38 +86,41 @@ impl ProcfsScanner {
     }
 
     /// Calculates the timestamp at which the process started
-    fn calculate_spawn_time(&mut self, pid: Pid) -> Result<Timestamp, CoreError> {
+    ///
+    /// # Arguments
+    ///  * `starttime`: The time at which the process started after boot, in clock ticks
+    fn calculate_spawn_time(&self, starttime: u64) -> Result<Timestamp, CoreError> {
         let clock_ticks = clock_ticks().map_err(|e| Error::SystemParsingFailure("_SC_CLK_TCK".into(), e))?;
 
-        let starttime = self
-            .stat_reader
-            .read(pid)
-            .map_err(|e| Error::ProcessParsing(pid, "stat".into(), e.into()))?
-            .starttime();
-
         Ok(self.boot_time + Duration::from_secs(starttime / clock_ticks))
     }
 }
 
+/// Returns the PIDs of the processes listed in a proc filesystem
+///
+/// # Arguments
+///  * `proc_dir`: The directory where the proc filesystem is mounted
+pub(crate) fn scan_pids(proc_dir: &Path) -> std::result::Result<Vec<Pid>, CoreError> {
+    let dir_iter = read_dir(proc_dir).map_err(|e| Error::ProcessScanningFailure(proc_dir.into(), e))?;
+
+    let pids = dir_iter
+        // only retrieve dir entry which are not err
+        .filter_map(|r| r.ok())
+        // only retrieve directories
+        .filter(|de| de.file_type().is_ok() && de.file_type().unwrap().is_dir())
+        // retrieve Result<PID> from dir name
+        .map(|de: DirEntry| ProcfsScanner::extract_pid_from_proc_dir(de.file_name().to_str()))
+        // Discard all dir names which could not be converted to PID
+        .filter_map(|pid_ret| pid_ret.ok())
+        .collect();
+
+    Ok(pids)
+}
+
 impl ProcessScanner for ProcfsScanner {
     /// Returns the PIDs of currently running processes
     fn scan(&mut self) -> std::result::Result<Vec<Pid>, CoreError> {
-        let path = self.proc_dir.as_path();
-
-        let dir_iter = read_dir(path).map_err(|e| Error::ProcessScanningFailure(path.into(), e))?;
-
-        let pids = dir_iter
-            // only retrieve dir entry which are not err
-            .filter_map(|r| r.ok())
-            // only retrieve directories
-            .filter(|de| de.file_type().is_ok() && de.file_type().unwrap().is_dir())
-            // retrieve Result<PID> from dir name
-            .map(|de: DirEntry| Self::extract_pid_from_proc_dir(de.file_name().to_str()))
-            // Discard all dir names which could not be converted to PID
-            .filter_map(|pid_ret| pid_ret.ok())
-            .collect();
-
-        Ok(pids)
+        scan_pids(self.proc_dir.as_path())
     }
 
     /// Fetch and returns the metadata of a process
@@ -121,7 +133,17 @@ impl ProcessScanner for ProcfsScanner {
             .read(pid)
             .map_err(|e| Error::ProcessParsing(pid, "comm".into(), e.into()))?;
 
-        let mut spawntime = self.calculate_spawn_time(pid)?;
+        let status = self
+            .status_reader
+            .read(pid)
+            .map_err(|e| Error::ProcessParsing(pid, "status".into(), e.into()))?;
+
+        let stat = self
+            .stat_reader
+            .read(pid)
+            .map_err(|e| Error::ProcessParsing(pid, "stat".into(), e.into()))?;
+
+        let mut spawntime = self.calculate_spawn_time(stat.starttime())?;
         let now = Timestamp::now();
         if spawntime > now {
             warn!(
@@ -131,7 +153,101 @@ impl ProcessScanner for ProcfsScanner {
             spawntime = now;
         }
 
-        Ok(ProcessMetadata::new(pid, comm.into_command(), spawntime))
+        // The command line is only used to label processes, so failing to read it should not discard the process
+        let cmdline = match self.cmdline_reader.read(pid) {
+            Ok(cmdline) => cmdline.into_args(),
+            Err(e) => {
+                warn!("Could not read the command line of process {}: {:?}", pid, e);
+                String::new()
+            }
+        };
+
+        let isolated = Namespaces::read(&pid.to_string()).isolated_from(&self.own_namespaces);
+        let unit = read_unit(pid);
+
+        let mut pm = ProcessMetadata::new(pid, comm.into_command(), spawntime)
+            .with_cmdline(cmdline)
+            .with_tgid(status.tgid())
+            .with_ppid(status.ppid())
+            .with_isolation(isolated)
+            .with_starttime(stat.starttime());
+        if let Some(unit) = unit {
+            pm = pm.with_unit(unit);
+        }
+        pm.set_stopped(stat.is_stopped());
+
+        Ok(pm)
+    }
+
+    /// Reads the current command of a process from its comm file
+    ///
+    /// # Arguments
+    ///  * `pid`: The identifier of the process for which to retrieve the command
+    fn fetch_command(&mut self, pid: Pid) -> std::result::Result<String, CoreError> {
+        let comm = self
+            .comm_reader
+            .read(pid)
+            .map_err(|e| Error::ProcessParsing(pid, "comm".into(), e.into()))?;
+
+        Ok(comm.into_command())
+    }
+
+    /// Reads the cumulative CPU time of a process from its stat file
+    ///
+    /// # Arguments
+    ///  * `pid`: The identifier of the process for which to retrieve the CPU time
+    fn fetch_cpu_time(&mut self, pid: Pid) -> std::result::Result<Duration, CoreError> {
+        let clock_ticks = clock_ticks().map_err(|e| Error::SystemParsingFailure("_SC_CLK_TCK".into(), e))?;
+
+        let running_ticks = self
+            .stat_reader
+            .read(pid)
+            .map_err(|e| Error::ProcessParsing(pid, "stat".into(), e.into()))?
+            .running_time();
+
+        Ok(ticks_to_duration(running_ticks, clock_ticks))
+    }
+
+    /// Reads the state of a process from its stat file
+    ///
+    /// # Arguments
+    ///  * `pid`: The identifier of the process for which to retrieve the state
+    fn fetch_stopped(&mut self, pid: Pid) -> std::result::Result<bool, CoreError> {
+        let stat = self
+            .stat_reader
+            .read(pid)
+            .map_err(|e| Error::ProcessParsing(pid, "stat".into(), e.into()))?;
+
+        Ok(stat.is_stopped())
+    }
+}
+
+/// Converts an amount of clock ticks to a duration
+///
+/// # Arguments
+///  * `ticks`: The amount of clock ticks, which is considered to be 0 if negative
+///  * `clock_ticks`: The amount of clock ticks per second
+fn ticks_to_duration(ticks: i64, clock_ticks: u64) -> Duration {
+    if ticks <= 0 || clock_ticks == 0 {
+        return Duration::ZERO;
+    }
+
+    Duration::from_millis(ticks as u64 * 1000 / clock_ticks)
+}
+
+#[cfg(test)]
+mod test_ticks_to_duration {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case(0, Duration::ZERO)]
+    #[case(1, Duration::from_millis(10))]
+    #[case(250, Duration::from_millis(2500))]
+    #[case(-5, Duration::ZERO)]
+    fn test_should_convert_ticks_to_duration(#[case] ticks: i64, #[case] expected: Duration) {
+        assert_eq!(ticks_to_duration(ticks, 100), expected);
     }
 }
 
@@ -150,7 +266,7 @@ mod test_pid_from_proc_dir {
     fn test_pid_from_invalid_proc_dir_name() {
         match ProcfsScanner::extract_pid_from_proc_dir(Some("abc")) {
             Err(Error::NotProcessDir(dir)) => assert_eq!(dir, String::from("abc")),
-            _ => assert!(false),
+            _ => panic!("Expected a NotProcessDir error"),
         }
     }
 
@@ -158,7 +274,7 @@ mod test_pid_from_proc_dir {
     fn test_pid_from_no_proc_dir_name() {
         match ProcfsScanner::extract_pid_from_proc_dir(None) {
             Err(Error::NotProcessDir(dir)) => assert_eq!(dir, String::new()),
-            _ => assert!(false),
+            _ => panic!("Expected a NotProcessDir error"),
         }
     }
 }
@@ -171,9 +287,12 @@ mod test_pid_scanner {
 
     use tempfile::{tempdir, NamedTempFile};
 
-    use crate::core::time::test_utils::advance_time_and_refresh_timestamp;
+    use crate::core::time::test_utils::{
+        advance_time_and_refresh_timestamp, setup_fake_clock_to_prevent_substract_overflow,
+    };
     use crate::core::Error as CoreError;
     use crate::procfs::parsers::fakes::FakeProcessDataReader;
+    use crate::procfs::test_utils::ProcRootOverride;
 
     use super::*;
 
@@ -201,8 +320,11 @@ mod test_pid_scanner {
         ProcfsScanner {
             proc_dir,
             comm_reader: Box::new(FakeProcessDataReader::new()),
+            cmdline_reader: Box::new(FakeProcessDataReader::new()),
             stat_reader: Box::new(FakeProcessDataReader::new()),
+            status_reader: Box::new(FakeProcessDataReader::new()),
             boot_time: Timestamp::now(),
+            own_namespaces: Namespaces::default(),
         }
     }
 
@@ -210,11 +332,28 @@ mod test_pid_scanner {
         comm_reader: FakeProcessDataReader<Comm>,
         stat_reader: FakeProcessDataReader<PidStat>,
     ) -> ProcfsScanner {
+        let mut status_reader = FakeProcessDataReader::<PidStatus>::new();
+        status_reader.set_pid_sequence(123, vec![PidStatus::new(123)]);
+
+        build_metadata_fetcher_with_status(comm_reader, stat_reader, status_reader)
+    }
+
+    fn build_metadata_fetcher_with_status(
+        comm_reader: FakeProcessDataReader<Comm>,
+        stat_reader: FakeProcessDataReader<PidStat>,
+        status_reader: FakeProcessDataReader<PidStatus>,
+    ) -> ProcfsScanner {
+        let mut cmdline_reader = FakeProcessDataReader::<Cmdline>::new();
+        cmdline_reader.set_pid_sequence(123, vec![Cmdline::new("test_cmd --verbose")]);
+
         ProcfsScanner {
             proc_dir: PathBuf::new(),
             comm_reader: Box::new(comm_reader),
+            cmdline_reader: Box::new(cmdline_reader),
             stat_reader: Box::new(stat_reader),
+            status_reader: Box::new(status_reader),
             boot_time: Timestamp::now(),
+            own_namespaces: Namespaces::default(),
         }
     }
 
@@ -272,6 +411,36 @@ mod test_pid_scanner {
         assert!(pids.is_err());
     }
 
+    #[test]
+    fn test_scanner_should_read_processes_from_proc_root() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let fixture_dir = tempdir().expect("Could not create tmp dir");
+        let pid_dir = fixture_dir.path().join("123");
+        fs::create_dir(&pid_dir).expect("Could not create PID dir");
+        fs::write(fixture_dir.path().join("uptime"), "4242.10 12345.67\n").expect("Could not write uptime");
+        fs::write(pid_dir.join("comm"), "fixture_cmd\n").expect("Could not write comm");
+        fs::write(pid_dir.join("cmdline"), "fixture_cmd\0--verbose\0").expect("Could not write cmdline");
+        fs::write(
+            pid_dir.join("stat"),
+            "123 (fixture_cmd) S 1 123 123 0 -1 4194304 0 0 0 0 1 2 0 0 20 0 1 0 100",
+        )
+        .expect("Could not write stat");
+        fs::write(
+            pid_dir.join("status"),
+            "Name:\tfixture_cmd\nTgid:\t123\nPPid:\t1\nvoluntary_ctxt_switches:\t0\n",
+        )
+        .expect("Could not write status");
+        let _proc_root = ProcRootOverride::new(fixture_dir.path());
+
+        let mut proc_scanner = ProcfsScanner::new().expect("Could not create scanner");
+
+        assert_eq!(proc_scanner.scan().unwrap(), vec![123]);
+        let process_metadata = proc_scanner.fetch_metadata(123).unwrap();
+        assert_eq!(process_metadata.command(), "fixture_cmd");
+        assert_eq!(process_metadata.cmdline(), "fixture_cmd --verbose");
+        assert_eq!(process_metadata.ppid(), 1);
+    }
+
     #[test]
     fn test_process_metadata_has_correct_cmd() {
         let mut comm_reader = FakeProcessDataReader::<Comm>::new();
@@ -287,6 +456,84 @@ mod test_pid_scanner {
             .expect("Could not get processes metadata");
 
         assert_eq!(process_metadata.command(), "test_cmd");
+        assert_eq!(process_metadata.cmdline(), "test_cmd --verbose");
+    }
+
+    #[test]
+    fn test_fetch_command_should_return_latest_comm() {
+        let mut comm_reader = FakeProcessDataReader::<Comm>::new();
+        let mut stat_reader = FakeProcessDataReader::<PidStat>::new();
+
+        comm_reader.set_pid_sequence(123, vec![Comm::new("test_cmd"), Comm::new("renamed_cmd")]);
+        stat_reader.set_pid_sequence(123, vec![PidStat::new(0, 0, 0, 0, 0)]);
+
+        let mut proc_scanner = build_metadata_fetcher(comm_reader, stat_reader);
+        proc_scanner
+            .fetch_metadata(123)
+            .expect("Could not get processes metadata");
+
+        assert_eq!(proc_scanner.fetch_command(123).unwrap(), "renamed_cmd");
+    }
+
+    #[test]
+    fn test_fetch_cpu_time_should_return_latest_running_time() {
+        let mut comm_reader = FakeProcessDataReader::<Comm>::new();
+        let mut stat_reader = FakeProcessDataReader::<PidStat>::new();
+
+        let clock_ticks = clock_ticks().unwrap();
+        comm_reader.set_pid_sequence(123, vec![Comm::new("test_cmd")]);
+        stat_reader.set_pid_sequence(
+            123,
+            vec![
+                PidStat::new(0, 0, 0, 0, 0),
+                PidStat::new(2 * clock_ticks as u32, clock_ticks as u32, 0, 0, 0),
+            ],
+        );
+
+        let mut proc_scanner = build_metadata_fetcher(comm_reader, stat_reader);
+        proc_scanner
+            .fetch_metadata(123)
+            .expect("Could not get processes metadata");
+
+        assert_eq!(proc_scanner.fetch_cpu_time(123).unwrap(), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_process_metadata_has_correct_tgid() {
+        let mut comm_reader = FakeProcessDataReader::<Comm>::new();
+        let mut stat_reader = FakeProcessDataReader::<PidStat>::new();
+        let mut status_reader = FakeProcessDataReader::<PidStatus>::new();
+
+        comm_reader.set_pid_sequence(123, vec![Comm::new("test_cmd")]);
+        stat_reader.set_pid_sequence(123, vec![PidStat::new(0, 0, 0, 0, 0)]);
+        status_reader.set_pid_sequence(123, vec![PidStatus::new(100)]);
+
+        let mut proc_scanner = build_metadata_fetcher_with_status(comm_reader, stat_reader, status_reader);
+
+        let process_metadata = proc_scanner
+            .fetch_metadata(123)
+            .expect("Could not get processes metadata");
+
+        assert_eq!(process_metadata.tgid(), 100);
+    }
+
+    #[test]
+    fn test_process_metadata_has_correct_ppid() {
+        let mut comm_reader = FakeProcessDataReader::<Comm>::new();
+        let mut stat_reader = FakeProcessDataReader::<PidStat>::new();
+        let mut status_reader = FakeProcessDataReader::<PidStatus>::new();
+
+        comm_reader.set_pid_sequence(123, vec![Comm::new("test_cmd")]);
+        stat_reader.set_pid_sequence(123, vec![PidStat::new(0, 0, 0, 0, 0)]);
+        status_reader.set_pid_sequence(123, vec![PidStatus::new(123).with_ppid(42)]);
+
+        let mut proc_scanner = build_metadata_fetcher_with_status(comm_reader, stat_reader, status_reader);
+
+        let process_metadata = proc_scanner
+            .fetch_metadata(123)
+            .expect("Could not get processes metadata");
+
+        assert_eq!(process_metadata.ppid(), 42);
     }
 
     #[test]
@@ -0,0 +1,219 @@
+//! TCP and UDP traffic probing
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::core::metrics::ProtocolIOMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::packet_capture::{CaptureTraffic, PacketCapture};
+use crate::procfs::rates::{ProcessesRates, PushMode};
+use crate::procfs::socket_protocols::{Protocol, SocketProtocols, SocketTraffic};
+
+const PROTOCOL_IO_RATE_RETENTION: Duration = Duration::from_secs(1);
+
+/// Probe implementation to measure the rate of bytes that processes receive and send over TCP and over UDP
+///
+/// The kernel does not account the traffic of each process, so the packets of the host are captured (see
+/// [`PacketCapture`]) and attributed to the sockets listed in `/proc/net/tcp`, `/proc/net/udp` and their IPv6
+/// counterparts. These sockets are then attributed to processes through the inodes of the sockets opened by each
+/// process, as listed in `/proc/[pid]/fd`.
+///
+/// The traffic of the sockets which are opened and closed between two probings is not attributed to any process.
+pub struct ProtocolIOProbe {
+    capture: Box<dyn CaptureTraffic>,
+    socket_protocols: SocketProtocols,
+    socket_traffic: HashMap<u64, SocketTraffic>,
+    tcp_input_rate_calculator: ProcessesRates,
+    tcp_output_rate_calculator: ProcessesRates,
+    udp_input_rate_calculator: ProcessesRates,
+    udp_output_rate_calculator: ProcessesRates,
+}
+
+impl ProtocolIOProbe {
+    /// Creates a new probe that can measure the TCP and UDP traffic of processes
+    ///
+    /// Fails if the packets of the host cannot be captured, which requires the `CAP_NET_RAW` capability.
+    pub fn new() -> Result<Self, Error> {
+        let capture = PacketCapture::start()
+            .map_err(|e| Error::ProbingError("Could not capture the packets of the host".to_string(), e.into()))?;
+
+        Ok(Self::from_sources(Box::new(capture), SocketProtocols::new()?))
+    }
+
+    fn from_sources(capture: Box<dyn CaptureTraffic>, socket_protocols: SocketProtocols) -> Self {
+        ProtocolIOProbe {
+            capture,
+            socket_protocols,
+            socket_traffic: HashMap::new(),
+            tcp_input_rate_calculator: ProcessesRates::new(PushMode::Increment, PROTOCOL_IO_RATE_RETENTION),
+            tcp_output_rate_calculator: ProcessesRates::new(PushMode::Increment, PROTOCOL_IO_RATE_RETENTION),
+            udp_input_rate_calculator: ProcessesRates::new(PushMode::Increment, PROTOCOL_IO_RATE_RETENTION),
+            udp_output_rate_calculator: ProcessesRates::new(PushMode::Increment, PROTOCOL_IO_RATE_RETENTION),
+        }
+    }
+}
+
+impl Probe<ProtocolIOMetric> for ProtocolIOProbe {
+    fn name(&self) -> &'static str {
+        "Protocol I/O"
+    }
+
+    fn init_iteration(&mut self) -> Result<(), Error> {
+        let traffic = self
+            .capture
+            .take()
+            .map_err(|e| Error::ProbingError("Could not capture the packets of the host".to_string(), e.into()))?;
+
+        // The sockets are listed after the packets are taken, so that the sockets which sent them are known
+        self.socket_protocols.refresh()?;
+        self.socket_traffic = self.socket_protocols.attribute(&traffic);
+
+        Ok(())
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<ProtocolIOMetric, Error> {
+        let traffic = self.socket_protocols.process_traffic(pid, &self.socket_traffic)?;
+        let tcp = traffic.get(&Protocol::Tcp).copied().unwrap_or_default();
+        let udp = traffic.get(&Protocol::Udp).copied().unwrap_or_default();
+
+        Ok(ProtocolIOMetric::new(
+            increment_rate(&mut self.tcp_input_rate_calculator, pid, tcp.received)?,
+            increment_rate(&mut self.tcp_output_rate_calculator, pid, tcp.sent)?,
+            increment_rate(&mut self.udp_input_rate_calculator, pid, udp.received)?,
+            increment_rate(&mut self.udp_output_rate_calculator, pid, udp.sent)?,
+        ))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().copied().for_each(|pid| {
+            self.tcp_input_rate_calculator.cleanup(pid);
+            self.tcp_output_rate_calculator.cleanup(pid);
+            self.udp_input_rate_calculator.cleanup(pid);
+            self.udp_output_rate_calculator.cleanup(pid);
+        });
+    }
+}
+
+/// Pushes the bytes exchanged by a process since the last probing, and returns its rate of bytes per second
+fn increment_rate(rate_calculator: &mut ProcessesRates, pid: Pid, bytes: usize) -> Result<usize, Error> {
+    rate_calculator.push(pid, bytes);
+
+    let rate = rate_calculator
+        .rate(pid)
+        .map_err(|e| Error::ProbingError("Could not calculate protocol I/O rate".to_string(), e.into()))?;
+
+    Ok(rate as usize)
+}
+
+#[cfg(test)]
+mod test_protocol_io_probe {
+    use std::collections::{HashMap, VecDeque};
+
+    use sn_fake_clock::FakeClock;
+
+    use crate::core::metrics::ProtocolIOMetric;
+    use crate::core::probe::Probe;
+    use crate::core::process::Pid;
+    use crate::procfs::packet_capture::{CaptureTraffic, Direction, Flow};
+    use crate::procfs::parsers::fakes::FakeSystemDataReader;
+    use crate::procfs::parsers::system::{NetTcp, NetUdp, SocketEndpoints};
+    use crate::procfs::protocol_io_probe::ProtocolIOProbe;
+    use crate::procfs::socket_probe::ReadSocketInodes;
+    use crate::procfs::socket_protocols::{Protocol, SocketProtocols};
+    use crate::procfs::ProcfsError;
+
+    struct FakeCapture {
+        traffic_sequence: VecDeque<HashMap<Flow, usize>>,
+    }
+
+    impl CaptureTraffic for FakeCapture {
+        fn take(&mut self) -> Result<HashMap<Flow, usize>, ProcfsError> {
+            Ok(self
+                .traffic_sequence
+                .pop_front()
+                .expect("The capture has no traffic to return"))
+        }
+    }
+
+    struct FakeSocketInodesReader {
+        inodes: HashMap<Pid, Vec<u64>>,
+    }
+
+    impl ReadSocketInodes for FakeSocketInodesReader {
+        fn read(&mut self, pid: Pid) -> Result<Vec<u64>, ProcfsError> {
+            self.inodes.get(&pid).cloned().ok_or(ProcfsError::UnknownPID(pid))
+        }
+    }
+
+    fn flow(protocol: Protocol, direction: Direction, local: &str, remote: &str) -> Flow {
+        Flow {
+            protocol,
+            direction,
+            local: local.parse().unwrap(),
+            remote: remote.parse().unwrap(),
+        }
+    }
+
+    fn build_probe(traffic_sequence: Vec<HashMap<Flow, usize>>) -> ProtocolIOProbe {
+        let iterations = traffic_sequence.len();
+        let tcp = NetTcp::with_socket_endpoints(hashmap!(10 => SocketEndpoints {
+            local: "10.0.2.15:41652".parse().unwrap(),
+            remote: "172.26.250.142:443".parse().unwrap(),
+        }));
+        let udp = NetUdp::with_socket_endpoints(hashmap!(30 => SocketEndpoints {
+            local: "0.0.0.0:53".parse().unwrap(),
+            remote: "0.0.0.0:0".parse().unwrap(),
+        }));
+        let socket_protocols = SocketProtocols::from_readers(
+            Box::new(FakeSystemDataReader::from_sequence(vec![tcp; iterations])),
+            None,
+            Box::new(FakeSystemDataReader::from_sequence(vec![udp; iterations])),
+            None,
+            Box::new(FakeSocketInodesReader {
+                inodes: hashmap!(1 => vec![10, 30], 2 => vec![]),
+            }),
+        );
+        let capture = FakeCapture {
+            traffic_sequence: traffic_sequence.into(),
+        };
+
+        ProtocolIOProbe::from_sources(Box::new(capture), socket_protocols)
+    }
+
+    #[test]
+    fn test_should_calculate_rate_of_traffic_by_protocol_and_direction() {
+        let traffic = hashmap!(
+            flow(Protocol::Tcp, Direction::Incoming, "10.0.2.15:41652", "172.26.250.142:443") => 4000,
+            flow(Protocol::Tcp, Direction::Outgoing, "10.0.2.15:41652", "172.26.250.142:443") => 1000,
+            flow(Protocol::Udp, Direction::Incoming, "10.0.2.15:53", "10.0.2.3:40000") => 300,
+            flow(Protocol::Udp, Direction::Outgoing, "10.0.2.15:53", "10.0.2.3:40000") => 600
+        );
+        let mut probe = build_probe(vec![HashMap::new(), traffic]);
+
+        probe.probe_processes(&[1, 2]).unwrap();
+        FakeClock::advance_time(1000);
+
+        assert_eq!(
+            probe.probe_processes(&[1, 2]).unwrap(),
+            hashmap!(1 => ProtocolIOMetric::new(4000, 1000, 300, 600), 2 => ProtocolIOMetric::default())
+        );
+    }
+
+    #[test]
+    fn test_should_not_attribute_traffic_of_unknown_sockets() {
+        let traffic = hashmap!(
+            flow(Protocol::Tcp, Direction::Incoming, "10.0.2.15:22", "10.0.2.2:51000") => 4000
+        );
+        let mut probe = build_probe(vec![HashMap::new(), traffic]);
+
+        probe.probe_processes(&[1]).unwrap();
+        FakeClock::advance_time(1000);
+
+        assert_eq!(
+            probe.probe_processes(&[1]).unwrap(),
+            hashmap!(1 => ProtocolIOMetric::default())
+        );
+    }
+}
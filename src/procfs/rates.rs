@@ -38,7 +38,7 @@ impl ProcessesRates {
     ///  * `mode`: Indicates how pushed values should be handled:
     ///     - PushMode.ACCUMULATIVE indicates that the value should be handled as-is
     ///     - PushMode.INCREMENT indicates that the given value is the difference with the last
-    ///         pushed value
+    ///       pushed value
     ///  * `data_retention`: Indicates over how much time to calculate the rate.
     pub fn new(mode: PushMode, data_retention: Duration) -> Self {
         ProcessesRates {
@@ -0,0 +1,183 @@
+//! Probing of single processes, to use the probes of spv without its UI
+
+use std::thread;
+use std::time::Duration;
+
+use crate::core::collection::{MetricCollector, ProbeCollector};
+use crate::core::process::{Pid, ProcessScanner};
+use crate::core::sample::ProcessSample;
+use crate::core::time::{refresh_current_timestamp, Timestamp};
+use crate::core::Error;
+use crate::procfs::cpu_probe::CpuProbe;
+use crate::procfs::diskio_probe::DiskIOProbe;
+use crate::procfs::memory_probe::MemoryProbe;
+use crate::procfs::process::ProcfsScanner;
+
+/// How many file descriptors each probe of the sampler keeps open at most
+const SAMPLER_FD_LIMIT: usize = 16;
+/// How long the sampler waits between the calibration of its probes and the collection of metrics by default
+const DEFAULT_CALIBRATION_PERIOD: Duration = Duration::from_millis(500);
+
+/// Samples the CPU usage, the memory and the disk I/O of processes, one process at a time
+///
+/// ```no_run
+/// use spv::procfs::sampler::ProcessSampler;
+///
+/// let mut sampler = ProcessSampler::new().unwrap();
+/// let sample = sampler.sample(std::process::id()).unwrap();
+///
+/// for name in sample.collector_names() {
+///     println!("{}: {}", name, sample.metric(name).unwrap().concise_repr());
+/// }
+/// ```
+pub struct ProcessSampler {
+    scanner: Box<dyn ProcessScanner>,
+    collectors: Vec<Box<dyn MetricCollector>>,
+    calibration_period: Duration,
+}
+
+impl ProcessSampler {
+    /// Creates a sampler with the standard probes of spv
+    pub fn new() -> Result<Self, Error> {
+        let collectors: Vec<Box<dyn MetricCollector>> = vec![
+            Box::new(ProbeCollector::new(CpuProbe::new(SAMPLER_FD_LIMIT)?)),
+            Box::new(ProbeCollector::new(MemoryProbe::new(SAMPLER_FD_LIMIT, false)?)),
+            Box::new(ProbeCollector::new(DiskIOProbe::new(SAMPLER_FD_LIMIT))),
+        ];
+
+        Ok(Self::from_collectors(Box::new(ProcfsScanner::new()?), collectors))
+    }
+
+    /// Creates a sampler collecting metrics with the given collectors
+    ///
+    /// # Arguments
+    ///  * `scanner`: Fetches the metadata of the sampled processes
+    ///  * `collectors`: Collect the metrics of the sampled processes
+    pub fn from_collectors(scanner: Box<dyn ProcessScanner>, collectors: Vec<Box<dyn MetricCollector>>) -> Self {
+        Self {
+            scanner,
+            collectors,
+            calibration_period: DEFAULT_CALIBRATION_PERIOD,
+        }
+    }
+
+    /// Sets how long the sampler measures the usage of resources, such as the CPU usage, of the sampled processes
+    pub fn with_calibration_period(mut self, calibration_period: Duration) -> Self {
+        self.calibration_period = calibration_period;
+        self
+    }
+
+    /// Returns the names of the collectors of the sampler
+    pub fn collector_names(&self) -> Vec<&str> {
+        self.collectors.iter().map(|collector| collector.name()).collect()
+    }
+
+    /// Calibrates the probes on the given process, then collects its metrics once the calibration period elapsed
+    ///
+    /// This blocks the calling thread for the calibration period.
+    ///
+    /// # Arguments
+    ///  * `pid`: The ID of the process to sample
+    pub fn sample(&mut self, pid: Pid) -> Result<ProcessSample, Error> {
+        self.calibrate(pid)?;
+        thread::sleep(self.calibration_period);
+        self.collect(pid)
+    }
+
+    fn calibrate(&mut self, pid: Pid) -> Result<(), Error> {
+        refresh_current_timestamp();
+        self.collectors
+            .iter_mut()
+            .try_for_each(|collector| collector.calibrate(&[pid]))
+    }
+
+    fn collect(&mut self, pid: Pid) -> Result<ProcessSample, Error> {
+        refresh_current_timestamp();
+        let metadata = self.scanner.fetch_metadata(pid)?;
+        for collector in self.collectors.iter_mut() {
+            collector.collect(&[pid])?;
+        }
+
+        let metrics = self
+            .collectors
+            .iter()
+            .filter_map(|collector| {
+                collector
+                    .last_metric(pid)
+                    .map(|metric| (collector.name().to_string(), metric))
+            })
+            .collect();
+
+        // The process may not be sampled again, and its next sample is calibrated anew
+        for collector in self.collectors.iter_mut() {
+            collector.cleanup(&[pid]);
+            collector.purge(Timestamp::now());
+        }
+
+        Ok(ProcessSample::new(metadata, metrics))
+    }
+}
+
+#[cfg(test)]
+mod test_process_sampler {
+    use rstest::*;
+    use sn_fake_clock::FakeClock;
+
+    use crate::core::metrics::{BytesMetric, IOMetric, Metric, PercentMetric};
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::procfs::fake_procfs::FakeProcfs;
+    use crate::procfs::libc::page_size;
+    use crate::procfs::sampler::ProcessSampler;
+
+    #[fixture]
+    fn procfs() -> FakeProcfs {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let procfs = FakeProcfs::new();
+        procfs.spawn_process(10, "compiler");
+        procfs.set_resident_pages(10, 100);
+        procfs
+    }
+
+    #[rstest]
+    fn test_should_collect_metrics_of_standard_probes(procfs: FakeProcfs) {
+        let mut sampler = ProcessSampler::new().unwrap();
+
+        sampler.calibrate(10).unwrap();
+        procfs.set_cpu_time(100, 50, 50);
+        procfs.set_process_cpu_time(10, 40, 10);
+        procfs.set_process_io(10, 4096, 0);
+        FakeClock::advance_time(1000);
+        let sample = sampler.collect(10).unwrap();
+
+        assert_eq!(sample.pid(), 10);
+        assert_eq!(sample.metadata().command(), "compiler");
+        assert_eq!(sample.collector_names(), vec!["CPU usage", "Disk I/O", "Memory"]);
+        assert_eq!(
+            sample.metric("CPU usage"),
+            Some(&PercentMetric::new(25.) as &dyn Metric)
+        );
+        assert_eq!(sample.metric("Disk I/O"), Some(&IOMetric::new(4096, 0) as &dyn Metric));
+        let memory = BytesMetric::new(100 * page_size().unwrap() as usize);
+        assert_eq!(sample.metric("Memory"), Some(&memory as &dyn Metric));
+    }
+
+    #[rstest]
+    fn test_should_sample_same_process_several_times(procfs: FakeProcfs) {
+        let mut sampler = ProcessSampler::new().unwrap();
+        sampler.calibrate(10).unwrap();
+        FakeClock::advance_time(1000);
+        sampler.collect(10).unwrap();
+
+        FakeClock::advance_time(1000);
+        sampler.calibrate(10).unwrap();
+        procfs.set_cpu_time(100, 50, 50);
+        procfs.set_process_cpu_time(10, 80, 20);
+        FakeClock::advance_time(1000);
+        let sample = sampler.collect(10).unwrap();
+
+        assert_eq!(
+            sample.metric("CPU usage"),
+            Some(&PercentMetric::new(50.) as &dyn Metric)
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Scheduling latency probing
+
+use std::time::Duration;
+
+use crate::core::metrics::PercentMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::parsers::process::PidSchedstat;
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData};
+use crate::procfs::rates::{ProcessesRates, PushMode};
+
+const RUN_DELAY_RATE_RETENTION: Duration = Duration::from_secs(1);
+
+const NANOSECONDS_PER_SECOND: f64 = 1_000_000_000.;
+
+/// Probe implementation to measure the share of time processes spend waiting for a CPU while they are runnable
+///
+/// A process competing with other processes for the CPUs waits on a runqueue before being scheduled. Such a process may
+/// show a low CPU usage, even though it would use more CPU time if it was available.
+/// The latency is read from the main thread of each process.
+pub struct SchedProbe {
+    reader: Box<dyn ReadProcessData<PidSchedstat>>,
+    run_delay_rate_calculator: ProcessesRates,
+}
+
+impl SchedProbe {
+    /// Creates a new probe that can measure the scheduling latency of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many open file descriptors this probe can keep open at most
+    pub fn new(fd_limit: usize) -> Self {
+        Self::from_reader(Box::new(ProcessDataReader::with_capacity(fd_limit)))
+    }
+
+    fn from_reader(reader: Box<dyn ReadProcessData<PidSchedstat>>) -> Self {
+        SchedProbe {
+            reader,
+            run_delay_rate_calculator: ProcessesRates::new(PushMode::Accumulative, RUN_DELAY_RATE_RETENTION),
+        }
+    }
+}
+
+impl Probe<PercentMetric> for SchedProbe {
+    fn name(&self) -> &'static str {
+        "Runqueue wait"
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<PercentMetric, Error> {
+        let schedstat = self
+            .reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError("Could not read process scheduling stats".to_string(), e.into()))?;
+
+        self.run_delay_rate_calculator.push(pid, schedstat.run_delay());
+        let run_delay_rate = self
+            .run_delay_rate_calculator
+            .rate(pid)
+            .map_err(|e| Error::ProbingError("Could not calculate runqueue wait rate".to_string(), e.into()))?;
+
+        Ok(PercentMetric::new(run_delay_rate / NANOSECONDS_PER_SECOND * 100.))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().copied().for_each(|pid| {
+            self.reader.cleanup(pid);
+            self.run_delay_rate_calculator.cleanup(pid);
+        });
+    }
+}
+
+#[cfg(test)]
+mod test_sched_probe {
+    use rstest::*;
+    use sn_fake_clock::FakeClock;
+
+    use crate::core::metrics::PercentMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::parsers::fakes::FakeProcessDataReader;
+    use crate::procfs::parsers::process::PidSchedstat;
+    use crate::procfs::sched_probe::SchedProbe;
+
+    #[rstest]
+    #[case(0, 0, PercentMetric::new(0.))]
+    #[case(1_000_000, 251_000_000, PercentMetric::new(25.))]
+    #[case(500_000_000, 500_000_000, PercentMetric::new(0.))]
+    #[case(0, 1_000_000_000, PercentMetric::new(100.))]
+    fn test_should_calculate_share_of_time_waiting_between_two_samples(
+        #[case] prev_run_delay: usize,
+        #[case] cur_run_delay: usize,
+        #[case] expected: PercentMetric,
+    ) {
+        let mut reader = FakeProcessDataReader::new();
+        reader.set_pid_sequence(
+            1,
+            vec![PidSchedstat::new(prev_run_delay), PidSchedstat::new(cur_run_delay)],
+        );
+
+        let mut probe = SchedProbe::from_reader(Box::new(reader));
+
+        let _ = probe.probe(1).unwrap();
+        FakeClock::advance_time(1000);
+
+        assert_eq!(probe.probe(1).unwrap(), expected);
+    }
+}
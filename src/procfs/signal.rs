@@ -0,0 +1,95 @@
+//! Signalling of processes
+
+use std::process;
+
+use crate::core::process::ProcessMetadata;
+use crate::core::signal::{ProcessSignal, SignalSender};
+use crate::core::Error;
+use crate::procfs::libc::send_signal;
+use crate::procfs::parsers::process::PidStat;
+use crate::procfs::parsers::{ReadProcessData, TransientProcessDataReader};
+
+/// Implementation of SignalSender which checks from `/proc` that a PID has not been recycled before signalling it
+pub struct ProcfsSignalSender {
+    stat_reader: Box<dyn ReadProcessData<PidStat>>,
+}
+
+impl ProcfsSignalSender {
+    pub fn new() -> Self {
+        Self::from_reader(Box::new(TransientProcessDataReader))
+    }
+
+    fn from_reader(stat_reader: Box<dyn ReadProcessData<PidStat>>) -> Self {
+        Self { stat_reader }
+    }
+}
+
+impl Default for ProcfsSignalSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SignalSender for ProcfsSignalSender {
+    fn send(&mut self, process: &ProcessMetadata, signal: ProcessSignal) -> Result<(), Error> {
+        let pid = process.pid();
+        // Stopping spv would freeze the terminal, which it keeps in raw mode
+        if pid == process::id() {
+            return Err(Error::OwnProcess(pid));
+        }
+
+        let stat = self
+            .stat_reader
+            .read(pid)
+            .map_err(|e| Error::SignalError(pid, e.into()))?;
+
+        // A process which started at another time is not the process the user selected, but one reusing its PID
+        if stat.starttime() != process.starttime() {
+            return Err(Error::RecycledPID(pid));
+        }
+
+        send_signal(pid, signal).map_err(|e| Error::SignalError(pid, e.into()))
+    }
+}
+
+#[cfg(test)]
+mod test_procfs_signal_sender {
+    use std::process;
+
+    use crate::core::process::ProcessMetadata;
+    use crate::core::signal::{ProcessSignal, SignalSender};
+    use crate::core::time::Timestamp;
+    use crate::core::Error;
+    use crate::procfs::parsers::fakes::FakeProcessDataReader;
+    use crate::procfs::parsers::process::PidStat;
+    use crate::procfs::signal::ProcfsSignalSender;
+
+    #[test]
+    fn test_should_not_signal_process_whose_pid_was_recycled() {
+        let mut stat_reader = FakeProcessDataReader::new();
+        stat_reader.set_pid_sequence(1, vec![PidStat::new(0, 0, 0, 0, 2000)]);
+        let mut sender = ProcfsSignalSender::from_reader(Box::new(stat_reader));
+
+        let process = ProcessMetadata::new(1, "command", Timestamp::now()).with_starttime(1000);
+
+        assert!(matches!(
+            sender.send(&process, ProcessSignal::Stop),
+            Err(Error::RecycledPID(1))
+        ));
+    }
+
+    #[test]
+    fn test_should_not_signal_own_process() {
+        let pid = process::id();
+        let mut stat_reader = FakeProcessDataReader::new();
+        stat_reader.set_pid_sequence(pid, vec![PidStat::new(0, 0, 0, 0, 1000)]);
+        let mut sender = ProcfsSignalSender::from_reader(Box::new(stat_reader));
+
+        let process = ProcessMetadata::new(pid, "spv", Timestamp::now()).with_starttime(1000);
+
+        assert!(matches!(
+            sender.send(&process, ProcessSignal::Stop),
+            Err(Error::OwnProcess(p)) if p == pid
+        ));
+    }
+}
@@ -0,0 +1,226 @@
+//! Established sockets probing
+
+use std::collections::HashSet;
+use std::fs::{read_dir, read_link};
+
+use crate::core::metrics::CountMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::parsers::system::{NetTcp, NetTcp6};
+use crate::procfs::parsers::{ReadSystemData, SystemDataReader};
+use crate::procfs::{proc_root, ProcfsError};
+
+/// Probe implementation to count the TCP sockets of processes which are in the ESTABLISHED state
+///
+/// Sockets are attributed to processes by correlating the inodes of the sockets listed in `/proc/net/tcp` and
+/// `/proc/net/tcp6` with the inodes of the sockets opened by each process, as listed in `/proc/[pid]/fd`.
+pub struct SocketProbe {
+    tcp_reader: Box<dyn ReadSystemData<NetTcp>>,
+    tcp6_reader: Option<Box<dyn ReadSystemData<NetTcp6>>>,
+    inodes_reader: Box<dyn ReadSocketInodes>,
+    established_inodes: HashSet<u64>,
+}
+
+impl SocketProbe {
+    /// Creates a new probe that can count the established sockets of processes
+    pub fn new() -> Result<Self, Error> {
+        let tcp_reader = SystemDataReader::new()
+            .map_err(|e| Error::ProbingError("Could not access /proc/net/tcp".to_string(), e.into()))?;
+        // /proc/net/tcp6 does not exist when IPv6 is disabled
+        let tcp6_reader = SystemDataReader::new()
+            .ok()
+            .map(|reader| Box::new(reader) as Box<dyn ReadSystemData<NetTcp6>>);
+
+        Ok(Self::from_readers(
+            Box::new(tcp_reader),
+            tcp6_reader,
+            Box::new(FdSocketInodesReader),
+        ))
+    }
+
+    fn from_readers(
+        tcp_reader: Box<dyn ReadSystemData<NetTcp>>,
+        tcp6_reader: Option<Box<dyn ReadSystemData<NetTcp6>>>,
+        inodes_reader: Box<dyn ReadSocketInodes>,
+    ) -> Self {
+        SocketProbe {
+            tcp_reader,
+            tcp6_reader,
+            inodes_reader,
+            established_inodes: HashSet::new(),
+        }
+    }
+}
+
+impl Probe<CountMetric> for SocketProbe {
+    fn name(&self) -> &'static str {
+        "Sockets"
+    }
+
+    fn init_iteration(&mut self) -> Result<(), Error> {
+        let tcp = self
+            .tcp_reader
+            .read()
+            .map_err(|e| Error::ProbingError("Could not read TCP sockets".to_string(), e.into()))?;
+        self.established_inodes = tcp.established_inodes();
+
+        if let Some(tcp6_reader) = self.tcp6_reader.as_mut() {
+            let tcp6 = tcp6_reader
+                .read()
+                .map_err(|e| Error::ProbingError("Could not read TCP6 sockets".to_string(), e.into()))?;
+            self.established_inodes.extend(tcp6.established_inodes());
+        }
+
+        Ok(())
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<CountMetric, Error> {
+        let process_inodes = self
+            .inodes_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not list sockets of PID {}", pid), e.into()))?;
+
+        Ok(CountMetric::new(count_established(
+            &process_inodes,
+            &self.established_inodes,
+        )))
+    }
+
+    fn cleanup(&mut self, _pids: &[Pid]) {
+        // Nothing to do, as no file is kept open between iterations
+    }
+}
+
+/// Counts the sockets of a process which are in the ESTABLISHED state
+///
+/// # Arguments
+///  * `process_inodes`: The inodes of the sockets opened by the process
+///  * `established_inodes`: The inodes of all established sockets of the system
+fn count_established(process_inodes: &[u64], established_inodes: &HashSet<u64>) -> usize {
+    process_inodes
+        .iter()
+        .filter(|inode| established_inodes.contains(inode))
+        .count()
+}
+
+/// Types which can list the inodes of the sockets opened by a process
+pub(crate) trait ReadSocketInodes {
+    fn read(&mut self, pid: Pid) -> Result<Vec<u64>, ProcfsError>;
+}
+
+/// Lists the inodes of the sockets of a process from the targets of the links in `/proc/[pid]/fd`
+pub(crate) struct FdSocketInodesReader;
+
+impl ReadSocketInodes for FdSocketInodesReader {
+    fn read(&mut self, pid: Pid) -> Result<Vec<u64>, ProcfsError> {
+        let fd_dir = proc_root().join(pid.to_string()).join("fd");
+
+        let inodes = read_dir(fd_dir)?
+            .filter_map(|entry| entry.ok())
+            // The file descriptor may have been closed since the directory was listed
+            .filter_map(|entry| read_link(entry.path()).ok())
+            .filter_map(|target| target.to_str().and_then(parse_socket_inode))
+            .collect();
+
+        Ok(inodes)
+    }
+}
+
+/// Extracts the inode of a socket from the target of a file descriptor link (e.g. `socket:[12345]`)
+///
+/// Returns `None` if the file descriptor does not refer to a socket
+fn parse_socket_inode(link_target: &str) -> Option<u64> {
+    link_target
+        .strip_prefix("socket:[")
+        .and_then(|s| s.strip_suffix(']'))
+        .and_then(|inode| inode.parse().ok())
+}
+
+#[cfg(test)]
+mod test_socket_correlation {
+    use std::collections::HashSet;
+
+    use rstest::*;
+
+    use crate::procfs::socket_probe::{count_established, parse_socket_inode};
+
+    #[rstest]
+    #[case("socket:[12345]", Some(12345))]
+    #[case("pipe:[12345]", None)]
+    #[case("anon_inode:[eventfd]", None)]
+    #[case("/dev/null", None)]
+    #[case("socket:[abc]", None)]
+    fn test_should_parse_inode_of_socket_links_only(#[case] link_target: &str, #[case] expected: Option<u64>) {
+        assert_eq!(parse_socket_inode(link_target), expected);
+    }
+
+    #[test]
+    fn test_should_only_count_established_sockets_of_process() {
+        let established_inodes = HashSet::from([10, 20, 30]);
+
+        assert_eq!(count_established(&[10, 30, 40], &established_inodes), 2);
+    }
+
+    #[test]
+    fn test_should_count_no_socket_when_process_has_none() {
+        assert_eq!(count_established(&[], &HashSet::from([10])), 0);
+    }
+}
+
+#[cfg(test)]
+mod test_socket_probe {
+    use std::collections::{HashMap, HashSet};
+
+    use crate::core::metrics::CountMetric;
+    use crate::core::probe::Probe;
+    use crate::core::process::Pid;
+    use crate::procfs::parsers::fakes::FakeSystemDataReader;
+    use crate::procfs::parsers::system::{NetTcp, NetTcp6};
+    use crate::procfs::socket_probe::{ReadSocketInodes, SocketProbe};
+    use crate::procfs::ProcfsError;
+
+    struct FakeSocketInodesReader {
+        inodes: HashMap<Pid, Vec<u64>>,
+    }
+
+    impl ReadSocketInodes for FakeSocketInodesReader {
+        fn read(&mut self, pid: Pid) -> Result<Vec<u64>, ProcfsError> {
+            self.inodes.get(&pid).cloned().ok_or(ProcfsError::UnknownPID(pid))
+        }
+    }
+
+    fn build_probe(tcp6_reader: Option<FakeSystemDataReader<NetTcp6>>) -> SocketProbe {
+        let tcp_reader = FakeSystemDataReader::from_sequence(vec![NetTcp::new(HashSet::from([10, 20]))]);
+        let inodes_reader = FakeSocketInodesReader {
+            inodes: hashmap!(1 => vec![10, 30], 2 => vec![20, 40, 50]),
+        };
+
+        SocketProbe::from_readers(
+            Box::new(tcp_reader),
+            tcp6_reader.map(|r| Box::new(r) as _),
+            Box::new(inodes_reader),
+        )
+    }
+
+    #[test]
+    fn test_should_attribute_established_sockets_to_processes() {
+        let mut probe = build_probe(None);
+
+        assert_eq!(
+            probe.probe_processes(&[1, 2]).unwrap(),
+            hashmap!(1 => CountMetric::new(1), 2 => CountMetric::new(1))
+        );
+    }
+
+    #[test]
+    fn test_should_include_ipv6_sockets() {
+        let tcp6_reader = FakeSystemDataReader::from_sequence(vec![NetTcp6::new(HashSet::from([40, 50]))]);
+        let mut probe = build_probe(Some(tcp6_reader));
+
+        assert_eq!(
+            probe.probe_processes(&[1, 2]).unwrap(),
+            hashmap!(1 => CountMetric::new(1), 2 => CountMetric::new(3))
+        );
+    }
+}
@@ -0,0 +1,519 @@
+//! Classification of the sockets of processes by transport protocol
+
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::packet_capture::{Direction, Flow};
+use crate::procfs::parsers::system::{NetTcp, NetTcp6, NetUdp, NetUdp6, SocketEndpoints};
+use crate::procfs::parsers::{ReadSystemData, SystemDataReader};
+use crate::procfs::socket_probe::{FdSocketInodesReader, ReadSocketInodes};
+
+/// The transport protocols by which sockets are classified
+#[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+/// The bytes received and sent through a socket, or through the sockets of a process
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Default)]
+pub struct SocketTraffic {
+    pub received: usize,
+    pub sent: usize,
+}
+
+/// The sockets bound to a local port, along with their remote endpoint
+type BoundSockets = Vec<(SocketAddr, u64)>;
+
+/// Classifies the sockets of processes as TCP or UDP sockets
+///
+/// The inodes of the sockets opened by each process, as listed in `/proc/[pid]/fd`, are looked up in the socket
+/// tables of each protocol, from `/proc/net/tcp`, `/proc/net/udp` and their IPv6 counterparts.
+pub struct SocketProtocols {
+    tcp_reader: Box<dyn ReadSystemData<NetTcp>>,
+    tcp6_reader: Option<Box<dyn ReadSystemData<NetTcp6>>>,
+    udp_reader: Box<dyn ReadSystemData<NetUdp>>,
+    udp6_reader: Option<Box<dyn ReadSystemData<NetUdp6>>>,
+    inodes_reader: Box<dyn ReadSocketInodes>,
+    protocols: HashMap<u64, Protocol>,
+    /// The sockets of each protocol, by local port
+    bound_sockets: HashMap<(Protocol, u16), BoundSockets>,
+}
+
+impl SocketProtocols {
+    pub fn new() -> Result<Self, Error> {
+        let tcp_reader = SystemDataReader::new()
+            .map_err(|e| Error::ProbingError("Could not access /proc/net/tcp".to_string(), e.into()))?;
+        let udp_reader = SystemDataReader::new()
+            .map_err(|e| Error::ProbingError("Could not access /proc/net/udp".to_string(), e.into()))?;
+        // /proc/net/tcp6 and /proc/net/udp6 do not exist when IPv6 is disabled
+        let tcp6_reader = SystemDataReader::new()
+            .ok()
+            .map(|reader| Box::new(reader) as Box<dyn ReadSystemData<NetTcp6>>);
+        let udp6_reader = SystemDataReader::new()
+            .ok()
+            .map(|reader| Box::new(reader) as Box<dyn ReadSystemData<NetUdp6>>);
+
+        Ok(Self::from_readers(
+            Box::new(tcp_reader),
+            tcp6_reader,
+            Box::new(udp_reader),
+            udp6_reader,
+            Box::new(FdSocketInodesReader),
+        ))
+    }
+
+    pub(crate) fn from_readers(
+        tcp_reader: Box<dyn ReadSystemData<NetTcp>>,
+        tcp6_reader: Option<Box<dyn ReadSystemData<NetTcp6>>>,
+        udp_reader: Box<dyn ReadSystemData<NetUdp>>,
+        udp6_reader: Option<Box<dyn ReadSystemData<NetUdp6>>>,
+        inodes_reader: Box<dyn ReadSocketInodes>,
+    ) -> Self {
+        SocketProtocols {
+            tcp_reader,
+            tcp6_reader,
+            udp_reader,
+            udp6_reader,
+            inodes_reader,
+            protocols: HashMap::new(),
+            bound_sockets: HashMap::new(),
+        }
+    }
+
+    /// Reads the socket tables of each protocol again, to classify the sockets opened since the last refresh
+    pub fn refresh(&mut self) -> Result<(), Error> {
+        let mut tcp_endpoints = self
+            .tcp_reader
+            .read()
+            .map_err(|e| Error::ProbingError("Could not read TCP sockets".to_string(), e.into()))?
+            .socket_endpoints()
+            .clone();
+        if let Some(tcp6_reader) = self.tcp6_reader.as_mut() {
+            let tcp6 = tcp6_reader
+                .read()
+                .map_err(|e| Error::ProbingError("Could not read TCP6 sockets".to_string(), e.into()))?;
+            tcp_endpoints.extend(tcp6.socket_endpoints());
+        }
+
+        let mut udp_endpoints = self
+            .udp_reader
+            .read()
+            .map_err(|e| Error::ProbingError("Could not read UDP sockets".to_string(), e.into()))?
+            .socket_endpoints()
+            .clone();
+        if let Some(udp6_reader) = self.udp6_reader.as_mut() {
+            let udp6 = udp6_reader
+                .read()
+                .map_err(|e| Error::ProbingError("Could not read UDP6 sockets".to_string(), e.into()))?;
+            udp_endpoints.extend(udp6.socket_endpoints());
+        }
+
+        self.protocols = protocols_by_inode(
+            &tcp_endpoints.keys().copied().collect(),
+            &udp_endpoints.keys().copied().collect(),
+        );
+        self.bound_sockets = bind_sockets(Protocol::Tcp, &tcp_endpoints);
+        self.bound_sockets.extend(bind_sockets(Protocol::Udp, &udp_endpoints));
+
+        Ok(())
+    }
+
+    /// Returns the protocols of the sockets opened by the given process, as of the last refresh
+    pub fn process_protocols(&mut self, pid: Pid) -> Result<HashSet<Protocol>, Error> {
+        let process_inodes = self
+            .inodes_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not list sockets of PID {}", pid), e.into()))?;
+
+        Ok(classify(&process_inodes, &self.protocols))
+    }
+
+    /// Attributes the bytes exchanged by each flow to the socket which sent or received them, as of the last refresh
+    ///
+    /// The bytes of the flows whose socket is not listed anymore, or not yet, are left out.
+    pub(crate) fn attribute(&self, traffic: &HashMap<Flow, usize>) -> HashMap<u64, SocketTraffic> {
+        let mut socket_traffic: HashMap<u64, SocketTraffic> = HashMap::new();
+
+        for (flow, bytes) in traffic {
+            let Some(inode) = self
+                .bound_sockets
+                .get(&(flow.protocol, flow.local.port()))
+                .and_then(|sockets| find_socket(sockets, flow.remote))
+            else {
+                continue;
+            };
+
+            let traffic = socket_traffic.entry(inode).or_default();
+            match flow.direction {
+                Direction::Incoming => traffic.received += bytes,
+                Direction::Outgoing => traffic.sent += bytes,
+            }
+        }
+
+        socket_traffic
+    }
+
+    /// Returns the bytes exchanged through the sockets opened by the given process, summed by protocol
+    ///
+    /// # Arguments
+    ///  * `pid`: The process whose sockets to sum the traffic of
+    ///  * `socket_traffic`: The traffic of each socket of the system, by inode
+    pub(crate) fn process_traffic(
+        &mut self,
+        pid: Pid,
+        socket_traffic: &HashMap<u64, SocketTraffic>,
+    ) -> Result<HashMap<Protocol, SocketTraffic>, Error> {
+        let process_inodes = self
+            .inodes_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not list sockets of PID {}", pid), e.into()))?;
+
+        Ok(traffic_by_protocol(&process_inodes, &self.protocols, socket_traffic))
+    }
+}
+
+/// Maps the inode of each socket of the system to its protocol
+///
+/// # Arguments
+///  * `tcp_inodes`: The inodes of the IPv4 and IPv6 TCP sockets
+///  * `udp_inodes`: The inodes of the IPv4 and IPv6 UDP sockets
+fn protocols_by_inode(tcp_inodes: &HashSet<u64>, udp_inodes: &HashSet<u64>) -> HashMap<u64, Protocol> {
+    let tcp = tcp_inodes.iter().map(|inode| (*inode, Protocol::Tcp));
+    let udp = udp_inodes.iter().map(|inode| (*inode, Protocol::Udp));
+
+    tcp.chain(udp).collect()
+}
+
+/// Returns the protocols of the given sockets, leaving out the sockets of other protocols (e.g. UNIX sockets)
+///
+/// # Arguments
+///  * `process_inodes`: The inodes of the sockets opened by a process
+///  * `protocols`: The protocol of each socket of the system, by inode
+fn classify(process_inodes: &[u64], protocols: &HashMap<u64, Protocol>) -> HashSet<Protocol> {
+    process_inodes
+        .iter()
+        .filter_map(|inode| protocols.get(inode).copied())
+        .collect()
+}
+
+/// Groups the sockets of a protocol by the port they are bound to
+///
+/// Sockets are looked up by their local port rather than by their local address, as the sockets bound to all the
+/// addresses of the host (e.g. `0.0.0.0`) exchange packets through the addresses of each interface.
+fn bind_sockets(
+    protocol: Protocol,
+    endpoints: &HashMap<u64, SocketEndpoints>,
+) -> HashMap<(Protocol, u16), BoundSockets> {
+    let mut bound_sockets: HashMap<(Protocol, u16), BoundSockets> = HashMap::new();
+
+    for (inode, endpoints) in endpoints {
+        bound_sockets
+            .entry((protocol, endpoints.local.port()))
+            .or_default()
+            .push((canonical(endpoints.remote), *inode));
+    }
+
+    bound_sockets
+}
+
+/// Finds the socket exchanging packets with the given remote endpoint among the sockets bound to a port
+///
+/// A socket connected to the remote endpoint is preferred to a socket exchanging packets with any remote endpoint,
+/// such as a TCP socket listening for connections or an unconnected UDP socket.
+fn find_socket(bound_sockets: &[(SocketAddr, u64)], remote: SocketAddr) -> Option<u64> {
+    let remote = canonical(remote);
+
+    bound_sockets
+        .iter()
+        .find(|(socket_remote, _)| *socket_remote == remote)
+        .or_else(|| {
+            bound_sockets
+                .iter()
+                .find(|(socket_remote, _)| socket_remote.port() == 0)
+        })
+        .map(|(_, inode)| *inode)
+}
+
+/// Converts the IPv4-mapped IPv6 addresses (e.g. `::ffff:10.0.2.15`), through which IPv6 sockets exchange IPv4
+/// packets, to IPv4 addresses
+fn canonical(address: SocketAddr) -> SocketAddr {
+    SocketAddr::new(address.ip().to_canonical(), address.port())
+}
+
+/// Sums the traffic of the given sockets by protocol, leaving out the sockets of other protocols
+///
+/// # Arguments
+///  * `process_inodes`: The inodes of the sockets opened by a process
+///  * `protocols`: The protocol of each socket of the system, by inode
+///  * `socket_traffic`: The traffic of each socket of the system, by inode
+fn traffic_by_protocol(
+    process_inodes: &[u64],
+    protocols: &HashMap<u64, Protocol>,
+    socket_traffic: &HashMap<u64, SocketTraffic>,
+) -> HashMap<Protocol, SocketTraffic> {
+    let mut totals: HashMap<Protocol, SocketTraffic> = HashMap::new();
+
+    for inode in process_inodes {
+        if let (Some(protocol), Some(traffic)) = (protocols.get(inode), socket_traffic.get(inode)) {
+            let total = totals.entry(*protocol).or_default();
+            total.received += traffic.received;
+            total.sent += traffic.sent;
+        }
+    }
+
+    totals
+}
+
+#[cfg(test)]
+mod test_classify {
+    use std::collections::{HashMap, HashSet};
+
+    use crate::procfs::socket_protocols::{classify, protocols_by_inode, traffic_by_protocol, Protocol, SocketTraffic};
+
+    #[test]
+    fn test_should_classify_sockets_by_the_table_listing_them() {
+        let protocols = protocols_by_inode(&HashSet::from([10, 20]), &HashSet::from([30]));
+
+        assert_eq!(
+            classify(&[10, 30], &protocols),
+            HashSet::from([Protocol::Tcp, Protocol::Udp])
+        );
+        assert_eq!(classify(&[20], &protocols), HashSet::from([Protocol::Tcp]));
+    }
+
+    #[test]
+    fn test_should_ignore_sockets_of_other_protocols() {
+        let protocols = protocols_by_inode(&HashSet::from([10]), &HashSet::from([30]));
+
+        assert!(classify(&[40, 50], &protocols).is_empty());
+    }
+
+    #[test]
+    fn test_should_sum_traffic_of_sockets_by_protocol() {
+        let protocols = protocols_by_inode(&HashSet::from([10, 20]), &HashSet::from([30]));
+        let socket_traffic = hashmap!(
+            10 => SocketTraffic { received: 1, sent: 2 },
+            20 => SocketTraffic { received: 10, sent: 20 },
+            30 => SocketTraffic { received: 100, sent: 200 }
+        );
+
+        assert_eq!(
+            traffic_by_protocol(&[10, 20, 30, 40], &protocols, &socket_traffic),
+            hashmap!(
+                Protocol::Tcp => SocketTraffic { received: 11, sent: 22 },
+                Protocol::Udp => SocketTraffic { received: 100, sent: 200 }
+            )
+        );
+    }
+
+    #[test]
+    fn test_should_sum_no_traffic_without_socket_of_known_protocol() {
+        let protocols = protocols_by_inode(&HashSet::from([10]), &HashSet::new());
+        let socket_traffic = hashmap!(10 => SocketTraffic { received: 1, sent: 2 });
+
+        assert_eq!(traffic_by_protocol(&[40], &protocols, &socket_traffic), HashMap::new());
+    }
+}
+
+#[cfg(test)]
+mod test_find_socket {
+    use rstest::*;
+
+    use crate::procfs::socket_protocols::find_socket;
+
+    fn bound_sockets() -> Vec<(std::net::SocketAddr, u64)> {
+        vec![
+            ("0.0.0.0:0".parse().unwrap(), 10),
+            ("172.26.250.142:41652".parse().unwrap(), 20),
+            ("10.0.2.2:51000".parse().unwrap(), 30),
+        ]
+    }
+
+    #[rstest]
+    #[case("172.26.250.142:41652", Some(20))]
+    #[case("[::ffff:10.0.2.2]:51000", Some(30))]
+    #[case("10.0.2.2:51001", Some(10))]
+    fn test_should_prefer_socket_connected_to_remote_endpoint(#[case] remote: &str, #[case] expected: Option<u64>) {
+        assert_eq!(find_socket(&bound_sockets(), remote.parse().unwrap()), expected);
+    }
+
+    #[test]
+    fn test_should_find_no_socket_without_socket_exchanging_with_remote_endpoint() {
+        let bound_sockets = vec![("172.26.250.142:443".parse().unwrap(), 20)];
+
+        assert_eq!(find_socket(&bound_sockets, "10.0.2.2:443".parse().unwrap()), None);
+    }
+}
+
+#[cfg(test)]
+mod test_socket_protocols {
+    use std::collections::{HashMap, HashSet};
+
+    use crate::core::process::Pid;
+    use crate::procfs::packet_capture::{Direction, Flow};
+    use crate::procfs::parsers::fakes::FakeSystemDataReader;
+    use crate::procfs::parsers::system::{NetTcp, NetTcp6, NetUdp, NetUdp6, SocketEndpoints};
+    use crate::procfs::socket_probe::ReadSocketInodes;
+    use crate::procfs::socket_protocols::{Protocol, SocketProtocols, SocketTraffic};
+    use crate::procfs::ProcfsError;
+
+    struct FakeSocketInodesReader {
+        inodes: HashMap<Pid, Vec<u64>>,
+    }
+
+    impl ReadSocketInodes for FakeSocketInodesReader {
+        fn read(&mut self, pid: Pid) -> Result<Vec<u64>, ProcfsError> {
+            self.inodes.get(&pid).cloned().ok_or(ProcfsError::UnknownPID(pid))
+        }
+    }
+
+    fn endpoints(local: &str, remote: &str) -> SocketEndpoints {
+        SocketEndpoints {
+            local: local.parse().unwrap(),
+            remote: remote.parse().unwrap(),
+        }
+    }
+
+    fn flow(protocol: Protocol, direction: Direction, local: &str, remote: &str) -> Flow {
+        Flow {
+            protocol,
+            direction,
+            local: local.parse().unwrap(),
+            remote: remote.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_should_classify_ipv4_and_ipv6_sockets_of_processes() {
+        let inodes_reader = FakeSocketInodesReader {
+            inodes: hashmap!(1 => vec![10, 40], 2 => vec![20], 3 => vec![30, 99]),
+        };
+        let mut protocols = SocketProtocols::from_readers(
+            Box::new(FakeSystemDataReader::from_sequence(vec![NetTcp::new(HashSet::from([
+                10,
+            ]))])),
+            Some(Box::new(FakeSystemDataReader::from_sequence(vec![NetTcp6::new(
+                HashSet::from([20]),
+            )]))),
+            Box::new(FakeSystemDataReader::from_sequence(vec![NetUdp::new(HashSet::from([
+                30,
+            ]))])),
+            Some(Box::new(FakeSystemDataReader::from_sequence(vec![NetUdp6::new(
+                HashSet::from([40]),
+            )]))),
+            Box::new(inodes_reader),
+        );
+
+        protocols.refresh().unwrap();
+
+        assert_eq!(
+            protocols.process_protocols(1).unwrap(),
+            HashSet::from([Protocol::Tcp, Protocol::Udp])
+        );
+        assert_eq!(protocols.process_protocols(2).unwrap(), HashSet::from([Protocol::Tcp]));
+        assert_eq!(protocols.process_protocols(3).unwrap(), HashSet::from([Protocol::Udp]));
+    }
+
+    #[test]
+    fn test_should_classify_sockets_without_ipv6_tables() {
+        let inodes_reader = FakeSocketInodesReader {
+            inodes: hashmap!(1 => vec![10, 30]),
+        };
+        let mut protocols = SocketProtocols::from_readers(
+            Box::new(FakeSystemDataReader::from_sequence(vec![NetTcp::new(HashSet::from([
+                10,
+            ]))])),
+            None,
+            Box::new(FakeSystemDataReader::from_sequence(vec![NetUdp::new(HashSet::from([
+                30,
+            ]))])),
+            None,
+            Box::new(inodes_reader),
+        );
+
+        protocols.refresh().unwrap();
+
+        assert_eq!(
+            protocols.process_protocols(1).unwrap(),
+            HashSet::from([Protocol::Tcp, Protocol::Udp])
+        );
+    }
+
+    #[test]
+    fn test_should_attribute_traffic_of_flows_to_sockets_of_their_protocol() {
+        let mut protocols = SocketProtocols::from_readers(
+            Box::new(FakeSystemDataReader::from_sequence(vec![
+                NetTcp::with_socket_endpoints(hashmap!(10 => endpoints("10.0.2.15:41652", "172.26.250.142:443"))),
+            ])),
+            Some(Box::new(FakeSystemDataReader::from_sequence(vec![
+                NetTcp6::with_socket_endpoints(hashmap!(20 => endpoints("[::]:8080", "[::]:0"))),
+            ]))),
+            Box::new(FakeSystemDataReader::from_sequence(vec![
+                NetUdp::with_socket_endpoints(hashmap!(30 => endpoints("0.0.0.0:443", "0.0.0.0:0"))),
+            ])),
+            Some(Box::new(FakeSystemDataReader::from_sequence(vec![
+                NetUdp6::with_socket_endpoints(HashMap::new()),
+            ]))),
+            Box::new(FakeSocketInodesReader { inodes: HashMap::new() }),
+        );
+        let traffic = hashmap!(
+            flow(Protocol::Tcp, Direction::Outgoing, "10.0.2.15:41652", "172.26.250.142:443") => 100,
+            flow(Protocol::Tcp, Direction::Incoming, "10.0.2.15:41652", "172.26.250.142:443") => 1000,
+            flow(Protocol::Tcp, Direction::Incoming, "[2001:db8::1]:8080", "[2001:db8::2]:51000") => 60,
+            flow(Protocol::Udp, Direction::Incoming, "10.0.2.15:443", "172.26.250.142:443") => 1200,
+            flow(Protocol::Udp, Direction::Outgoing, "10.0.2.15:53", "10.0.2.3:53") => 80
+        );
+
+        protocols.refresh().unwrap();
+
+        assert_eq!(
+            protocols.attribute(&traffic),
+            hashmap!(
+                10 => SocketTraffic { received: 1000, sent: 100 },
+                20 => SocketTraffic { received: 60, sent: 0 },
+                30 => SocketTraffic { received: 1200, sent: 0 }
+            )
+        );
+    }
+
+    #[test]
+    fn test_should_sum_traffic_of_ipv4_and_ipv6_sockets_of_processes() {
+        let inodes_reader = FakeSocketInodesReader {
+            inodes: hashmap!(1 => vec![10, 20, 30, 40]),
+        };
+        let mut protocols = SocketProtocols::from_readers(
+            Box::new(FakeSystemDataReader::from_sequence(vec![NetTcp::new(HashSet::from([
+                10,
+            ]))])),
+            Some(Box::new(FakeSystemDataReader::from_sequence(vec![NetTcp6::new(
+                HashSet::from([20]),
+            )]))),
+            Box::new(FakeSystemDataReader::from_sequence(vec![NetUdp::new(HashSet::from([
+                30,
+            ]))])),
+            Some(Box::new(FakeSystemDataReader::from_sequence(vec![NetUdp6::new(
+                HashSet::from([40]),
+            )]))),
+            Box::new(inodes_reader),
+        );
+        let socket_traffic = hashmap!(
+            10 => SocketTraffic { received: 1, sent: 2 },
+            20 => SocketTraffic { received: 3, sent: 4 },
+            30 => SocketTraffic { received: 5, sent: 0 },
+            40 => SocketTraffic { received: 7, sent: 0 }
+        );
+
+        protocols.refresh().unwrap();
+
+        assert_eq!(
+            protocols.process_traffic(1, &socket_traffic).unwrap(),
+            hashmap!(
+                Protocol::Tcp => SocketTraffic { received: 4, sent: 6 },
+                Protocol::Udp => SocketTraffic { received: 12, sent: 0 }
+            )
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! TCP sockets states probing
+
+use std::collections::HashMap;
+
+use crate::core::metrics::SocketStatesMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::parsers::system::{NetTcp, NetTcp6, TcpState};
+use crate::procfs::parsers::{ReadSystemData, SystemDataReader};
+use crate::procfs::socket_probe::{FdSocketInodesReader, ReadSocketInodes};
+
+/// Probe implementation to count the TCP sockets of processes in the ESTABLISHED, TIME_WAIT and CLOSE_WAIT states
+///
+/// A process whose sockets pile up in the CLOSE_WAIT state does not close the connections closed by their peer, which
+/// is a typical connection leak.
+pub struct SocketStatesProbe {
+    tcp_reader: Box<dyn ReadSystemData<NetTcp>>,
+    tcp6_reader: Option<Box<dyn ReadSystemData<NetTcp6>>>,
+    inodes_reader: Box<dyn ReadSocketInodes>,
+    socket_states: HashMap<u64, TcpState>,
+}
+
+impl SocketStatesProbe {
+    /// Creates a new probe that can count the sockets of processes by state
+    pub fn new() -> Result<Self, Error> {
+        let tcp_reader = SystemDataReader::new()
+            .map_err(|e| Error::ProbingError("Could not access /proc/net/tcp".to_string(), e.into()))?;
+        // /proc/net/tcp6 does not exist when IPv6 is disabled
+        let tcp6_reader = SystemDataReader::new()
+            .ok()
+            .map(|reader| Box::new(reader) as Box<dyn ReadSystemData<NetTcp6>>);
+
+        Ok(Self::from_readers(
+            Box::new(tcp_reader),
+            tcp6_reader,
+            Box::new(FdSocketInodesReader),
+        ))
+    }
+
+    fn from_readers(
+        tcp_reader: Box<dyn ReadSystemData<NetTcp>>,
+        tcp6_reader: Option<Box<dyn ReadSystemData<NetTcp6>>>,
+        inodes_reader: Box<dyn ReadSocketInodes>,
+    ) -> Self {
+        SocketStatesProbe {
+            tcp_reader,
+            tcp6_reader,
+            inodes_reader,
+            socket_states: HashMap::new(),
+        }
+    }
+}
+
+impl Probe<SocketStatesMetric> for SocketStatesProbe {
+    fn name(&self) -> &'static str {
+        "Socket states"
+    }
+
+    fn init_iteration(&mut self) -> Result<(), Error> {
+        let tcp = self
+            .tcp_reader
+            .read()
+            .map_err(|e| Error::ProbingError("Could not read TCP sockets".to_string(), e.into()))?;
+        self.socket_states = tcp.socket_states().clone();
+
+        if let Some(tcp6_reader) = self.tcp6_reader.as_mut() {
+            let tcp6 = tcp6_reader
+                .read()
+                .map_err(|e| Error::ProbingError("Could not read TCP6 sockets".to_string(), e.into()))?;
+            self.socket_states.extend(tcp6.socket_states());
+        }
+
+        Ok(())
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<SocketStatesMetric, Error> {
+        let process_inodes = self
+            .inodes_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not list sockets of PID {}", pid), e.into()))?;
+
+        Ok(count_by_state(&process_inodes, &self.socket_states))
+    }
+
+    fn cleanup(&mut self, _pids: &[Pid]) {
+        // Nothing to do, as no file is kept open between iterations
+    }
+}
+
+/// Counts the sockets of a process in each tracked state
+///
+/// # Arguments
+///  * `process_inodes`: The inodes of the sockets opened by the process
+///  * `socket_states`: The states of all TCP sockets of the system, by inode
+fn count_by_state(process_inodes: &[u64], socket_states: &HashMap<u64, TcpState>) -> SocketStatesMetric {
+    let (mut established, mut time_wait, mut close_wait) = (0, 0, 0);
+
+    for state in process_inodes.iter().filter_map(|inode| socket_states.get(inode)) {
+        match state {
+            TcpState::Established => established += 1,
+            TcpState::TimeWait => time_wait += 1,
+            TcpState::CloseWait => close_wait += 1,
+            TcpState::Other => {}
+        }
+    }
+
+    SocketStatesMetric::new(established, time_wait, close_wait)
+}
+
+#[cfg(test)]
+mod test_count_by_state {
+    use std::collections::HashMap;
+
+    use crate::core::metrics::SocketStatesMetric;
+    use crate::procfs::parsers::system::TcpState;
+    use crate::procfs::socket_states_probe::count_by_state;
+
+    fn socket_states() -> HashMap<u64, TcpState> {
+        hashmap!(
+            10 => TcpState::Established,
+            20 => TcpState::Established,
+            30 => TcpState::TimeWait,
+            40 => TcpState::CloseWait,
+            50 => TcpState::CloseWait,
+            60 => TcpState::Other
+        )
+    }
+
+    #[test]
+    fn test_should_count_sockets_of_process_in_each_state() {
+        let metric = count_by_state(&[10, 30, 40, 50], &socket_states());
+
+        assert_eq!(metric, SocketStatesMetric::new(1, 1, 2));
+    }
+
+    #[test]
+    fn test_should_ignore_untracked_states_and_unknown_sockets() {
+        let metric = count_by_state(&[60, 70], &socket_states());
+
+        assert_eq!(metric, SocketStatesMetric::default());
+    }
+}
+
+#[cfg(test)]
+mod test_socket_states_probe {
+    use std::collections::HashMap;
+
+    use crate::core::metrics::SocketStatesMetric;
+    use crate::core::probe::Probe;
+    use crate::core::process::Pid;
+    use crate::procfs::parsers::fakes::FakeSystemDataReader;
+    use crate::procfs::parsers::system::{NetTcp, NetTcp6, TcpState};
+    use crate::procfs::socket_probe::ReadSocketInodes;
+    use crate::procfs::socket_states_probe::SocketStatesProbe;
+    use crate::procfs::ProcfsError;
+
+    struct FakeSocketInodesReader {
+        inodes: HashMap<Pid, Vec<u64>>,
+    }
+
+    impl ReadSocketInodes for FakeSocketInodesReader {
+        fn read(&mut self, pid: Pid) -> Result<Vec<u64>, ProcfsError> {
+            self.inodes.get(&pid).cloned().ok_or(ProcfsError::UnknownPID(pid))
+        }
+    }
+
+    #[test]
+    fn test_should_attribute_ipv4_and_ipv6_socket_states_to_processes() {
+        let tcp = NetTcp::with_socket_states(hashmap!(10 => TcpState::Established, 20 => TcpState::CloseWait));
+        let tcp6 = NetTcp6::with_socket_states(hashmap!(30 => TcpState::TimeWait, 40 => TcpState::CloseWait));
+        let inodes_reader = FakeSocketInodesReader {
+            inodes: hashmap!(1 => vec![10, 40], 2 => vec![20, 30]),
+        };
+
+        let mut probe = SocketStatesProbe::from_readers(
+            Box::new(FakeSystemDataReader::from_sequence(vec![tcp])),
+            Some(Box::new(FakeSystemDataReader::from_sequence(vec![tcp6]))),
+            Box::new(inodes_reader),
+        );
+
+        assert_eq!(
+            probe.probe_processes(&[1, 2]).unwrap(),
+            hashmap!(1 => SocketStatesMetric::new(1, 0, 1), 2 => SocketStatesMetric::new(0, 1, 1))
+        );
+    }
+}
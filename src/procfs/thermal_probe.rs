@@ -0,0 +1,196 @@
+//! CPU temperature probing
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::metrics::TemperatureMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::ProcfsError;
+
+/// The directory where the kernel exposes the thermal zones of the system
+const THERMAL_ROOT: &str = "/sys/class/thermal";
+
+/// Probe implementation to measure the temperature of the system, as the highest temperature of its thermal zones
+///
+/// The temperature is a system-wide metric: the same temperature is reported for all processes, so that it can be
+/// charted whichever process is selected.
+pub struct ThermalProbe {
+    zone_files: Vec<PathBuf>,
+    celsius: f64,
+}
+
+impl ThermalProbe {
+    /// Creates a new probe that can measure the temperature of the system
+    ///
+    /// Fails if the system has no thermal zone, which is often the case of servers and virtual machines.
+    pub fn new() -> Result<Self, Error> {
+        let zone_files = list_zone_files(Path::new(THERMAL_ROOT))
+            .map_err(|e| Error::ProbingError("Could not list thermal zones".to_string(), e.into()))?;
+
+        if zone_files.is_empty() {
+            let e = ProcfsError::InvalidFileFormat(THERMAL_ROOT.to_string());
+            return Err(Error::ProbingError(
+                "No thermal zone is available".to_string(),
+                e.into(),
+            ));
+        }
+
+        Ok(Self::from_zone_files(zone_files))
+    }
+
+    fn from_zone_files(zone_files: Vec<PathBuf>) -> Self {
+        ThermalProbe {
+            zone_files,
+            celsius: 0.,
+        }
+    }
+}
+
+impl Probe<TemperatureMetric> for ThermalProbe {
+    fn name(&self) -> &'static str {
+        "Temperature"
+    }
+
+    fn init_iteration(&mut self) -> Result<(), Error> {
+        // Some zones can not be read, for instance when their sensor is disabled
+        let temperatures = self
+            .zone_files
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .filter_map(|content| parse_millidegrees(&content).ok());
+
+        self.celsius = max_temperature(temperatures).ok_or_else(|| {
+            let e = ProcfsError::InvalidFileContent("no thermal zone could be read".to_string());
+            Error::ProbingError("Could not read temperature".to_string(), e.into())
+        })?;
+
+        Ok(())
+    }
+
+    fn probe(&mut self, _pid: Pid) -> Result<TemperatureMetric, Error> {
+        Ok(TemperatureMetric::new(self.celsius))
+    }
+
+    fn cleanup(&mut self, _pids: &[Pid]) {
+        // Nothing to do, as no data is kept per process
+    }
+}
+
+/// Lists the `temp` files of the thermal zones found in `thermal_root`, ordered by zone
+fn list_zone_files(thermal_root: &Path) -> Result<Vec<PathBuf>, ProcfsError> {
+    let mut zone_files: Vec<PathBuf> = fs::read_dir(thermal_root)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("thermal_zone"))
+        .map(|entry| entry.path().join("temp"))
+        .filter(|path| path.is_file())
+        .collect();
+
+    zone_files.sort();
+    Ok(zone_files)
+}
+
+/// Parses the content of a `temp` file of a thermal zone, expressed in millidegrees Celsius, into degrees Celsius
+fn parse_millidegrees(content: &str) -> Result<f64, ProcfsError> {
+    let millidegrees: i64 = content
+        .trim()
+        .parse()
+        .map_err(|_| ProcfsError::InvalidFileContent(content.to_string()))?;
+
+    Ok(millidegrees as f64 / 1000.)
+}
+
+/// Returns the highest of the given temperatures, or `None` if there is none
+fn max_temperature<I: IntoIterator<Item = f64>>(temperatures: I) -> Option<f64> {
+    temperatures.into_iter().reduce(f64::max)
+}
+
+#[cfg(test)]
+mod test_temperature_parsing {
+    use rstest::*;
+
+    use crate::procfs::thermal_probe::{max_temperature, parse_millidegrees};
+
+    #[rstest]
+    #[case("45000\n", 45.)]
+    #[case("37500", 37.5)]
+    #[case("-5000\n", -5.)]
+    fn test_should_parse_millidegrees_into_degrees(#[case] content: &str, #[case] expected: f64) {
+        assert_eq!(parse_millidegrees(content).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("hot")]
+    #[case("45.5")]
+    fn test_should_reject_invalid_temperature(#[case] content: &str) {
+        assert!(parse_millidegrees(content).is_err());
+    }
+
+    #[test]
+    fn test_should_take_highest_temperature_across_zones() {
+        assert_eq!(max_temperature(vec![45., 62.5, 38.]), Some(62.5));
+    }
+
+    #[test]
+    fn test_should_have_no_temperature_without_zone() {
+        assert_eq!(max_temperature(vec![]), None);
+    }
+}
+
+#[cfg(test)]
+mod test_thermal_probe {
+    use std::fs;
+    use std::path::Path;
+
+    use tempfile::tempdir;
+
+    use crate::core::metrics::TemperatureMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::thermal_probe::{list_zone_files, ThermalProbe};
+
+    fn create_zone(thermal_root: &Path, zone: &str, temp: &str) {
+        let zone_dir = thermal_root.join(zone);
+        fs::create_dir(&zone_dir).expect("Could not create zone dir");
+        fs::write(zone_dir.join("temp"), temp).expect("Could not write temp file");
+    }
+
+    #[test]
+    fn test_should_only_list_thermal_zones() {
+        let thermal_root = tempdir().expect("Could not create tmp dir");
+        create_zone(thermal_root.path(), "thermal_zone1", "50000");
+        create_zone(thermal_root.path(), "thermal_zone0", "40000");
+        create_zone(thermal_root.path(), "cooling_device0", "0");
+
+        assert_eq!(
+            list_zone_files(thermal_root.path()).unwrap(),
+            vec![
+                thermal_root.path().join("thermal_zone0/temp"),
+                thermal_root.path().join("thermal_zone1/temp")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_should_report_highest_temperature_of_readable_zones_for_all_processes() {
+        let thermal_root = tempdir().expect("Could not create tmp dir");
+        create_zone(thermal_root.path(), "thermal_zone0", "40000\n");
+        create_zone(thermal_root.path(), "thermal_zone1", "62500\n");
+        create_zone(thermal_root.path(), "thermal_zone2", "disabled");
+
+        let mut probe = ThermalProbe::from_zone_files(list_zone_files(thermal_root.path()).unwrap());
+
+        assert_eq!(
+            probe.probe_processes(&[1, 2]).unwrap(),
+            hashmap!(1 => TemperatureMetric::new(62.5), 2 => TemperatureMetric::new(62.5))
+        );
+    }
+
+    #[test]
+    fn test_should_fail_when_no_zone_can_be_read() {
+        let mut probe = ThermalProbe::from_zone_files(vec![]);
+
+        assert!(probe.probe_processes(&[1]).is_err());
+    }
+}
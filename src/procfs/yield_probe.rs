@@ -0,0 +1,179 @@
+//! Voluntary CPU yield ratio probing
+
+use std::collections::HashMap;
+
+use crate::core::metrics::RateMetric;
+use crate::core::probe::Probe;
+use crate::core::process::Pid;
+use crate::core::Error;
+use crate::procfs::libc::clock_ticks;
+use crate::procfs::parsers::process::{PidStat, PidStatus};
+use crate::procfs::parsers::{ProcessDataReader, ReadProcessData};
+
+/// Probe implementation to measure how often processes voluntarily yield the CPU, per second of CPU time they consume
+///
+/// A process waiting for I/O or for a lock gives up the CPU often, while a process busy-waiting keeps the CPU until the
+/// scheduler preempts it. A busy process with a low yield ratio is then likely to be busy-waiting.
+pub struct YieldProbe {
+    pid_stat_reader: Box<dyn ReadProcessData<PidStat>>,
+    pid_status_reader: Box<dyn ReadProcessData<PidStatus>>,
+    clock_ticks: u64,
+    processes_prev_counters: HashMap<Pid, YieldCounters>,
+}
+
+/// The cumulated counters of a process, from which its yield ratio is computed
+#[derive(Copy, Clone)]
+struct YieldCounters {
+    voluntary_switches: u64,
+    running_ticks: i64,
+}
+
+impl YieldProbe {
+    /// Creates a new probe that can measure the voluntary CPU yield ratio of processes
+    ///
+    /// # Arguments
+    ///  * `fd_limit`: Indicates how many files descriptor the probe should keep open at most
+    pub fn new(fd_limit: usize) -> Result<Self, Error> {
+        let clock_ticks =
+            clock_ticks().map_err(|e| Error::ProbingError("Could not read clock ticks".to_string(), e.into()))?;
+
+        // Each process has two files to read, so each reader can only keep half of the allowed files open
+        Ok(Self::from_readers(
+            Box::new(ProcessDataReader::with_capacity(fd_limit / 2)),
+            Box::new(ProcessDataReader::with_capacity(fd_limit / 2)),
+            clock_ticks,
+        ))
+    }
+
+    fn from_readers(
+        pid_stat_reader: Box<dyn ReadProcessData<PidStat>>,
+        pid_status_reader: Box<dyn ReadProcessData<PidStatus>>,
+        clock_ticks: u64,
+    ) -> Self {
+        YieldProbe {
+            pid_stat_reader,
+            pid_status_reader,
+            clock_ticks,
+            processes_prev_counters: HashMap::new(),
+        }
+    }
+}
+
+impl Probe<RateMetric> for YieldProbe {
+    fn name(&self) -> &'static str {
+        "CPU yields"
+    }
+
+    fn probe(&mut self, pid: Pid) -> Result<RateMetric, Error> {
+        let pid_stat = self
+            .pid_stat_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read process CPU stats for PID {}", pid), e.into()))?;
+        let pid_status = self
+            .pid_status_reader
+            .read(pid)
+            .map_err(|e| Error::ProbingError(format!("Could not read process status for PID {}", pid), e.into()))?;
+
+        let counters = YieldCounters {
+            voluntary_switches: pid_status.voluntary_ctxt_switches(),
+            running_ticks: pid_stat.running_time(),
+        };
+
+        let ratio = match self.processes_prev_counters.insert(pid, counters) {
+            Some(prev_counters) => yield_ratio(prev_counters, counters, self.clock_ticks),
+            None => 0.,
+        };
+
+        Ok(RateMetric::new(ratio))
+    }
+
+    fn cleanup(&mut self, pids: &[Pid]) {
+        pids.iter().for_each(|pid| {
+            self.pid_stat_reader.cleanup(*pid);
+            self.pid_status_reader.cleanup(*pid);
+            self.processes_prev_counters.remove(pid);
+        });
+    }
+}
+
+/// Calculates the amount of voluntary context switches per second of CPU time between two readings of the counters
+///
+/// If the process did not consume any CPU time, its ratio is 0, as it can not be busy-waiting.
+///
+/// # Arguments
+///  * `prev`, `cur`: The previous and current counters of the process
+///  * `clock_ticks`: The amount of clock ticks per second
+fn yield_ratio(prev: YieldCounters, cur: YieldCounters, clock_ticks: u64) -> f64 {
+    let running_ticks = cur.running_ticks - prev.running_ticks;
+    if running_ticks <= 0 || clock_ticks == 0 {
+        return 0.;
+    }
+
+    let switches = cur.voluntary_switches.saturating_sub(prev.voluntary_switches);
+    let running_secs = running_ticks as f64 / clock_ticks as f64;
+
+    switches as f64 / running_secs
+}
+
+#[cfg(test)]
+mod test_yield_ratio {
+    use rstest::*;
+
+    use crate::procfs::yield_probe::{yield_ratio, YieldCounters};
+
+    fn counters(voluntary_switches: u64, running_ticks: i64) -> YieldCounters {
+        YieldCounters {
+            voluntary_switches,
+            running_ticks,
+        }
+    }
+
+    #[rstest]
+    #[case(counters(0, 0), counters(500, 100), 500.)]
+    #[case(counters(100, 50), counters(150, 100), 100.)]
+    #[case(counters(10, 0), counters(10, 200), 0.)]
+    #[case(counters(0, 0), counters(25, 25), 100.)]
+    fn test_should_divide_switches_by_cpu_seconds(
+        #[case] prev: YieldCounters,
+        #[case] cur: YieldCounters,
+        #[case] expected: f64,
+    ) {
+        assert_eq!(yield_ratio(prev, cur, 100), expected);
+    }
+
+    #[test]
+    fn test_should_be_zero_when_process_did_not_run() {
+        assert_eq!(yield_ratio(counters(0, 100), counters(50, 100), 100), 0.);
+    }
+}
+
+#[cfg(test)]
+mod test_yield_probe {
+    use crate::core::metrics::RateMetric;
+    use crate::core::probe::Probe;
+    use crate::procfs::parsers::fakes::FakeProcessDataReader;
+    use crate::procfs::parsers::process::{PidStat, PidStatus};
+    use crate::procfs::yield_probe::YieldProbe;
+
+    #[test]
+    fn test_should_combine_context_switches_and_cpu_time_of_processes() {
+        let mut pid_stat_reader = FakeProcessDataReader::new();
+        pid_stat_reader.set_pid_sequence(1, vec![PidStat::new(0, 0, 0, 0, 0), PidStat::new(150, 50, 0, 0, 0)]);
+        let mut pid_status_reader = FakeProcessDataReader::new();
+        pid_status_reader.set_pid_sequence(
+            1,
+            vec![
+                PidStatus::with_voluntary_ctxt_switches(1, 1000),
+                PidStatus::with_voluntary_ctxt_switches(1, 1600),
+            ],
+        );
+
+        let mut probe = YieldProbe::from_readers(Box::new(pid_stat_reader), Box::new(pid_status_reader), 100);
+
+        probe.probe_processes(&[1]).unwrap(); // calibrating probe
+        let metrics = probe.probe_processes(&[1]).unwrap();
+
+        // The process voluntarily yielded the CPU 600 times during 2 seconds of CPU time
+        assert_eq!(metrics, hashmap!(1 => RateMetric::new(300.)));
+    }
+}
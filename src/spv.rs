@@ -1,68 +1,359 @@
 //! Integrates all other modules to run spv
 
-use std::sync::mpsc::Receiver;
-use std::time::Duration;
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::ops::ControlFlow;
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc::{Receiver, RecvError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{fs, iter};
 
-use log::warn;
+use log::{debug, error, warn, Log};
 
-use crate::core::collection::MetricCollector;
+use crate::config::{Config, ConfigSource, ProbeKind};
+use crate::core::collection::{lacks_permissions, MetricCollector, ProbeOutcome};
+use crate::core::command::CommandTemplate;
+use crate::core::connections::ConnectionLister;
+use crate::core::devices::DeviceResolver;
+use crate::core::filesystems::FilesystemScanner;
+use crate::core::labels::ProcessLabels;
 use crate::core::ordering::sort_processes;
-use crate::core::process::{ProcessCollector, ProcessMetadata};
-use crate::core::time::refresh_current_timestamp;
-use crate::ctrl::{Controls, Effect};
-use crate::triggers::Trigger;
+use crate::core::process::{Pid, ProcessCollector, ProcessMetadata};
+use crate::core::signal::{ProcessSignal, SignalSender};
+use crate::core::summary::summarize;
+use crate::core::time::{refresh_current_timestamp, Timestamp};
+use crate::core::units::sum_by_unit;
+use crate::core::view::MetricsOverview;
+use crate::core::watch::WatchExpression;
+use crate::ctrl::preset::PresetStore;
+use crate::ctrl::processes::SortHysteresis;
+use crate::ctrl::{Controls, Effect, State};
+use crate::triggers::{Trigger, TypingMode};
 use crate::ui::SpvUI;
 use crate::Error;
 
+/// How many of the leading processes of each tab are listed by the summary
+const SUMMARY_LEADERS: usize = 3;
+/// The duration covered by each bucket of compacted metrics
+const COMPACTION_BUCKET: Duration = Duration::from_secs(10);
+
+/// The collectors of the enabled probes, along with the kind of their probe
+pub type EnabledCollectors = Vec<(ProbeKind, Box<dyn MetricCollector>)>;
+
 pub struct SpvApplication {
     receiver: Receiver<Trigger>,
     process_collector: ProcessCollector,
+    signal_sender: Box<dyn SignalSender>,
+    device_resolver: Box<dyn DeviceResolver>,
+    // Lists the connections of the selected process, when its details are displayed
+    connection_lister: Option<Box<dyn ConnectionLister>>,
+    // Measures the disk space usage of the mounted filesystems, when they are displayed
+    filesystem_scanner: Option<Box<dyn FilesystemScanner>>,
     ui: SpvUI,
     controls: Controls,
+    history: Option<Duration>,
+    // The condition highlighting the processes satisfying it
+    watch: Option<WatchExpression>,
+    // The condition pinning the processes satisfying it to the top of the list
+    pin: Option<WatchExpression>,
+    command: Option<CommandTemplate>,
+    presets: PresetStore,
+    // Tells the input listener whether the user is typing text in a prompt
+    typing_mode: TypingMode,
+    // The indices of the collectors whose metrics the watch and pin expressions refer to, along with the name of their
+    // probe
+    expression_collectors: Vec<(String, usize)>,
+    // The probe of each collector, in the order of the collectors
+    probes: Vec<ProbeKind>,
+    // Reads the configuration again when reloading it
+    config_source: Option<ConfigSource>,
+    // The configuration applied until the next successful reload
+    config: Config,
+    // The amount of collections since the application started
+    collections: usize,
+    // When the metrics were last compacted
+    last_compaction: Timestamp,
 }
 
 impl SpvApplication {
     pub fn new(
         receiver: Receiver<Trigger>,
-        collectors: Vec<Box<dyn MetricCollector>>,
+        collectors: EnabledCollectors,
         process_collector: ProcessCollector,
-        impulse_tolerance: Duration,
+        signal_sender: Box<dyn SignalSender>,
+        device_resolver: Box<dyn DeviceResolver>,
+        typing_mode: TypingMode,
+        config: &Config,
     ) -> Result<Self, Error> {
-        const DEFAULT_REPRESENTED_SPAN_DURATION: Duration = Duration::from_secs(60);
+        // Some probes may have been disabled, so that collectors are not built for all the enabled probes
+        let (probes, collectors): (Vec<ProbeKind>, Vec<_>) = collectors.into_iter().unzip();
+        let controls = Controls::new(collectors, config.span_duration(), config.history())
+            .with_min_uptime(config.min_uptime())
+            .with_sort_hysteresis(config.sort_hysteresis().map(SortHysteresis::new))
+            .with_own_process(config.own_process())
+            .with_newest_left_chart(config.newest_left());
+        let ui = SpvUI::new(config, controls.sort_criteria().to_vec(), &probes)?;
 
         Ok(Self {
             receiver,
             process_collector,
-            ui: SpvUI::new(2 * impulse_tolerance)?,
-            controls: Controls::new(collectors, DEFAULT_REPRESENTED_SPAN_DURATION),
+            signal_sender,
+            device_resolver,
+            connection_lister: None,
+            filesystem_scanner: None,
+            ui,
+            controls,
+            history: config.history(),
+            watch: config.watch().cloned(),
+            pin: config.pin().cloned(),
+            command: config.exec().cloned(),
+            presets: PresetStore::from_env(),
+            typing_mode,
+            expression_collectors: expression_collectors(config, &probes),
+            probes,
+            config_source: None,
+            config: config.clone(),
+            collections: 0,
+            last_compaction: Timestamp::app_init(),
         })
     }
 
+    /// Sets the source from which the configuration is read again when spv receives SIGHUP
+    ///
+    /// Without a source, the configuration can not be reloaded.
+    pub fn with_config_source(mut self, config_source: ConfigSource) -> Self {
+        self.config_source = Some(config_source);
+        self
+    }
+
+    /// Sets the lister of the connections of processes, which are then listed in the details of the selected process
+    pub fn with_connection_lister(mut self, connection_lister: Box<dyn ConnectionLister>) -> Self {
+        self.connection_lister = Some(connection_lister);
+        self
+    }
+
+    /// Sets the scanner measuring the disk space usage of the mounted filesystems
+    ///
+    /// Without a scanner, no filesystem is listed in the filesystem pane.
+    pub fn with_filesystem_scanner(mut self, filesystem_scanner: Box<dyn FilesystemScanner>) -> Self {
+        self.filesystem_scanner = Some(filesystem_scanner);
+        self
+    }
+
+    /// Runs the application until an exit is requested, by the user or by a signal, or until an error occurs
+    ///
+    /// Whichever the cause of the exit, the application is torn down before returning.
     pub fn run(mut self) -> Result<(), Error> {
+        let ret = self.run_until_exit();
+        teardown(self, log::logger());
+
+        ret
+    }
+
+    fn run_until_exit(&mut self) -> Result<(), Error> {
         self.calibrate_probes()?;
 
         loop {
-            let trigger = self.receiver.recv()?;
+            let triggers = receive_pending_triggers(&self.receiver)?;
+            match handle_triggers(triggers, self)? {
+                ControlFlow::Break(()) => return Ok(()),
+                ControlFlow::Continue(true) => self.draw_ui()?,
+                ControlFlow::Continue(false) => (),
+            }
+        }
+    }
+}
 
-            match trigger {
-                Trigger::Exit => break,
-                Trigger::Impulse => {
-                    self.increment_iteration();
-                    self.collect_metrics()?;
-                }
-                Trigger::Resize => (), // No need to do anything, just receiving a signal will refresh UI at the end of the loop
-                Trigger::Input(input) => {
-                    let effect = self.controls.interpret_input(input);
-                    if effect != Effect::None {
-                        self.ui.set_status_from_effect(effect);
-                    }
+impl TriggerHandler for SpvApplication {
+    fn handle_trigger(&mut self, trigger: Trigger) -> Result<ControlFlow<(), bool>, Error> {
+        match trigger {
+            Trigger::Exit => return Ok(ControlFlow::Break(())),
+            // Samples are only aggregated into the next collected metrics, so they leave the UI unchanged
+            Trigger::Sample => {
+                self.sample_metrics();
+                return Ok(ControlFlow::Continue(false));
+            }
+            // A static snapshot keeps displaying its first collection
+            Trigger::Impulse if !is_impulse_collected(self.config.static_snapshot(), self.collections) => {
+                return Ok(ControlFlow::Continue(false));
+            }
+            Trigger::Impulse => {
+                self.increment_iteration();
+                self.collect_metrics()?;
+            }
+            Trigger::Resize => (), // No need to do anything, just receiving a signal will refresh UI after the trigger
+            Trigger::Suspend => self.ui.suspend_until_continued().map_err(Error::UiError)?,
+            Trigger::Resume => self.ui.resume().map_err(Error::UiError)?,
+            Trigger::Reload => self.reload_config(),
+            Trigger::Input(input) => {
+                let effect = match self.controls.interpret_input(input) {
+                    Effect::QuitConfirmed => return Ok(ControlFlow::Break(())),
+                    Effect::StateDumpRequested => self.dump_state(),
+                    Effect::SignalRequested(signal) => self.signal_selected_process(signal),
+                    Effect::CommandRequested => self.run_command_on_selected_process()?,
+                    Effect::PresetSaveRequested(name) => self.save_preset(name),
+                    Effect::PresetSelectionRequested => self.prompt_preset_selection(),
+                    Effect::PresetLoadRequested(name) => self.load_preset(name),
+                    Effect::MetricsClearRequested(pid) => self.clear_process_metrics(pid),
+                    effect => effect,
+                };
+                if effect != Effect::None {
+                    self.ui.set_status_from_effect(effect);
                 }
+                self.typing_mode.set(self.controls.is_typing());
+            }
+            Trigger::Mouse(event) => {
+                let (column, row) = event.position();
+                let target = self.ui.locate(column, row);
+                self.controls.interpret_mouse(event, target);
             }
+        }
+
+        Ok(ControlFlow::Continue(true))
+    }
+}
+
+impl SpvApplication {
+    /// Reads the configuration again and applies it, keeping the collected metrics
+    ///
+    /// The current configuration remains applied if the new one is invalid, or changes options which can not be
+    /// applied while spv runs.
+    fn reload_config(&mut self) {
+        let Some(source) = &self.config_source else {
+            return;
+        };
 
-            self.draw_ui()?;
+        match source.reload(&self.config) {
+            Ok(config) => {
+                self.apply_config(&config);
+                self.config = config;
+            }
+            Err(e) => error!("Could not reload the configuration, keeping the current one: {}", e),
         }
+    }
 
-        Ok(())
+    /// Applies the options of the configuration which can change while spv runs
+    ///
+    /// The collectors and the process list are left as they are, so that the collected metrics are kept.
+    fn apply_config(&mut self, config: &Config) {
+        self.watch = config.watch().cloned();
+        self.pin = config.pin().cloned();
+        self.command = config.exec().cloned();
+        self.expression_collectors = expression_collectors(config, &self.probes);
+        self.controls.set_min_uptime(config.min_uptime());
+        self.controls
+            .set_sort_hysteresis(config.sort_hysteresis().map(SortHysteresis::new));
+        self.controls.set_own_process(config.own_process());
+        self.process_collector
+            .set_labels(ProcessLabels::new(config.labels().to_vec()));
+    }
+
+    /// Writes a description of the state of the application to a new file of the working directory
+    fn dump_state(&self) -> Effect {
+        let unix_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let path = PathBuf::from(format!("spv-dump-{}.txt", unix_time));
+        let processes = self.process_collector.processes();
+        let dump = self.controls.state_dump(&processes);
+
+        match fs::write(&path, dump.to_string()) {
+            Ok(()) => Effect::StateDumped(path),
+            Err(e) => {
+                error!("Could not write state dump to {}: {}", path.display(), e);
+                Effect::StateDumpFailed
+            }
+        }
+    }
+
+    /// Saves the current configuration of the view as a preset with the given name
+    fn save_preset(&self, name: String) -> Effect {
+        match self.presets.save(&name, &self.controls.preset()) {
+            Ok(()) => Effect::PresetSaved(name),
+            Err(e) => {
+                error!("Could not save preset {:?}: {}", name, e);
+                Effect::PresetSaveFailed(name)
+            }
+        }
+    }
+
+    /// Prompts the user to select one of the saved presets
+    fn prompt_preset_selection(&mut self) -> Effect {
+        match self.presets.names() {
+            Ok(names) => {
+                self.controls.prompt_preset_selection(names);
+                Effect::None
+            }
+            Err(e) => {
+                error!("Could not list the saved presets: {}", e);
+                Effect::PresetsUnavailable
+            }
+        }
+    }
+
+    /// Restores the configuration of the view saved in the preset with the given name
+    fn load_preset(&mut self, name: String) -> Effect {
+        match self.presets.load(&name) {
+            Ok(preset) => {
+                self.controls.apply_preset(&preset);
+                Effect::PresetLoaded(name)
+            }
+            Err(e) => {
+                error!("Could not load preset {:?}: {}", name, e);
+                Effect::PresetLoadFailed(name)
+            }
+        }
+    }
+
+    /// Discards the metrics collected for a process by every collector, so that its chart starts over
+    fn clear_process_metrics(&mut self, pid: Pid) -> Effect {
+        for collector in self.controls.collectors_as_mut_slice() {
+            collector.clear(pid);
+        }
+
+        Effect::MetricsCleared(pid)
+    }
+
+    /// Sends a signal to the selected process
+    fn signal_selected_process(&mut self, signal: ProcessSignal) -> Effect {
+        let processes = self.controls.to_processes_view();
+
+        match processes.selected_process() {
+            Some(process) => signal_process(self.signal_sender.as_mut(), process, signal),
+            None => Effect::None,
+        }
+    }
+
+    /// Runs the configured command on the selected process, while the UI is suspended
+    fn run_command_on_selected_process(&mut self) -> Result<Effect, Error> {
+        let Some(command) = &self.command else {
+            return Ok(Effect::CommandNotConfigured);
+        };
+        let processes = self.controls.to_processes_view();
+        let Some(process) = processes.selected_process() else {
+            return Ok(Effect::None);
+        };
+
+        let pid = process.pid();
+        let args = command.expand(process);
+        let status = self.ui.run_suspended(|| run_command(&args)).map_err(Error::UiError)?;
+
+        // The keys and signals received while the command ran, such as Ctrl-C, were meant for the command
+        self.receiver.try_iter().for_each(drop);
+
+        match status {
+            Ok(status) if status.success() => Ok(Effect::None),
+            Ok(status) => {
+                warn!("Command {:?} run on process {} failed: {}", args, pid, status);
+                Ok(Effect::CommandFailed(pid))
+            }
+            Err(e) => {
+                error!("Could not run command {:?} on process {}: {}", args, pid, e);
+                Ok(Effect::CommandFailed(pid))
+            }
+        }
     }
 
     fn increment_iteration(&mut self) {
@@ -85,23 +376,119 @@ impl SpvApplication {
         self.scan_processes()?;
         let running_pids = self.process_collector.running_pids();
 
-        for collector in self.controls.collectors_as_mut_slice() {
-            collector.collect(&running_pids).unwrap_or_else(|e| {
-                warn!("Error reading from collector {}: {}", collector.name(), e.to_string());
-            });
+        collect_due_metrics(self.controls.collectors_as_mut_slice(), &running_pids, self.collections);
+        if self.collections == 0 {
+            self.check_permissions();
         }
+        self.collections += 1;
+
+        self.purge_metrics();
+        self.compact_metrics();
 
         let mut exposed_processes = self.represented_processes();
         sort_processes(
             &mut exposed_processes,
             self.controls.process_ordering_criteria(),
-            self.controls.current_collector(),
+            self.controls.ordering_collector(),
+            self.controls.averaged_span(),
         );
+        let watched = self.matching_processes(self.watch.as_ref(), &exposed_processes);
+        let pinned = self.matching_processes(self.pin.as_ref(), &exposed_processes);
+        self.controls.set_pinned(pinned);
         self.controls.set_processes(exposed_processes);
+        self.controls.set_watched(watched);
+        self.controls
+            .set_recently_spawned(self.process_collector.recently_spawned());
+        self.ui.set_churn_rate(self.process_collector.churn_rate());
 
         Ok(())
     }
 
+    /// Samples the subsampled collectors, for the processes running since the latest collection
+    fn sample_metrics(&mut self) {
+        // Some probes measure the time elapsed since their previous sample
+        refresh_current_timestamp();
+        let running_pids = self.process_collector.running_pids();
+
+        for collector in self.controls.collectors_as_mut_slice() {
+            collector.sample(&running_pids).unwrap_or_else(|e| {
+                warn!("Error sampling collector {}: {}", collector.name(), e.to_string());
+            });
+        }
+    }
+
+    /// Returns the PIDs of the processes whose latest metrics satisfy the given expression, if any
+    fn matching_processes(&self, expression: Option<&WatchExpression>, processes: &[ProcessMetadata]) -> HashSet<Pid> {
+        let Some(expression) = expression else {
+            return HashSet::new();
+        };
+
+        let collectors = self.controls.collectors_as_slice();
+        let overviews: HashMap<&str, MetricsOverview> = self
+            .expression_collectors
+            .iter()
+            .map(|(name, idx)| (name.as_str(), collectors[*idx].overview()))
+            .collect();
+
+        processes
+            .iter()
+            .map(|pm| pm.pid())
+            .filter(|pid| expression.matches(*pid, &overviews))
+            .collect()
+    }
+
+    /// Warns the user if the first collection shows that spv lacks the permissions to monitor most processes
+    fn check_permissions(&mut self) {
+        let outcomes: Vec<ProbeOutcome> = self
+            .controls
+            .collectors_as_slice()
+            .iter()
+            .flat_map(|collector| collector.latest_outcomes().iter().copied())
+            .collect();
+
+        if lacks_permissions(&outcomes) {
+            warn!("The permission to probe most processes was denied");
+            self.controls.warn_about_permissions();
+        }
+    }
+
+    /// Discards the metrics which are older than the configured history
+    fn purge_metrics(&mut self) {
+        let history = match self.history {
+            Some(history) => history,
+            None => return,
+        };
+
+        let now = Timestamp::now();
+        if now.duration_since(&Timestamp::app_init()) <= history {
+            return;
+        }
+
+        for collector in self.controls.collectors_as_mut_slice() {
+            collector.purge(now - history);
+        }
+    }
+
+    /// Merges the metrics which are older than the configured age into buckets, at most once per bucket
+    fn compact_metrics(&mut self) {
+        let compact_after = match self.config.compact_after() {
+            Some(compact_after) => compact_after,
+            None => return,
+        };
+
+        let now = Timestamp::now();
+        if now.duration_since(&Timestamp::app_init()) <= compact_after
+            || now.duration_since(&self.last_compaction) < COMPACTION_BUCKET
+        {
+            return;
+        }
+
+        self.last_compaction = now;
+        for collector in self.controls.collectors_as_mut_slice() {
+            collector.compact(now - compact_after, COMPACTION_BUCKET);
+        }
+    }
+
     fn scan_processes(&mut self) -> Result<(), Error> {
         let collection_ret = self.process_collector.collect_processes().map_err(Error::CoreError);
 
@@ -128,22 +515,718 @@ impl SpvApplication {
         let collectors = self.controls.to_collectors_view();
         let processes = self.controls.to_processes_view();
 
+        if let (State::ProcessDetail, Some(process)) = (self.controls.state(), processes.selected_process()) {
+            // The device is left out of the details when it cannot be resolved, e.g. without permission
+            let device = self.device_resolver.dominant_device(process.pid()).ok().flatten();
+            // Likewise for the connections, which are only read for the selected process
+            let connections = self
+                .connection_lister
+                .as_mut()
+                .and_then(|lister| lister.connections(process.pid()).ok());
+
+            return self
+                .ui
+                .render_process_detail(
+                    process,
+                    device.as_ref(),
+                    connections.as_deref(),
+                    self.controls.collectors_as_slice(),
+                )
+                .map_err(Error::UiError);
+        }
+
+        if let State::Summary = self.controls.state() {
+            let summary = summarize(
+                self.controls.visible_collectors(),
+                processes.as_slice(),
+                SUMMARY_LEADERS,
+            );
+
+            return self.ui.render_summary(&summary).map_err(Error::UiError);
+        }
+
+        if let State::Filesystems = self.controls.state() {
+            let filesystems = match self.filesystem_scanner.as_mut().map(|scanner| scanner.filesystems()) {
+                Some(Ok(filesystems)) => filesystems,
+                Some(Err(e)) => {
+                    warn!("Could not list the mounted filesystems: {}", e);
+                    vec![]
+                }
+                None => vec![],
+            };
+
+            return self.ui.render_filesystems(&filesystems).map_err(Error::UiError);
+        }
+
+        if let State::Units = self.controls.state() {
+            let current_collector = self.controls.current_collector();
+            let overview = current_collector.overview();
+            let units = sum_by_unit(&overview, processes.as_slice());
+
+            return self
+                .ui
+                .render_units(&units, current_collector.name(), |value| {
+                    overview.concise_repr_of_value(value)
+                })
+                .map_err(Error::UiError);
+        }
+
         // TODO move overview building code to Controls module
         let current_collector = self.controls.current_collector();
-        let overview = current_collector.overview();
+        let overviews = metrics_overviews(
+            self.controls.collectors_as_slice(),
+            current_collector,
+            &self.ui.collector_columns(),
+        );
         let metrics_view = processes
             .selected_process()
             .map(|pm| current_collector.view(pm.pid(), self.controls.to_span()));
 
         // TODO wrap all these views/state in a standalone structure (or pass Controls) ?
+        self.ui.set_current_collector(self.controls.current_collector_index());
+        self.ui.set_chart_only(self.controls.is_chart_only());
+        self.ui.set_histogram_mode(self.controls.is_histogram_mode());
+        self.ui.set_preset_prompt(self.controls.preset_prompt().clone());
         self.ui
             .render(
                 &collectors,
                 &processes,
-                &overview,
+                &overviews,
                 metrics_view.as_ref(),
+                self.controls.is_view_frozen(),
                 self.controls.state(),
             )
             .map_err(Error::UiError)
     }
 }
+
+/// Releases the resources of the application, then flushes the logs
+///
+/// Dropping the resources restores the terminal, so that the messages they log while being released are flushed too.
+fn teardown<R>(resources: R, logger: &dyn Log) {
+    drop(resources);
+    logger.flush();
+}
+
+/// Returns the indices of the collectors whose metrics the watch and pin expressions refer to, along with the name of
+/// their probe
+///
+/// # Arguments
+///  * `config`: The configuration holding the watch and pin expressions
+///  * `probes`: The probe of each collector, in the order of the collectors
+fn expression_collectors(config: &Config, probes: &[ProbeKind]) -> Vec<(String, usize)> {
+    let expression_metrics: Vec<&str> = config
+        .watch()
+        .into_iter()
+        .chain(config.pin())
+        .flat_map(|expression| expression.metrics())
+        .collect();
+
+    probes
+        .iter()
+        .enumerate()
+        .map(|(idx, probe)| (config.probe_name(*probe), idx))
+        .filter(|(name, _)| expression_metrics.contains(name))
+        .map(|(name, idx)| (name.to_string(), idx))
+        .collect()
+}
+
+#[cfg(test)]
+mod test_expression_collectors {
+    use crate::config::{ConfigBuilder, ProbeKind};
+    use crate::core::external::ExternalProbeSpec;
+    use crate::spv::expression_collectors;
+
+    #[test]
+    fn test_should_locate_collectors_of_metrics_of_watch_and_pin_expressions() {
+        let config = ConfigBuilder::default()
+            .watch("cpu > 50".parse().unwrap())
+            .pin("memory > 1G".parse().unwrap())
+            .probes(vec![ProbeKind::Cpu, ProbeKind::DiskIO, ProbeKind::Memory])
+            .build()
+            .unwrap();
+        let probes = [ProbeKind::Cpu, ProbeKind::DiskIO, ProbeKind::Memory];
+
+        assert_eq!(
+            expression_collectors(&config, &probes),
+            vec![("cpu".to_string(), 0), ("memory".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn test_should_locate_collectors_of_external_probes_by_the_name_of_their_script() {
+        let config = ConfigBuilder::default()
+            .watch("lag > 3".parse().unwrap())
+            .external_probes(vec![
+                ExternalProbeSpec::new("queue", "/usr/local/bin/queue-depth"),
+                ExternalProbeSpec::new("lag", "/opt/lag.sh"),
+            ])
+            .build()
+            .unwrap();
+        let probes = [ProbeKind::Cpu, ProbeKind::External(0), ProbeKind::External(1)];
+
+        assert_eq!(expression_collectors(&config, &probes), vec![("lag".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_should_locate_no_collector_without_expression() {
+        let config = ConfigBuilder::default().build().unwrap();
+
+        assert!(expression_collectors(&config, &[ProbeKind::Cpu]).is_empty());
+    }
+}
+
+/// Returns the overview of the current collector, followed by the overviews of the collectors displayed in columns of
+/// the process list
+///
+/// # Arguments
+///  * `collectors`: All the collectors
+///  * `current_collector`: The collector of the current tab
+///  * `column_collectors`: The indices in `collectors` of the collectors displayed in columns
+fn metrics_overviews<'a>(
+    collectors: &'a [Box<dyn MetricCollector>],
+    current_collector: &'a dyn MetricCollector,
+    column_collectors: &[usize],
+) -> Vec<MetricsOverview<'a>> {
+    let column_overviews = column_collectors
+        .iter()
+        .filter_map(|index| collectors.get(*index))
+        .map(|collector| collector.overview());
+
+    iter::once(current_collector.overview())
+        .chain(column_overviews)
+        .collect()
+}
+
+/// Runs a command in the foreground, and waits for it to exit
+///
+/// The command does not read the standard input, from which spv keeps reading the keys typed by the user.
+fn run_command(args: &[String]) -> io::Result<ExitStatus> {
+    let (program, args) = args
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "the command is empty"))?;
+
+    Command::new(program).args(args).stdin(Stdio::null()).status()
+}
+
+/// Sends a signal to a process, and returns the effect describing the outcome to the user
+fn signal_process(sender: &mut dyn SignalSender, process: &ProcessMetadata, signal: ProcessSignal) -> Effect {
+    match sender.send(process, signal) {
+        Ok(()) => Effect::ProcessSignalled(process.pid(), signal),
+        Err(e) => {
+            error!("Could not send {} to process {}: {}", signal, process.pid(), e);
+            Effect::SignalFailed(process.pid(), signal)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_signal_process {
+    use crate::core::process::{Pid, ProcessMetadata};
+    use crate::core::signal::{ProcessSignal, SignalSender};
+    use crate::core::time::Timestamp;
+    use crate::core::Error;
+    use crate::ctrl::Effect;
+    use crate::spv::signal_process;
+
+    /// Records the signals it sends, and refuses to signal the processes whose PID was recycled
+    #[derive(Default)]
+    struct FakeSignalSender {
+        recycled_pids: Vec<Pid>,
+        sent_signals: Vec<(Pid, ProcessSignal)>,
+    }
+
+    impl SignalSender for FakeSignalSender {
+        fn send(&mut self, process: &ProcessMetadata, signal: ProcessSignal) -> Result<(), Error> {
+            if self.recycled_pids.contains(&process.pid()) {
+                return Err(Error::RecycledPID(process.pid()));
+            }
+
+            self.sent_signals.push((process.pid(), signal));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_should_report_signal_sent_to_process() {
+        let mut sender = FakeSignalSender::default();
+        let process = ProcessMetadata::new(1, "command", Timestamp::now());
+
+        let effect = signal_process(&mut sender, &process, ProcessSignal::Stop);
+
+        assert_eq!(effect, Effect::ProcessSignalled(1, ProcessSignal::Stop));
+        assert_eq!(sender.sent_signals, vec![(1, ProcessSignal::Stop)]);
+    }
+
+    #[test]
+    fn test_should_report_failure_to_signal_recycled_pid() {
+        let mut sender = FakeSignalSender {
+            recycled_pids: vec![1],
+            ..Default::default()
+        };
+        let process = ProcessMetadata::new(1, "command", Timestamp::now());
+
+        let effect = signal_process(&mut sender, &process, ProcessSignal::Continue);
+
+        assert_eq!(effect, Effect::SignalFailed(1, ProcessSignal::Continue));
+        assert!(sender.sent_signals.is_empty());
+    }
+}
+
+/// Waits for a trigger, and returns it along with all the triggers which are already pending in the channel
+///
+/// When collecting metrics takes longer than the refresh period, impulses queue up in the channel. Only the latest of
+/// them is returned, so that spv skips the collections it fell behind on instead of accumulating lag.
+fn receive_pending_triggers(receiver: &Receiver<Trigger>) -> Result<Vec<Trigger>, RecvError> {
+    let mut triggers = vec![receiver.recv()?];
+    triggers.extend(receiver.try_iter());
+    collapse_impulses(&mut triggers);
+
+    Ok(triggers)
+}
+
+/// Handles the triggers received by the application
+trait TriggerHandler {
+    /// Handles a trigger, and indicates whether it makes spv exit, or else whether it changes what the UI displays
+    fn handle_trigger(&mut self, trigger: Trigger) -> Result<ControlFlow<(), bool>, Error>;
+}
+
+/// Handles a batch of triggers, and indicates whether one of them makes spv exit, or else whether the UI needs to be
+/// redrawn
+///
+/// Holding a key down floods the channel with inputs, which are all handled before a single redraw. The triggers
+/// following an exit are left unhandled.
+fn handle_triggers(triggers: Vec<Trigger>, handler: &mut impl TriggerHandler) -> Result<ControlFlow<(), bool>, Error> {
+    let mut redraw = false;
+    for trigger in triggers {
+        match handler.handle_trigger(trigger)? {
+            ControlFlow::Break(()) => return Ok(ControlFlow::Break(())),
+            ControlFlow::Continue(changed) => redraw |= changed,
+        }
+    }
+
+    Ok(ControlFlow::Continue(redraw))
+}
+
+/// Removes all impulses from the given triggers but the last one
+fn collapse_impulses(triggers: &mut Vec<Trigger>) {
+    let Some(last_impulse) = triggers.iter().rposition(|t| matches!(t, Trigger::Impulse)) else {
+        return;
+    };
+
+    let count = triggers.len();
+    let mut index = 0;
+    triggers.retain(|trigger| {
+        let keep = index == last_impulse || !matches!(trigger, Trigger::Impulse);
+        index += 1;
+        keep
+    });
+
+    let skipped = count - triggers.len();
+    if skipped > 0 {
+        debug!(
+            "Collection fell behind the refresh period, skipping {} impulses",
+            skipped
+        );
+    }
+}
+
+/// Indicates whether an impulse triggers a collection, which it does unless the first collection of a static snapshot
+/// already occurred
+///
+/// # Arguments
+///  * `static_snapshot`: Whether metrics are only collected once at startup
+///  * `collections`: The amount of collections since the application started
+fn is_impulse_collected(static_snapshot: bool, collections: usize) -> bool {
+    !static_snapshot || collections == 0
+}
+
+/// Collects the metrics of the collectors which are due for the given iteration
+///
+/// # Arguments
+///  * `collectors`: All the collectors, whether they are due or not
+///  * `pids`: The processes of which to collect metrics
+///  * `iteration`: The index of the collection, starting at 0
+fn collect_due_metrics(collectors: &mut [Box<dyn MetricCollector>], pids: &[Pid], iteration: usize) {
+    for collector in collectors.iter_mut().filter(|c| c.is_due(iteration)) {
+        collector.collect(pids).unwrap_or_else(|e| {
+            warn!("Error reading from collector {}: {}", collector.name(), e.to_string());
+        });
+    }
+}
+
+#[cfg(test)]
+mod test_collect_due_metrics {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::core::collection::{MetricCollector, ProbeCollector};
+    use crate::core::metrics::PercentMetric;
+    use crate::core::probe::Probe;
+    use crate::core::process::Pid;
+    use crate::core::time::test_utils::advance_time_and_refresh_timestamp;
+    use crate::core::time::{Span, Timestamp};
+    use crate::core::Error;
+    use crate::spv::{collect_due_metrics, is_impulse_collected};
+
+    /// Probes the same metric for all processes, as many times as needed
+    struct ConstantProbe;
+
+    impl Probe<PercentMetric> for ConstantProbe {
+        fn name(&self) -> &'static str {
+            "constant"
+        }
+
+        fn probe(&mut self, _pid: Pid) -> Result<PercentMetric, Error> {
+            Ok(PercentMetric::new(10.))
+        }
+
+        fn cleanup(&mut self, _pids: &[Pid]) {}
+    }
+
+    #[test]
+    fn test_collector_with_divisor_should_only_collect_every_divisor_iterations() {
+        let origin = Timestamp::now();
+        let mut collectors: Vec<Box<dyn MetricCollector>> = vec![
+            Box::new(ProbeCollector::new(ConstantProbe)),
+            Box::new(ProbeCollector::new(ConstantProbe).with_refresh_divisor(5)),
+        ];
+
+        for iteration in 0..10 {
+            advance_time_and_refresh_timestamp(Duration::from_secs(1));
+            collect_due_metrics(&mut collectors, &[1], iteration);
+        }
+
+        let span = Span::new(origin, Timestamp::now());
+        assert_eq!(collectors[0].view(1, span).as_slice().len(), 10);
+        assert_eq!(collectors[1].view(1, span).as_slice().len(), 2);
+    }
+
+    #[rstest]
+    #[case(false, 10)]
+    #[case(true, 1)]
+    fn test_static_snapshot_should_only_collect_once_regardless_of_impulses(
+        #[case] static_snapshot: bool,
+        #[case] expected_metrics: usize,
+    ) {
+        let origin = Timestamp::now();
+        let mut collectors: Vec<Box<dyn MetricCollector>> = vec![Box::new(ProbeCollector::new(ConstantProbe))];
+        let mut collections = 0;
+
+        for _ in 0..10 {
+            advance_time_and_refresh_timestamp(Duration::from_secs(1));
+            if is_impulse_collected(static_snapshot, collections) {
+                collect_due_metrics(&mut collectors, &[1], collections);
+                collections += 1;
+            }
+        }
+
+        let span = Span::new(origin, Timestamp::now());
+        assert_eq!(collectors[0].view(1, span).as_slice().len(), expected_metrics);
+    }
+}
+
+#[cfg(test)]
+mod test_receive_pending_triggers {
+    use std::sync::mpsc::channel;
+
+    use crate::spv::receive_pending_triggers;
+    use crate::triggers::{Input, Trigger};
+
+    #[test]
+    fn test_should_receive_all_queued_inputs_at_once() {
+        let (sender, receiver) = channel();
+        for _ in 0..5 {
+            sender.send(Trigger::Input(Input::Down)).unwrap();
+        }
+        sender.send(Trigger::Impulse).unwrap();
+
+        let triggers = receive_pending_triggers(&receiver).unwrap();
+
+        assert_eq!(triggers.len(), 6);
+        assert!(triggers[..5].iter().all(|t| matches!(t, Trigger::Input(Input::Down))));
+        assert!(matches!(triggers[5], Trigger::Impulse));
+    }
+
+    #[test]
+    fn test_should_only_receive_triggers_sent_after_previous_reception() {
+        let (sender, receiver) = channel();
+        sender.send(Trigger::Input(Input::Down)).unwrap();
+        sender.send(Trigger::Input(Input::Up)).unwrap();
+        receive_pending_triggers(&receiver).unwrap();
+
+        sender.send(Trigger::Resize).unwrap();
+        let triggers = receive_pending_triggers(&receiver).unwrap();
+
+        assert_eq!(triggers.len(), 1);
+        assert!(matches!(triggers[0], Trigger::Resize));
+    }
+
+    #[test]
+    fn test_should_collapse_queued_impulses_into_one() {
+        let (sender, receiver) = channel();
+        for _ in 0..4 {
+            sender.send(Trigger::Impulse).unwrap();
+        }
+
+        let triggers = receive_pending_triggers(&receiver).unwrap();
+
+        assert_eq!(triggers.len(), 1);
+        assert!(matches!(triggers[0], Trigger::Impulse));
+    }
+
+    #[test]
+    fn test_should_keep_other_triggers_around_collapsed_impulses() {
+        let (sender, receiver) = channel();
+        sender.send(Trigger::Impulse).unwrap();
+        sender.send(Trigger::Input(Input::Down)).unwrap();
+        sender.send(Trigger::Impulse).unwrap();
+        sender.send(Trigger::Sample).unwrap();
+        sender.send(Trigger::Impulse).unwrap();
+        sender.send(Trigger::Input(Input::Up)).unwrap();
+
+        let triggers = receive_pending_triggers(&receiver).unwrap();
+
+        assert_eq!(triggers.len(), 4);
+        assert!(matches!(triggers[0], Trigger::Input(Input::Down)));
+        assert!(matches!(triggers[1], Trigger::Sample));
+        assert!(matches!(triggers[2], Trigger::Impulse));
+        assert!(matches!(triggers[3], Trigger::Input(Input::Up)));
+    }
+
+    #[test]
+    fn test_should_fail_when_all_senders_are_dropped() {
+        let (sender, receiver) = channel::<Trigger>();
+        drop(sender);
+
+        assert!(receive_pending_triggers(&receiver).is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_handle_triggers {
+    use std::cell::Cell;
+    use std::ops::ControlFlow;
+    use std::rc::Rc;
+    use std::sync::mpsc::{channel, Sender};
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::config::{ConfigBuilder, ProbeKind};
+    use crate::core::collection::ProbeCollector;
+    use crate::core::devices::{Device, DeviceResolver};
+    use crate::core::metrics::PercentMetric;
+    use crate::core::probe::Probe;
+    use crate::core::process::{Pid, ProcessCollector, ProcessMetadata, ProcessScanner};
+    use crate::core::signal::{ProcessSignal, SignalSender};
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::core::time::Timestamp;
+    use crate::core::Error;
+    use crate::spv::{handle_triggers, receive_pending_triggers, SpvApplication};
+    use crate::triggers::{Input, Trigger, TypingMode};
+
+    /// Counts the iterations of its collector, each of which is a collection of metrics
+    struct CountingProbe {
+        collections: Rc<Cell<usize>>,
+    }
+
+    impl Probe<PercentMetric> for CountingProbe {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        fn init_iteration(&mut self) -> Result<(), Error> {
+            self.collections.set(self.collections.get() + 1);
+            Ok(())
+        }
+
+        fn probe(&mut self, _pid: Pid) -> Result<PercentMetric, Error> {
+            Ok(PercentMetric::new(10.))
+        }
+
+        fn cleanup(&mut self, _pids: &[Pid]) {}
+    }
+
+    /// Scans a single process, which keeps running
+    struct SingleProcessScanner;
+
+    impl ProcessScanner for SingleProcessScanner {
+        fn scan(&mut self) -> Result<Vec<Pid>, Error> {
+            Ok(vec![1])
+        }
+
+        fn fetch_metadata(&mut self, pid: Pid) -> Result<ProcessMetadata, Error> {
+            Ok(ProcessMetadata::new(pid, "command", Timestamp::now()))
+        }
+
+        fn fetch_command(&mut self, _pid: Pid) -> Result<String, Error> {
+            Ok("command".to_string())
+        }
+
+        fn fetch_cpu_time(&mut self, _pid: Pid) -> Result<Duration, Error> {
+            Ok(Duration::ZERO)
+        }
+
+        fn fetch_stopped(&mut self, _pid: Pid) -> Result<bool, Error> {
+            Ok(false)
+        }
+    }
+
+    struct SignalSenderStub;
+
+    impl SignalSender for SignalSenderStub {
+        fn send(&mut self, _process: &ProcessMetadata, _signal: ProcessSignal) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    struct DeviceResolverStub;
+
+    impl DeviceResolver for DeviceResolverStub {
+        fn dominant_device(&mut self, _pid: Pid) -> Result<Option<Device>, Error> {
+            Ok(None)
+        }
+    }
+
+    /// Builds an application collecting metrics with a single counting collector, along with the sender of its
+    /// triggers and the count of its collections
+    fn application(static_snapshot: bool) -> (SpvApplication, Sender<Trigger>, Rc<Cell<usize>>) {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let (sender, receiver) = channel();
+        let collections = Rc::new(Cell::new(0));
+        let probe = CountingProbe {
+            collections: collections.clone(),
+        };
+        let config = ConfigBuilder::default()
+            .static_snapshot(static_snapshot)
+            .build()
+            .unwrap();
+
+        let app = SpvApplication::new(
+            receiver,
+            vec![(ProbeKind::Cpu, Box::new(ProbeCollector::new(probe)))],
+            ProcessCollector::new(Box::new(SingleProcessScanner)),
+            Box::new(SignalSenderStub),
+            Box::new(DeviceResolverStub),
+            TypingMode::default(),
+            &config,
+        )
+        .unwrap();
+
+        (app, sender, collections)
+    }
+
+    #[rstest]
+    #[case(false, 3)]
+    #[case(true, 1)]
+    fn test_should_collect_once_per_burst_of_impulses(#[case] static_snapshot: bool, #[case] expected: usize) {
+        let (mut app, sender, collections) = application(static_snapshot);
+
+        for _ in 0..3 {
+            for _ in 0..4 {
+                sender.send(Trigger::Impulse).unwrap();
+            }
+            let triggers = receive_pending_triggers(&app.receiver).unwrap();
+            let flow = handle_triggers(triggers, &mut app).unwrap();
+            assert!(flow.is_continue());
+        }
+
+        assert_eq!(collections.get(), expected);
+    }
+
+    #[test]
+    fn test_should_redraw_after_collecting() {
+        let (mut app, _sender, _collections) = application(false);
+
+        let flow = handle_triggers(vec![Trigger::Impulse], &mut app).unwrap();
+
+        assert_eq!(flow, ControlFlow::Continue(true));
+    }
+
+    #[test]
+    fn test_should_not_redraw_when_static_snapshot_skips_collection() {
+        let (mut app, _sender, collections) = application(true);
+        assert!(handle_triggers(vec![Trigger::Impulse], &mut app).unwrap().is_continue());
+
+        let flow = handle_triggers(vec![Trigger::Impulse, Trigger::Sample], &mut app).unwrap();
+
+        assert_eq!(flow, ControlFlow::Continue(false));
+        assert_eq!(collections.get(), 1);
+    }
+
+    #[test]
+    fn test_should_redraw_once_for_all_inputs_of_a_batch() {
+        let (mut app, _sender, collections) = application(false);
+        let triggers = vec![Trigger::Input(Input::Down), Trigger::Sample, Trigger::Input(Input::Up)];
+
+        let flow = handle_triggers(triggers, &mut app).unwrap();
+
+        assert_eq!(flow, ControlFlow::Continue(true));
+        assert_eq!(collections.get(), 0);
+    }
+
+    #[test]
+    fn test_should_stop_handling_triggers_on_exit() {
+        let (mut app, _sender, collections) = application(false);
+        let triggers = vec![Trigger::Input(Input::Down), Trigger::Exit, Trigger::Impulse];
+
+        let flow = handle_triggers(triggers, &mut app).unwrap();
+
+        assert_eq!(flow, ControlFlow::Break(()));
+        assert_eq!(collections.get(), 0);
+    }
+}
+
+#[cfg(test)]
+mod test_teardown {
+    use std::sync::Mutex;
+
+    use log::{Log, Metadata, Record};
+
+    use crate::spv::teardown;
+
+    /// Records the events of the teardown, in their order
+    type Events = Mutex<Vec<&'static str>>;
+
+    struct FakeLogger<'a>(&'a Events);
+
+    impl Log for FakeLogger<'_> {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, _record: &Record) {}
+
+        fn flush(&self) {
+            self.0.lock().unwrap().push("flush");
+        }
+    }
+
+    struct FakeResources<'a>(&'a Events);
+
+    impl Drop for FakeResources<'_> {
+        fn drop(&mut self) {
+            self.0.lock().unwrap().push("drop");
+        }
+    }
+
+    #[test]
+    fn test_should_flush_logs_exactly_once() {
+        let events = Events::default();
+
+        teardown(FakeResources(&events), &FakeLogger(&events));
+
+        assert_eq!(events.lock().unwrap().iter().filter(|e| **e == "flush").count(), 1);
+    }
+
+    #[test]
+    fn test_should_release_resources_before_flushing_logs() {
+        let events = Events::default();
+
+        teardown(FakeResources(&events), &FakeLogger(&events));
+
+        assert_eq!(*events.lock().unwrap(), vec!["drop", "flush"]);
+    }
+}
@@ -1,36 +1,51 @@
 use std::io::stdin;
 use std::sync::mpsc::Sender;
 
-use termion::event::Key as TermionKey;
+use termion::event::{Event, Key as TermionKey, MouseButton, MouseEvent as TermionMouseEvent};
 use termion::input::TermRead;
 
-use crate::triggers::{Error, Input, Trigger};
+use crate::triggers::{Error, Input, MouseEvent, Trigger, TypingMode};
 
 pub struct InputListener {
     sender: Sender<Trigger>,
+    typing_mode: TypingMode,
+    // If true, q asks the controls for a confirmation instead of exiting right away
+    confirm_quit: bool,
     exit: bool,
 }
 
 impl InputListener {
-    pub fn new(sender: Sender<Trigger>) -> Self {
-        Self { sender, exit: false }
+    pub fn new(sender: Sender<Trigger>, typing_mode: TypingMode) -> Self {
+        Self {
+            sender,
+            typing_mode,
+            confirm_quit: false,
+            exit: false,
+        }
+    }
+
+    /// Indicates if pressing q must be confirmed before exiting
+    ///
+    /// Ctrl-C and Ctrl-D still exit right away, as they are unlikely to be pressed by accident.
+    pub fn with_confirm_quit(mut self, confirm_quit: bool) -> Self {
+        self.confirm_quit = confirm_quit;
+        self
     }
 
     pub fn listen(mut self) -> Result<(), Error> {
         let stdin = stdin();
 
-        for key_ret in stdin.keys() {
-            let key = key_ret.map_err(Error::InputError)?;
-
-            match key {
-                TermionKey::Ctrl(c) => self.on_ctrl_key_pressed(c),
-                TermionKey::Char(c) => self.on_key_pressed(c),
-                TermionKey::Left => self.send(Trigger::Input(Input::Left)),
-                TermionKey::Right => self.send(Trigger::Input(Input::Right)),
-                TermionKey::Up => self.send(Trigger::Input(Input::Up)),
-                TermionKey::Down => self.send(Trigger::Input(Input::Down)),
-                TermionKey::Esc => self.send(Trigger::Input(Input::Escape)),
-                _ => (),
+        for event_ret in stdin.events() {
+            let event = event_ret.map_err(Error::InputError)?;
+
+            match event {
+                Event::Key(key) => self.on_key(key),
+                Event::Mouse(mouse_event) => {
+                    if let Some(mouse_event) = convert_mouse_event(mouse_event) {
+                        self.send(Trigger::Mouse(mouse_event));
+                    }
+                }
+                Event::Unsupported(_) => (),
             }
 
             if self.exit {
@@ -41,16 +56,38 @@ impl InputListener {
         Ok(())
     }
 
+    fn on_key(&mut self, key: TermionKey) {
+        match key {
+            TermionKey::Ctrl(c) => self.on_ctrl_key_pressed(c),
+            TermionKey::Char(c) if c != '\n' && self.typing_mode.is_typing() => {
+                self.send(Trigger::Input(Input::Char(c)))
+            }
+            TermionKey::Char(c) => self.on_key_pressed(c),
+            TermionKey::Left => self.send(Trigger::Input(Input::Left)),
+            TermionKey::Right => self.send(Trigger::Input(Input::Right)),
+            TermionKey::Up => self.send(Trigger::Input(Input::Up)),
+            TermionKey::Down => self.send(Trigger::Input(Input::Down)),
+            TermionKey::Esc => self.send(Trigger::Input(Input::Escape)),
+            TermionKey::Backspace => self.send(Trigger::Input(Input::Backspace)),
+            TermionKey::Delete => self.send(Trigger::Input(Input::Delete)),
+            _ => (),
+        }
+    }
+
     fn on_ctrl_key_pressed(&mut self, key: char) {
         match key {
             'c' | 'd' => self.send_exit(),
+            // Raw mode keeps Ctrl-Z from stopping spv, which has to restore the terminal before stopping itself
+            'z' => self.send(Trigger::Suspend),
             _ => (),
         }
     }
 
     fn on_key_pressed(&mut self, key: char) {
         match key {
+            'q' if self.confirm_quit => self.send(Trigger::Input(Input::Q)),
             'q' => self.send_exit(),
+            'y' => self.send(Trigger::Input(Input::Y)),
             'h' => self.send(Trigger::Input(Input::Left)),
             'j' => self.send(Trigger::Input(Input::Down)),
             'k' => self.send(Trigger::Input(Input::Up)),
@@ -61,14 +98,36 @@ impl InputListener {
             'L' => self.send(Trigger::Input(Input::AltRight)),
             'g' => self.send(Trigger::Input(Input::G)),
             's' => self.send(Trigger::Input(Input::S)),
+            't' => self.send(Trigger::Input(Input::T)),
+            'u' => self.send(Trigger::Input(Input::U)),
+            'n' => self.send(Trigger::Input(Input::N)),
+            'd' => self.send(Trigger::Input(Input::D)),
+            'f' => self.send(Trigger::Input(Input::F)),
+            'x' => self.send(Trigger::Input(Input::X)),
+            'r' => self.send(Trigger::Input(Input::R)),
+            'c' => self.send(Trigger::Input(Input::C)),
+            'p' => self.send(Trigger::Input(Input::P)),
+            'i' => self.send(Trigger::Input(Input::I)),
+            'e' => self.send(Trigger::Input(Input::E)),
+            'b' => self.send(Trigger::Input(Input::B)),
+            'w' => self.send(Trigger::Input(Input::W)),
+            'o' => self.send(Trigger::Input(Input::O)),
+            'a' => self.send(Trigger::Input(Input::A)),
+            'v' => self.send(Trigger::Input(Input::V)),
+            'm' => self.send(Trigger::Input(Input::M)),
+            'z' => self.send(Trigger::Input(Input::Z)),
+            'U' => self.send(Trigger::Input(Input::ShiftU)),
             '\n' => self.send(Trigger::Input(Input::Submit)),
             _ => {}
         };
     }
 
+    /// Requests the application to exit
+    ///
+    /// The listener keeps listening until the application stops, as the keys typed while an external command runs are
+    /// discarded once the command exits.
     fn send_exit(&mut self) {
         self.send(Trigger::Exit);
-        self.exit = true;
     }
 
     fn send(&mut self, trigger: Trigger) {
@@ -77,3 +136,48 @@ impl InputListener {
         }
     }
 }
+
+/// Converts the events of the left mouse button, whose positions termion reports from 1, to positions starting at 0
+fn convert_mouse_event(event: TermionMouseEvent) -> Option<MouseEvent> {
+    let to_position = |column: u16, row: u16| (column.saturating_sub(1), row.saturating_sub(1));
+
+    match event {
+        TermionMouseEvent::Press(MouseButton::Left, column, row) => {
+            let (column, row) = to_position(column, row);
+            Some(MouseEvent::Press(column, row))
+        }
+        TermionMouseEvent::Hold(column, row) => {
+            let (column, row) = to_position(column, row);
+            Some(MouseEvent::Drag(column, row))
+        }
+        TermionMouseEvent::Release(column, row) => {
+            let (column, row) = to_position(column, row);
+            Some(MouseEvent::Release(column, row))
+        }
+        TermionMouseEvent::Press(_, _, _) => None,
+    }
+}
+
+#[cfg(test)]
+mod test_mouse_event_conversion {
+    use termion::event::{MouseButton, MouseEvent as TermionMouseEvent};
+
+    use crate::triggers::input::convert_mouse_event;
+    use crate::triggers::MouseEvent;
+
+    #[test]
+    fn test_should_convert_positions_to_start_from_zero() {
+        assert_eq!(
+            convert_mouse_event(TermionMouseEvent::Press(MouseButton::Left, 1, 5)),
+            Some(MouseEvent::Press(0, 4))
+        );
+    }
+
+    #[test]
+    fn test_should_ignore_other_buttons() {
+        assert_eq!(
+            convert_mouse_event(TermionMouseEvent::Press(MouseButton::WheelUp, 1, 5)),
+            None
+        );
+    }
+}
@@ -1,12 +1,15 @@
 //! Generates events which drive spv, either from user input, OS events or a timer
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
+use std::sync::Arc;
 use std::time::Duration;
 use std::{io, thread};
 
 use log::error;
 use thiserror::Error;
 
+use crate::config::Config;
 use crate::triggers::input::InputListener;
 use crate::triggers::pulse::Pulse;
 use crate::triggers::signal::SignalListener;
@@ -27,8 +30,38 @@ pub enum Error {
 pub enum Trigger {
     Exit,
     Impulse,
+    /// Samples the subsampled probes between two impulses, without refreshing the UI
+    Sample,
     Resize,
+    /// Stops spv until it is continued, as when the user presses Ctrl-Z
+    Suspend,
+    /// Sets the terminal back up, after spv has been continued
+    Resume,
+    /// Reads the configuration again, as when spv receives SIGHUP
+    Reload,
     Input(Input),
+    Mouse(MouseEvent),
+}
+
+/// Events of the left button of the mouse, located by the column and row of the cell under the cursor
+///
+/// Columns and rows start at 0, from the top left corner of the terminal.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MouseEvent {
+    Press(u16, u16),
+    Drag(u16, u16),
+    Release(u16, u16),
+}
+
+impl MouseEvent {
+    /// Returns the column and row of the cell under the cursor
+    pub fn position(&self) -> (u16, u16) {
+        match *self {
+            MouseEvent::Press(column, row) | MouseEvent::Drag(column, row) | MouseEvent::Release(column, row) => {
+                (column, row)
+            }
+        }
+    }
 }
 
 /// Keyboard events submitted by users to interact with the application
@@ -44,28 +77,100 @@ pub enum Input {
     AltLeft,
     S,
     G,
+    T,
+    U,
+    N,
+    F,
+    D,
+    /// Hides the current tab
+    X,
+    /// Restores the hidden tabs
+    R,
+    /// Toggles the display of the chart alone, without the process list
+    C,
+    /// Pauses or resumes the selected process
+    P,
+    /// Switches the current tab to its next source of metrics
+    I,
+    /// Runs the configured external command on the selected process
+    E,
+    /// Toggles the histogram of the latest metrics of processes, in place of the chart
+    B,
+    /// Prompts for the name under which to save the view as a preset
+    W,
+    /// Prompts for the saved preset to load
+    O,
+    /// Toggles the summary of the leading processes of every tab, in place of the main view
+    A,
+    /// Toggles the sorting of processes by the average of their metrics over the span of the chart
+    V,
+    /// Toggles the disk space usage of the mounted filesystems, in place of the main view
+    M,
+    /// Clears the collected metrics of the selected process
+    Delete,
+    /// Fits the span of the chart to the metrics collected for the selected process
+    Z,
+    /// Toggles the metrics of the current tab summed by systemd unit, in place of the main view
+    ShiftU,
+    /// Asks to quit, when quitting must be confirmed
+    Q,
+    /// Confirms the question of a prompt
+    Y,
+    /// A character typed while a prompt expects text
+    Char(char),
     Submit,
+    Backspace,
+}
+
+/// Shared between the application and the input listener, so that characters typed while a prompt expects text are
+/// forwarded as such, instead of being interpreted as shortcuts
+#[derive(Clone, Default)]
+pub struct TypingMode(Arc<AtomicBool>);
+
+impl TypingMode {
+    pub fn set(&self, typing: bool) {
+        self.0.store(typing, Ordering::Relaxed);
+    }
+
+    pub fn is_typing(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
 }
 
 pub struct TriggersEmitter;
 
 impl TriggersEmitter {
-    pub fn launch_async(sender: Sender<Trigger>, refresh_period: Duration) {
+    pub fn launch_async(sender: Sender<Trigger>, config: &Config, typing_mode: TypingMode) {
+        let refresh_period = config.refresh_period();
+        let samples_per_refresh = config.samples_per_refresh();
         let impulse_sender = sender.clone();
         let input_sender = sender.clone();
         let signal_sender = sender;
 
-        Self::start_impulse_thread(impulse_sender, refresh_period);
-        Self::start_input_thread(input_sender);
+        if config.static_snapshot() {
+            Self::send_single_impulse(impulse_sender);
+        } else {
+            Self::start_impulse_thread(impulse_sender, refresh_period, samples_per_refresh);
+        }
+        Self::start_input_thread(input_sender, typing_mode, config.confirm_quit());
         Self::start_signal_thread(signal_sender);
     }
 
     /// Launches a thread which will emit a `Trigger::Impulse` event every `refresh_period`
-    fn start_impulse_thread(sender: Sender<Trigger>, refresh_period: Duration) {
+    ///
+    /// The period is divided into `samples_per_refresh` pulses, the first of which emits the impulse while the others
+    /// emit a `Trigger::Sample` event. Using a single pulse keeps the samples from drifting away from the impulses.
+    fn start_impulse_thread(sender: Sender<Trigger>, refresh_period: Duration, samples_per_refresh: u32) {
+        let samples_per_refresh = samples_per_refresh.max(1);
+
         thread::spawn(move || {
-            let mut pulse = Pulse::new(refresh_period);
-            loop {
-                if sender.send(Trigger::Impulse).is_err() {
+            let mut pulse = Pulse::new(refresh_period / samples_per_refresh);
+            for pulse_index in (0..samples_per_refresh).cycle() {
+                let trigger = match pulse_index {
+                    0 => Trigger::Impulse,
+                    _ => Trigger::Sample,
+                };
+                if sender.send(trigger).is_err() {
                     break;
                 }
                 pulse.pulse();
@@ -73,13 +178,20 @@ impl TriggersEmitter {
         });
     }
 
+    /// Emits the only `Trigger::Impulse` event of a static snapshot, which is collected once and never refreshed
+    fn send_single_impulse(sender: Sender<Trigger>) {
+        // The receiver is still alive, as the application has not started yet
+        let _ = sender.send(Trigger::Impulse);
+    }
+
     pub fn impulse_time_tolerance(refresh_period: Duration) -> Duration {
         Pulse::tolerance(refresh_period)
     }
 
-    fn start_input_thread(sender: Sender<Trigger>) {
+    fn start_input_thread(sender: Sender<Trigger>, typing_mode: TypingMode, confirm_quit: bool) {
         thread::spawn(move || {
-            if let Err(e) = InputListener::new(sender).listen() {
+            let listener = InputListener::new(sender, typing_mode).with_confirm_quit(confirm_quit);
+            if let Err(e) = listener.listen() {
                 error!("Trigger error: {:?}", e);
             }
         });
@@ -3,7 +3,7 @@ use std::sync::mpsc::Sender;
 use signal_hook::iterator::Signals;
 
 use crate::triggers::{Error, Trigger};
-use signal_hook::consts::{SIGINT, SIGQUIT, SIGTERM, SIGWINCH};
+use signal_hook::consts::{SIGCONT, SIGHUP, SIGINT, SIGQUIT, SIGTERM, SIGTSTP, SIGWINCH};
 
 pub struct SignalListener {
     sender: Sender<Trigger>,
@@ -17,16 +17,14 @@ impl SignalListener {
     }
 
     pub fn listen(mut self) -> Result<(), Error> {
-        let mut signals = Signals::new(&[SIGINT, SIGTERM, SIGQUIT, SIGWINCH]).map_err(Error::SignalError)?;
+        let mut signals =
+            Signals::new([SIGINT, SIGTERM, SIGQUIT, SIGWINCH, SIGTSTP, SIGCONT, SIGHUP]).map_err(Error::SignalError)?;
 
         while !self.exit {
             for signal in signals.wait() {
-                match signal as i32 {
-                    SIGTERM | SIGINT | SIGQUIT => {
-                        self.send_exit();
-                    }
-                    SIGWINCH => self.send(Trigger::Resize),
-                    _ => unreachable!(),
+                match signal_trigger(signal) {
+                    Trigger::Exit => self.send_exit(),
+                    trigger => self.send(trigger),
                 }
             }
         }
@@ -34,9 +32,12 @@ impl SignalListener {
         Ok(())
     }
 
+    /// Requests the application to exit
+    ///
+    /// The listener keeps listening until the application stops, as an interruption such as Ctrl-C is also received by
+    /// the external command running in the foreground, if any, and discarded once the command exits.
     fn send_exit(&mut self) {
         self.send(Trigger::Exit);
-        self.exit = true;
     }
 
     fn send(&mut self, trigger: Trigger) {
@@ -45,3 +46,57 @@ impl SignalListener {
         }
     }
 }
+
+/// Returns the trigger emitted when receiving the given signal
+///
+/// Handling SIGTSTP prevents it from stopping spv right away, so that spv restores the terminal before stopping itself.
+/// SIGCONT sets the terminal back up, as the terminal may have been altered while spv was stopped.
+/// SIGHUP reloads the configuration, as is customary for long-running programs.
+fn signal_trigger(signal: i32) -> Trigger {
+    match signal {
+        SIGTERM | SIGINT | SIGQUIT => Trigger::Exit,
+        SIGWINCH => Trigger::Resize,
+        SIGTSTP => Trigger::Suspend,
+        SIGCONT => Trigger::Resume,
+        SIGHUP => Trigger::Reload,
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod test_signal_trigger {
+    use rstest::*;
+    use signal_hook::consts::{SIGCONT, SIGHUP, SIGINT, SIGQUIT, SIGTERM, SIGTSTP, SIGWINCH};
+
+    use crate::triggers::signal::signal_trigger;
+    use crate::triggers::Trigger;
+
+    #[rstest]
+    #[case(SIGINT)]
+    #[case(SIGTERM)]
+    #[case(SIGQUIT)]
+    fn test_should_exit_on_interruption_signals(#[case] signal: i32) {
+        assert!(matches!(signal_trigger(signal), Trigger::Exit));
+    }
+
+    #[rstest]
+    fn test_should_suspend_on_terminal_stop() {
+        assert!(matches!(signal_trigger(SIGTSTP), Trigger::Suspend));
+    }
+
+    #[rstest]
+    fn test_should_resume_on_continue() {
+        assert!(matches!(signal_trigger(SIGCONT), Trigger::Resume));
+    }
+
+    #[rstest]
+    fn test_should_reload_on_hangup() {
+        assert!(matches!(signal_trigger(SIGHUP), Trigger::Reload));
+    }
+
+    #[rstest]
+    #[case(SIGWINCH)]
+    fn test_should_neither_suspend_nor_resume_on_other_signals(#[case] signal: i32) {
+        assert!(!matches!(signal_trigger(signal), Trigger::Suspend | Trigger::Resume));
+    }
+}
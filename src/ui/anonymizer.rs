@@ -0,0 +1,103 @@
+//! Hides the names of processes, to share screenshots without disclosing the commands being run
+
+use std::collections::HashMap;
+
+use crate::core::process::{Pid, ProcessMetadata};
+
+/// Replaces the command, command line and label of processes with generic names such as `process-1`
+///
+/// A process keeps the same generic name for as long as spv runs, so that it can be followed across screenshots.
+/// Everything else, such as the PID of the process, is left untouched, and so are its metrics.
+#[derive(Default)]
+pub struct Anonymizer {
+    names: HashMap<Pid, String>,
+}
+
+impl Anonymizer {
+    /// Returns a copy of the given processes, under their generic names
+    pub fn anonymize(&mut self, processes: &[ProcessMetadata]) -> Vec<ProcessMetadata> {
+        processes
+            .iter()
+            .map(|process| self.anonymize_process(process))
+            .collect()
+    }
+
+    /// Returns a copy of the given process, under its generic name
+    pub fn anonymize_process(&mut self, process: &ProcessMetadata) -> ProcessMetadata {
+        let next_index = self.names.len() + 1;
+        let name = self
+            .names
+            .entry(process.pid())
+            .or_insert_with(|| format!("process-{}", next_index));
+
+        let mut anonymized = process.clone().with_cmdline(name.clone());
+        anonymized.set_command(name.clone());
+        anonymized.clear_label();
+
+        anonymized
+    }
+}
+
+#[cfg(test)]
+mod test_anonymizer {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::core::process::ProcessMetadata;
+    use crate::core::time::Timestamp;
+    use crate::ui::anonymizer::Anonymizer;
+
+    #[fixture]
+    fn processes() -> Vec<ProcessMetadata> {
+        let mut kafka = ProcessMetadata::new(10, "java", Timestamp::now())
+            .with_tgid(9)
+            .with_cmdline("java -Dpassword=secret kafka.Kafka");
+        kafka.set_label("kafka");
+        kafka.set_cpu_time(Duration::from_secs(12));
+
+        vec![kafka, ProcessMetadata::new(20, "ssh", Timestamp::now())]
+    }
+
+    #[rstest]
+    fn test_should_name_processes_in_order_of_appearance(processes: Vec<ProcessMetadata>) {
+        let anonymized = Anonymizer::default().anonymize(&processes);
+
+        let names: Vec<_> = anonymized.iter().map(|pm| pm.display_name()).collect();
+        assert_eq!(names, vec!["process-1", "process-2"]);
+    }
+
+    #[rstest]
+    fn test_should_hide_command_line_and_label(processes: Vec<ProcessMetadata>) {
+        let anonymized = Anonymizer::default().anonymize_process(&processes[0]);
+
+        assert_eq!(anonymized.command(), "process-1");
+        assert_eq!(anonymized.cmdline(), "process-1");
+        assert_eq!(anonymized.label(), None);
+    }
+
+    #[rstest]
+    fn test_should_keep_same_name_for_same_pid(processes: Vec<ProcessMetadata>) {
+        let mut anonymizer = Anonymizer::default();
+        anonymizer.anonymize(&processes);
+
+        // The processes are listed in another order once sorted differently
+        let reordered: Vec<_> = processes.into_iter().rev().collect();
+        let anonymized = anonymizer.anonymize(&reordered);
+
+        assert_eq!(anonymized[0].pid(), 20);
+        assert_eq!(anonymized[0].command(), "process-2");
+        assert_eq!(anonymized[1].pid(), 10);
+        assert_eq!(anonymized[1].command(), "process-1");
+    }
+
+    #[rstest]
+    fn test_should_keep_attributes_other_than_names(processes: Vec<ProcessMetadata>) {
+        let anonymized = Anonymizer::default().anonymize_process(&processes[0]);
+
+        assert_eq!(anonymized.pid(), 10);
+        assert_eq!(anonymized.tgid(), 9);
+        assert_eq!(anonymized.running_span(), processes[0].running_span());
+        assert_eq!(anonymized.cpu_time(), Duration::from_secs(12));
+    }
+}
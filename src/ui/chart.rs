@@ -1,31 +1,88 @@
 use std::ops::Neg;
 use std::time::Duration;
 
-use tui::layout::Alignment;
+use tui::buffer::Buffer;
+use tui::layout::{Alignment, Rect};
 use tui::style::{Color, Style};
 use tui::symbols;
-use tui::text::Span;
-use tui::widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph};
+use tui::text::{Span, Spans};
+use tui::widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Widget};
 
-use crate::core::time::Timestamp;
+use crate::config::Theme;
+use crate::core::process::{ProcessMetadata, Status};
+use crate::core::time::{Span as TimeSpan, Timestamp};
 use crate::core::view::MetricView;
 use crate::ui::labels::relative_timestamp_label;
 use crate::ui::terminal::FrameRegion;
 
+const SPAWN_MARKER_COLOR: Color = Color::Green;
+const DEATH_MARKER_COLOR: Color = Color::Red;
+const GAPS_CAPTION_COLOR: Color = Color::DarkGray;
+/// The maximum amount of labels of the y-axis, when they are rounded to nice numbers
+const Y_AXIS_MAX_TICKS: usize = 5;
+
 pub struct MetricsChart {
     resolution: Milliseconds,
+    theme: Theme,
+    ascii: bool,
+    // If true, the newest metrics are drawn on the left of the chart, and the oldest on the right
+    newest_left: bool,
+    // If true, the y-axis is extended to round bounds, and labelled at regular round ticks
+    nice_y_axis: bool,
+    // The time expected between two collected metrics, to detect the gaps in the collected metrics
+    collection_period: Option<Duration>,
 }
 
 impl MetricsChart {
-    pub fn new(resolution: Duration) -> Self {
+    /// Creates a new chart
+    ///
+    /// # Arguments
+    ///  * `resolution`: The time period represented by a single point of the chart
+    ///  * `theme`: The colors used to draw the chart
+    ///  * `ascii`: If true, the chart is only drawn with ASCII characters
+    pub fn new(resolution: Duration, theme: Theme, ascii: bool) -> Self {
         Self {
             resolution: resolution.as_millis().max(1),
+            theme,
+            ascii,
+            newest_left: false,
+            nice_y_axis: false,
+            collection_period: None,
         }
     }
 
-    pub fn render(&self, frame: &mut FrameRegion, view_opt: Option<&MetricView>) {
+    /// Indicates when fewer metrics than expected from the given collection period are drawn, as the chart then has
+    /// gaps, such as when collections are skipped
+    pub fn with_collection_period(mut self, collection_period: Duration) -> Self {
+        self.collection_period = Some(collection_period);
+        self
+    }
+
+    /// Draws the newest metrics on the left of the chart, like a tape readout
+    pub fn with_newest_left(mut self) -> Self {
+        self.newest_left = true;
+        self
+    }
+
+    /// Labels the y-axis at ticks spaced by 1, 2 or 5 times a power of 10, e.g. 0, 20, 40 instead of 0 and 37.482
+    pub fn with_nice_y_axis(mut self) -> Self {
+        self.nice_y_axis = true;
+        self
+    }
+
+    /// Renders the metrics of the selected process
+    ///
+    /// If `frozen` is true, the chart indicates that it does not track the latest metrics.
+    /// When `process` is given, its spawn and death are marked on the chart if they happened in the displayed span.
+    pub fn render(
+        &self,
+        frame: &mut FrameRegion,
+        view_opt: Option<&MetricView>,
+        process: Option<&ProcessMetadata>,
+        frozen: bool,
+    ) {
         match view_opt {
-            Some(view) => self.render_metrics_view(frame, view),
+            Some(view) => self.render_metrics_view(frame, view, process, frozen),
             None => self.render_no_process_selected_message(frame),
         }
     }
@@ -41,58 +98,363 @@ impl MetricsChart {
 
         let paragraph = Paragraph::new(text).block(block).alignment(Alignment::Center);
 
-        frame.render_widget(paragraph)
+        self.render_widget(frame, paragraph)
     }
 
-    fn render_metrics_view(&self, frame: &mut FrameRegion, view: &MetricView) {
-        let raw_data = build_raw_vecs(view, self.resolution);
+    fn render_metrics_view(
+        &self,
+        frame: &mut FrameRegion,
+        view: &MetricView,
+        process: Option<&ProcessMetadata>,
+        frozen: bool,
+    ) {
+        let mut raw_data = build_raw_vecs(view, self.resolution);
+        if self.newest_left {
+            raw_data = mirror_series(raw_data);
+        }
+        let y_ticks = match self.nice_y_axis {
+            true => nice_ticks(y_lower_bound(view), y_upper_bound(view), Y_AXIS_MAX_TICKS),
+            false => vec![y_lower_bound(view), y_upper_bound(view)],
+        };
+        let (lower_bound, upper_bound) = (y_ticks[0], y_ticks[y_ticks.len() - 1]);
+        let markers_data: Vec<_> = process
+            .map(process_events)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|event| {
+                marker_x_value(event.timestamp, view.span(), self.resolution)
+                    .map(|x| orient_x_value(x, self.newest_left))
+                    .map(|x| (event, vec![(x, lower_bound), (x, upper_bound)]))
+            })
+            .collect();
+        let marker = match self.ascii {
+            true => symbols::Marker::Dot,
+            false => symbols::Marker::Braille,
+        };
+
+        let mut captions = vec![];
+        if frozen {
+            captions.push(Span::styled(
+                " Frozen ",
+                Style::default().fg(Color::Black).bg(Color::White),
+            ));
+        }
+        if self.collection_period.is_some_and(|period| has_gaps(view, period)) {
+            captions.push(Span::styled(
+                " Data gaps present ",
+                Style::default().fg(GAPS_CAPTION_COLOR),
+            ));
+        }
+        let mut block = Self::widget_block();
+        if !captions.is_empty() {
+            block = block.title(Spans::from(captions));
+        }
 
-        let chart = Chart::new(build_datasets(&raw_data, view))
-            .block(Self::widget_block())
+        let mut datasets = build_datasets(&raw_data, view, &self.theme, marker);
+        datasets.extend(markers_data.iter().map(|(event, data)| {
+            Dataset::default()
+                .name(event.kind.label())
+                .marker(marker)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(event.kind.color()))
+                .data(data)
+        }));
+
+        let chart = Chart::new(datasets)
+            .block(block)
             .x_axis(self.define_x_axis(view))
-            .y_axis(self.define_y_axis(view));
+            .y_axis(self.define_y_axis(view, &y_ticks));
+
+        self.render_widget(frame, chart);
+    }
 
-        frame.render_widget(chart);
+    fn render_widget<W: Widget>(&self, frame: &mut FrameRegion, widget: W) {
+        match self.ascii {
+            true => frame.render_widget(AsciiWidget(widget)),
+            false => frame.render_widget(widget),
+        }
     }
 
     fn widget_block<'a>() -> Block<'a> {
         Block::default().borders(Borders::ALL)
     }
 
-    fn define_x_axis(&self, metrics_view: &MetricView) -> Axis {
-        let (begin, end) = (metrics_view.span().begin(), metrics_view.span().end());
-        let labels = vec![
-            Span::from(relative_timestamp_label(begin)),
-            Span::from(relative_timestamp_label(end)),
-        ];
+    fn define_x_axis(&self, metrics_view: &MetricView) -> Axis<'_> {
+        let labels = x_axis_labels(metrics_view.span(), self.newest_left)
+            .into_iter()
+            .map(Span::from)
+            .collect();
 
         Axis::default()
             .style(Style::default().fg(Color::White))
-            .bounds([
-                calculate_x_value_of_timestamp(metrics_view.span().begin(), self.resolution),
-                calculate_x_value_of_timestamp(metrics_view.span().end(), self.resolution),
-            ])
+            .bounds(x_axis_bounds(metrics_view.span(), self.resolution, self.newest_left))
             .labels(labels)
             .labels_alignment(Alignment::Right)
     }
 
-    fn define_y_axis(&self, metrics_view: &MetricView) -> Axis {
-        const MINIMUM_UPPER_BOUND: f64 = 10.;
-        let upper_bound = (1.1 * metrics_view.max_f64()).max(MINIMUM_UPPER_BOUND);
-
-        let labels = vec![
-            Span::from("0"),
-            Span::from(metrics_view.concise_repr_of_value(upper_bound)),
-        ];
+    /// Defines the y-axis, bounded by the first and the last of the given ticks, which are evenly spaced
+    fn define_y_axis(&self, metrics_view: &MetricView, ticks: &[f64]) -> Axis<'_> {
+        let labels = ticks
+            .iter()
+            .map(|tick| match *tick == 0. {
+                true => "0".to_string(),
+                false => metrics_view.concise_repr_of_value(*tick),
+            })
+            .map(Span::from)
+            .collect();
 
         Axis::default()
-            .title(metrics_view.unit())
+            .title(metrics_view.unit().to_string())
             .style(Style::default().fg(Color::White))
-            .bounds([0., upper_bound]) // 0 to 1.1 * max(dataset.y)
+            .bounds([ticks[0], ticks[ticks.len() - 1]]) // min(0, 1.1 * min(dataset.y)) to 1.1 * max(dataset.y)
             .labels(labels)
     }
 }
 
+/// Returns the x values of the left and right sides of the chart
+fn x_axis_bounds(span: &TimeSpan, resolution: Milliseconds, newest_left: bool) -> [f64; 2] {
+    let begin = calculate_x_value_of_timestamp(span.begin(), resolution);
+    let end = calculate_x_value_of_timestamp(span.end(), resolution);
+
+    match newest_left {
+        true => [orient_x_value(end, true), orient_x_value(begin, true)],
+        false => [begin, end],
+    }
+}
+
+/// Returns the labels of the left and right sides of the chart
+fn x_axis_labels(span: &TimeSpan, newest_left: bool) -> Vec<String> {
+    let mut labels = vec![
+        relative_timestamp_label(span.begin()),
+        relative_timestamp_label(span.end()),
+    ];
+    if newest_left {
+        labels.reverse();
+    }
+
+    labels
+}
+
+/// Mirrors an x value around the current time when the newest metrics are drawn on the left of the chart
+fn orient_x_value(x: f64, newest_left: bool) -> f64 {
+    match newest_left {
+        true => -x,
+        false => x,
+    }
+}
+
+/// Mirrors the series around the current time, so that the newest metrics are drawn on the left of the chart
+///
+/// The points of each series are reversed, so that they remain ordered by increasing x values.
+fn mirror_series(series: Vec<Vec<(f64, f64)>>) -> Vec<Vec<(f64, f64)>> {
+    series
+        .into_iter()
+        .map(|data| {
+            data.into_iter()
+                .rev()
+                .map(|(x, y)| (orient_x_value(x, true), y))
+                .collect()
+        })
+        .collect()
+}
+
+fn y_upper_bound(metrics_view: &MetricView) -> f64 {
+    const MINIMUM_UPPER_BOUND: f64 = 10.;
+    (1.1 * metrics_view.max_f64()).max(MINIMUM_UPPER_BOUND)
+}
+
+/// The lower bound of the chart is 0, unless some metrics are negative
+fn y_lower_bound(metrics_view: &MetricView) -> f64 {
+    (1.1 * metrics_view.min_f64()).min(0.)
+}
+
+/// Returns evenly spaced ticks covering the range from `lower` to `upper`, spaced by 1, 2 or 5 times a power of 10
+///
+/// The first and last ticks are the multiples of the spacing surrounding `lower` and `upper`, so that there are at
+/// most `max_ticks` ticks, or a few more when the range is not well divided by the spacing. At least two ticks are
+/// returned.
+fn nice_ticks(lower: f64, upper: f64, max_ticks: usize) -> Vec<f64> {
+    let range = nice_number(upper - lower, false);
+    let spacing = nice_number(range / (max_ticks.max(2) - 1) as f64, true);
+
+    let first = (lower / spacing).floor() as i64;
+    let last = ((upper / spacing).ceil() as i64).max(first + 1);
+
+    // Ticks are computed from their index, so that floating-point errors do not accumulate
+    (first..=last).map(|index| index as f64 * spacing).collect()
+}
+
+/// Returns a number close to `value`, which is 1, 2 or 5 times a power of 10
+///
+/// # Arguments
+///  * `value`: The number to approximate
+///  * `round`: If true, the closest nice number is returned. Otherwise, the nice number is greater than `value`.
+fn nice_number(value: f64, round: bool) -> f64 {
+    if value <= 0. || !value.is_finite() {
+        return 1.;
+    }
+
+    let magnitude = 10_f64.powf(value.log10().floor());
+    let fraction = value / magnitude;
+
+    let nice_fraction = match round {
+        true if fraction < 1.5 => 1.,
+        true if fraction < 3. => 2.,
+        true if fraction < 7. => 5.,
+        false if fraction <= 1. => 1.,
+        false if fraction <= 2. => 2.,
+        false if fraction <= 5. => 5.,
+        _ => 10.,
+    };
+
+    nice_fraction * magnitude
+}
+
+#[cfg(test)]
+mod test_nice_ticks {
+    use rstest::*;
+
+    use crate::ui::chart::{nice_number, nice_ticks};
+
+    fn assert_ticks_eq(ticks: Vec<f64>, expected: &[f64]) {
+        assert_eq!(ticks.len(), expected.len(), "{:?} != {:?}", ticks, expected);
+        for (tick, expected_tick) in ticks.iter().zip(expected) {
+            assert!((tick - expected_tick).abs() < 1e-9, "{:?} != {:?}", ticks, expected);
+        }
+    }
+
+    #[rstest]
+    #[case(0., 37.482, &[0., 10., 20., 30., 40.])]
+    #[case(0., 110., &[0., 50., 100., 150.])]
+    #[case(0., 10., &[0., 2., 4., 6., 8., 10.])]
+    #[case(0., 0.37, &[0., 0.1, 0.2, 0.3, 0.4])]
+    #[case(-110., 110., &[-200., -100., 0., 100., 200.])]
+    #[case(0., 5_368_709_120., &[0., 2e9, 4e9, 6e9])]
+    fn test_should_compute_round_ticks_covering_range(
+        #[case] lower: f64,
+        #[case] upper: f64,
+        #[case] expected: &[f64],
+    ) {
+        assert_ticks_eq(nice_ticks(lower, upper, 5), expected);
+    }
+
+    #[rstest]
+    #[case(0., 37.482)]
+    #[case(-12.5, 980.)]
+    #[case(0.001, 0.002)]
+    fn test_ticks_should_surround_range(#[case] lower: f64, #[case] upper: f64) {
+        let ticks = nice_ticks(lower, upper, 5);
+
+        assert!(ticks[0] <= lower && ticks[ticks.len() - 1] >= upper);
+    }
+
+    #[test]
+    fn test_should_return_two_ticks_for_empty_range() {
+        assert_ticks_eq(nice_ticks(10., 10., 5), &[10., 10.2]);
+    }
+
+    #[rstest]
+    #[case(37.482, false, 50.)]
+    #[case(37.482, true, 50.)]
+    #[case(12., false, 20.)]
+    #[case(12., true, 10.)]
+    #[case(0.27, true, 0.2)]
+    #[case(7., true, 10.)]
+    #[case(1000., false, 1000.)]
+    fn test_should_approximate_number_with_1_2_or_5_times_power_of_10(
+        #[case] value: f64,
+        #[case] round: bool,
+        #[case] expected: f64,
+    ) {
+        assert!((nice_number(value, round) - expected).abs() < 1e-9);
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum ProcessEventKind {
+    Spawn,
+    Death,
+}
+
+impl ProcessEventKind {
+    fn label(&self) -> &'static str {
+        match self {
+            ProcessEventKind::Spawn => "Spawned",
+            ProcessEventKind::Death => "Died",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            ProcessEventKind::Spawn => SPAWN_MARKER_COLOR,
+            ProcessEventKind::Death => DEATH_MARKER_COLOR,
+        }
+    }
+}
+
+/// An event of the lifetime of a process, which can be marked on the chart
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+struct ProcessEvent {
+    kind: ProcessEventKind,
+    timestamp: Timestamp,
+}
+
+/// Returns the spawn of the process, and its death if the process is dead
+fn process_events(process: &ProcessMetadata) -> Vec<ProcessEvent> {
+    let running_span = process.running_span();
+    let mut events = vec![ProcessEvent {
+        kind: ProcessEventKind::Spawn,
+        timestamp: running_span.begin(),
+    }];
+
+    if process.status() == Status::DEAD {
+        events.push(ProcessEvent {
+            kind: ProcessEventKind::Death,
+            timestamp: running_span.end(),
+        });
+    }
+
+    events
+}
+
+/// Returns the x value at which a marker should be drawn for the given timestamp, or `None` if the timestamp is not
+/// covered by the span displayed by the chart
+fn marker_x_value(timestamp: Timestamp, displayed_span: &TimeSpan, resolution: Milliseconds) -> Option<f64> {
+    displayed_span
+        .contains(timestamp)
+        .then(|| calculate_x_value_of_timestamp(timestamp, resolution))
+}
+
+/// Renders a widget, then replaces the non-ASCII symbols it drew with ASCII equivalents
+pub struct AsciiWidget<W: Widget>(pub W);
+
+impl<W: Widget> Widget for AsciiWidget<W> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.0.render(area, buf);
+
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+
+                if !cell.symbol.is_ascii() {
+                    let ascii_symbol = ascii_equivalent(&cell.symbol);
+                    cell.set_symbol(ascii_symbol);
+                }
+            }
+        }
+    }
+}
+
+/// Returns an ASCII symbol resembling the given symbol
+fn ascii_equivalent(symbol: &str) -> &'static str {
+    match symbol.chars().next() {
+        Some('─' | '━' | '═' | '┄' | '┅' | '╌' | '╍') => "-",
+        Some('│' | '┃' | '║' | '┆' | '┇' | '╎' | '╏') => "|",
+        Some('\u{2500}'..='\u{257f}') => "+", // Other box drawing symbols are corners and junctions
+        _ => "*",
+    }
+}
+
 type Milliseconds = u128;
 
 fn calculate_x_value_of_timestamp(timestamp: Timestamp, resolution: Milliseconds) -> f64 {
@@ -147,6 +509,112 @@ mod test_x_value_calculation {
     }
 }
 
+/// Indicates if fewer metrics were collected during the span of the view than expected from the collection period
+///
+/// Only the time between the first and the last metrics of the span is accounted for, so that a process spawned during
+/// the span is not considered to have gaps.
+///
+/// # Arguments
+///  * `metrics_view`: The metrics drawn on the chart
+///  * `collection_period`: The time expected between two collected metrics
+fn has_gaps(metrics_view: &MetricView, collection_period: Duration) -> bool {
+    let timestamps: Vec<Timestamp> = metrics_view
+        .as_slice()
+        .iter()
+        .map(|dm| dm.timestamp)
+        .filter(|timestamp| metrics_view.span().contains(*timestamp))
+        .collect();
+
+    let (Some(first), Some(last)) = (timestamps.first(), timestamps.last()) else {
+        return false;
+    };
+    if collection_period.is_zero() {
+        return false;
+    }
+
+    let periods = last.duration_since(first).as_secs_f64() / collection_period.as_secs_f64();
+    let expected_count = periods.round() as usize + 1;
+
+    timestamps.len() < expected_count
+}
+
+#[cfg(test)]
+mod test_gap_detection {
+    use std::time::Duration;
+
+    use crate::core::collection::ProcessData;
+    use crate::core::metrics::PercentMetric;
+    use crate::core::time::test_utils::advance_time_and_refresh_timestamp;
+    use crate::core::time::{Span, Timestamp};
+    use crate::ui::chart::has_gaps;
+
+    const PERIOD: Duration = Duration::from_secs(1);
+
+    /// Pushes a metric after each of the given delays
+    fn build_process_data(delays: &[Duration]) -> ProcessData<PercentMetric> {
+        let mut process_data = ProcessData::new();
+        for delay in delays {
+            advance_time_and_refresh_timestamp(*delay);
+            process_data.push(PercentMetric::new(10.));
+        }
+
+        process_data
+    }
+
+    #[test]
+    fn test_should_not_detect_gaps_when_metrics_are_collected_every_period() {
+        let origin_ts = Timestamp::now();
+        let process_data = build_process_data(&[PERIOD; 10]);
+
+        let view = process_data.view(Span::new(origin_ts, Timestamp::now()));
+
+        assert!(!has_gaps(&view, PERIOD));
+    }
+
+    #[test]
+    fn test_should_tolerate_jitter_of_collections() {
+        let origin_ts = Timestamp::now();
+        let delays = [900, 1100, 1050, 950, 1000].map(Duration::from_millis);
+        let process_data = build_process_data(&delays);
+
+        let view = process_data.view(Span::new(origin_ts, Timestamp::now()));
+
+        assert!(!has_gaps(&view, PERIOD));
+    }
+
+    #[test]
+    fn test_should_detect_gaps_when_view_has_fewer_metrics_than_span_implies() {
+        let origin_ts = Timestamp::now();
+        // Three collections are skipped between the second and the third metrics
+        let delays = [PERIOD, PERIOD, 4 * PERIOD, PERIOD];
+        let process_data = build_process_data(&delays);
+
+        let view = process_data.view(Span::new(origin_ts, Timestamp::now()));
+
+        assert!(has_gaps(&view, PERIOD));
+    }
+
+    #[test]
+    fn test_should_not_detect_gaps_before_first_metric_of_process() {
+        advance_time_and_refresh_timestamp(Duration::from_secs(30));
+        let origin_ts = Timestamp::now() - Duration::from_secs(30);
+        let process_data = build_process_data(&[PERIOD; 5]);
+
+        let view = process_data.view(Span::new(origin_ts, Timestamp::now()));
+
+        assert!(!has_gaps(&view, PERIOD));
+    }
+
+    #[test]
+    fn test_should_not_detect_gaps_in_empty_view() {
+        let process_data = ProcessData::<PercentMetric>::new();
+
+        let view = process_data.view(Span::new(Timestamp::now(), Timestamp::now() + Duration::from_secs(10)));
+
+        assert!(!has_gaps(&view, PERIOD));
+    }
+}
+
 fn build_raw_vecs(metrics_view: &MetricView, resolution: Milliseconds) -> Vec<Vec<(f64, f64)>> {
     let mut data_vecs: Vec<_> = Vec::new();
     let metrics_cardinality = metrics_view.last_or_default().cardinality();
@@ -171,9 +639,12 @@ fn build_raw_vecs(metrics_view: &MetricView, resolution: Milliseconds) -> Vec<Ve
     data_vecs
 }
 
-fn build_datasets<'a>(raw_data: &'a [Vec<(f64, f64)>], metrics_view: &MetricView) -> Vec<Dataset<'a>> {
-    const COLORS: [Color; 2] = [Color::Blue, Color::Green];
-
+fn build_datasets<'a>(
+    raw_data: &'a [Vec<(f64, f64)>],
+    metrics_view: &MetricView,
+    theme: &Theme,
+    marker: symbols::Marker,
+) -> Vec<Dataset<'a>> {
     raw_data
         .iter()
         .enumerate()
@@ -184,11 +655,11 @@ fn build_datasets<'a>(raw_data: &'a [Vec<(f64, f64)>], metrics_view: &MetricView
                 // panic should never happen as index should never be greater than cardinality:
                 .expect("Invalid index when building dataframe");
 
-            let ds_style = Style::default().fg(COLORS[index]);
+            let ds_style = Style::default().fg(theme.chart_color(index));
 
             Dataset::default()
                 .name(name)
-                .marker(symbols::Marker::Braille)
+                .marker(marker)
                 .graph_type(GraphType::Line)
                 .style(ds_style)
                 .data(data)
@@ -196,12 +667,86 @@ fn build_datasets<'a>(raw_data: &'a [Vec<(f64, f64)>], metrics_view: &MetricView
         .collect()
 }
 
+#[cfg(test)]
+mod test_process_event_markers {
+    use std::time::Duration;
+
+    use crate::core::process::ProcessMetadata;
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::core::time::{Span, Timestamp};
+    use crate::ui::chart::{marker_x_value, process_events, ProcessEvent, ProcessEventKind};
+
+    #[test]
+    fn test_should_only_mark_spawn_of_running_process() {
+        let spawn_time = Timestamp::now();
+        let process = ProcessMetadata::new(1, "command", spawn_time);
+
+        assert_eq!(
+            process_events(&process),
+            vec![ProcessEvent {
+                kind: ProcessEventKind::Spawn,
+                timestamp: spawn_time
+            }]
+        );
+    }
+
+    #[test]
+    fn test_should_mark_spawn_and_death_of_dead_process() {
+        let spawn_time = Timestamp::now();
+        let mut process = ProcessMetadata::new(1, "command", spawn_time);
+        process.mark_dead();
+
+        assert_eq!(
+            process_events(&process).iter().map(|e| e.kind).collect::<Vec<_>>(),
+            vec![ProcessEventKind::Spawn, ProcessEventKind::Death]
+        );
+    }
+
+    #[test]
+    fn test_marker_should_be_placed_relatively_to_current_time() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let span = Span::from_duration(Duration::from_secs(60));
+        let event_time = Timestamp::now() - Duration::from_secs(10);
+
+        assert_eq!(marker_x_value(event_time, &span, 1000), Some(-10.));
+    }
+
+    #[test]
+    fn test_marker_should_be_placed_on_bounds_of_span() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let span = Span::from_duration(Duration::from_secs(60));
+
+        assert_eq!(marker_x_value(span.begin(), &span, 1000), Some(-60.));
+        assert_eq!(marker_x_value(span.end(), &span, 1000), Some(0.));
+    }
+
+    #[test]
+    fn test_should_not_place_marker_of_event_older_than_span() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let span = Span::from_duration(Duration::from_secs(60));
+        let event_time = span.begin() - Duration::from_millis(1);
+
+        assert_eq!(marker_x_value(event_time, &span, 1000), None);
+    }
+
+    #[test]
+    fn test_should_not_place_marker_of_event_after_span() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let span = Span::new(
+            Timestamp::now() - Duration::from_secs(60),
+            Timestamp::now() - Duration::from_secs(30),
+        );
+
+        assert_eq!(marker_x_value(Timestamp::now(), &span, 1000), None);
+    }
+}
+
 #[cfg(test)]
 mod test_raw_data_from_metrics_view {
     use std::time::Duration;
 
     use crate::core::collection::ProcessData;
-    use crate::core::metrics::IOMetric;
+    use crate::core::metrics::{IOMetric, SocketStatesMetric};
     use crate::core::time::test_utils::advance_time_and_refresh_timestamp;
     use crate::core::time::{Span, Timestamp};
     use crate::ui::chart::build_raw_vecs;
@@ -222,4 +767,246 @@ mod test_raw_data_from_metrics_view {
             vec![vec![(-1000.0, 10.0), (0.0, 30.0)], vec![(-1000.0, 20.0), (0.0, 40.0)]]
         );
     }
+
+    #[test]
+    fn test_should_build_one_series_per_component_of_metric() {
+        let origin_ts = Timestamp::now();
+        let mut process_data = ProcessData::<SocketStatesMetric>::new();
+        process_data.push(SocketStatesMetric::new(1, 2, 3));
+        advance_time_and_refresh_timestamp(Duration::from_secs(1));
+        process_data.push(SocketStatesMetric::new(4, 5, 6));
+
+        let metrics_view = process_data.view(Span::new(origin_ts, Timestamp::now()));
+        let raw_vecs = build_raw_vecs(&metrics_view, 1);
+
+        assert_eq!(
+            raw_vecs,
+            vec![
+                vec![(-1000.0, 1.0), (0.0, 4.0)],
+                vec![(-1000.0, 2.0), (0.0, 5.0)],
+                vec![(-1000.0, 3.0), (0.0, 6.0)]
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_newest_left {
+    use std::time::Duration;
+
+    use crate::config::Theme;
+    use crate::core::collection::ProcessData;
+    use crate::core::metrics::{IOMetric, PercentMetric};
+    use crate::core::time::test_utils::{
+        advance_time_and_refresh_timestamp, setup_fake_clock_to_prevent_substract_overflow,
+    };
+    use crate::core::time::{Span, Timestamp};
+    use crate::ui::chart::{build_raw_vecs, mirror_series, x_axis_bounds, x_axis_labels, MetricsChart};
+    use crate::ui::terminal::Terminal;
+
+    #[test]
+    fn test_should_mirror_series_with_newest_metric_first() {
+        let origin_ts = Timestamp::now();
+        let mut process_data = ProcessData::<IOMetric>::new();
+        process_data.push(IOMetric::new(10, 20));
+        advance_time_and_refresh_timestamp(Duration::from_secs(1));
+        process_data.push(IOMetric::new(30, 40));
+
+        let metrics_view = process_data.view(Span::new(origin_ts, Timestamp::now()));
+        let raw_vecs = mirror_series(build_raw_vecs(&metrics_view, 1));
+
+        assert_eq!(
+            raw_vecs,
+            vec![vec![(0.0, 30.0), (1000.0, 10.0)], vec![(0.0, 40.0), (1000.0, 20.0)]]
+        );
+    }
+
+    #[test]
+    fn test_should_reverse_axis_bounds_and_labels() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let span = Span::from_duration(Duration::from_secs(60));
+
+        assert_eq!(x_axis_bounds(&span, 1000, false), [-60., 0.]);
+        assert_eq!(x_axis_bounds(&span, 1000, true), [0., 60.]);
+
+        let labels = x_axis_labels(&span, false);
+        let reversed_labels = x_axis_labels(&span, true);
+        assert_eq!(reversed_labels, labels.into_iter().rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_should_draw_newest_metric_on_the_left() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let origin_ts = Timestamp::now();
+        let mut process_data = ProcessData::<PercentMetric>::new();
+        for _ in 0..30 {
+            process_data.push(PercentMetric::new(0.));
+            advance_time_and_refresh_timestamp(Duration::from_secs(1));
+        }
+        process_data.push(PercentMetric::new(100.));
+        let view = process_data.view(Span::new(origin_ts, Timestamp::now()));
+
+        let mut terminal = Terminal::from_size(60, 20).unwrap();
+        let chart = MetricsChart::new(Duration::from_secs(1), Theme::default(), true).with_newest_left();
+        terminal.draw(|fr| chart.render(fr, Some(&view), None, false)).unwrap();
+
+        // The spike of the newest metric is drawn at the top of the chart, next to the y axis
+        let top_line = &terminal.buffer_lines()[2];
+        let spike = top_line.rfind('*').expect("The spike is not drawn");
+        assert!(
+            spike < top_line.len() / 2,
+            "The spike is not on the left: {:?}",
+            top_line
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_multi_component_rendering {
+    use std::time::Duration;
+
+    use crate::config::Theme;
+    use crate::core::collection::ProcessData;
+    use crate::core::metrics::SocketStatesMetric;
+    use crate::core::time::test_utils::advance_time_and_refresh_timestamp;
+    use crate::core::time::{Span, Timestamp};
+    use crate::ui::chart::MetricsChart;
+    use crate::ui::terminal::Terminal;
+
+    #[test]
+    fn test_should_indicate_gaps_in_drawn_metrics() {
+        let origin_ts = Timestamp::now();
+        let mut process_data = ProcessData::<SocketStatesMetric>::new();
+        for i in 0..5 {
+            process_data.push(SocketStatesMetric::new(i, 2 * i, 3 * i));
+            advance_time_and_refresh_timestamp(Duration::from_secs(2));
+        }
+        let view = process_data.view(Span::new(origin_ts, Timestamp::now()));
+
+        let mut terminal = Terminal::from_size(80, 30).unwrap();
+        let chart = MetricsChart::new(Duration::from_secs(1), Theme::default(), false)
+            .with_collection_period(Duration::from_secs(1));
+        terminal.draw(|fr| chart.render(fr, Some(&view), None, false)).unwrap();
+
+        assert!(terminal.buffer_lines()[0].contains("Data gaps present"));
+    }
+
+    #[test]
+    fn test_should_label_every_component_of_metric_in_legend() {
+        let origin_ts = Timestamp::now();
+        let mut process_data = ProcessData::<SocketStatesMetric>::new();
+        for i in 0..10 {
+            process_data.push(SocketStatesMetric::new(i, 2 * i, 3 * i));
+            advance_time_and_refresh_timestamp(Duration::from_secs(1));
+        }
+        let view = process_data.view(Span::new(origin_ts, Timestamp::now()));
+
+        let mut terminal = Terminal::from_size(80, 30).unwrap();
+        let chart = MetricsChart::new(Duration::from_secs(1), Theme::default(), false);
+        terminal.draw(|fr| chart.render(fr, Some(&view), None, false)).unwrap();
+
+        let lines = terminal.buffer_lines();
+        for label in ["Established: 9", "Time wait  : 18", "Close wait : 27"] {
+            assert!(
+                lines.iter().any(|line| line.contains(label)),
+                "{} is not in the legend",
+                label
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_y_bounds {
+    use std::time::Duration;
+
+    use crate::core::collection::ProcessData;
+    use crate::core::metrics::SignedBytesMetric;
+    use crate::core::time::test_utils::advance_time_and_refresh_timestamp;
+    use crate::core::time::{Span, Timestamp};
+    use crate::ui::chart::{y_lower_bound, y_upper_bound};
+
+    fn build_process_data(values: &[i64]) -> (ProcessData<SignedBytesMetric>, Span) {
+        let origin_ts = Timestamp::now();
+        let mut process_data = ProcessData::<SignedBytesMetric>::new();
+        for value in values {
+            process_data.push(SignedBytesMetric::new(*value));
+            advance_time_and_refresh_timestamp(Duration::from_secs(1));
+        }
+
+        (process_data, Span::new(origin_ts, Timestamp::now()))
+    }
+
+    #[test]
+    fn test_lower_bound_should_be_zero_when_all_metrics_are_positive() {
+        let (process_data, span) = build_process_data(&[10, 100]);
+
+        assert_eq!(y_lower_bound(&process_data.view(span)), 0.);
+    }
+
+    #[test]
+    fn test_lower_bound_should_be_below_lowest_negative_metric() {
+        let (process_data, span) = build_process_data(&[100, -100]);
+        let view = process_data.view(span);
+
+        assert!((y_lower_bound(&view) + 110.).abs() < 1e-9);
+        assert!((y_upper_bound(&view) - 110.).abs() < 1e-9);
+    }
+}
+
+#[cfg(test)]
+mod test_ascii_rendering {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::config::Theme;
+    use crate::core::collection::ProcessData;
+    use crate::core::metrics::IOMetric;
+    use crate::core::time::test_utils::advance_time_and_refresh_timestamp;
+    use crate::core::time::{Span, Timestamp};
+    use crate::ui::chart::{ascii_equivalent, MetricsChart};
+    use crate::ui::terminal::Terminal;
+
+    fn render_sample_series(ascii: bool) -> Vec<String> {
+        let origin_ts = Timestamp::now();
+        let mut process_data = ProcessData::<IOMetric>::new();
+        for i in 0..10 {
+            process_data.push(IOMetric::new(i * 100, 1000 - i * 100));
+            advance_time_and_refresh_timestamp(Duration::from_secs(1));
+        }
+        let view = process_data.view(Span::new(origin_ts, Timestamp::now()));
+
+        let mut terminal = Terminal::from_size(60, 20).unwrap();
+        let chart = MetricsChart::new(Duration::from_secs(1), Theme::default(), ascii);
+        terminal.draw(|fr| chart.render(fr, Some(&view), None, false)).unwrap();
+
+        terminal.buffer_lines()
+    }
+
+    #[test]
+    fn test_ascii_chart_should_only_contain_ascii_characters() {
+        let lines = render_sample_series(true);
+
+        assert!(lines.iter().all(|line| line.is_ascii()));
+        assert!(lines.iter().any(|line| line.contains('*')));
+    }
+
+    #[test]
+    fn test_default_chart_should_draw_unicode_characters() {
+        let lines = render_sample_series(false);
+
+        assert!(lines.iter().any(|line| !line.is_ascii()));
+    }
+
+    #[rstest]
+    #[case("─", "-")]
+    #[case("│", "|")]
+    #[case("┌", "+")]
+    #[case("└", "+")]
+    #[case("•", "*")]
+    #[case("⣿", "*")]
+    fn test_should_replace_unicode_symbol_with_ascii_equivalent(#[case] symbol: &str, #[case] expected: &str) {
+        assert_eq!(ascii_equivalent(symbol), expected);
+    }
 }
@@ -0,0 +1,267 @@
+use tui::style::{Modifier, Style};
+use tui::text::{Span, Spans};
+
+use crate::core::collection::MetricCollector;
+use crate::core::connections::Connection;
+use crate::core::devices::Device;
+use crate::core::process::{ProcessMetadata, Status};
+use crate::core::time::Timestamp;
+use crate::ui::labels::relative_timestamp_label;
+use crate::ui::pane::render_pane;
+use crate::ui::processes::format_cpu_time;
+use crate::ui::terminal::FrameRegion;
+
+/// Width of the column of labels of the detail pane
+const LABEL_COL_WIDTH: usize = 18;
+
+/// A full-screen pane describing the selected process, with the latest metric of every collector
+pub struct DetailPane {
+    // The label and value of each row of the pane
+    rows: Vec<(String, String)>,
+}
+
+impl DetailPane {
+    /// Assembles the content of the pane
+    ///
+    /// # Arguments
+    ///  * `process`: The process to describe
+    ///  * `device`: The device storing most of the files opened by the process, if known
+    ///  * `collectors`: The collectors from which to read the latest metric of the process
+    pub fn new(process: &ProcessMetadata, device: Option<&Device>, collectors: &[Box<dyn MetricCollector>]) -> Self {
+        let mut rows = vec![
+            ("PID".to_string(), process.pid().to_string()),
+            ("TGID".to_string(), process.tgid().to_string()),
+            ("Command".to_string(), process.command().to_string()),
+        ];
+
+        if let Some(label) = process.label() {
+            rows.push(("Label".to_string(), label.to_string()));
+        }
+
+        rows.push(("Command line".to_string(), process.cmdline().to_string()));
+        rows.push(("Status".to_string(), process.status().to_string()));
+        rows.push(("Spawned".to_string(), relative_timestamp_label(process.spawn_time())));
+        // Metrics are only collected from the moment spv saw the process
+        rows.push((
+            "Observed since".to_string(),
+            relative_timestamp_label(process.first_seen()),
+        ));
+
+        if process.status() == Status::DEAD {
+            rows.push((
+                "Died".to_string(),
+                relative_timestamp_label(process.running_span().end()),
+            ));
+        } else {
+            let uptime = Timestamp::now().duration_since(&process.spawn_time());
+            rows.push(("Uptime".to_string(), format!("{}s", uptime.as_secs())));
+        }
+
+        // The CPU time is only tracked when the TIME+ column is displayed
+        if !process.cpu_time().is_zero() {
+            rows.push(("CPU time".to_string(), format_cpu_time(process.cpu_time())));
+        }
+
+        let isolation = if process.is_isolated() { "container" } else { "host" };
+        rows.push(("Namespaces".to_string(), isolation.to_string()));
+
+        if let Some(device) = device {
+            rows.push(("Main device".to_string(), device.to_string()));
+        }
+
+        for collector in collectors {
+            let metric = collector.overview().concise_repr(process.pid());
+            rows.push((collector.name().to_string(), metric));
+        }
+
+        Self { rows }
+    }
+
+    /// Lists the given TCP connections of the process below its metrics, one connection per row
+    pub fn with_connections(mut self, connections: &[Connection]) -> Self {
+        if connections.is_empty() {
+            self.rows.push(("Connections".to_string(), "none".to_string()));
+        }
+
+        for (index, connection) in connections.iter().enumerate() {
+            let label = if index == 0 { "Connections" } else { "" };
+            self.rows.push((label.to_string(), connection.to_string()));
+        }
+
+        self
+    }
+
+    pub fn render(&self, frame: &mut FrameRegion) {
+        let lines: Vec<Spans> = self
+            .rows
+            .iter()
+            .map(|(label, value)| {
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{:<width$}", label, width = LABEL_COL_WIDTH),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(value.as_str()),
+                ])
+            })
+            .collect();
+
+        render_pane(frame, "Process details", lines);
+    }
+}
+
+#[cfg(test)]
+mod test_detail_pane {
+    use std::time::Duration;
+
+    use crate::core::collection::{MetricCollector, ProbeCollector};
+    use crate::core::connections::Connection;
+    use crate::core::devices::{Device, DeviceId};
+    use crate::core::metrics::{Metric, PercentMetric};
+    use crate::core::probe::fakes::FakeProbe;
+    use crate::core::process::ProcessMetadata;
+    use crate::core::time::test_utils::setup_fake_clock_to_prevent_substract_overflow;
+    use crate::core::time::Timestamp;
+    use crate::ui::detail::DetailPane;
+    use crate::ui::terminal::Terminal;
+
+    fn value_of<'a>(pane: &'a DetailPane, label: &str) -> Option<&'a str> {
+        pane.rows.iter().find(|(l, _)| l == label).map(|(_, v)| v.as_str())
+    }
+
+    fn collectors() -> Vec<Box<dyn MetricCollector>> {
+        let probe = FakeProbe::from_percent_map(hashmap!(123 => 42.));
+        let mut collector = ProbeCollector::new(probe);
+        collector.collect(&[123]).unwrap();
+
+        vec![Box::new(collector)]
+    }
+
+    #[test]
+    fn test_should_describe_metadata_of_process() {
+        let process = ProcessMetadata::new(123, "firefox", Timestamp::now()).with_tgid(100);
+
+        let pane = DetailPane::new(&process, None, &[]);
+
+        assert_eq!(value_of(&pane, "PID"), Some("123"));
+        assert_eq!(value_of(&pane, "TGID"), Some("100"));
+        assert_eq!(value_of(&pane, "Command"), Some("firefox"));
+        assert_eq!(value_of(&pane, "Status"), Some("running"));
+        assert_eq!(value_of(&pane, "Namespaces"), Some("host"));
+        assert_eq!(value_of(&pane, "Died"), None);
+    }
+
+    #[test]
+    fn test_should_only_describe_label_of_labelled_process() {
+        let mut process = ProcessMetadata::new(123, "java", Timestamp::now()).with_cmdline("java -jar kafka.jar");
+        assert_eq!(value_of(&DetailPane::new(&process, None, &[]), "Label"), None);
+
+        process.set_label("kafka");
+        let pane = DetailPane::new(&process, None, &[]);
+
+        assert_eq!(value_of(&pane, "Label"), Some("kafka"));
+        assert_eq!(value_of(&pane, "Command line"), Some("java -jar kafka.jar"));
+    }
+
+    #[test]
+    fn test_should_describe_main_device_when_known() {
+        let process = ProcessMetadata::new(123, "postgres", Timestamp::now());
+        assert_eq!(value_of(&DetailPane::new(&process, None, &[]), "Main device"), None);
+
+        let device = Device::new(DeviceId::new(259, 2), Some("nvme0n1p2".to_string()));
+        let pane = DetailPane::new(&process, Some(&device), &[]);
+
+        assert_eq!(value_of(&pane, "Main device"), Some("nvme0n1p2 (259:2)"));
+    }
+
+    #[test]
+    fn test_should_distinguish_spawn_of_process_from_its_observation() {
+        setup_fake_clock_to_prevent_substract_overflow();
+        let process = ProcessMetadata::new(123, "postgres", Timestamp::now() - Duration::from_secs(90));
+
+        let pane = DetailPane::new(&process, None, &[]);
+
+        assert_eq!(value_of(&pane, "Spawned"), Some("1m 30s ago"));
+        assert_eq!(value_of(&pane, "Observed since"), Some("now"));
+    }
+
+    #[test]
+    fn test_should_describe_death_of_dead_process() {
+        let mut process = ProcessMetadata::new(123, "firefox", Timestamp::now());
+        process.mark_dead();
+
+        let pane = DetailPane::new(&process, None, &[]);
+
+        assert_eq!(value_of(&pane, "Status"), Some("dead"));
+        assert!(value_of(&pane, "Died").is_some());
+        assert_eq!(value_of(&pane, "Uptime"), None);
+    }
+
+    #[test]
+    fn test_should_only_describe_cpu_time_once_tracked() {
+        let mut process = ProcessMetadata::new(123, "firefox", Timestamp::now());
+        assert_eq!(value_of(&DetailPane::new(&process, None, &[]), "CPU time"), None);
+
+        process.set_cpu_time(Duration::from_secs(61));
+
+        assert_eq!(
+            value_of(&DetailPane::new(&process, None, &[]), "CPU time"),
+            Some("01:01.00")
+        );
+    }
+
+    #[test]
+    fn test_should_describe_latest_metric_of_every_collector() {
+        let process = ProcessMetadata::new(123, "firefox", Timestamp::now());
+        let collectors = collectors();
+
+        let pane = DetailPane::new(&process, None, &collectors);
+
+        let expected = PercentMetric::new(42.).concise_repr();
+        assert_eq!(value_of(&pane, collectors[0].name()), Some(expected.as_str()));
+    }
+
+    #[test]
+    fn test_should_list_connections_one_per_row() {
+        let process = ProcessMetadata::new(123, "nginx", Timestamp::now());
+        let connections = [
+            Connection::new("0.0.0.0:80".parse().unwrap(), "0.0.0.0:0".parse().unwrap(), "LISTEN"),
+            Connection::new(
+                "10.0.2.15:80".parse().unwrap(),
+                "10.0.2.2:51234".parse().unwrap(),
+                "ESTABLISHED",
+            ),
+        ];
+
+        let pane = DetailPane::new(&process, None, &[]).with_connections(&connections);
+
+        assert_eq!(value_of(&pane, "Connections"), Some("0.0.0.0:80 -> 0.0.0.0:0 LISTEN"));
+        assert_eq!(
+            pane.rows.last(),
+            Some(&("".to_string(), "10.0.2.15:80 -> 10.0.2.2:51234 ESTABLISHED".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_should_indicate_absence_of_connection() {
+        let process = ProcessMetadata::new(123, "bash", Timestamp::now());
+
+        let pane = DetailPane::new(&process, None, &[]).with_connections(&[]);
+
+        assert_eq!(value_of(&pane, "Connections"), Some("none"));
+    }
+
+    #[test]
+    fn test_should_render_rows_of_pane() {
+        let process = ProcessMetadata::new(123, "firefox", Timestamp::now());
+        let mut terminal = Terminal::from_size(60, 15).unwrap();
+
+        terminal
+            .draw(|frame| DetailPane::new(&process, None, &collectors()).render(frame))
+            .unwrap();
+
+        let lines = terminal.buffer_lines();
+        assert!(lines.iter().any(|l| l.contains("Command") && l.contains("firefox")));
+        assert!(lines.iter().any(|l| l.contains("Press Esc to go back")));
+    }
+}
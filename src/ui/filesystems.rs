@@ -0,0 +1,105 @@
+use crate::core::filesystems::FilesystemUsage;
+use crate::core::metrics::{BytesMetric, Metric};
+use crate::ui::pane::{listing, render_pane, truncate};
+use crate::ui::terminal::FrameRegion;
+
+/// Width of the column of mount points of the filesystem pane
+const MOUNT_POINT_COL_WIDTH: usize = 24;
+/// Width of the column of filesystem types of the filesystem pane
+const TYPE_COL_WIDTH: usize = 10;
+/// Width of the columns of used percents and of used bytes of the filesystem pane
+const VALUE_COL_WIDTH: usize = 12;
+
+/// A full-screen pane listing the disk space usage of each mounted filesystem
+pub struct FilesystemsPane {
+    rows: Vec<String>,
+}
+
+impl FilesystemsPane {
+    /// Assembles the content of the pane
+    ///
+    /// # Arguments
+    ///  * `filesystems`: The usage of each mounted filesystem, in the order in which they are listed
+    pub fn new(filesystems: &[FilesystemUsage]) -> Self {
+        let rows = filesystems
+            .iter()
+            .map(|fs| {
+                let percent = format!("{:.1}%", fs.used_percent());
+                format!(
+                    "{:<mp_width$}{:<type_width$}{:<value_width$}{:<value_width$}{}",
+                    truncate(fs.mount_point(), MOUNT_POINT_COL_WIDTH - 1),
+                    truncate(fs.fs_type(), TYPE_COL_WIDTH - 1),
+                    percent,
+                    bytes_repr(fs.used_bytes()),
+                    bytes_repr(fs.available_bytes()),
+                    mp_width = MOUNT_POINT_COL_WIDTH,
+                    type_width = TYPE_COL_WIDTH,
+                    value_width = VALUE_COL_WIDTH
+                )
+            })
+            .collect();
+
+        Self { rows }
+    }
+
+    pub fn render(&self, frame: &mut FrameRegion) {
+        let header = format!(
+            "{:<mp_width$}{:<type_width$}{:<value_width$}{:<value_width$}{}",
+            "Mount point",
+            "Type",
+            "Use%",
+            "Used",
+            "Available",
+            mp_width = MOUNT_POINT_COL_WIDTH,
+            type_width = TYPE_COL_WIDTH,
+            value_width = VALUE_COL_WIDTH
+        );
+
+        render_pane(frame, "Filesystems", listing(header, &self.rows, "No filesystem"));
+    }
+}
+
+fn bytes_repr(bytes: u64) -> String {
+    BytesMetric::new(bytes as usize).concise_repr()
+}
+
+#[cfg(test)]
+mod test_filesystems_pane {
+    use crate::core::filesystems::FilesystemUsage;
+    use crate::core::metrics::{BytesMetric, Metric};
+    use crate::ui::filesystems::FilesystemsPane;
+    use crate::ui::terminal::Terminal;
+
+    #[test]
+    fn test_should_list_used_percent_of_each_filesystem() {
+        let filesystems = vec![
+            FilesystemUsage::new("/", "ext4", 3 << 30, 1 << 30),
+            FilesystemUsage::new("/home", "btrfs", 0, 1 << 30),
+        ];
+        let mut terminal = Terminal::from_size(80, 10).unwrap();
+
+        terminal
+            .draw(|frame| FilesystemsPane::new(&filesystems).render(frame))
+            .unwrap();
+
+        let lines = terminal.buffer_lines();
+        let used = BytesMetric::new(3 << 30).concise_repr();
+        let available = BytesMetric::new(1 << 30).concise_repr();
+        assert!(lines[0].contains("Filesystems"));
+        assert!(lines[1].starts_with("│Mount point             Type      Use%        Used        Available"));
+        assert!(lines[2].starts_with(&format!(
+            "│/                       ext4      75.0%       {:<12}{}",
+            used, available
+        )));
+        assert!(lines[3].starts_with("│/home                   btrfs     0.0%"));
+    }
+
+    #[test]
+    fn test_should_indicate_absence_of_filesystem() {
+        let mut terminal = Terminal::from_size(60, 10).unwrap();
+
+        terminal.draw(|frame| FilesystemsPane::new(&[]).render(frame)).unwrap();
+
+        assert!(terminal.buffer_lines()[2].contains("No filesystem"));
+    }
+}
@@ -0,0 +1,127 @@
+use tui::style::{Color, Style};
+use tui::widgets::{BarChart, Block, Borders};
+
+use crate::core::histogram::{histogram, Bin};
+use crate::core::view::{MetricsOverview, ProcessesView};
+use crate::ui::chart::AsciiWidget;
+use crate::ui::terminal::FrameRegion;
+
+/// How many bins the latest metrics of processes are distributed into
+const BIN_COUNT: usize = 10;
+/// The amount of columns between two bars
+const BAR_GAP: u16 = 1;
+
+/// Renders the distribution of the latest metrics of the listed processes, in place of the chart
+pub struct MetricsHistogram {
+    ascii: bool,
+}
+
+impl MetricsHistogram {
+    /// Creates a new histogram
+    ///
+    /// # Arguments
+    ///  * `ascii`: If true, the histogram is only drawn with ASCII characters
+    pub fn new(ascii: bool) -> Self {
+        Self { ascii }
+    }
+
+    pub fn render(&self, frame: &mut FrameRegion, overview: &MetricsOverview, processes: &ProcessesView) {
+        let values: Vec<f64> = processes
+            .as_slice()
+            .iter()
+            .map(|pm| overview.last_or_default(pm.pid()).max_value())
+            .collect();
+        let bins = histogram(&values, BIN_COUNT);
+
+        let labels: Vec<String> = bins.iter().map(|bin| bin_label(bin, overview)).collect();
+        let data: Vec<(&str, u64)> = labels
+            .iter()
+            .zip(bins.iter())
+            .map(|(label, bin)| (label.as_str(), bin.count() as u64))
+            .collect();
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Processes per {} ", overview.unit()));
+        let inner_width = block.inner(frame.region()).width;
+
+        let bar_chart = BarChart::default()
+            .block(block)
+            .data(&data)
+            .bar_width(bar_width(inner_width, BIN_COUNT as u16))
+            .bar_gap(BAR_GAP)
+            .bar_style(Style::default().fg(Color::Cyan))
+            .value_style(Style::default().fg(Color::Black).bg(Color::Cyan));
+
+        match self.ascii {
+            true => frame.render_widget(AsciiWidget(bar_chart)),
+            false => frame.render_widget(bar_chart),
+        }
+    }
+}
+
+/// Labels a bin with its lower bound, as bars are too narrow to represent both of its bounds
+fn bin_label(bin: &Bin, overview: &MetricsOverview) -> String {
+    overview.concise_repr_of_value(bin.lower())
+}
+
+/// Returns the width of the bars filling the given width, separated by gaps
+fn bar_width(width: u16, bar_count: u16) -> u16 {
+    ((width + BAR_GAP) / bar_count).saturating_sub(BAR_GAP).max(1)
+}
+
+#[cfg(test)]
+mod test_bar_width {
+    use rstest::*;
+
+    use crate::ui::histogram::bar_width;
+
+    #[rstest]
+    #[case(109, 10)]
+    #[case(115, 10)]
+    #[case(10, 1)]
+    #[case(0, 1)]
+    fn test_bars_should_fill_width_with_gaps(#[case] width: u16, #[case] expected: u16) {
+        assert_eq!(bar_width(width, 10), expected);
+    }
+}
+
+#[cfg(test)]
+mod test_metrics_histogram {
+    use crate::core::collection::{MetricCollector, ProbeCollector};
+    use crate::core::probe::fakes::FakeProbe;
+    use crate::core::process::{ProcessIdentifier, ProcessMetadata};
+    use crate::core::time::Timestamp;
+    use crate::core::view::ProcessesView;
+    use crate::ui::histogram::MetricsHistogram;
+    use crate::ui::terminal::Terminal;
+
+    #[test]
+    fn test_should_render_bins_of_latest_metrics() {
+        let mut collector = ProbeCollector::new(FakeProbe::from_percent_map(hashmap!(1 => 5., 2 => 8., 3 => 100.)));
+        collector.collect(&[1, 2, 3]).unwrap();
+        let processes: Vec<_> = (1..=3)
+            .map(|pid| ProcessMetadata::new(pid, "cmd", Timestamp::now()))
+            .collect();
+        let mut terminal = Terminal::from_size(62, 12).unwrap();
+
+        terminal
+            .draw(|frame| {
+                MetricsHistogram::new(false).render(
+                    frame,
+                    &collector.overview(),
+                    &ProcessesView::new(&processes, None, ProcessIdentifier::Pid),
+                )
+            })
+            .unwrap();
+
+        let lines = terminal.buffer_lines();
+        assert!(lines[0].contains("Processes per %"));
+        // The two lowest processes fall in the first bin, and the highest one in the last bin
+        let values_line = &lines[lines.len() - 3];
+        assert!(values_line.starts_with("│██2██"));
+        assert!(values_line.ends_with("██1██ │"));
+        // Each bin is labelled with its lower bound
+        assert!(lines[lines.len() - 2].starts_with("│0.0   10.0"));
+    }
+}
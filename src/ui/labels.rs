@@ -28,6 +28,16 @@ pub fn relative_timestamp_label(timestamp: Timestamp) -> String {
     }
 }
 
+/// Returns a user-friendly representation of a process ordering criteria
+pub fn process_criteria_label(criteria: &ProcessOrdering) -> String {
+    match criteria {
+        ProcessOrdering::CurrentMetric => "Latest metric".to_string(),
+        ProcessOrdering::Metric(name) => name.to_string(),
+        ProcessOrdering::Pid => "Process PID".to_string(),
+        ProcessOrdering::Command => "Process command".to_string(),
+    }
+}
+
 #[cfg(test)]
 mod test_relative_time_label {
     use std::time::Duration;
@@ -99,12 +109,3 @@ mod test_relative_time_label {
         assert_eq!(label, "100h ago");
     }
 }
-
-/// Returns a user-friendly representation of a process ordering criteria
-pub fn process_criteria_label(criteria: &ProcessOrdering) -> String {
-    match criteria {
-        ProcessOrdering::CurrentMetric => "Latest metric".to_string(),
-        ProcessOrdering::Pid => "Process PID".to_string(),
-        ProcessOrdering::Command => "Process command".to_string(),
-    }
-}
@@ -1,12 +1,23 @@
 use tui::layout::{Constraint, Direction, Layout, Rect};
 
+/// The minimum width required to render spv: the process list, and a few columns of chart
+pub const MIN_WIDTH: u16 = 40;
+/// The minimum height required to render spv: the tabs, the metadata bar and a few rows of processes
+pub const MIN_HEIGHT: u16 = 8;
+
 pub struct UiLayout {
     main_chunks: Vec<Rect>,
     center_chunks: Vec<Rect>,
+    chart_only: bool,
 }
 
 impl UiLayout {
-    pub fn new(region: Rect) -> Self {
+    /// Splits the region in which spv is rendered
+    ///
+    /// # Arguments
+    ///  * `region`: The region of the whole UI
+    ///  * `processes_width`: The width of the process list
+    pub fn new(region: Rect, processes_width: u16) -> Self {
         let main_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints(
@@ -23,8 +34,8 @@ impl UiLayout {
             .direction(Direction::Horizontal)
             .constraints(
                 [
-                    Constraint::Length(30), // Processes constraint
-                    Constraint::Min(1),     // graph constraint
+                    Constraint::Length(processes_width), // Processes constraint
+                    Constraint::Min(1),                  // graph constraint
                 ]
                 .as_ref(),
             )
@@ -33,9 +44,28 @@ impl UiLayout {
         Self {
             main_chunks,
             center_chunks,
+            chart_only: false,
         }
     }
 
+    /// Gives the whole region to the chart, hiding the tabs, the process list and the metadata bar
+    ///
+    /// The chunks of the hidden elements are empty.
+    pub fn chart_only(region: Rect) -> Self {
+        let hidden = Rect::new(region.x, region.y, 0, 0);
+
+        Self {
+            main_chunks: vec![hidden, region, hidden],
+            center_chunks: vec![hidden, region],
+            chart_only: true,
+        }
+    }
+
+    /// Indicates if the chart is the only element of the UI
+    pub fn is_chart_only(&self) -> bool {
+        self.chart_only
+    }
+
     pub fn tabs_chunk(&self) -> Rect {
         self.main_chunks[0]
     }
@@ -53,6 +83,11 @@ impl UiLayout {
     }
 }
 
+/// Indicates if the region is too small to render spv
+pub fn is_too_small(region: Rect) -> bool {
+    region.width < MIN_WIDTH || region.height < MIN_HEIGHT
+}
+
 pub fn centered_area(parent_area: Rect, width: u16, height: u16) -> Rect {
     let width = width.min(parent_area.width);
     let height = height.min(parent_area.height);
@@ -68,6 +103,74 @@ pub fn centered_area(parent_area: Rect, width: u16, height: u16) -> Rect {
     )
 }
 
+#[cfg(test)]
+mod test_ui_layout {
+    use rstest::*;
+    use tui::layout::Rect;
+
+    use crate::ui::layout::{is_too_small, UiLayout, MIN_HEIGHT, MIN_WIDTH};
+
+    fn contains(region: Rect, chunk: Rect) -> bool {
+        chunk.left() >= region.left()
+            && chunk.right() <= region.right()
+            && chunk.top() >= region.top()
+            && chunk.bottom() <= region.bottom()
+    }
+
+    #[rstest]
+    #[case(Rect::new(0, 0, 0, 0))]
+    #[case(Rect::new(0, 0, 1, 1))]
+    #[case(Rect::new(0, 0, 1, 100))]
+    #[case(Rect::new(0, 0, 200, 1))]
+    #[case(Rect::new(0, 0, 20, 3))]
+    #[case(Rect::new(0, 0, 200, 100))]
+    fn test_chunks_should_fit_in_region_of_any_size(#[case] region: Rect) {
+        let layout = UiLayout::new(region, 30);
+
+        let chunks = [
+            layout.tabs_chunk(),
+            layout.processes_chunk(),
+            layout.chart_chunk(),
+            layout.metadata_chunk(),
+        ];
+
+        assert!(chunks.iter().all(|chunk| contains(region, *chunk)));
+    }
+
+    #[rstest]
+    #[case(Rect::new(0, 0, 200, 100))]
+    #[case(Rect::new(10, 5, 80, 20))]
+    fn test_chart_only_layout_should_give_whole_region_to_chart(#[case] region: Rect) {
+        let layout = UiLayout::chart_only(region);
+
+        assert!(layout.is_chart_only());
+        assert_eq!(layout.chart_chunk(), region);
+        assert_eq!(layout.tabs_chunk().area(), 0);
+        assert_eq!(layout.processes_chunk().area(), 0);
+        assert_eq!(layout.metadata_chunk().area(), 0);
+    }
+
+    #[test]
+    fn test_default_layout_should_not_be_chart_only() {
+        let layout = UiLayout::new(Rect::new(0, 0, 200, 100), 30);
+
+        assert!(!layout.is_chart_only());
+        assert_eq!(layout.chart_chunk().height, 98);
+        assert_eq!(layout.chart_chunk().width, 170);
+    }
+
+    #[rstest]
+    #[case(0, 0, true)]
+    #[case(1, 1, true)]
+    #[case(MIN_WIDTH - 1, MIN_HEIGHT, true)]
+    #[case(MIN_WIDTH, MIN_HEIGHT - 1, true)]
+    #[case(MIN_WIDTH, MIN_HEIGHT, false)]
+    #[case(200, 100, false)]
+    fn test_should_detect_regions_too_small_to_render(#[case] width: u16, #[case] height: u16, #[case] expected: bool) {
+        assert_eq!(is_too_small(Rect::new(0, 0, width, height)), expected);
+    }
+}
+
 #[cfg(test)]
 mod test_centered_area {
     use tui::layout::Rect;
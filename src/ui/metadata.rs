@@ -1,13 +1,14 @@
 use std::fmt::Write;
 use std::time::Duration;
 
-use tui::layout::Alignment;
+use tui::layout::{Alignment, Rect};
 use tui::style::{Color, Style};
 use tui::text::Span;
 use tui::widgets::Paragraph;
 
+use crate::core::churn::ChurnRate;
 use crate::core::ordering::ProcessOrdering;
-use crate::core::process::{ProcessMetadata, Status};
+use crate::core::process::{ProcessIdentifier, ProcessMetadata, Status};
 use crate::core::time::Timestamp;
 use crate::ctrl::Effect;
 use crate::ui::labels::{process_criteria_label, relative_timestamp_label};
@@ -19,6 +20,7 @@ const STATUS_DISPLAY_TIME: Duration = Duration::from_secs(2);
 pub struct MetadataBar {
     status: Effect,
     date_of_status: Timestamp,
+    churn_rate: ChurnRate,
 }
 
 impl Default for MetadataBar {
@@ -26,6 +28,7 @@ impl Default for MetadataBar {
         Self {
             status: Effect::None,
             date_of_status: Timestamp::app_init(),
+            churn_rate: ChurnRate::default(),
         }
     }
 }
@@ -36,7 +39,16 @@ impl MetadataBar {
         self.date_of_status = Timestamp::from_current_instant();
     }
 
-    pub fn render(&mut self, frame: &mut FrameRegion, process: Option<&ProcessMetadata>) {
+    pub fn set_churn_rate(&mut self, churn_rate: ChurnRate) {
+        self.churn_rate = churn_rate;
+    }
+
+    pub fn render(
+        &mut self,
+        frame: &mut FrameRegion,
+        process: Option<&ProcessMetadata>,
+        identifier: ProcessIdentifier,
+    ) {
         self.refresh_status();
 
         let original_area = frame.region();
@@ -46,10 +58,79 @@ impl MetadataBar {
             original_area.height,
         );
 
-        match self.status {
-            Effect::None => render_process_metadata(frame.with_region(area_with_margin), process),
+        match &self.status {
+            Effect::None
+            | Effect::StateDumpRequested
+            | Effect::SignalRequested(_)
+            | Effect::CommandRequested
+            | Effect::PresetSaveRequested(_)
+            | Effect::PresetSelectionRequested
+            | Effect::PresetLoadRequested(_)
+            | Effect::MetricsClearRequested(_)
+            | Effect::QuitConfirmed => {
+                let frame = frame.with_region(area_with_margin);
+                render_process_metadata(frame, process, identifier);
+                if self.churn_rate.is_high() {
+                    render_churn_warning(frame, self.churn_rate);
+                }
+            }
             Effect::ProcessesSorted(criteria) => {
-                render_process_sorted_status(frame.with_region(area_with_margin), criteria)
+                render_process_sorted_status(frame.with_region(area_with_margin), criteria.clone())
+            }
+            Effect::StateDumped(path) => {
+                let text = format!("State dumped to {}", path.display());
+                render_status(frame.with_region(area_with_margin), text)
+            }
+            Effect::StateDumpFailed => {
+                let text = "Could not dump state, see the logs for details".to_string();
+                render_status(frame.with_region(area_with_margin), text)
+            }
+            Effect::ProcessSignalled(pid, signal) => {
+                let text = format!("Sent {} to process {}", signal, pid);
+                render_status(frame.with_region(area_with_margin), text)
+            }
+            Effect::SignalFailed(pid, signal) => {
+                let text = format!("Could not send {} to process {}, see the logs for details", signal, pid);
+                render_status(frame.with_region(area_with_margin), text)
+            }
+            Effect::CommandNotConfigured => {
+                let text = "No command to run, see the --exec option".to_string();
+                render_status(frame.with_region(area_with_margin), text)
+            }
+            Effect::CommandFailed(pid) => {
+                let text = format!("The command run on process {} failed, see the logs for details", pid);
+                render_status(frame.with_region(area_with_margin), text)
+            }
+            Effect::PresetSaved(name) => {
+                let text = format!("View saved as preset {}", name);
+                render_status(frame.with_region(area_with_margin), text)
+            }
+            Effect::PresetSaveFailed(name) => {
+                let text = format!("Could not save preset {}, see the logs for details", name);
+                render_status(frame.with_region(area_with_margin), text)
+            }
+            Effect::PresetsUnavailable => {
+                let text = "Could not list the saved presets, see the logs for details".to_string();
+                render_status(frame.with_region(area_with_margin), text)
+            }
+            Effect::PresetLoaded(name) => {
+                let text = format!("Loaded preset {}", name);
+                render_status(frame.with_region(area_with_margin), text)
+            }
+            Effect::PresetLoadFailed(name) => {
+                let text = format!("Could not load preset {}, see the logs for details", name);
+                render_status(frame.with_region(area_with_margin), text)
+            }
+            Effect::SpanAverageSorting(averaged) => {
+                let text = match averaged {
+                    true => "Processes sorted by the average of their metrics over the chart",
+                    false => "Processes sorted by their last metrics",
+                };
+                render_status(frame.with_region(area_with_margin), text.to_string())
+            }
+            Effect::MetricsCleared(pid) => {
+                let text = format!("Cleared the metrics of process {}", pid);
+                render_status(frame.with_region(area_with_margin), text)
             }
         }
     }
@@ -61,15 +142,28 @@ impl MetadataBar {
     }
 }
 
-fn render_process_metadata(frame: &mut FrameRegion, process: Option<&ProcessMetadata>) {
+fn render_process_metadata(frame: &mut FrameRegion, process: Option<&ProcessMetadata>, identifier: ProcessIdentifier) {
     match process {
         None => render_no_process_selected(frame),
-        Some(pm) => render_process_info(frame, pm),
+        Some(pm) => render_process_info(frame, pm, identifier),
     };
 }
 
-fn render_process_info(frame: &mut FrameRegion, pm: &ProcessMetadata) {
-    let left_text = format!("{} - {}", pm.pid(), pm.command());
+fn render_process_info(frame: &mut FrameRegion, pm: &ProcessMetadata, identifier: ProcessIdentifier) {
+    let name = match pm.label() {
+        Some(label) => format!("{} ({})", label, pm.command()),
+        None => pm.command().to_string(),
+    };
+    let mut left_text = match identifier {
+        ProcessIdentifier::Pid => format!("{} - {}", pm.pid(), name),
+        ProcessIdentifier::Tgid => format!("{} (TGID {}) - {}", pm.pid(), pm.tgid(), name),
+    };
+    if pm.is_isolated() {
+        left_text.push_str(" [container]");
+    }
+    if pm.is_stopped() {
+        left_text.push_str(" [stopped]");
+    }
 
     let begin_time = relative_timestamp_label(pm.running_span().begin());
     let mut right_text = format!("Started {}", begin_time);
@@ -92,6 +186,22 @@ fn render_process_info(frame: &mut FrameRegion, pm: &ProcessMetadata) {
     }
 }
 
+/// Replaces the right part of the bar with a warning about processes spawning and dying at a high rate
+fn render_churn_warning(frame: &mut FrameRegion, churn_rate: ChurnRate) {
+    let text = format!(
+        " High process churn: {:.0} spawned/s, {:.0} dead/s",
+        churn_rate.spawned_per_sec(),
+        churn_rate.dead_per_sec()
+    );
+
+    let region = frame.region();
+    let width = (text.len() as u16).min(region.width);
+    let warning_area = Rect::new(region.right() - width, region.y, width, region.height);
+
+    let paragraph = Paragraph::new(Span::from(text)).style(Style::default().fg(Color::Yellow));
+    frame.with_region(warning_area).render_widget(paragraph);
+}
+
 fn render_no_process_selected(frame: &mut FrameRegion) {
     let left_text = "No process is currently selected";
     let paragraph = Paragraph::new(Span::raw(left_text)).style(Style::default().fg(Color::White));
@@ -103,6 +213,83 @@ fn render_process_sorted_status(frame: &mut FrameRegion, criteria: ProcessOrderi
         "Processes sorted by {}",
         process_criteria_label(&criteria).to_lowercase()
     );
+    render_status(frame, text);
+}
+
+fn render_status(frame: &mut FrameRegion, text: String) {
     let paragraph = Paragraph::new(Span::from(text)).style(Style::default().fg(Color::Black).bg(Color::White));
     frame.render_widget(paragraph);
 }
+
+#[cfg(test)]
+mod test_metadata_bar {
+    use rstest::*;
+
+    use crate::core::churn::ChurnRate;
+    use crate::core::process::{ProcessIdentifier, ProcessMetadata};
+    use crate::core::time::Timestamp;
+    use crate::ui::metadata::MetadataBar;
+    use crate::ui::terminal::Terminal;
+
+    fn render_metadata_bar(identifier: ProcessIdentifier) -> String {
+        let process = ProcessMetadata::new(123, "firefox", Timestamp::now()).with_tgid(100);
+        render_metadata_bar_of(&process, identifier)
+    }
+
+    fn render_metadata_bar_of(process: &ProcessMetadata, identifier: ProcessIdentifier) -> String {
+        let mut terminal = Terminal::from_size(60, 1).unwrap();
+
+        let mut metadata_bar = MetadataBar::default();
+        terminal
+            .draw(|fr| metadata_bar.render(fr, Some(process), identifier))
+            .unwrap();
+
+        terminal.buffer_lines().remove(0)
+    }
+
+    #[test]
+    fn test_should_display_pid_of_selected_process() {
+        assert!(render_metadata_bar(ProcessIdentifier::Pid).starts_with(" 123 - firefox "));
+    }
+
+    #[test]
+    fn test_should_display_tgid_alongside_pid_when_toggled() {
+        assert!(render_metadata_bar(ProcessIdentifier::Tgid).starts_with(" 123 (TGID 100) - firefox "));
+    }
+
+    #[test]
+    fn test_should_display_label_alongside_command() {
+        let mut process = ProcessMetadata::new(123, "java", Timestamp::now());
+        process.set_label("kafka");
+
+        assert!(render_metadata_bar_of(&process, ProcessIdentifier::Pid).starts_with(" 123 - kafka (java) "));
+    }
+
+    #[test]
+    fn test_should_flag_stopped_process() {
+        let mut process = ProcessMetadata::new(123, "java", Timestamp::now());
+        process.set_stopped(true);
+
+        assert!(render_metadata_bar_of(&process, ProcessIdentifier::Pid).starts_with(" 123 - java [stopped] "));
+    }
+
+    #[rstest]
+    #[case(ChurnRate::new(30., 28.), true)]
+    #[case(ChurnRate::new(1., 1.), false)]
+    fn test_should_warn_about_high_churn(#[case] churn_rate: ChurnRate, #[case] expect_warning: bool) {
+        let mut terminal = Terminal::from_size(80, 1).unwrap();
+        let mut metadata_bar = MetadataBar::default();
+        metadata_bar.set_churn_rate(churn_rate);
+
+        terminal
+            .draw(|fr| metadata_bar.render(fr, None, ProcessIdentifier::Pid))
+            .unwrap();
+        let line = terminal.buffer_lines().remove(0);
+
+        assert!(line.starts_with(" No process is currently selected"));
+        assert_eq!(
+            line.contains("High process churn: 30 spawned/s, 28 dead/s"),
+            expect_warning
+        );
+    }
+}
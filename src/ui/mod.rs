@@ -1,29 +1,62 @@
 //! Renders spv on the terminal
 
 use std::io;
-use std::time::Duration;
 
-use log::error;
+use signal_hook::consts::SIGSTOP;
+use signal_hook::low_level::raise;
 use thiserror::Error;
+use tui::layout::{Alignment, Rect};
+use tui::widgets::Paragraph;
 
+use crate::config::{Config, ProbeKind};
+use crate::core::churn::ChurnRate;
+use crate::core::collection::MetricCollector;
+use crate::core::connections::Connection;
+use crate::core::devices::Device;
+use crate::core::filesystems::FilesystemUsage;
+use crate::core::ordering::ProcessOrdering;
+use crate::core::process::ProcessMetadata;
+use crate::core::summary::CollectorLeaders;
+use crate::core::units::UnitUsage;
 use crate::core::view::{CollectorsView, MetricView, MetricsOverview, ProcessesView};
-use crate::ctrl::{Effect, State};
+use crate::ctrl::preset::PresetPrompt;
+use crate::ctrl::{Effect, MouseTarget, State};
+use crate::triggers::TriggersEmitter;
+use crate::ui::anonymizer::Anonymizer;
 use crate::ui::chart::MetricsChart;
-use crate::ui::layout::UiLayout;
+use crate::ui::detail::DetailPane;
+use crate::ui::filesystems::FilesystemsPane;
+use crate::ui::histogram::MetricsHistogram;
+use crate::ui::layout::{centered_area, UiLayout};
 use crate::ui::metadata::MetadataBar;
-use crate::ui::processes::ProcessList;
+use crate::ui::permissions::render_permission_warning_popup;
+use crate::ui::presets::{render_preset_naming_popup, render_preset_selection_popup};
+use crate::ui::processes::{resolve_columns, ProcessList};
+use crate::ui::quit::render_quit_confirmation_popup;
 use crate::ui::sort_processes::render_process_order_popup;
+use crate::ui::summary::SummaryPane;
 use crate::ui::tabs::render_tabs;
-use crate::ui::terminal::Terminal;
+use crate::ui::terminal::{FrameRegion, Suspend, Terminal};
+use crate::ui::units::UnitsPane;
 
+mod anonymizer;
 mod chart;
+mod detail;
+mod filesystems;
+mod histogram;
 mod labels;
 mod layout;
 mod metadata;
+mod pane;
+mod permissions;
+mod presets;
 mod processes;
+mod quit;
 mod sort_processes;
+mod summary;
 mod tabs;
 mod terminal;
+mod units;
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -35,16 +68,56 @@ pub struct SpvUI {
     terminal: Terminal,
     process_list: ProcessList,
     chart: MetricsChart,
+    histogram: MetricsHistogram,
     metadata_bar: MetadataBar,
+    sort_criteria: Vec<ProcessOrdering>,
+    chart_only: bool,
+    // If true, the distribution of the latest metrics is rendered in place of the chart
+    histogram_mode: bool,
+    // Hides the names of processes when set
+    anonymizer: Option<Anonymizer>,
+    preset_prompt: PresetPrompt,
+    // The regions of the latest rendering, to locate the element under the mouse cursor
+    frame_region: Rect,
+    chart_region: Rect,
 }
 
 impl SpvUI {
-    pub fn new(chart_resolution: Duration) -> Result<Self, Error> {
+    /// Builds the UI of spv
+    ///
+    /// # Arguments
+    ///  * `config`: The configuration of spv
+    ///  * `sort_criteria`: The criteria listed by the prompt to sort processes
+    ///  * `probes`: The probe of each collector, in the order of the collectors
+    pub fn new(config: &Config, sort_criteria: Vec<ProcessOrdering>, probes: &[ProbeKind]) -> Result<Self, Error> {
+        let chart_resolution = 2 * TriggersEmitter::impulse_time_tolerance(config.refresh_period());
+        let ascii_chart = config.ascii_chart() || !terminal::supports_unicode();
+        let mut process_list = ProcessList::new(resolve_columns(&config.columns(), probes));
+        if ascii_chart {
+            process_list = process_list.with_ascii_trends();
+        }
+        let mut chart = MetricsChart::new(chart_resolution, config.theme().clone(), ascii_chart)
+            .with_collection_period(config.refresh_period());
+        if config.newest_left() {
+            chart = chart.with_newest_left();
+        }
+        if config.nice_y_axis() {
+            chart = chart.with_nice_y_axis();
+        }
+
         Ok(Self {
             terminal: Terminal::new()?,
-            process_list: ProcessList::default(),
-            chart: MetricsChart::new(chart_resolution),
+            process_list,
+            chart,
+            histogram: MetricsHistogram::new(ascii_chart),
             metadata_bar: MetadataBar::default(),
+            sort_criteria,
+            chart_only: false,
+            histogram_mode: false,
+            anonymizer: config.anonymize().then(Anonymizer::default),
+            preset_prompt: PresetPrompt::default(),
+            frame_region: Rect::default(),
+            chart_region: Rect::default(),
         })
     }
 
@@ -52,30 +125,270 @@ impl SpvUI {
         &mut self,
         collectors: &CollectorsView,
         processes: &ProcessesView,
-        overview: &MetricsOverview,
+        overviews: &[MetricsOverview],
         view: Option<&MetricView>,
+        view_frozen: bool,
         state: State,
     ) -> Result<(), Error> {
+        let anonymized;
+        let processes = match &mut self.anonymizer {
+            Some(anonymizer) => {
+                anonymized = anonymizer.anonymize(processes.as_slice());
+                processes.with_processes(&anonymized)
+            }
+            None => processes.with_processes(processes.as_slice()),
+        };
+
         self.terminal.draw(|frame| {
-            let layout = UiLayout::new(frame.region());
+            self.frame_region = frame.region();
+            if layout::is_too_small(frame.region()) {
+                render_terminal_too_small(frame);
+                return;
+            }
+
+            let layout = if self.chart_only {
+                UiLayout::chart_only(frame.region())
+            } else {
+                UiLayout::new(frame.region(), self.process_list.width())
+            };
+            self.chart_region = layout.chart_chunk();
 
-            render_tabs(frame.with_region(layout.tabs_chunk()), collectors);
+            if !layout.is_chart_only() {
+                render_tabs(frame.with_region(layout.tabs_chunk()), collectors);
+
+                self.process_list
+                    .render(frame.with_region(layout.processes_chunk()), overviews, &processes);
+            }
+
+            match overviews.first() {
+                Some(overview) if self.histogram_mode => {
+                    self.histogram
+                        .render(frame.with_region(layout.chart_chunk()), overview, &processes)
+                }
+                _ => self.chart.render(
+                    frame.with_region(layout.chart_chunk()),
+                    view,
+                    processes.selected_process(),
+                    view_frozen,
+                ),
+            }
 
-            self.process_list
-                .render(frame.with_region(layout.processes_chunk()), overview, processes);
+            if !layout.is_chart_only() {
+                self.metadata_bar.render(
+                    frame.with_region(layout.metadata_chunk()),
+                    processes.selected_process(),
+                    processes.identifier(),
+                );
+            }
 
-            self.chart.render(frame.with_region(layout.chart_chunk()), view);
+            if let State::SortingPrompt(criteria) = &state {
+                render_process_order_popup(frame.with_original_region(), &self.sort_criteria, criteria.clone());
+            }
 
-            self.metadata_bar
-                .render(frame.with_region(layout.metadata_chunk()), processes.selected_process());
+            if let State::PermissionWarning = state {
+                render_permission_warning_popup(frame.with_original_region());
+            }
 
-            if let State::SortingPrompt(criteria) = state {
-                render_process_order_popup(frame.with_original_region(), criteria);
+            match state {
+                State::PresetNaming => render_preset_naming_popup(frame.with_original_region(), &self.preset_prompt),
+                State::PresetSelection => {
+                    render_preset_selection_popup(frame.with_original_region(), &self.preset_prompt)
+                }
+                State::QuitConfirmation => render_quit_confirmation_popup(frame.with_original_region()),
+                _ => {}
             }
         })
     }
 
+    /// Renders the details of a process in place of the main view
+    ///
+    /// # Arguments
+    ///  * `process`: The process to describe
+    ///  * `device`: The device storing most of the files opened by the process, if known
+    ///  * `connections`: The TCP connections of the process, if known
+    ///  * `collectors`: The collectors from which to read the latest metric of the process
+    pub fn render_process_detail(
+        &mut self,
+        process: &ProcessMetadata,
+        device: Option<&Device>,
+        connections: Option<&[Connection]>,
+        collectors: &[Box<dyn MetricCollector>],
+    ) -> Result<(), Error> {
+        let mut pane = match &mut self.anonymizer {
+            Some(anonymizer) => DetailPane::new(&anonymizer.anonymize_process(process), device, collectors),
+            None => DetailPane::new(process, device, collectors),
+        };
+        if let Some(connections) = connections {
+            pane = pane.with_connections(connections);
+        }
+
+        self.terminal.draw(|frame| {
+            self.frame_region = frame.region();
+            pane.render(frame);
+        })
+    }
+
+    /// Renders the leading processes of every visible tab in place of the main view
+    ///
+    /// # Arguments
+    ///  * `summary`: The leading processes of each collector
+    pub fn render_summary(&mut self, summary: &[CollectorLeaders]) -> Result<(), Error> {
+        let pane = match &mut self.anonymizer {
+            Some(anonymizer) => SummaryPane::new(summary, |pm| {
+                anonymizer.anonymize_process(pm).display_name().to_string()
+            }),
+            None => SummaryPane::new(summary, |pm| pm.display_name().to_string()),
+        };
+
+        self.terminal.draw(|frame| {
+            self.frame_region = frame.region();
+            pane.render(frame);
+        })
+    }
+
+    /// Renders the disk space usage of the mounted filesystems in place of the main view
+    ///
+    /// # Arguments
+    ///  * `filesystems`: The usage of each mounted filesystem
+    pub fn render_filesystems(&mut self, filesystems: &[FilesystemUsage]) -> Result<(), Error> {
+        let pane = FilesystemsPane::new(filesystems);
+
+        self.terminal.draw(|frame| {
+            self.frame_region = frame.region();
+            pane.render(frame);
+        })
+    }
+
+    /// Renders the metrics of a collector summed by systemd unit in place of the main view
+    ///
+    /// # Arguments
+    ///  * `units`: The summed metrics of each unit
+    ///  * `collector_name`: The name of the collector whose metrics are summed
+    ///  * `repr`: Returns a concise representation of a sum of metrics
+    pub fn render_units(
+        &mut self,
+        units: &[UnitUsage],
+        collector_name: &str,
+        repr: impl Fn(f64) -> String,
+    ) -> Result<(), Error> {
+        let pane = UnitsPane::new(units, collector_name, repr);
+
+        self.terminal.draw(|frame| {
+            self.frame_region = frame.region();
+            pane.render(frame);
+        })
+    }
+
+    /// Hands the terminal over to `run`, such as an external command, and redraws the whole UI on the next rendering
+    pub fn run_suspended<T>(&mut self, run: impl FnOnce() -> T) -> Result<T, Error> {
+        terminal::run_suspended(&mut self.terminal, run)
+    }
+
+    /// Stops spv until it is continued, e.g. when the user presses Ctrl-Z, handing the terminal back to the shell
+    pub fn suspend_until_continued(&mut self) -> Result<(), Error> {
+        terminal::suspend_until_continued(&mut self.terminal, || raise(SIGSTOP))
+    }
+
+    /// Sets the terminal back up for spv, e.g. after spv was stopped and continued without handing the terminal back
+    /// to the shell, and redraws the whole UI on the next rendering
+    pub fn resume(&mut self) -> Result<(), Error> {
+        self.terminal.resume()
+    }
+
+    /// Returns the element of the latest rendered UI located at the given position of the terminal
+    pub fn locate(&self, column: u16, row: u16) -> MouseTarget {
+        if layout::is_too_small(self.frame_region) {
+            return MouseTarget::None;
+        }
+
+        if let Some(index) = self
+            .process_list
+            .process_index_at(column, row)
+            .filter(|_| !self.chart_only)
+        {
+            return MouseTarget::ProcessRow(index);
+        }
+
+        let chart = self.chart_region;
+        if (chart.left()..chart.right()).contains(&column) && (chart.top()..chart.bottom()).contains(&row) {
+            return MouseTarget::Chart {
+                column: column - chart.left(),
+                width: chart.width,
+            };
+        }
+
+        MouseTarget::None
+    }
+
     pub fn set_status_from_effect(&mut self, effect: Effect) {
         self.metadata_bar.set_status_from_effect(effect)
     }
+
+    /// Indicates if the chart should be rendered alone, in place of the tabs, the process list and the metadata bar
+    pub fn set_chart_only(&mut self, chart_only: bool) {
+        self.chart_only = chart_only;
+    }
+
+    /// Indicates if the distribution of the latest metrics of processes should be rendered in place of the chart
+    pub fn set_histogram_mode(&mut self, histogram_mode: bool) {
+        self.histogram_mode = histogram_mode;
+    }
+
+    /// Returns the indices of the collectors whose metrics are displayed in the process list whichever the current tab,
+    /// in the order of their columns
+    pub fn collector_columns(&self) -> Vec<usize> {
+        self.process_list.collector_columns()
+    }
+
+    /// Sets the index of the collector of the current tab
+    pub fn set_current_collector(&mut self, current_collector: usize) {
+        self.process_list.set_current_collector(current_collector)
+    }
+
+    /// Sets the prompt rendered while the user names or selects a preset
+    pub fn set_preset_prompt(&mut self, preset_prompt: PresetPrompt) {
+        self.preset_prompt = preset_prompt;
+    }
+
+    /// Updates the rate at which processes spawn and die, which is reported when it is high
+    pub fn set_churn_rate(&mut self, churn_rate: ChurnRate) {
+        self.metadata_bar.set_churn_rate(churn_rate)
+    }
+}
+
+/// Renders a message asking to enlarge the terminal, in place of the UI
+fn render_terminal_too_small(frame: &mut FrameRegion) {
+    let region = frame.region();
+    let text = format!("Terminal too small ({}x{})", layout::MIN_WIDTH, layout::MIN_HEIGHT);
+    let message_area = centered_area(region, text.len() as u16, 1);
+
+    let paragraph = Paragraph::new(text).alignment(Alignment::Center);
+    frame.with_region(message_area).render_widget(paragraph);
+}
+
+#[cfg(test)]
+mod test_terminal_too_small {
+    use rstest::*;
+
+    use crate::ui::render_terminal_too_small;
+    use crate::ui::terminal::Terminal;
+
+    #[rstest]
+    #[case(1, 1)]
+    #[case(1, 20)]
+    #[case(20, 1)]
+    fn test_should_render_message_in_degenerate_terminal(#[case] width: u16, #[case] height: u16) {
+        let mut terminal = Terminal::from_size(width, height).unwrap();
+
+        terminal.draw(render_terminal_too_small).unwrap();
+    }
+
+    #[test]
+    fn test_should_display_minimum_size() {
+        let mut terminal = Terminal::from_size(39, 3).unwrap();
+
+        terminal.draw(render_terminal_too_small).unwrap();
+
+        assert_eq!(terminal.buffer_lines()[1].trim(), "Terminal too small (40x8)");
+    }
 }
@@ -0,0 +1,100 @@
+//! Rendering shared by the full-screen panes which replace the process list, such as the filesystems pane
+
+use std::borrow::Cow;
+
+use tui::layout::{Constraint, Direction, Layout};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Paragraph};
+
+use crate::ui::terminal::FrameRegion;
+
+/// Renders the lines of a full-screen pane in a titled frame, above a hint telling how to leave the pane
+///
+/// # Arguments
+///  * `frame`: The region of the terminal covered by the pane
+///  * `title`: The title of the frame of the pane
+///  * `lines`: The content of the pane
+pub fn render_pane(frame: &mut FrameRegion, title: &str, lines: Vec<Spans>) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(frame.region());
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().fg(Color::White))
+        .block(Block::default().borders(Borders::ALL).title(title));
+    frame.with_region(chunks[0]).render_widget(paragraph);
+
+    let hint = Paragraph::new("Press Esc to go back").style(Style::default().fg(Color::White));
+    frame.with_region(chunks[1]).render_widget(hint);
+}
+
+/// Returns the lines of a listing: its heading in bold, followed by its rows, or by a placeholder if it has none
+///
+/// # Arguments
+///  * `heading`: The first line of the listing, such as the names of its columns
+///  * `rows`: The rows of the listing
+///  * `placeholder`: The line displayed in place of the rows when there are none
+pub fn listing<'a>(heading: impl Into<Cow<'a, str>>, rows: &'a [String], placeholder: &'a str) -> Vec<Spans<'a>> {
+    let mut lines = vec![Spans::from(Span::styled(
+        heading,
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+
+    if rows.is_empty() {
+        lines.push(Spans::from(placeholder));
+    }
+
+    lines.extend(rows.iter().map(|row| Spans::from(row.as_str())));
+    lines
+}
+
+/// Keeps the first `max_chars` characters of the given text
+pub fn truncate(text: &str, max_chars: usize) -> String {
+    text.chars().take(max_chars).collect()
+}
+
+#[cfg(test)]
+mod test_listing {
+    use tui::text::Spans;
+
+    use crate::ui::pane::listing;
+
+    fn texts(lines: Vec<Spans>) -> Vec<String> {
+        lines
+            .iter()
+            .map(|spans| spans.0.iter().map(|span| span.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_should_list_rows_under_heading() {
+        let rows = vec!["first".to_string(), "second".to_string()];
+
+        assert_eq!(
+            texts(listing("Heading", &rows, "Nothing")),
+            vec!["Heading", "first", "second"]
+        );
+    }
+
+    #[test]
+    fn test_should_display_placeholder_without_rows() {
+        assert_eq!(texts(listing("Heading", &[], "Nothing")), vec!["Heading", "Nothing"]);
+    }
+}
+
+#[cfg(test)]
+mod test_truncate {
+    use rstest::*;
+
+    use crate::ui::pane::truncate;
+
+    #[rstest]
+    #[case("nginx", 10, "nginx")]
+    #[case("nginx: master process", 5, "nginx")]
+    #[case("élégant", 3, "élé")]
+    fn test_should_keep_first_characters(#[case] text: &str, #[case] max_chars: usize, #[case] expected: &str) {
+        assert_eq!(truncate(text, max_chars), expected);
+    }
+}
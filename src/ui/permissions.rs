@@ -0,0 +1,44 @@
+use tui::layout::Alignment;
+use tui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+use crate::ui::layout::centered_area;
+use crate::ui::terminal::FrameRegion;
+
+const WARNING: &str = "spv is not allowed to read the data of most processes, whose metrics are thus missing. \
+Run spv with elevated privileges (e.g. with sudo) to monitor all processes.";
+const DISMISS_HINT: &str = "Press Enter or Escape to dismiss";
+
+/// Renders a popup explaining that spv lacks the permissions to monitor most processes
+pub fn render_permission_warning_popup(frame_region: &mut FrameRegion) {
+    const POPUP_WIDTH: u16 = 50;
+    const POPUP_HEIGHT: u16 = 8;
+
+    let popup_area = centered_area(frame_region.region(), POPUP_WIDTH, POPUP_HEIGHT);
+    frame_region.with_region(popup_area).render_widget(Clear);
+
+    let text = format!("{}\n\n{}", WARNING, DISMISS_HINT);
+    let paragraph = Paragraph::new(text)
+        .block(Block::default().borders(Borders::ALL).title("Missing permissions"))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    frame_region.with_region(popup_area).render_widget(paragraph);
+}
+
+#[cfg(test)]
+mod test_permission_warning {
+    use crate::ui::permissions::render_permission_warning_popup;
+    use crate::ui::terminal::Terminal;
+
+    #[test]
+    fn test_should_suggest_elevated_privileges() {
+        let mut terminal = Terminal::from_size(60, 12).unwrap();
+
+        terminal.draw(render_permission_warning_popup).unwrap();
+
+        let text = terminal.buffer_lines().join(" ");
+        assert!(text.contains("Missing permissions"));
+        assert!(text.contains("elevated privileges"));
+        assert!(text.contains("Press Enter or Escape to dismiss"));
+    }
+}
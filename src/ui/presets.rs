@@ -0,0 +1,103 @@
+use tui::layout::Alignment;
+use tui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+
+use crate::ctrl::preset::PresetPrompt;
+use crate::ui::layout::centered_area;
+use crate::ui::terminal::FrameRegion;
+
+const POPUP_WIDTH: u16 = 50;
+/// The maximum amount of presets listed at once by the selection popup
+const MAX_LISTED_PRESETS: u16 = 10;
+
+/// Renders a popup in which the user types the name under which to save the view
+pub fn render_preset_naming_popup(frame_region: &mut FrameRegion, prompt: &PresetPrompt) {
+    let popup_area = centered_area(frame_region.region(), POPUP_WIDTH, 4);
+    frame_region.with_region(popup_area).render_widget(Clear);
+
+    let text = format!("{}_\nPress Enter to save, or Escape to cancel", prompt.typed_name());
+    let paragraph = Paragraph::new(text)
+        .block(Block::default().borders(Borders::ALL).title("Save view as preset"))
+        .alignment(Alignment::Center);
+
+    frame_region.with_region(popup_area).render_widget(paragraph);
+}
+
+/// Renders a popup in which the user selects the saved preset to load
+pub fn render_preset_selection_popup(frame_region: &mut FrameRegion, prompt: &PresetPrompt) {
+    let block = Block::default().borders(Borders::ALL).title("Load preset");
+
+    if prompt.names().is_empty() {
+        let popup_area = centered_area(frame_region.region(), POPUP_WIDTH, 3);
+        frame_region.with_region(popup_area).render_widget(Clear);
+
+        let paragraph = Paragraph::new("No saved preset, press w to save one")
+            .block(block)
+            .alignment(Alignment::Center);
+        frame_region.with_region(popup_area).render_widget(paragraph);
+        return;
+    }
+
+    // Why +2 -> for the top and bottom borders
+    let popup_height = (prompt.names().len() as u16).min(MAX_LISTED_PRESETS) + 2;
+    let popup_area = centered_area(frame_region.region(), POPUP_WIDTH, popup_height);
+    frame_region.with_region(popup_area).render_widget(Clear);
+
+    let mut state = ListState::default();
+    state.select(prompt.selected_index());
+
+    let items: Vec<_> = prompt.names().iter().map(|name| ListItem::new(name.as_str())).collect();
+    let list = List::new(items).block(block).highlight_symbol(">> ");
+
+    frame_region
+        .with_region(popup_area)
+        .render_stateful_widget(list, &mut state);
+}
+
+#[cfg(test)]
+mod test_preset_popups {
+    use crate::ctrl::preset::PresetPrompt;
+    use crate::ui::presets::{render_preset_naming_popup, render_preset_selection_popup};
+    use crate::ui::terminal::Terminal;
+
+    #[test]
+    fn test_should_render_typed_name() {
+        let mut prompt = PresetPrompt::default();
+        "cpu".chars().for_each(|c| prompt.type_char(c));
+        let mut terminal = Terminal::from_size(60, 12).unwrap();
+
+        terminal
+            .draw(|frame| render_preset_naming_popup(frame, &prompt))
+            .unwrap();
+
+        let text = terminal.buffer_lines().join(" ");
+        assert!(text.contains("Save view as preset"));
+        assert!(text.contains("cpu_"));
+    }
+
+    #[test]
+    fn test_should_highlight_selected_preset() {
+        let mut prompt = PresetPrompt::selection(vec!["cpu".to_string(), "memory".to_string()]);
+        prompt.next();
+        let mut terminal = Terminal::from_size(60, 12).unwrap();
+
+        terminal
+            .draw(|frame| render_preset_selection_popup(frame, &prompt))
+            .unwrap();
+
+        let lines = terminal.buffer_lines();
+        assert!(lines.iter().any(|line| line.contains("│   cpu")));
+        assert!(lines.iter().any(|line| line.contains("│>> memory")));
+    }
+
+    #[test]
+    fn test_should_explain_how_to_save_first_preset() {
+        let prompt = PresetPrompt::selection(vec![]);
+        let mut terminal = Terminal::from_size(60, 12).unwrap();
+
+        terminal
+            .draw(|frame| render_preset_selection_popup(frame, &prompt))
+            .unwrap();
+
+        assert!(terminal.buffer_lines().join(" ").contains("No saved preset"));
+    }
+}
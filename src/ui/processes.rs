@@ -1,31 +1,181 @@
+use std::iter;
+use std::time::Duration;
+
 use tui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use tui::style::{Color, Modifier, Style};
 use tui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
 
-use crate::core::process::{Pid, ProcessMetadata, Status};
-use crate::core::view::{MetricsOverview, ProcessesView};
+use crate::config::{ColumnKind, ProbeKind};
+use crate::core::process::{ProcessIdentifier, ProcessMetadata, Status};
+use crate::core::time::Timestamp;
+use crate::core::view::{MetricsOverview, ProcessesView, Trend};
 use crate::ui::terminal::FrameRegion;
 
 /// Width of the process name column
 const CMD_COL_WIDTH: usize = 16;
+/// Width of the process identifier column
+const ID_COL_WIDTH: usize = 8;
 /// Width of the metrics values column
 const METRICS_COL_WIDTH: usize = 10;
+/// Width of the cumulative CPU time column, which fits up to 9999 minutes
+const CPU_TIME_COL_WIDTH: usize = 10;
+/// Width of the uptime column
+const UPTIME_COL_WIDTH: usize = 8;
+/// Width of the state column, which fits the longest state and a leading space
+const STATE_COL_WIDTH: usize = 9;
+/// Color of the name of processes which have recently been spawned
+const RECENTLY_SPAWNED_COLOR: Color = Color::LightGreen;
+/// Background color of the name of processes whose metrics satisfy the watch expression
+const WATCHED_COLOR: Color = Color::Yellow;
+/// Color of the name of spv's own process, when it is highlighted
+const OWN_PROCESS_COLOR: Color = Color::LightCyan;
+/// Color of the metrics which exceed the level from which they reveal a problem
+const ALARMING_METRIC_COLOR: Color = Color::LightRed;
+
+/// A column of the process list, as rendered
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Column {
+    Name,
+    Identifier,
+    CpuTime,
+    Uptime,
+    State,
+    /// The latest metrics of the collector of the current tab
+    CurrentMetric,
+    /// The latest metrics of the collector at the given index, whichever the current tab
+    CollectorMetric(usize),
+}
+
+impl Column {
+    fn width(&self) -> usize {
+        match self {
+            Column::Name => CMD_COL_WIDTH + 2, // room for the highlight symbol
+            Column::Identifier => ID_COL_WIDTH,
+            Column::CpuTime => CPU_TIME_COL_WIDTH,
+            Column::Uptime => UPTIME_COL_WIDTH,
+            Column::State => STATE_COL_WIDTH,
+            Column::CurrentMetric | Column::CollectorMetric(_) => METRICS_COL_WIDTH,
+        }
+    }
+
+    /// Whether the values of the column are numbers, which are right-justified
+    fn is_numeric(&self) -> bool {
+        !matches!(self, Column::Name | Column::State)
+    }
+
+    /// The amount of blank characters following the values of the column
+    fn margin(&self) -> usize {
+        match self {
+            Column::CurrentMetric | Column::CollectorMetric(_) => 1,
+            _ => 0,
+        }
+    }
+}
+
+/// Resolves the configured columns of the process list into the columns to render
+///
+/// The columns of probes for which no collector could be built are left out.
+///
+/// # Arguments
+///  * `columns`: The configured columns, in the order in which they are displayed
+///  * `probes`: The probe of each collector, in the order of the collectors
+pub fn resolve_columns(columns: &[ColumnKind], probes: &[ProbeKind]) -> Vec<Column> {
+    columns
+        .iter()
+        .filter_map(|column| match column {
+            ColumnKind::Command => Some(Column::Name),
+            ColumnKind::Identifier => Some(Column::Identifier),
+            ColumnKind::CpuTime => Some(Column::CpuTime),
+            ColumnKind::Uptime => Some(Column::Uptime),
+            ColumnKind::State => Some(Column::State),
+            ColumnKind::Metric => Some(Column::CurrentMetric),
+            ColumnKind::Probe(probe) => probes.iter().position(|p| p == probe).map(Column::CollectorMetric),
+        })
+        .collect()
+}
 
-#[derive(Default)]
 pub struct ProcessList {
     state: ListState,
+    // The geometry of the latest rendering, to locate the process under the mouse cursor
+    rows_region: Rect,
+    rows_offset: usize,
+    rows_count: usize,
+    columns: Vec<Column>,
+    current_collector: Option<usize>,
+    // How much wider than their default width the columns of the latest rendering were
+    extra_width: usize,
+    // Whether the trends of metrics are drawn with ASCII characters only
+    ascii: bool,
+}
+
+impl Default for ProcessList {
+    fn default() -> Self {
+        Self::new(vec![Column::Name, Column::Identifier, Column::CurrentMetric])
+    }
 }
 
 impl ProcessList {
+    /// Creates a process list displaying the given columns, from left to right
+    pub fn new(columns: Vec<Column>) -> Self {
+        Self {
+            state: ListState::default(),
+            rows_region: Rect::default(),
+            rows_offset: 0,
+            rows_count: 0,
+            columns,
+            current_collector: None,
+            extra_width: 0,
+            ascii: false,
+        }
+    }
+
+    /// Draws the trends of metrics with ASCII characters only, for terminals which can not display arrows
+    pub fn with_ascii_trends(mut self) -> Self {
+        self.ascii = true;
+        self
+    }
+
+    /// Sets the index of the collector of the current tab
+    ///
+    /// The column of this collector is not rendered when the list already displays the metrics of the current tab.
+    pub fn set_current_collector(&mut self, current_collector: usize) {
+        self.current_collector = Some(current_collector);
+    }
+
+    /// Returns the indices of the collectors whose metrics are displayed whichever the current tab, in the order of
+    /// their columns
+    pub fn collector_columns(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .filter_map(|column| match column {
+                Column::CollectorMetric(index) => Some(*index),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the width required to render the process list
+    ///
+    /// Columns are widened to fit their values, so that the width may grow with the values of the latest rendering.
+    pub fn width(&self) -> u16 {
+        (self.columns.iter().map(|column| column.width()).sum::<usize>() + self.extra_width) as u16
+    }
+
     /// Renders the processes assigned through the
     /// [`set_processes`](struct.ProcessList#method.set_processes) method
     ///
     /// # Arguments
     ///   * `frame`: The `Frame` on which to render the process list widget
-    ///   * `chunk`: The region within the `frame` reserved for this widget
-    ///   * `archive`: The metrics archive, to display the current metric of each process
-    ///   * `label`: The name of the metric to display
-    pub fn render(&mut self, frame: &mut FrameRegion, metrics_overview: &MetricsOverview, processes: &ProcessesView) {
+    ///   * `metrics_overviews`: The latest metrics of processes. The first overview is the one of the current tab, by
+    ///     which processes are sorted, followed by the overview of each collector listed by
+    ///     [`collector_columns`](struct.ProcessList#method.collector_columns)
+    ///   * `processes`: The processes to list
+    pub fn render(
+        &mut self,
+        frame: &mut FrameRegion,
+        metrics_overviews: &[MetricsOverview],
+        processes: &ProcessesView,
+    ) {
         self.state.select(processes.selected_index());
 
         let rows_chunks = Layout::default()
@@ -33,97 +183,180 @@ impl ProcessList {
             .constraints([Constraint::Length(2), Constraint::Min(1)])
             .split(frame.region());
 
-        let (proc_chunk, metric_chunk) = Self::split_column_chunks(rows_chunks[1]);
-
-        Self::render_title_row(frame.with_region(rows_chunks[0]), metrics_overview.unit());
-        self.render_name_column(frame.with_region(proc_chunk), processes.as_slice());
-        self.render_metric_column(frame.with_region(metric_chunk), metrics_overview, processes.as_slice());
-    }
-
-    /// Splits a `Rect` into two:
-    ///   - One that will contain the command name
-    ///   - One that will contain the metric value
-    fn split_column_chunks(chunk: Rect) -> (Rect, Rect) {
-        let columns_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(
-                [
-                    Constraint::Min(CMD_COL_WIDTH as u16 + 2), // processes names
-                    Constraint::Min(METRICS_COL_WIDTH as u16), // processes metrics
-                ]
-                .as_ref(),
-            )
-            .split(chunk);
+        // The last row of the list is taken by the bottom border
+        self.rows_region = Rect {
+            height: rows_chunks[1].height.saturating_sub(1),
+            ..rows_chunks[1]
+        };
+        self.rows_count = processes.as_slice().len();
+        self.rows_offset = list_offset(
+            self.rows_offset,
+            processes.selected_index(),
+            self.rows_count,
+            self.rows_region.height as usize,
+        );
 
-        (columns_chunks[0], columns_chunks[1])
-    }
+        // The values of all rows are formatted first, so that each column can be as wide as its widest value
+        let columns: Vec<_> = self
+            .rendered_columns(metrics_overviews)
+            .into_iter()
+            .map(|(column, overview)| {
+                let (title, alignment) = title(column, overview, processes.identifier());
+                let values = cell_values(column, overview, processes, self.ascii);
+                let width = fit_column_width(column, &title, &values);
+                (column, overview, title, alignment, values, width)
+            })
+            .collect();
 
-    fn render_title_row(frame: &mut FrameRegion, metric_unit: &'static str) {
-        let (proc_chunk, metric_chunk) = Self::split_column_chunks(frame.region());
+        self.extra_width = columns
+            .iter()
+            .map(|(column, _, _, _, _, width)| width - column.width())
+            .sum();
 
-        let proc_paragraph = Paragraph::new("Process name")
-            .block(Block::default().borders(Borders::LEFT | Borders::TOP))
-            .alignment(Alignment::Center);
+        let widths: Vec<_> = columns.iter().map(|(column, .., width)| (*column, *width)).collect();
+        let title_chunks = split_column_chunks(rows_chunks[0], &widths);
+        let rows_chunks = split_column_chunks(rows_chunks[1], &widths);
 
-        let metric_text = format!("{} ", metric_unit);
-        let metric_title = Paragraph::new(metric_text)
-            .block(Block::default().borders(Borders::TOP))
-            .alignment(Alignment::Right);
+        for (position, ((column, overview, title, alignment, values, width), (title_chunk, rows_chunk))) in columns
+            .into_iter()
+            .zip(title_chunks.into_iter().zip(rows_chunks))
+            .enumerate()
+        {
+            let borders = match position {
+                0 => Borders::LEFT,
+                _ => Borders::NONE,
+            };
+            Self::render_title(frame.with_region(title_chunk), title, alignment, borders);
 
-        frame.with_region(proc_chunk).render_widget(proc_paragraph);
-        frame.with_region(metric_chunk).render_widget(metric_title);
+            let cells = values.iter().map(|value| justify_cell(column, value, width)).collect();
+            self.render_column(
+                frame.with_region(rows_chunk),
+                column,
+                overview,
+                processes,
+                cells,
+                borders,
+            );
+        }
     }
 
-    fn render_name_column(&mut self, frame: &mut FrameRegion, processes: &[ProcessMetadata]) {
-        let processes_names: Vec<_> = processes.iter().map(Self::shortened_command_name).collect();
+    /// Returns the index of the process displayed at the given position of the terminal, as of the latest rendering
+    pub fn process_index_at(&self, column: u16, row: u16) -> Option<usize> {
+        if column < self.rows_region.left() || column >= self.rows_region.right() {
+            return None;
+        }
 
-        let items: Vec<ListItem> = processes_names.iter().map(|cmd| ListItem::new(cmd.as_str())).collect();
+        row_index_at(row, self.rows_region, self.rows_offset, self.rows_count)
+    }
 
-        let list = Self::build_default_list_widget(items)
-            .block(Block::default().borders(Borders::LEFT | Borders::BOTTOM))
-            .highlight_symbol(">> ");
+    /// Returns the columns to render, along with the overview of the metrics they display
+    ///
+    /// The columns of collectors whose overview is missing, as well as the column of the collector of the current
+    /// tab when the metrics of the current tab are already displayed, are left out.
+    fn rendered_columns<'a, 'b>(
+        &self,
+        metrics_overviews: &'a [MetricsOverview<'b>],
+    ) -> Vec<(Column, Option<&'a MetricsOverview<'b>>)> {
+        let shows_current = self.columns.contains(&Column::CurrentMetric);
+        let mut collector_overviews = metrics_overviews.iter().skip(1);
 
-        frame.render_stateful_widget(list, &mut self.state);
+        self.columns
+            .iter()
+            .filter_map(|column| match column {
+                Column::CurrentMetric => metrics_overviews.first().map(|overview| (*column, Some(overview))),
+                Column::CollectorMetric(index) => {
+                    let overview = collector_overviews.next()?;
+                    let duplicate = shows_current && self.current_collector == Some(*index);
+                    (!duplicate).then_some((*column, Some(overview)))
+                }
+                _ => Some((*column, None)),
+            })
+            .collect()
     }
 
-    /// Returns the formatted command name of `process_metadata` so that its length does not exceed
-    /// `MAX_COMMAND_LENGTH` characters
-    fn shortened_command_name(process_metadata: &ProcessMetadata) -> String {
-        if process_metadata.command().len() > CMD_COL_WIDTH {
-            format!("{}..", &process_metadata.command()[0..CMD_COL_WIDTH - 2])
-        } else {
-            process_metadata.command().to_string()
-        }
+    fn render_title(frame: &mut FrameRegion, title: String, alignment: Alignment, borders: Borders) {
+        let paragraph = Paragraph::new(title)
+            .block(Block::default().borders(borders | Borders::TOP))
+            .alignment(alignment);
+        frame.render_widget(paragraph);
     }
 
-    fn render_metric_column(
+    fn render_column(
         &mut self,
         frame: &mut FrameRegion,
-        metrics_overview: &MetricsOverview,
-        processes: &[ProcessMetadata],
+        column: Column,
+        overview: Option<&MetricsOverview>,
+        processes: &ProcessesView,
+        cells: Vec<String>,
+        borders: Borders,
     ) {
-        let str_metrics: Vec<String> = processes
+        let block = Block::default().borders(borders | Borders::BOTTOM);
+
+        let list = match (column, overview) {
+            (Column::Name, _) => Self::name_list(processes).highlight_symbol(">> "),
+            (_, Some(overview)) => Self::metrics_list(processes.as_slice(), overview, cells),
+            (_, None) => Self::cells_list(cells),
+        };
+
+        frame.render_stateful_widget(list.block(block), &mut self.state);
+    }
+
+    fn name_list<'a>(processes: &ProcessesView) -> List<'a> {
+        let items: Vec<ListItem> = processes
+            .as_slice()
             .iter()
-            .map(|pm| match pm.status() {
-                Status::RUNNING => self.formatted_process_metric(pm.pid(), metrics_overview),
-                Status::DEAD => self.justify_metric_repr("DEAD".to_string()),
+            .map(|pm| {
+                let item = ListItem::new(Self::shortened_command_name(pm));
+                if processes.is_watched(pm.pid()) {
+                    item.style(Style::default().fg(Color::Black).bg(WATCHED_COLOR))
+                } else if processes.is_own_process(pm.pid()) {
+                    item.style(Style::default().fg(OWN_PROCESS_COLOR).add_modifier(Modifier::BOLD))
+                } else if processes.is_recently_spawned(pm.pid()) {
+                    item.style(Style::default().fg(RECENTLY_SPAWNED_COLOR))
+                } else {
+                    item
+                }
             })
             .collect();
 
-        let items: Vec<ListItem> = str_metrics.iter().map(|m| ListItem::new(m.as_str())).collect();
+        Self::build_default_list_widget(items)
+    }
 
-        let list = Self::build_default_list_widget(items).block(Block::default().borders(Borders::BOTTOM));
+    /// Builds a list whose rows contain the given cells
+    fn cells_list<'a>(cells: Vec<String>) -> List<'a> {
+        let items: Vec<ListItem> = cells.into_iter().map(ListItem::new).collect();
 
-        frame.render_stateful_widget(list, &mut self.state);
+        Self::build_default_list_widget(items)
     }
 
-    fn formatted_process_metric(&self, pid: Pid, metrics_overview: &MetricsOverview) -> String {
-        let m = metrics_overview.last_or_default(pid);
-        self.justify_metric_repr(m.concise_repr())
+    /// Builds a list whose rows contain the latest metric of each process, highlighting the alarming metrics
+    fn metrics_list<'a>(processes: &[ProcessMetadata], overview: &MetricsOverview, cells: Vec<String>) -> List<'a> {
+        let items: Vec<ListItem> = processes
+            .iter()
+            .zip(cells)
+            .map(|(pm, cell)| {
+                let item = ListItem::new(cell);
+                let alarming = pm.status() == Status::RUNNING && overview.last_or_default(pm.pid()).is_alarming();
+                if alarming {
+                    item.style(Style::default().fg(ALARMING_METRIC_COLOR))
+                } else {
+                    item
+                }
+            })
+            .collect();
+
+        Self::build_default_list_widget(items)
     }
 
-    fn justify_metric_repr(&self, metric_repr: String) -> String {
-        format!("{:>width$} ", metric_repr, width = METRICS_COL_WIDTH - 1) // - 1 because of the trailing space
+    /// Returns the formatted name of `process_metadata` (its label or its command) so that its length does not exceed
+    /// `CMD_COL_WIDTH` characters
+    fn shortened_command_name(process_metadata: &ProcessMetadata) -> String {
+        let name = process_metadata.display_name();
+        if name.chars().count() > CMD_COL_WIDTH {
+            format!("{}..", name.chars().take(CMD_COL_WIDTH - 2).collect::<String>())
+        } else {
+            name.to_string()
+        }
     }
 
     fn build_default_list_widget(items: Vec<ListItem>) -> List {
@@ -133,42 +366,692 @@ impl ProcessList {
     }
 }
 
+/// Returns the title of a column, and how it is aligned
+fn title(column: Column, overview: Option<&MetricsOverview>, identifier: ProcessIdentifier) -> (String, Alignment) {
+    match column {
+        Column::Name => ("Process name".to_string(), Alignment::Center),
+        Column::Identifier => (identifier.to_string(), Alignment::Right),
+        Column::CpuTime => ("TIME+".to_string(), Alignment::Right),
+        Column::Uptime => ("UPTIME".to_string(), Alignment::Right),
+        Column::State => (" STATE".to_string(), Alignment::Left),
+        Column::CurrentMetric | Column::CollectorMetric(_) => {
+            let unit = overview.map(|overview| overview.unit()).unwrap_or_default();
+            (format!("{} ", unit), Alignment::Right)
+        }
+    }
+}
+
+/// Returns the value displayed by a column for each process, before it is justified
+///
+/// # Arguments
+///  * `column`: The column whose values to format
+///  * `overview`: The latest metrics of processes, for the columns displaying metrics
+///  * `processes`: The processes listed by the column
+///  * `ascii`: Whether the trends of metrics are drawn with ASCII characters only
+fn cell_values(
+    column: Column,
+    overview: Option<&MetricsOverview>,
+    processes: &ProcessesView,
+    ascii: bool,
+) -> Vec<String> {
+    let identifier = processes.identifier();
+
+    processes
+        .as_slice()
+        .iter()
+        .map(|pm| match (column, overview) {
+            (Column::Name, _) => ProcessList::shortened_command_name(pm),
+            (Column::Identifier, _) => identifier.of(pm).to_string(),
+            (Column::CpuTime, _) => format_cpu_time(pm.cpu_time()),
+            (Column::Uptime, _) => format_uptime(uptime(pm)),
+            (Column::State, _) => format!(" {}", state(pm)),
+            (_, Some(overview)) => match pm.status() {
+                Status::RUNNING => format!(
+                    "{} {}",
+                    overview.concise_repr(pm.pid()),
+                    trend_arrow(overview.trend(pm.pid()), ascii)
+                ),
+                Status::DEAD => format!("DEAD {}", trend_arrow(None, ascii)),
+            },
+            (_, None) => String::new(),
+        })
+        .collect()
+}
+
+/// Returns the arrow telling whether a metric rose, fell or held since the previous iteration
+///
+/// Metrics without a trend are followed by a blank, so that the values of all processes stay aligned.
+///
+/// # Arguments
+///  * `trend`: The trend of the metric, if it has one
+///  * `ascii`: If true, the arrow is drawn with an ASCII character
+fn trend_arrow(trend: Option<Trend>, ascii: bool) -> char {
+    match (trend, ascii) {
+        (Some(Trend::Rising), false) => '↑',
+        (Some(Trend::Falling), false) => '↓',
+        (Some(Trend::Steady), false) => '→',
+        (Some(Trend::Rising), true) => '^',
+        (Some(Trend::Falling), true) => 'v',
+        (Some(Trend::Steady), true) => '-',
+        (None, _) => ' ',
+    }
+}
+
 #[cfg(test)]
-mod test_justify_right {
+mod test_trend_arrow {
     use rstest::*;
 
-    use crate::ui::processes::{ProcessList, METRICS_COL_WIDTH};
+    use crate::core::view::Trend;
+    use crate::ui::processes::trend_arrow;
 
-    #[fixture]
-    fn process_list() -> ProcessList {
-        ProcessList::default()
+    #[rstest]
+    #[case(Some(Trend::Rising), false, '↑')]
+    #[case(Some(Trend::Falling), false, '↓')]
+    #[case(Some(Trend::Steady), false, '→')]
+    #[case(Some(Trend::Rising), true, '^')]
+    #[case(Some(Trend::Falling), true, 'v')]
+    #[case(Some(Trend::Steady), true, '-')]
+    #[case(None, false, ' ')]
+    #[case(None, true, ' ')]
+    fn test_should_draw_arrow_of_trend(#[case] trend: Option<Trend>, #[case] ascii: bool, #[case] expected: char) {
+        assert_eq!(trend_arrow(trend, ascii), expected);
+    }
+}
+
+/// Returns the width of a column which fits its title and all of its values, and which is at least its default width
+///
+/// The name column keeps its default width, as the names of processes are shortened to fit it.
+///
+/// # Arguments
+///  * `column`: The column whose width to compute
+///  * `title`: The title of the column
+///  * `values`: The values displayed by the column in the current frame
+fn fit_column_width(column: Column, title: &str, values: &[String]) -> usize {
+    if column == Column::Name {
+        return column.width();
+    }
+
+    values
+        .iter()
+        .map(|value| value.chars().count() + column.margin())
+        .chain(iter::once(title.chars().count()))
+        .fold(column.width(), usize::max)
+}
+
+/// Pads the value of a cell to the width of its column, right-justifying numbers
+fn justify_cell(column: Column, value: &str, width: usize) -> String {
+    if column.is_numeric() {
+        let margin = column.margin();
+        format!(
+            "{:>width$}{:margin$}",
+            value,
+            "",
+            width = width - margin,
+            margin = margin
+        )
+    } else {
+        value.to_string()
+    }
+}
+
+/// Splits a `Rect` into the regions of columns of the given widths
+///
+/// The name column, as well as the last column, take the remaining width.
+fn split_column_chunks(chunk: Rect, columns: &[(Column, usize)]) -> Vec<Rect> {
+    let constraints: Vec<_> = columns
+        .iter()
+        .enumerate()
+        .map(|(position, (column, width))| {
+            if *column == Column::Name || position + 1 == columns.len() {
+                Constraint::Min(*width as u16)
+            } else {
+                Constraint::Length(*width as u16)
+            }
+        })
+        .collect();
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .split(chunk)
+}
+
+/// Returns how long the process has been running, or how long it ran if it is dead
+fn uptime(process: &ProcessMetadata) -> Duration {
+    match process.status() {
+        Status::RUNNING => Timestamp::now().duration_since(&process.spawn_time()),
+        Status::DEAD => process.running_span().end().duration_since(&process.spawn_time()),
+    }
+}
+
+fn state(process: &ProcessMetadata) -> &'static str {
+    match process.status() {
+        Status::DEAD => "dead",
+        Status::RUNNING if process.is_stopped() => "stopped",
+        Status::RUNNING => "running",
+    }
+}
+
+/// Formats an uptime with its two most significant units, such as `3m05s` or `2h07m`
+pub fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        3600..=86399 => format!("{}h{:02}m", secs / 3600, secs / 60 % 60),
+        _ => format!("{}d{:02}h", secs / 86400, secs / 3600 % 24),
+    }
+}
+
+/// Formats a cumulative CPU time as `MM:SS.cc`, like the TIME+ column of top
+///
+/// Minutes are not wrapped into hours, so that the CPU times of processes remain comparable at a glance.
+pub fn format_cpu_time(cpu_time: Duration) -> String {
+    let centis = cpu_time.as_millis() / 10;
+
+    format!("{:02}:{:02}.{:02}", centis / 6000, centis / 100 % 60, centis % 100)
+}
+
+/// Returns the index of the first row displayed by a list, the same way tui scrolls its `List` widget to keep the
+/// selected row visible
+///
+/// tui does not expose the offset of a list, so it is tracked alongside it.
+///
+/// # Arguments
+///  * `previous_offset`: The offset of the list during its previous rendering
+///  * `selected`: The index of the selected row
+///  * `rows_count`: The amount of rows of the list
+///  * `visible_rows`: The amount of rows which fit in the list region
+fn list_offset(previous_offset: usize, selected: Option<usize>, rows_count: usize, visible_rows: usize) -> usize {
+    if rows_count == 0 {
+        return previous_offset; // tui does not update the offset of empty lists
+    }
+
+    let mut offset = previous_offset.min(rows_count - 1);
+    let selected = selected.unwrap_or(0).min(rows_count - 1);
+
+    if selected >= offset + visible_rows {
+        offset = selected + 1 - visible_rows;
+    }
+    if selected < offset {
+        offset = selected;
+    }
+
+    offset
+}
+
+/// Returns the index of the row displayed at the given row of the terminal
+///
+/// # Arguments
+///  * `row`: The row of the terminal
+///  * `rows_region`: The region in which the rows of the list are rendered
+///  * `offset`: The index of the first row displayed by the list
+///  * `rows_count`: The amount of rows of the list
+fn row_index_at(row: u16, rows_region: Rect, offset: usize, rows_count: usize) -> Option<usize> {
+    if row < rows_region.top() || row >= rows_region.bottom() {
+        return None;
     }
 
+    let index = offset + (row - rows_region.top()) as usize;
+    (index < rows_count).then_some(index)
+}
+
+#[cfg(test)]
+mod test_row_location {
+    use rstest::*;
+    use tui::layout::Rect;
+
+    use crate::ui::processes::{list_offset, row_index_at};
+
+    #[rstest]
+    #[case(2, Some(0))]
+    #[case(3, Some(1))]
+    #[case(11, Some(9))]
+    fn test_should_map_row_to_index_of_displayed_process(#[case] row: u16, #[case] expected: Option<usize>) {
+        assert_eq!(row_index_at(row, Rect::new(0, 2, 30, 10), 0, 20), expected);
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(12)]
+    fn test_should_not_map_rows_outside_of_list(#[case] row: u16) {
+        assert_eq!(row_index_at(row, Rect::new(0, 2, 30, 10), 0, 20), None);
+    }
+
+    #[test]
+    fn test_should_not_map_rows_after_last_process() {
+        assert_eq!(row_index_at(5, Rect::new(0, 2, 30, 10), 0, 3), None);
+    }
+
+    #[test]
+    fn test_should_shift_index_by_offset_of_scrolled_list() {
+        assert_eq!(row_index_at(2, Rect::new(0, 2, 30, 10), 5, 20), Some(5));
+    }
+
+    #[rstest]
+    #[case(0, Some(3), 0)]
+    #[case(0, Some(12), 3)]
+    #[case(5, Some(2), 2)]
+    #[case(5, Some(10), 5)]
+    #[case(15, None, 0)]
+    fn test_should_scroll_list_to_keep_selected_row_visible(
+        #[case] previous_offset: usize,
+        #[case] selected: Option<usize>,
+        #[case] expected: usize,
+    ) {
+        assert_eq!(list_offset(previous_offset, selected, 20, 10), expected);
+    }
+}
+
+#[cfg(test)]
+mod test_format_cpu_time {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::ui::processes::format_cpu_time;
+
+    #[rstest]
+    #[case(Duration::ZERO, "00:00.00")]
+    #[case(Duration::from_millis(10), "00:00.01")]
+    #[case(Duration::from_millis(1234), "00:01.23")]
+    #[case(Duration::from_secs(59), "00:59.00")]
+    #[case(Duration::from_secs(61), "01:01.00")]
+    #[case(Duration::from_secs(3600), "60:00.00")]
+    #[case(Duration::from_millis(6_000_050), "100:00.05")]
+    #[case(Duration::from_secs(9999 * 60 + 59), "9999:59.00")]
+    fn test_should_format_cpu_time_as_minutes_seconds_and_hundredths(
+        #[case] cpu_time: Duration,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(format_cpu_time(cpu_time), expected);
+    }
+}
+
+#[cfg(test)]
+mod test_justify_right {
+    use rstest::*;
+
+    use crate::ui::processes::{justify_cell, Column, METRICS_COL_WIDTH};
+
     #[fixture]
     fn short_metric_repr() -> String {
-        std::iter::repeat('0').take(METRICS_COL_WIDTH / 2).collect()
+        std::iter::repeat_n('0', METRICS_COL_WIDTH / 2).collect()
     }
 
     #[rstest(input, case("a"), case("abcdefgh"))]
-    fn test_should_align_right_with_right_padding(process_list: ProcessList, input: &str) {
-        let aligned = process_list.justify_metric_repr(input.to_string());
+    fn test_should_align_right_with_right_padding(input: &str) {
+        let aligned = justify_cell(Column::CurrentMetric, input, METRICS_COL_WIDTH);
 
         assert!(aligned.ends_with(&format!("{} ", input)));
         assert_eq!(aligned.len(), METRICS_COL_WIDTH)
     }
 
     #[rstest]
-    fn test_should_contain_one_extra_space_in_front_of_short_text(
-        process_list: ProcessList,
-        short_metric_repr: String,
-    ) {
-        let justified_repr = process_list.justify_metric_repr(short_metric_repr);
+    fn test_should_contain_one_extra_space_in_front_of_short_text(short_metric_repr: String) {
+        let justified_repr = justify_cell(Column::CurrentMetric, &short_metric_repr, METRICS_COL_WIDTH);
         assert!(justified_repr.starts_with(" "));
     }
 
     #[rstest]
-    fn test_should_add_trailing_space_on_short_repr(process_list: ProcessList, short_metric_repr: String) {
-        let justified_repr = process_list.justify_metric_repr(short_metric_repr);
+    fn test_should_add_trailing_space_on_short_repr(short_metric_repr: String) {
+        let justified_repr = justify_cell(Column::CurrentMetric, &short_metric_repr, METRICS_COL_WIDTH);
         assert!(justified_repr.ends_with(" "));
     }
+
+    #[rstest]
+    #[case(Column::Identifier, "123", "     123")]
+    #[case(Column::Uptime, "3m05s", "   3m05s")]
+    #[case(Column::State, " running", " running")]
+    fn test_should_only_justify_numbers_to_the_right(
+        #[case] column: Column,
+        #[case] value: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(justify_cell(column, value, 8), expected);
+    }
+}
+
+#[cfg(test)]
+mod test_fit_column_width {
+    use rstest::*;
+
+    use crate::ui::processes::{fit_column_width, Column, ID_COL_WIDTH, METRICS_COL_WIDTH};
+
+    fn values(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[rstest]
+    #[case(&[], ID_COL_WIDTH)]
+    #[case(&["1", "123", "12"], ID_COL_WIDTH)]
+    #[case(&["1", "1234567890", "123"], 10)]
+    #[case(&["123456789", "1234567890123"], 13)]
+    fn test_should_fit_widest_value_of_column(#[case] column_values: &[&str], #[case] expected: usize) {
+        assert_eq!(
+            fit_column_width(Column::Identifier, "PID", &values(column_values)),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(&["1.0", "42.5"], METRICS_COL_WIDTH)]
+    #[case(&["1.0", "123456789.5"], 12)]
+    fn test_should_leave_room_for_margin_of_metrics(#[case] column_values: &[&str], #[case] expected: usize) {
+        assert_eq!(
+            fit_column_width(Column::CurrentMetric, "% ", &values(column_values)),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_should_fit_title_wider_than_values() {
+        assert_eq!(
+            fit_column_width(Column::Identifier, "LONG IDENTIFIER", &values(&["1"])),
+            15
+        );
+    }
+
+    #[test]
+    fn test_should_not_widen_name_column() {
+        let names = values(&["a-very-long-process-name"]);
+
+        assert_eq!(
+            fit_column_width(Column::Name, "Process name", &names),
+            Column::Name.width()
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_process_list {
+    use std::time::Duration;
+
+    use crate::core::metrics::{BytesMetric, Metric, PercentMetric};
+    use crate::core::process::{ProcessIdentifier, ProcessMetadata};
+    use crate::core::time::Timestamp;
+    use crate::core::view::{MetricsOverview, ProcessesView};
+    use crate::ui::processes::{Column, ProcessList, METRICS_COL_WIDTH};
+    use crate::ui::terminal::Terminal;
+
+    fn render_process_list_with(mut process_list: ProcessList, identifier: ProcessIdentifier) -> Vec<String> {
+        let mut terminal = Terminal::from_size(process_list.width() + 10, 4).unwrap();
+        let mut process = ProcessMetadata::new(123, "firefox", Timestamp::now()).with_tgid(100);
+        process.set_cpu_time(Duration::from_millis(83_450));
+        let processes = vec![process];
+        let default_metric = PercentMetric::default();
+        let overview = MetricsOverview::new(hashmap!(), &default_metric);
+        let view = ProcessesView::new(&processes, Some(0), identifier);
+
+        terminal.draw(|fr| process_list.render(fr, &[overview], &view)).unwrap();
+
+        terminal.buffer_lines()
+    }
+
+    fn render_process_list(identifier: ProcessIdentifier) -> Vec<String> {
+        render_process_list_with(ProcessList::default(), identifier)
+    }
+
+    #[test]
+    fn test_should_not_display_cpu_time_by_default() {
+        let lines = render_process_list(ProcessIdentifier::Pid);
+
+        assert!(!lines[1].contains("TIME+"));
+        assert!(!lines[2].contains("01:23.45"));
+    }
+
+    #[test]
+    fn test_should_display_cpu_time_of_processes_when_enabled() {
+        let columns = vec![Column::Name, Column::Identifier, Column::CpuTime, Column::CurrentMetric];
+        let lines = render_process_list_with(ProcessList::new(columns), ProcessIdentifier::Pid);
+
+        assert!(lines[1].contains("TIME+"));
+        assert!(lines[2].contains("firefox") && lines[2].contains("01:23.45"));
+    }
+
+    #[test]
+    fn test_should_display_pid_of_processes_by_default() {
+        let lines = render_process_list(ProcessIdentifier::Pid);
+
+        assert!(lines[1].contains("PID"));
+        assert!(lines[2].contains("firefox") && lines[2].contains("123"));
+    }
+
+    #[test]
+    fn test_should_display_tgid_of_processes_when_toggled() {
+        let lines = render_process_list(ProcessIdentifier::Tgid);
+
+        assert!(lines[1].contains("TGID"));
+        assert!(lines[2].contains("firefox") && lines[2].contains("100"));
+        assert!(!lines[2].contains("123"));
+    }
+
+    #[test]
+    fn test_should_display_label_of_labelled_processes_in_place_of_command() {
+        let mut process = ProcessMetadata::new(123, "java", Timestamp::now());
+        process.set_label("kafka");
+
+        assert_eq!(ProcessList::shortened_command_name(&process), "kafka");
+    }
+
+    #[test]
+    fn test_should_display_metrics_of_combined_collectors_in_adjacent_columns() {
+        let columns = vec![
+            Column::Name,
+            Column::Identifier,
+            Column::CurrentMetric,
+            Column::CollectorMetric(1),
+        ];
+        let mut process_list = ProcessList::new(columns);
+        let mut terminal = Terminal::from_size(process_list.width() + 10, 4).unwrap();
+        let processes = vec![ProcessMetadata::new(123, "firefox", Timestamp::now())];
+        let (cpu_usage, memory) = (PercentMetric::new(42.), BytesMetric::new(3 * 1024 * 1024));
+        let (default_percent, default_bytes) = (PercentMetric::default(), BytesMetric::default());
+        let overviews = [
+            MetricsOverview::new(hashmap!(123 => &cpu_usage as &dyn Metric), &default_percent),
+            MetricsOverview::new(hashmap!(123 => &memory as &dyn Metric), &default_bytes),
+        ];
+        let view = ProcessesView::new(&processes, Some(0), ProcessIdentifier::Pid);
+
+        terminal.draw(|fr| process_list.render(fr, &overviews, &view)).unwrap();
+
+        let lines = terminal.buffer_lines();
+        let (cpu_col, mem_col) = (lines[2].find(&cpu_usage.concise_repr()), lines[2].find("3.0M"));
+        assert!(lines[1].contains('%') && lines[1].contains('B'));
+        assert!(matches!((cpu_col, mem_col), (Some(cpu_col), Some(mem_col)) if cpu_col < mem_col));
+    }
+
+    #[test]
+    fn test_should_not_repeat_metrics_of_current_tab() {
+        let columns = vec![Column::Name, Column::CurrentMetric, Column::CollectorMetric(0)];
+        let mut process_list = ProcessList::new(columns);
+        process_list.set_current_collector(0);
+        let mut terminal = Terminal::from_size(process_list.width() + 10, 4).unwrap();
+        let processes = vec![ProcessMetadata::new(123, "firefox", Timestamp::now())];
+        let cpu_usage = PercentMetric::new(42.);
+        let default_percent = PercentMetric::default();
+        let overviews = [
+            MetricsOverview::new(hashmap!(123 => &cpu_usage as &dyn Metric), &default_percent),
+            MetricsOverview::new(hashmap!(123 => &cpu_usage as &dyn Metric), &default_percent),
+        ];
+        let view = ProcessesView::new(&processes, Some(0), ProcessIdentifier::Pid);
+
+        terminal.draw(|fr| process_list.render(fr, &overviews, &view)).unwrap();
+
+        assert_eq!(terminal.buffer_lines()[2].matches(&cpu_usage.concise_repr()).count(), 1);
+    }
+
+    #[test]
+    fn test_should_render_columns_in_given_order() {
+        let columns = vec![Column::CurrentMetric, Column::State, Column::Identifier, Column::Name];
+        let lines = render_process_list_with(ProcessList::new(columns), ProcessIdentifier::Pid);
+
+        let positions: Vec<_> = ["%", "STATE", "PID", "Process name"]
+            .iter()
+            .map(|title| lines[1].find(title).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(lines[2].contains("running"));
+    }
+
+    #[test]
+    fn test_should_display_uptime_of_processes() {
+        let columns = vec![Column::Name, Column::Uptime];
+        let lines = render_process_list_with(ProcessList::new(columns), ProcessIdentifier::Pid);
+
+        assert!(lines[1].contains("UPTIME"));
+        assert!(lines[2].contains("0s"));
+    }
+
+    #[test]
+    fn test_collector_columns_should_widen_process_list() {
+        let columns = vec![Column::Name, Column::Identifier, Column::CurrentMetric];
+        let combined_columns = [
+            columns.clone(),
+            vec![Column::CollectorMetric(1), Column::CollectorMetric(2)],
+        ]
+        .concat();
+
+        assert_eq!(
+            ProcessList::new(combined_columns).width(),
+            ProcessList::new(columns).width() + 2 * METRICS_COL_WIDTH as u16
+        );
+    }
+
+    #[test]
+    fn test_should_widen_process_list_to_fit_values_of_latest_rendering() {
+        let mut process_list = ProcessList::default();
+        let default_width = process_list.width();
+        let mut terminal = Terminal::from_size(default_width + 10, 4).unwrap();
+        let processes = vec![ProcessMetadata::new(123, "firefox", Timestamp::now())];
+        let usage = PercentMetric::new(123456789.);
+        let default_percent = PercentMetric::default();
+        let overview = MetricsOverview::new(hashmap!(123 => &usage as &dyn Metric), &default_percent);
+        let view = ProcessesView::new(&processes, Some(0), ProcessIdentifier::Pid);
+
+        terminal.draw(|fr| process_list.render(fr, &[overview], &view)).unwrap();
+
+        // "123456789.0", the blank left for its trend and its trailing margin
+        assert_eq!(process_list.width(), default_width + 14 - METRICS_COL_WIDTH as u16);
+        assert!(terminal.buffer_lines()[2].contains("123456789.0"));
+    }
+
+    #[test]
+    fn test_should_display_trend_of_metrics_next_to_their_value() {
+        let mut process_list = ProcessList::default();
+        let mut terminal = Terminal::from_size(process_list.width() + 10, 5).unwrap();
+        let processes = vec![
+            ProcessMetadata::new(123, "firefox", Timestamp::now()),
+            ProcessMetadata::new(456, "bash", Timestamp::now()),
+        ];
+        let (previous, last) = (PercentMetric::new(10.), PercentMetric::new(42.));
+        let default_percent = PercentMetric::default();
+        let overview = MetricsOverview::new(
+            hashmap!(123 => &last as &dyn Metric, 456 => &last as &dyn Metric),
+            &default_percent,
+        )
+        .with_previous_metrics(hashmap!(123 => &previous as &dyn Metric));
+        let view = ProcessesView::new(&processes, Some(0), ProcessIdentifier::Pid);
+
+        terminal.draw(|fr| process_list.render(fr, &[overview], &view)).unwrap();
+
+        let lines = terminal.buffer_lines();
+        assert!(lines[2].contains(&format!("{} ↑", last.concise_repr())));
+        assert!(lines[3].contains(&format!("{}  ", last.concise_repr())));
+        assert_eq!(lines[2].find("42.0"), lines[3].find("42.0"));
+    }
+
+    #[test]
+    fn test_should_display_trend_with_ascii_characters_when_enabled() {
+        let mut process_list = ProcessList::default().with_ascii_trends();
+        let mut terminal = Terminal::from_size(process_list.width() + 10, 4).unwrap();
+        let processes = vec![ProcessMetadata::new(123, "firefox", Timestamp::now())];
+        let (previous, last) = (PercentMetric::new(42.), PercentMetric::new(10.));
+        let default_percent = PercentMetric::default();
+        let overview = MetricsOverview::new(hashmap!(123 => &last as &dyn Metric), &default_percent)
+            .with_previous_metrics(hashmap!(123 => &previous as &dyn Metric));
+        let view = ProcessesView::new(&processes, Some(0), ProcessIdentifier::Pid);
+
+        terminal.draw(|fr| process_list.render(fr, &[overview], &view)).unwrap();
+
+        assert!(terminal.buffer_lines()[2].contains(&format!("{} v", last.concise_repr())));
+    }
+
+    #[test]
+    fn test_should_list_collectors_of_collector_columns() {
+        let columns = vec![Column::CollectorMetric(3), Column::Name, Column::CollectorMetric(1)];
+
+        assert_eq!(ProcessList::new(columns).collector_columns(), vec![3, 1]);
+    }
+}
+
+#[cfg(test)]
+mod test_resolve_columns {
+    use crate::config::{ColumnKind, ProbeKind};
+    use crate::ui::processes::{resolve_columns, Column};
+
+    #[test]
+    fn test_should_resolve_columns_in_given_order() {
+        let columns = [
+            ColumnKind::Identifier,
+            ColumnKind::Command,
+            ColumnKind::Metric,
+            ColumnKind::CpuTime,
+            ColumnKind::Uptime,
+            ColumnKind::State,
+        ];
+
+        assert_eq!(
+            resolve_columns(&columns, &[ProbeKind::Cpu]),
+            vec![
+                Column::Identifier,
+                Column::Name,
+                Column::CurrentMetric,
+                Column::CpuTime,
+                Column::Uptime,
+                Column::State
+            ]
+        );
+    }
+
+    #[test]
+    fn test_should_resolve_probe_columns_to_index_of_their_collector() {
+        let columns = [
+            ColumnKind::Command,
+            ColumnKind::Probe(ProbeKind::Memory),
+            ColumnKind::Probe(ProbeKind::Cpu),
+        ];
+
+        assert_eq!(
+            resolve_columns(&columns, &[ProbeKind::Cpu, ProbeKind::DiskIO, ProbeKind::Memory]),
+            vec![Column::Name, Column::CollectorMetric(2), Column::CollectorMetric(0)]
+        );
+    }
+
+    #[test]
+    fn test_should_leave_out_columns_of_probes_without_collector() {
+        let columns = [ColumnKind::Command, ColumnKind::Probe(ProbeKind::Power)];
+
+        assert_eq!(resolve_columns(&columns, &[ProbeKind::Cpu]), vec![Column::Name]);
+    }
+}
+
+#[cfg(test)]
+mod test_format_uptime {
+    use std::time::Duration;
+
+    use rstest::*;
+
+    use crate::ui::processes::format_uptime;
+
+    #[rstest]
+    #[case(Duration::ZERO, "0s")]
+    #[case(Duration::from_millis(59_999), "59s")]
+    #[case(Duration::from_secs(185), "3m05s")]
+    #[case(Duration::from_secs(2 * 3600 + 7 * 60 + 30), "2h07m")]
+    #[case(Duration::from_secs(3 * 86400 + 4 * 3600), "3d04h")]
+    fn test_should_format_uptime_with_two_most_significant_units(#[case] uptime: Duration, #[case] expected: &str) {
+        assert_eq!(format_uptime(uptime), expected);
+    }
 }
@@ -0,0 +1,42 @@
+use tui::layout::Alignment;
+use tui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+use crate::ui::layout::centered_area;
+use crate::ui::terminal::FrameRegion;
+
+const QUESTION: &str = "Do you really want to quit spv?";
+const CONFIRM_HINT: &str = "Press y or Enter to quit, or any other key to cancel";
+
+/// Renders a popup asking the user to confirm that spv must quit
+pub fn render_quit_confirmation_popup(frame_region: &mut FrameRegion) {
+    const POPUP_WIDTH: u16 = 40;
+    const POPUP_HEIGHT: u16 = 6;
+
+    let popup_area = centered_area(frame_region.region(), POPUP_WIDTH, POPUP_HEIGHT);
+    frame_region.with_region(popup_area).render_widget(Clear);
+
+    let text = format!("{}\n\n{}", QUESTION, CONFIRM_HINT);
+    let paragraph = Paragraph::new(text)
+        .block(Block::default().borders(Borders::ALL).title("Quit"))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    frame_region.with_region(popup_area).render_widget(paragraph);
+}
+
+#[cfg(test)]
+mod test_quit_confirmation {
+    use crate::ui::quit::render_quit_confirmation_popup;
+    use crate::ui::terminal::Terminal;
+
+    #[test]
+    fn test_should_explain_how_to_confirm_or_cancel() {
+        let mut terminal = Terminal::from_size(60, 12).unwrap();
+
+        terminal.draw(render_quit_confirmation_popup).unwrap();
+
+        let text = terminal.buffer_lines().join(" ");
+        assert!(text.contains("really want to quit"));
+        assert!(text.contains("Press y or Enter to quit"));
+    }
+}
@@ -2,37 +2,34 @@ use tui::layout::{Alignment, Constraint, Direction, Layout};
 use tui::text::Spans;
 use tui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap};
 
-use crate::core::ordering::{ProcessOrdering, PROCESS_ORDERING_CRITERIA};
+use crate::core::ordering::ProcessOrdering;
 use crate::ui::labels::process_criteria_label;
 use crate::ui::layout::centered_area;
 use crate::ui::terminal::FrameRegion;
 
-pub fn render_process_order_popup(frame_region: &mut FrameRegion, selected_criteria: ProcessOrdering) {
+pub fn render_process_order_popup(
+    frame_region: &mut FrameRegion,
+    criteria: &[ProcessOrdering],
+    selected_criteria: ProcessOrdering,
+) {
     const POPUP_WIDTH: u16 = 50;
     // Why +5 -> 3 for borders (top, middle, bottom) + 2 for criteria description:
-    const POPUP_HEIGHT: u16 = PROCESS_ORDERING_CRITERIA.len() as u16 + 5;
+    let popup_height = criteria.len() as u16 + 5;
 
-    let popup_area = centered_area(frame_region.region(), POPUP_WIDTH, POPUP_HEIGHT);
+    let popup_area = centered_area(frame_region.region(), POPUP_WIDTH, popup_height);
 
     frame_region.with_region(popup_area).render_widget(Clear);
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(PROCESS_ORDERING_CRITERIA.len() as u16),
-            Constraint::Length(3),
-        ])
+        .constraints([Constraint::Length(criteria.len() as u16), Constraint::Length(3)])
         .margin(1)
         .split(popup_area);
 
     let popup_block = Block::default().borders(Borders::ALL).title("Sort processes");
     frame_region.with_region(popup_area).render_widget(popup_block);
 
-    render_selection_list(
-        frame_region.with_region(chunks[0]),
-        &PROCESS_ORDERING_CRITERIA,
-        &selected_criteria,
-    );
+    render_selection_list(frame_region.with_region(chunks[0]), criteria, &selected_criteria);
     render_selection_description(frame_region.with_region(chunks[1]), &selected_criteria);
 }
 
@@ -63,11 +60,18 @@ fn render_selection_list(
 
 fn render_selection_description(frame_region: &mut FrameRegion, selected_criteria: &ProcessOrdering) {
     let text = match selected_criteria {
-        ProcessOrdering::CurrentMetric => "Order processes by their last collected metric, in a descending order",
-        ProcessOrdering::Pid => "Order processes by their pid, in an ascending order",
-        ProcessOrdering::Command => "Order processes by their command, in an alphabetically ascending order",
-    }
-    .to_string();
+        ProcessOrdering::CurrentMetric => {
+            "Order processes by their last collected metric, in a descending order".to_string()
+        }
+        ProcessOrdering::Metric(name) => format!(
+            "Order processes by their last {} metric, in a descending order",
+            name.to_lowercase()
+        ),
+        ProcessOrdering::Pid => "Order processes by their pid, in an ascending order".to_string(),
+        ProcessOrdering::Command => {
+            "Order processes by their command, in an alphabetically ascending order".to_string()
+        }
+    };
 
     let paragraph = Paragraph::new(Spans::from(text))
         .block(Block::default().borders(Borders::TOP))
@@ -0,0 +1,108 @@
+use tui::text::Spans;
+
+use crate::core::process::ProcessMetadata;
+use crate::core::summary::CollectorLeaders;
+use crate::ui::pane::{listing, render_pane, truncate};
+use crate::ui::terminal::FrameRegion;
+
+/// Width of the column of PIDs of the summary pane
+const PID_COL_WIDTH: usize = 8;
+/// Width of the column of process names of the summary pane
+const NAME_COL_WIDTH: usize = 24;
+
+/// A full-screen pane listing the leading processes of every visible tab
+pub struct SummaryPane {
+    // The name of each collector, along with a row describing each of its leading processes
+    sections: Vec<(String, Vec<String>)>,
+}
+
+impl SummaryPane {
+    /// Assembles the content of the pane
+    ///
+    /// # Arguments
+    ///  * `summary`: The leading processes of each collector
+    ///  * `process_name`: Returns the name under which a process is listed
+    pub fn new(summary: &[CollectorLeaders], mut process_name: impl FnMut(&ProcessMetadata) -> String) -> Self {
+        let sections = summary
+            .iter()
+            .map(|collector_leaders| {
+                let rows = collector_leaders
+                    .leaders()
+                    .iter()
+                    .map(|(pm, metric)| {
+                        format!(
+                            "  {:<pid_width$}{:<name_width$}{}",
+                            pm.pid(),
+                            truncate(&process_name(pm), NAME_COL_WIDTH - 1),
+                            metric,
+                            pid_width = PID_COL_WIDTH,
+                            name_width = NAME_COL_WIDTH
+                        )
+                    })
+                    .collect();
+
+                (collector_leaders.name().to_string(), rows)
+            })
+            .collect();
+
+        Self { sections }
+    }
+
+    pub fn render(&self, frame: &mut FrameRegion) {
+        let mut lines: Vec<Spans> = vec![];
+        for (name, rows) in self.sections.iter() {
+            lines.extend(listing(name.as_str(), rows, "  No process"));
+            lines.push(Spans::default());
+        }
+
+        render_pane(frame, "Top processes per tab", lines);
+    }
+}
+
+#[cfg(test)]
+mod test_summary_pane {
+    use crate::core::collection::{MetricCollector, ProbeCollector};
+    use crate::core::probe::fakes::FakeProbe;
+    use crate::core::process::ProcessMetadata;
+    use crate::core::summary::summarize;
+    use crate::core::time::Timestamp;
+    use crate::ui::summary::SummaryPane;
+    use crate::ui::terminal::Terminal;
+
+    #[test]
+    fn test_should_list_leaders_under_the_name_of_their_collector() {
+        let mut cpu = ProbeCollector::new(FakeProbe::from_percent_map(hashmap!(1 => 5., 2 => 40.)).with_name("cpu"));
+        cpu.collect(&[1, 2]).unwrap();
+        let processes = vec![
+            ProcessMetadata::new(1, "bash", Timestamp::now()),
+            ProcessMetadata::new(2, "firefox", Timestamp::now()),
+        ];
+        let collectors: [&dyn MetricCollector; 1] = [&cpu];
+        let summary = summarize(collectors, &processes, 3);
+        let mut terminal = Terminal::from_size(60, 10).unwrap();
+
+        terminal
+            .draw(|frame| SummaryPane::new(&summary, |pm| pm.display_name().to_string()).render(frame))
+            .unwrap();
+
+        let lines = terminal.buffer_lines();
+        assert!(lines[0].contains("Top processes per tab"));
+        assert!(lines[1].starts_with("│cpu"));
+        assert!(lines[2].starts_with("│  2       firefox                 40.0"));
+        assert!(lines[3].starts_with("│  1       bash                    5.0"));
+    }
+
+    #[test]
+    fn test_should_indicate_collector_without_process() {
+        let cpu = ProbeCollector::new(FakeProbe::from_percent_map(hashmap!()).with_name("cpu"));
+        let collectors: [&dyn MetricCollector; 1] = [&cpu];
+        let summary = summarize(collectors, &[], 3);
+        let mut terminal = Terminal::from_size(60, 10).unwrap();
+
+        terminal
+            .draw(|frame| SummaryPane::new(&summary, |pm| pm.display_name().to_string()).render(frame))
+            .unwrap();
+
+        assert!(terminal.buffer_lines()[2].contains("No process"));
+    }
+}
@@ -6,7 +6,15 @@ use crate::core::view::CollectorsView;
 use crate::ui::terminal::FrameRegion;
 
 pub fn render_tabs(frame: &mut FrameRegion, collectors: &CollectorsView) {
-    let tabs_spans = collectors.collectors_names().iter().cloned().map(Spans::from).collect();
+    let tabs_spans = collectors
+        .collectors_names()
+        .iter()
+        .enumerate()
+        .map(|(index, name)| match collectors.selected_source() {
+            Some(source) if index == collectors.selected_index() => Spans::from(format!("{} [{}]", name, source)),
+            _ => Spans::from(*name),
+        })
+        .collect();
 
     let tabs = Tabs::new(tabs_spans)
         .style(Style::default().fg(Color::White))
@@ -43,4 +51,14 @@ mod test_tabs {
 
         terminal.assert_buffer(expected_buffer)
     }
+
+    #[test]
+    fn should_render_source_of_selected_collector() {
+        let mut terminal = Terminal::from_size(40, 1).unwrap();
+        let view = CollectorsView::new(vec!["cpu", "disk"], 1).with_selected_source(Some("logical"));
+
+        terminal.draw(|fr| render_tabs(fr, &view)).unwrap();
+
+        assert_eq!(terminal.buffer_lines()[0], " cpu | disk [logical]                   ");
+    }
 }
@@ -1,3 +1,6 @@
+use std::env;
+use std::io;
+
 use log::error;
 use tui::layout::Rect;
 use tui::widgets::{StatefulWidget, Widget};
@@ -5,9 +8,10 @@ use tui::{Frame, Terminal as TuiTerminal};
 
 #[cfg(not(test))]
 use {
-    std::io,
-    std::io::Stdout,
+    std::io::{Stdout, Write},
+    termion::input::MouseTerminal,
     termion::raw::{IntoRawMode, RawTerminal},
+    termion::screen::{AlternateScreen, ToAlternateScreen, ToMainScreen},
     tui::backend::TermionBackend,
 };
 #[cfg(test)]
@@ -16,25 +20,67 @@ use {tui::backend::TestBackend, tui::buffer::Buffer};
 use crate::ui::Error;
 
 #[cfg(not(test))]
-pub type TuiBackend = TermionBackend<RawTerminal<Stdout>>;
+pub type TuiBackend = TermionBackend<Box<dyn Write>>;
 #[cfg(test)]
 pub type TuiBackend = TestBackend;
 
+/// Escape sequence enabling the mouse support of the terminal, as written by `MouseTerminal`
+#[cfg(not(test))]
+const ENTER_MOUSE_SEQUENCE: &str = "\x1b[?1000h\x1b[?1002h\x1b[?1015h\x1b[?1006h";
+/// Escape sequence disabling the mouse support of the terminal, as written by `MouseTerminal`
+#[cfg(not(test))]
+const EXIT_MOUSE_SEQUENCE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1002l\x1b[?1000l";
+
 pub struct Terminal {
     tui_terminal: TuiTerminal<TuiBackend>,
+    screen_mode: ScreenMode,
+    // Restores the original mode of the terminal when dropped, after the UI has been torn down
+    #[cfg(not(test))]
+    raw_mode: RawTerminal<Stdout>,
+}
+
+/// A terminal which can be handed over to another program, and taken back once this program exits
+pub trait Suspend {
+    /// Restores the terminal as it was before the application started, so that another program can use it
+    fn suspend(&mut self) -> Result<(), Error>;
+
+    /// Sets the terminal back up for the application, whose UI is entirely redrawn on the next draw
+    fn resume(&mut self) -> Result<(), Error>;
+}
+
+/// Where the application is drawn in the terminal
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ScreenMode {
+    /// The application is drawn on the alternate screen, which is left on exit to restore the original content
+    Alternate,
+    /// The application is drawn below the current content of the terminal, and erased on exit
+    Inline,
 }
 
 #[cfg(not(test))]
 impl Terminal {
     pub fn new() -> Result<Self, Error> {
-        let stdout = io::stdout().into_raw_mode()?;
-        let backend = TermionBackend::new(stdout);
+        let screen_mode = screen_mode(env::var("TERM").ok().as_deref(), termion::is_tty(&io::stdout()));
+
+        let raw_mode = io::stdout().into_raw_mode()?;
+        let stdout = MouseTerminal::from(io::stdout());
+        let output: Box<dyn Write> = match screen_mode {
+            ScreenMode::Alternate => Box::new(AlternateScreen::from(stdout)),
+            ScreenMode::Inline => Box::new(stdout),
+        };
+        let backend = TermionBackend::new(output);
 
         let mut tui_terminal = TuiTerminal::new(backend)?;
 
-        Self::generate_empty_frame(&mut tui_terminal);
+        if screen_mode == ScreenMode::Inline {
+            Self::generate_empty_frame(&mut tui_terminal);
+        }
 
-        Ok(Terminal { tui_terminal })
+        Ok(Terminal {
+            tui_terminal,
+            screen_mode,
+            raw_mode,
+        })
     }
 
     /// On application startup, calling self.clear() would overwrite the current content of the
@@ -45,6 +91,35 @@ impl Terminal {
     }
 }
 
+#[cfg(not(test))]
+impl Suspend for Terminal {
+    fn suspend(&mut self) -> Result<(), Error> {
+        match self.screen_mode {
+            ScreenMode::Alternate => write!(self.tui_terminal.backend_mut(), "{}", ToMainScreen)?,
+            ScreenMode::Inline => self.tui_terminal.clear()?,
+        }
+        write!(self.tui_terminal.backend_mut(), "{}", EXIT_MOUSE_SEQUENCE)?;
+        self.tui_terminal.show_cursor()?;
+        self.tui_terminal.backend_mut().flush()?;
+
+        self.raw_mode.suspend_raw_mode()?;
+        Ok(())
+    }
+
+    fn resume(&mut self) -> Result<(), Error> {
+        self.raw_mode.activate_raw_mode()?;
+
+        if self.screen_mode == ScreenMode::Alternate {
+            write!(self.tui_terminal.backend_mut(), "{}", ToAlternateScreen)?;
+        }
+        write!(self.tui_terminal.backend_mut(), "{}", ENTER_MOUSE_SEQUENCE)?;
+        self.tui_terminal.hide_cursor()?;
+        // Clearing the terminal resets the buffers of the previous frame, so that the next frame is drawn entirely
+        self.tui_terminal.clear()?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 impl Terminal {
     pub fn new() -> Result<Self, Error> {
@@ -56,12 +131,38 @@ impl Terminal {
 
         let tui_terminal = TuiTerminal::new(backend)?;
 
-        Ok(Terminal { tui_terminal })
+        Ok(Terminal {
+            tui_terminal,
+            screen_mode: ScreenMode::Inline,
+        })
     }
 
     pub fn assert_buffer(&self, buffer: Buffer) {
         self.tui_terminal.backend().assert_buffer(&buffer);
     }
+
+    /// Returns the text content of each line of the terminal, ignoring styles
+    pub fn buffer_lines(&self) -> Vec<String> {
+        let buffer = self.tui_terminal.backend().buffer();
+        let width = buffer.area.width as usize;
+
+        buffer
+            .content
+            .chunks(width)
+            .map(|line| line.iter().map(|cell| cell.symbol.as_str()).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+impl Suspend for Terminal {
+    fn suspend(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn resume(&mut self) -> Result<(), Error> {
+        self.tui_terminal.clear().map_err(Error::IOError)
+    }
 }
 
 impl Terminal {
@@ -81,9 +182,178 @@ impl Terminal {
 
 impl Drop for Terminal {
     fn drop(&mut self) {
-        if let Err(e) = self.tui_terminal.clear() {
-            error!("Error clearing terminal: {}", e);
+        // Leaving the alternate screen restores the original content, so that only the inline rendering is erased
+        if self.screen_mode == ScreenMode::Inline {
+            if let Err(e) = self.tui_terminal.clear() {
+                error!("Error clearing terminal: {}", e);
+            }
+        }
+
+        if let Err(e) = self.tui_terminal.show_cursor() {
+            error!("Error restoring cursor: {}", e);
+        }
+    }
+}
+
+/// Runs `run` while the terminal is suspended, so that `run` can hand the terminal over to another program
+///
+/// `run` is not called if the terminal could not be suspended, in which case the terminal is resumed as far as possible.
+/// Otherwise, the terminal is resumed once `run` returns, whichever its outcome.
+pub fn run_suspended<S, T>(terminal: &mut S, run: impl FnOnce() -> T) -> Result<T, Error>
+where
+    S: Suspend,
+{
+    if let Err(e) = terminal.suspend() {
+        if let Err(resume_err) = terminal.resume() {
+            error!("Error resuming terminal: {}", resume_err);
         }
+        return Err(e);
+    }
+
+    let ret = run();
+    terminal.resume()?;
+
+    Ok(ret)
+}
+
+/// Stops the process with `stop` until it is continued, e.g. when the user presses Ctrl-Z
+///
+/// The terminal is restored beforehand, so that the shell which takes it back is not left in raw mode, and it is set
+/// back up once the process is continued, e.g. by `fg`.
+pub fn suspend_until_continued<S>(terminal: &mut S, stop: impl FnOnce() -> io::Result<()>) -> Result<(), Error>
+where
+    S: Suspend,
+{
+    run_suspended(terminal, stop)?.map_err(Error::IOError)
+}
+
+#[cfg(test)]
+mod test_run_suspended {
+    use std::cell::RefCell;
+    use std::io;
+
+    use crate::ui::terminal::{run_suspended, suspend_until_continued, Suspend};
+    use crate::ui::Error;
+
+    /// Records the operations made on the terminal, and around it, in a shared journal
+    struct FakeTerminal<'a> {
+        journal: &'a RefCell<Vec<&'static str>>,
+        failing_suspension: bool,
+    }
+
+    impl<'a> FakeTerminal<'a> {
+        fn new(journal: &'a RefCell<Vec<&'static str>>) -> Self {
+            Self {
+                journal,
+                failing_suspension: false,
+            }
+        }
+
+        fn record(&self, operation: &'static str) {
+            self.journal.borrow_mut().push(operation);
+        }
+    }
+
+    impl Suspend for FakeTerminal<'_> {
+        fn suspend(&mut self) -> Result<(), Error> {
+            self.record("suspend");
+            match self.failing_suspension {
+                true => Err(Error::IOError(io::Error::from(io::ErrorKind::BrokenPipe))),
+                false => Ok(()),
+            }
+        }
+
+        fn resume(&mut self) -> Result<(), Error> {
+            self.record("resume");
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_should_run_between_suspension_and_resumption() {
+        let journal = RefCell::new(vec![]);
+        let mut terminal = FakeTerminal::new(&journal);
+
+        let ret = run_suspended(&mut terminal, || {
+            journal.borrow_mut().push("run");
+            42
+        });
+
+        assert_eq!(ret.unwrap(), 42);
+        assert_eq!(*journal.borrow(), vec!["suspend", "run", "resume"]);
+    }
+
+    #[test]
+    fn test_should_resume_after_failed_run() {
+        let journal = RefCell::new(vec![]);
+        let mut terminal = FakeTerminal::new(&journal);
+
+        let ret = run_suspended(&mut terminal, || {
+            journal.borrow_mut().push("run");
+            Err::<(), _>("command not found")
+        });
+
+        assert_eq!(ret.unwrap(), Err("command not found"));
+        assert_eq!(*journal.borrow(), vec!["suspend", "run", "resume"]);
+    }
+
+    #[test]
+    fn test_should_not_run_when_suspension_fails() {
+        let journal = RefCell::new(vec![]);
+        let mut terminal = FakeTerminal {
+            failing_suspension: true,
+            ..FakeTerminal::new(&journal)
+        };
+
+        let ret = run_suspended(&mut terminal, || journal.borrow_mut().push("run"));
+
+        assert!(ret.is_err());
+        assert_eq!(*journal.borrow(), vec!["suspend", "resume"]);
+    }
+
+    #[test]
+    fn test_should_leave_raw_mode_while_stopped() {
+        let journal = RefCell::new(vec![]);
+        let mut terminal = FakeTerminal::new(&journal);
+
+        let ret = suspend_until_continued(&mut terminal, || {
+            journal.borrow_mut().push("stop");
+            Ok(())
+        });
+
+        assert!(ret.is_ok());
+        assert_eq!(*journal.borrow(), vec!["suspend", "stop", "resume"]);
+    }
+
+    #[test]
+    fn test_should_restore_raw_mode_when_stopping_fails() {
+        let journal = RefCell::new(vec![]);
+        let mut terminal = FakeTerminal::new(&journal);
+
+        let ret = suspend_until_continued(&mut terminal, || {
+            journal.borrow_mut().push("stop");
+            Err(io::Error::from(io::ErrorKind::PermissionDenied))
+        });
+
+        assert!(ret.is_err());
+        assert_eq!(*journal.borrow(), vec!["suspend", "stop", "resume"]);
+    }
+
+    #[test]
+    fn test_should_not_stop_when_raw_mode_can_not_be_left() {
+        let journal = RefCell::new(vec![]);
+        let mut terminal = FakeTerminal {
+            failing_suspension: true,
+            ..FakeTerminal::new(&journal)
+        };
+
+        let ret = suspend_until_continued(&mut terminal, || {
+            journal.borrow_mut().push("stop");
+            Ok(())
+        });
+
+        assert!(ret.is_err());
+        assert_eq!(*journal.borrow(), vec!["suspend", "resume"]);
     }
 }
 
@@ -130,3 +400,90 @@ impl<'a, 'b: 'a> FrameRegion<'a, 'b> {
         self
     }
 }
+
+/// Terminals which do not support the alternate screen, identified by the prefix of their `TERM` value
+const TERMS_WITHOUT_ALTERNATE_SCREEN: [&str; 7] = ["dumb", "unknown", "linux", "vt52", "vt100", "vt102", "cons25"];
+
+/// Decides where to draw the application, depending on the capabilities of the terminal
+///
+/// # Arguments
+///  * `term`: The value of the `TERM` environment variable, if it is set
+///  * `is_tty`: Whether the standard output is a terminal, rather than a pipe or a file
+fn screen_mode(term: Option<&str>, is_tty: bool) -> ScreenMode {
+    let term = match term {
+        Some(term) if is_tty && !term.is_empty() => term,
+        _ => return ScreenMode::Inline,
+    };
+
+    match TERMS_WITHOUT_ALTERNATE_SCREEN
+        .iter()
+        .any(|prefix| term.starts_with(prefix))
+    {
+        true => ScreenMode::Inline,
+        false => ScreenMode::Alternate,
+    }
+}
+
+#[cfg(test)]
+mod test_screen_mode {
+    use rstest::*;
+
+    use crate::ui::terminal::{screen_mode, ScreenMode};
+
+    #[rstest]
+    #[case(Some("xterm-256color"), ScreenMode::Alternate)]
+    #[case(Some("screen"), ScreenMode::Alternate)]
+    #[case(Some("tmux-256color"), ScreenMode::Alternate)]
+    #[case(Some("dumb"), ScreenMode::Inline)]
+    #[case(Some("linux"), ScreenMode::Inline)]
+    #[case(Some("vt100"), ScreenMode::Inline)]
+    #[case(Some(""), ScreenMode::Inline)]
+    #[case(None, ScreenMode::Inline)]
+    fn test_should_decide_screen_mode_from_term(#[case] term: Option<&str>, #[case] expected: ScreenMode) {
+        assert_eq!(screen_mode(term, true), expected);
+    }
+
+    #[test]
+    fn test_should_render_inline_when_output_is_not_a_terminal() {
+        assert_eq!(screen_mode(Some("xterm-256color"), false), ScreenMode::Inline);
+    }
+}
+
+/// Indicates if the terminal is expected to render unicode characters, based on the locale environment variables
+pub fn supports_unicode() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty());
+
+    locale_supports_unicode(locale.as_deref())
+}
+
+fn locale_supports_unicode(locale: Option<&str>) -> bool {
+    match locale {
+        // Without any locale information, we assume that the terminal is modern enough to support unicode
+        None => true,
+        Some(locale) => {
+            let locale = locale.to_uppercase();
+            locale.contains("UTF-8") || locale.contains("UTF8")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_unicode_support {
+    use rstest::*;
+
+    use crate::ui::terminal::locale_supports_unicode;
+
+    #[rstest]
+    #[case(None, true)]
+    #[case(Some("en_US.UTF-8"), true)]
+    #[case(Some("fr_FR.utf8"), true)]
+    #[case(Some("C"), false)]
+    #[case(Some("POSIX"), false)]
+    #[case(Some("en_US.ISO-8859-1"), false)]
+    fn test_should_detect_unicode_support_from_locale(#[case] locale: Option<&str>, #[case] expected: bool) {
+        assert_eq!(locale_supports_unicode(locale), expected);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::core::units::UnitUsage;
+use crate::ui::pane::{listing, render_pane, truncate};
+use crate::ui::terminal::FrameRegion;
+
+/// Width of the column of unit names of the unit pane
+const UNIT_COL_WIDTH: usize = 40;
+/// Width of the column of process counts of the unit pane
+const PROCESSES_COL_WIDTH: usize = 12;
+
+/// A full-screen pane listing the sum of the latest metrics of the processes of each systemd unit
+pub struct UnitsPane {
+    title: String,
+    rows: Vec<String>,
+}
+
+impl UnitsPane {
+    /// Assembles the content of the pane
+    ///
+    /// # Arguments
+    ///  * `units`: The summed metrics of each unit, in the order in which they are listed
+    ///  * `collector_name`: The name of the collector whose metrics are summed
+    ///  * `repr`: Returns a concise representation of a sum of metrics
+    pub fn new(units: &[UnitUsage], collector_name: &str, repr: impl Fn(f64) -> String) -> Self {
+        let rows = units
+            .iter()
+            .map(|unit| {
+                format!(
+                    "{:<unit_width$}{:<processes_width$}{}",
+                    truncate(unit.name(), UNIT_COL_WIDTH - 1),
+                    unit.processes(),
+                    repr(unit.total()),
+                    unit_width = UNIT_COL_WIDTH,
+                    processes_width = PROCESSES_COL_WIDTH
+                )
+            })
+            .collect();
+
+        Self {
+            title: format!("Units - {}", collector_name),
+            rows,
+        }
+    }
+
+    pub fn render(&self, frame: &mut FrameRegion) {
+        let header = format!(
+            "{:<unit_width$}{:<processes_width$}{}",
+            "Unit",
+            "Processes",
+            "Total",
+            unit_width = UNIT_COL_WIDTH,
+            processes_width = PROCESSES_COL_WIDTH
+        );
+        let lines = listing(header, &self.rows, "No process belongs to a systemd unit");
+
+        render_pane(frame, &self.title, lines);
+    }
+}
+
+#[cfg(test)]
+mod test_units_pane {
+    use crate::core::metrics::{Metric, PercentMetric};
+    use crate::core::process::ProcessMetadata;
+    use crate::core::time::Timestamp;
+    use crate::core::units::sum_by_unit;
+    use crate::core::view::MetricsOverview;
+    use crate::ui::terminal::Terminal;
+    use crate::ui::units::UnitsPane;
+
+    #[test]
+    fn test_should_list_summed_metrics_of_each_unit() {
+        let processes = vec![
+            ProcessMetadata::new(1, "nginx", Timestamp::now()).with_unit("nginx.service"),
+            ProcessMetadata::new(2, "nginx", Timestamp::now()).with_unit("nginx.service"),
+        ];
+        let (usage, default) = (PercentMetric::new(20.), PercentMetric::default());
+        let overview = MetricsOverview::new(
+            hashmap!(1 => &usage as &dyn Metric, 2 => &usage as &dyn Metric),
+            &default,
+        );
+        let units = sum_by_unit(&overview, &processes);
+        let mut terminal = Terminal::from_size(80, 10).unwrap();
+
+        terminal
+            .draw(|frame| UnitsPane::new(&units, "CPU", |value| overview.concise_repr_of_value(value)).render(frame))
+            .unwrap();
+
+        let lines = terminal.buffer_lines();
+        assert!(lines[0].contains("Units - CPU"));
+        assert!(lines[1].starts_with("│Unit                                    Processes   Total"));
+        assert!(lines[2].starts_with(&format!(
+            "│nginx.service                           2           {}",
+            PercentMetric::new(40.).concise_repr()
+        )));
+    }
+
+    #[test]
+    fn test_should_explain_when_no_process_belongs_to_a_unit() {
+        let mut terminal = Terminal::from_size(80, 10).unwrap();
+
+        terminal
+            .draw(|frame| UnitsPane::new(&[], "CPU", |value| value.to_string()).render(frame))
+            .unwrap();
+
+        assert!(terminal.buffer_lines()[2].contains("No process belongs to a systemd unit"));
+    }
+}
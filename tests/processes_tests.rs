@@ -1,4 +1,4 @@
-use std::process::Command;
+use std::process::{Child, Command};
 use std::thread::sleep;
 use std::time::Duration;
 
@@ -23,32 +23,43 @@ fn probe() -> CpuProbe {
 #[rstest]
 fn test_should_not_fail_due_to_too_many_open_files_over_time(mut collector: ProcessCollector, mut probe: CpuProbe) {
     (0..2).for_each(|_| {
-        spawn_processes(500, "sleep 0.1");
+        let children = spawn_processes(500, "sleep 0.1");
         collector.collect_processes().expect("Could not collect processes");
         probe
             .probe_processes(&collector.running_pids())
             .expect("Error running processes");
         sleep(Duration::from_millis(100));
+        reap_processes(children);
     });
 }
 
 #[rstest]
 fn test_should_not_fail_due_to_too_many_open_files_at_once(mut collector: ProcessCollector, mut probe: CpuProbe) {
-    spawn_processes(1000, "sleep 1");
+    let children = spawn_processes(1000, "sleep 1");
 
     collector.collect_processes().expect("Could not collect processes");
 
     probe
         .probe_processes(&collector.running_pids())
         .expect("Error running processes");
+    reap_processes(children);
 }
 
-fn spawn_processes(count: usize, cmd: &str) {
-    (0..count).for_each(|_| {
-        Command::new("sh")
-            .arg("-c")
-            .arg(cmd)
-            .spawn()
-            .expect("Could not launch child process");
+fn spawn_processes(count: usize, cmd: &str) -> Vec<Child> {
+    (0..count)
+        .map(|_| {
+            Command::new("sh")
+                .arg("-c")
+                .arg(cmd)
+                .spawn()
+                .expect("Could not launch child process")
+        })
+        .collect()
+}
+
+/// Waits for the spawned processes to terminate, so that they do not linger as zombies
+fn reap_processes(children: Vec<Child>) {
+    children.into_iter().for_each(|mut child| {
+        child.wait().expect("Could not wait for child process");
     })
 }